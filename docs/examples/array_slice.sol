@@ -0,0 +1,13 @@
+contract payload_reader {
+    function split(bytes payload) public pure returns (bytes4, uint64) {
+        // the first four bytes are the selector, followed by the arguments
+        bytes4 selector = bytes4(bytes(payload[:4]));
+        uint64 arg = abi.decode(payload[4:], (uint64));
+
+        return (selector, arg);
+    }
+
+    function hash_tail(uint32 offset) public pure returns (bytes32) {
+        return keccak256(msg.data[offset:]);
+    }
+}
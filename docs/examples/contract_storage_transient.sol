@@ -0,0 +1,14 @@
+contract guarded {
+    bool transient locked;
+
+    modifier nonReentrant() {
+        require(!locked, "reentrant call");
+        locked = true;
+        _;
+        locked = false;
+    }
+
+    function withdraw() public nonReentrant {
+        // ...
+    }
+}
@@ -0,0 +1,17 @@
+contract fixed_point_example {
+    ufixed64x4 fee = 0.0025;
+
+    function fee_for(ufixed64x4 amount) public view returns (ufixed64x4) {
+        return amount * fee;
+    }
+
+    function whole_units(ufixed64x4 amount) public pure returns (uint64) {
+        // rounds towards zero
+        return uint64(amount);
+    }
+
+    function with_precision(ufixed64x4 amount) public pure returns (ufixed128x18) {
+        // implicit conversion, no precision is lost
+        return amount;
+    }
+}
@@ -0,0 +1,20 @@
+contract Counter {
+    address authority;
+
+    @mutableSigner(payer)
+    @mutableAccount(counter)
+    @signer(authority)
+    @seeds(counter, [bytes("counter"), name], bump)
+    @initIfNeeded(counter, payer, 1)
+    @hasOne(authority)
+    function increment(bytes name, bytes1 bump) external {
+        uint8 value = uint8(tx.accounts.counter.data[0]);
+        tx.accounts.counter.data[0] = bytes1(value + 1);
+    }
+
+    @account(mint)
+    @owner(mint, address"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")
+    function supply() external view returns (uint64) {
+        return tx.accounts.mint.data.readUint64LE(36);
+    }
+}
@@ -0,0 +1,19 @@
+@program_id("Ga2u8oNcCQkgZwkiY3R9Bn7FTGGMHSk6EJ3hWuUgQwfB")
+contract Journal {
+    string[] entries;
+
+    @payer(payer)
+    @space(1024)
+    constructor() {}
+
+    // If the entries no longer fit, the data account is grown and
+    // the payer tops up its lamports, so it stays rent exempt.
+    @payer(payer)
+    function add(string entry) external {
+        entries.push(entry);
+    }
+
+    function count() external view returns (uint64) {
+        return entries.length;
+    }
+}
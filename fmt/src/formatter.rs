@@ -2001,7 +2001,17 @@ impl<W: Write> Visitor for Formatter<'_, W> {
         let id = self.simulate_to_string(|fmt| annotation.id.visit(fmt))?;
         write!(self.buf(), "@{id}")?;
         write!(self.buf(), "(")?;
-        annotation.value.visit(self)?;
+        if let Some(Expression::List(_, args)) = &mut annotation.value {
+            // Annotations with multiple arguments
+            for (i, (_, arg)) in args.iter_mut().enumerate() {
+                if i > 0 {
+                    write!(self.buf(), ", ")?;
+                }
+                arg.visit(self)?;
+            }
+        } else {
+            annotation.value.visit(self)?;
+        }
         write!(self.buf(), ")")?;
         Ok(())
     }
@@ -2353,6 +2363,8 @@ impl<W: Write> Visitor for Formatter<'_, W> {
                 Type::String => write_chunk!(self, loc.start(), "string")?,
                 Type::Bytes(n) => write_chunk!(self, loc.start(), "bytes{}", n)?,
                 Type::Rational => write_chunk!(self, loc.start(), "rational")?,
+                Type::Fixed(m, n) => write_chunk!(self, loc.start(), "fixed{m}x{n}")?,
+                Type::Ufixed(m, n) => write_chunk!(self, loc.start(), "ufixed{m}x{n}")?,
                 Type::DynamicBytes => write_chunk!(self, loc.start(), "bytes")?,
                 Type::Int(ref n) | Type::Uint(ref n) => {
                     let int = if matches!(typ, Type::Int(_)) {
@@ -3337,6 +3349,7 @@ impl<W: Write> Visitor for Formatter<'_, W> {
                 StorageType::Instance(_) => Some("instance".to_string()),
                 StorageType::Temporary(_) => Some("temporary".to_string()),
                 StorageType::Persistent(_) => Some("persistent".to_string()),
+                StorageType::Transient(_) => Some("transient".to_string()),
             },
         };
         if let Some(token) = token {
@@ -82,6 +82,7 @@ impl AstEq for StorageType {
             (StorageType::Instance(_), StorageType::Instance(_))
                 | (StorageType::Persistent(_), StorageType::Persistent(_))
                 | (StorageType::Temporary(_), StorageType::Temporary(_))
+                | (StorageType::Transient(_), StorageType::Transient(_))
         )
     }
 }
@@ -538,6 +539,7 @@ derive_ast_eq! { enum StorageLocation {
     Memory(loc),
     Storage(loc),
     Calldata(loc),
+    Transient(loc),
     _
 }}
 derive_ast_eq! { enum Type {
@@ -552,6 +554,8 @@ derive_ast_eq! { enum Type {
     Int(int),
     Uint(int),
     Bytes(int),
+    Fixed(int1, int2),
+    Ufixed(int1, int2),
     _
     Mapping{ loc, key, key_name, value, value_name },
     Function { params, attributes, returns },
@@ -9,6 +9,10 @@ contract A {
     @first("")
     @second("")
     function foobar() public {}
+
+    @owner(acc, programId)
+    @seeds(acc, [bytes("seed"), seed], bump)
+    function baz() public {}
 }
 
 @topselector(2)
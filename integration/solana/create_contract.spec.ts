@@ -72,7 +72,7 @@ describe('ChildContract', function () {
 
         const idl = JSON.parse(fs.readFileSync('Seed1.json', 'utf8'));
 
-        const seed1 = new Program(idl, seed_program, provider);
+        const seed1 = new Program({ ...idl, address: seed_program.toBase58() }, provider);
 
         const res = await seed1.methods.sign()
             .accounts({ dataAccount: address, creator_program_id: program_key })
@@ -111,7 +111,7 @@ describe('ChildContract', function () {
 
         const idl = JSON.parse(fs.readFileSync('Seed2.json', 'utf8'));
 
-        const seed2 = new Program(idl, seed_program, provider);
+        const seed2 = new Program({ ...idl, address: seed_program.toBase58() }, provider);
 
         let res = await seed2.methods.check()
             .accounts({ dataAccount: address })
@@ -31,7 +31,7 @@ export async function loadContract(name: string):
 
     const provider = AnchorProvider.local(endpoint);
     const program_key = loadKey(`${name}.key`);
-    const program = new Program(idl, program_key.publicKey, provider)
+    const program = new Program({ ...idl, address: program_key.publicKey.toBase58() }, provider)
     return { program, payer, provider, program_key: program_key.publicKey };
 }
 
@@ -69,7 +69,7 @@ export async function loadContractWithProvider(provider: AnchorProvider, name: s
 
     await create_account(storage, program_key.publicKey, space);
 
-    const program = new Program(idl, program_key.publicKey, provider);
+    const program = new Program({ ...idl, address: program_key.publicKey.toBase58() }, provider);
 
     await program.methods.new(...args)
         .accounts({ dataAccount: storage.publicKey })
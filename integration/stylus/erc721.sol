@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity >=0.8.0;
+
+// A minimal ERC-721 token, using mappings keyed by integers and addresses and nested mappings.
+//
+// This contract is deployed and called by `tests/stylus_tests/contracts.rs`.
+
+contract ERC721 {
+    event Transfer(address indexed from, address indexed to, uint256 indexed tokenId);
+    event Approval(address indexed owner, address indexed approved, uint256 indexed tokenId);
+    event ApprovalForAll(address indexed owner, address indexed operator, bool approved);
+
+    string public name;
+    string public symbol;
+    address minter;
+
+    mapping(uint256 => address) owners;
+    mapping(address => uint256) balances;
+    mapping(uint256 => address) tokenApprovals;
+    mapping(address => mapping(address => bool)) operatorApprovals;
+    mapping(uint256 => string) tokenURIs;
+
+    // Constructors are not run on Stylus
+    function initialize(string memory name_, string memory symbol_) public {
+        require(minter == address(0), "already initialized");
+        name = name_;
+        symbol = symbol_;
+        minter = msg.sender;
+    }
+
+    function balanceOf(address owner) public view returns (uint256) {
+        require(owner != address(0), "zero address");
+        return balances[owner];
+    }
+
+    function ownerOf(uint256 tokenId) public view returns (address) {
+        address owner = owners[tokenId];
+        require(owner != address(0), "nonexistent token");
+        return owner;
+    }
+
+    function tokenURI(uint256 tokenId) public view returns (string memory) {
+        ownerOf(tokenId);
+        return tokenURIs[tokenId];
+    }
+
+    function getApproved(uint256 tokenId) public view returns (address) {
+        ownerOf(tokenId);
+        return tokenApprovals[tokenId];
+    }
+
+    function isApprovedForAll(address owner, address operator) public view returns (bool) {
+        return operatorApprovals[owner][operator];
+    }
+
+    function approve(address to, uint256 tokenId) public {
+        address owner = ownerOf(tokenId);
+        require(msg.sender == owner || operatorApprovals[owner][msg.sender], "not authorized");
+        tokenApprovals[tokenId] = to;
+        emit Approval(owner, to, tokenId);
+    }
+
+    function setApprovalForAll(address operator, bool approved) public {
+        operatorApprovals[msg.sender][operator] = approved;
+        emit ApprovalForAll(msg.sender, operator, approved);
+    }
+
+    function mint(address to, uint256 tokenId, string memory uri) public {
+        require(msg.sender == minter, "not minter");
+        require(to != address(0), "zero address");
+        require(owners[tokenId] == address(0), "already minted");
+        balances[to] += 1;
+        owners[tokenId] = to;
+        tokenURIs[tokenId] = uri;
+        emit Transfer(address(0), to, tokenId);
+    }
+
+    function transferFrom(address from, address to, uint256 tokenId) public {
+        address owner = ownerOf(tokenId);
+        require(owner == from, "wrong owner");
+        require(to != address(0), "zero address");
+        require(
+            msg.sender == owner ||
+                tokenApprovals[tokenId] == msg.sender ||
+                operatorApprovals[owner][msg.sender],
+            "not authorized"
+        );
+        delete tokenApprovals[tokenId];
+        balances[from] -= 1;
+        balances[to] += 1;
+        owners[tokenId] = to;
+        emit Transfer(from, to, tokenId);
+    }
+
+    function burn(uint256 tokenId) public {
+        address owner = ownerOf(tokenId);
+        require(msg.sender == owner, "not owner");
+        delete tokenApprovals[tokenId];
+        delete tokenURIs[tokenId];
+        balances[owner] -= 1;
+        delete owners[tokenId];
+        emit Transfer(owner, address(0), tokenId);
+    }
+}
@@ -0,0 +1,107 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity >=0.8.0;
+
+// A simple limit order book, using storage arrays of structs, mappings to dynamic arrays and
+// `pop`.
+//
+// This contract is deployed and called by `tests/stylus_tests/contracts.rs`.
+
+contract OrderBook {
+    struct Order {
+        address maker;
+        uint64 price;
+        uint64 amount;
+    }
+
+    event Placed(uint256 indexed id, address indexed maker, bool buy, uint64 price, uint64 amount);
+    event Filled(uint256 indexed bid, uint256 indexed ask, uint64 price, uint64 amount);
+
+    Order[] orders;
+    uint256[] bids;
+    uint256[] asks;
+    mapping(address => uint256[]) ordersByMaker;
+    mapping(address => uint64) filled;
+
+    function place(bool buy, uint64 price, uint64 amount) public returns (uint256 id) {
+        require(amount > 0, "empty order");
+        id = orders.length;
+        orders.push(Order(msg.sender, price, amount));
+        ordersByMaker[msg.sender].push(id);
+        if (buy) {
+            bids.push(id);
+        } else {
+            asks.push(id);
+        }
+        emit Placed(id, msg.sender, buy, price, amount);
+    }
+
+    function cancel(uint256 id) public {
+        Order storage o = orders[id];
+        require(o.maker == msg.sender, "not maker");
+        o.amount = 0;
+        prune(bids);
+        prune(asks);
+    }
+
+    // Match the best bid against the best ask until the book no longer crosses
+    function matchOrders() public returns (uint64 volume) {
+        while (bids.length > 0 && asks.length > 0) {
+            uint256 bid = best(bids, true);
+            uint256 ask = best(asks, false);
+            Order storage b = orders[bid];
+            Order storage a = orders[ask];
+            if (b.price < a.price) {
+                break;
+            }
+            uint64 amount = b.amount < a.amount ? b.amount : a.amount;
+            b.amount -= amount;
+            a.amount -= amount;
+            filled[b.maker] += amount;
+            filled[a.maker] += amount;
+            volume += amount;
+            emit Filled(bid, ask, a.price, amount);
+            prune(bids);
+            prune(asks);
+        }
+    }
+
+    function best(uint256[] storage side, bool highest) internal view returns (uint256 id) {
+        id = side[0];
+        for (uint256 i = 1; i < side.length; i++) {
+            uint64 price = orders[side[i]].price;
+            if (highest ? price > orders[id].price : price < orders[id].price) {
+                id = side[i];
+            }
+        }
+    }
+
+    // Remove orders which have been filled or cancelled
+    function prune(uint256[] storage side) internal {
+        uint256 i = 0;
+        while (i < side.length) {
+            if (orders[side[i]].amount == 0) {
+                side[i] = side[side.length - 1];
+                side.pop();
+            } else {
+                i++;
+            }
+        }
+    }
+
+    function order(uint256 id) public view returns (address, uint64, uint64) {
+        Order storage o = orders[id];
+        return (o.maker, o.price, o.amount);
+    }
+
+    function ordersOf(address maker) public view returns (uint256[] memory) {
+        return ordersByMaker[maker];
+    }
+
+    function filledOf(address maker) public view returns (uint64) {
+        return filled[maker];
+    }
+
+    function depth() public view returns (uint256, uint256) {
+        return (bids.length, asks.length);
+    }
+}
@@ -0,0 +1,58 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity >=0.8.0;
+
+// This contract exercises storage bytes and function pointers on Stylus.
+//
+// This contract is deployed and called by `tests/stylus_tests/storage.rs`.
+
+contract C {
+    bytes data;
+    uint64[] numbers;
+    function(uint64) external returns (uint64) callback;
+
+    function push(bytes1 b) public {
+        data.push(b);
+    }
+
+    function pop() public returns (bytes1) {
+        return data.pop();
+    }
+
+    function get(uint32 i) public view returns (bytes1) {
+        return data[i];
+    }
+
+    function set(uint32 i, bytes1 b) public {
+        data[i] = b;
+    }
+
+    function all() public view returns (bytes memory) {
+        return data;
+    }
+
+    function push_number(uint64 n) public {
+        numbers.push(n);
+    }
+
+    function pop_number() public returns (uint64) {
+        return numbers.pop();
+    }
+
+    function sum() public view returns (uint64 total) {
+        for (uint32 i = 0; i < numbers.length; i++) {
+            total += numbers[i];
+        }
+    }
+
+    function double(uint64 n) public pure returns (uint64) {
+        return n * 2;
+    }
+
+    function set_callback() public {
+        callback = this.double;
+    }
+
+    function run_callback(uint64 n) public returns (uint64) {
+        return callback(n);
+    }
+}
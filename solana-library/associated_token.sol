@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Disclaimer: This library provides a way for Solidity to interact with Solana's Associated Token Account program.
+// Although it is production ready, it has not been audited for security, so use it at your own risk.
+
+import 'solana';
+
+library AssociatedToken {
+	address constant associatedTokenProgramId = address"ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+	address constant systemProgramId = address"11111111111111111111111111111111";
+
+	enum AssociatedTokenInstruction {
+		Create, // 0
+		CreateIdempotent, // 1
+		RecoverNested // 2
+	}
+
+	/// Derive the associated token account address for a wallet and a mint. The token program
+	/// is part of the derivation, so a mint owned by Token-2022 has a different associated token
+	/// account than a mint owned by the classic SPL Token program.
+	///
+	/// @param wallet the public key of the wallet which owns the token account
+	/// @param mint the account of the mint
+	/// @param tokenProgramId the token program which owns the mint
+	/// @return the address of the associated token account and its bump seed
+	function get_associated_token_address(address wallet, address mint, address tokenProgramId) internal pure returns (address, bytes1) {
+		return try_find_program_address([wallet, tokenProgramId, mint], associatedTokenProgramId);
+	}
+
+	/// Create the associated token account for a wallet and a mint. The transaction fails if the
+	/// account already exists. The payer keypair should sign the transaction.
+	///
+	/// @param payer the account which pays for the new token account
+	/// @param associatedTokenAccount the address of the associated token account
+	/// @param wallet the public key of the wallet which owns the token account
+	/// @param mint the account of the mint
+	/// @param tokenProgramId the token program which owns the mint
+	function create(address payer, address associatedTokenAccount, address wallet, address mint, address tokenProgramId) internal {
+		create_instruction(AssociatedTokenInstruction.Create, payer, associatedTokenAccount, wallet, mint, tokenProgramId);
+	}
+
+	/// Create the associated token account for a wallet and a mint, unless it exists already. The
+	/// transaction fails if the existing account has a different owner. The payer keypair should
+	/// sign the transaction.
+	///
+	/// @param payer the account which pays for the new token account
+	/// @param associatedTokenAccount the address of the associated token account
+	/// @param wallet the public key of the wallet which owns the token account
+	/// @param mint the account of the mint
+	/// @param tokenProgramId the token program which owns the mint
+	function create_idempotent(address payer, address associatedTokenAccount, address wallet, address mint, address tokenProgramId) internal {
+		create_instruction(AssociatedTokenInstruction.CreateIdempotent, payer, associatedTokenAccount, wallet, mint, tokenProgramId);
+	}
+
+	function create_instruction(AssociatedTokenInstruction kind, address payer, address associatedTokenAccount, address wallet, address mint, address tokenProgramId) private {
+		bytes instr = new bytes(1);
+
+		instr[0] = uint8(kind);
+
+		AccountMeta[6] metas = [
+			AccountMeta({pubkey: payer, is_writable: true, is_signer: true}),
+			AccountMeta({pubkey: associatedTokenAccount, is_writable: true, is_signer: false}),
+			AccountMeta({pubkey: wallet, is_writable: false, is_signer: false}),
+			AccountMeta({pubkey: mint, is_writable: false, is_signer: false}),
+			AccountMeta({pubkey: systemProgramId, is_writable: false, is_signer: false}),
+			AccountMeta({pubkey: tokenProgramId, is_writable: false, is_signer: false})
+		];
+
+		associatedTokenProgramId.call{accounts: metas}(instr);
+	}
+}
@@ -0,0 +1,276 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Disclaimer: This library provides a way for Solidity to interact with Solana's Token-2022 program. Although it is production ready,
+// it has not been audited for security, so use it at your own risk.
+
+import 'solana';
+
+library SplToken2022 {
+	address constant tokenProgramId = address"TokenzQdBNbLqP5VEhdkS6EPFLC1PHnBrDyrdbdpY2w";
+	enum TokenInstruction {
+		InitializeMint, // 0
+		InitializeAccount, // 1
+		InitializeMultisig, // 2
+		Transfer, // 3
+		Approve, // 4
+		Revoke, // 5
+		SetAuthority, // 6
+		MintTo, // 7
+		Burn, // 8
+		CloseAccount, // 9
+		FreezeAccount, // 10
+		ThawAccount, // 11
+		TransferChecked, // 12
+		ApproveChecked, // 13
+		MintToChecked, // 14
+		BurnChecked, // 15
+		InitializeAccount2, // 16
+		SyncNative, // 17
+		InitializeAccount3, // 18
+		InitializeMultisig2, // 19
+		InitializeMint2, // 20
+		GetAccountDataSize, // 21
+		InitializeImmutableOwner, // 22
+		AmountToUiAmount, // 23
+		UiAmountToAmount, // 24
+		InitializeMintCloseAuthority, // 25
+		TransferFeeExtension // 26
+	}
+
+	// Instructions of the transfer fee extension, which follow TokenInstruction.TransferFeeExtension
+	enum TransferFeeInstruction {
+		InitializeTransferFeeConfig, // 0
+		TransferCheckedWithFee // 1
+	}
+
+	/// The extensions which a Token-2022 mint or token account can carry
+	enum ExtensionType {
+		Uninitialized, // 0
+		TransferFeeConfig, // 1
+		TransferFeeAmount, // 2
+		MintCloseAuthority, // 3
+		ConfidentialTransferMint, // 4
+		ConfidentialTransferAccount, // 5
+		DefaultAccountState, // 6
+		ImmutableOwner, // 7
+		MemoTransfer, // 8
+		NonTransferable, // 9
+		InterestBearingConfig, // 10
+		CpiGuard, // 11
+		PermanentDelegate, // 12
+		NonTransferableAccount, // 13
+		TransferHook, // 14
+		TransferHookAccount, // 15
+		ConfidentialTransferFeeConfig, // 16
+		ConfidentialTransferFeeAmount, // 17
+		MetadataPointer, // 18
+		TokenMetadata // 19
+	}
+
+	// Mints are padded to the size of a token account, so the extensions of both start at the
+	// same offset: one byte for the account type, followed by the type-length-value entries.
+	uint32 constant EXTENSIONS_OFFSET = 166;
+
+	/// Transfer @amount tokens from @from to @to. The mint and its decimals are checked by the
+	/// token program, which is required for mints with extensions. The transaction should be signed
+	/// by the owner keypair of the from account.
+	///
+	/// @param from the account to transfer tokens from
+	/// @param mint the mint for this token
+	/// @param to the account to transfer tokens to
+	/// @param owner the publickey of the from account owner keypair
+	/// @param amount the amount to transfer
+	/// @param decimals the number of decimals of the mint
+	function transfer_checked(address from, address mint, address to, address owner, uint64 amount, uint8 decimals) internal {
+		bytes instr = new bytes(10);
+
+		instr[0] = uint8(TokenInstruction.TransferChecked);
+		instr.writeUint64LE(amount, 1);
+		instr[9] = decimals;
+
+		AccountMeta[4] metas = [
+			AccountMeta({pubkey: from, is_writable: true, is_signer: false}),
+			AccountMeta({pubkey: mint, is_writable: false, is_signer: false}),
+			AccountMeta({pubkey: to, is_writable: true, is_signer: false}),
+			AccountMeta({pubkey: owner, is_writable: false, is_signer: true})
+		];
+
+		tokenProgramId.call{accounts: metas}(instr);
+	}
+
+	/// Transfer @amount tokens from @from to @to for a mint with a transfer fee. The token program
+	/// fails the transfer if @fee does not match the fee it calculates. The transaction should be
+	/// signed by the owner keypair of the from account.
+	///
+	/// @param from the account to transfer tokens from
+	/// @param mint the mint for this token
+	/// @param to the account to transfer tokens to
+	/// @param owner the publickey of the from account owner keypair
+	/// @param amount the amount to transfer
+	/// @param decimals the number of decimals of the mint
+	/// @param fee the expected transfer fee, see 'calculate_fee'
+	function transfer_checked_with_fee(address from, address mint, address to, address owner, uint64 amount, uint8 decimals, uint64 fee) internal {
+		bytes instr = new bytes(19);
+
+		instr[0] = uint8(TokenInstruction.TransferFeeExtension);
+		instr[1] = uint8(TransferFeeInstruction.TransferCheckedWithFee);
+		instr.writeUint64LE(amount, 2);
+		instr[10] = decimals;
+		instr.writeUint64LE(fee, 11);
+
+		AccountMeta[4] metas = [
+			AccountMeta({pubkey: from, is_writable: true, is_signer: false}),
+			AccountMeta({pubkey: mint, is_writable: false, is_signer: false}),
+			AccountMeta({pubkey: to, is_writable: true, is_signer: false}),
+			AccountMeta({pubkey: owner, is_writable: false, is_signer: true})
+		];
+
+		tokenProgramId.call{accounts: metas}(instr);
+	}
+
+	/// Find an extension in a mint or token account. The extensions are stored as type-length-value
+	/// entries after the base account data.
+	///
+	/// @param ai the AccountInfo struct for the mint or token account
+	/// @param extension the extension to look for
+	/// @return whether the extension is present, the offset of its value and the length of its value
+	function find_extension(AccountInfo ai, ExtensionType extension) internal pure returns (bool, uint32, uint16) {
+		uint32 offset = EXTENSIONS_OFFSET;
+
+		while (offset + 4 <= ai.data.length) {
+			uint16 ty = ai.data.readUint16LE(offset);
+			uint16 len = ai.data.readUint16LE(offset + 2);
+
+			if (ty == uint16(ExtensionType.Uninitialized)) {
+				break;
+			}
+
+			if (ty == uint16(extension)) {
+				return (true, offset + 4, len);
+			}
+
+			offset += 4 + len;
+		}
+
+		return (false, 0, 0);
+	}
+
+	/// Check whether a mint or token account has an extension
+	///
+	/// @param ai the AccountInfo struct for the mint or token account
+	/// @param extension the extension to look for
+	function has_extension(AccountInfo ai, ExtensionType extension) internal pure returns (bool) {
+		(bool found, , ) = find_extension(ai, extension);
+
+		return found;
+	}
+
+	function extension_offset(AccountInfo ai, ExtensionType extension, uint16 size) private pure returns (uint32) {
+		(bool found, uint32 offset, uint16 len) = find_extension(ai, extension);
+
+		if (!found || len < size) {
+			revert("extension missing");
+		}
+
+		return offset;
+	}
+
+	/// The fee which applies to transfers from a given epoch
+	struct TransferFee {
+		uint64 epoch;
+		uint64 maximum_fee;
+		uint16 transfer_fee_basis_points;
+	}
+
+	/// This struct is the return of 'get_transfer_fee_config'. An authority which is not set is
+	/// the zero address.
+	struct TransferFeeConfig {
+		address transfer_fee_config_authority;
+		address withdraw_withheld_authority;
+		uint64 withheld_amount;
+		TransferFee older_transfer_fee;
+		TransferFee newer_transfer_fee;
+	}
+
+	/// Retrieve the transfer fee configuration of a mint. The transaction fails if the mint does not
+	/// have the transfer fee extension.
+	///
+	/// @param ai the AccountInfo struct for the mint account
+	/// @return the TransferFeeConfig struct
+	function get_transfer_fee_config(AccountInfo ai) internal pure returns (TransferFeeConfig) {
+		uint32 offset = extension_offset(ai, ExtensionType.TransferFeeConfig, 108);
+
+		TransferFeeConfig config = TransferFeeConfig({
+			transfer_fee_config_authority: ai.data.readAddress(offset),
+			withdraw_withheld_authority: ai.data.readAddress(offset + 32),
+			withheld_amount: ai.data.readUint64LE(offset + 64),
+			older_transfer_fee: TransferFee({
+				epoch: ai.data.readUint64LE(offset + 72),
+				maximum_fee: ai.data.readUint64LE(offset + 80),
+				transfer_fee_basis_points: ai.data.readUint16LE(offset + 88)
+			}),
+			newer_transfer_fee: TransferFee({
+				epoch: ai.data.readUint64LE(offset + 90),
+				maximum_fee: ai.data.readUint64LE(offset + 98),
+				transfer_fee_basis_points: ai.data.readUint16LE(offset + 106)
+			})
+		});
+
+		return config;
+	}
+
+	/// Calculate the fee for transferring @amount tokens during @epoch. The result can be passed
+	/// to 'transfer_checked_with_fee'.
+	///
+	/// @param config the transfer fee configuration of the mint
+	/// @param epoch the current epoch
+	/// @param amount the amount to transfer
+	function calculate_fee(TransferFeeConfig config, uint64 epoch, uint64 amount) internal pure returns (uint64) {
+		TransferFee fee = epoch >= config.newer_transfer_fee.epoch ? config.newer_transfer_fee : config.older_transfer_fee;
+
+		if (fee.transfer_fee_basis_points == 0 || amount == 0) {
+			return 0;
+		}
+
+		// the fee is rounded up, and the intermediate product may not fit in 64 bits
+		uint128 raw = (uint128(amount) * fee.transfer_fee_basis_points + 9999) / 10000;
+
+		if (raw > fee.maximum_fee) {
+			return fee.maximum_fee;
+		}
+
+		return uint64(raw);
+	}
+
+	/// This struct is the return of 'get_metadata_pointer'. An address which is not set is the
+	/// zero address.
+	struct MetadataPointer {
+		address authority;
+		address metadata_address;
+	}
+
+	/// Retrieve the metadata pointer of a mint. The transaction fails if the mint does not have the
+	/// metadata pointer extension.
+	///
+	/// @param ai the AccountInfo struct for the mint account
+	/// @return the MetadataPointer struct
+	function get_metadata_pointer(AccountInfo ai) internal pure returns (MetadataPointer) {
+		uint32 offset = extension_offset(ai, ExtensionType.MetadataPointer, 64);
+
+		return MetadataPointer({
+			authority: ai.data.readAddress(offset),
+			metadata_address: ai.data.readAddress(offset + 32)
+		});
+	}
+
+	/// Retrieve the close authority of a mint. The transaction fails if the mint does not have the
+	/// mint close authority extension.
+	///
+	/// @param ai the AccountInfo struct for the mint account
+	/// @return the close authority, or the zero address if it is not set
+	function get_mint_close_authority(AccountInfo ai) internal pure returns (address) {
+		uint32 offset = extension_offset(ai, ExtensionType.MintCloseAuthority, 32);
+
+		return ai.data.readAddress(offset);
+	}
+}
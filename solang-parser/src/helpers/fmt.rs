@@ -49,10 +49,15 @@ impl Display for pt::Annotation {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         f.write_char('@')?;
         self.id.fmt(f)?;
-        if let Some(value) = &self.value {
-            f.write_char('(')?;
-            value.fmt(f)?;
-            f.write_char(')')?;
+        match &self.value {
+            // a list already has its parentheses
+            Some(value @ pt::Expression::List(..)) => value.fmt(f)?,
+            Some(value) => {
+                f.write_char('(')?;
+                value.fmt(f)?;
+                f.write_char(')')?;
+            }
+            None => (),
         }
 
         Ok(())
@@ -1033,6 +1038,7 @@ impl pt::StorageLocation {
             Self::Memory(_) => "memory",
             Self::Storage(_) => "storage",
             Self::Calldata(_) => "calldata",
+            Self::Transient(_) => "transient",
         }
     }
 }
@@ -1059,6 +1065,8 @@ impl Display for pt::Type {
                 f.write_str("uint")?;
                 n.fmt(f)
             }
+            Self::Fixed(m, n) => write!(f, "fixed{m}x{n}"),
+            Self::Ufixed(m, n) => write!(f, "ufixed{m}x{n}"),
             Self::Mapping {
                 key,
                 key_name,
@@ -1173,6 +1181,7 @@ impl Display for pt::VariableAttribute {
                 StorageType::Instance(_) => f.write_str("instance"),
                 StorageType::Temporary(_) => f.write_str("temporary"),
                 StorageType::Persistent(_) => f.write_str("persistent"),
+                StorageType::Transient(_) => f.write_str("transient"),
             },
         }
     }
@@ -1581,6 +1590,9 @@ mod tests {
         (calldata) => {
             pt::StorageLocation::Calldata(loc!())
         };
+        (transient) => {
+            pt::StorageLocation::Transient(loc!())
+        };
     }
 
     /// Identifier
@@ -2517,6 +2529,7 @@ mod tests {
                 pt::StorageLocation::Memory(loc!()) => "memory",
                 pt::StorageLocation::Storage(loc!()) => "storage",
                 pt::StorageLocation::Calldata(loc!()) => "calldata",
+                pt::StorageLocation::Transient(loc!()) => "transient",
             }
 
             pt::Type: {
@@ -2528,6 +2541,8 @@ mod tests {
                 pt::Type::Int(256) => "int256",
                 pt::Type::Uint(256) => "uint256",
                 pt::Type::Bytes(32) => "bytes32",
+                pt::Type::Fixed(128, 18) => "fixed128x18",
+                pt::Type::Ufixed(64, 4) => "ufixed64x4",
                 pt::Type::Rational => "fixed",
                 pt::Type::DynamicBytes => "bytes",
 
@@ -2639,6 +2654,7 @@ mod tests {
             pt::VariableAttribute: {
                 pt::VariableAttribute::Constant(loc!()) => "constant",
                 pt::VariableAttribute::Immutable(loc!()) => "immutable",
+                pt::VariableAttribute::StorageType(pt::StorageType::Transient(None)) => "transient",
 
                 pt::VariableAttribute::Override(loc!(), vec![]) => "override",
                 pt::VariableAttribute::Override(loc!(), vec![idp!["a", "b"]]) => "override(a.b)",
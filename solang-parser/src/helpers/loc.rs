@@ -31,7 +31,7 @@ impl OptionalCodeLocation for pt::Visibility {
 impl OptionalCodeLocation for pt::StorageType {
     fn loc_opt(&self) -> Option<Loc> {
         match self {
-            Self::Persistent(l) | Self::Temporary(l) | Self::Instance(l) => *l,
+            Self::Persistent(l) | Self::Temporary(l) | Self::Instance(l) | Self::Transient(l) => *l,
         }
     }
 }
@@ -428,7 +428,8 @@ impl_for_enums! {
     pt::StorageLocation: match self {
         Self::Calldata(l, ..)
         | Self::Memory(l, ..)
-        | Self::Storage(l, ..) => l,
+        | Self::Storage(l, ..)
+        | Self::Transient(l, ..) => l,
     }
 
     pt::UsingList: match self {
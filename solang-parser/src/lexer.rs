@@ -77,6 +77,8 @@ pub enum Token<'input> {
     Uint(u16),
     Int(u16),
     Bytes(u8),
+    Fixed(u16, u8),
+    Ufixed(u16, u8),
     // prior to 0.8.0 `byte` used to be an alias for `bytes1`
     Byte,
     DynamicBytes,
@@ -184,6 +186,9 @@ pub enum Token<'input> {
     Temporary,
     Instance,
 
+    // EIP-1153 transient storage
+    Transient,
+
     Annotation(&'input str),
 }
 
@@ -207,6 +212,8 @@ impl fmt::Display for Token<'_> {
             Token::Uint(w) => write!(f, "uint{w}"),
             Token::Int(w) => write!(f, "int{w}"),
             Token::Bytes(w) => write!(f, "bytes{w}"),
+            Token::Fixed(m, n) => write!(f, "fixed{m}x{n}"),
+            Token::Ufixed(m, n) => write!(f, "ufixed{m}x{n}"),
             Token::Byte => write!(f, "byte"),
             Token::DynamicBytes => write!(f, "bytes"),
             Token::Semicolon => write!(f, ";"),
@@ -324,6 +331,7 @@ impl fmt::Display for Token<'_> {
             Token::Persistent => write!(f, "persistent"),
             Token::Temporary => write!(f, "temporary"),
             Token::Instance => write!(f, "instance"),
+            Token::Transient => write!(f, "transient"),
         }
     }
 }
@@ -394,7 +402,43 @@ pub enum LexicalError {
 
 /// Returns whether `word` is a keyword in Solidity.
 pub fn is_keyword(word: &str) -> bool {
-    KEYWORDS.contains_key(word)
+    KEYWORDS.contains_key(word) || fixed_point_type(word).is_some()
+}
+
+/// Fixed point types are `fixed<M>x<N>` and `ufixed<M>x<N>`, where M is the number of bits
+/// (a multiple of 8, from 8 to 256) and N the number of decimals (0 to 80). `fixed` and `ufixed`
+/// are aliases for `fixed128x18` and `ufixed128x18`.
+fn fixed_point_type(word: &str) -> Option<Token<'_>> {
+    let (signed, suffix) = if let Some(suffix) = word.strip_prefix("fixed") {
+        (true, suffix)
+    } else {
+        (false, word.strip_prefix("ufixed")?)
+    };
+
+    let (m, n) = if suffix.is_empty() {
+        (128, 18)
+    } else {
+        let (m, n) = suffix.split_once('x')?;
+        let m = m.parse::<u16>().ok()?;
+        let n = n.parse::<u8>().ok()?;
+
+        // reject leading zeros or signs, e.g. fixed0128x018
+        if suffix != format!("{m}x{n}") {
+            return None;
+        }
+
+        (m, n)
+    };
+
+    if m % 8 != 0 || !(8..=256).contains(&m) || n > 80 {
+        return None;
+    }
+
+    Some(if signed {
+        Token::Fixed(m, n)
+    } else {
+        Token::Ufixed(m, n)
+    })
 }
 
 static KEYWORDS: phf::Map<&'static str, Token> = phf_map! {
@@ -564,6 +608,7 @@ static KEYWORDS: phf::Map<&'static str, Token> = phf_map! {
     "persistent" => Token::Persistent,
     "temporary" => Token::Temporary,
     "instance" => Token::Instance,
+    "transient" => Token::Transient,
 };
 
 impl<'input> Lexer<'input> {
@@ -861,6 +906,8 @@ impl<'input> Lexer<'input> {
 
                     return if let Some(w) = KEYWORDS.get(id) {
                         Some((start, *w, end))
+                    } else if let Some(w) = fixed_point_type(id) {
+                        Some((start, w, end))
                     } else {
                         Some((start, Token::Identifier(id), end))
                     };
@@ -1936,5 +1983,27 @@ mod tests {
             )]
         );
         assert!(comments.is_empty());
+
+        errors.clear();
+        let tokens = Lexer::new(
+            "fixed ufixed fixed64x10 ufixed256x80 fixed7x1 fixed8x81 fixed064x1",
+            0,
+            &mut comments,
+            &mut errors,
+        )
+        .collect::<Vec<_>>();
+        assert_eq!(
+            tokens,
+            vec![
+                (0, Token::Fixed(128, 18), 5),
+                (6, Token::Ufixed(128, 18), 12),
+                (13, Token::Fixed(64, 10), 23),
+                (24, Token::Ufixed(256, 80), 36),
+                (37, Token::Identifier("fixed7x1"), 45),
+                (46, Token::Identifier("fixed8x81"), 55),
+                (56, Token::Identifier("fixed064x1"), 66),
+            ]
+        );
+        assert!(errors.is_empty());
     }
 }
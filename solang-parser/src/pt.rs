@@ -459,6 +459,12 @@ pub enum Type {
     /// `bytes<n>`
     Bytes(u8),
 
+    /// `fixed<m>x<n>`
+    Fixed(u16, u8),
+
+    /// `ufixed<m>x<n>`
+    Ufixed(u16, u8),
+
     /// `fixed`
     Rational,
 
@@ -504,6 +510,9 @@ pub enum StorageLocation {
 
     /// `calldata`
     Calldata(Loc),
+
+    /// `transient`
+    Transient(Loc),
 }
 
 /// A variable declaration.
@@ -960,7 +969,8 @@ pub enum VariableAttribute {
     StorageType(StorageType),
 }
 
-/// Soroban storage types.
+/// Storage types of state variables. `transient` is the EIP-1153 transient storage, the
+/// others are Soroban storage types.
 #[derive(Debug, PartialEq, Eq, Clone)]
 #[cfg_attr(feature = "pt-serde", derive(Serialize, Deserialize))]
 #[repr(u8)] // for cmp; order of variants is important
@@ -973,6 +983,9 @@ pub enum StorageType {
 
     /// `Instance`
     Instance(Option<Loc>),
+
+    /// `transient`
+    Transient(Option<Loc>),
 }
 
 /// A variable definition.
@@ -0,0 +1,63 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Disclaimer: This library provides a way for Solidity to interact with Soroban tokens. Although it is production ready,
+// it has not been audited for security, so use it at your own risk.
+
+/// The SEP-41 token interface, which every Soroban token implements, including the Stellar Asset
+/// Contract. See https://github.com/stellar/stellar-protocol/blob/master/ecosystem/sep-0041.md
+interface TokenInterface {
+	/// Returns the amount `spender` is allowed to withdraw from `from`
+	function allowance(address from, address spender) external view returns (int128);
+
+	/// Allow `spender` to withdraw up to `amount` from `from`, until the ledger `expiration_ledger`.
+	/// Needs the authorization of `from`.
+	function approve(address from, address spender, int128 amount, uint32 expiration_ledger) external;
+
+	/// Returns the balance of `id`
+	function balance(address id) external view returns (int128);
+
+	/// Transfer `amount` from `from` to `to`. Needs the authorization of `from`.
+	function transfer(address from, address to, int128 amount) external;
+
+	/// Transfer `amount` from `from` to `to`, using the allowance of `spender`. Needs the
+	/// authorization of `spender`.
+	function transfer_from(address spender, address from, address to, int128 amount) external;
+
+	/// Burn `amount` from `from`. Needs the authorization of `from`.
+	function burn(address from, int128 amount) external;
+
+	/// Burn `amount` from `from`, using the allowance of `spender`. Needs the authorization of
+	/// `spender`.
+	function burn_from(address spender, address from, int128 amount) external;
+
+	/// Returns the number of decimals used to represent amounts
+	function decimals() external view returns (uint32);
+
+	/// Returns the name of the token
+	function name() external view returns (string memory);
+
+	/// Returns the symbol of the token
+	function symbol() external view returns (string memory);
+}
+
+/// The administrative functions of the Stellar Asset Contract, in addition to SEP-41. See
+/// https://developers.stellar.org/docs/tokens/stellar-asset-contract
+interface StellarAssetInterface is TokenInterface {
+	/// Returns the administrator of the asset
+	function admin() external view returns (address);
+
+	/// Set a new administrator. Needs the authorization of the current administrator.
+	function set_admin(address new_admin) external;
+
+	/// Returns true if `id` is allowed to use its balance
+	function authorized(address id) external view returns (bool);
+
+	/// Allow or prevent `id` from using its balance. Needs the authorization of the administrator.
+	function set_authorized(address id, bool authorize) external;
+
+	/// Create `amount` new tokens for `to`. Needs the authorization of the administrator.
+	function mint(address to, int128 amount) external;
+
+	/// Take `amount` from `from`. Needs the authorization of the administrator.
+	function clawback(address from, int128 amount) external;
+}
@@ -1,11 +1,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::codegen::revert::{solana_error_code, solana_error_message};
 use crate::sema::ast::{
-    ArrayLength, Contract, Function, Namespace, Parameter, StructDecl, StructType, Tag, Type,
+    AccountConstraint, ArrayLength, Contract, Expression, Function, Namespace, Parameter,
+    StructDecl, StructType, Tag, Type,
 };
-use anchor_syn::idl::types::{
-    Idl, IdlAccount, IdlAccountItem, IdlEnumVariant, IdlEvent, IdlEventField, IdlField,
-    IdlInstruction, IdlType, IdlTypeDefinition, IdlTypeDefinitionTy,
+use crate::sema::solana_accounts::BuiltinAccounts;
+use anchor_lang_idl::types::{
+    Idl, IdlArrayLen, IdlDefinedFields, IdlEnumVariant, IdlErrorCode, IdlEvent, IdlField,
+    IdlInstruction, IdlInstructionAccount, IdlInstructionAccountItem, IdlMetadata, IdlPda, IdlSeed,
+    IdlSeedArg, IdlSeedConst, IdlType, IdlTypeDef, IdlTypeDefTy, IDL_SPEC,
 };
 use base58::ToBase58;
 use num_traits::ToPrimitive;
@@ -13,7 +17,6 @@ use semver::Version;
 use std::collections::{HashMap, HashSet};
 
 use convert_case::{Boundary, Case, Casing};
-use serde_json::json;
 use sha2::{Digest, Sha256};
 use solang_parser::pt::FunctionTy;
 
@@ -43,7 +46,8 @@ fn discriminator(namespace: &'static str, name: &str) -> Vec<u8> {
     hasher.finalize()[..8].to_vec()
 }
 
-/// Generate an Anchor IDL for a Solidity contract.
+/// Generate an Anchor IDL for a Solidity contract, following the IDL spec of Anchor v0.30 and
+/// later. The address is left empty when the contract has no `@program_id`.
 pub fn generate_anchor_idl(contract_no: usize, ns: &Namespace, contract_version: &str) -> Idl {
     let contract = &ns.contracts[contract_no];
     let docs = idl_docs(&contract.tags);
@@ -51,58 +55,96 @@ pub fn generate_anchor_idl(contract_no: usize, ns: &Namespace, contract_version:
 
     let instructions = idl_instructions(contract_no, contract, &mut type_manager, ns);
 
-    let events = idl_events(contract, &mut type_manager, ns);
+    let (events, event_types) = idl_events(contract, &mut type_manager, ns);
 
-    let metadata = contract
-        .program_id
-        .as_ref()
-        .map(|id| json!({"address": id.to_base58()}));
+    let mut types = type_manager.generate_custom_idl_types();
+    types.extend(event_types);
 
     Idl {
-        version: Version::parse(contract_version).unwrap().to_string(),
-        name: ns.contracts[contract_no].id.name.clone(),
+        address: contract
+            .program_id
+            .as_ref()
+            .map(|id| id.to_base58())
+            .unwrap_or_default(),
+        metadata: IdlMetadata {
+            name: contract.id.name.clone(),
+            version: Version::parse(contract_version).unwrap().to_string(),
+            spec: IDL_SPEC.to_string(),
+            description: None,
+            repository: None,
+            dependencies: vec![],
+            contact: None,
+            deployments: None,
+        },
         docs,
-        constants: vec![],
         instructions,
         accounts: vec![],
-        types: type_manager.generate_custom_idl_types(),
         events,
-        errors: None,
-        metadata,
+        errors: idl_errors(contract_no, ns),
+        types,
+        constants: vec![],
     }
 }
 
-/// Generate IDL events for a contract.
+/// Generate IDL error codes for the custom errors a contract can revert with.
+fn idl_errors(contract_no: usize, ns: &Namespace) -> Vec<IdlErrorCode> {
+    ns.contracts[contract_no]
+        .custom_errors
+        .iter()
+        .map(|error_no| {
+            let err = &ns.errors[*error_no];
+
+            IdlErrorCode {
+                code: solana_error_code(contract_no, *error_no, ns),
+                name: err.name.clone(),
+                msg: solana_error_message(err).map(String::from),
+            }
+        })
+        .collect()
+}
+
+/// Generate IDL events for a contract. The fields of an event are described by a type with the
+/// same name as the event. Unlike the legacy format, the IDL format of Anchor v0.30 has no place for
+/// the `indexed` flag of a field, so it is not recorded.
 fn idl_events(
     contract: &Contract,
     type_manager: &mut TypeManager,
     ns: &Namespace,
-) -> Option<Vec<IdlEvent>> {
-    if contract.emits_events.is_empty() {
-        None
-    } else {
-        let mut events: Vec<IdlEvent> = Vec::with_capacity(contract.emits_events.len());
-        for event_no in &contract.emits_events {
-            let def = &ns.events[*event_no];
-            let mut fields: Vec<IdlEventField> = Vec::with_capacity(def.fields.len());
-            let mut dedup = Deduplicate::new("field".to_owned());
-            for item in &def.fields {
-                let name = dedup.unique_name(item);
-                fields.push(IdlEventField {
-                    name,
-                    ty: type_manager.convert(&item.ty),
-                    index: item.indexed,
-                });
-            }
+) -> (Vec<IdlEvent>, Vec<IdlTypeDef>) {
+    let mut events: Vec<IdlEvent> = Vec::with_capacity(contract.emits_events.len());
+    let mut types: Vec<IdlTypeDef> = Vec::with_capacity(contract.emits_events.len());
 
-            events.push(IdlEvent {
-                name: def.id.name.clone(),
-                fields,
+    for event_no in &contract.emits_events {
+        let def = &ns.events[*event_no];
+        let mut fields: Vec<IdlField> = Vec::with_capacity(def.fields.len());
+        let mut dedup = Deduplicate::new("field".to_owned());
+        for item in &def.fields {
+            let name = dedup.unique_name(item);
+            fields.push(IdlField {
+                name,
+                docs: vec![],
+                ty: type_manager.convert(&item.ty),
             });
         }
 
-        Some(events)
+        events.push(IdlEvent {
+            name: def.id.name.clone(),
+            discriminator: event_discriminator(&def.id.name),
+        });
+
+        types.push(IdlTypeDef {
+            name: def.id.name.clone(),
+            docs: idl_docs(&def.tags),
+            serialization: Default::default(),
+            repr: None,
+            generics: vec![],
+            ty: IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Named(fields)),
+            },
+        });
     }
+
+    (events, types)
 }
 
 /// Generate the IDL instructions for a contract.
@@ -117,16 +159,9 @@ fn idl_instructions(
     if contract.constructors(ns).is_empty() {
         instructions.push(IdlInstruction {
             name: "new".to_string(),
-            docs: None,
-            accounts: vec![IdlAccountItem::IdlAccount(IdlAccount {
-                name: "dataAccount".to_string(),
-                is_mut: true,
-                is_signer: false,
-                is_optional: Some(false),
-                docs: None,
-                pda: None,
-                relations: vec![],
-            })],
+            docs: vec![],
+            discriminator: function_discriminator("new"),
+            accounts: vec![idl_account("dataAccount", true, false)],
             args: vec![],
             returns: None,
         })
@@ -156,7 +191,7 @@ fn idl_instructions(
 
             args.push(IdlField {
                 name: normalized,
-                docs: None,
+                docs: vec![],
                 ty: type_manager.convert(&item.ty),
             });
         }
@@ -174,17 +209,31 @@ fn idl_instructions(
             .borrow()
             .iter()
             .map(|(account_name, account)| {
-                IdlAccountItem::IdlAccount(IdlAccount {
-                    name: account_name.clone(),
-                    is_mut: account.is_writer,
-                    is_signer: account.is_signer,
-                    is_optional: Some(false),
-                    docs: None,
-                    pda: None,
-                    relations: vec![],
-                })
+                let mut item = idl_account(account_name, account.is_writer, account.is_signer);
+                let IdlInstructionAccountItem::Single(idl) = &mut item else {
+                    unreachable!();
+                };
+
+                for constraint in &func.account_constraints {
+                    if constraint.account() != account_name {
+                        continue;
+                    }
+
+                    match constraint {
+                        AccountConstraint::Seeds { seeds, .. } => {
+                            idl.pda = idl_pda(seeds, func, &args);
+                        }
+                        AccountConstraint::HasOne { .. } => {
+                            // The address of this account is stored in the data account
+                            idl.relations.push(BuiltinAccounts::DataAccount.to_string());
+                        }
+                        _ => (),
+                    }
+                }
+
+                item
             })
-            .collect::<Vec<IdlAccountItem>>();
+            .collect::<Vec<IdlInstructionAccountItem>>();
 
         let returns = if func.returns.is_empty() {
             None
@@ -195,6 +244,7 @@ fn idl_instructions(
         };
 
         instructions.push(IdlInstruction {
+            discriminator: func.selector(ns, &contract_no),
             name,
             docs: tags,
             accounts,
@@ -206,6 +256,60 @@ fn idl_instructions(
     instructions
 }
 
+/// An account of an instruction
+fn idl_account(name: &str, writable: bool, signer: bool) -> IdlInstructionAccountItem {
+    IdlInstructionAccountItem::Single(IdlInstructionAccount {
+        name: name.to_string(),
+        docs: vec![],
+        writable,
+        signer,
+        optional: false,
+        address: None,
+        pda: None,
+        relations: vec![],
+    })
+}
+
+/// The seeds of a program derived address, if all of them are constants or function arguments. The
+/// bump is not part of the seeds in the IDL.
+fn idl_pda(seeds: &[Expression], func: &Function, args: &[IdlField]) -> Option<IdlPda> {
+    let seeds = seeds
+        .iter()
+        .map(|seed| {
+            let mut seed = seed;
+
+            while let Expression::Cast { expr, .. } | Expression::BytesCast { expr, .. } = seed {
+                seed = expr;
+            }
+
+            match seed {
+                Expression::BytesLiteral { value, .. }
+                | Expression::AllocDynamicBytes {
+                    init: Some(value), ..
+                } => Some(IdlSeed::Const(IdlSeedConst {
+                    value: value.clone(),
+                })),
+                Expression::Variable { var_no, .. } => func
+                    .symtable
+                    .arguments
+                    .iter()
+                    .position(|arg| *arg == Some(*var_no))
+                    .map(|arg_no| {
+                        IdlSeed::Arg(IdlSeedArg {
+                            path: args[arg_no].name.clone(),
+                        })
+                    }),
+                _ => None,
+            }
+        })
+        .collect::<Option<Vec<IdlSeed>>>()?;
+
+    Some(IdlPda {
+        seeds,
+        program: None,
+    })
+}
+
 /// This struct accounts all the user defined types used in the contract that need to be present
 /// in the IDL 'types' field.
 struct TypeManager<'a> {
@@ -215,8 +319,8 @@ struct TypeManager<'a> {
     /// This is a mapping between the IDL type and the tuple
     /// (index into the types vector, is the type from the current contract?, original type name)
     added_names: HashMap<String, (usize, Option<String>, String)>,
-    returns_structs: Vec<IdlTypeDefinition>,
-    types: Vec<IdlTypeDefinition>,
+    returns_structs: Vec<IdlTypeDef>,
+    types: Vec<IdlTypeDef>,
 }
 
 impl TypeManager<'_> {
@@ -241,23 +345,27 @@ impl TypeManager<'_> {
 
             fields.push(IdlField {
                 name,
-                docs: None,
+                docs: vec![],
                 ty: self.convert(&item.ty),
             });
         }
 
         let name = format!("{effective_name}_returns");
-        self.returns_structs.push(IdlTypeDefinition {
+        self.returns_structs.push(IdlTypeDef {
             name: name.clone(),
-            docs: Some(vec![format!(
+            docs: vec![format!(
                 "Data structure to hold the multiple returns of function {}",
                 func.id
-            )]),
-            ty: IdlTypeDefinitionTy::Struct { fields },
-            generics: None,
+            )],
+            serialization: Default::default(),
+            repr: None,
+            generics: vec![],
+            ty: IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Named(fields)),
+            },
         });
 
-        IdlType::Defined(name)
+        defined(name)
     }
 
     /// This function creates an unique name for either a custom struct or enum.
@@ -310,7 +418,7 @@ impl TypeManager<'_> {
         for item in &def.fields {
             fields.push(IdlField {
                 name: item.name_as_str().to_string(),
-                docs: None,
+                docs: vec![],
                 ty: self.convert(&item.ty),
             });
         }
@@ -322,17 +430,21 @@ impl TypeManager<'_> {
             (self.types.len(), def.contract.clone(), def.id.name.clone()),
         );
 
-        self.types.push(IdlTypeDefinition {
+        self.types.push(IdlTypeDef {
             name,
             docs,
-            ty: IdlTypeDefinitionTy::Struct { fields },
-            generics: None,
+            serialization: Default::default(),
+            repr: None,
+            generics: vec![],
+            ty: IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Named(fields)),
+            },
         });
     }
 
     /// This function ensures there are no name collisions on the structs created for the functions
     /// with multiple returns, before returning all the custom types needed for the IDL file.
-    fn generate_custom_idl_types(self) -> Vec<IdlTypeDefinition> {
+    fn generate_custom_idl_types(self) -> Vec<IdlTypeDef> {
         let mut custom_types = self.types;
         let mut used_names: HashSet<String> = custom_types
             .iter()
@@ -347,12 +459,7 @@ impl TypeManager<'_> {
                 name = format!("{}_{}", item.name, value);
             }
             used_names.insert(name.clone());
-            custom_types.push(IdlTypeDefinition {
-                name,
-                docs: item.docs,
-                ty: item.ty,
-                generics: None,
-            });
+            custom_types.push(IdlTypeDef { name, ..item });
         }
 
         custom_types
@@ -383,11 +490,13 @@ impl TypeManager<'_> {
             })
             .collect::<Vec<IdlEnumVariant>>();
 
-        self.types.push(IdlTypeDefinition {
+        self.types.push(IdlTypeDef {
             name,
             docs,
-            ty: IdlTypeDefinitionTy::Enum { variants },
-            generics: None,
+            serialization: Default::default(),
+            repr: None,
+            generics: vec![],
+            ty: IdlTypeDefTy::Enum { variants },
         });
     }
 
@@ -413,21 +522,26 @@ impl TypeManager<'_> {
                 129..=256 => IdlType::U256,
                 _ => unreachable!("Unsigned integers wider than 256 bits are not supported"),
             },
+            // fixed point numbers are encoded as scaled integers
+            Type::Fixed(n, _) => self.convert(&Type::Int(*n)),
+            Type::Ufixed(n, _) => self.convert(&Type::Uint(*n)),
             Type::DynamicBytes => IdlType::Bytes,
             Type::String => IdlType::String,
-            Type::Address(_) | Type::Contract(_) => IdlType::PublicKey,
+            Type::Address(_) | Type::Contract(_) => IdlType::Pubkey,
             Type::Struct(struct_type) => {
                 let def = struct_type.definition(self.namespace);
                 self.add_struct_definition(def, ast_type);
-                IdlType::Defined(def.id.name.clone())
+                defined(def.id.name.clone())
             }
             Type::Array(ty, dims) => {
                 let mut idl_type = self.convert(ty);
                 for item in dims {
                     match item {
                         ArrayLength::Fixed(number) => {
-                            idl_type =
-                                IdlType::Array(Box::new(idl_type), number.to_usize().unwrap());
+                            idl_type = IdlType::Array(
+                                Box::new(idl_type),
+                                IdlArrayLen::Value(number.to_usize().unwrap()),
+                            );
                         }
                         ArrayLength::Dynamic => {
                             idl_type = IdlType::Vec(Box::new(idl_type));
@@ -439,10 +553,12 @@ impl TypeManager<'_> {
                 }
                 idl_type
             }
-            Type::Bytes(dim) => IdlType::Array(Box::new(IdlType::U8), *dim as usize),
+            Type::Bytes(dim) => {
+                IdlType::Array(Box::new(IdlType::U8), IdlArrayLen::Value(*dim as usize))
+            }
             Type::Enum(enum_no) => {
                 self.add_enum_definition(*enum_no, ast_type);
-                IdlType::Defined(self.namespace.enums[*enum_no].id.name.clone())
+                defined(self.namespace.enums[*enum_no].id.name.clone())
             }
             Type::ExternalFunction { .. } => {
                 self.convert(&Type::Struct(StructType::ExternalFunction))
@@ -466,19 +582,21 @@ impl TypeManager<'_> {
     }
 }
 
-/// Prepare the docs from doc comments.
-fn idl_docs(tags: &[Tag]) -> Option<Vec<String>> {
-    if tags.is_empty() {
-        None
-    } else {
-        Some(
-            tags.iter()
-                .map(|tag| format!("{}: {}", tag.tag, tag.value))
-                .collect::<Vec<String>>(),
-        )
+/// A reference to a type in the 'types' field
+fn defined(name: String) -> IdlType {
+    IdlType::Defined {
+        name,
+        generics: vec![],
     }
 }
 
+/// Prepare the docs from doc comments.
+fn idl_docs(tags: &[Tag]) -> Vec<String> {
+    tags.iter()
+        .map(|tag| format!("{}: {}", tag.tag, tag.value))
+        .collect::<Vec<String>>()
+}
+
 struct Deduplicate {
     prefix: String,
     counter: u16,
@@ -143,6 +143,9 @@ fn resolve_ast(ty: &ast::Type, ns: &ast::Namespace, registry: &mut PortableRegis
         ast::Type::Bool | ast::Type::Int(_) | ast::Type::Uint(_) | ast::Type::String => {
             primitive_to_ty(ty, registry)
         }
+        // fixed point numbers are encoded as scaled integers
+        ast::Type::Fixed(n, _) => primitive_to_ty(&ast::Type::Int(*n), registry),
+        ast::Type::Ufixed(n, _) => primitive_to_ty(&ast::Type::Uint(*n), registry),
         // resolve from the deepest element to outside
         // [[A; a: usize]; b: usize] -> Array(A_id, vec![a, b])
         ast::Type::Array(ty, dims) => {
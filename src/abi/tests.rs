@@ -2,17 +2,17 @@
 
 #![cfg(test)]
 
-use crate::abi::anchor::generate_anchor_idl;
+use crate::abi::anchor::{event_discriminator, function_discriminator, generate_anchor_idl};
 use crate::codegen::{codegen, Options};
 use crate::file_resolver::FileResolver;
 use crate::sema::ast::Namespace;
 use crate::{codegen, parse_and_resolve, Target};
-use anchor_syn::idl::types::{
-    IdlAccount, IdlAccountItem, IdlEnumVariant, IdlEvent, IdlEventField, IdlField, IdlType,
-    IdlTypeDefinition, IdlTypeDefinitionTy,
+use anchor_lang_idl::types::{
+    IdlArrayLen, IdlDefinedFields, IdlEnumVariant, IdlErrorCode, IdlEvent, IdlField,
+    IdlInstructionAccount, IdlInstructionAccountItem, IdlPda, IdlSeed, IdlSeedArg, IdlSeedConst,
+    IdlSerialization, IdlType, IdlTypeDef, IdlTypeDefTy, IDL_SPEC,
 };
 use semver::Version;
-use serde_json::json;
 use std::ffi::OsStr;
 
 fn generate_namespace(src: &'static str) -> Namespace {
@@ -44,12 +44,15 @@ contract caller {
     let mut ns = generate_namespace(src);
     codegen(&mut ns, &Options::default());
     let idl = generate_anchor_idl(0, &ns, "0.1.0");
-    assert_eq!(idl.version, Version::parse("0.1.0").unwrap().to_string());
-    assert_eq!(idl.name, "caller");
-    assert!(idl.docs.is_some());
-    assert_eq!(idl.docs.as_ref().unwrap().len(), 2);
-    assert_eq!(idl.docs.as_ref().unwrap()[0], "title: MyContract");
-    assert_eq!(idl.docs.as_ref().unwrap()[1], "author: Lucas");
+    assert_eq!(
+        idl.metadata.version,
+        Version::parse("0.1.0").unwrap().to_string()
+    );
+    assert_eq!(idl.metadata.name, "caller");
+    assert_eq!(idl.metadata.spec, IDL_SPEC);
+    assert_eq!(idl.docs.len(), 2);
+    assert_eq!(idl.docs[0], "title: MyContract");
+    assert_eq!(idl.docs[1], "author: Lucas");
 }
 
 #[test]
@@ -80,10 +83,10 @@ fn constants_and_types() {
     assert_eq!(idl.types.len(), 2);
 
     assert_eq!(idl.types[0].name, "Week");
-    assert!(idl.types[0].docs.is_none());
+    assert!(idl.types[0].docs.is_empty());
     assert_eq!(
         idl.types[0].ty,
-        IdlTypeDefinitionTy::Enum {
+        IdlTypeDefTy::Enum {
             variants: vec![
                 IdlEnumVariant {
                     name: "Monday".to_string(),
@@ -104,25 +107,23 @@ fn constants_and_types() {
     assert_eq!(idl.types[1].name, "cte5_returns");
     assert_eq!(
         idl.types[1].docs,
-        Some(vec![
-            "Data structure to hold the multiple returns of function cte5".into()
-        ])
+        vec!["Data structure to hold the multiple returns of function cte5".to_string()]
     );
     assert_eq!(
         idl.types[1].ty,
-        IdlTypeDefinitionTy::Struct {
-            fields: vec![
+        IdlTypeDefTy::Struct {
+            fields: Some(IdlDefinedFields::Named(vec![
                 IdlField {
                     name: "g".to_string(),
-                    docs: None,
+                    docs: vec![],
                     ty: IdlType::U8,
                 },
                 IdlField {
                     name: "d".to_string(),
-                    docs: None,
-                    ty: IdlType::Array(IdlType::U8.into(), 2)
+                    docs: vec![],
+                    ty: IdlType::Array(IdlType::U8.into(), IdlArrayLen::Value(2))
                 }
-            ]
+            ])),
         }
     );
 }
@@ -187,7 +188,11 @@ fn instructions_and_types() {
 
     // implicit constructor
     assert_eq!(idl.instructions[0].name, "new");
-    assert!(idl.instructions[0].docs.is_none());
+    assert_eq!(
+        idl.instructions[0].discriminator,
+        function_discriminator("new")
+    );
+    assert!(idl.instructions[0].docs.is_empty());
     assert_eq!(
         idl.instructions[0].accounts,
         vec![idl_account("dataAccount", true, false)]
@@ -197,7 +202,11 @@ fn instructions_and_types() {
 
     // cte accessor function
     assert_eq!(idl.instructions[1].name, "cte");
-    assert!(idl.instructions[1].docs.is_none());
+    assert_eq!(
+        idl.instructions[1].discriminator,
+        function_discriminator("cte")
+    );
+    assert!(idl.instructions[1].docs.is_empty());
     assert_eq!(
         idl.instructions[1].accounts,
         vec![idl_account("dataAccount", false, false)]
@@ -207,7 +216,7 @@ fn instructions_and_types() {
 
     // cte2 accessor function
     assert_eq!(idl.instructions[2].name, "cte2");
-    assert!(idl.instructions[2].docs.is_none());
+    assert!(idl.instructions[2].docs.is_empty());
     assert_eq!(
         idl.instructions[2].accounts,
         vec![idl_account("dataAccount", false, false)]
@@ -216,7 +225,7 @@ fn instructions_and_types() {
         idl.instructions[2].args,
         vec![IdlField {
             name: "arg0".to_string(),
-            docs: None,
+            docs: vec![],
             ty: IdlType::U256,
         }]
     );
@@ -224,19 +233,19 @@ fn instructions_and_types() {
 
     // sum function
     assert_eq!(idl.instructions[3].name, "sum");
-    assert!(idl.instructions[3].docs.is_none());
+    assert!(idl.instructions[3].docs.is_empty());
     assert_eq!(idl.instructions[3].accounts, vec![]);
     assert_eq!(
         idl.instructions[3].args,
         vec![
             IdlField {
                 name: "a".to_string(),
-                docs: None,
+                docs: vec![],
                 ty: IdlType::U256
             },
             IdlField {
                 name: "b".to_string(),
-                docs: None,
+                docs: vec![],
                 ty: IdlType::I256
             }
         ]
@@ -244,10 +253,7 @@ fn instructions_and_types() {
     assert_eq!(idl.instructions[3].returns, Some(IdlType::I256));
 
     assert_eq!(idl.instructions[4].name, "setString");
-    assert_eq!(
-        idl.instructions[4].docs,
-        Some(vec!["param: input".to_string()])
-    );
+    assert_eq!(idl.instructions[4].docs, vec!["param: input".to_string()]);
     assert_eq!(
         idl.instructions[4].accounts,
         vec![idl_account("dataAccount", true, false)]
@@ -256,7 +262,7 @@ fn instructions_and_types() {
         idl.instructions[4].args,
         vec![IdlField {
             name: "c".to_string(),
-            docs: None,
+            docs: vec![],
             ty: IdlType::String
         }]
     );
@@ -265,7 +271,7 @@ fn instructions_and_types() {
     assert_eq!(idl.instructions[5].name, "getString");
     assert_eq!(
         idl.instructions[5].docs,
-        Some(vec!["return: the string".to_string()])
+        vec!["return: the string".to_string()]
     );
     assert_eq!(
         idl.instructions[5].accounts,
@@ -275,7 +281,7 @@ fn instructions_and_types() {
     assert_eq!(idl.instructions[5].returns, Some(IdlType::String));
 
     assert_eq!(idl.instructions[6].name, "multipleReturns");
-    assert!(idl.instructions[6].docs.is_none());
+    assert!(idl.instructions[6].docs.is_empty());
     assert_eq!(
         idl.instructions[6].accounts,
         vec![idl_account("dataAccount", true, false),]
@@ -283,7 +289,10 @@ fn instructions_and_types() {
     assert!(idl.instructions[6].args.is_empty());
     assert_eq!(
         idl.instructions[6].returns,
-        Some(IdlType::Defined("multipleReturns_returns".to_string()))
+        Some(IdlType::Defined {
+            name: "multipleReturns_returns".to_string(),
+            generics: vec![],
+        })
     );
 
     assert!(idl.accounts.is_empty());
@@ -292,33 +301,35 @@ fn instructions_and_types() {
 
     assert_eq!(
         idl.types[0],
-        IdlTypeDefinition {
+        IdlTypeDef {
             name: "multipleReturns_returns".to_string(),
-            docs: Some(vec![
+            docs: vec![
                 "Data structure to hold the multiple returns of function multipleReturns"
                     .to_string()
-            ]),
-            ty: IdlTypeDefinitionTy::Struct {
-                fields: vec![
+            ],
+            ty: IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Named(vec![
                     IdlField {
                         name: "return_0".to_string(),
-                        docs: None,
+                        docs: vec![],
                         ty: IdlType::U64
                     },
                     IdlField {
                         name: "return_1".to_string(),
-                        docs: None,
+                        docs: vec![],
                         ty: IdlType::String,
                     }
-                ]
+                ])),
             },
-            generics: None
+            serialization: IdlSerialization::Borsh,
+            repr: None,
+            generics: vec![]
         }
     );
 
-    assert!(idl.events.is_none());
-    assert!(idl.events.is_none());
-    assert!(idl.metadata.is_none());
+    assert!(idl.events.is_empty());
+    assert!(idl.events.is_empty());
+    assert!(idl.address.is_empty());
 }
 
 #[test]
@@ -347,7 +358,7 @@ contract caller {
 
     // implicit constructor
     assert_eq!(idl.instructions[0].name, "new");
-    assert!(idl.instructions[0].docs.is_none());
+    assert!(idl.instructions[0].docs.is_empty());
     assert_eq!(
         idl.instructions[0].accounts,
         vec![idl_account("dataAccount", true, false)]
@@ -356,29 +367,29 @@ contract caller {
     assert!(idl.instructions[0].returns.is_none());
 
     assert_eq!(idl.instructions[1].name, "emitAll");
-    assert!(idl.instructions[1].docs.is_none());
+    assert!(idl.instructions[1].docs.is_empty());
     assert!(idl.instructions[1].accounts.is_empty());
     assert_eq!(
         idl.instructions[1].args,
         vec![
             IdlField {
                 name: "a".to_string(),
-                docs: None,
+                docs: vec![],
                 ty: IdlType::Bool,
             },
             IdlField {
                 name: "b".to_string(),
-                docs: None,
+                docs: vec![],
                 ty: IdlType::String,
             },
             IdlField {
                 name: "d".to_string(),
-                docs: None,
+                docs: vec![],
                 ty: IdlType::I8
             },
             IdlField {
                 name: "e".to_string(),
-                docs: None,
+                docs: vec![],
                 ty: IdlType::U128,
             }
         ]
@@ -387,14 +398,14 @@ contract caller {
 
     assert!(idl.accounts.is_empty());
 
-    assert_eq!(idl.types.len(), 1);
+    assert_eq!(idl.types.len(), 3);
 
     assert_eq!(
         idl.types[0],
-        IdlTypeDefinition {
+        IdlTypeDef {
             name: "Color".to_string(),
-            docs: None,
-            ty: IdlTypeDefinitionTy::Enum {
+            docs: vec![],
+            ty: IdlTypeDefTy::Enum {
                 variants: vec![
                     IdlEnumVariant {
                         name: "Yellow".to_string(),
@@ -410,58 +421,88 @@ contract caller {
                     }
                 ]
             },
-            generics: None
+            serialization: IdlSerialization::Borsh,
+            repr: None,
+            generics: vec![]
         }
     );
 
     assert_eq!(
         idl.events,
-        Some(vec![
+        vec![
             IdlEvent {
                 name: "Event1".to_string(),
-                fields: vec![
-                    IdlEventField {
-                        name: "field_0".to_string(),
-                        ty: IdlType::Bool,
-                        index: false,
-                    },
-                    IdlEventField {
-                        name: "field_1".to_string(),
-                        ty: IdlType::String,
-                        index: true,
-                    },
-                    IdlEventField {
-                        name: "field_2".to_string(),
-                        ty: IdlType::I8,
-                        index: false,
-                    },
-                    IdlEventField {
-                        name: "field_3".to_string(),
-                        ty: IdlType::Defined("Color".to_string()),
-                        index: false,
-                    }
-                ],
+                discriminator: event_discriminator("Event1")
             },
             IdlEvent {
                 name: "Event2".to_string(),
-                fields: vec![
-                    IdlEventField {
-                        name: "a".to_string(),
-                        ty: IdlType::Bool,
-                        index: false,
-                    },
-                    IdlEventField {
-                        name: "cc".to_string(),
-                        ty: IdlType::U128,
-                        index: true,
-                    }
-                ]
+                discriminator: event_discriminator("Event2")
             }
-        ])
+        ]
+    );
+    assert_eq!(
+        idl.types[1..],
+        [
+            IdlTypeDef {
+                name: "Event1".to_string(),
+                docs: vec![],
+                serialization: IdlSerialization::Borsh,
+                repr: None,
+                generics: vec![],
+                ty: IdlTypeDefTy::Struct {
+                    fields: Some(IdlDefinedFields::Named(vec![
+                        IdlField {
+                            name: "field_0".to_string(),
+                            docs: vec![],
+                            ty: IdlType::Bool
+                        },
+                        IdlField {
+                            name: "field_1".to_string(),
+                            docs: vec![],
+                            ty: IdlType::String
+                        },
+                        IdlField {
+                            name: "field_2".to_string(),
+                            docs: vec![],
+                            ty: IdlType::I8
+                        },
+                        IdlField {
+                            name: "field_3".to_string(),
+                            docs: vec![],
+                            ty: IdlType::Defined {
+                                name: "Color".to_string(),
+                                generics: vec![],
+                            }
+                        }
+                    ]))
+                }
+            },
+            IdlTypeDef {
+                name: "Event2".to_string(),
+                docs: vec![],
+                serialization: IdlSerialization::Borsh,
+                repr: None,
+                generics: vec![],
+                ty: IdlTypeDefTy::Struct {
+                    fields: Some(IdlDefinedFields::Named(vec![
+                        IdlField {
+                            name: "a".to_string(),
+                            docs: vec![],
+                            ty: IdlType::Bool
+                        },
+                        IdlField {
+                            name: "cc".to_string(),
+                            docs: vec![],
+                            ty: IdlType::U128
+                        }
+                    ]))
+                }
+            }
+        ]
     );
 
-    assert!(idl.errors.is_none());
-    assert!(idl.metadata.is_none());
+    assert!(idl.errors.is_empty());
+    assert!(idl.address.is_empty());
 }
 
 #[test]
@@ -484,7 +525,7 @@ fn types() {
 
     // implicit constructor
     assert_eq!(idl.instructions[0].name, "new");
-    assert!(idl.instructions[0].docs.is_none());
+    assert!(idl.instructions[0].docs.is_empty());
     assert_eq!(
         idl.instructions[0].accounts,
         vec![idl_account("dataAccount", true, false)]
@@ -499,49 +540,68 @@ fn types() {
         vec![
             IdlField {
                 name: "a".to_string(),
-                docs: None,
+                docs: vec![],
                 ty: IdlType::I32,
             },
             IdlField {
                 name: "b".to_string(),
-                docs: None,
-                ty: IdlType::Vec(IdlType::Array(IdlType::U32.into(), 2).into()),
+                docs: vec![],
+                ty: IdlType::Vec(IdlType::Array(IdlType::U32.into(), IdlArrayLen::Value(2)).into()),
             },
             IdlField {
                 name: "d".to_string(),
-                docs: None,
-                ty: IdlType::Array(IdlType::Array(IdlType::U32.into(), 2).into(), 4),
+                docs: vec![],
+                ty: IdlType::Array(
+                    IdlType::Array(IdlType::U32.into(), IdlArrayLen::Value(2)).into(),
+                    IdlArrayLen::Value(4)
+                ),
             },
             IdlField {
                 name: "e".to_string(),
-                docs: None,
-                ty: IdlType::Array(IdlType::Vec(IdlType::U32.into()).into(), 2)
+                docs: vec![],
+                ty: IdlType::Array(
+                    IdlType::Vec(IdlType::U32.into()).into(),
+                    IdlArrayLen::Value(2)
+                )
             }
         ]
     );
     assert!(idl.instructions[1].returns.is_none());
     assert!(idl.accounts.is_empty());
-    assert!(idl.types.is_empty());
+    assert_eq!(idl.types.len(), 1);
     assert_eq!(
-        idl.events.unwrap(),
+        idl.events,
         vec![IdlEvent {
             name: "Event1".to_string(),
-            fields: vec![
-                IdlEventField {
-                    name: "field_0".to_string(),
-                    ty: IdlType::I32,
-                    index: false,
-                },
-                IdlEventField {
-                    name: "field_1".to_string(),
-                    ty: IdlType::U32,
-                    index: false,
-                }
-            ]
+            discriminator: event_discriminator("Event1")
         }]
     );
-    assert!(idl.errors.is_none());
-    assert!(idl.metadata.is_none());
+    assert_eq!(
+        idl.types[0..],
+        [IdlTypeDef {
+            name: "Event1".to_string(),
+            docs: vec![],
+            serialization: IdlSerialization::Borsh,
+            repr: None,
+            generics: vec![],
+            ty: IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Named(vec![
+                    IdlField {
+                        name: "field_0".to_string(),
+                        docs: vec![],
+                        ty: IdlType::I32
+                    },
+                    IdlField {
+                        name: "field_1".to_string(),
+                        docs: vec![],
+                        ty: IdlType::U32
+                    }
+                ]))
+            }
+        }]
+    );
+    assert!(idl.errors.is_empty());
+    assert!(idl.address.is_empty());
 }
 
 #[test]
@@ -562,13 +622,13 @@ fn constructor() {
     codegen(&mut ns, &Options::default());
     let idl = generate_anchor_idl(0, &ns, "0.1.0");
 
-    assert_eq!(idl.name, "caller");
-    assert!(idl.docs.is_none());
+    assert_eq!(idl.metadata.name, "caller");
+    assert!(idl.docs.is_empty());
     assert!(idl.constants.is_empty());
 
     assert_eq!(idl.instructions.len(), 2);
     assert_eq!(idl.instructions[0].name, "new");
-    assert!(idl.instructions[0].docs.is_none());
+    assert!(idl.instructions[0].docs.is_empty());
     assert_eq!(
         idl.instructions[0].accounts,
         vec![idl_account("dataAccount", true, false)]
@@ -577,14 +637,14 @@ fn constructor() {
         idl.instructions[0].args,
         vec![IdlField {
             name: "ff".to_string(),
-            docs: None,
+            docs: vec![],
             ty: IdlType::U64,
         },]
     );
     assert!(idl.instructions[0].returns.is_none());
 
     assert_eq!(idl.instructions[1].name, "getNum");
-    assert!(idl.instructions[1].docs.is_none());
+    assert!(idl.instructions[1].docs.is_empty());
     assert_eq!(
         idl.instructions[1].accounts,
         vec![idl_account("dataAccount", false, false)]
@@ -594,9 +654,9 @@ fn constructor() {
 
     assert!(idl.accounts.is_empty());
     assert!(idl.types.is_empty());
-    assert!(idl.events.is_none());
-    assert!(idl.errors.is_none());
-    assert!(idl.metadata.is_none());
+    assert!(idl.events.is_empty());
+    assert!(idl.errors.is_empty());
+    assert!(idl.address.is_empty());
 }
 
 #[test]
@@ -626,19 +686,22 @@ contract Testing {
     assert_eq!(idl.instructions[1].name, "getNum");
     assert_eq!(
         idl.instructions[1].returns,
-        Some(IdlType::Defined("getNum_returns".to_string()))
+        Some(IdlType::Defined {
+            name: "getNum_returns".to_string(),
+            generics: vec![],
+        })
     );
     assert_eq!(
         idl.instructions[1].args,
         vec![
             IdlField {
                 name: "a".to_string(),
-                docs: None,
+                docs: vec![],
                 ty: IdlType::U64
             },
             IdlField {
                 name: "b".to_string(),
-                docs: None,
+                docs: vec![],
                 ty: IdlType::U64
             },
         ]
@@ -647,26 +710,28 @@ contract Testing {
     assert_eq!(idl.types.len(), 1);
     assert_eq!(
         idl.types[0],
-        IdlTypeDefinition {
+        IdlTypeDef {
             name: "getNum_returns".to_string(),
-            docs: Some(vec![
+            docs: vec![
                 "Data structure to hold the multiple returns of function getNum".to_string()
-            ]),
-            ty: IdlTypeDefinitionTy::Struct {
-                fields: vec![
+            ],
+            ty: IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Named(vec![
                     IdlField {
                         name: "ret1".to_string(),
-                        docs: None,
+                        docs: vec![],
                         ty: IdlType::U64,
                     },
                     IdlField {
                         name: "ret2".to_string(),
-                        docs: None,
+                        docs: vec![],
                         ty: IdlType::U64,
                     }
-                ],
+                ])),
             },
-            generics: None
+            serialization: IdlSerialization::Borsh,
+            repr: None,
+            generics: vec![]
         }
     );
 }
@@ -696,14 +761,14 @@ contract Testing {
     assert_eq!(idl.instructions[0].name, "new");
 
     assert_eq!(idl.instructions[1].name, "getNum_uint64_uint64");
-    assert!(idl.instructions[1].docs.is_none());
+    assert!(idl.instructions[1].docs.is_empty());
     assert_eq!(idl.instructions[1].accounts, vec![]);
     assert_eq!(idl.instructions[1].args.len(), 2);
     assert_eq!(
         idl.instructions[1].args[0],
         IdlField {
             name: "a".to_string(),
-            docs: None,
+            docs: vec![],
             ty: IdlType::U64
         }
     );
@@ -711,17 +776,20 @@ contract Testing {
         idl.instructions[1].args[1],
         IdlField {
             name: "b".to_string(),
-            docs: None,
+            docs: vec![],
             ty: IdlType::U64
         }
     );
     assert_eq!(
         idl.instructions[1].returns,
-        Some(IdlType::Defined("getNum_uint64_uint64_returns".to_string()))
+        Some(IdlType::Defined {
+            name: "getNum_uint64_uint64_returns".to_string(),
+            generics: vec![],
+        })
     );
 
     assert_eq!(idl.instructions[2].name, "getNum_int32_int32");
-    assert!(idl.instructions[2].docs.is_none());
+    assert!(idl.instructions[2].docs.is_empty());
     assert_eq!(idl.instructions[1].accounts, vec![]);
 
     assert_eq!(idl.instructions[2].args.len(), 2);
@@ -729,7 +797,7 @@ contract Testing {
         idl.instructions[2].args[0],
         IdlField {
             name: "a".to_string(),
-            docs: None,
+            docs: vec![],
             ty: IdlType::I32,
         }
     );
@@ -737,64 +805,71 @@ contract Testing {
         idl.instructions[2].args[1],
         IdlField {
             name: "b".to_string(),
-            docs: None,
+            docs: vec![],
             ty: IdlType::I32
         }
     );
     assert_eq!(
         idl.instructions[2].returns,
-        Some(IdlType::Defined("getNum_int32_int32_returns".to_string()))
+        Some(IdlType::Defined {
+            name: "getNum_int32_int32_returns".to_string(),
+            generics: vec![],
+        })
     );
 
     assert_eq!(idl.types.len(), 2);
 
     assert_eq!(
         idl.types[0],
-        IdlTypeDefinition {
+        IdlTypeDef {
             name: "getNum_uint64_uint64_returns".to_string(),
-            docs: Some(vec![
+            docs: vec![
                 "Data structure to hold the multiple returns of function getNum".to_string()
-            ]),
-            ty: IdlTypeDefinitionTy::Struct {
-                fields: vec![
+            ],
+            ty: IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Named(vec![
                     IdlField {
                         name: "ret1".to_string(),
-                        docs: None,
+                        docs: vec![],
                         ty: IdlType::U64
                     },
                     IdlField {
                         name: "ret2".to_string(),
-                        docs: None,
+                        docs: vec![],
                         ty: IdlType::U64
                     }
-                ]
+                ])),
             },
-            generics: None
+            serialization: IdlSerialization::Borsh,
+            repr: None,
+            generics: vec![]
         }
     );
 
     assert_eq!(
         idl.types[1],
-        IdlTypeDefinition {
+        IdlTypeDef {
             name: "getNum_int32_int32_returns".to_string(),
-            docs: Some(vec![
+            docs: vec![
                 "Data structure to hold the multiple returns of function getNum".to_string()
-            ]),
-            ty: IdlTypeDefinitionTy::Struct {
-                fields: vec![
+            ],
+            ty: IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Named(vec![
                     IdlField {
                         name: "ret3".to_string(),
-                        docs: None,
+                        docs: vec![],
                         ty: IdlType::I32
                     },
                     IdlField {
                         name: "ret4".to_string(),
-                        docs: None,
+                        docs: vec![],
                         ty: IdlType::I32
                     }
-                ]
+                ])),
             },
-            generics: None
+            serialization: IdlSerialization::Borsh,
+            repr: None,
+            generics: vec![]
         }
     );
 }
@@ -827,67 +902,73 @@ fn name_collision() {
 
     assert_eq!(
         idl.types[0],
-        IdlTypeDefinition {
+        IdlTypeDef {
             name: "getNum_returns".to_string(),
-            docs: None,
-            ty: IdlTypeDefinitionTy::Struct {
-                fields: vec![IdlField {
+            docs: vec![],
+            ty: IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Named(vec![IdlField {
                     name: "str".to_string(),
-                    docs: None,
+                    docs: vec![],
                     ty: IdlType::String,
-                }]
+                }])),
             },
-            generics: None
+            serialization: IdlSerialization::Borsh,
+            repr: None,
+            generics: vec![]
         }
     );
 
     assert_eq!(
         idl.types[1],
-        IdlTypeDefinition {
+        IdlTypeDef {
             name: "getNum_returns_1".to_string(),
-            docs: Some(vec![
+            docs: vec![
                 "Data structure to hold the multiple returns of function getNum".to_string()
-            ]),
-            ty: IdlTypeDefinitionTy::Struct {
-                fields: vec![
+            ],
+            ty: IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Named(vec![
                     IdlField {
                         name: "ret1".to_string(),
-                        docs: None,
+                        docs: vec![],
                         ty: IdlType::U64
                     },
                     IdlField {
                         name: "ret2".to_string(),
-                        docs: None,
+                        docs: vec![],
                         ty: IdlType::U64
                     }
-                ]
+                ])),
             },
-            generics: None
+            serialization: IdlSerialization::Borsh,
+            repr: None,
+            generics: vec![]
         }
     );
 
     assert_eq!(
         idl.types[2],
-        IdlTypeDefinition {
+        IdlTypeDef {
             name: "doNotGetNum_returns".to_string(),
-            docs: Some(vec![
+            docs: vec![
                 "Data structure to hold the multiple returns of function doNotGetNum".to_string()
-            ]),
-            ty: IdlTypeDefinitionTy::Struct {
-                fields: vec![
+            ],
+            ty: IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Named(vec![
                     IdlField {
                         name: "ret3".to_string(),
-                        docs: None,
+                        docs: vec![],
                         ty: IdlType::I32
                     },
                     IdlField {
                         name: "ret4".to_string(),
-                        docs: None,
+                        docs: vec![],
                         ty: IdlType::I32
                     }
-                ]
+                ])),
             },
-            generics: None
+            serialization: IdlSerialization::Borsh,
+            repr: None,
+            generics: vec![]
         }
     );
 }
@@ -924,83 +1005,91 @@ fn double_name_collision() {
 
     assert_eq!(
         idl.types[0],
-        IdlTypeDefinition {
+        IdlTypeDef {
             name: "getNum_returns".to_string(),
-            docs: None,
-            ty: IdlTypeDefinitionTy::Struct {
-                fields: vec![IdlField {
+            docs: vec![],
+            ty: IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Named(vec![IdlField {
                     name: "str".to_string(),
-                    docs: None,
+                    docs: vec![],
                     ty: IdlType::String,
-                }]
+                }])),
             },
-            generics: None
+            serialization: IdlSerialization::Borsh,
+            repr: None,
+            generics: vec![]
         }
     );
 
     assert_eq!(
         idl.types[1],
-        IdlTypeDefinition {
+        IdlTypeDef {
             name: "getNum_returns_1".to_string(),
-            docs: None,
-            ty: IdlTypeDefinitionTy::Struct {
-                fields: vec![IdlField {
+            docs: vec![],
+            ty: IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Named(vec![IdlField {
                     name: "bt".to_string(),
-                    docs: None,
+                    docs: vec![],
                     ty: IdlType::Bytes
-                },]
+                },])),
             },
-            generics: None
+            serialization: IdlSerialization::Borsh,
+            repr: None,
+            generics: vec![]
         }
     );
 
     assert_eq!(
         idl.types[2],
-        IdlTypeDefinition {
+        IdlTypeDef {
             name: "getNum_returns_2".to_string(),
-            docs: Some(vec![
+            docs: vec![
                 "Data structure to hold the multiple returns of function getNum".to_string()
-            ]),
-            ty: IdlTypeDefinitionTy::Struct {
-                fields: vec![
+            ],
+            ty: IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Named(vec![
                     IdlField {
                         name: "ret1".to_string(),
-                        docs: None,
+                        docs: vec![],
                         ty: IdlType::U64
                     },
                     IdlField {
                         name: "ret2".to_string(),
-                        docs: None,
+                        docs: vec![],
                         ty: IdlType::U64
                     }
-                ]
+                ])),
             },
-            generics: None
+            serialization: IdlSerialization::Borsh,
+            repr: None,
+            generics: vec![]
         }
     );
 
     assert_eq!(
         idl.types[3],
-        IdlTypeDefinition {
+        IdlTypeDef {
             name: "doNotGetNum_returns".to_string(),
-            docs: Some(vec![
+            docs: vec![
                 "Data structure to hold the multiple returns of function doNotGetNum".to_string()
-            ]),
-            ty: IdlTypeDefinitionTy::Struct {
-                fields: vec![
+            ],
+            ty: IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Named(vec![
                     IdlField {
                         name: "ret3".to_string(),
-                        docs: None,
+                        docs: vec![],
                         ty: IdlType::I32
                     },
                     IdlField {
                         name: "ret4".to_string(),
-                        docs: None,
+                        docs: vec![],
                         ty: IdlType::I32
                     }
-                ]
+                ])),
             },
-            generics: None
+            serialization: IdlSerialization::Borsh,
+            repr: None,
+            generics: vec![]
         }
     );
 }
@@ -1034,81 +1123,85 @@ fn deduplication() {
         vec![
             IdlField {
                 name: "ff".to_string(),
-                docs: None,
-                ty: IdlType::PublicKey,
+                docs: vec![],
+                ty: IdlType::Pubkey,
             },
             IdlField {
                 name: "arg0".to_string(),
-                docs: None,
+                docs: vec![],
                 ty: IdlType::String,
             }
         ]
     );
 
-    assert_eq!(idl.types.len(), 1);
+    assert_eq!(idl.types.len(), 2);
     assert_eq!(
         idl.types[0],
-        IdlTypeDefinition {
+        IdlTypeDef {
             name: "myFunc_returns".to_string(),
-            docs: Some(vec![
-                "Data structure to hold the multiple returns of function myFunc".to_owned()
-            ]),
-            ty: IdlTypeDefinitionTy::Struct {
-                fields: vec![
+            docs: vec!["Data structure to hold the multiple returns of function myFunc".to_owned()],
+            ty: IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Named(vec![
                     IdlField {
                         name: "return_0".to_string(),
-                        docs: None,
-                        ty: IdlType::PublicKey
+                        docs: vec![],
+                        ty: IdlType::Pubkey
                     },
                     IdlField {
                         name: "return_0_1".to_string(),
-                        docs: None,
-                        ty: IdlType::PublicKey
+                        docs: vec![],
+                        ty: IdlType::Pubkey
                     }
-                ]
+                ])),
             },
-            generics: None
+            serialization: IdlSerialization::Borsh,
+            repr: None,
+            generics: vec![]
         }
     );
 
-    assert!(idl.events.is_some());
-    assert_eq!(idl.events.as_ref().unwrap().len(), 1);
     assert_eq!(
-        idl.events.as_ref().unwrap()[0],
-        IdlEvent {
+        idl.events,
+        vec![IdlEvent {
             name: "myEvent".to_string(),
-            fields: vec![
-                IdlEventField {
+            discriminator: event_discriminator("myEvent"),
+        }]
+    );
+    assert_eq!(
+        idl.types[1].ty,
+        IdlTypeDefTy::Struct {
+            fields: Some(IdlDefinedFields::Named(vec![
+                IdlField {
                     name: "field_0".to_string(),
+                    docs: vec![],
                     ty: IdlType::U32,
-                    index: false,
                 },
-                IdlEventField {
+                IdlField {
                     name: "field_0_1".to_string(),
+                    docs: vec![],
                     ty: IdlType::U32,
-                    index: false,
                 },
-                IdlEventField {
+                IdlField {
                     name: "field_1".to_string(),
+                    docs: vec![],
                     ty: IdlType::U32,
-                    index: false,
                 },
-                IdlEventField {
+                IdlField {
                     name: "field_0_1_1".to_string(),
+                    docs: vec![],
                     ty: IdlType::I64,
-                    index: false,
                 },
-                IdlEventField {
+                IdlField {
                     name: "field_1_1".to_string(),
+                    docs: vec![],
                     ty: IdlType::I64,
-                    index: false,
                 },
-                IdlEventField {
+                IdlField {
                     name: "field_2".to_string(),
+                    docs: vec![],
                     ty: IdlType::U128,
-                    index: false,
-                }
-            ]
+                },
+            ])),
         }
     );
 }
@@ -1409,11 +1502,7 @@ contract C {
     codegen(&mut ns, &Options::default());
     let idl = generate_anchor_idl(0, &ns, "0.1.0");
 
-    assert!(idl.metadata.is_some());
-    assert_eq!(
-        idl.metadata.unwrap(),
-        json!({"address": "Foo5mMfYo5RhRcWa4NZ2bwFn4Kdhe8rNK5jchxsKrivA"})
-    );
+    assert_eq!(idl.address, "Foo5mMfYo5RhRcWa4NZ2bwFn4Kdhe8rNK5jchxsKrivA");
 }
 
 #[test]
@@ -1433,7 +1522,7 @@ fn data_account_signer() {
 
     // implicit constructor
     assert_eq!(idl.instructions[0].name, "new");
-    assert!(idl.instructions[0].docs.is_none());
+    assert!(idl.instructions[0].docs.is_empty());
     assert_eq!(
         idl.instructions[0].accounts,
         vec![
@@ -1460,7 +1549,7 @@ fn data_account_signer() {
     assert_eq!(idl.instructions.len(), 1);
 
     assert_eq!(idl.instructions[0].name, "new");
-    assert!(idl.instructions[0].docs.is_none());
+    assert!(idl.instructions[0].docs.is_empty());
     assert_eq!(
         idl.instructions[0].accounts,
         vec![
@@ -1513,6 +1602,117 @@ fn accounts_call_chain() {
     assert_eq!(idl.instructions[3].accounts, idl.instructions[1].accounts);
 }
 
+#[test]
+fn sysvar_accounts() {
+    let src = r#"
+import "solana";
+
+contract Test {
+    function rent() public view returns (uint64) {
+        Rent memory r = sysvar.rent();
+        return r.lamports_per_byte_year;
+    }
+
+    function current() public view returns (uint16) {
+        return tx.currentIndex();
+    }
+
+    function previous() public view returns (address) {
+        return tx.instructionAt(tx.currentIndex() - 1).program_id;
+    }
+}
+    "#;
+    let mut ns = generate_namespace(src);
+    codegen(&mut ns, &Options::default());
+    let idl = generate_anchor_idl(0, &ns, "0.1.0");
+
+    assert_eq!(idl.instructions[1].name, "rent");
+    assert!(idl.instructions[1].accounts.is_empty());
+
+    assert_eq!(idl.instructions[2].name, "current");
+    assert_eq!(
+        idl.instructions[2].accounts,
+        vec![idl_account("SysvarInstruction", false, false)]
+    );
+
+    assert_eq!(idl.instructions[3].name, "previous");
+    assert_eq!(idl.instructions[3].accounts, idl.instructions[2].accounts);
+}
+
+#[test]
+fn account_constraints() {
+    let src = r#"
+contract Test {
+    address authority;
+
+    @mutableSigner(payer)
+    @mutableAccount(vault)
+    @account(authority)
+    @seeds(vault, [bytes("vault"), name], bump)
+    @initIfNeeded(vault, payer, 64)
+    @hasOne(authority)
+    function deposit(bytes name, bytes1 bump) external {
+        authority = tx.accounts.authority.key;
+    }
+
+    @account(vault)
+    @seeds(vault, [abi.encode(block.slot)], bump)
+    @owner(vault, address(this))
+    function check(bytes1 bump) external view {}
+}
+    "#;
+    let mut ns = generate_namespace(src);
+    codegen(&mut ns, &Options::default());
+    let idl = generate_anchor_idl(0, &ns, "0.1.0");
+
+    assert_eq!(idl.instructions[1].name, "deposit");
+
+    let account = |name: &str| {
+        idl.instructions[1]
+            .accounts
+            .iter()
+            .find_map(|account| match account {
+                IdlInstructionAccountItem::Single(account) if account.name == name => Some(account),
+                _ => None,
+            })
+            .unwrap()
+    };
+
+    let vault = account("vault");
+    assert_eq!(
+        vault.pda,
+        Some(IdlPda {
+            seeds: vec![
+                IdlSeed::Const(IdlSeedConst {
+                    value: b"vault".to_vec()
+                }),
+                IdlSeed::Arg(IdlSeedArg {
+                    path: "name".to_string()
+                }),
+            ],
+            program: None,
+        })
+    );
+
+    assert_eq!(
+        account("authority").relations,
+        vec!["dataAccount".to_string()]
+    );
+    assert!(account("payer").relations.is_empty());
+    assert_eq!(account("systemProgram").pda, None);
+
+    assert_eq!(idl.instructions[2].name, "check");
+    // The seed is only known at runtime
+    assert_eq!(
+        idl.instructions[2].accounts,
+        vec![
+            idl_account("vault", false, false),
+            idl_account("clock", false, false),
+            idl_account("systemProgram", false, false),
+        ]
+    );
+}
+
 #[test]
 fn accounts_on_recursion() {
     let src = r#"
@@ -2117,13 +2317,95 @@ contract Test2 {
     );
 }
 
-fn idl_account(name: &str, is_mut: bool, is_signer: bool) -> IdlAccountItem {
-    IdlAccountItem::IdlAccount(IdlAccount {
+#[test]
+fn errors() {
+    let src = r#"
+    /// @notice Caller is not allowed
+    error Unauthorized();
+
+    contract Other {
+        error NotUsed();
+    }
+
+    contract Base {
+        error Paused(bool p);
+    }
+
+    contract caller is Base {
+        error InsufficientBalance(uint64 available, uint64 required);
+
+        function withdraw(uint64 amount) public pure {
+            revert InsufficientBalance(0, amount);
+        }
+
+        function check(uint64 amount) public pure {
+            Checks.small(amount);
+        }
+    }
+
+    library Checks {
+        error Zero();
+        error TooLarge(uint64 amount);
+        error NotReverted();
+
+        modifier positive(uint64 amount) {
+            if (amount == 0) {
+                revert Zero();
+            }
+            _;
+        }
+
+        function small(uint64 amount) internal pure positive(amount) {
+            if (amount > 100) {
+                revert TooLarge(amount);
+            }
+        }
+    }"#;
+
+    let mut ns = generate_namespace(src);
+    codegen(&mut ns, &Options::default());
+    let idl = generate_anchor_idl(2, &ns, "0.1.0");
+
+    assert_eq!(
+        idl.errors,
+        vec![
+            IdlErrorCode {
+                code: 6000,
+                name: "Unauthorized".to_string(),
+                msg: Some("Caller is not allowed".to_string()),
+            },
+            IdlErrorCode {
+                code: 6001,
+                name: "Paused".to_string(),
+                msg: None,
+            },
+            IdlErrorCode {
+                code: 6002,
+                name: "InsufficientBalance".to_string(),
+                msg: None,
+            },
+            IdlErrorCode {
+                code: 6003,
+                name: "Zero".to_string(),
+                msg: None,
+            },
+            IdlErrorCode {
+                code: 6004,
+                name: "TooLarge".to_string(),
+                msg: None,
+            },
+        ]
+    );
+}
+
+fn idl_account(name: &str, writable: bool, signer: bool) -> IdlInstructionAccountItem {
+    IdlInstructionAccountItem::Single(IdlInstructionAccount {
         name: name.to_string(),
-        is_mut,
-        is_signer,
-        is_optional: Some(false),
-        docs: None,
+        docs: vec![],
+        writable,
+        signer,
+        optional: false,
+        address: None,
         pda: None,
         relations: vec![],
     })
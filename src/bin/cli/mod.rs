@@ -50,7 +50,7 @@ pub enum Commands {
 
 #[derive(Args)]
 pub struct New {
-    #[arg(name = "TARGETNAME",required= true, long = "target", value_parser = ["solana", "polkadot", "evm"], help = "Target to build for [possible values: solana, polkadot]", num_args = 1, hide_possible_values = true)]
+    #[arg(name = "TARGETNAME",required= true, long = "target", value_parser = ["solana", "polkadot", "evm"], help = "Target to build for [possible values: solana, polkadot, evm]", num_args = 1, hide_possible_values = true)]
     pub target_name: String,
 
     #[arg(name = "INPUT", help = "Name of the project", num_args = 1, value_parser = ValueParser::os_string())]
@@ -178,6 +178,14 @@ impl Compile {
                 "VERBOSE" => {
                     self.compiler_output.verbose = *matches.get_one::<bool>("VERBOSE").unwrap()
                 }
+                "STYLUS-VALIDATE" => {
+                    self.compiler_output.stylus_validate =
+                        *matches.get_one::<bool>("STYLUS-VALIDATE").unwrap()
+                }
+                "STYLUS-ARTIFACTS" => {
+                    self.compiler_output.stylus_artifacts =
+                        *matches.get_one::<bool>("STYLUS-ARTIFACTS").unwrap()
+                }
 
                 // DebugFeatures args
                 "NOLOGRUNTIMEERRORS" => {
@@ -257,11 +265,19 @@ pub struct CompilerOutput {
     #[arg(name = "VERBOSE" ,help = "show debug messages", short = 'v', action = ArgAction::SetTrue, long = "verbose")]
     #[serde(default)]
     pub verbose: bool,
+
+    #[arg(name = "STYLUS-VALIDATE", help = "Check Stylus programs against the activation rules", action = ArgAction::SetTrue, long = "stylus-validate")]
+    #[serde(default)]
+    pub stylus_validate: bool,
+
+    #[arg(name = "STYLUS-ARTIFACTS", help = "Validate Stylus programs and emit their compressed code and deployment init code", action = ArgAction::SetTrue, long = "stylus-artifacts")]
+    #[serde(default)]
+    pub stylus_artifacts: bool,
 }
 
 #[derive(Args)]
 pub struct TargetArg {
-    #[arg(name = "TARGET",required= true, long = "target", value_parser = ["solana", "polkadot", "evm"], help = "Target to build for [possible values: solana, polkadot]", num_args = 1, hide_possible_values = true)]
+    #[arg(name = "TARGET",required= true, long = "target", value_parser = ["solana", "polkadot", "evm", "polkavm"], help = "Target to build for [possible values: solana, polkadot, evm, polkavm]", num_args = 1, hide_possible_values = true)]
     pub name: String,
 
     #[arg(name = "ADDRESS_LENGTH", help = "Address length on the Polkadot Parachain", long = "address-length", num_args = 1, value_parser = value_parser!(u64).range(4..1024))]
@@ -273,7 +289,7 @@ pub struct TargetArg {
 
 #[derive(Args, Deserialize, Debug, PartialEq)]
 pub struct CompileTargetArg {
-    #[arg(name = "TARGET", long = "target", value_parser = ["solana", "polkadot", "evm", "soroban", "stylus"], help = "Target to build for [possible values: solana, polkadot, stylus]", num_args = 1, hide_possible_values = true)]
+    #[arg(name = "TARGET", long = "target", value_parser = ["solana", "polkadot", "evm", "soroban", "stylus", "polkavm"], help = "Target to build for [possible values: solana, polkadot, evm, stylus, polkavm]", num_args = 1, hide_possible_values = true)]
     pub name: Option<String>,
 
     #[arg(name = "ADDRESS_LENGTH", help = "Address length on the Polkadot Parachain", long = "address-length", num_args = 1, value_parser = value_parser!(u64).range(4..1024))]
@@ -449,7 +465,7 @@ impl TargetArgTrait for CompileTargetArg {
 pub(crate) fn target_arg<T: TargetArgTrait>(target_arg: &T) -> Target {
     let target_name = target_arg.get_name();
 
-    if target_name == "solana" || target_name == "evm" {
+    if target_name == "solana" || target_name == "evm" || target_name == "polkavm" {
         if target_arg.get_address_length().is_some() {
             eprintln!("error: address length cannot be modified except for polkadot target");
             exit(1);
@@ -470,6 +486,7 @@ pub(crate) fn target_arg<T: TargetArgTrait>(target_arg: &T) -> Target {
         "evm" => solang::Target::EVM,
         "soroban" => solang::Target::Soroban,
         "stylus" => solang::Target::Stylus,
+        "polkavm" => solang::Target::PolkaVM,
         _ => unreachable!(),
     };
 
@@ -203,7 +203,9 @@ mod tests {
                     std_json_output: false,
                     output_directory: None,
                     output_meta: None,
-                    verbose: false
+                    verbose: false,
+                    stylus_validate: false,
+                    stylus_artifacts: false
                 },
                 target_arg: cli::CompileTargetArg {
                     name: Some("solana".to_owned()),
@@ -259,7 +261,9 @@ mod tests {
                     std_json_output: false,
                     output_directory: None,
                     output_meta: None,
-                    verbose: false
+                    verbose: false,
+                    stylus_validate: false,
+                    stylus_artifacts: false
                 },
                 target_arg: cli::CompileTargetArg {
                     name: Some("polkadot".to_owned()),
@@ -1,12 +1,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::cli::IdlCommand;
-use anchor_syn::idl::types::{Idl, IdlAccountItem, IdlInstruction, IdlType, IdlTypeDefinitionTy};
+use anchor_lang_idl::convert::convert_idl;
+use anchor_lang_idl::types::{
+    Idl, IdlArrayLen, IdlDefinedFields, IdlField, IdlInstruction, IdlInstructionAccountItem,
+    IdlType, IdlTypeDefTy,
+};
 use itertools::Itertools;
 use serde_json::Value as JsonValue;
-use solang::abi::anchor::function_discriminator;
 use solang_parser::lexer::is_keyword;
-use std::{ffi::OsStr, fs::File, io::Write, path::PathBuf, process::exit};
+use std::{
+    collections::HashSet, ffi::OsStr, fs, fs::File, io::Write, path::PathBuf, process::exit,
+};
+
+/// The event fields which are indexed, by event and field name
+type IndexedFields = HashSet<(String, String)>;
 
 /// This subcommand generates a Solidity interface file from Anchor IDL file.
 /// The IDL file is json and lists all the instructions, events, structs, enums,
@@ -18,7 +26,7 @@ pub fn idl(idl_args: &IdlCommand) {
 }
 
 fn idl_file(file: &OsStr, output: &Option<PathBuf>) {
-    let f = match File::open(file) {
+    let contents = match fs::read(file) {
         Ok(s) => s,
         Err(e) => {
             eprintln!("{}: error: {}", file.to_string_lossy(), e);
@@ -26,7 +34,7 @@ fn idl_file(file: &OsStr, output: &Option<PathBuf>) {
         }
     };
 
-    let idl: Idl = match serde_json::from_reader(f) {
+    let (idl, indexed_fields) = match parse_idl(&contents) {
         Ok(idl) => idl,
         Err(e) => {
             eprintln!("{}: error: {}", file.to_string_lossy(), e);
@@ -34,7 +42,7 @@ fn idl_file(file: &OsStr, output: &Option<PathBuf>) {
         }
     };
 
-    let filename = format!("{}.sol", idl.name);
+    let filename = format!("{}.sol", idl.metadata.name);
 
     let path = if let Some(base) = output {
         base.join(filename)
@@ -56,29 +64,102 @@ fn idl_file(file: &OsStr, output: &Option<PathBuf>) {
         }
     };
 
-    if let Err(e) = write_solidity(&idl, f) {
+    if let Err(e) = write_solidity(&idl, &indexed_fields, f) {
         eprintln!("{}: error: {}", path.display(), e);
         exit(1);
     }
 }
 
-fn write_solidity(idl: &Idl, mut f: File) -> Result<(), std::io::Error> {
+/// Parse an IDL file in the format of Anchor v0.30 and later. Older IDL files are converted to the
+/// new format; they only list the address of the program if it is known, so an empty address is
+/// assumed otherwise. Only older IDL files record which event fields are indexed.
+fn parse_idl(contents: &[u8]) -> Result<(Idl, IndexedFields), String> {
+    let mut value: JsonValue = serde_json::from_slice(contents).map_err(|e| e.to_string())?;
+    let mut legacy = false;
+
+    if let Some(idl) = value.as_object_mut() {
+        let metadata = idl
+            .entry("metadata")
+            .or_insert_with(|| JsonValue::Object(Default::default()));
+
+        if let Some(metadata) = metadata.as_object_mut() {
+            legacy = !metadata.contains_key("spec");
+
+            if legacy && !metadata.contains_key("address") {
+                metadata.insert("address".into(), JsonValue::String(String::new()));
+            }
+        }
+    }
+
+    let idl = convert_idl(value.to_string().as_bytes()).map_err(|e| e.to_string())?;
+
+    let indexed_fields = if legacy {
+        legacy_indexed_fields(&value, &idl)
+    } else {
+        IndexedFields::new()
+    };
+
+    Ok((idl, indexed_fields))
+}
+
+/// The conversion from the legacy format drops the `index` flag of event fields, so collect the
+/// indexed fields from the legacy events.
+fn legacy_indexed_fields(value: &JsonValue, idl: &Idl) -> IndexedFields {
+    let mut indexed_fields = IndexedFields::new();
+
+    let Some(events) = value.get("events").and_then(JsonValue::as_array) else {
+        return indexed_fields;
+    };
+
+    for event in events {
+        let Some(name) = event.get("name").and_then(JsonValue::as_str) else {
+            continue;
+        };
+
+        let Some(IdlTypeDefTy::Struct {
+            fields: Some(IdlDefinedFields::Named(fields)),
+        }) = idl
+            .types
+            .iter()
+            .find(|ty_def| ty_def.name == name)
+            .map(|ty_def| &ty_def.ty)
+        else {
+            continue;
+        };
+
+        let legacy_fields = event
+            .get("fields")
+            .and_then(JsonValue::as_array)
+            .into_iter()
+            .flatten();
+
+        // the fields are converted in order
+        for (field, legacy_field) in fields.iter().zip(legacy_fields) {
+            if legacy_field.get("index").and_then(JsonValue::as_bool) == Some(true) {
+                indexed_fields.insert((name.to_string(), field.name.clone()));
+            }
+        }
+    }
+
+    indexed_fields
+}
+
+fn write_solidity(
+    idl: &Idl,
+    indexed_fields: &IndexedFields,
+    mut f: File,
+) -> Result<(), std::io::Error> {
+    // events are listed as types too
     let mut ty_names = idl
         .types
         .iter()
         .map(|ty| (ty.name.to_string(), ty.name.to_string()))
         .collect::<Vec<(String, String)>>();
 
-    if let Some(events) = &idl.events {
-        events
-            .iter()
-            .for_each(|event| ty_names.push((event.name.to_string(), event.name.to_string())));
-    }
-
     rename_keywords(&mut ty_names);
 
     for ty_def in &idl.types {
-        if let IdlTypeDefinitionTy::Enum { variants } = &ty_def.ty {
+        if let IdlTypeDefTy::Enum { variants } = &ty_def.ty {
             if variants.iter().any(|variant| variant.fields.is_some()) {
                 eprintln!(
                     "enum {} has variants with fields, not supported in Solidity\n",
@@ -115,7 +196,19 @@ fn write_solidity(idl: &Idl, mut f: File) -> Result<(), std::io::Error> {
     }
 
     for ty_def in &idl.types {
-        if let IdlTypeDefinitionTy::Struct { fields } = &ty_def.ty {
+        if idl.events.iter().any(|event| event.name == ty_def.name) {
+            continue;
+        }
+
+        if let IdlTypeDefTy::Struct { fields } = &ty_def.ty {
+            let Some(fields) = named_fields(fields) else {
+                eprintln!(
+                    "struct {} has unnamed fields, not supported in Solidity",
+                    ty_def.name
+                );
+                continue;
+            };
+
             let badtys: Vec<String> = fields
                 .iter()
                 .filter_map(|field| idltype_to_solidity(&field.ty, &ty_names).err())
@@ -157,57 +250,74 @@ fn write_solidity(idl: &Idl, mut f: File) -> Result<(), std::io::Error> {
         }
     }
 
-    if let Some(events) = &idl.events {
-        for event in events {
-            let badtys: Vec<String> = event
-                .fields
-                .iter()
-                .filter_map(|field| idltype_to_solidity(&field.ty, &ty_names).err())
-                .collect();
+    for event in &idl.events {
+        let fields = idl
+            .types
+            .iter()
+            .find(|ty_def| ty_def.name == event.name)
+            .and_then(|ty_def| match &ty_def.ty {
+                IdlTypeDefTy::Struct { fields } => named_fields(fields),
+                _ => None,
+            });
+
+        let Some(fields) = fields else {
+            eprintln!(
+                "event {} has no type with named fields, not supported in Solidity",
+                event.name
+            );
+            continue;
+        };
+
+        let badtys: Vec<String> = fields
+            .iter()
+            .filter_map(|field| idltype_to_solidity(&field.ty, &ty_names).err())
+            .collect();
 
-            if badtys.is_empty() {
-                let mut name_map = event
-                    .fields
-                    .iter()
-                    .map(|field| (field.name.to_string(), field.name.to_string()))
-                    .collect::<Vec<(String, String)>>();
+        if badtys.is_empty() {
+            let mut name_map = fields
+                .iter()
+                .map(|field| (field.name.to_string(), field.name.to_string()))
+                .collect::<Vec<(String, String)>>();
 
-                rename_keywords(&mut name_map);
+            rename_keywords(&mut name_map);
 
-                let name = &ty_names.iter().find(|e| *e.0 == event.name).unwrap().1;
+            let name = &ty_names.iter().find(|e| *e.0 == event.name).unwrap().1;
 
-                writeln!(f, "event {name} (")?;
-                let mut iter = event.fields.iter().enumerate();
-                let mut next = iter.next();
-                while let Some((no, e)) = next {
-                    next = iter.next();
+            writeln!(f, "event {name} (")?;
+            let mut iter = fields.iter().enumerate();
+            let mut next = iter.next();
+            while let Some((no, e)) = next {
+                next = iter.next();
 
-                    writeln!(
-                        f,
-                        "\t{}\t{}{}{}",
-                        idltype_to_solidity(&e.ty, &ty_names).unwrap(),
-                        if e.index { " indexed " } else { " " },
-                        name_map[no].1,
-                        if next.is_some() { "," } else { "" }
-                    )?;
-                }
-                writeln!(f, ");")?;
-            } else {
-                eprintln!(
-                    "event {} has fields of type {} which is not supported on Solidity",
-                    event.name,
-                    badtys.join(", ")
-                );
+                writeln!(
+                    f,
+                    "\t{}{}{}{}",
+                    idltype_to_solidity(&e.ty, &ty_names).unwrap(),
+                    if indexed_fields.contains(&(event.name.clone(), e.name.clone())) {
+                        " indexed\t"
+                    } else {
+                        "\t"
+                    },
+                    name_map[no].1,
+                    if next.is_some() { "," } else { "" }
+                )?;
             }
+            writeln!(f, ");")?;
+        } else {
+            eprintln!(
+                "event {} has fields of type {} which is not supported on Solidity",
+                event.name,
+                badtys.join(", ")
+            );
         }
     }
 
     docs(&mut f, 0, &idl.docs)?;
 
-    if let Some(program_id) = program_id(idl) {
-        writeln!(f, "@program_id(\"{program_id}\")")?;
+    if !idl.address.is_empty() {
+        writeln!(f, "@program_id(\"{}\")", idl.address)?;
     }
-    writeln!(f, "interface {} {{", idl.name)?;
+    writeln!(f, "interface {} {{", idl.metadata.name)?;
 
     let mut instruction_names = idl
         .instructions
@@ -254,12 +364,14 @@ fn instruction(
             .1;
 
         // The anchor discriminator is what Solidity calls a selector
-        let selector = function_discriminator(&instr.name);
-
         write!(
             f,
             "\t@selector([{}])\n\tfunction {}(",
-            selector.iter().map(|v| format!("{v:#04x}")).join(","),
+            instr
+                .discriminator
+                .iter()
+                .map(|v| format!("{v:#04x}"))
+                .join(","),
             if instr.name == "new" {
                 "initialize"
             } else {
@@ -305,21 +417,28 @@ fn instruction(
     Ok(())
 }
 
-fn mutable_account_exists(accounts: &[IdlAccountItem]) -> bool {
+fn mutable_account_exists(accounts: &[IdlInstructionAccountItem]) -> bool {
     accounts.iter().any(|item| match item {
-        IdlAccountItem::IdlAccount(acc) => acc.is_mut,
-        IdlAccountItem::IdlAccounts(accs) => mutable_account_exists(&accs.accounts),
+        IdlInstructionAccountItem::Single(acc) => acc.writable,
+        IdlInstructionAccountItem::Composite(accs) => mutable_account_exists(&accs.accounts),
     })
 }
 
-fn docs(f: &mut File, indent: usize, docs: &Option<Vec<String>>) -> std::io::Result<()> {
-    if let Some(docs) = docs {
-        for doc in docs {
-            for _ in 0..indent {
-                write!(f, "\t")?;
-            }
-            writeln!(f, "/// {doc}")?;
+/// The fields of a struct, if they have names. A struct without fields has no field list at all.
+fn named_fields(fields: &Option<IdlDefinedFields>) -> Option<&[IdlField]> {
+    match fields {
+        None => Some(&[]),
+        Some(IdlDefinedFields::Named(fields)) => Some(fields),
+        Some(IdlDefinedFields::Tuple(_)) => None,
+    }
+}
+
+fn docs(f: &mut File, indent: usize, docs: &[String]) -> std::io::Result<()> {
+    for doc in docs {
+        for _ in 0..indent {
+            write!(f, "\t")?;
         }
+        writeln!(f, "/// {doc}")?;
     }
 
     Ok(())
@@ -344,7 +463,7 @@ fn idltype_to_solidity(ty: &IdlType, ty_names: &[(String, String)]) -> Result<St
         IdlType::F64 => Err("f64".to_string()),
         IdlType::Bytes => Ok("bytes".to_string()),
         IdlType::String => Ok("string".to_string()),
-        IdlType::PublicKey => Ok("address".to_string()),
+        IdlType::Pubkey => Ok("address".to_string()),
         IdlType::Option(ty) => Err(format!(
             "Option({})",
             match idltype_to_solidity(ty, ty_names) {
@@ -352,37 +471,25 @@ fn idltype_to_solidity(ty: &IdlType, ty_names: &[(String, String)]) -> Result<St
                 Err(ty) => ty,
             }
         )),
-        IdlType::Defined(ty) => {
-            if let Some(e) = ty_names.iter().find(|rename| rename.0 == *ty) {
+        IdlType::Defined { name, generics } if generics.is_empty() => {
+            if let Some(e) = ty_names.iter().find(|rename| rename.0 == *name) {
                 Ok(e.1.clone())
             } else {
-                Ok(ty.into())
+                Ok(name.into())
             }
         }
         IdlType::Vec(ty) => match idltype_to_solidity(ty, ty_names) {
             Ok(ty) => Ok(format!("{ty}[]")),
             Err(ty) => Err(format!("{ty}[]")),
         },
-        IdlType::Array(ty, size) => match idltype_to_solidity(ty, ty_names) {
+        IdlType::Array(ty, IdlArrayLen::Value(size)) => match idltype_to_solidity(ty, ty_names) {
             Ok(ty) => Ok(format!("{ty}[{size}]")),
             Err(ty) => Err(format!("{ty}[{size}]")),
         },
-        IdlType::Generic(..)
-        | IdlType::GenericLenArray(..)
-        | IdlType::DefinedWithTypeArgs { .. } => Err("generics are not supported".into()),
+        _ => Err("generics are not supported".into()),
     }
 }
 
-fn program_id(idl: &Idl) -> Option<&String> {
-    if let Some(JsonValue::Object(metadata)) = &idl.metadata {
-        if let Some(JsonValue::String(address)) = metadata.get("address") {
-            return Some(address);
-        }
-    }
-
-    None
-}
-
 /// There are many keywords in Solidity which are not keywords in Rust, so they may
 /// occur as field name, function name, etc. Rename those fields by prepending
 /// underscores until unique
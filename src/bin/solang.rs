@@ -8,10 +8,13 @@ use itertools::Itertools;
 use solang::{
     abi,
     codegen::{codegen, Options},
-    emit::Generate,
+    emit::{evm::EvmCode, Generate},
     file_resolver::FileResolver,
-    sema::{ast::Namespace, file::PathDisplay},
-    standard_json::{EwasmContract, JsonContract, JsonResult},
+    sema::{
+        ast::{Diagnostic, Namespace},
+        file::PathDisplay,
+    },
+    standard_json::{EvmBytecode, EvmContract, EwasmContract, JsonContract, JsonResult},
 };
 use std::{
     collections::{HashMap, HashSet},
@@ -96,10 +99,7 @@ fn new_command(args: New) {
     let flipper = match target {
         "solana" => include_str!("../../examples/solana/flipper.sol"),
         "polkadot" => include_str!("../../examples/polkadot/flipper.sol"),
-        "evm" => {
-            eprintln!("EVM target is not supported yet!");
-            exit(1);
-        }
+        "evm" => include_str!("../../examples/evm/flipper.sol"),
         _ => unreachable!(),
     };
 
@@ -113,6 +113,7 @@ fn new_command(args: New) {
     let toml_content = match target {
         "solana" => include_str!("../../examples/solana/solana_config.toml"),
         "polkadot" => include_str!("../../examples/polkadot/polkadot_config.toml"),
+        "evm" => include_str!("../../examples/evm/evm_config.toml"),
         _ => unreachable!(),
     };
     toml_file
@@ -186,6 +187,8 @@ fn compile(compile_args: &Compile) {
 
     let mut namespaces = Vec::new();
 
+    let mut evm_codes = Vec::new();
+
     let mut errors = false;
 
     // Build a map of requested contract names, and a flag specifying whether it was found or not
@@ -197,7 +200,7 @@ fn compile(compile_args: &Compile) {
 
     for filename in compile_args.package.get_input() {
         // TODO: this could be parallelized using e.g. rayon
-        let ns = process_file(
+        let (ns, evm_code) = process_file(
             filename,
             &mut resolver,
             target,
@@ -206,6 +209,7 @@ fn compile(compile_args: &Compile) {
         );
 
         namespaces.push(ns);
+        evm_codes.push(evm_code);
     }
 
     let mut json_contracts = HashMap::new();
@@ -269,12 +273,13 @@ fn compile(compile_args: &Compile) {
             "0.0.1"
         };
 
-        for ns in &mut namespaces {
+        for (ns, evm_code) in namespaces.iter_mut().zip(&mut evm_codes) {
             for contract_no in 0..ns.contracts.len() {
                 contract_results(
                     contract_no,
                     &compile_args.compiler_output,
                     ns,
+                    evm_code,
                     &mut json_contracts,
                     &mut seen_contracts,
                     &opt,
@@ -318,7 +323,7 @@ fn process_file(
     target: solang::Target,
     compiler_output: &CompilerOutput,
     opt: &Options,
-) -> Namespace {
+) -> (Namespace, HashMap<usize, EvmCode>) {
     let verbose = compiler_output.verbose;
 
     let filepath = match filename.canonicalize() {
@@ -333,6 +338,78 @@ fn process_file(
     // codegen all the contracts; some additional errors/warnings will be detected here
     codegen(&mut ns, opt);
 
+    let mut evm_code = HashMap::new();
+
+    // EVM bytecode is generated without LLVM, so errors like exceeding the code size limit
+    // can be reported before any output is written. The code is kept for writing the output.
+    if ns.target == solang::Target::EVM
+        && !matches!(compiler_output.emit.as_deref(), Some("ast-dot" | "cfg"))
+        && !ns.diagnostics.any_errors()
+    {
+        let mut errors = Vec::new();
+
+        for contract_no in 0..ns.contracts.len() {
+            if ns.contracts[contract_no].instantiable {
+                if verbose {
+                    eprintln!(
+                        "info: Generating EVM bytecode for contract {}",
+                        ns.contracts[contract_no].id
+                    );
+                }
+
+                match solang::emit::evm::build(contract_no, &ns, opt) {
+                    Ok(code) => {
+                        evm_code.insert(contract_no, code);
+                    }
+                    Err(mut diagnostics) => errors.append(&mut diagnostics),
+                }
+            }
+        }
+
+        ns.diagnostics.append(&mut errors);
+        ns.diagnostics.sort_and_dedup();
+    }
+
+    // Link the contracts before any output is written, so that link errors and programs which
+    // Stylus would refuse to activate are reported like any other error
+    if ns.target != solang::Target::EVM
+        && compiler_output.emit.is_none()
+        && !ns.diagnostics.any_errors()
+    {
+        let mut errors = Vec::new();
+
+        for (contract_no, contract) in ns.contracts.iter().enumerate() {
+            if contract.instantiable && contract.loc.file_no() == ns.top_file_no() {
+                match contract.try_emit(&ns, opt, contract_no) {
+                    Ok(code) => {
+                        if ns.target == solang::Target::Stylus
+                            && (compiler_output.stylus_validate || compiler_output.stylus_artifacts)
+                        {
+                            if verbose {
+                                eprintln!(
+                                    "info: Validating Stylus program for contract {}",
+                                    contract.id
+                                );
+                            }
+
+                            if let Err(messages) = solang::emit::stylus::validate::validate(&code) {
+                                errors.extend(
+                                    messages
+                                        .into_iter()
+                                        .map(|message| Diagnostic::error(contract.loc, message)),
+                                );
+                            }
+                        }
+                    }
+                    Err(message) => errors.push(Diagnostic::error(contract.loc, message)),
+                }
+            }
+        }
+
+        ns.diagnostics.append(&mut errors);
+        ns.diagnostics.sort_and_dedup();
+    }
+
     if let Some("ast-dot") = compiler_output.emit.as_deref() {
         let stem = filepath.file_stem().unwrap().to_string_lossy();
         let dot_filename = output_file(compiler_output, &stem, "dot", false);
@@ -351,13 +428,14 @@ fn process_file(
         }
     }
 
-    ns
+    (ns, evm_code)
 }
 
 fn contract_results(
     contract_no: usize,
     compiler_output: &CompilerOutput,
     ns: &mut Namespace,
+    evm_code: &mut HashMap<usize, EvmCode>,
     json_contracts: &mut HashMap<String, JsonContract>,
     seen_contracts: &mut HashMap<String, String>,
     opt: &Options,
@@ -398,6 +476,14 @@ fn contract_results(
         return;
     }
 
+    if ns.target == solang::Target::EVM {
+        // every instantiable contract was built when the file was processed
+        if let Some(code) = evm_code.remove(&contract_no) {
+            evm_contract_results(contract_no, code, compiler_output, ns, json_contracts);
+        }
+        return;
+    }
+
     if verbose {
         if ns.target == solang::Target::Solana {
             eprintln!(
@@ -412,18 +498,27 @@ fn contract_results(
         );
     }
 
-    let context = inkwell::context::Context::create();
+    if compiler_output.emit.is_some() {
+        let context = inkwell::context::Context::create();
 
-    let bin = resolved_contract.binary(ns, &context, opt, contract_no);
+        let bin = resolved_contract.binary(ns, &context, opt, contract_no);
+
+        save_intermediates(&bin, compiler_output);
 
-    if save_intermediates(&bin, compiler_output) {
         return;
     }
 
-    let code = bin.code(Generate::Linked).expect("llvm build");
+    let name = &resolved_contract.id.name;
+
+    let code = resolved_contract
+        .try_emit(ns, opt, contract_no)
+        .expect("link errors are reported when the file is processed");
 
     #[cfg(feature = "wasm_opt")]
-    if let Some(level) = opt.wasm_opt.filter(|_| ns.target.is_polkadot() && verbose) {
+    if let Some(level) = opt
+        .wasm_opt
+        .filter(|_| matches!(ns.target, solang::Target::Polkadot { .. }) && verbose)
+    {
         eprintln!(
             "info: wasm-opt level '{}' for contract {}",
             level, resolved_contract.id
@@ -432,28 +527,24 @@ fn contract_results(
 
     if std_json {
         json_contracts.insert(
-            bin.name,
+            name.to_owned(),
             JsonContract {
                 abi: abi::ethereum::gen_abi(contract_no, ns),
                 ewasm: Some(EwasmContract {
                     wasm: hex::encode_upper(code),
                 }),
+                evm: None,
                 minimum_space: None,
             },
         );
     } else {
-        let bin_filename = output_file(
-            compiler_output,
-            &bin.name,
-            ns.target.file_extension(),
-            false,
-        );
+        let bin_filename = output_file(compiler_output, name, ns.target.file_extension(), false);
 
         if verbose {
             eprintln!(
                 "info: Saving binary {} for contract {}",
                 bin_filename.display(),
-                bin.name
+                name
             );
         }
 
@@ -461,15 +552,19 @@ fn contract_results(
 
         file.write_all(&code).unwrap();
 
+        if ns.target == solang::Target::Stylus && compiler_output.stylus_artifacts {
+            stylus_artifacts(name, &code, compiler_output);
+        }
+
         let (metadata, meta_ext) =
             abi::generate_abi(contract_no, ns, &code, verbose, default_authors, version);
-        let meta_filename = output_file(compiler_output, &bin.name, meta_ext, true);
+        let meta_filename = output_file(compiler_output, name, meta_ext, true);
 
         if verbose {
             eprintln!(
                 "info: Saving metadata {} for contract {}",
                 meta_filename.display(),
-                bin.name
+                name
             );
         }
 
@@ -478,10 +573,97 @@ fn contract_results(
     }
 }
 
-fn save_intermediates(
-    bin: &solang::emit::binary::Binary,
+/// EVM code is generated without LLVM. The creation and runtime code are written as hex, next
+/// to the Ethereum ABI.
+fn evm_contract_results(
+    contract_no: usize,
+    code: EvmCode,
     compiler_output: &CompilerOutput,
-) -> bool {
+    ns: &Namespace,
+    json_contracts: &mut HashMap<String, JsonContract>,
+) {
+    let verbose = compiler_output.verbose;
+    let name = &ns.contracts[contract_no].id.name;
+
+    if compiler_output.std_json_output {
+        json_contracts.insert(
+            name.to_owned(),
+            JsonContract {
+                abi: abi::ethereum::gen_abi(contract_no, ns),
+                ewasm: None,
+                evm: Some(EvmContract {
+                    bytecode: EvmBytecode {
+                        object: hex::encode(&code.creation),
+                    },
+                    deployed_bytecode: EvmBytecode {
+                        object: hex::encode(&code.runtime),
+                    },
+                }),
+                minimum_space: None,
+            },
+        );
+        return;
+    }
+
+    for (code, ext) in [
+        (&code.creation, ns.target.file_extension()),
+        (&code.runtime, "bin-runtime"),
+    ] {
+        let filename = output_file(compiler_output, name, ext, false);
+
+        if verbose {
+            eprintln!(
+                "info: Saving binary {} for contract {name}",
+                filename.display()
+            );
+        }
+
+        let mut file = create_file(&filename);
+        file.write_all(hex::encode(code).as_bytes()).unwrap();
+    }
+
+    let (abi, abi_ext) = abi::generate_abi(contract_no, ns, &code.runtime, verbose, &[], "");
+    let abi_filename = output_file(compiler_output, name, abi_ext, true);
+
+    if verbose {
+        eprintln!(
+            "info: Saving metadata {} for contract {name}",
+            abi_filename.display()
+        );
+    }
+
+    let mut file = create_file(&abi_filename);
+    file.write_all(abi.as_bytes()).unwrap();
+}
+
+/// Stylus programs are deployed Brotli-compressed, wrapped in EVM init code which returns them.
+fn stylus_artifacts(name: &str, code: &[u8], compiler_output: &CompilerOutput) {
+    let (_, compressed) =
+        solang::emit::stylus::cargo_stylus::compress_wasm(code).unwrap_or_else(|err| {
+            eprintln!("error: contract {name}: {err:#}");
+            exit(1);
+        });
+    let deploy = solang::emit::stylus::cargo_stylus::contract_deployment_calldata(&compressed);
+
+    for (contents, ext) in [
+        (compressed, "compressed.wasm"),
+        (hex::encode(deploy).into_bytes(), "deploy.hex"),
+    ] {
+        let filename = output_file(compiler_output, name, ext, false);
+
+        if compiler_output.verbose {
+            eprintln!(
+                "info: Saving Stylus artifact {} for contract {name}",
+                filename.display()
+            );
+        }
+
+        let mut file = create_file(&filename);
+        file.write_all(&contents).unwrap();
+    }
+}
+
+fn save_intermediates(bin: &solang::emit::binary::Binary, compiler_output: &CompilerOutput) {
     let verbose = compiler_output.verbose;
 
     match compiler_output.emit.as_deref() {
@@ -497,8 +679,6 @@ fn save_intermediates(
             }
 
             bin.dump_llvm(&llvm_filename).unwrap();
-
-            true
         }
 
         Some("llvm-bc") => {
@@ -513,8 +693,6 @@ fn save_intermediates(
             }
 
             bin.bitcode(&bc_filename);
-
-            true
         }
 
         Some("object") => {
@@ -538,7 +716,6 @@ fn save_intermediates(
 
             let mut file = create_file(&obj_filename);
             file.write_all(&obj).unwrap();
-            true
         }
         Some("asm") => {
             let obj = match bin.code(Generate::Assembly) {
@@ -561,11 +738,8 @@ fn save_intermediates(
 
             let mut file = create_file(&obj_filename);
             file.write_all(&obj).unwrap();
-            true
         }
-        Some("cfg") => true,
-        Some("ast-dot") => true,
-        _ => false,
+        _ => (),
     }
 }
 
@@ -8,12 +8,15 @@ use super::{
     vartable::{Vars, Vartable},
     vector_to_slice, Options,
 };
+use crate::codegen::solana_accounts::account_constraints::account_constraints;
+use crate::codegen::solana_accounts::rent::top_up_rent;
 use crate::codegen::subexpression_elimination::common_sub_expression_elimination;
 use crate::codegen::{undefined_variable, Expression, LLVMName};
 use crate::sema::ast::{
     CallTy, Contract, ExternalCallAccounts, FunctionAttributes, Namespace, Parameter, RetrieveType,
     Statement, StringLocation, StructType, Type,
 };
+use crate::sema::solana_accounts::BuiltinAccounts;
 use crate::sema::{contracts::collect_base_args, diagnostics::Diagnostics, Recurse};
 use crate::{sema::ast, Target};
 use indexmap::IndexMap;
@@ -213,6 +216,9 @@ pub enum ReturnCode {
     InvalidDataError,
     AccountDataTooSmall,
     InvalidProgramId,
+    /// A custom error code, which Solana reports as `ProgramError::Custom`
+    #[display("custom error {0}")]
+    CustomError(u32),
 }
 
 impl Instr {
@@ -1036,9 +1042,15 @@ impl ControlFlowGraph {
                 true_block,
                 false_block,
             ),
-            Instr::LoadStorage { ty, res, storage, .. } => format!(
-                "%{} = load storage slot({}) ty:{}",
+            Instr::LoadStorage {
+                ty,
+                res,
+                storage,
+                storage_type,
+            } => format!(
+                "%{} = load {} slot({}) ty:{}",
                 self.vars[res].id.name,
+                storage_kind(storage_type),
                 self.expr_to_string(contract, ns, storage),
                 ty.to_string(ns),
             ),
@@ -1047,8 +1059,14 @@ impl ControlFlowGraph {
                 self.expr_to_string(contract, ns, storage),
                 ty.to_string(ns),
             ),
-            Instr::SetStorage { ty, value, storage, .. } => format!(
-                "store storage slot({}) ty:{} = {}",
+            Instr::SetStorage {
+                ty,
+                value,
+                storage,
+                storage_type,
+            } => format!(
+                "store {} slot({}) ty:{} = {}",
+                storage_kind(storage_type),
                 self.expr_to_string(contract, ns, storage),
                 ty.to_string(ns),
                 self.expr_to_string(contract, ns, value),
@@ -1443,6 +1461,14 @@ impl ControlFlowGraph {
     }
 }
 
+/// Name of the storage accessed by a storage instruction, for printing the cfg
+fn storage_kind(storage_type: &Option<pt::StorageType>) -> &'static str {
+    match storage_type {
+        Some(pt::StorageType::Transient(_)) => "transient",
+        _ => "storage",
+    }
+}
+
 /// Checks whether there is a virtual fallback or receive function
 fn is_there_virtual_function(
     ns: &Namespace,
@@ -1589,7 +1615,8 @@ pub fn optimize_and_check_cfg(
     // do not depend which passes are enabled. If the constant_folding is not enabled, run it
     // dry mode.
     constant_folding::constant_folding(cfg, !opt.constant_folding, ns);
-    if opt.vector_to_slice {
+    // EVM vectors are not copied when they are read, and EVM slices are not vectors
+    if opt.vector_to_slice && ns.target != Target::EVM {
         vector_to_slice::vector_to_slice(cfg, ns);
     }
     if opt.strength_reduce {
@@ -1672,6 +1699,10 @@ fn function_cfg(
     // populate the argument variables
     populate_arguments(func, &mut cfg, &mut vartab);
 
+    if ns.target == Target::Solana && !func.account_constraints.is_empty() {
+        account_constraints(func, contract_no, &mut cfg, &mut vartab, ns, opt);
+    }
+
     // Hold your breath, this is the trickest part of the codegen ahead.
     // For each contract, the top-level constructor calls the base constructors. The base
     // constructors do not call their base constructors; everything is called from the top
@@ -1839,6 +1870,20 @@ fn function_cfg(
         );
     }
 
+    if ns.target == Target::Solana && !func.is_constructor() {
+        if let Some((_, payer)) = &func.annotations.payer {
+            let writes_storage = func
+                .solana_accounts
+                .borrow()
+                .get(BuiltinAccounts::DataAccount.as_str())
+                .is_some_and(|account| account.is_writer);
+
+            if writes_storage {
+                top_up_rent(payer, &mut cfg, &mut vartab);
+            }
+        }
+    }
+
     vartab.finalize(ns, &mut cfg);
 
     // walk cfg to check for use for before initialize
@@ -2121,6 +2166,7 @@ impl Contract {
         if let Some(layout) = self
             .layout
             .iter()
+            .chain(self.transient_layout.iter())
             .find(|l| l.contract_no == var_contract_no && l.var_no == var_no)
         {
             Expression::NumberLiteral {
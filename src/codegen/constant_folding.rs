@@ -7,6 +7,7 @@ use crate::sema::{
     ast::{Diagnostic, Namespace, RetrieveType, StringLocation, Type},
     eval::overflow_diagnostic,
 };
+use crate::Target;
 use num_bigint::{BigInt, Sign};
 use num_traits::{ToPrimitive, Zero};
 use ripemd::Ripemd160;
@@ -687,7 +688,7 @@ fn bigint_to_expression(
     }
 
     let value = match ty {
-        Type::Uint(bits) => {
+        Type::Uint(bits) | Type::Ufixed(bits, _) => {
             if value.sign() == Sign::Minus {
                 let mut bs = value.to_signed_bytes_le();
                 bs.resize(*bits as usize / 8, 0xff);
@@ -702,7 +703,7 @@ fn bigint_to_expression(
                 value
             }
         }
-        Type::Int(bits) => {
+        Type::Int(bits) | Type::Fixed(bits, _) => {
             let mut bs = value.to_signed_bytes_le();
 
             if bs.len() * 8 > *bits as usize {
@@ -1458,13 +1459,26 @@ fn keccak_256(
                     | Expression::BytesLiteral { value, .. } => {
                         hasher.update(value);
                     }
+                    Expression::NumberLiteral { value, .. } if ns.target == Target::EVM => {
+                        // The EVM hashes each value as a big endian word
+                        let fill = if value.sign() == Sign::Minus { 0xff } else { 0 };
+                        let bs = value.to_signed_bytes_be();
+                        let len = bs.len().min(32);
+                        let mut word = vec![fill; 32];
+
+                        word[32 - len..].copy_from_slice(&bs[bs.len() - len..]);
+
+                        hasher.update(&word);
+                    }
                     Expression::NumberLiteral { ty, value, .. } => {
                         let (sign, mut bs) = value.to_bytes_le();
 
                         match ty {
                             Type::Enum(_) => bs.resize(1, 0),
-                            Type::Uint(bits) => bs.resize(*bits as usize / 8, 0),
-                            Type::Int(bits) => {
+                            Type::Uint(bits) | Type::Ufixed(bits, _) => {
+                                bs.resize(*bits as usize / 8, 0)
+                            }
+                            Type::Int(bits) | Type::Fixed(bits, _) => {
                                 let v = if sign == Sign::Minus { 0xffu8 } else { 0 };
 
                                 bs.resize(*bits as usize / 8, v);
@@ -1496,7 +1510,9 @@ fn keccak_256(
         let mut hash = [0u8; 32];
         hasher.finalize(&mut hash);
         let mut hash = hash.to_vec();
-        hash.reverse();
+        if ns.target != Target::EVM {
+            hash.reverse();
+        }
 
         (
             Expression::BytesLiteral {
@@ -13,6 +13,7 @@ use num_bigint::{BigInt, Sign};
 use solang_parser::pt::Loc;
 
 use super::encoding::abi_encode;
+use super::encoding::soroban_encoding::{new_vec, soroban_encode_arg};
 
 /// This function encodes the constructor arguments and place an instruction in the CFG to
 /// call the constructor of a contract.
@@ -76,30 +77,31 @@ pub(super) fn call_constructor(
         .accounts
         .map(|expr| expression(expr, cfg, contract_no, func, ns, vartab, opt));
 
-    let mut constructor_args = constructor_args
+    let constructor_args = constructor_args
         .iter()
         .map(|e| expression(e, cfg, callee_contract_no, func, ns, vartab, opt))
         .collect::<Vec<Expression>>();
 
-    let selector = match constructor_no {
-        Some(func_no) => ns.functions[*func_no].selector(ns, &contract_no),
-        None => ns.contracts[contract_no]
-            .default_constructor
-            .as_ref()
-            .unwrap()
-            .0
-            .selector(ns, &contract_no),
-    };
-
-    let mut args = vec![Expression::BytesLiteral {
-        loc: *loc,
-        ty: Type::FunctionSelector,
-        value: selector,
-    }];
+    // Soroban passes the constructor arguments as a vector of Vals, which has no selector
+    let encoded_args = if ns.target == Target::Soroban {
+        let args = constructor_args
+            .into_iter()
+            .map(|arg| soroban_encode_arg(arg, cfg, vartab, ns))
+            .collect();
 
-    args.append(&mut constructor_args);
+        new_vec(args, cfg, vartab)
+    } else {
+        encode_constructor_args(
+            loc,
+            contract_no,
+            constructor_no,
+            constructor_args,
+            ns,
+            vartab,
+            cfg,
+        )
+    };
 
-    let (encoded_args, _) = abi_encode(loc, args, ns, vartab, cfg, false, false);
     cfg.add(
         vartab,
         Instr::Constructor {
@@ -118,3 +120,40 @@ pub(super) fn call_constructor(
         },
     );
 }
+
+/// ABI encode the selector of the constructor, if the target has one, and its arguments
+fn encode_constructor_args(
+    loc: &Loc,
+    contract_no: usize,
+    constructor_no: &Option<usize>,
+    mut constructor_args: Vec<Expression>,
+    ns: &Namespace,
+    vartab: &mut Vartable,
+    cfg: &mut ControlFlowGraph,
+) -> Expression {
+    // On EVM, the constructor arguments are appended to the creation code, without a selector.
+    // PolkaVM passes them to the constructor without a selector, too.
+    let mut args = if ns.target.is_ethereum_abi() {
+        Vec::new()
+    } else {
+        let selector = match constructor_no {
+            Some(func_no) => ns.functions[*func_no].selector(ns, &contract_no),
+            None => ns.contracts[contract_no]
+                .default_constructor
+                .as_ref()
+                .unwrap()
+                .0
+                .selector(ns, &contract_no),
+        };
+
+        vec![Expression::BytesLiteral {
+            loc: *loc,
+            ty: Type::FunctionSelector,
+            value: selector,
+        }]
+    };
+
+    args.append(&mut constructor_args);
+
+    abi_encode(loc, args, ns, vartab, cfg, false, false).0
+}
@@ -0,0 +1,135 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::polkadot::{self, new_cfg};
+use crate::{
+    codegen::{
+        cfg::{ControlFlowGraph, Instr, InternalCallTy, ReturnCode},
+        encoding::abi_decode,
+        revert::log_runtime_error,
+        vartable::Vartable,
+        Expression, Options,
+    },
+    sema::ast::{Namespace, Type, Type::Uint},
+};
+use solang_parser::pt::{FunctionTy, Loc::Codegen};
+
+/// On EVM, the constructor is not selected by a function selector. The creation code runs
+/// the only constructor of the contract, with its arguments appended to the creation code.
+/// Function calls are dispatched the same way as on Polkadot. PolkaVM contracts on pallet-revive
+/// are called the same way, so they share this dispatch.
+pub(crate) fn function_dispatch(
+    _contract_no: usize,
+    all_cfg: &[ControlFlowGraph],
+    ns: &mut Namespace,
+    opt: &Options,
+) -> Vec<ControlFlowGraph> {
+    vec![
+        constructor_dispatch(all_cfg, ns, opt),
+        polkadot::Dispatch::new(all_cfg, ns, opt, FunctionTy::Function).build(),
+    ]
+}
+
+/// Build the deploy dispatch: check for value transfer, decode the arguments and call the
+/// constructor.
+fn constructor_dispatch(
+    all_cfg: &[ControlFlowGraph],
+    ns: &mut Namespace,
+    opt: &Options,
+) -> ControlFlowGraph {
+    let mut vartab = Vartable::new(ns.next_id);
+    let mut cfg = new_cfg(ns, FunctionTy::Constructor);
+
+    let (cfg_no, constructor) = all_cfg
+        .iter()
+        .enumerate()
+        .find(|(_, cfg)| cfg.ty == FunctionTy::Constructor && cfg.public)
+        .expect("contract should have a constructor");
+
+    if constructor.nonpayable {
+        let got_value = cfg.new_basic_block("constructor_got_value".into());
+        let no_value = cfg.new_basic_block("constructor_no_value".into());
+
+        cfg.add(
+            &mut vartab,
+            Instr::BranchCond {
+                cond: Expression::More {
+                    loc: Codegen,
+                    signed: false,
+                    left: Expression::FunctionArg {
+                        loc: Codegen,
+                        ty: ns.value_type(),
+                        arg_no: 2,
+                    }
+                    .into(),
+                    right: Expression::NumberLiteral {
+                        loc: Codegen,
+                        ty: ns.value_type(),
+                        value: 0.into(),
+                    }
+                    .into(),
+                },
+                true_block: got_value,
+                false_block: no_value,
+            },
+        );
+
+        cfg.set_basic_block(got_value);
+        log_runtime_error(
+            opt.log_runtime_errors,
+            "runtime_error: non payable constructor received value",
+            Codegen,
+            &mut cfg,
+            &mut vartab,
+            ns,
+        );
+        cfg.add(&mut vartab, Instr::AssertFailure { encoded_args: None });
+
+        cfg.set_basic_block(no_value);
+    }
+
+    let args = if constructor.params.is_empty() {
+        vec![]
+    } else {
+        abi_decode(
+            &Codegen,
+            &Expression::FunctionArg {
+                loc: Codegen,
+                ty: Type::BufferPointer,
+                arg_no: 0,
+            },
+            &constructor
+                .params
+                .iter()
+                .map(|p| p.ty.clone())
+                .collect::<Vec<_>>(),
+            ns,
+            &mut vartab,
+            &mut cfg,
+            Some(Expression::FunctionArg {
+                loc: Codegen,
+                ty: Uint(32),
+                arg_no: 1,
+            }),
+        )
+    };
+
+    cfg.add(
+        &mut vartab,
+        Instr::Call {
+            res: vec![],
+            return_tys: vec![],
+            call: InternalCallTy::Static { cfg_no },
+            args,
+        },
+    );
+
+    cfg.add(
+        &mut vartab,
+        Instr::ReturnCode {
+            code: ReturnCode::Success,
+        },
+    );
+
+    vartab.finalize(ns, &mut cfg);
+    cfg
+}
@@ -3,6 +3,7 @@
 use super::{cfg::ControlFlowGraph, Options};
 use crate::{sema::ast::Namespace, Target};
 
+mod evm;
 pub(crate) mod polkadot;
 pub(super) mod solana;
 pub(super) mod soroban;
@@ -16,9 +17,8 @@ pub(super) fn function_dispatch(
 ) -> Vec<ControlFlowGraph> {
     match &ns.target {
         Target::Solana => vec![solana::function_dispatch(contract_no, all_cfg, ns, opt)],
-        Target::Polkadot { .. } | Target::EVM => {
-            polkadot::function_dispatch(contract_no, all_cfg, ns, opt)
-        }
+        Target::Polkadot { .. } => polkadot::function_dispatch(contract_no, all_cfg, ns, opt),
+        Target::EVM | Target::PolkaVM => evm::function_dispatch(contract_no, all_cfg, ns, opt),
         Target::Soroban => soroban::function_dispatch(contract_no, all_cfg, ns, opt),
         Target::Stylus => stylus::function_dispatch(contract_no, all_cfg, ns, opt),
     }
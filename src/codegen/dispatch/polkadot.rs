@@ -83,7 +83,7 @@ pub(super) struct Dispatch<'a> {
     ty: FunctionTy,
 }
 
-fn new_cfg(ns: &Namespace, ty: FunctionTy) -> ControlFlowGraph {
+pub(super) fn new_cfg(ns: &Namespace, ty: FunctionTy) -> ControlFlowGraph {
     let mut cfg = ControlFlowGraph::new(DispatchType::from(ty).to_string(), ASTFunction::None);
     let input_ptr = Parameter {
         loc: Codegen,
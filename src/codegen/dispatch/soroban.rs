@@ -26,7 +26,7 @@ pub fn function_dispatch(
 
     let mut wrapper_cfgs = Vec::new();
 
-    for cfg in all_cfg.iter_mut() {
+    for (cfg_no, cfg) in all_cfg.iter_mut().enumerate() {
         let wrapper_name = if cfg.public {
             if cfg.name.contains("constructor") {
                 "__constructor".to_string()
@@ -92,7 +92,7 @@ pub fn function_dispatch(
             call_returns.push(new);
         }
 
-        let decoded = decode_args(&mut wrapper_cfg, &mut vartab);
+        let decoded = decode_args(&mut wrapper_cfg, &mut vartab, ns);
 
         // call storage initializer if needed
         if wrapper_cfg.name == "__constructor" {
@@ -107,7 +107,7 @@ pub fn function_dispatch(
             wrapper_cfg.add(&mut vartab, placeholder);
 
             // check if constructor exists. If it does, we need to call it
-            if let ASTFunction::SolidityFunction(cfg_no) = cfg.function_no {
+            if let ASTFunction::SolidityFunction(_) = cfg.function_no {
                 // add a call to the constructor
                 let placeholder = Instr::Call {
                     res: call_returns.clone(),
@@ -120,11 +120,6 @@ pub fn function_dispatch(
         }
 
         if wrapper_cfg.name != "__constructor" {
-            let cfg_no = match cfg.function_no {
-                ASTFunction::SolidityFunction(no) => no,
-                _ => unreachable!(),
-            };
-
             let placeholder = Instr::Call {
                 res: call_returns,
                 call: InternalCallTy::Static { cfg_no },
@@ -145,7 +140,11 @@ pub fn function_dispatch(
     wrapper_cfgs
 }
 
-fn decode_args(wrapper_cfg: &mut ControlFlowGraph, vartab: &mut Vartable) -> Vec<Expression> {
+fn decode_args(
+    wrapper_cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+    ns: &Namespace,
+) -> Vec<Expression> {
     let mut args = Vec::new();
 
     let params = wrapper_cfg.params.clone();
@@ -157,7 +156,7 @@ fn decode_args(wrapper_cfg: &mut ControlFlowGraph, vartab: &mut Vartable) -> Vec
             arg_no: i,
         };
 
-        let decoded = soroban_decode_arg(arg.clone(), wrapper_cfg, vartab);
+        let decoded = soroban_decode_arg(arg.clone(), wrapper_cfg, vartab, ns);
 
         args.push(decoded);
     }
@@ -1,5 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use super::ethereum_head_size;
 use crate::codegen::cfg::{ControlFlowGraph, Instr};
 use crate::codegen::revert::{assert_failure, PanicCode, SolidityError};
 use crate::codegen::vartable::Vartable;
@@ -175,7 +176,11 @@ impl BufferValidator<'_> {
         // Create validation check
         let mut advance = BigInt::zero();
         for i in self.current_arg..=maximum_verifiable {
-            advance.add_assign(self.types[i].memory_size_of(ns));
+            if ns.target.is_ethereum_abi() {
+                advance.add_assign(ethereum_head_size(&self.types[i], ns));
+            } else {
+                advance.add_assign(self.types[i].memory_size_of(ns));
+            }
         }
 
         let reach = Expression::Add {
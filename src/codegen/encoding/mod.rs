@@ -89,8 +89,15 @@ pub(super) fn abi_encode(
         ty: Type::DynamicBytes,
         var_no: encoded_bytes,
     };
-    for (arg_no, item) in args.iter().enumerate() {
-        let advance = encoder.encode(item, &buffer, &offset, arg_no, ns, vartab, cfg);
+    let mut items = args.into_iter().enumerate().collect::<Vec<_>>();
+    // The Ethereum ABI encodes the arguments as a tuple, which starts after the function selector
+    let tuple = if ns.target.is_ethereum_abi() && !packed {
+        items.split_off(usize::from(offset_includes_function_selector).min(items.len()))
+    } else {
+        Vec::new()
+    };
+    for (arg_no, item) in &items {
+        let advance = encoder.encode(item, &buffer, &offset, *arg_no, ns, vartab, cfg);
         offset = Expression::Add {
             loc: *loc,
             ty: Uint(32),
@@ -99,6 +106,9 @@ pub(super) fn abi_encode(
             right: advance.into(),
         };
     }
+    if !tuple.is_empty() {
+        encoder.encode_tuple(&tuple, &buffer, &offset, ns, vartab, cfg);
+    }
     (buffer, size)
 }
 
@@ -155,6 +165,14 @@ pub(super) fn abi_decode(
     validator.initialize_validation(&offset, ns, vartab, cfg);
     let encoder = create_encoder(ns, false);
 
+    if ns.target.is_ethereum_abi() {
+        // The data of dynamic items may be anywhere after the head of the tuple, so there is
+        // no end offset to check
+        return encoder
+            .decode_tuple(buffer, &offset, types, &mut validator, ns, vartab, cfg)
+            .0;
+    }
+
     for (item_no, item) in types.iter().enumerate() {
         validator.set_argument_number(item_no);
         validator.validate_buffer(&offset, ns, vartab, cfg);
@@ -191,8 +209,22 @@ fn calculate_size_args(
         };
     }
     let mut size = encoder.get_expr_size(0, &args[0], ns, vartab, cfg);
+    if encoder.needs_tuple_offset(&args[0], ns) {
+        size = size.add_u32(Expression::NumberLiteral {
+            loc: Codegen,
+            ty: Uint(32),
+            value: BigInt::from(32),
+        });
+    }
     for (i, item) in args.iter().enumerate().skip(1) {
-        let additional = encoder.get_expr_size(i, item, ns, vartab, cfg);
+        let mut additional = encoder.get_expr_size(i, item, ns, vartab, cfg);
+        if encoder.needs_tuple_offset(item, ns) {
+            additional = additional.add_u32(Expression::NumberLiteral {
+                loc: Codegen,
+                ty: Uint(32),
+                value: BigInt::from(32),
+            });
+        }
         size = Expression::Add {
             loc: Codegen,
             ty: Uint(32),
@@ -238,7 +270,8 @@ pub(crate) trait AbiEncoding {
         let expr_ty = &expr.ty().unwrap_user_type(ns);
         match expr_ty {
             Type::Contract(_) | Type::Address(_) => {
-                if ns.target == Target::Stylus {
+                if ns.target == Target::Stylus || (ns.target.is_ethereum_abi() && !self.is_packed())
+                {
                     let expr = if matches!(expr_ty, Type::Contract(_)) {
                         expr.cast(&Type::Address(false), ns)
                     } else {
@@ -264,15 +297,35 @@ pub(crate) trait AbiEncoding {
                     )
                 }
             }
+            Type::Bool | Type::Enum(_) | Type::Value
+                if ns.target.is_ethereum_abi() && !self.is_packed() =>
+            {
+                // Every value occupies a full 32 byte big endian word
+                let expr = Expression::ZeroExt {
+                    loc: Codegen,
+                    ty: Type::Uint(256),
+                    expr: expr.clone().into(),
+                };
+                self.encode_int(&expr, buffer, offset, ns, vartab, cfg, 256)
+            }
             Type::Bool => self.encode_directly(expr, buffer, offset, vartab, cfg, 1.into()),
-            Type::Uint(width) | Type::Int(width) => {
+            Type::Uint(width)
+            | Type::Int(width)
+            | Type::Fixed(width, _)
+            | Type::Ufixed(width, _) => {
                 self.encode_int(expr, buffer, offset, ns, vartab, cfg, *width)
             }
             Type::Value => {
                 self.encode_directly(expr, buffer, offset, vartab, cfg, ns.value_length.into())
             }
             Type::Bytes(length) => {
-                self.encode_directly(expr, buffer, offset, vartab, cfg, (*length).into())
+                // Fixed length byte arrays are written big endian, so they only need padding
+                let size = if ns.target.is_ethereum_abi() && !self.is_packed() {
+                    32.into()
+                } else {
+                    (*length).into()
+                };
+                self.encode_directly(expr, buffer, offset, vartab, cfg, size)
             }
             Type::String | Type::DynamicBytes => {
                 self.encode_bytes(expr, buffer, offset, ns, vartab, cfg)
@@ -387,7 +440,7 @@ pub(crate) trait AbiEncoding {
             expr.clone()
         };
 
-        let maybe_swapped_expr = if ns.target == Target::Stylus {
+        let maybe_swapped_expr = if is_word_encoded(ns) {
             Expression::ByteSwap {
                 expr: Box::new(expr),
                 le_to_be: true,
@@ -437,54 +490,61 @@ pub(crate) trait AbiEncoding {
         let (data_offset, size, trailing_padding) = if self.is_packed() {
             (offset.clone(), None, None)
         } else {
-            let (size, trailing_padding) = if ns.target == Target::Stylus {
-                // smoelius: `next_offset` may include a function selector, but `next_data_offset`
-                // should not. `next_offset` is where data is written into the buffer.
-                // `next_data_offset` is the value written into the buffer.
-                let next_offset = Expression::Add {
-                    loc: Codegen,
-                    ty: Type::Uint(32),
-                    overflowing: false,
-                    left: Box::new(offset.clone()),
-                    right: Box::new(Expression::NumberLiteral {
+            let (size, trailing_padding) = if is_word_encoded(ns) {
+                // On EVM, the offset of the data is written in the head of the enclosing tuple,
+                // so only the length precedes the data. Stylus writes the offset in place.
+                let (length_offset, size) = if ns.target == Target::Stylus {
+                    // smoelius: `next_offset` may include a function selector, but `next_data_offset`
+                    // should not. `next_offset` is where data is written into the buffer.
+                    // `next_data_offset` is the value written into the buffer.
+                    let next_offset = Expression::Add {
                         loc: Codegen,
                         ty: Type::Uint(32),
-                        value: BigInt::from(32),
-                    }),
-                };
-                let next_data_offset = Expression::Add {
-                    loc: Codegen,
-                    ty: Type::Uint(32),
-                    overflowing: false,
-                    left: Box::new(offset.clone()),
-                    right: Box::new(Expression::NumberLiteral {
+                        overflowing: false,
+                        left: Box::new(offset.clone()),
+                        right: Box::new(Expression::NumberLiteral {
+                            loc: Codegen,
+                            ty: Type::Uint(32),
+                            value: BigInt::from(32),
+                        }),
+                    };
+                    let next_data_offset = Expression::Add {
                         loc: Codegen,
                         ty: Type::Uint(32),
-                        value: BigInt::from(
-                            if OFFSET_INCLUDES_FUNCTION_SELECTOR.load(Ordering::SeqCst) {
-                                28
-                            } else {
-                                32
-                            },
-                        ),
-                    }),
-                };
-                let next_data_offset_swapped = Expression::ByteSwap {
-                    expr: Box::new(Expression::ZeroExt {
-                        loc: Codegen,
-                        ty: Type::Uint(256),
-                        expr: Box::new(next_data_offset.clone()),
-                    }),
-                    le_to_be: true,
+                        overflowing: false,
+                        left: Box::new(offset.clone()),
+                        right: Box::new(Expression::NumberLiteral {
+                            loc: Codegen,
+                            ty: Type::Uint(32),
+                            value: BigInt::from(
+                                if OFFSET_INCLUDES_FUNCTION_SELECTOR.load(Ordering::SeqCst) {
+                                    28
+                                } else {
+                                    32
+                                },
+                            ),
+                        }),
+                    };
+                    let next_data_offset_swapped = Expression::ByteSwap {
+                        expr: Box::new(Expression::ZeroExt {
+                            loc: Codegen,
+                            ty: Type::Uint(256),
+                            expr: Box::new(next_data_offset.clone()),
+                        }),
+                        le_to_be: true,
+                    };
+                    cfg.add(
+                        vartab,
+                        Instr::WriteBuffer {
+                            buf: buffer.clone(),
+                            offset: offset.clone(),
+                            value: next_data_offset_swapped,
+                        },
+                    );
+                    (next_offset, 64)
+                } else {
+                    (offset.clone(), 32)
                 };
-                cfg.add(
-                    vartab,
-                    Instr::WriteBuffer {
-                        buf: buffer.clone(),
-                        offset: offset.clone(),
-                        value: next_data_offset_swapped,
-                    },
-                );
                 let swapped_bytes = Expression::ByteSwap {
                     expr: Box::new(Expression::ZeroExt {
                         loc: Codegen,
@@ -497,7 +557,7 @@ pub(crate) trait AbiEncoding {
                     vartab,
                     Instr::WriteBuffer {
                         buf: buffer.clone(),
-                        offset: next_offset,
+                        offset: length_offset,
                         value: swapped_bytes,
                     },
                 );
@@ -505,7 +565,7 @@ pub(crate) trait AbiEncoding {
                     Expression::NumberLiteral {
                         loc: Codegen,
                         ty: Type::Uint(32),
-                        value: BigInt::from(64),
+                        value: BigInt::from(size),
                     },
                     Some(trailing_padding(&len)),
                 )
@@ -544,6 +604,101 @@ pub(crate) trait AbiEncoding {
         len
     }
 
+    /// In the Ethereum ABI, dynamic items of a tuple are written after the static ones, and
+    /// the tuple has their offset in its place
+    fn needs_tuple_offset(&self, expr: &Expression, ns: &Namespace) -> bool {
+        ns.target.is_ethereum_abi() && !self.is_packed() && expr.ty().is_dynamic(ns)
+    }
+
+    /// Encode the items as an Ethereum ABI tuple at `offset`. Each item comes with the
+    /// argument number used for the storage cache. Returns the encoded size.
+    fn encode_tuple(
+        &mut self,
+        items: &[(usize, Expression)],
+        buffer: &Expression,
+        offset: &Expression,
+        ns: &Namespace,
+        vartab: &mut Vartable,
+        cfg: &mut ControlFlowGraph,
+    ) -> Expression {
+        let head_size = items
+            .iter()
+            .map(|(_, item)| ethereum_head_size(&item.ty(), ns))
+            .sum::<BigInt>();
+        let tail_var = vartab.temp_anonymous(&Uint(32));
+        cfg.add(
+            vartab,
+            Instr::Set {
+                loc: Codegen,
+                res: tail_var,
+                expr: offset.clone().add_u32(Expression::NumberLiteral {
+                    loc: Codegen,
+                    ty: Uint(32),
+                    value: head_size,
+                }),
+            },
+        );
+        let tail = Expression::Variable {
+            loc: Codegen,
+            ty: Uint(32),
+            var_no: tail_var,
+        };
+
+        let mut head = offset.clone();
+        for (arg_no, item) in items {
+            let advance = if self.needs_tuple_offset(item, ns) {
+                let item_offset = Expression::Subtract {
+                    loc: Codegen,
+                    ty: Uint(32),
+                    overflowing: false,
+                    left: tail.clone().into(),
+                    right: offset.clone().into(),
+                };
+                cfg.add(
+                    vartab,
+                    Instr::WriteBuffer {
+                        buf: buffer.clone(),
+                        offset: head.clone(),
+                        value: Expression::ByteSwap {
+                            expr: Expression::ZeroExt {
+                                loc: Codegen,
+                                ty: Uint(256),
+                                expr: item_offset.into(),
+                            }
+                            .into(),
+                            le_to_be: true,
+                        },
+                    },
+                );
+                let size = self.encode(item, buffer, &tail, *arg_no, ns, vartab, cfg);
+                cfg.add(
+                    vartab,
+                    Instr::Set {
+                        loc: Codegen,
+                        res: tail_var,
+                        expr: tail.clone().add_u32(size),
+                    },
+                );
+                Expression::NumberLiteral {
+                    loc: Codegen,
+                    ty: Uint(32),
+                    value: BigInt::from(32),
+                }
+            } else {
+                self.encode(item, buffer, &head, *arg_no, ns, vartab, cfg)
+            };
+            head = head.add_u32(advance);
+        }
+
+        Expression::Subtract {
+            loc: Codegen,
+            ty: Uint(32),
+            overflowing: false,
+            left: tail.into(),
+            right: offset.clone().into(),
+        }
+    }
+
     /// Encode `expr` into `buffer` as a struct type.
     fn encode_struct(
         &mut self,
@@ -556,6 +711,22 @@ pub(crate) trait AbiEncoding {
         vartab: &mut Vartable,
         cfg: &mut ControlFlowGraph,
     ) -> Expression {
+        if ns.target.is_ethereum_abi() && !self.is_packed() {
+            let fields = struct_ty
+                .definition(ns)
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(i, field)| {
+                    (
+                        arg_no,
+                        load_struct_member(field.ty.clone(), expr.clone(), i, ns),
+                    )
+                })
+                .collect::<Vec<_>>();
+            return self.encode_tuple(&fields, buffer, &offset, ns, vartab, cfg);
+        }
+
         let size = ns.calculate_struct_non_padded_size(struct_ty);
         // If the size without padding equals the size with padding, memcpy this struct directly.
         if let Some(no_padding_size) = size.as_ref().filter(|no_pad| {
@@ -652,7 +823,7 @@ pub(crate) trait AbiEncoding {
                     let (new_offset, size_length) = if self.is_packed() {
                         (offset.clone(), None)
                     } else {
-                        let encoded_size = if ns.target == Target::Stylus {
+                        let encoded_size = if is_word_encoded(ns) {
                             let swapped_bytes = Expression::ByteSwap {
                                 expr: Box::new(Expression::ZeroExt {
                                     loc: Codegen,
@@ -811,7 +982,7 @@ pub(crate) trait AbiEncoding {
                 ty: Uint(32),
                 var_no: offset_var,
             };
-            let encoded_size = if ns.target == Target::Stylus {
+            let encoded_size = if is_word_encoded(ns) {
                 let swapped_bytes = Expression::ByteSwap {
                     expr: Box::new(Expression::ZeroExt {
                         loc: Codegen,
@@ -912,7 +1083,10 @@ pub(crate) trait AbiEncoding {
         cfg: &mut ControlFlowGraph,
     ) -> (Expression, Expression) {
         match ty {
-            Type::Uint(width) | Type::Int(width) => {
+            Type::Uint(width)
+            | Type::Int(width)
+            | Type::Fixed(width, _)
+            | Type::Ufixed(width, _) => {
                 let encoding_size = self.encoding_size(ns, *width);
 
                 let size = Expression::NumberLiteral {
@@ -922,30 +1096,54 @@ pub(crate) trait AbiEncoding {
                 };
                 validator.validate_offset_plus_size(offset, &size, ns, vartab, cfg);
 
-                let read_value = Expression::Builtin {
-                    loc: Codegen,
-                    tys: vec![ty.clone()],
-                    kind: Builtin::ReadFromBuffer,
-                    args: vec![buffer.clone(), offset.clone()],
-                };
+                let maybe_swapped_expr = if ns.target.is_ethereum_abi() {
+                    // The value is in the low order bytes of a big endian word, so the whole
+                    // word must be swapped before truncating
+                    let word = Expression::ByteSwap {
+                        expr: Box::new(Expression::Builtin {
+                            loc: Codegen,
+                            tys: vec![Uint(256)],
+                            kind: Builtin::ReadFromBuffer,
+                            args: vec![buffer.clone(), offset.clone()],
+                        }),
+                        le_to_be: false,
+                    };
 
-                let maybe_truncated_expr = if encoding_size == *width {
-                    read_value
+                    if *width == 256 {
+                        word
+                    } else {
+                        Expression::Trunc {
+                            loc: Codegen,
+                            ty: ty.clone(),
+                            expr: Box::new(word),
+                        }
+                    }
                 } else {
-                    Expression::Trunc {
+                    let read_value = Expression::Builtin {
                         loc: Codegen,
-                        ty: ty.clone(),
-                        expr: Box::new(read_value),
-                    }
-                };
+                        tys: vec![ty.clone()],
+                        kind: Builtin::ReadFromBuffer,
+                        args: vec![buffer.clone(), offset.clone()],
+                    };
 
-                let maybe_swapped_expr = if ns.target == Target::Stylus {
-                    Expression::ByteSwap {
-                        expr: Box::new(maybe_truncated_expr),
-                        le_to_be: false,
+                    let maybe_truncated_expr = if encoding_size == *width {
+                        read_value
+                    } else {
+                        Expression::Trunc {
+                            loc: Codegen,
+                            ty: ty.clone(),
+                            expr: Box::new(read_value),
+                        }
+                    };
+
+                    if ns.target == Target::Stylus {
+                        Expression::ByteSwap {
+                            expr: Box::new(maybe_truncated_expr),
+                            le_to_be: false,
+                        }
+                    } else {
+                        maybe_truncated_expr
                     }
-                } else {
-                    maybe_truncated_expr
                 };
 
                 let read_var = vartab.temp_anonymous(ty);
@@ -973,12 +1171,17 @@ pub(crate) trait AbiEncoding {
             | Type::Enum(_)
             | Type::Value
             | Type::Bytes(_) => {
-                let (read_ty, read_bytes) =
-                    if ns.target == Target::Stylus && matches!(ty, Type::Address(_)) {
-                        (Type::Uint(256), BigInt::from(32))
-                    } else {
-                        (ty.clone(), ty.memory_size_of(ns))
-                    };
+                let word_sized = (ns.target.is_ethereum_abi() && !matches!(ty, Type::Bytes(_)))
+                    || (ns.target == Target::Stylus && matches!(ty, Type::Address(_)));
+
+                let (read_ty, read_bytes) = if word_sized {
+                    (Type::Uint(256), BigInt::from(32))
+                } else if ns.target.is_ethereum_abi() {
+                    // bytesN is left aligned, so it can be read directly
+                    (ty.clone(), BigInt::from(32))
+                } else {
+                    (ty.clone(), ty.memory_size_of(ns))
+                };
 
                 let size = Expression::NumberLiteral {
                     loc: Codegen,
@@ -994,13 +1197,20 @@ pub(crate) trait AbiEncoding {
                     args: vec![buffer.clone(), offset.clone()],
                 };
 
-                if ns.target == Target::Stylus && matches!(ty, Type::Address(_)) {
+                if word_sized {
                     read_value = Expression::ByteSwap {
                         expr: Box::new(read_value),
                         le_to_be: false,
                     };
-                    read_value = read_value.cast(&Type::Uint(160), ns);
-                    read_value = read_value.cast(ty, ns);
+                    read_value = if matches!(ty, Type::Address(_) | Type::Contract(_)) {
+                        read_value.cast(&Type::Uint(160), ns).cast(ty, ns)
+                    } else {
+                        Expression::Trunc {
+                            loc: Codegen,
+                            ty: ty.clone(),
+                            expr: Box::new(read_value),
+                        }
+                    };
                 }
 
                 let read_var = vartab.temp_anonymous(ty);
@@ -1024,7 +1234,40 @@ pub(crate) trait AbiEncoding {
 
             Type::DynamicBytes | Type::String => {
                 // String and Dynamic bytes are encoded as size + elements
-                let (array_length_var, size_length) = if ns.target == Target::Stylus {
+                let (array_length_var, size_length) = if ns.target.is_ethereum_abi() {
+                    // The data is preceded by its length as a 32 byte word
+                    let len = Expression::Builtin {
+                        loc: Codegen,
+                        tys: vec![Uint(256)],
+                        kind: Builtin::ReadFromBuffer,
+                        args: vec![buffer.clone(), offset.clone()],
+                    };
+                    let len_swapped = Expression::ByteSwap {
+                        expr: Box::new(len),
+                        le_to_be: false,
+                    };
+                    let array_length_var = vartab.temp_anonymous(&Uint(32));
+                    cfg.add(
+                        vartab,
+                        Instr::Set {
+                            loc: Codegen,
+                            res: array_length_var,
+                            expr: Expression::Trunc {
+                                loc: Codegen,
+                                ty: Uint(32),
+                                expr: Box::new(len_swapped),
+                            },
+                        },
+                    );
+                    (
+                        array_length_var,
+                        Expression::NumberLiteral {
+                            loc: Codegen,
+                            ty: Type::Uint(32),
+                            value: BigInt::from(32),
+                        },
+                    )
+                } else if ns.target == Target::Stylus {
                     let len_offset = Expression::Builtin {
                         loc: Codegen,
                         tys: vec![Uint(256)],
@@ -1115,12 +1358,18 @@ pub(crate) trait AbiEncoding {
                         bytes: array_length.clone(),
                     },
                 );
-                let total_size_with_trailing_padding = Expression::Add {
-                    loc: Codegen,
-                    ty: Type::Uint(32),
-                    overflowing: false,
-                    left: Box::new(total_size),
-                    right: Box::new(trailing_padding(&array_length)),
+                // Only the Ethereum ABI pads the data to a multiple of 32 bytes; Borsh and SCALE
+                // encoded data is followed directly by the next item
+                let total_size = if is_word_encoded(ns) {
+                    Expression::Add {
+                        loc: Codegen,
+                        ty: Type::Uint(32),
+                        overflowing: false,
+                        left: Box::new(total_size),
+                        right: Box::new(trailing_padding(&array_length)),
+                    }
+                } else {
+                    total_size
                 };
                 (
                     Expression::Variable {
@@ -1128,7 +1377,7 @@ pub(crate) trait AbiEncoding {
                         ty: ty.clone(),
                         var_no: allocated_array,
                     },
-                    total_size_with_trailing_padding,
+                    total_size,
                 )
             }
 
@@ -1393,7 +1642,7 @@ pub(crate) trait AbiEncoding {
 
         // Dynamic dimensions mean that the subarray we are processing must be allocated in memory.
         if dims[dimension] == ArrayLength::Dynamic {
-            let (array_length, size_length) = if ns.target == Target::Stylus {
+            let (array_length, size_length) = if is_word_encoded(ns) {
                 let array_length_var = vartab.temp_anonymous(&Uint(32));
                 let len = Expression::Builtin {
                     loc: Codegen,
@@ -1536,6 +1785,83 @@ pub(crate) trait AbiEncoding {
         finish_array_loop(&for_loop, vartab, cfg);
     }
 
+    /// Read an Ethereum ABI tuple of the given types at `offset`. Returns the items read and
+    /// the size of the head of the tuple.
+    fn decode_tuple(
+        &self,
+        buffer: &Expression,
+        offset: &Expression,
+        types: &[Type],
+        validator: &mut BufferValidator,
+        ns: &Namespace,
+        vartab: &mut Vartable,
+        cfg: &mut ControlFlowGraph,
+    ) -> (Vec<Expression>, Expression) {
+        let mut head = offset.clone();
+        let mut items = Vec::with_capacity(types.len());
+
+        for (item_no, ty) in types.iter().enumerate() {
+            validator.set_argument_number(item_no);
+            validator.validate_buffer(&head, ns, vartab, cfg);
+
+            let head_size = Expression::NumberLiteral {
+                loc: Codegen,
+                ty: Uint(32),
+                value: ethereum_head_size(ty, ns),
+            };
+
+            let item = if ty.is_dynamic(ns) {
+                validator.validate_offset(head.clone().add_u32(head_size.clone()), ns, vartab, cfg);
+                // The head has the offset of the data from the start of the tuple
+                let item_offset = Expression::Trunc {
+                    loc: Codegen,
+                    ty: Uint(32),
+                    expr: Expression::ByteSwap {
+                        expr: Expression::Builtin {
+                            loc: Codegen,
+                            tys: vec![Uint(256)],
+                            kind: Builtin::ReadFromBuffer,
+                            args: vec![buffer.clone(), head.clone()],
+                        }
+                        .into(),
+                        le_to_be: false,
+                    }
+                    .into(),
+                };
+                let data_offset = vartab.temp_anonymous(&Uint(32));
+                cfg.add(
+                    vartab,
+                    Instr::Set {
+                        loc: Codegen,
+                        res: data_offset,
+                        expr: offset.clone().add_u32(item_offset),
+                    },
+                );
+                let data_offset = Expression::Variable {
+                    loc: Codegen,
+                    ty: Uint(32),
+                    var_no: data_offset,
+                };
+                self.read_from_buffer(buffer, &data_offset, ty, validator, ns, vartab, cfg)
+                    .0
+            } else {
+                self.read_from_buffer(buffer, &head, ty, validator, ns, vartab, cfg)
+                    .0
+            };
+
+            items.push(item);
+            head = head.add_u32(head_size);
+        }
+
+        let size = Expression::NumberLiteral {
+            loc: Codegen,
+            ty: Uint(32),
+            value: types.iter().map(|ty| ethereum_head_size(ty, ns)).sum(),
+        };
+
+        (items, size)
+    }
+
     /// Read a struct from the buffer
     fn decode_struct(
         &self,
@@ -1548,6 +1874,47 @@ pub(crate) trait AbiEncoding {
         vartab: &mut Vartable,
         cfg: &mut ControlFlowGraph,
     ) -> (Expression, Expression) {
+        if ns.target.is_ethereum_abi() {
+            let struct_tys = struct_ty
+                .definition(ns)
+                .fields
+                .iter()
+                .map(|item| item.ty.clone())
+                .collect::<Vec<Type>>();
+            let mut struct_validator = validator.create_sub_validator(&struct_tys);
+            if validator.validation_necessary() {
+                struct_validator.initialize_validation(&offset, ns, vartab, cfg);
+            }
+            let (values, size) = self.decode_tuple(
+                buffer,
+                &offset,
+                &struct_tys,
+                &mut struct_validator,
+                ns,
+                vartab,
+                cfg,
+            );
+            let allocated_struct = vartab.temp_anonymous(expr_ty);
+            cfg.add(
+                vartab,
+                Instr::Set {
+                    loc: Codegen,
+                    res: allocated_struct,
+                    expr: Expression::StructLiteral {
+                        loc: Codegen,
+                        ty: expr_ty.clone(),
+                        values,
+                    },
+                },
+            );
+            let struct_var = Expression::Variable {
+                loc: Codegen,
+                ty: expr_ty.clone(),
+                var_no: allocated_struct,
+            };
+            return (struct_var, size);
+        }
+
         let size = ns.calculate_struct_non_padded_size(struct_ty);
         // If the size without padding equals the size with padding, memcpy this struct directly.
         if let Some(no_padding_size) = size.as_ref().filter(|no_pad| {
@@ -1694,18 +2061,25 @@ pub(crate) trait AbiEncoding {
                 ty: Uint(32),
                 value: BigInt::from(ns.value_length),
             },
-            Type::Uint(n) | Type::Int(n) => Expression::NumberLiteral {
-                loc: Codegen,
-                ty: Uint(32),
-                value: BigInt::from(self.encoding_size(ns, *n) / 8),
-            },
+            Type::Uint(n) | Type::Int(n) | Type::Fixed(n, _) | Type::Ufixed(n, _) => {
+                Expression::NumberLiteral {
+                    loc: Codegen,
+                    ty: Uint(32),
+                    value: BigInt::from(self.encoding_size(ns, *n) / 8),
+                }
+            }
             Type::Enum(_) | Type::Bool | Type::Bytes(_) => Expression::NumberLiteral {
                 loc: Codegen,
                 ty: Uint(32),
-                value: ty.memory_size_of(ns),
+                value: if ns.target.is_ethereum_abi() && !self.is_packed() {
+                    BigInt::from(32)
+                } else {
+                    ty.memory_size_of(ns)
+                },
             },
             Type::Contract(_) | Type::Address(_) => {
-                if ns.target == Target::Stylus {
+                if ns.target == Target::Stylus || (ns.target.is_ethereum_abi() && !self.is_packed())
+                {
                     Expression::NumberLiteral {
                         loc: Codegen,
                         ty: Uint(32),
@@ -1740,7 +2114,12 @@ pub(crate) trait AbiEncoding {
                 Expression::NumberLiteral {
                     loc: Codegen,
                     ty: Uint(32),
-                    value: address_size + selector_len,
+                    value: if ns.target.is_ethereum_abi() && !self.is_packed() {
+                        // bytes24, padded to a word
+                        BigInt::from(32)
+                    } else {
+                        address_size + selector_len
+                    },
                 }
             }
             Type::Ref(r) => {
@@ -1761,12 +2140,9 @@ pub(crate) trait AbiEncoding {
                 size
             }
             Type::String | Type::DynamicBytes => {
-                if ns.target == Target::Stylus {
-                    let offset_size = Expression::NumberLiteral {
-                        loc: Codegen,
-                        ty: Uint(32),
-                        value: BigInt::from(32),
-                    };
+                if ns.target == Target::Stylus || (ns.target.is_ethereum_abi() && !self.is_packed())
+                {
+                    // The length word, followed by the data padded to a multiple of 32 bytes
                     let array_length_size = Expression::NumberLiteral {
                         loc: Codegen,
                         ty: Uint(32),
@@ -1779,24 +2155,35 @@ pub(crate) trait AbiEncoding {
                         args: vec![expr.clone()],
                     };
                     let trailing_padding = trailing_padding(&array_size);
-                    Expression::Add {
+                    let size = Expression::Add {
                         loc: Codegen,
                         ty: Uint(32),
                         overflowing: false,
-                        left: Box::new(offset_size),
+                        left: Box::new(array_length_size),
                         right: Box::new(Expression::Add {
                             loc: Codegen,
                             ty: Uint(32),
                             overflowing: false,
-                            left: Box::new(array_length_size),
-                            right: Box::new(Expression::Add {
-                                loc: Codegen,
-                                ty: Uint(32),
-                                overflowing: false,
-                                left: Box::new(array_size),
-                                right: Box::new(trailing_padding),
-                            }),
+                            left: Box::new(array_size),
+                            right: Box::new(trailing_padding),
                         }),
+                    };
+                    if ns.target == Target::Stylus {
+                        // Stylus writes the offset of the data in place
+                        let offset_size = Expression::NumberLiteral {
+                            loc: Codegen,
+                            ty: Uint(32),
+                            value: BigInt::from(32),
+                        };
+                        Expression::Add {
+                            loc: Codegen,
+                            ty: Uint(32),
+                            overflowing: false,
+                            left: Box::new(offset_size),
+                            right: Box::new(size),
+                        }
+                    } else {
+                        size
                     }
                 } else {
                     self.calculate_string_size(expr, vartab, cfg)
@@ -1846,12 +2233,18 @@ pub(crate) trait AbiEncoding {
 
         // Check if the array contains only fixed sized elements
         let primitive_size = if elem_ty.is_primitive() && direct_assessment {
-            Some(elem_ty.memory_size_of(ns))
-        } else if let Type::Struct(struct_ty) = elem_ty {
-            if direct_assessment {
-                ns.calculate_struct_non_padded_size(struct_ty)
+            if ns.target.is_ethereum_abi() && !self.is_packed() {
+                Some(BigInt::from(32))
             } else {
+                Some(elem_ty.memory_size_of(ns))
+            }
+        } else if let Type::Struct(struct_ty) = elem_ty {
+            if !direct_assessment {
                 None
+            } else if ns.target.is_ethereum_abi() && !self.is_packed() {
+                Some(ethereum_head_size(elem_ty, ns)).filter(|_| !elem_ty.is_dynamic(ns))
+            } else {
+                ns.calculate_struct_non_padded_size(struct_ty)
             }
         } else {
             None
@@ -1889,7 +2282,7 @@ pub(crate) trait AbiEncoding {
                 };
             }
 
-            let size_width = if ns.target == Target::Stylus {
+            let size_width = if is_word_encoded(ns) {
                 Expression::NumberLiteral {
                     loc: Codegen,
                     ty: Type::Uint(32),
@@ -2066,6 +2459,26 @@ pub(crate) trait AbiEncoding {
         vartab: &mut Vartable,
         cfg: &mut ControlFlowGraph,
     ) -> Expression {
+        if ns.target.is_ethereum_abi() && !self.is_packed() {
+            // The head of the tuple, followed by the data of the dynamic fields
+            let mut size = Expression::NumberLiteral {
+                loc: Codegen,
+                ty: Uint(32),
+                value: struct_ty
+                    .definition(ns)
+                    .fields
+                    .iter()
+                    .map(|field| ethereum_head_size(&field.ty, ns))
+                    .sum::<BigInt>(),
+            };
+            for (i, field) in struct_ty.definition(ns).fields.iter().enumerate() {
+                if field.ty.is_dynamic(ns) {
+                    let field = load_struct_member(field.ty.clone(), expr.clone(), i, ns);
+                    size = size.add_u32(self.get_expr_size(arg_no, &field, ns, vartab, cfg));
+                }
+            }
+            return size;
+        }
         if let Some(struct_size) = ns.calculate_struct_non_padded_size(struct_ty) {
             return Expression::NumberLiteral {
                 loc: Codegen,
@@ -2124,7 +2537,7 @@ pub(crate) trait AbiEncoding {
     }
 
     fn encoding_size(&self, ns: &Namespace, n: u16) -> u16 {
-        if !self.is_packed() && ns.target == Target::Stylus {
+        if !self.is_packed() && is_word_encoded(ns) {
             256
         } else {
             n.next_power_of_two()
@@ -2416,11 +2829,16 @@ fn array_outer_length(
     }
 }
 
+/// The Ethereum ABI, which is also used by Stylus, encodes integers as big endian 32 byte words
+fn is_word_encoded(ns: &Namespace) -> bool {
+    ns.target.is_ethereum_abi() || ns.target == Target::Stylus
+}
+
 /// Check if we can MemCpy a type to/from a buffer
 fn allow_memcpy(ty: &Type, ns: &Namespace) -> bool {
-    // smoelius: `memcpy` cannot be used for Stylus because integers are encoded big endian but are
-    // stored in memory little endian.
-    if ns.target == Target::Stylus {
+    // smoelius: `memcpy` cannot be used for Stylus and EVM because integers are encoded big endian
+    // but are stored in memory little endian.
+    if is_word_encoded(ns) {
         return false;
     }
     match ty {
@@ -2565,3 +2983,29 @@ fn trailing_padding(len: &Expression) -> Expression {
         }),
     }
 }
+
+/// The number of bytes a type occupies in the head of an Ethereum ABI tuple. Dynamic types
+/// are represented by the offset of their data, and static types are encoded in place.
+pub(super) fn ethereum_head_size(ty: &Type, ns: &Namespace) -> BigInt {
+    if ty.is_dynamic(ns) {
+        return BigInt::from(32);
+    }
+
+    match ty.deref_any() {
+        Type::Struct(struct_ty) => struct_ty
+            .definition(ns)
+            .fields
+            .iter()
+            .map(|field| ethereum_head_size(&field.ty, ns))
+            .sum(),
+        Type::Array(elem_ty, dims) => {
+            dims.iter()
+                .map(|dim| dim.array_length().unwrap().clone())
+                .product::<BigInt>()
+                * ethereum_head_size(elem_ty, ns)
+        }
+        Type::UserType(no) => ethereum_head_size(&ns.user_types[*no].ty, ns),
+        Type::FunctionSelector => BigInt::from(ns.target.selector_length()),
+        _ => BigInt::from(32),
+    }
+}
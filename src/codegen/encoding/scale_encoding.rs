@@ -472,10 +472,16 @@ impl AbiEncoding for ScaleEncoding {
         };
         let selector = expr.external_function_selector();
         self.encode_directly(&selector, buffer, &offset, vartab, cfg, 4.into());
+        // The Ethereum ABI pads the address and selector to a word
+        let size = if ns.target.is_ethereum_abi() && !self.is_packed() {
+            32
+        } else {
+            ns.address_length + 4
+        };
         Expression::NumberLiteral {
             loc: Codegen,
             ty: Uint(32),
-            value: (ns.address_length + 4).into(),
+            value: size.into(),
         }
     }
 
@@ -529,6 +535,15 @@ impl AbiEncoding for ScaleEncoding {
             ty: Type::Struct(StructType::ExternalFunction),
             values: vec![selector, address],
         };
+        let size = if ns.target.is_ethereum_abi() {
+            Expression::NumberLiteral {
+                loc: Codegen,
+                ty: Uint(32),
+                value: 32.into(),
+            }
+        } else {
+            size
+        };
         (
             Expression::Cast {
                 loc: Codegen,
@@ -6,7 +6,7 @@ use crate::codegen::encoding::create_encoder;
 use crate::codegen::vartable::Vartable;
 use crate::codegen::Expression;
 use crate::codegen::HostFunctions;
-use crate::sema::ast::{Namespace, RetrieveType, Type, Type::Uint};
+use crate::sema::ast::{Namespace, RetrieveType, StructType, Type, Type::Uint};
 use num_bigint::BigInt;
 use num_traits::Zero;
 use solang_parser::helpers::CodeLocation;
@@ -87,8 +87,8 @@ pub fn soroban_encode(
 pub fn soroban_decode(
     _loc: &Loc,
     buffer: &Expression,
-    _types: &[Type],
-    _ns: &Namespace,
+    types: &[Type],
+    ns: &Namespace,
     vartab: &mut Vartable,
     cfg: &mut ControlFlowGraph,
     _buffer_size_expr: Option<Expression>,
@@ -101,7 +101,17 @@ pub fn soroban_decode(
         expr: Box::new(buffer.clone()),
     };
 
-    let decoded_val = soroban_decode_arg(loaded_val, cfg, vartab);
+    // the returned Val is decoded according to the expected type
+    let loaded_val = match types.first() {
+        Some(ty) => Expression::Variable {
+            loc: Loc::Codegen,
+            ty: ty.clone(),
+            var_no: val_to_var(loaded_val, cfg, vartab),
+        },
+        None => loaded_val,
+    };
+
+    let decoded_val = soroban_decode_arg(loaded_val, cfg, vartab, ns);
 
     returns.push(decoded_val);
 
@@ -112,6 +122,7 @@ pub fn soroban_decode_arg(
     arg: Expression,
     wrapper_cfg: &mut ControlFlowGraph,
     vartab: &mut Vartable,
+    ns: &Namespace,
 ) -> Expression {
     let ty = if let Type::Ref(inner_ty) = arg.ty() {
         *inner_ty
@@ -141,9 +152,10 @@ pub fn soroban_decode_arg(
             signed: false,
         },
 
-        Type::Address(_) | Type::String => arg.clone(),
+        Type::Address(_) | Type::String | Type::DynamicBytes => arg.clone(),
 
         Type::Int(128) | Type::Uint(128) => decode_i128(wrapper_cfg, vartab, arg),
+        Type::Int(256) | Type::Uint(256) => decode_i256(wrapper_cfg, vartab, arg, ty),
         Type::Uint(32) => {
             // get payload out of major bits then truncate to 32‑bit
             Expression::Trunc {
@@ -190,7 +202,26 @@ pub fn soroban_decode_arg(
             signed: true,
         },
 
-        _ => unimplemented!(),
+        Type::Enum(enum_no) => {
+            // enums are passed as the u32 value of the case
+            let value = Expression::Variable {
+                loc: Loc::Codegen,
+                ty: Type::Uint(32),
+                var_no: val_to_var(arg, wrapper_cfg, vartab),
+            };
+
+            Expression::Trunc {
+                loc: Loc::Codegen,
+                ty: ns.enums[enum_no].ty.clone(),
+                expr: Box::new(soroban_decode_arg(value, wrapper_cfg, vartab, ns)),
+            }
+        }
+
+        Type::Struct(StructType::UserDefined(struct_no)) => {
+            decode_struct(wrapper_cfg, vartab, arg, struct_no, ns)
+        }
+
+        _ => unreachable!("type without a Val conversion is rejected in sema"),
     }
 }
 
@@ -219,7 +250,7 @@ pub fn soroban_encode_arg(
                 expr: encoded,
             }
         }
-        Type::String => {
+        Type::String | Type::DynamicBytes => {
             if let Expression::Variable {
                 loc: _,
                 ty: _,
@@ -323,11 +354,18 @@ pub fn soroban_encode_arg(
                     _ => unreachable!(),
                 };
 
+                // strings are passed as symbols
+                let host_function = if item.ty() == Type::String {
+                    HostFunctions::SymbolNewFromLinearMemory
+                } else {
+                    HostFunctions::BytesNewFromLinearMemory
+                };
+
                 Instr::Call {
                     res: vec![obj],
                     return_tys: vec![Type::Uint(64)],
                     call: crate::codegen::cfg::InternalCallTy::HostFunction {
-                        name: HostFunctions::SymbolNewFromLinearMemory.name().to_string(),
+                        name: host_function.name().to_string(),
                     },
                     args: vec![encoded, len],
                 }
@@ -419,49 +457,54 @@ pub fn soroban_encode_arg(
             }
         }
         Type::Address(_) => {
-            let instr = if let Expression::Cast { loc, ty: _, expr } = item {
-                let address_literal = expr;
-
-                let pointer = Expression::VectorData {
-                    pointer: address_literal.clone(),
-                };
+            // address literals are converted from their strkey; any other address is a host object already
+            let literal = match &item {
+                Expression::Cast { loc, ty: _, expr } => match expr.as_ref() {
+                    Expression::BytesLiteral { value, .. } => Some((*loc, expr, value.len())),
+                    _ => None,
+                },
+                _ => None,
+            };
 
-                let pointer_extend = Expression::ZeroExt {
-                    loc,
-                    ty: Type::Uint(64),
-                    expr: Box::new(pointer),
-                };
+            let instr = match literal {
+                Some((loc, address_literal, len)) => {
+                    let pointer = Expression::VectorData {
+                        pointer: address_literal.clone(),
+                    };
 
-                let encoded = Expression::ShiftLeft {
-                    loc,
-                    ty: Uint(64),
-                    left: Box::new(pointer_extend),
-                    right: Box::new(Expression::NumberLiteral {
+                    let pointer_extend = Expression::ZeroExt {
                         loc,
                         ty: Type::Uint(64),
-                        value: BigInt::from(32),
-                    }),
-                };
+                        expr: Box::new(pointer),
+                    };
 
-                let encoded = Expression::Add {
-                    loc,
-                    ty: Type::Uint(64),
-                    overflowing: true,
-                    left: Box::new(encoded),
-                    right: Box::new(Expression::NumberLiteral {
+                    let encoded = Expression::ShiftLeft {
+                        loc,
+                        ty: Uint(64),
+                        left: Box::new(pointer_extend),
+                        right: Box::new(Expression::NumberLiteral {
+                            loc,
+                            ty: Type::Uint(64),
+                            value: BigInt::from(32),
+                        }),
+                    };
+
+                    let encoded = Expression::Add {
                         loc,
                         ty: Type::Uint(64),
-                        value: BigInt::from(4),
-                    }),
-                };
+                        overflowing: true,
+                        left: Box::new(encoded),
+                        right: Box::new(Expression::NumberLiteral {
+                            loc,
+                            ty: Type::Uint(64),
+                            value: BigInt::from(4),
+                        }),
+                    };
 
-                let len = if let Expression::BytesLiteral { loc, ty: _, value } =
-                    *address_literal.clone()
-                {
                     let len = Expression::NumberLiteral {
                         loc,
                         ty: Type::Uint(64),
-                        value: BigInt::from(value.len() as u64),
+                        value: BigInt::from(len as u64),
                     };
 
                     let len = Expression::ShiftLeft {
@@ -475,7 +518,7 @@ pub fn soroban_encode_arg(
                         }),
                     };
 
-                    Expression::Add {
+                    let len = Expression::Add {
                         loc,
                         ty: Type::Uint(64),
                         left: Box::new(len),
@@ -485,45 +528,42 @@ pub fn soroban_encode_arg(
                             value: BigInt::from(4),
                         }),
                         overflowing: false,
-                    }
-                } else {
-                    todo!()
-                };
+                    };
 
-                let str_key_temp = vartab.temp_name("str_key", &Type::Uint(64));
-                let str_key_var = Expression::Variable {
-                    loc,
-                    ty: Type::Uint(64),
-                    var_no: str_key_temp,
-                };
+                    let str_key_temp = vartab.temp_name("str_key", &Type::Uint(64));
+                    let str_key_var = Expression::Variable {
+                        loc,
+                        ty: Type::Uint(64),
+                        var_no: str_key_temp,
+                    };
 
-                let soroban_str_key = Instr::Call {
-                    res: vec![str_key_temp],
-                    return_tys: vec![Type::Uint(64)],
-                    call: crate::codegen::cfg::InternalCallTy::HostFunction {
-                        name: HostFunctions::StringNewFromLinearMemory.name().to_string(),
-                    },
-                    args: vec![encoded.clone(), len.clone()],
-                };
+                    let soroban_str_key = Instr::Call {
+                        res: vec![str_key_temp],
+                        return_tys: vec![Type::Uint(64)],
+                        call: crate::codegen::cfg::InternalCallTy::HostFunction {
+                            name: HostFunctions::StringNewFromLinearMemory.name().to_string(),
+                        },
+                        args: vec![encoded.clone(), len.clone()],
+                    };
 
-                cfg.add(vartab, soroban_str_key);
+                    cfg.add(vartab, soroban_str_key);
 
-                let address_object = Instr::Call {
-                    res: vec![obj],
-                    return_tys: vec![Type::Uint(64)],
-                    call: crate::codegen::cfg::InternalCallTy::HostFunction {
-                        name: HostFunctions::StrKeyToAddr.name().to_string(),
-                    },
-                    args: vec![str_key_var],
-                };
+                    let address_object = Instr::Call {
+                        res: vec![obj],
+                        return_tys: vec![Type::Uint(64)],
+                        call: crate::codegen::cfg::InternalCallTy::HostFunction {
+                            name: HostFunctions::StrKeyToAddr.name().to_string(),
+                        },
+                        args: vec![str_key_var],
+                    };
 
-                address_object
-            } else {
-                Instr::Set {
+                    address_object
+                }
+                None => Instr::Set {
                     loc: Loc::Codegen,
                     res: obj,
                     expr: item.clone(),
-                }
+                },
             };
 
             instr
@@ -560,7 +600,30 @@ pub fn soroban_encode_arg(
                 expr: encoded,
             }
         }
-        _ => todo!("Type not yet supported"),
+        Type::Int(256) | Type::Uint(256) => Instr::Set {
+            loc: item.loc(),
+            res: obj,
+            expr: encode_i256(cfg, vartab, item),
+        },
+        Type::Enum(_) => {
+            let value = Expression::ZeroExt {
+                loc: item.loc(),
+                ty: Type::Uint(32),
+                expr: Box::new(item.clone()),
+            };
+
+            Instr::Set {
+                loc: item.loc(),
+                res: obj,
+                expr: soroban_encode_arg(value, cfg, vartab, ns),
+            }
+        }
+        Type::Struct(StructType::UserDefined(struct_no)) => Instr::Set {
+            loc: item.loc(),
+            res: obj,
+            expr: encode_struct(cfg, vartab, item.clone(), struct_no, ns),
+        },
+        _ => unreachable!("type without a Val conversion is rejected in sema"),
     };
 
     cfg.add(vartab, ret);
@@ -572,6 +635,321 @@ pub fn soroban_encode_arg(
     }
 }
 
+/// Encode a struct as a `Map` from the field names to the field values, which is how the Soroban
+/// SDK represents a `#[contracttype]` struct.
+fn encode_struct(
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+    item: Expression,
+    struct_no: usize,
+    ns: &Namespace,
+) -> Expression {
+    let ty = item.ty();
+
+    // Every field is loaded from the struct, so evaluate it only once
+    let struct_var = vartab.temp_anonymous(&ty);
+    cfg.add(
+        vartab,
+        Instr::Set {
+            loc: Loc::Codegen,
+            res: struct_var,
+            expr: item,
+        },
+    );
+
+    let map_var = vartab.temp_anonymous(&Type::Uint(64));
+    let map = Expression::Variable {
+        loc: Loc::Codegen,
+        ty: Type::Uint(64),
+        var_no: map_var,
+    };
+
+    cfg.add(
+        vartab,
+        Instr::Call {
+            res: vec![map_var],
+            return_tys: vec![Type::Uint(64)],
+            call: InternalCallTy::HostFunction {
+                name: HostFunctions::MapNew.name().to_string(),
+            },
+            args: vec![],
+        },
+    );
+
+    for (field_no, field) in ns.structs[struct_no].fields.iter().enumerate() {
+        let member = Expression::StructMember {
+            loc: Loc::Codegen,
+            ty: Type::Ref(Box::new(field.ty.clone())),
+            expr: Box::new(Expression::Variable {
+                loc: Loc::Codegen,
+                ty: ty.clone(),
+                var_no: struct_var,
+            }),
+            member: field_no,
+        };
+
+        let value = if field.ty.is_fixed_reference_type(ns) {
+            member
+        } else {
+            Expression::Load {
+                loc: Loc::Codegen,
+                ty: field.ty.clone(),
+                expr: Box::new(member),
+            }
+        };
+
+        // Strings are only encoded from a variable or a literal
+        let field_var = vartab.temp_anonymous(&field.ty);
+        cfg.add(
+            vartab,
+            Instr::Set {
+                loc: Loc::Codegen,
+                res: field_var,
+                expr: value,
+            },
+        );
+
+        let value = soroban_encode_arg(
+            Expression::Variable {
+                loc: Loc::Codegen,
+                ty: field.ty.clone(),
+                var_no: field_var,
+            },
+            cfg,
+            vartab,
+            ns,
+        );
+        let key = field_symbol(field.name_as_str(), cfg, vartab, ns);
+
+        cfg.add(
+            vartab,
+            Instr::Call {
+                res: vec![map_var],
+                return_tys: vec![Type::Uint(64)],
+                call: InternalCallTy::HostFunction {
+                    name: HostFunctions::MapPut.name().to_string(),
+                },
+                args: vec![map.clone(), key, value],
+            },
+        );
+    }
+
+    map
+}
+
+/// Decode a struct from a `Map` of field names to values. A `Void` value is decoded as a struct
+/// with all fields set to zero, so that reading a struct which was never written to storage
+/// behaves like it does on other targets.
+fn decode_struct(
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+    arg: Expression,
+    struct_no: usize,
+    ns: &Namespace,
+) -> Expression {
+    let ty = Type::Struct(StructType::UserDefined(struct_no));
+    let map = Expression::Variable {
+        loc: Loc::Codegen,
+        ty: Type::Uint(64),
+        var_no: val_to_var(arg, cfg, vartab),
+    };
+
+    let ret_var = vartab.temp_anonymous(&ty);
+
+    vartab.new_dirty_tracker();
+    let not_set = cfg.new_basic_block("struct_not_set".to_string());
+    let is_map = cfg.new_basic_block("struct_is_map".to_string());
+    let return_block = cfg.new_basic_block("finish".to_string());
+
+    let is_void = Expression::Equal {
+        loc: Loc::Codegen,
+        left: extract_tag(map.clone()).into(),
+        right: Expression::NumberLiteral {
+            loc: Loc::Codegen,
+            ty: Type::Uint(64),
+            value: BigInt::from(2_u64),
+        }
+        .into(),
+    };
+
+    cfg.add(
+        vartab,
+        Instr::BranchCond {
+            cond: is_void,
+            true_block: not_set,
+            false_block: is_map,
+        },
+    );
+
+    for block in [not_set, is_map] {
+        cfg.set_basic_block(block);
+
+        let mut values = Vec::new();
+
+        for field in &ns.structs[struct_no].fields {
+            let field_var = vartab.temp_anonymous(&Type::Uint(64));
+
+            let instr = if block == not_set {
+                Instr::Set {
+                    loc: Loc::Codegen,
+                    res: field_var,
+                    expr: Expression::NumberLiteral {
+                        loc: Loc::Codegen,
+                        ty: Type::Uint(64),
+                        value: BigInt::from(zero_val(&field.ty)),
+                    },
+                }
+            } else {
+                let key = field_symbol(field.name_as_str(), cfg, vartab, ns);
+
+                Instr::Call {
+                    res: vec![field_var],
+                    return_tys: vec![Type::Uint(64)],
+                    call: InternalCallTy::HostFunction {
+                        name: HostFunctions::MapGet.name().to_string(),
+                    },
+                    args: vec![map.clone(), key],
+                }
+            };
+
+            cfg.add(vartab, instr);
+
+            let value = Expression::Variable {
+                loc: Loc::Codegen,
+                ty: field.ty.clone(),
+                var_no: field_var,
+            };
+
+            values.push(soroban_decode_arg(value, cfg, vartab, ns));
+        }
+
+        cfg.add(
+            vartab,
+            Instr::Set {
+                loc: Loc::Codegen,
+                res: ret_var,
+                expr: Expression::StructLiteral {
+                    loc: Loc::Codegen,
+                    ty: ty.clone(),
+                    values,
+                },
+            },
+        );
+
+        cfg.add(
+            vartab,
+            Instr::Branch {
+                block: return_block,
+            },
+        );
+    }
+
+    cfg.set_basic_block(return_block);
+    cfg.set_phis(return_block, vartab.pop_dirty_tracker());
+
+    Expression::Variable {
+        loc: Loc::Codegen,
+        ty,
+        var_no: ret_var,
+    }
+}
+
+/// The symbol for a struct field name, used as the key of the map
+fn field_symbol(
+    name: &str,
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+    ns: &Namespace,
+) -> Expression {
+    soroban_encode_arg(
+        Expression::BytesLiteral {
+            loc: Loc::Codegen,
+            ty: Type::String,
+            value: name.as_bytes().to_vec(),
+        },
+        cfg,
+        vartab,
+        ns,
+    )
+}
+
+/// Create a Soroban vector object holding the given values
+pub(crate) fn new_vec(
+    values: Vec<Expression>,
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+) -> Expression {
+    let vec_var = vartab.temp_anonymous(&Type::Uint(64));
+    let vec = Expression::Variable {
+        loc: Loc::Codegen,
+        ty: Type::Uint(64),
+        var_no: vec_var,
+    };
+
+    cfg.add(
+        vartab,
+        Instr::Call {
+            res: vec![vec_var],
+            return_tys: vec![Type::Uint(64)],
+            call: InternalCallTy::HostFunction {
+                name: HostFunctions::VectorNew.name().to_string(),
+            },
+            args: vec![],
+        },
+    );
+
+    for value in values {
+        cfg.add(
+            vartab,
+            Instr::Call {
+                res: vec![vec_var],
+                return_tys: vec![Type::Uint(64)],
+                call: InternalCallTy::HostFunction {
+                    name: HostFunctions::VecPushBack.name().to_string(),
+                },
+                args: vec![vec.clone(), value],
+            },
+        );
+    }
+
+    vec
+}
+
+/// Store a `Val` in a new variable, so that it can be decoded as a different type
+fn val_to_var(val: Expression, cfg: &mut ControlFlowGraph, vartab: &mut Vartable) -> usize {
+    let var_no = vartab.temp_anonymous(&Type::Uint(64));
+
+    cfg.add(
+        vartab,
+        Instr::Set {
+            loc: Loc::Codegen,
+            res: var_no,
+            expr: val,
+        },
+    );
+
+    var_no
+}
+
+/// The `Val` for the zero value of a type. Strings are symbols, so the zero value is the empty
+/// symbol. Other objects have no zero value, so `Void` is used.
+pub(crate) fn zero_val(ty: &Type) -> u64 {
+    match ty {
+        Type::Bool => 0,       // Tag::False
+        Type::Uint(32) => 4,   // Tag::U32Val
+        Type::Enum(_) => 4,    // Tag::U32Val
+        Type::Int(32) => 5,    // Tag::I32Val
+        Type::Uint(64) => 6,   // Tag::U64Small
+        Type::Int(64) => 7,    // Tag::I64Small
+        Type::Uint(128) => 10, // Tag::U128Small
+        Type::Int(128) => 11,  // Tag::I128Small
+        Type::Uint(256) => 12, // Tag::U256Small
+        Type::Int(256) => 13,  // Tag::I256Small
+        Type::String => 14,    // Tag::SymbolSmall
+        _ => 2,                // Tag::Void
+    }
+}
+
 fn encode_i128(
     cfg: &mut ControlFlowGraph,
     vartab: &mut Vartable,
@@ -903,6 +1281,385 @@ fn decode_i128(cfg: &mut ControlFlowGraph, vartab: &mut Vartable, arg: Expressio
     ret
 }
 
+/// Encode a 256 bit integer as a `U256Val` or `I256Val`. Values which fit in 56 bits are stored
+/// in the `Val` itself, larger values are passed to the host as four 64 bit pieces.
+fn encode_i256(cfg: &mut ControlFlowGraph, vartab: &mut Vartable, item: Expression) -> Expression {
+    let ty = item.ty();
+    let signed = matches!(ty, Type::Int(_));
+
+    let (tag, from_pieces) = if signed {
+        (13, HostFunctions::ObjFromI256Pieces)
+    } else {
+        (12, HostFunctions::ObjFromU256Pieces)
+    };
+
+    // The value is used several times, so evaluate it only once
+    let value_var = vartab.temp_anonymous(&ty);
+    cfg.add(
+        vartab,
+        Instr::Set {
+            loc: Loc::Codegen,
+            res: value_var,
+            expr: item,
+        },
+    );
+
+    let value = Expression::Variable {
+        loc: Loc::Codegen,
+        ty: ty.clone(),
+        var_no: value_var,
+    };
+
+    let ret_var = vartab.temp_anonymous(&Type::Uint(64));
+
+    vartab.new_dirty_tracker();
+    let fits_in_56_bits = cfg.new_basic_block("fits_in_56_bits".to_string());
+    let should_be_in_host = cfg.new_basic_block("should_be_in_host".to_string());
+    let return_block = cfg.new_basic_block("finish".to_string());
+
+    // A signed value fits if it is in [-2^55, 2^55), which is the same as the value plus 2^55
+    // being in [0, 2^56)
+    let biased = if signed {
+        Expression::Add {
+            loc: Loc::Codegen,
+            ty: ty.clone(),
+            overflowing: true,
+            left: value.clone().into(),
+            right: Expression::NumberLiteral {
+                loc: Loc::Codegen,
+                ty: ty.clone(),
+                value: BigInt::from(1) << 55,
+            }
+            .into(),
+        }
+    } else {
+        value.clone()
+    };
+
+    let cond = Expression::Equal {
+        loc: Loc::Codegen,
+        left: Expression::ShiftRight {
+            loc: Loc::Codegen,
+            ty: ty.clone(),
+            left: biased.into(),
+            right: Expression::NumberLiteral {
+                loc: Loc::Codegen,
+                ty: ty.clone(),
+                value: BigInt::from(56),
+            }
+            .into(),
+            signed: false,
+        }
+        .into(),
+        right: Expression::NumberLiteral {
+            loc: Loc::Codegen,
+            ty: ty.clone(),
+            value: BigInt::zero(),
+        }
+        .into(),
+    };
+
+    cfg.add(
+        vartab,
+        Instr::BranchCond {
+            cond,
+            true_block: fits_in_56_bits,
+            false_block: should_be_in_host,
+        },
+    );
+
+    cfg.set_basic_block(fits_in_56_bits);
+
+    let small = Expression::Add {
+        loc: Loc::Codegen,
+        ty: Type::Uint(64),
+        overflowing: true,
+        left: Expression::ShiftLeft {
+            loc: Loc::Codegen,
+            ty: Type::Uint(64),
+            left: Expression::Trunc {
+                loc: Loc::Codegen,
+                ty: Type::Uint(64),
+                expr: value.clone().into(),
+            }
+            .into(),
+            right: Expression::NumberLiteral {
+                loc: Loc::Codegen,
+                ty: Type::Uint(64),
+                value: BigInt::from(8),
+            }
+            .into(),
+        }
+        .into(),
+        right: Expression::NumberLiteral {
+            loc: Loc::Codegen,
+            ty: Type::Uint(64),
+            value: BigInt::from(tag),
+        }
+        .into(),
+    };
+
+    cfg.add(
+        vartab,
+        Instr::Set {
+            loc: Loc::Codegen,
+            res: ret_var,
+            expr: small,
+        },
+    );
+
+    cfg.add(
+        vartab,
+        Instr::Branch {
+            block: return_block,
+        },
+    );
+
+    cfg.set_basic_block(should_be_in_host);
+
+    // the pieces are passed with the most significant first
+    let pieces = [192, 128, 64, 0]
+        .into_iter()
+        .map(|shift| Expression::Trunc {
+            loc: Loc::Codegen,
+            ty: Type::Uint(64),
+            expr: Expression::ShiftRight {
+                loc: Loc::Codegen,
+                ty: ty.clone(),
+                left: value.clone().into(),
+                right: Expression::NumberLiteral {
+                    loc: Loc::Codegen,
+                    ty: ty.clone(),
+                    value: BigInt::from(shift),
+                }
+                .into(),
+                signed: false,
+            }
+            .into(),
+        })
+        .collect();
+
+    cfg.add(
+        vartab,
+        Instr::Call {
+            res: vec![ret_var],
+            return_tys: vec![Type::Uint(64)],
+            call: InternalCallTy::HostFunction {
+                name: from_pieces.name().to_string(),
+            },
+            args: pieces,
+        },
+    );
+
+    cfg.add(
+        vartab,
+        Instr::Branch {
+            block: return_block,
+        },
+    );
+
+    cfg.set_basic_block(return_block);
+    cfg.set_phis(return_block, vartab.pop_dirty_tracker());
+
+    Expression::Variable {
+        loc: Loc::Codegen,
+        ty: Type::Uint(64),
+        var_no: ret_var,
+    }
+}
+
+/// Decode a `U256Val` or `I256Val`, which either holds a small value itself or refers to an
+/// object on the host.
+fn decode_i256(
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+    arg: Expression,
+    ty: Type,
+) -> Expression {
+    let signed = matches!(ty, Type::Int(_));
+
+    let (tag, to_pieces) = if signed {
+        (
+            13,
+            [
+                HostFunctions::ObjToI256HiHi,
+                HostFunctions::ObjToI256HiLo,
+                HostFunctions::ObjToI256LoHi,
+                HostFunctions::ObjToI256LoLo,
+            ],
+        )
+    } else {
+        (
+            12,
+            [
+                HostFunctions::ObjToU256HiHi,
+                HostFunctions::ObjToU256HiLo,
+                HostFunctions::ObjToU256LoHi,
+                HostFunctions::ObjToU256LoLo,
+            ],
+        )
+    };
+
+    let arg = Expression::Variable {
+        loc: Loc::Codegen,
+        ty: Type::Uint(64),
+        var_no: val_to_var(arg, cfg, vartab),
+    };
+
+    let ret_var = vartab.temp_anonymous(&ty);
+
+    vartab.new_dirty_tracker();
+    let val_is_small = cfg.new_basic_block("val_is_small".to_string());
+    let val_in_host = cfg.new_basic_block("val_in_host".to_string());
+    let return_block = cfg.new_basic_block("finish".to_string());
+
+    let is_small = Expression::Equal {
+        loc: Loc::Codegen,
+        left: extract_tag(arg.clone()).into(),
+        right: Expression::NumberLiteral {
+            loc: Loc::Codegen,
+            ty: Type::Uint(64),
+            value: BigInt::from(tag),
+        }
+        .into(),
+    };
+
+    cfg.add(
+        vartab,
+        Instr::BranchCond {
+            cond: is_small,
+            true_block: val_is_small,
+            false_block: val_in_host,
+        },
+    );
+
+    cfg.set_basic_block(val_is_small);
+
+    let payload = Expression::ShiftRight {
+        loc: Loc::Codegen,
+        ty: if signed {
+            Type::Int(64)
+        } else {
+            Type::Uint(64)
+        },
+        left: arg.clone().into(),
+        right: Expression::NumberLiteral {
+            loc: Loc::Codegen,
+            ty: Type::Uint(64),
+            value: BigInt::from(8),
+        }
+        .into(),
+        signed,
+    };
+
+    let extended = if signed {
+        Expression::SignExt {
+            loc: Loc::Codegen,
+            ty: ty.clone(),
+            expr: payload.into(),
+        }
+    } else {
+        Expression::ZeroExt {
+            loc: Loc::Codegen,
+            ty: ty.clone(),
+            expr: payload.into(),
+        }
+    };
+
+    cfg.add(
+        vartab,
+        Instr::Set {
+            loc: Loc::Codegen,
+            res: ret_var,
+            expr: extended,
+        },
+    );
+
+    cfg.add(
+        vartab,
+        Instr::Branch {
+            block: return_block,
+        },
+    );
+
+    cfg.set_basic_block(val_in_host);
+
+    let mut value: Option<Expression> = None;
+
+    for (host_function, shift) in to_pieces.into_iter().zip([192, 128, 64, 0]) {
+        let piece_var = vartab.temp_anonymous(&Type::Uint(64));
+
+        cfg.add(
+            vartab,
+            Instr::Call {
+                res: vec![piece_var],
+                return_tys: vec![Type::Uint(64)],
+                call: InternalCallTy::HostFunction {
+                    name: host_function.name().to_string(),
+                },
+                args: vec![arg.clone()],
+            },
+        );
+
+        let piece = Expression::ShiftLeft {
+            loc: Loc::Codegen,
+            ty: ty.clone(),
+            left: Expression::ZeroExt {
+                loc: Loc::Codegen,
+                ty: ty.clone(),
+                expr: Expression::Variable {
+                    loc: Loc::Codegen,
+                    ty: Type::Uint(64),
+                    var_no: piece_var,
+                }
+                .into(),
+            }
+            .into(),
+            right: Expression::NumberLiteral {
+                loc: Loc::Codegen,
+                ty: ty.clone(),
+                value: BigInt::from(shift),
+            }
+            .into(),
+        };
+
+        value = Some(match value {
+            Some(value) => Expression::BitwiseOr {
+                loc: Loc::Codegen,
+                ty: ty.clone(),
+                left: value.into(),
+                right: piece.into(),
+            },
+            None => piece,
+        });
+    }
+
+    cfg.add(
+        vartab,
+        Instr::Set {
+            loc: Loc::Codegen,
+            res: ret_var,
+            expr: value.unwrap(),
+        },
+    );
+
+    cfg.add(
+        vartab,
+        Instr::Branch {
+            block: return_block,
+        },
+    );
+
+    cfg.set_basic_block(return_block);
+    cfg.set_phis(return_block, vartab.pop_dirty_tracker());
+
+    Expression::Variable {
+        loc: Loc::Codegen,
+        ty,
+        var_no: ret_var,
+    }
+}
+
 fn extract_tag(arg: Expression) -> Expression {
     let bit_mask = Expression::NumberLiteral {
         loc: pt::Loc::Codegen,
@@ -1,12 +1,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
+mod evm;
 mod polkadot;
 mod solana;
+mod soroban;
 mod stylus;
 
 use crate::codegen::cfg::ControlFlowGraph;
+use crate::codegen::events::evm::EvmEventEmitter;
 use crate::codegen::events::polkadot::PolkadotEventEmitter;
 use crate::codegen::events::solana::SolanaEventEmitter;
+use crate::codegen::events::soroban::SorobanEventEmitter;
 use crate::codegen::events::stylus::StylusEventEmitter;
 use crate::codegen::vartable::Vartable;
 use crate::codegen::Options;
@@ -42,9 +46,7 @@ pub(super) fn new_event_emitter<'a>(
     ns: &'a Namespace,
 ) -> Box<dyn EventEmitter + 'a> {
     match ns.target {
-        Target::Polkadot { .. } | Target::EVM => {
-            Box::new(PolkadotEventEmitter { args, ns, event_no })
-        }
+        Target::Polkadot { .. } => Box::new(PolkadotEventEmitter { args, ns, event_no }),
 
         Target::Solana => Box::new(SolanaEventEmitter {
             loc: *loc,
@@ -53,8 +55,10 @@ pub(super) fn new_event_emitter<'a>(
             event_no,
         }),
 
-        Target::Soroban => todo!(),
+        Target::Soroban => Box::new(SorobanEventEmitter { args, ns, event_no }),
 
         Target::Stylus => Box::new(StylusEventEmitter { args, ns, event_no }),
+
+        Target::EVM | Target::PolkaVM => Box::new(EvmEventEmitter { args, ns, event_no }),
     }
 }
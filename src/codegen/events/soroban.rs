@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::codegen::cfg::{ControlFlowGraph, Instr};
+use crate::codegen::encoding::soroban_encoding::{new_vec, soroban_encode};
+use crate::codegen::events::EventEmitter;
+use crate::codegen::expression::expression;
+use crate::codegen::vartable::Vartable;
+use crate::codegen::{Expression, Options};
+use crate::sema::ast::{self, Function, Namespace, Type};
+use solang_parser::pt;
+
+/// Implements [EventEmitter] to handle the emission of events on Soroban.
+///
+/// The event is published with the `contract_event` host function. Unless the event is anonymous,
+/// the first topic is the event name as a symbol, followed by the indexed fields. The remaining
+/// fields are passed as a vector in the data of the event.
+pub(super) struct SorobanEventEmitter<'a> {
+    /// Arguments passed to the event
+    pub(super) args: &'a [ast::Expression],
+    pub(super) ns: &'a Namespace,
+    pub(super) event_no: usize,
+}
+
+impl EventEmitter for SorobanEventEmitter<'_> {
+    fn selector(&self, _emitting_contract_no: usize) -> Vec<u8> {
+        self.ns.events[self.event_no].id.name.as_bytes().to_vec()
+    }
+
+    fn emit(
+        &self,
+        contract_no: usize,
+        func: &Function,
+        cfg: &mut ControlFlowGraph,
+        vartab: &mut Vartable,
+        opt: &Options,
+    ) {
+        let loc = pt::Loc::Codegen;
+        let event = &self.ns.events[self.event_no];
+
+        let mut args = Vec::new();
+
+        if !event.anonymous {
+            args.push(Expression::BytesLiteral {
+                loc,
+                ty: Type::String,
+                value: self.selector(contract_no),
+            });
+        }
+
+        for ast_exp in self.args {
+            args.push(expression(
+                ast_exp,
+                cfg,
+                contract_no,
+                Some(func),
+                self.ns,
+                vartab,
+                opt,
+            ));
+        }
+
+        let mut encoded = soroban_encode(&loc, args, self.ns, vartab, cfg, false)
+            .2
+            .into_iter();
+
+        let (mut data, mut topics) = (Vec::new(), Vec::new());
+
+        if !event.anonymous {
+            topics.push(encoded.next().unwrap());
+        }
+
+        for (value, field) in encoded.zip(event.fields.iter()) {
+            if field.indexed {
+                topics.push(value);
+            } else {
+                data.push(value);
+            }
+        }
+
+        let topics = new_vec(topics, cfg, vartab);
+        let data = new_vec(data, cfg, vartab);
+
+        cfg.add(
+            vartab,
+            Instr::EmitEvent {
+                event_no: self.event_no,
+                data,
+                topics: vec![topics],
+            },
+        );
+    }
+}
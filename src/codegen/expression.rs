@@ -6,7 +6,9 @@ use super::revert::{
     assert_failure, expr_assert, log_runtime_error, require, PanicCode, SolidityError,
 };
 use super::storage::{
-    array_offset, array_pop, array_push, storage_slots_array_pop, storage_slots_array_push,
+    array_offset, array_pop, array_push, soroban_array_get, soroban_array_length,
+    soroban_array_pop, soroban_array_push, soroban_array_set, soroban_storage_object,
+    storage_slots_array_pop, storage_slots_array_push,
 };
 use super::{
     cfg::{ControlFlowGraph, Instr, InternalCallTy},
@@ -27,7 +29,7 @@ use crate::sema::{
     },
     diagnostics::Diagnostics,
     eval::{eval_const_number, eval_const_rational, eval_constants_in_expression},
-    expression::integers::bigint_to_expression,
+    expression::integers::{bigint_to_expression, fixed_range},
     expression::ResolveTo,
 };
 use crate::Target;
@@ -59,6 +61,9 @@ pub fn expression(
             // base storage variables should precede contract variables, not overlap
             ns.contracts[contract_no].get_storage_slot(*loc, *var_contract_no, *var_no, ns, None)
         }
+        ast::Expression::StorageLoad { loc, ty, expr } if soroban_storage_member(expr, ns) => {
+            soroban_load_storage_member(loc, ty, expr, cfg, contract_no, func, ns, vartab, opt)
+        }
         ast::Expression::StorageLoad { loc, ty, expr } => {
             let storage_type = storage_type(expr, ns);
             let storage = expression(expr, cfg, contract_no, func, ns, vartab, opt);
@@ -118,6 +123,11 @@ pub fn expression(
                     ty: ty.clone(),
                     value: r.to_integer(),
                 }
+            } else if ty.is_fixed() {
+                let l = expression(left, cfg, contract_no, func, ns, vartab, opt);
+                let r = expression(right, cfg, contract_no, func, ns, vartab, opt);
+
+                fixed_multiply(loc, ty, *unchecked, l, r, cfg, ns, vartab, opt)
             } else {
                 Expression::Multiply {
                     loc: *loc,
@@ -136,7 +146,9 @@ pub fn expression(
         } => {
             let l = expression(left, cfg, contract_no, func, ns, vartab, opt);
             let r = expression(right, cfg, contract_no, func, ns, vartab, opt);
-            if ty.is_signed_int(ns) {
+            if ty.is_fixed() {
+                fixed_divide(loc, ty, l, r, cfg, ns, vartab, opt)
+            } else if ty.is_signed_int(ns) {
                 Expression::SignedDivide {
                     loc: *loc,
                     ty: ty.clone(),
@@ -456,9 +468,7 @@ pub fn expression(
             call_args,
         } => {
             let address_res = vartab.temp_anonymous(&Type::Contract(*constructor_contract));
-            let success = ns
-                .target
-                .is_polkadot_stylus()
+            let success = (ns.target.is_polkadot_stylus() || ns.target == Target::PolkaVM)
                 .then(|| vartab.temp_name("success", &Type::Uint(32)));
             call_constructor(
                 loc,
@@ -475,7 +485,7 @@ pub fn expression(
                 cfg,
                 opt,
             );
-            if ns.target.is_polkadot_stylus() {
+            if ns.target.is_polkadot_stylus() || ns.target == Target::PolkaVM {
                 polkadot::RetCodeCheckBuilder::default()
                     .loc(*loc)
                     .msg("contract creation failed")
@@ -508,6 +518,11 @@ pub fn expression(
                 cfg_no: ns.contracts[contract_no].all_functions[function_no],
             }
         }
+        ast::Expression::StorageArrayLength { ty, array, .. }
+            if ns.target == Target::Soroban && soroban_storage_object(&array.ty()) =>
+        {
+            soroban_array_length(array, ty, cfg, contract_no, func, ns, vartab, opt)
+        }
         ast::Expression::StorageArrayLength {
             loc,
             ty,
@@ -643,6 +658,25 @@ pub fn expression(
                 expr: Box::new(struct_literal),
             }
         }
+        ast::Expression::ArraySlice {
+            loc,
+            ty,
+            array,
+            start,
+            end,
+        } => array_slice(
+            loc,
+            ty,
+            array,
+            start.as_deref(),
+            end.as_deref(),
+            cfg,
+            contract_no,
+            func,
+            ns,
+            vartab,
+            opt,
+        ),
         ast::Expression::Subscript {
             loc,
             ty: elem_ty,
@@ -790,6 +824,12 @@ pub fn expression(
             let codegen_expr = expression(expr, cfg, contract_no, func, ns, vartab, opt);
             array_literal_to_memory_array(loc, &codegen_expr, to, cfg, vartab)
         }
+        ast::Expression::Cast { loc, to, expr } if to.is_fixed() || expr.ty().is_fixed() => {
+            let from = expr.ty();
+            let expr = expression(expr, cfg, contract_no, func, ns, vartab, opt);
+
+            fixed_cast(loc, &from, to, expr, ns)
+        }
         ast::Expression::Cast { loc, to, expr } => {
             if expr.ty() == Type::Rational {
                 let (_, n) = eval_const_rational(expr, ns).unwrap();
@@ -845,7 +885,9 @@ pub fn expression(
             args,
         } => {
             if args[0].ty().is_contract_storage() {
-                if ns.target == Target::Solana || args[0].ty().is_storage_bytes() {
+                if ns.target == Target::Soroban {
+                    soroban_array_push(args, cfg, contract_no, func, ns, vartab, opt)
+                } else if ns.target == Target::Solana || args[0].ty().is_storage_bytes() {
                     array_push(loc, args, cfg, contract_no, func, ns, vartab, opt)
                 } else {
                     storage_slots_array_push(loc, args, cfg, contract_no, func, ns, vartab, opt)
@@ -877,7 +919,9 @@ pub fn expression(
             args,
         } => {
             if args[0].ty().is_contract_storage() {
-                if ns.target == Target::Solana || args[0].ty().is_storage_bytes() {
+                if ns.target == Target::Soroban {
+                    soroban_array_pop(loc, args, &ty[0], cfg, contract_no, func, ns, vartab, opt)
+                } else if ns.target == Target::Solana || args[0].ty().is_storage_bytes() {
                     array_pop(loc, args, &ty[0], cfg, contract_no, func, ns, vartab, opt)
                 } else {
                     storage_slots_array_pop(
@@ -936,7 +980,7 @@ pub fn expression(
             if opt.log_prints {
                 let expr = expression(&args[0], cfg, contract_no, func, ns, vartab, opt);
 
-                let to_print = if ns.target.is_polkadot() {
+                let to_print = if matches!(ns.target, Target::Polkadot { .. } | Target::PolkaVM) {
                     add_prefix_and_delimiter_to_print(expr)
                 } else {
                     expr
@@ -1021,7 +1065,7 @@ pub fn expression(
             kind: ast::Builtin::Gasprice,
             args: expr,
             ..
-        } if expr.len() == 1 && ns.target == Target::EVM => {
+        } if expr.len() == 1 && matches!(ns.target, Target::EVM | Target::PolkaVM) => {
             builtin_evm_gasprice(loc, expr, cfg, contract_no, func, ns, vartab, opt)
         }
         ast::Expression::Builtin {
@@ -1234,7 +1278,6 @@ fn post_incdec(
     opt: &Options,
 ) -> Expression {
     let res = vartab.temp_anonymous(ty);
-    let v = expression(var, cfg, contract_no, func, ns, vartab, opt);
 
     let storage_type = storage_type(var, ns);
 
@@ -1242,18 +1285,29 @@ fn post_incdec(
         Type::Ref(ty) => Expression::Load {
             loc: var.loc(),
             ty: ty.as_ref().clone(),
-            expr: Box::new(v),
+            expr: Box::new(expression(var, cfg, contract_no, func, ns, vartab, opt)),
         },
+        Type::StorageRef(_, ty) if soroban_storage_member(var, ns) => soroban_load_storage_member(
+            &var.loc(),
+            ty.as_ref(),
+            var,
+            cfg,
+            contract_no,
+            func,
+            ns,
+            vartab,
+            opt,
+        ),
         Type::StorageRef(_, ty) => load_storage(
             &var.loc(),
             ty.as_ref(),
-            v,
+            expression(var, cfg, contract_no, func, ns, vartab, opt),
             cfg,
             vartab,
             storage_type.clone(),
             ns,
         ),
-        _ => v,
+        _ => expression(var, cfg, contract_no, func, ns, vartab, opt),
     };
     cfg.add(
         vartab,
@@ -1305,7 +1359,6 @@ fn post_incdec(
             );
         }
         _ => {
-            let dest = expression(var, cfg, contract_no, func, ns, vartab, opt);
             let res = vartab.temp_anonymous(ty);
             cfg.add(
                 vartab,
@@ -1317,7 +1370,26 @@ fn post_incdec(
             );
 
             match var.ty() {
+                Type::StorageRef(..) if soroban_storage_member(var, ns) => {
+                    let value = Expression::Variable {
+                        loc: *loc,
+                        ty: ty.clone(),
+                        var_no: res,
+                    };
+
+                    soroban_store_storage_member(
+                        var,
+                        value,
+                        cfg,
+                        contract_no,
+                        func,
+                        ns,
+                        vartab,
+                        opt,
+                    );
+                }
                 Type::StorageRef(..) => {
+                    let dest = expression(var, cfg, contract_no, func, ns, vartab, opt);
                     let mut value = Expression::Variable {
                         loc: *loc,
                         ty: ty.clone(),
@@ -1339,6 +1411,7 @@ fn post_incdec(
                     );
                 }
                 Type::Ref(_) => {
+                    let dest = expression(var, cfg, contract_no, func, ns, vartab, opt);
                     cfg.add(
                         vartab,
                         Instr::Store {
@@ -1376,24 +1449,34 @@ fn pre_incdec(
     opt: &Options,
 ) -> Expression {
     let res = vartab.temp_anonymous(ty);
-    let v = expression(var, cfg, contract_no, func, ns, vartab, opt);
     let storage_type = storage_type(var, ns);
     let v = match var.ty() {
         Type::Ref(ty) => Expression::Load {
             loc: var.loc(),
             ty: ty.as_ref().clone(),
-            expr: Box::new(v),
+            expr: Box::new(expression(var, cfg, contract_no, func, ns, vartab, opt)),
         },
+        Type::StorageRef(_, ty) if soroban_storage_member(var, ns) => soroban_load_storage_member(
+            &var.loc(),
+            ty.as_ref(),
+            var,
+            cfg,
+            contract_no,
+            func,
+            ns,
+            vartab,
+            opt,
+        ),
         Type::StorageRef(_, ty) => load_storage(
             &var.loc(),
             ty.as_ref(),
-            v,
+            expression(var, cfg, contract_no, func, ns, vartab, opt),
             cfg,
             vartab,
             storage_type.clone(),
             ns,
         ),
-        _ => v,
+        _ => expression(var, cfg, contract_no, func, ns, vartab, opt),
     };
     let one = Box::new(Expression::NumberLiteral {
         loc: *loc,
@@ -1441,47 +1524,54 @@ fn pre_incdec(
                 },
             );
         }
-        _ => {
-            let dest = expression(var, cfg, contract_no, func, ns, vartab, opt);
+        _ => match var.ty() {
+            Type::StorageRef(..) if soroban_storage_member(var, ns) => {
+                let value = Expression::Variable {
+                    loc: *loc,
+                    ty: ty.clone(),
+                    var_no: res,
+                };
 
-            match var.ty() {
-                Type::StorageRef(..) => {
-                    let mut value = Expression::Variable {
-                        loc: *loc,
-                        ty: ty.clone(),
-                        var_no: res,
-                    };
+                soroban_store_storage_member(var, value, cfg, contract_no, func, ns, vartab, opt);
+            }
+            Type::StorageRef(..) => {
+                let dest = expression(var, cfg, contract_no, func, ns, vartab, opt);
+                let mut value = Expression::Variable {
+                    loc: *loc,
+                    ty: ty.clone(),
+                    var_no: res,
+                };
 
-                    if ns.target == Target::Soroban {
-                        value = soroban_encode_arg(value, cfg, vartab, ns)
-                    }
+                if ns.target == Target::Soroban {
+                    value = soroban_encode_arg(value, cfg, vartab, ns)
+                }
 
-                    cfg.add(
-                        vartab,
-                        Instr::SetStorage {
-                            value,
+                cfg.add(
+                    vartab,
+                    Instr::SetStorage {
+                        value,
+                        ty: ty.clone(),
+                        storage: dest,
+                        storage_type: storage_type.clone(),
+                    },
+                );
+            }
+            Type::Ref(_) => {
+                let dest = expression(var, cfg, contract_no, func, ns, vartab, opt);
+                cfg.add(
+                    vartab,
+                    Instr::Store {
+                        dest,
+                        data: Expression::Variable {
+                            loc: Loc::Codegen,
                             ty: ty.clone(),
-                            storage: dest,
-                            storage_type: storage_type.clone(),
-                        },
-                    );
-                }
-                Type::Ref(_) => {
-                    cfg.add(
-                        vartab,
-                        Instr::Store {
-                            dest,
-                            data: Expression::Variable {
-                                loc: Loc::Codegen,
-                                ty: ty.clone(),
-                                var_no: res,
-                            },
+                            var_no: res,
                         },
-                    );
-                }
-                _ => unreachable!(),
+                    },
+                );
             }
-        }
+            _ => unreachable!(),
+        },
     }
     Expression::Variable {
         loc: *loc,
@@ -1746,10 +1836,11 @@ fn payable_transfer(
         return Expression::Poison;
     }
 
-    let success = ns
-        .target
-        .is_polkadot()
-        .then(|| vartab.temp_name("success", &Type::Uint(32)));
+    let checked = matches!(
+        ns.target,
+        Target::Polkadot { .. } | Target::PolkaVM | Target::Stylus
+    );
+    let success = checked.then(|| vartab.temp_name("success", &Type::Uint(32)));
     let ins = Instr::ValueTransfer {
         success,
         address,
@@ -1757,7 +1848,7 @@ fn payable_transfer(
     };
     cfg.add(vartab, ins);
 
-    if ns.target.is_polkadot() {
+    if checked {
         polkadot::check_transfer_ret(loc, success.unwrap(), cfg, ns, opt, vartab, true);
     }
 
@@ -1810,6 +1901,16 @@ fn encode_many_with_selector(
     cfg: &mut ControlFlowGraph,
 ) -> Expression {
     let mut encoder_args: Vec<Expression> = Vec::with_capacity(args.len() + 1);
+    // The Ethereum ABI pads bytes4 to 32 bytes, so the selector needs its own type
+    let selector = if ns.target.is_ethereum_abi() {
+        Expression::Cast {
+            loc: *loc,
+            ty: Type::FunctionSelector,
+            expr: selector.into(),
+        }
+    } else {
+        selector
+    };
     encoder_args.push(selector);
     encoder_args.append(&mut args);
     abi_encode(loc, encoder_args, ns, vartab, cfg, false, true).0
@@ -2443,6 +2544,45 @@ fn expr_builtin(
             var
         }
 
+        // These have side effects, so they are calls rather than expressions
+        ast::Builtin::Ed25519Verify | ast::Builtin::PrngU64 => {
+            // Both host functions return an i64; verifying a signature returns a void `Val`
+            let function = if builtin == ast::Builtin::Ed25519Verify {
+                HostFunctions::VerifySigEd25519
+            } else {
+                HostFunctions::PrngU64InInclusiveRange
+            };
+
+            let res = vartab.temp_anonymous(&Type::Uint(64));
+
+            let args = args
+                .iter()
+                .map(|v| expression(v, cfg, contract_no, func, ns, vartab, opt))
+                .collect();
+
+            cfg.add(
+                vartab,
+                Instr::Call {
+                    res: vec![res],
+                    return_tys: vec![Type::Uint(64)],
+                    call: InternalCallTy::HostFunction {
+                        name: function.name().to_string(),
+                    },
+                    args,
+                },
+            );
+
+            if builtin == ast::Builtin::Ed25519Verify {
+                Expression::Poison
+            } else {
+                Expression::Variable {
+                    loc: *loc,
+                    ty: Type::Uint(64),
+                    var_no: res,
+                }
+            }
+        }
+
         // This is the trickiest host function to implement. The reason is takes `InvokerContractAuthEntry` enum as an argument.
         // let x = SubContractInvocation {
         //     context: ContractContext {
@@ -2864,6 +3004,7 @@ fn expr_builtin(
                 solang_parser::pt::StorageType::Temporary(_) => 0,
                 solang_parser::pt::StorageType::Persistent(_) => 1,
                 solang_parser::pt::StorageType::Instance(_) => panic!("Calling extendTtl() on instance storage is not allowed. Use `extendInstanceTtl()` instead."),
+                solang_parser::pt::StorageType::Transient(_) => panic!("Calling extendTtl() on transient storage is not allowed. Transient storage does not outlive the transaction."),
             };
 
             // append the storage type to the arguments
@@ -3066,86 +3207,791 @@ fn checking_trunc(
     }
 }
 
-fn format_string(
-    args: &[(FormatArg, ast::Expression)],
-    cfg: &mut ControlFlowGraph,
-    contract_no: usize,
-    func: Option<&Function>,
-    ns: &Namespace,
-    vartab: &mut Vartable,
+/// The 256-bit integer type used for the intermediate results of fixed point arithmetic
+fn fixed_wide_type(ty: &Type, ns: &Namespace) -> Type {
+    if ty.is_signed_int(ns) {
+        Type::Int(256)
+    } else {
+        Type::Uint(256)
+    }
+}
+
+/// Multiply a 256-bit value by 10^decimals. A single power of ten larger than 10^76 does
+/// not fit into 256 bits, so larger scales are applied in steps.
+fn scale_up(
     loc: &pt::Loc,
-    opt: &Options,
+    ty: &Type,
+    overflowing: bool,
+    mut value: Expression,
+    mut decimals: u32,
 ) -> Expression {
-    let args = args
-        .iter()
-        .map(|(spec, arg)| {
-            (
-                *spec,
-                expression(arg, cfg, contract_no, func, ns, vartab, opt),
-            )
-        })
-        .collect();
-    Expression::FormatString { loc: *loc, args }
+    while decimals > 0 {
+        let step = decimals.min(76);
+
+        value = Expression::Multiply {
+            loc: *loc,
+            ty: ty.clone(),
+            overflowing,
+            left: Box::new(value),
+            right: Box::new(Expression::NumberLiteral {
+                loc: *loc,
+                ty: ty.clone(),
+                value: BigInt::from(10).pow(step),
+            }),
+        };
+
+        decimals -= step;
+    }
+
+    value
 }
 
-fn conditional_operator(
+/// Divide a 256-bit value by 10^decimals, rounding towards zero. Truncating divisions
+/// compose, so larger scales are divided out in steps.
+fn scale_down(
     loc: &pt::Loc,
     ty: &Type,
-    cond: &ast::Expression,
+    signed: bool,
+    mut value: Expression,
+    mut decimals: u32,
+) -> Expression {
+    while decimals > 0 {
+        let step = decimals.min(76);
+        let left = Box::new(value);
+        let right = Box::new(Expression::NumberLiteral {
+            loc: *loc,
+            ty: ty.clone(),
+            value: BigInt::from(10).pow(step),
+        });
+
+        value = if signed {
+            Expression::SignedDivide {
+                loc: *loc,
+                ty: ty.clone(),
+                left,
+                right,
+            }
+        } else {
+            Expression::UnsignedDivide {
+                loc: *loc,
+                ty: ty.clone(),
+                left,
+                right,
+            }
+        };
+
+        decimals -= step;
+    }
+
+    value
+}
+
+/// Convert to or from a fixed point type. The value is rescaled to the decimals of the
+/// target type, rounding towards zero, and then truncated or extended like an integer.
+fn fixed_cast(
+    loc: &pt::Loc,
+    from: &Type,
+    to: &Type,
+    expr: Expression,
+    ns: &Namespace,
+) -> Expression {
+    let from_decimals = from.fixed_decimals().unwrap_or(0) as u32;
+    let to_decimals = to.fixed_decimals().unwrap_or(0) as u32;
+
+    if from_decimals == to_decimals {
+        return expr.cast(to, ns);
+    }
+
+    let signed = from.is_signed_int(ns);
+    let wide = fixed_wide_type(from, ns);
+    let value = expr.cast(&wide, ns);
+
+    let value = if from_decimals < to_decimals {
+        scale_up(loc, &wide, false, value, to_decimals - from_decimals)
+    } else {
+        scale_down(loc, &wide, signed, value, from_decimals - to_decimals)
+    };
+
+    value.cast(to, ns)
+}
+
+/// Multiply two fixed point numbers. The raw product has twice the decimals, so it is
+/// divided by the scale, rounding towards zero. The product of numbers wider than 128 bits
+/// does not fit into 256 bits, so it is kept as a 512-bit intermediate.
+fn fixed_multiply(
+    loc: &pt::Loc,
+    ty: &Type,
+    unchecked: bool,
+    left: Expression,
+    right: Expression,
     cfg: &mut ControlFlowGraph,
-    contract_no: usize,
-    func: Option<&Function>,
     ns: &Namespace,
     vartab: &mut Vartable,
-    left: &ast::Expression,
-    right: &ast::Expression,
     opt: &Options,
 ) -> Expression {
-    let cond = expression(cond, cfg, contract_no, func, ns, vartab, opt);
+    let (bits, decimals) = match ty {
+        Type::Fixed(bits, decimals) | Type::Ufixed(bits, decimals) => (*bits, *decimals),
+        _ => unreachable!(),
+    };
 
-    let pos = vartab.temp(
-        &pt::Identifier {
-            name: "ternary_result".to_owned(),
+    if bits > 128 {
+        let mut wide = WideArithmetic {
             loc: *loc,
-        },
-        ty,
-    );
-
-    vartab.new_dirty_tracker();
+            cfg,
+            vartab,
+            ns,
+            opt,
+        };
 
-    let left_block = cfg.new_basic_block("left_value".to_string());
-    let right_block = cfg.new_basic_block("right_value".to_string());
-    let done_block = cfg.new_basic_block("conditional_done".to_string());
+        let (left, left_sign) = wide.magnitude(left);
+        let (right, right_sign) = wide.magnitude(right);
+        let sign = wide.temp("sign", wide.xor(left_sign, right_sign));
 
-    cfg.add(
-        vartab,
-        Instr::BranchCond {
-            cond,
-            true_block: left_block,
-            false_block: right_block,
-        },
-    );
+        let (mut high, mut low) = wide.full_multiply(left, right);
+        let mut decimals = decimals as u32;
 
-    cfg.set_basic_block(left_block);
+        // 10^80 does not fit into 256 bits, so larger scales are divided out in steps
+        while decimals > 0 {
+            let step = decimals.min(76);
+            let scale = wide.number(BigInt::from(10).pow(step));
 
-    let expr = expression(left, cfg, contract_no, func, ns, vartab, opt);
+            let remainder = wide.temp("remainder", wide.remainder(high.clone(), scale.clone()));
+            let quotient = wide.temp("high", wide.divide(high, scale.clone()));
+            let narrow = wide.divide_narrow(remainder, low, scale);
 
-    cfg.add(
-        vartab,
-        Instr::Set {
-            loc: expr.loc(),
-            res: pos,
-            expr,
-        },
-    );
+            low = wide.temp("low", narrow);
+            high = quotient;
+            decimals -= step;
+        }
 
-    cfg.add(vartab, Instr::Branch { block: done_block });
+        if !unchecked {
+            let overflow = Expression::NotEqual {
+                loc: *loc,
+                left: Box::new(high),
+                right: Box::new(wide.number(BigInt::zero())),
+            };
+            wide.overflow_if(overflow);
+        }
 
-    cfg.set_basic_block(right_block);
+        return wide.fixed_result(ty, unchecked, low, sign);
+    }
 
-    let expr = expression(right, cfg, contract_no, func, ns, vartab, opt);
+    let wide = fixed_wide_type(ty, ns);
 
-    cfg.add(
+    let product = Expression::Multiply {
+        loc: *loc,
+        ty: wide.clone(),
+        // the product of two numbers of 128 bits or less always fits
+        overflowing: true,
+        left: Box::new(left.cast(&wide, ns)),
+        right: Box::new(right.cast(&wide, ns)),
+    };
+
+    let value = scale_down(loc, &wide, ty.is_signed_int(ns), product, decimals as u32);
+
+    fixed_checked_trunc(loc, ty, unchecked, value, cfg, ns, vartab, opt)
+}
+
+/// Divide two fixed point numbers. The dividend is multiplied by the scale first, so
+/// the quotient keeps its decimals. The result is rounded towards zero. If the scaled
+/// dividend might not fit into 256 bits, a long division is done instead.
+fn fixed_divide(
+    loc: &pt::Loc,
+    ty: &Type,
+    left: Expression,
+    right: Expression,
+    cfg: &mut ControlFlowGraph,
+    ns: &Namespace,
+    vartab: &mut Vartable,
+    opt: &Options,
+) -> Expression {
+    let (bits, decimals) = match ty {
+        Type::Fixed(bits, decimals) | Type::Ufixed(bits, decimals) => (*bits, *decimals),
+        _ => unreachable!(),
+    };
+
+    let scale_bits = BigInt::from(10).pow(decimals as u32).bits();
+
+    if bits as u64 + scale_bits >= 256 {
+        let mut wide = WideArithmetic {
+            loc: *loc,
+            cfg,
+            vartab,
+            ns,
+            opt,
+        };
+
+        let (left, left_sign) = wide.magnitude(left);
+        let (right, right_sign) = wide.magnitude(right);
+        let sign = wide.temp("sign", wide.xor(left_sign, right_sign));
+
+        let mut quotient = wide.temp("quotient", wide.divide(left.clone(), right.clone()));
+        let mut remainder = wide.temp("remainder", wide.remainder(left, right.clone()));
+        let mut decimals = decimals as u32;
+
+        // Long division, with up to 76 decimals per step. The remainder is smaller than the
+        // divisor, so the digits of each step fit into 256 bits.
+        while decimals > 0 {
+            let step = decimals.min(76);
+            let scale = wide.number(BigInt::from(10).pow(step));
+
+            let (high, low) = wide.full_multiply(remainder.clone(), scale.clone());
+            let digits = wide.divide_narrow(high, low, right.clone());
+            let shifted = Expression::Multiply {
+                loc: *loc,
+                ty: Type::Uint(256),
+                overflowing: false,
+                left: Box::new(quotient),
+                right: Box::new(scale.clone()),
+            };
+
+            remainder = wide.temp("remainder", wide.mulmod(remainder, scale, right.clone()));
+            quotient = wide.temp(
+                "quotient",
+                Expression::Add {
+                    loc: *loc,
+                    ty: Type::Uint(256),
+                    overflowing: false,
+                    left: Box::new(shifted),
+                    right: Box::new(digits),
+                },
+            );
+            decimals -= step;
+        }
+
+        return wide.fixed_result(ty, false, quotient, sign);
+    }
+
+    let wide = fixed_wide_type(ty, ns);
+
+    let dividend = scale_up(loc, &wide, true, left.cast(&wide, ns), decimals as u32);
+    let divisor = Box::new(right.cast(&wide, ns));
+
+    let value = if ty.is_signed_int(ns) {
+        Expression::SignedDivide {
+            loc: *loc,
+            ty: wide,
+            left: Box::new(dividend),
+            right: divisor,
+        }
+    } else {
+        Expression::UnsignedDivide {
+            loc: *loc,
+            ty: wide,
+            left: Box::new(dividend),
+            right: divisor,
+        }
+    };
+
+    fixed_checked_trunc(loc, ty, false, value, cfg, ns, vartab, opt)
+}
+
+/// Unsigned 256-bit arithmetic for fixed point numbers whose intermediate results need
+/// 512 bits. A 512-bit value is held as its high and low 256-bit halves, so this works on
+/// every target, including EVM.
+struct WideArithmetic<'a> {
+    loc: pt::Loc,
+    cfg: &'a mut ControlFlowGraph,
+    vartab: &'a mut Vartable,
+    ns: &'a Namespace,
+    opt: &'a Options,
+}
+
+impl WideArithmetic<'_> {
+    fn number(&self, value: BigInt) -> Expression {
+        Expression::NumberLiteral {
+            loc: self.loc,
+            ty: Type::Uint(256),
+            value,
+        }
+    }
+
+    /// Evaluate the expression into a temporary, so that it can be used more than once
+    fn temp(&mut self, name: &str, expr: Expression) -> Expression {
+        let ty = expr.ty();
+        let var_no = self.vartab.temp_name(name, &ty);
+
+        self.cfg.add(
+            self.vartab,
+            Instr::Set {
+                loc: self.loc,
+                res: var_no,
+                expr,
+            },
+        );
+
+        Expression::Variable {
+            loc: self.loc,
+            ty,
+            var_no,
+        }
+    }
+
+    fn add(&self, left: Expression, right: Expression) -> Expression {
+        Expression::Add {
+            loc: self.loc,
+            ty: Type::Uint(256),
+            overflowing: true,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    fn subtract(&self, left: Expression, right: Expression) -> Expression {
+        Expression::Subtract {
+            loc: self.loc,
+            ty: Type::Uint(256),
+            overflowing: true,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    fn multiply(&self, left: Expression, right: Expression) -> Expression {
+        Expression::Multiply {
+            loc: self.loc,
+            ty: Type::Uint(256),
+            overflowing: true,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    fn divide(&self, left: Expression, right: Expression) -> Expression {
+        Expression::UnsignedDivide {
+            loc: self.loc,
+            ty: Type::Uint(256),
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    fn remainder(&self, left: Expression, right: Expression) -> Expression {
+        Expression::UnsignedModulo {
+            loc: self.loc,
+            ty: Type::Uint(256),
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    fn and(&self, left: Expression, right: Expression) -> Expression {
+        Expression::BitwiseAnd {
+            loc: self.loc,
+            ty: Type::Uint(256),
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    fn or(&self, left: Expression, right: Expression) -> Expression {
+        Expression::BitwiseOr {
+            loc: self.loc,
+            ty: Type::Uint(256),
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    fn xor(&self, left: Expression, right: Expression) -> Expression {
+        Expression::BitwiseXor {
+            loc: self.loc,
+            ty: Type::Uint(256),
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    fn mulmod(&self, left: Expression, right: Expression, modulus: Expression) -> Expression {
+        Expression::Builtin {
+            loc: self.loc,
+            tys: vec![Type::Uint(256)],
+            kind: Builtin::MulMod,
+            args: vec![left, right, modulus],
+        }
+    }
+
+    fn addmod(&self, left: Expression, right: Expression, modulus: Expression) -> Expression {
+        Expression::Builtin {
+            loc: self.loc,
+            tys: vec![Type::Uint(256)],
+            kind: Builtin::AddMod,
+            args: vec![left, right, modulus],
+        }
+    }
+
+    /// One if left is less than right, zero otherwise
+    fn borrow(&self, left: Expression, right: Expression) -> Expression {
+        Expression::ZeroExt {
+            loc: self.loc,
+            ty: Type::Uint(256),
+            expr: Box::new(Expression::Less {
+                loc: self.loc,
+                signed: false,
+                left: Box::new(left),
+                right: Box::new(right),
+            }),
+        }
+    }
+
+    /// Split a fixed point number into its magnitude and a sign mask, which has all bits
+    /// set if the number is negative.
+    fn magnitude(&mut self, value: Expression) -> (Expression, Expression) {
+        let ty = value.ty();
+
+        if !ty.is_signed_int(self.ns) {
+            let value = self.temp("magnitude", value.cast(&Type::Uint(256), self.ns));
+
+            return (value, self.number(BigInt::zero()));
+        }
+
+        let value = self.temp("value", value.cast(&Type::Int(256), self.ns));
+        let sign = Expression::ShiftRight {
+            loc: self.loc,
+            ty: Type::Int(256),
+            left: Box::new(value.clone()),
+            right: Box::new(Expression::NumberLiteral {
+                loc: self.loc,
+                ty: Type::Int(256),
+                value: BigInt::from(255),
+            }),
+            signed: true,
+        };
+        let sign = self.temp("sign", sign.cast(&Type::Uint(256), self.ns));
+        let value = self.xor(value.cast(&Type::Uint(256), self.ns), sign.clone());
+        let magnitude = self.subtract(value, sign.clone());
+
+        (self.temp("magnitude", magnitude), sign)
+    }
+
+    /// The 512-bit product of two values, as its high and low halves
+    fn full_multiply(&mut self, left: Expression, right: Expression) -> (Expression, Expression) {
+        let low = self.temp("low", self.multiply(left.clone(), right.clone()));
+
+        // The product modulo 2^256 - 1 and modulo 2^256 differ by the high half
+        let all_ones = self.number((BigInt::one() << 256) - 1);
+        let mulmod = self.temp("mulmod", self.mulmod(left, right, all_ones));
+        let high = self.subtract(
+            self.subtract(mulmod.clone(), low.clone()),
+            self.borrow(mulmod, low.clone()),
+        );
+
+        (self.temp("high", high), low)
+    }
+
+    /// Divide the 512-bit value high:low by the divisor, where high is less than the
+    /// divisor so that the quotient fits into 256 bits. Once the remainder is subtracted
+    /// the division is exact, so it can be done by multiplying with the inverse of the
+    /// divisor modulo 2^256, like the well-known mulDiv by Remco Bloemen.
+    fn divide_narrow(
+        &mut self,
+        high: Expression,
+        low: Expression,
+        divisor: Expression,
+    ) -> Expression {
+        let divisor = self.temp("divisor", divisor);
+
+        // 2^256 modulo the divisor; this also aborts on division by zero
+        let zero = self.number(BigInt::zero());
+        let base = self.remainder(
+            self.subtract(zero.clone(), divisor.clone()),
+            divisor.clone(),
+        );
+        let remainder = self.addmod(
+            self.mulmod(high.clone(), base, divisor.clone()),
+            low.clone(),
+            divisor.clone(),
+        );
+        let remainder = self.temp("remainder", remainder);
+
+        let high = self.subtract(high, self.borrow(low.clone(), remainder.clone()));
+        let low = self.subtract(low, remainder);
+
+        // Divide out the largest power of two which divides the divisor, and shift the
+        // corresponding bits of the high half into the low half
+        let twos = self.and(
+            divisor.clone(),
+            self.subtract(zero.clone(), divisor.clone()),
+        );
+        let twos = self.temp("twos", twos);
+        let divisor = self.temp("odd_divisor", self.divide(divisor, twos.clone()));
+        let low = self.divide(low, twos.clone());
+        let shift = self.add(
+            self.divide(self.subtract(zero, twos.clone()), twos),
+            self.number(BigInt::one()),
+        );
+        let low = self.temp("low", self.or(low, self.multiply(high, shift)));
+
+        // The inverse of the odd divisor modulo 2^256, using Newton-Raphson. The initial
+        // value is correct for four bits, and each iteration doubles the correct bits.
+        let mut inverse = self.xor(
+            self.multiply(self.number(BigInt::from(3)), divisor.clone()),
+            self.number(BigInt::from(2)),
+        );
+
+        for _ in 0..6 {
+            let inverse_var = self.temp("inverse", inverse);
+            let step = self.subtract(
+                self.number(BigInt::from(2)),
+                self.multiply(divisor.clone(), inverse_var.clone()),
+            );
+
+            inverse = self.multiply(inverse_var, step);
+        }
+
+        self.multiply(low, inverse)
+    }
+
+    /// Abort with a math overflow panic if the condition is true
+    fn overflow_if(&mut self, cond: Expression) {
+        let out_of_bounds = self.cfg.new_basic_block("out_of_bounds".to_string());
+        let in_bounds = self.cfg.new_basic_block("in_bounds".to_string());
+
+        self.cfg.add(
+            self.vartab,
+            Instr::BranchCond {
+                cond,
+                true_block: out_of_bounds,
+                false_block: in_bounds,
+            },
+        );
+
+        self.cfg.set_basic_block(out_of_bounds);
+        log_runtime_error(
+            self.opt.log_runtime_errors,
+            "fixed point overflow",
+            self.loc,
+            self.cfg,
+            self.vartab,
+            self.ns,
+        );
+        let error = SolidityError::Panic(PanicCode::MathOverflow);
+        assert_failure(&self.loc, error, self.ns, self.cfg, self.vartab);
+
+        self.cfg.set_basic_block(in_bounds);
+    }
+
+    /// Apply the sign mask to the magnitude of a result, and convert it to the fixed point
+    /// type. Unless the arithmetic is unchecked, a magnitude which does not fit causes a math
+    /// overflow panic.
+    fn fixed_result(
+        &mut self,
+        ty: &Type,
+        unchecked: bool,
+        magnitude: Expression,
+        sign: Expression,
+    ) -> Expression {
+        let magnitude = self.temp("magnitude", magnitude);
+        let (_, max) = fixed_range(ty);
+
+        // every magnitude fits into ufixed256
+        if !unchecked && (ty.bits(self.ns) < 256 || ty.is_signed_int(self.ns)) {
+            // the magnitude of a negative number can be one more than the maximum
+            let limit = self.add(
+                self.number(max),
+                self.and(sign.clone(), self.number(BigInt::one())),
+            );
+
+            self.overflow_if(Expression::More {
+                loc: self.loc,
+                signed: false,
+                left: Box::new(magnitude.clone()),
+                right: Box::new(limit),
+            });
+        }
+
+        let value = self.subtract(self.xor(magnitude, sign.clone()), sign);
+
+        value.cast(ty, self.ns)
+    }
+}
+
+/// Truncate the 256-bit result of fixed point arithmetic to the fixed point type `ty`. Unless
+/// the arithmetic is unchecked, a value which does not fit causes a math overflow panic.
+fn fixed_checked_trunc(
+    loc: &pt::Loc,
+    ty: &Type,
+    unchecked: bool,
+    value: Expression,
+    cfg: &mut ControlFlowGraph,
+    ns: &Namespace,
+    vartab: &mut Vartable,
+    opt: &Options,
+) -> Expression {
+    let wide = value.ty();
+
+    if unchecked || ty.bits(ns) == 256 {
+        return value.cast(ty, ns);
+    }
+
+    let signed = ty.is_signed_int(ns);
+    let (min, max) = fixed_range(ty);
+
+    let pos = vartab.temp(
+        &pt::Identifier {
+            name: "value".to_owned(),
+            loc: *loc,
+        },
+        &wide,
+    );
+
+    cfg.add(
+        vartab,
+        Instr::Set {
+            loc: *loc,
+            res: pos,
+            expr: value,
+        },
+    );
+
+    let variable = Expression::Variable {
+        loc: *loc,
+        ty: wide.clone(),
+        var_no: pos,
+    };
+
+    let out_of_bounds = cfg.new_basic_block("out_of_bounds".to_string());
+    let in_bounds = cfg.new_basic_block("in_bounds".to_string());
+
+    if signed {
+        let above_min = cfg.new_basic_block("above_min".to_string());
+
+        cfg.add(
+            vartab,
+            Instr::BranchCond {
+                cond: Expression::Less {
+                    loc: *loc,
+                    signed,
+                    left: Box::new(variable.clone()),
+                    right: Box::new(Expression::NumberLiteral {
+                        loc: *loc,
+                        ty: wide.clone(),
+                        value: min,
+                    }),
+                },
+                true_block: out_of_bounds,
+                false_block: above_min,
+            },
+        );
+
+        cfg.set_basic_block(above_min);
+    }
+
+    cfg.add(
+        vartab,
+        Instr::BranchCond {
+            cond: Expression::More {
+                loc: *loc,
+                signed,
+                left: Box::new(variable.clone()),
+                right: Box::new(Expression::NumberLiteral {
+                    loc: *loc,
+                    ty: wide,
+                    value: max,
+                }),
+            },
+            true_block: out_of_bounds,
+            false_block: in_bounds,
+        },
+    );
+
+    cfg.set_basic_block(out_of_bounds);
+    log_runtime_error(
+        opt.log_runtime_errors,
+        "fixed point overflow",
+        *loc,
+        cfg,
+        vartab,
+        ns,
+    );
+    let error = SolidityError::Panic(PanicCode::MathOverflow);
+    assert_failure(loc, error, ns, cfg, vartab);
+
+    cfg.set_basic_block(in_bounds);
+
+    variable.cast(ty, ns)
+}
+
+fn format_string(
+    args: &[(FormatArg, ast::Expression)],
+    cfg: &mut ControlFlowGraph,
+    contract_no: usize,
+    func: Option<&Function>,
+    ns: &Namespace,
+    vartab: &mut Vartable,
+    loc: &pt::Loc,
+    opt: &Options,
+) -> Expression {
+    let args = args
+        .iter()
+        .map(|(spec, arg)| {
+            (
+                *spec,
+                expression(arg, cfg, contract_no, func, ns, vartab, opt),
+            )
+        })
+        .collect();
+    Expression::FormatString { loc: *loc, args }
+}
+
+fn conditional_operator(
+    loc: &pt::Loc,
+    ty: &Type,
+    cond: &ast::Expression,
+    cfg: &mut ControlFlowGraph,
+    contract_no: usize,
+    func: Option<&Function>,
+    ns: &Namespace,
+    vartab: &mut Vartable,
+    left: &ast::Expression,
+    right: &ast::Expression,
+    opt: &Options,
+) -> Expression {
+    let cond = expression(cond, cfg, contract_no, func, ns, vartab, opt);
+
+    let pos = vartab.temp(
+        &pt::Identifier {
+            name: "ternary_result".to_owned(),
+            loc: *loc,
+        },
+        ty,
+    );
+
+    vartab.new_dirty_tracker();
+
+    let left_block = cfg.new_basic_block("left_value".to_string());
+    let right_block = cfg.new_basic_block("right_value".to_string());
+    let done_block = cfg.new_basic_block("conditional_done".to_string());
+
+    cfg.add(
+        vartab,
+        Instr::BranchCond {
+            cond,
+            true_block: left_block,
+            false_block: right_block,
+        },
+    );
+
+    cfg.set_basic_block(left_block);
+
+    let expr = expression(left, cfg, contract_no, func, ns, vartab, opt);
+
+    cfg.add(
+        vartab,
+        Instr::Set {
+            loc: expr.loc(),
+            res: pos,
+            expr,
+        },
+    );
+
+    cfg.add(vartab, Instr::Branch { block: done_block });
+
+    cfg.set_basic_block(right_block);
+
+    let expr = expression(right, cfg, contract_no, func, ns, vartab, opt);
+
+    cfg.add(
         vartab,
         Instr::Set {
             loc: expr.loc(),
@@ -3216,6 +4062,38 @@ pub fn assign_single(
                 var_no: *var_no,
             }
         }
+        _ if soroban_storage_member(left, ns) => {
+            let ty = cfg_right.ty();
+            let pos = vartab.temp_anonymous(&ty);
+
+            cfg.add(
+                vartab,
+                Instr::Set {
+                    loc: pt::Loc::Codegen,
+                    res: pos,
+                    expr: cfg_right,
+                },
+            );
+
+            let value = Expression::Variable {
+                loc: left.loc(),
+                ty,
+                var_no: pos,
+            };
+
+            soroban_store_storage_member(
+                left,
+                value.clone(),
+                cfg,
+                contract_no,
+                func,
+                ns,
+                vartab,
+                opt,
+            );
+
+            value
+        }
         _ => {
             let left_ty = left.ty();
             let ty = cfg_right.ty();
@@ -3523,7 +4401,8 @@ pub fn emit_function_call(
                 },
             );
 
-            let success = if ns.target.is_polkadot() {
+            // The contracts and revive pallets return an error code, where 0 means success
+            let success = if matches!(ns.target, Target::Polkadot { .. } | Target::PolkaVM) {
                 let ret_code = Expression::Variable {
                     loc: *loc,
                     ty: Type::Uint(32),
@@ -3596,18 +4475,43 @@ pub fn emit_function_call(
                     }
                 };
 
-                let selector = dest_func.selector(ns, &caller_contract_no);
+                if ns.target == Target::Soroban {
+                    // Soroban functions are called by name, which is passed as a symbol
+                    let name = dest_func
+                        .contract_no
+                        .filter(|contract_no| {
+                            dest_func.mangled_name_contracts.contains(contract_no)
+                        })
+                        .map_or(&dest_func.id.name, |_| &dest_func.mangled_name);
 
-                tys.insert(0, Type::Bytes(selector.len() as u8));
+                    tys.insert(0, Type::String);
 
-                args.insert(
-                    0,
-                    Expression::BytesLiteral {
-                        loc: *loc,
-                        ty: Type::Bytes(selector.len() as u8),
-                        value: selector,
-                    },
-                );
+                    args.insert(
+                        0,
+                        Expression::BytesLiteral {
+                            loc: *loc,
+                            ty: Type::String,
+                            value: name.as_bytes().to_vec(),
+                        },
+                    );
+                } else {
+                    let selector = dest_func.selector(ns, &caller_contract_no);
+
+                    tys.insert(0, Type::Bytes(selector.len() as u8));
+
+                    args.insert(
+                        0,
+                        Expression::BytesLiteral {
+                            loc: *loc,
+                            ty: if ns.target.is_ethereum_abi() {
+                                Type::FunctionSelector
+                            } else {
+                                Type::Bytes(selector.len() as u8)
+                            },
+                            value: selector,
+                        },
+                    );
+                }
 
                 let (payload, _) = abi_encode(loc, args, ns, vartab, cfg, false, true);
 
@@ -3616,9 +4520,7 @@ pub fn emit_function_call(
                     .as_ref()
                     .map(|expr| expression(expr, cfg, caller_contract_no, func, ns, vartab, opt));
 
-                let success = ns
-                    .target
-                    .is_polkadot()
+                let success = matches!(ns.target, Target::Polkadot { .. } | Target::PolkaVM)
                     .then(|| vartab.temp_name("success", &Type::Uint(32)));
                 cfg.add(
                     vartab,
@@ -3637,7 +4539,7 @@ pub fn emit_function_call(
                     },
                 );
 
-                if ns.target.is_polkadot() {
+                if matches!(ns.target, Target::Polkadot { .. } | Target::PolkaVM) {
                     polkadot::RetCodeCheckBuilder::default()
                         .loc(*loc)
                         .msg("external call failed")
@@ -3703,9 +4605,7 @@ pub fn emit_function_call(
                     .flags
                     .as_ref()
                     .map(|expr| expression(expr, cfg, caller_contract_no, func, ns, vartab, opt));
-                let success = ns
-                    .target
-                    .is_polkadot()
+                let success = matches!(ns.target, Target::Polkadot { .. } | Target::PolkaVM)
                     .then(|| vartab.temp_name("success", &Type::Uint(32)));
                 cfg.add(
                     vartab,
@@ -3724,7 +4624,7 @@ pub fn emit_function_call(
                     },
                 );
 
-                if ns.target.is_polkadot() {
+                if matches!(ns.target, Target::Polkadot { .. } | Target::PolkaVM) {
                     polkadot::RetCodeCheckBuilder::default()
                         .loc(*loc)
                         .msg("external call failed")
@@ -3796,6 +4696,10 @@ fn array_subscript(
     opt: &Options,
 ) -> Expression {
     if array_ty.is_storage_bytes() {
+        if ns.target == Target::Soroban {
+            return soroban_array_get(loc, array, index, cfg, contract_no, func, ns, vartab, opt);
+        }
+
         return Expression::Subscript {
             loc: *loc,
             ty: elem_ty.clone(),
@@ -3817,11 +4721,17 @@ fn array_subscript(
                 expr: Box::new(array),
                 index: Box::new(index),
             },
-            Target::Polkadot { .. } | Target::Stylus => Expression::Keccak256 {
+            Target::Polkadot { .. } => Expression::Keccak256 {
                 loc: *loc,
                 ty: array_ty.clone(),
                 exprs: vec![array, index],
             },
+            // Like Solidity, the key is hashed followed by the slot
+            Target::Stylus | Target::PolkaVM => Expression::Keccak256 {
+                loc: *loc,
+                ty: array_ty.clone(),
+                exprs: vec![index, array],
+            },
         };
     }
 
@@ -3856,7 +4766,6 @@ fn array_subscript(
                             elem_ty: array_ty.storage_array_elem().deref_into(),
                         }
                     } else {
-                        // TODO(Soroban): Storage type here is None, since arrays are not yet supported in Soroban
                         let array_length = load_storage(
                             loc,
                             &Type::Uint(256),
@@ -4182,6 +5091,176 @@ fn array_subscript(
     }
 }
 
+/// Codegen for an array slice expression. The bounds are checked against the length of the
+/// array, and the slice refers to the elements of the array, so nothing is copied.
+fn array_slice(
+    loc: &pt::Loc,
+    ty: &Type,
+    array: &ast::Expression,
+    start: Option<&ast::Expression>,
+    end: Option<&ast::Expression>,
+    cfg: &mut ControlFlowGraph,
+    contract_no: usize,
+    func: Option<&Function>,
+    ns: &Namespace,
+    vartab: &mut Vartable,
+    opt: &Options,
+) -> Expression {
+    let array_ty = array.ty();
+    let array_expr = expression(array, cfg, contract_no, func, ns, vartab, opt);
+
+    let array = if ns.target == Target::EVM
+        && matches!(
+            array_expr,
+            Expression::Builtin {
+                kind: Builtin::Calldata,
+                ..
+            }
+        ) {
+        // EVM reads msg.data in place, rather than copying it into a vector
+        array_expr
+    } else {
+        // the array is used for both the length and the slice, so evaluate it only once
+        let array_var = vartab.temp_name("array", &array_ty);
+
+        cfg.add(
+            vartab,
+            Instr::Set {
+                loc: *loc,
+                res: array_var,
+                expr: array_expr,
+            },
+        );
+
+        Expression::Variable {
+            loc: *loc,
+            ty: array_ty.clone(),
+            var_no: array_var,
+        }
+    };
+
+    let array_length = Expression::Builtin {
+        loc: *loc,
+        tys: vec![Type::Uint(32)],
+        kind: Builtin::ArrayLength,
+        args: vec![array.clone()],
+    };
+
+    let start = match start {
+        Some(start) => expression(start, cfg, contract_no, func, ns, vartab, opt),
+        None => Expression::NumberLiteral {
+            loc: *loc,
+            ty: Type::Uint(32),
+            value: BigInt::zero(),
+        },
+    };
+
+    let end = match end {
+        Some(end) => expression(end, cfg, contract_no, func, ns, vartab, opt),
+        None => array_length.clone(),
+    };
+
+    let width = std::cmp::max(32, std::cmp::max(start.ty().bits(ns), end.ty().bits(ns)));
+    let bound_ty = Type::Uint(width);
+
+    let mut bound = |name: &str, expr: Expression, cfg: &mut ControlFlowGraph| {
+        let var_no = vartab.temp_name(name, &bound_ty);
+
+        cfg.add(
+            vartab,
+            Instr::Set {
+                loc: expr.loc(),
+                res: var_no,
+                expr: expr.cast(&bound_ty, ns),
+            },
+        );
+
+        Expression::Variable {
+            loc: *loc,
+            ty: bound_ty.clone(),
+            var_no,
+        }
+    };
+
+    let start = bound("slice_start", start, cfg);
+    let end = bound("slice_end", end, cfg);
+
+    let out_of_bounds = cfg.new_basic_block("slice_out_of_bounds".to_string());
+    let end_checked = cfg.new_basic_block("slice_end_checked".to_string());
+    let in_bounds = cfg.new_basic_block("slice_in_bounds".to_string());
+
+    cfg.add(
+        vartab,
+        Instr::BranchCond {
+            cond: Expression::More {
+                loc: *loc,
+                signed: false,
+                left: Box::new(start.clone()),
+                right: Box::new(end.clone()),
+            },
+            true_block: out_of_bounds,
+            false_block: end_checked,
+        },
+    );
+
+    cfg.set_basic_block(end_checked);
+
+    cfg.add(
+        vartab,
+        Instr::BranchCond {
+            cond: Expression::More {
+                loc: *loc,
+                signed: false,
+                left: Box::new(end.clone()),
+                right: Box::new(array_length.cast(&bound_ty, ns)),
+            },
+            true_block: out_of_bounds,
+            false_block: in_bounds,
+        },
+    );
+
+    cfg.set_basic_block(out_of_bounds);
+    log_runtime_error(
+        opt.log_runtime_errors,
+        "array slice out of bounds",
+        *loc,
+        cfg,
+        vartab,
+        ns,
+    );
+    let error = SolidityError::Panic(PanicCode::ArrayIndexOob);
+    assert_failure(loc, error, ns, cfg, vartab);
+
+    cfg.set_basic_block(in_bounds);
+
+    // the bounds are no larger than the array length, so they fit in 32 bits
+    let slice = vartab.temp_name("slice", ty);
+
+    cfg.add(
+        vartab,
+        Instr::Set {
+            loc: *loc,
+            res: slice,
+            expr: Expression::Builtin {
+                loc: *loc,
+                tys: vec![ty.clone()],
+                kind: Builtin::ArraySlice,
+                args: vec![
+                    array,
+                    start.cast(&Type::Uint(32), ns),
+                    end.cast(&Type::Uint(32), ns),
+                ],
+            },
+        },
+    );
+
+    Expression::Variable {
+        loc: *loc,
+        ty: ty.clone(),
+        var_no: slice,
+    }
+}
+
 fn string_location(
     loc: &StringLocation<ast::Expression>,
     cfg: &mut ControlFlowGraph,
@@ -4234,12 +5313,157 @@ pub fn load_storage(
     };
 
     if ns.target == Target::Soroban {
-        soroban_decode_arg(var, cfg, vartab)
+        soroban_decode_arg(var, cfg, vartab, ns)
     } else {
         var
     }
 }
 
+/// On Soroban, a struct in contract storage is stored as a single `Map` value, and a dynamic
+/// array or bytes as a single `Vec` or `Bytes` object. Their members and elements do not have
+/// storage slots of their own. Returns true if `expr` is such a member or element.
+fn soroban_storage_member(expr: &ast::Expression, ns: &Namespace) -> bool {
+    ns.target == Target::Soroban
+        && match expr {
+            ast::Expression::StructMember { expr, .. } => expr.ty().is_contract_storage(),
+            ast::Expression::Subscript { array_ty, .. } => soroban_storage_object(array_ty),
+            _ => false,
+        }
+}
+
+/// Load a member of a struct in Soroban storage by loading the entire struct, or an element of
+/// an array through the host object
+fn soroban_load_storage_member(
+    loc: &pt::Loc,
+    ty: &Type,
+    member: &ast::Expression,
+    cfg: &mut ControlFlowGraph,
+    contract_no: usize,
+    func: Option<&Function>,
+    ns: &Namespace,
+    vartab: &mut Vartable,
+    opt: &Options,
+) -> Expression {
+    let (var, field) = match member {
+        ast::Expression::StructMember { expr, field, .. } => (expr, field),
+        ast::Expression::Subscript { array, index, .. } => {
+            return soroban_array_get(loc, array, index, cfg, contract_no, func, ns, vartab, opt);
+        }
+        _ => unreachable!(),
+    };
+
+    let value = expression(
+        &ast::Expression::StorageLoad {
+            loc: *loc,
+            ty: var.ty().deref_any().clone(),
+            expr: var.clone(),
+        },
+        cfg,
+        contract_no,
+        func,
+        ns,
+        vartab,
+        opt,
+    );
+
+    if ty.is_fixed_reference_type(ns) {
+        Expression::StructMember {
+            loc: *loc,
+            ty: ty.clone(),
+            expr: Box::new(value),
+            member: *field,
+        }
+    } else {
+        Expression::Load {
+            loc: *loc,
+            ty: ty.clone(),
+            expr: Box::new(Expression::StructMember {
+                loc: *loc,
+                ty: Type::Ref(Box::new(ty.clone())),
+                expr: Box::new(value),
+                member: *field,
+            }),
+        }
+    }
+}
+
+/// Store a member of a struct in Soroban storage by loading the entire struct, modifying it in
+/// memory and storing it again. Elements of arrays are stored through the host object.
+fn soroban_store_storage_member(
+    member: &ast::Expression,
+    value: Expression,
+    cfg: &mut ControlFlowGraph,
+    contract_no: usize,
+    func: Option<&Function>,
+    ns: &Namespace,
+    vartab: &mut Vartable,
+    opt: &Options,
+) {
+    let (loc, var, field) = match member {
+        ast::Expression::StructMember {
+            loc, expr, field, ..
+        } => (loc, expr, field),
+        ast::Expression::Subscript {
+            loc, array, index, ..
+        } => {
+            return soroban_array_set(
+                loc,
+                array,
+                index,
+                value,
+                cfg,
+                contract_no,
+                func,
+                ns,
+                vartab,
+                opt,
+            );
+        }
+        _ => unreachable!(),
+    };
+
+    let value_ty = value.ty();
+    let whole = expression(
+        &ast::Expression::StorageLoad {
+            loc: *loc,
+            ty: var.ty().deref_any().clone(),
+            expr: var.clone(),
+        },
+        cfg,
+        contract_no,
+        func,
+        ns,
+        vartab,
+        opt,
+    );
+
+    // Nested structs are stored inline, so copy the value into the member
+    let data = if value_ty.is_fixed_reference_type(ns) {
+        Expression::Load {
+            loc: *loc,
+            ty: value_ty.clone(),
+            expr: Box::new(value),
+        }
+    } else {
+        value
+    };
+
+    cfg.add(
+        vartab,
+        Instr::Store {
+            dest: Expression::StructMember {
+                loc: *loc,
+                ty: Type::Ref(Box::new(value_ty)),
+                expr: Box::new(whole.clone()),
+                member: *field,
+            },
+            data,
+        },
+    );
+
+    assign_single(var, whole, cfg, contract_no, func, ns, vartab, opt);
+}
+
 fn array_literal_to_memory_array(
     loc: &pt::Loc,
     expr: &Expression,
@@ -4412,7 +5636,9 @@ fn add_prefix_and_delimiter_to_print(mut expr: Expression) -> Expression {
     }
 }
 
-fn storage_type(expr: &ast::Expression, ns: &Namespace) -> Option<pt::StorageType> {
+/// The Soroban storage type of a storage expression, which is the storage type of the contract
+/// variable it refers to
+pub(super) fn storage_type(expr: &ast::Expression, ns: &Namespace) -> Option<pt::StorageType> {
     match expr {
         ast::Expression::StorageVariable {
             loc: _,
@@ -4424,6 +5650,8 @@ fn storage_type(expr: &ast::Expression, ns: &Namespace) -> Option<pt::StorageTyp
 
             var.storage_type.clone()
         }
+        ast::Expression::Subscript { array: expr, .. }
+        | ast::Expression::StructMember { expr, .. } => storage_type(expr, ns),
         _ => None,
     }
 }
@@ -50,7 +50,7 @@ use encoding::soroban_encoding::soroban_encode_arg;
 use num_bigint::{BigInt, Sign};
 use num_rational::BigRational;
 use num_traits::{FromPrimitive, Zero};
-use solang_parser::diagnostics::Diagnostic;
+use solang_parser::diagnostics::{Diagnostic, Note};
 use solang_parser::{pt, pt::CodeLocation};
 
 // The sizeof(struct account_data_header)
@@ -115,14 +115,50 @@ pub enum HostFunctions {
     ObjToU128Hi64,
     ObjFromI128Pieces,
     ObjFromU128Pieces,
+    ObjToU256HiHi,
+    ObjToU256HiLo,
+    ObjToU256LoHi,
+    ObjToU256LoLo,
+    ObjToI256HiHi,
+    ObjToI256HiLo,
+    ObjToI256LoHi,
+    ObjToI256LoLo,
+    ObjFromU256Pieces,
+    ObjFromI256Pieces,
     RequireAuth,
     AuthAsCurrContract,
     MapNew,
     MapPut,
+    MapGet,
     VecPushBack,
+    VecPut,
+    VecGet,
+    VecLen,
+    VecPopBack,
+    VecBack,
+    BytesNewFromLinearMemory,
+    BytesNew,
+    BytesPut,
+    BytesGet,
+    BytesLen,
+    BytesPush,
+    BytesPop,
+    BytesBack,
     StringNewFromLinearMemory,
     StrKeyToAddr,
     GetCurrentContractAddress,
+    ContractEvent,
+    FailWithError,
+    GetLedgerSequence,
+    GetLedgerTimestamp,
+    GetLedgerNetworkId,
+    ComputeHashSha256,
+    ComputeHashKeccak256,
+    VerifySigEd25519,
+    RecoverKeyEcdsaSecp256k1,
+    PrngU64InInclusiveRange,
+    BytesCopyToLinearMemory,
+    CreateContractWithConstructor,
 }
 
 impl HostFunctions {
@@ -146,15 +182,51 @@ impl HostFunctions {
             HostFunctions::ObjToU128Hi64 => "i.5",
             HostFunctions::ObjFromI128Pieces => "i.6",
             HostFunctions::ObjFromU128Pieces => "i.3",
+            HostFunctions::ObjToU256HiHi => "i.c",
+            HostFunctions::ObjToU256HiLo => "i.d",
+            HostFunctions::ObjToU256LoHi => "i.e",
+            HostFunctions::ObjToU256LoLo => "i.f",
+            HostFunctions::ObjToI256HiHi => "i.j",
+            HostFunctions::ObjToI256HiLo => "i.k",
+            HostFunctions::ObjToI256LoHi => "i.l",
+            HostFunctions::ObjToI256LoLo => "i.m",
+            HostFunctions::ObjFromU256Pieces => "i.9",
+            HostFunctions::ObjFromI256Pieces => "i.g",
             HostFunctions::RequireAuth => "a.0",
             HostFunctions::AuthAsCurrContract => "a.3",
             HostFunctions::MapNewFromLinearMemory => "m.9",
             HostFunctions::MapNew => "m._",
             HostFunctions::MapPut => "m.0",
+            HostFunctions::MapGet => "m.1",
             HostFunctions::VecPushBack => "v.6",
+            HostFunctions::VecPut => "v.0",
+            HostFunctions::VecGet => "v.1",
+            HostFunctions::VecLen => "v.3",
+            HostFunctions::VecPopBack => "v.7",
+            HostFunctions::VecBack => "v.9",
+            HostFunctions::BytesNewFromLinearMemory => "b.3",
+            HostFunctions::BytesNew => "b.4",
+            HostFunctions::BytesPut => "b.5",
+            HostFunctions::BytesGet => "b.6",
+            HostFunctions::BytesLen => "b.8",
+            HostFunctions::BytesPush => "b.9",
+            HostFunctions::BytesPop => "b.a",
+            HostFunctions::BytesBack => "b.c",
             HostFunctions::StringNewFromLinearMemory => "b.i",
             HostFunctions::StrKeyToAddr => "a.1",
             HostFunctions::GetCurrentContractAddress => "x.7",
+            HostFunctions::ContractEvent => "x.1",
+            HostFunctions::FailWithError => "x.5",
+            HostFunctions::GetLedgerSequence => "x.3",
+            HostFunctions::GetLedgerTimestamp => "x.4",
+            HostFunctions::GetLedgerNetworkId => "x.6",
+            HostFunctions::ComputeHashSha256 => "c._",
+            HostFunctions::ComputeHashKeccak256 => "c.1",
+            HostFunctions::VerifySigEd25519 => "c.0",
+            HostFunctions::RecoverKeyEcdsaSecp256k1 => "c.2",
+            HostFunctions::PrngU64InInclusiveRange => "p.1",
+            HostFunctions::BytesCopyToLinearMemory => "b.1",
+            HostFunctions::CreateContractWithConstructor => "l.e",
         }
     }
 }
@@ -378,9 +450,33 @@ fn layout(contract_no: usize, ns: &mut Namespace) {
         BigInt::zero()
     };
 
+    // transient variables are numbered from zero in their own address space
+    let mut transient_slot = BigInt::zero();
+
     for base_contract_no in ns.contract_bases(contract_no) {
         for var_no in 0..ns.contracts[base_contract_no].variables.len() {
-            if !ns.contracts[base_contract_no].variables[var_no].constant {
+            if ns.contracts[base_contract_no].variables[var_no].is_transient() {
+                let ty = ns.contracts[base_contract_no].variables[var_no].ty.clone();
+
+                if ns.target == Target::Solana {
+                    let alignment = ty.align_of(ns);
+
+                    let offset = transient_slot.clone() % alignment;
+
+                    if offset > BigInt::zero() {
+                        transient_slot += alignment - offset;
+                    }
+                }
+
+                ns.contracts[contract_no].transient_layout.push(Layout {
+                    slot: transient_slot.clone(),
+                    contract_no: base_contract_no,
+                    var_no,
+                    ty: ty.clone(),
+                });
+
+                transient_slot += ty.storage_slots(ns);
+            } else if !ns.contracts[base_contract_no].variables[var_no].constant {
                 let ty = ns.contracts[base_contract_no].variables[var_no].ty.clone();
 
                 if ns.target == Target::Solana {
@@ -422,12 +518,55 @@ fn layout(contract_no: usize, ns: &mut Namespace) {
                         exp.loc(),
                         "Solana's runtime does not permit accounts larger than 10 MB".to_string(),
                     ));
+                } else {
+                    // The storage variables with a fixed size fit. Variables without a fixed size
+                    // live on the heap after them, which can outgrow any space. The account is
+                    // grown at runtime when that happens.
+                    let unbounded: Vec<Note> = ns.contracts[contract_no]
+                        .layout
+                        .iter()
+                        .filter(|layout| layout.ty.is_dynamic(ns) || layout.ty.is_sparse_solana(ns))
+                        .map(|layout| {
+                            let var = &ns.contracts[layout.contract_no].variables[layout.var_no];
+
+                            Note {
+                                loc: var.loc,
+                                message: format!(
+                                    "storage variable '{}' has no fixed size",
+                                    var.name
+                                ),
+                            }
+                        })
+                        .collect();
+
+                    if !unbounded.is_empty() {
+                        // the heap starts on an 8 byte boundary
+                        let heap_offset = (slot.clone() + 7u8) / 8u8 * 8u8;
+                        let heap_size = if value > heap_offset {
+                            value.clone() - heap_offset
+                        } else {
+                            BigInt::zero()
+                        };
+
+                        let message = if value < BigInt::from(MAXIMUM_ACCOUNT_SIZE) {
+                            format!("storage variables with no fixed size can exceed the {heap_size} bytes left of the {value} bytes of space, so the account may be grown while the contract runs")
+                        } else {
+                            format!("storage variables with no fixed size can exceed the {heap_size} bytes left of the {value} bytes of space, which cannot be grown")
+                        };
+
+                        ns.diagnostics.push(Diagnostic::warning_with_notes(
+                            exp.loc(),
+                            message,
+                            unbounded,
+                        ));
+                    }
                 }
             }
         }
     }
 
     ns.contracts[contract_no].fixed_layout_size = slot;
+    ns.contracts[contract_no].transient_layout_size = transient_slot;
 }
 
 trait LLVMName {
@@ -977,8 +1116,29 @@ impl Expression {
 
         let address_bits = ns.address_length as u16 * 8;
 
+        // Fixed point numbers are scaled integers, and only the representation is converted here.
+        // Any rescaling is done when generating code for the conversion in the AST.
+        match to {
+            Type::Fixed(bits, _) => {
+                return Expression::Cast {
+                    loc: self.loc(),
+                    ty: to.clone(),
+                    expr: Box::new(self.cast(&Type::Int(*bits), ns)),
+                }
+            }
+            Type::Ufixed(bits, _) => {
+                return Expression::Cast {
+                    loc: self.loc(),
+                    ty: to.clone(),
+                    expr: Box::new(self.cast(&Type::Uint(*bits), ns)),
+                }
+            }
+            _ => (),
+        }
+
         // When converting from literals, there is not need to trunc or extend.
         match (self, &from, to) {
+            (Expression::NumberLiteral { .. }, Type::Fixed(..) | Type::Ufixed(..), _) => (),
             (Expression::NumberLiteral { value, .. }, p, &Type::Uint(to_len))
                 if p.is_primitive() =>
             {
@@ -1085,6 +1245,9 @@ impl Expression {
                 Type::Int(from_len)
             }
 
+            (Type::Fixed(bits, _), _) => Type::Int(*bits),
+            (Type::Ufixed(bits, _), _) => Type::Uint(*bits),
+
             _ => from,
         };
 
@@ -1797,6 +1960,8 @@ pub enum Builtin {
     Accounts,
     AddMod,
     ArrayLength,
+    /// A view of part of an array or bytes; the arguments are the array, start and end
+    ArraySlice,
     Balance,
     Blake2_128,
     Blake2_256,
@@ -1851,6 +2016,12 @@ pub enum Builtin {
     ExtendTtl,
     ExtendInstanceTtl,
     AccessMapping,
+    Secp256k1Recover,
+    NetworkId,
+    SysvarRent,
+    SysvarEpochSchedule,
+    CurrentInstructionIndex,
+    InstructionAt,
 }
 
 impl From<&ast::Builtin> for Builtin {
@@ -1918,6 +2089,12 @@ impl From<&ast::Builtin> for Builtin {
             ast::Builtin::AuthAsCurrContract => Builtin::AuthAsCurrContract,
             ast::Builtin::ExtendTtl => Builtin::ExtendTtl,
             ast::Builtin::ExtendInstanceTtl => Builtin::ExtendInstanceTtl,
+            ast::Builtin::Secp256k1Recover => Builtin::Secp256k1Recover,
+            ast::Builtin::NetworkId => Builtin::NetworkId,
+            ast::Builtin::SysvarRent => Builtin::SysvarRent,
+            ast::Builtin::SysvarEpochSchedule => Builtin::SysvarEpochSchedule,
+            ast::Builtin::CurrentInstructionIndex => Builtin::CurrentInstructionIndex,
+            ast::Builtin::InstructionAt => Builtin::InstructionAt,
             _ => panic!("Builtin should not be in the cfg"),
         }
     }
@@ -7,14 +7,15 @@ use super::encoding::{abi_encode, create_encoder};
 use super::expression::expression;
 use super::Options;
 use super::{
-    cfg::{ControlFlowGraph, Instr},
+    cfg::{ControlFlowGraph, Instr, InternalCallTy, ReturnCode},
     vartable::Vartable,
+    HostFunctions,
 };
 
 use crate::codegen::Expression;
 use crate::sema::{
     ast,
-    ast::{FormatArg, Function, Namespace, Type},
+    ast::{ErrorDecl, FormatArg, Function, Namespace, Type},
     file::PathDisplay,
 };
 use crate::Target;
@@ -27,6 +28,8 @@ use tiny_keccak::{Hasher, Keccak};
 pub(crate) const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
 /// Signature of `Keccak256('Panic(uint256)')[:4]`
 pub(crate) const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+/// Anchor reserves the error codes below 6000 for the framework
+const ANCHOR_ERROR_CODE_OFFSET: u32 = 6000;
 
 /// Corresponds to the error types from the Solidity language.
 ///
@@ -52,9 +55,15 @@ pub enum SolidityError {
 impl SolidityError {
     /// Return the selector expression of the error.
     pub fn selector_expression(&self, ns: &Namespace) -> Expression {
+        let ty = if ns.target.is_ethereum_abi() {
+            Type::FunctionSelector
+        } else {
+            Type::Bytes(4)
+        };
+
         Expression::NumberLiteral {
             loc: Codegen,
-            ty: Type::Bytes(4),
+            ty,
             value: BigInt::from_bytes_be(Sign::Plus, &self.selector(ns)),
         }
     }
@@ -107,7 +116,9 @@ impl SolidityError {
                         }
                     })
                     .or_else(|| {
-                        abi_encode(loc, args, ns, vartab, cfg, false, false)
+                        // The Ethereum ABI tuple of the arguments starts after the selector
+                        let selector = ns.target.is_ethereum_abi();
+                        abi_encode(loc, args, ns, vartab, cfg, false, selector)
                             .0
                             .into()
                     })
@@ -131,11 +142,31 @@ impl SolidityError {
                         }
                     })
                     .or_else(|| {
-                        abi_encode(loc, args, ns, vartab, cfg, false, false)
+                        // The Ethereum ABI tuple of the arguments starts after the selector
+                        let selector = ns.target.is_ethereum_abi();
+                        abi_encode(loc, args, ns, vartab, cfg, false, selector)
                             .0
                             .into()
                     })
             }
+            Self::Panic(code) if ns.target.is_ethereum_abi() => {
+                // The panic code is a big endian uint256 following the selector
+                let mut bytes = PANIC_SELECTOR.to_vec();
+                bytes.resize(35, 0);
+                bytes.push(*code as u8);
+
+                Some(Expression::AllocDynamicBytes {
+                    loc: Codegen,
+                    ty: Type::Slice(Type::Bytes(1).into()),
+                    size: Expression::NumberLiteral {
+                        loc: Codegen,
+                        ty: Type::Uint(32),
+                        value: bytes.len().into(),
+                    }
+                    .into(),
+                    initializer: bytes.into(),
+                })
+            }
             Self::Panic(code) => {
                 let code = Expression::NumberLiteral {
                     loc: Codegen,
@@ -206,6 +237,95 @@ pub(super) fn assert_failure(
     cfg.add(vartab, Instr::AssertFailure { encoded_args })
 }
 
+/// The position of an error among the custom errors of a contract
+fn contract_error_index(contract_no: usize, error_no: usize, ns: &Namespace) -> u32 {
+    ns.contracts[contract_no]
+        .custom_errors
+        .iter()
+        .position(|no| *no == error_no)
+        .expect("the contract lists every error it reverts with") as u32
+}
+
+/// The code of a custom error on Soroban. The errors of a contract are numbered from 1.
+pub(crate) fn soroban_error_code(contract_no: usize, error_no: usize, ns: &Namespace) -> u32 {
+    contract_error_index(contract_no, error_no, ns) + 1
+}
+
+/// The code of a custom error on Solana. Anchor numbers the errors of a program from 6000.
+pub(crate) fn solana_error_code(contract_no: usize, error_no: usize, ns: &Namespace) -> u32 {
+    ANCHOR_ERROR_CODE_OFFSET + contract_error_index(contract_no, error_no, ns)
+}
+
+/// The message of a custom error is taken from its `@notice` tag, like the `#[msg]` of an Anchor error
+pub(crate) fn solana_error_message(error: &ErrorDecl) -> Option<&str> {
+    error
+        .tags
+        .iter()
+        .find(|tag| tag.tag == "notice")
+        .map(|tag| tag.value.as_str())
+}
+
+/// Build the `Error` value for a custom error, with the `ScErrorType::Contract` type
+fn soroban_contract_error(contract_no: usize, error_no: usize, ns: &Namespace) -> BigInt {
+    // Tag::Error is 3, the error type is in the minor bits and the code in the major bits
+    BigInt::from(soroban_error_code(contract_no, error_no, ns)) << 32 | BigInt::from(3)
+}
+
+/// Fail with a custom error. Solana returns it as an Anchor error code, logged the way Anchor does
+/// so that clients can find it. Soroban fails with a contract error code. The error data is dropped.
+fn custom_error_failure(
+    contract_no: usize,
+    error_no: usize,
+    ns: &Namespace,
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+) {
+    if ns.target == Target::Solana {
+        let code = solana_error_code(contract_no, error_no, ns);
+        let err = &ns.errors[error_no];
+        let message = format!(
+            "AnchorError occurred. Error Code: {}. Error Number: {}. Error Message: {}.",
+            err.name,
+            code,
+            solana_error_message(err).unwrap_or(&err.name),
+        );
+        cfg.add(
+            vartab,
+            Instr::Print {
+                expr: Expression::BytesLiteral {
+                    loc: Codegen,
+                    ty: Type::String,
+                    value: message.into_bytes(),
+                },
+            },
+        );
+        cfg.add(
+            vartab,
+            Instr::ReturnCode {
+                code: ReturnCode::CustomError(code),
+            },
+        );
+    } else {
+        let res = vartab.temp_anonymous(&Type::Uint(64));
+        cfg.add(
+            vartab,
+            Instr::Call {
+                res: vec![res],
+                return_tys: vec![Type::Uint(64)],
+                call: InternalCallTy::HostFunction {
+                    name: HostFunctions::FailWithError.name().to_string(),
+                },
+                args: vec![Expression::NumberLiteral {
+                    loc: Codegen,
+                    ty: Type::Uint(64),
+                    value: soroban_contract_error(contract_no, error_no, ns),
+                }],
+            },
+        );
+        cfg.add(vartab, Instr::AssertFailure { encoded_args: None });
+    }
+}
+
 pub(super) fn expr_assert(
     cfg: &mut ControlFlowGraph,
     args: &ast::Expression,
@@ -267,9 +387,12 @@ pub(super) fn require(
         .get(1)
         .map(|s| expression(s, cfg, contract_no, func, ns, vartab, opt));
 
-    // On Solana, Polkadot, and Stylus, print the reason
+    // On Solana, Polkadot, PolkaVM and Stylus, print the reason
     if opt.log_runtime_errors
-        && (ns.target == Target::Solana || ns.target.is_polkadot() || ns.target == Target::Stylus)
+        && matches!(
+            ns.target,
+            Target::Solana | Target::Polkadot { .. } | Target::PolkaVM | Target::Stylus
+        )
     {
         if let Some(expr) = expr.clone() {
             let prefix = b"runtime_error: ";
@@ -425,7 +548,15 @@ pub(super) fn revert(
         // No error number and no data means just "revert();" without any reason
         (None, None) => SolidityError::Empty,
     };
-    assert_failure(&Codegen, error, ns, cfg, vartab);
+
+    match error {
+        SolidityError::Custom { error_no, .. }
+            if ns.target == Target::Solana || ns.target == Target::Soroban =>
+        {
+            custom_error_failure(contract_no, error_no, ns, cfg, vartab)
+        }
+        _ => assert_failure(&Codegen, error, ns, cfg, vartab),
+    }
 }
 
 pub(crate) fn log_runtime_error(
@@ -3,7 +3,9 @@
 use crate::codegen::cfg::{ASTFunction, ControlFlowGraph, Instr, InternalCallTy};
 use crate::codegen::solana_accounts::account_from_number;
 use crate::codegen::{Builtin, Expression};
-use crate::sema::ast::{Contract, ExternalCallAccounts, Function, Namespace, SolanaAccount};
+use crate::sema::ast::{
+    AccountConstraint, Contract, ExternalCallAccounts, Function, Namespace, SolanaAccount,
+};
 use crate::sema::diagnostics::Diagnostics;
 use crate::sema::solana_accounts::BuiltinAccounts;
 use crate::sema::Recurse;
@@ -110,7 +112,13 @@ pub(crate) fn collect_accounts_from_contract(contract_no: usize, ns: &Namespace)
                     .move_index(data_account_index, 0);
             }
 
-            if func.is_constructor() && func.has_payer_annotation() {
+            // Creating an account or topping up its rent calls the system program
+            if func.has_payer_annotation()
+                || func
+                    .account_constraints
+                    .iter()
+                    .any(|constraint| matches!(constraint, AccountConstraint::InitIfNeeded { .. }))
+            {
                 func.solana_accounts.borrow_mut().insert(
                     BuiltinAccounts::SystemAccount.to_string(),
                     SolanaAccount {
@@ -464,7 +472,8 @@ fn check_expression(expr: &Expression, data: &mut RecurseData) -> bool {
             );
         }
         Expression::Builtin {
-            kind: Builtin::SignatureVerify,
+            kind:
+                Builtin::SignatureVerify | Builtin::CurrentInstructionIndex | Builtin::InstructionAt,
             ..
         } => {
             data.add_account(
@@ -0,0 +1,444 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::codegen::cfg::{ControlFlowGraph, Instr, InternalCallTy, ReturnCode};
+use crate::codegen::revert::string_to_expr;
+use crate::codegen::solana_accounts::account_management::{
+    account_field, account_meta_literal, retrieve_key_from_account_info,
+};
+use crate::codegen::solana_deploy::{
+    address_seeds, call_system_program, create_account_instruction, program_id_expression,
+    rent_exempt_lamports, signer_seeds,
+};
+use crate::codegen::vartable::Vartable;
+use crate::codegen::{expression, Builtin, Expression, Options};
+use crate::sema::ast::{
+    self, AccountConstraint, ArrayLength, Function, Namespace, StructType, Type,
+};
+use num_bigint::BigInt;
+use solang_parser::pt::Loc;
+
+/// The Anchor errors for violated account constraints
+/// https://github.com/coral-xyz/anchor/blob/v0.30.1/lang/src/error.rs
+#[derive(Clone, Copy)]
+enum ConstraintError {
+    HasOne,
+    Owner,
+    Seeds,
+    Space,
+    AccountOwnedByWrongProgram,
+}
+
+impl ConstraintError {
+    fn code(self) -> u32 {
+        match self {
+            ConstraintError::HasOne => 2001,
+            ConstraintError::Owner => 2004,
+            ConstraintError::Seeds => 2006,
+            ConstraintError::Space => 2019,
+            ConstraintError::AccountOwnedByWrongProgram => 3007,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            ConstraintError::HasOne => "ConstraintHasOne",
+            ConstraintError::Owner => "ConstraintOwner",
+            ConstraintError::Seeds => "ConstraintSeeds",
+            ConstraintError::Space => "ConstraintSpace",
+            ConstraintError::AccountOwnedByWrongProgram => "AccountOwnedByWrongProgram",
+        }
+    }
+
+    fn message(self) -> &'static str {
+        match self {
+            ConstraintError::HasOne => "A has one constraint was violated",
+            ConstraintError::Owner => "An owner constraint was violated",
+            ConstraintError::Seeds => "A seeds constraint was violated",
+            ConstraintError::Space => "A space constraint was violated",
+            ConstraintError::AccountOwnedByWrongProgram => {
+                "The given account is owned by a different program than expected"
+            }
+        }
+    }
+}
+
+/// Check the account constraints of a function when it is entered. The seeds are checked first, so
+/// that an account is only created at the right address, then the other constraints follow.
+pub(crate) fn account_constraints(
+    func: &Function,
+    contract_no: usize,
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+    ns: &Namespace,
+    opt: &Options,
+) {
+    let mut constraints = func.account_constraints.iter().collect::<Vec<_>>();
+
+    constraints.sort_by_key(|constraint| match constraint {
+        AccountConstraint::Seeds { .. } => 0,
+        AccountConstraint::InitIfNeeded { .. } => 1,
+        AccountConstraint::Owner { .. } => 2,
+        AccountConstraint::HasOne { .. } => 3,
+    });
+
+    for constraint in constraints {
+        let account = constraint.account();
+        let info = account_info(account, cfg, vartab);
+
+        match constraint {
+            AccountConstraint::Seeds { .. } => {
+                let seeds = pda_seeds(func, account, contract_no, cfg, vartab, ns, opt).unwrap();
+                let address = vartab.temp_name("pda", &Type::Address(false));
+                let create_program_address = ns
+                    .functions
+                    .iter()
+                    .position(|func| {
+                        func.loc_prototype == Loc::Builtin
+                            && func.id.name == "create_program_address"
+                    })
+                    .unwrap();
+
+                cfg.add(
+                    vartab,
+                    Instr::Call {
+                        res: vec![address],
+                        return_tys: vec![Type::Address(false)],
+                        call: InternalCallTy::Builtin {
+                            ast_func_no: create_program_address,
+                        },
+                        args: vec![
+                            address_seeds(seeds),
+                            program_id_expression(ns.contracts[contract_no].program_id.as_ref()),
+                        ],
+                    },
+                );
+
+                let cond = Expression::Equal {
+                    loc: Loc::Codegen,
+                    left: Expression::Variable {
+                        loc: Loc::Codegen,
+                        ty: Type::Address(false),
+                        var_no: address,
+                    }
+                    .into(),
+                    right: account_field(info, 0, Type::Address(false)).into(),
+                };
+
+                check(cond, ConstraintError::Seeds, account, cfg, vartab);
+            }
+            AccountConstraint::InitIfNeeded { payer, space, .. } => {
+                init_if_needed(
+                    func,
+                    account,
+                    info,
+                    payer,
+                    space,
+                    contract_no,
+                    cfg,
+                    vartab,
+                    ns,
+                    opt,
+                );
+            }
+            AccountConstraint::Owner { owner, .. } => {
+                let cond = Expression::Equal {
+                    loc: Loc::Codegen,
+                    left: expression(owner, cfg, contract_no, Some(func), ns, vartab, opt).into(),
+                    right: account_field(info, 3, Type::Address(false)).into(),
+                };
+
+                check(cond, ConstraintError::Owner, account, cfg, vartab);
+            }
+            AccountConstraint::HasOne { field, .. } => {
+                let cond = Expression::Equal {
+                    loc: Loc::Codegen,
+                    left: expression(field, cfg, contract_no, Some(func), ns, vartab, opt).into(),
+                    right: account_field(info, 0, Type::Address(false)).into(),
+                };
+
+                check(cond, ConstraintError::HasOne, account, cfg, vartab);
+            }
+        }
+    }
+}
+
+/// Create the account if it has no lamports. If it exists already, it must be owned by this
+/// program and have the right size.
+#[allow(clippy::too_many_arguments)]
+fn init_if_needed(
+    func: &Function,
+    account: &str,
+    info: Expression,
+    payer: &str,
+    space: &ast::Expression,
+    contract_no: usize,
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+    ns: &Namespace,
+    opt: &Options,
+) {
+    let space_var = vartab.temp_name("space", &Type::Uint(64));
+    let space_expr = expression(space, cfg, contract_no, Some(func), ns, vartab, opt);
+
+    cfg.add(
+        vartab,
+        Instr::Set {
+            loc: Loc::Codegen,
+            res: space_var,
+            expr: space_expr,
+        },
+    );
+
+    let space = Expression::Variable {
+        loc: Loc::Codegen,
+        ty: Type::Uint(64),
+        var_no: space_var,
+    };
+
+    let create = cfg.new_basic_block(format!("{account}_create"));
+    let exists = cfg.new_basic_block(format!("{account}_exists"));
+    let done = cfg.new_basic_block(format!("{account}_initialized"));
+
+    cfg.add(
+        vartab,
+        Instr::BranchCond {
+            cond: Expression::Equal {
+                loc: Loc::Codegen,
+                left: account_field(info.clone(), 1, Type::Uint(64)).into(),
+                right: Expression::NumberLiteral {
+                    loc: Loc::Codegen,
+                    ty: Type::Uint(64),
+                    value: BigInt::from(0),
+                }
+                .into(),
+            },
+            true_block: create,
+            false_block: exists,
+        },
+    );
+
+    cfg.set_basic_block(create);
+
+    let metas_ty = Type::Array(
+        Box::new(Type::Struct(StructType::AccountMeta)),
+        vec![ArrayLength::Fixed(BigInt::from(2))],
+    );
+    let metas = vartab.temp_name("metas", &metas_ty);
+    let payer_info = account_info(payer, cfg, vartab);
+
+    cfg.add(
+        vartab,
+        Instr::Set {
+            loc: Loc::Codegen,
+            res: metas,
+            expr: Expression::ArrayLiteral {
+                loc: Loc::Codegen,
+                ty: metas_ty.clone(),
+                dimensions: vec![2],
+                values: vec![
+                    account_meta_literal(retrieve_key_from_account_info(payer_info), true, true),
+                    account_meta_literal(retrieve_key_from_account_info(info.clone()), true, true),
+                ],
+            },
+        },
+    );
+
+    let instruction = create_account_instruction(
+        rent_exempt_lamports(space.clone()),
+        space.clone(),
+        program_id_expression(ns.contracts[contract_no].program_id.as_ref()),
+        cfg,
+        vartab,
+    );
+
+    // A program derived address is signed for by the program
+    let seeds = pda_seeds(func, account, contract_no, cfg, vartab, ns, opt)
+        .map(|seeds| signer_seeds(address_seeds(seeds)));
+
+    call_system_program(
+        Expression::Variable {
+            loc: Loc::Codegen,
+            ty: metas_ty,
+            var_no: metas,
+        },
+        instruction,
+        seeds,
+        cfg,
+        vartab,
+    );
+
+    cfg.add(vartab, Instr::Branch { block: done });
+
+    cfg.set_basic_block(exists);
+
+    let cond = Expression::Equal {
+        loc: Loc::Codegen,
+        left: program_id_expression(ns.contracts[contract_no].program_id.as_ref()).into(),
+        right: account_field(info.clone(), 3, Type::Address(false)).into(),
+    };
+
+    check(
+        cond,
+        ConstraintError::AccountOwnedByWrongProgram,
+        account,
+        cfg,
+        vartab,
+    );
+
+    let data = Expression::Load {
+        loc: Loc::Codegen,
+        ty: Type::Slice(Box::new(Type::Bytes(1))),
+        expr: Expression::StructMember {
+            loc: Loc::Codegen,
+            ty: Type::Ref(Box::new(Type::Slice(Box::new(Type::Bytes(1))))),
+            expr: info.into(),
+            member: 2,
+        }
+        .into(),
+    };
+
+    let cond = Expression::Equal {
+        loc: Loc::Codegen,
+        left: space.into(),
+        right: Expression::ZeroExt {
+            loc: Loc::Codegen,
+            ty: Type::Uint(64),
+            expr: Expression::Builtin {
+                loc: Loc::Codegen,
+                tys: vec![Type::Uint(32)],
+                kind: Builtin::ArrayLength,
+                args: vec![data],
+            }
+            .into(),
+        }
+        .into(),
+    };
+
+    check(cond, ConstraintError::Space, account, cfg, vartab);
+
+    cfg.add(vartab, Instr::Branch { block: done });
+
+    cfg.set_basic_block(done);
+}
+
+/// The seeds of the account including the bump, if it has a @seeds constraint
+fn pda_seeds(
+    func: &Function,
+    account: &str,
+    contract_no: usize,
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+    ns: &Namespace,
+    opt: &Options,
+) -> Option<Vec<Expression>> {
+    func.account_constraints
+        .iter()
+        .find_map(|constraint| match constraint {
+            AccountConstraint::Seeds {
+                account: name,
+                seeds,
+                bump,
+                ..
+            } if name == account => Some((seeds, bump)),
+            _ => None,
+        })
+        .map(|(seeds, bump)| {
+            let mut seeds = seeds
+                .iter()
+                .map(|seed| expression(seed, cfg, contract_no, Some(func), ns, vartab, opt))
+                .collect::<Vec<Expression>>();
+
+            let bump = ast::Expression::Cast {
+                loc: Loc::Codegen,
+                to: Type::Slice(Type::Bytes(1).into()),
+                expr: ast::Expression::BytesCast {
+                    loc: Loc::Codegen,
+                    to: Type::DynamicBytes,
+                    from: Type::Bytes(1),
+                    expr: bump.clone().into(),
+                }
+                .into(),
+            };
+
+            seeds.push(expression(
+                &bump,
+                cfg,
+                contract_no,
+                Some(func),
+                ns,
+                vartab,
+                opt,
+            ));
+
+            seeds
+        })
+}
+
+/// Retrieve the AccountInfo of a named account
+pub(super) fn account_info(
+    name: &str,
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+) -> Expression {
+    let ty = Type::Ref(Box::new(Type::Struct(StructType::AccountInfo)));
+    let var_no = vartab.temp_anonymous(&ty);
+
+    cfg.add(
+        vartab,
+        Instr::AccountAccess {
+            loc: Loc::Codegen,
+            name: name.to_string(),
+            var_no,
+        },
+    );
+
+    Expression::Variable {
+        loc: Loc::Codegen,
+        ty,
+        var_no,
+    }
+}
+
+/// Fail with the Anchor error if the condition does not hold
+fn check(
+    cond: Expression,
+    error: ConstraintError,
+    account: &str,
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+) {
+    let success = cfg.new_basic_block(format!("{account}_{}_ok", error.name()));
+    let fail = cfg.new_basic_block(format!("{account}_{}_violated", error.name()));
+
+    cfg.add(
+        vartab,
+        Instr::BranchCond {
+            cond,
+            true_block: success,
+            false_block: fail,
+        },
+    );
+
+    cfg.set_basic_block(fail);
+
+    cfg.add(
+        vartab,
+        Instr::Print {
+            expr: string_to_expr(format!(
+                "AnchorError caused by account: {account}. Error Code: {}. Error Number: {}. Error Message: {}.",
+                error.name(),
+                error.code(),
+                error.message()
+            )),
+        },
+    );
+
+    cfg.add(
+        vartab,
+        Instr::ReturnCode {
+            code: ReturnCode::CustomError(error.code()),
+        },
+    );
+
+    cfg.set_basic_block(success);
+}
@@ -227,8 +227,29 @@ pub(crate) fn retrieve_key_from_account_info(account_info: Expression) -> Expres
     }
 }
 
+/// Load a field of the AccountInfo which is stored by reference, like the key, lamports or owner
+pub(crate) fn account_field(info: Expression, member: usize, ty: Type) -> Expression {
+    let field = Expression::StructMember {
+        loc: Loc::Codegen,
+        ty: Type::Ref(Box::new(Type::Ref(Box::new(ty.clone())))),
+        expr: info.into(),
+        member,
+    };
+
+    Expression::Load {
+        loc: Loc::Codegen,
+        ty: ty.clone(),
+        expr: Expression::Load {
+            loc: Loc::Codegen,
+            ty: Type::Ref(Box::new(ty)),
+            expr: field.into(),
+        }
+        .into(),
+    }
+}
+
 /// This function automates the process of retrieving 'tx.accounts[index]'.
-fn index_accounts_vector(index: usize) -> Expression {
+pub(crate) fn index_accounts_vector(index: usize) -> Expression {
     let accounts_vector = Expression::Builtin {
         loc: Loc::Codegen,
         tys: vec![Type::Array(
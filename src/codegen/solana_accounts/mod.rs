@@ -1,7 +1,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub(super) mod account_collection;
+pub(super) mod account_constraints;
 pub(super) mod account_management;
+pub(super) mod rent;
 
 use crate::sema::solana_accounts::BuiltinAccounts;
 use base58::FromBase58;
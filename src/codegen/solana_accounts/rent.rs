@@ -0,0 +1,170 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::codegen::cfg::{ControlFlowGraph, Instr};
+use crate::codegen::solana_accounts::account_constraints::account_info;
+use crate::codegen::solana_accounts::account_management::{
+    account_field, account_meta_literal, retrieve_key_from_account_info,
+};
+use crate::codegen::solana_deploy::{
+    call_system_program, rent_exempt_lamports, transfer_instruction,
+};
+use crate::codegen::vartable::Vartable;
+use crate::codegen::{Builtin, Expression};
+use crate::sema::ast::{ArrayLength, StructType, Type};
+use crate::sema::solana_accounts::BuiltinAccounts;
+use num_bigint::BigInt;
+use solang_parser::pt::Loc;
+
+/// Writing to storage may grow the data account. Before the function returns, the payer transfers
+/// the lamports the data account needs to stay rent exempt at its new size.
+pub(crate) fn top_up_rent(payer: &str, cfg: &mut ControlFlowGraph, vartab: &mut Vartable) {
+    for block_no in 0..cfg.blocks.len() {
+        if !matches!(
+            cfg.blocks[block_no].instr.last(),
+            Some(Instr::Return { .. })
+        ) {
+            continue;
+        }
+
+        let return_instr = cfg.blocks[block_no].instr.pop().unwrap();
+
+        cfg.set_basic_block(block_no);
+
+        let data_account = account_info(BuiltinAccounts::DataAccount.as_str(), cfg, vartab);
+
+        let lamports = vartab.temp_name("lamports", &Type::Uint(64));
+        let minimum = vartab.temp_name("minimum_balance", &Type::Uint(64));
+
+        cfg.add(
+            vartab,
+            Instr::Set {
+                loc: Loc::Codegen,
+                res: lamports,
+                expr: account_field(data_account.clone(), 1, Type::Uint(64)),
+            },
+        );
+
+        let data = Expression::Load {
+            loc: Loc::Codegen,
+            ty: Type::Slice(Box::new(Type::Bytes(1))),
+            expr: Expression::StructMember {
+                loc: Loc::Codegen,
+                ty: Type::Ref(Box::new(Type::Slice(Box::new(Type::Bytes(1))))),
+                expr: data_account.clone().into(),
+                member: 2,
+            }
+            .into(),
+        };
+
+        cfg.add(
+            vartab,
+            Instr::Set {
+                loc: Loc::Codegen,
+                res: minimum,
+                expr: rent_exempt_lamports(Expression::ZeroExt {
+                    loc: Loc::Codegen,
+                    ty: Type::Uint(64),
+                    expr: Expression::Builtin {
+                        loc: Loc::Codegen,
+                        tys: vec![Type::Uint(32)],
+                        kind: Builtin::ArrayLength,
+                        args: vec![data],
+                    }
+                    .into(),
+                }),
+            },
+        );
+
+        let lamports = Expression::Variable {
+            loc: Loc::Codegen,
+            ty: Type::Uint(64),
+            var_no: lamports,
+        };
+        let minimum = Expression::Variable {
+            loc: Loc::Codegen,
+            ty: Type::Uint(64),
+            var_no: minimum,
+        };
+
+        let top_up = cfg.new_basic_block("top_up_rent".into());
+        let rent_exempt = cfg.new_basic_block("rent_exempt".into());
+
+        cfg.add(
+            vartab,
+            Instr::BranchCond {
+                cond: Expression::Less {
+                    loc: Loc::Codegen,
+                    signed: false,
+                    left: lamports.clone().into(),
+                    right: minimum.clone().into(),
+                },
+                true_block: top_up,
+                false_block: rent_exempt,
+            },
+        );
+
+        cfg.set_basic_block(top_up);
+
+        let instruction = transfer_instruction(
+            Expression::Subtract {
+                loc: Loc::Codegen,
+                ty: Type::Uint(64),
+                overflowing: true,
+                left: minimum.into(),
+                right: lamports.into(),
+            },
+            cfg,
+            vartab,
+        );
+
+        let metas_ty = Type::Array(
+            Box::new(Type::Struct(StructType::AccountMeta)),
+            vec![ArrayLength::Fixed(BigInt::from(2))],
+        );
+        let metas = vartab.temp_name("metas", &metas_ty);
+        let payer_info = account_info(payer, cfg, vartab);
+
+        cfg.add(
+            vartab,
+            Instr::Set {
+                loc: Loc::Codegen,
+                res: metas,
+                expr: Expression::ArrayLiteral {
+                    loc: Loc::Codegen,
+                    ty: metas_ty.clone(),
+                    dimensions: vec![2],
+                    values: vec![
+                        account_meta_literal(
+                            retrieve_key_from_account_info(payer_info),
+                            true,
+                            true,
+                        ),
+                        account_meta_literal(
+                            retrieve_key_from_account_info(data_account),
+                            false,
+                            true,
+                        ),
+                    ],
+                },
+            },
+        );
+
+        call_system_program(
+            Expression::Variable {
+                loc: Loc::Codegen,
+                ty: metas_ty,
+                var_no: metas,
+            },
+            instruction,
+            None,
+            cfg,
+            vartab,
+        );
+
+        cfg.add(vartab, Instr::Branch { block: rent_exempt });
+
+        cfg.set_basic_block(rent_exempt);
+
+        cfg.add(vartab, return_instr);
+    }
+}
@@ -372,31 +372,7 @@ pub(super) fn solana_deploy(
                 var_no: space_var,
             };
 
-            // https://github.com/solana-labs/solana/blob/718f433206c124da85a8aa2476c0753f351f9a28/sdk/program/src/rent.rs#L78-L82
-            let lamports = Expression::Multiply {
-                loc: Loc::Codegen,
-                ty: Type::Uint(64),
-                overflowing: false,
-                left: Expression::Add {
-                    loc: Loc::Codegen,
-                    ty: Type::Uint(64),
-                    overflowing: false,
-                    left: space.clone().into(),
-                    right: Expression::NumberLiteral {
-                        loc: Loc::Codegen,
-                        ty: Type::Uint(64),
-                        value: ACCOUNT_STORAGE_OVERHEAD.into(),
-                    }
-                    .into(),
-                }
-                .into(),
-                right: Expression::NumberLiteral {
-                    loc: Loc::Codegen,
-                    ty: Type::Uint(64),
-                    value: BigInt::from(LAMPORTS_PER_BYTE_YER * EXEMPTION_THRESHOLD),
-                }
-                .into(),
-            };
+            let lamports = rent_exempt_lamports(space.clone());
 
             (space, lamports)
         } else {
@@ -421,111 +397,12 @@ pub(super) fn solana_deploy(
             )
         };
 
-        let instruction_var = vartab.temp_name("instruction", &Type::DynamicBytes);
-        let instruction = Expression::Variable {
-            loc: Loc::Codegen,
-            ty: Type::DynamicBytes,
-            var_no: instruction_var,
-        };
-
-        // The CreateAccount instruction is 52 bytes (4 + 8 + 8 + 32)
-        let instruction_size = 52;
-
-        cfg.add(
+        let instruction = create_account_instruction(
+            lamports,
+            space,
+            program_id_expression(program_id),
+            cfg,
             vartab,
-            Instr::Set {
-                loc: Loc::Codegen,
-                res: instruction_var,
-                expr: Expression::AllocDynamicBytes {
-                    loc: Loc::Codegen,
-                    ty: Type::DynamicBytes,
-                    size: Expression::NumberLiteral {
-                        loc: Loc::Codegen,
-                        ty: Type::Uint(32),
-                        value: instruction_size.into(),
-                    }
-                    .into(),
-                    initializer: None,
-                },
-            },
-        );
-
-        // instruction CreateAccount
-        cfg.add(
-            vartab,
-            Instr::WriteBuffer {
-                buf: instruction.clone(),
-                value: Expression::NumberLiteral {
-                    loc: Loc::Codegen,
-                    ty: Type::Uint(32),
-                    value: BigInt::from(0),
-                },
-                offset: Expression::NumberLiteral {
-                    loc: Loc::Codegen,
-                    ty: Type::Uint(32),
-                    value: BigInt::from(0),
-                },
-            },
-        );
-
-        // lamports
-        cfg.add(
-            vartab,
-            Instr::WriteBuffer {
-                buf: instruction.clone(),
-                value: lamports,
-                offset: Expression::NumberLiteral {
-                    loc: Loc::Codegen,
-                    ty: Type::Uint(32),
-                    value: BigInt::from(4),
-                },
-            },
-        );
-
-        // space
-        cfg.add(
-            vartab,
-            Instr::WriteBuffer {
-                buf: instruction.clone(),
-                value: space,
-                offset: Expression::NumberLiteral {
-                    loc: Loc::Codegen,
-                    ty: Type::Uint(32),
-                    value: BigInt::from(12),
-                },
-            },
-        );
-
-        // owner
-        cfg.add(
-            vartab,
-            Instr::WriteBuffer {
-                buf: instruction.clone(),
-                value: if let Some(program_id) = program_id {
-                    Expression::NumberLiteral {
-                        loc: Loc::Codegen,
-                        ty: Type::Address(false),
-                        value: BigInt::from_bytes_be(Sign::Plus, program_id),
-                    }
-                } else {
-                    let addr_ptr = Expression::Builtin {
-                        loc: Loc::Codegen,
-                        tys: vec![Type::Ref(Box::new(Type::Address(false)))],
-                        kind: Builtin::GetAddress,
-                        args: vec![],
-                    };
-                    Expression::Load {
-                        loc: Loc::Codegen,
-                        ty: Type::Address(false),
-                        expr: Box::new(addr_ptr),
-                    }
-                },
-                offset: Expression::NumberLiteral {
-                    loc: Loc::Codegen,
-                    ty: Type::Uint(32),
-                    value: BigInt::from(20),
-                },
-            },
         );
 
         // seeds
@@ -552,64 +429,21 @@ pub(super) fn solana_deploy(
         }
 
         let seeds = if !seeds.is_empty() {
-            let ty = Type::Array(
-                Box::new(Type::Slice(Box::new(Type::Bytes(1)))),
-                vec![ArrayLength::Fixed(seeds.len().into())],
-            );
-
-            let address_seeds = Expression::ArrayLiteral {
-                loc: Loc::Codegen,
-                ty,
-                dimensions: vec![seeds.len() as u32],
-                values: seeds,
-            };
-
-            let ty = Type::Array(
-                Box::new(Type::Slice(Box::new(Type::Slice(Box::new(Type::Bytes(1)))))),
-                vec![ArrayLength::Fixed(1.into())],
-            );
-
-            Some(Expression::ArrayLiteral {
-                loc: Loc::Codegen,
-                ty,
-                dimensions: vec![1],
-                values: vec![address_seeds],
-            })
+            Some(signer_seeds(address_seeds(seeds)))
         } else {
             None
         };
 
-        cfg.add(
-            vartab,
-            Instr::ExternalCall {
+        call_system_program(
+            Expression::Variable {
                 loc: Loc::Codegen,
-                success: None,
-                seeds,
-                address: Some(Expression::NumberLiteral {
-                    loc: Loc::Codegen,
-                    ty: Type::Address(false),
-                    value: BigInt::from(0),
-                }), // SystemProgram 11111111111111111111111111111111
-                accounts: ExternalCallAccounts::Present(Expression::Variable {
-                    loc: Loc::Codegen,
-                    ty: metas_ty,
-                    var_no: metas,
-                }),
-                payload: instruction,
-                value: Expression::NumberLiteral {
-                    loc: Loc::Codegen,
-                    ty: Type::Uint(64),
-                    value: BigInt::from(0),
-                },
-                gas: Expression::NumberLiteral {
-                    loc: Loc::Codegen,
-                    ty: Type::Uint(64),
-                    value: BigInt::from(0),
-                },
-                callty: CallTy::Regular,
-                contract_function_no: None,
-                flags: None,
+                ty: metas_ty,
+                var_no: metas,
             },
+            instruction,
+            seeds,
+            cfg,
+            vartab,
         );
 
         cfg.add(vartab, Instr::Branch { block: account_ok });
@@ -668,3 +502,294 @@ pub(super) fn solana_deploy(
         },
     );
 }
+
+/// The address of the program being executed. If the program id is declared in the contract,
+/// it is a constant.
+pub(super) fn program_id_expression(program_id: Option<&Vec<u8>>) -> Expression {
+    if let Some(program_id) = program_id {
+        Expression::NumberLiteral {
+            loc: Loc::Codegen,
+            ty: Type::Address(false),
+            value: BigInt::from_bytes_be(Sign::Plus, program_id),
+        }
+    } else {
+        let addr_ptr = Expression::Builtin {
+            loc: Loc::Codegen,
+            tys: vec![Type::Ref(Box::new(Type::Address(false)))],
+            kind: Builtin::GetAddress,
+            args: vec![],
+        };
+        Expression::Load {
+            loc: Loc::Codegen,
+            ty: Type::Address(false),
+            expr: Box::new(addr_ptr),
+        }
+    }
+}
+
+/// The seeds of a program derived address, without the bump or with the bump as the last seed
+pub(super) fn address_seeds(seeds: Vec<Expression>) -> Expression {
+    let ty = Type::Array(
+        Box::new(Type::Slice(Box::new(Type::Bytes(1)))),
+        vec![ArrayLength::Fixed(seeds.len().into())],
+    );
+
+    Expression::ArrayLiteral {
+        loc: Loc::Codegen,
+        ty,
+        dimensions: vec![seeds.len() as u32],
+        values: seeds,
+    }
+}
+
+/// The signer seeds for an external call, which lets the program sign for a single program
+/// derived address
+pub(super) fn signer_seeds(address_seeds: Expression) -> Expression {
+    let ty = Type::Array(
+        Box::new(Type::Slice(Box::new(Type::Slice(Box::new(Type::Bytes(1)))))),
+        vec![ArrayLength::Fixed(1.into())],
+    );
+
+    Expression::ArrayLiteral {
+        loc: Loc::Codegen,
+        ty,
+        dimensions: vec![1],
+        values: vec![address_seeds],
+    }
+}
+
+/// The minimum balance for an account of `space` bytes to be rent exempt
+pub(super) fn rent_exempt_lamports(space: Expression) -> Expression {
+    // https://github.com/solana-labs/solana/blob/718f433206c124da85a8aa2476c0753f351f9a28/sdk/program/src/rent.rs#L78-L82
+    Expression::Multiply {
+        loc: Loc::Codegen,
+        ty: Type::Uint(64),
+        overflowing: false,
+        left: Expression::Add {
+            loc: Loc::Codegen,
+            ty: Type::Uint(64),
+            overflowing: false,
+            left: space.into(),
+            right: Expression::NumberLiteral {
+                loc: Loc::Codegen,
+                ty: Type::Uint(64),
+                value: ACCOUNT_STORAGE_OVERHEAD.into(),
+            }
+            .into(),
+        }
+        .into(),
+        right: Expression::NumberLiteral {
+            loc: Loc::Codegen,
+            ty: Type::Uint(64),
+            value: BigInt::from(LAMPORTS_PER_BYTE_YER * EXEMPTION_THRESHOLD),
+        }
+        .into(),
+    }
+}
+
+/// Encode the SystemProgram CreateAccount instruction
+pub(super) fn create_account_instruction(
+    lamports: Expression,
+    space: Expression,
+    owner: Expression,
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+) -> Expression {
+    let instruction_var = vartab.temp_name("instruction", &Type::DynamicBytes);
+    let instruction = Expression::Variable {
+        loc: Loc::Codegen,
+        ty: Type::DynamicBytes,
+        var_no: instruction_var,
+    };
+
+    // The CreateAccount instruction is 52 bytes (4 + 8 + 8 + 32)
+    let instruction_size = 52;
+
+    cfg.add(
+        vartab,
+        Instr::Set {
+            loc: Loc::Codegen,
+            res: instruction_var,
+            expr: Expression::AllocDynamicBytes {
+                loc: Loc::Codegen,
+                ty: Type::DynamicBytes,
+                size: Expression::NumberLiteral {
+                    loc: Loc::Codegen,
+                    ty: Type::Uint(32),
+                    value: instruction_size.into(),
+                }
+                .into(),
+                initializer: None,
+            },
+        },
+    );
+
+    // instruction CreateAccount
+    cfg.add(
+        vartab,
+        Instr::WriteBuffer {
+            buf: instruction.clone(),
+            value: Expression::NumberLiteral {
+                loc: Loc::Codegen,
+                ty: Type::Uint(32),
+                value: BigInt::from(0),
+            },
+            offset: Expression::NumberLiteral {
+                loc: Loc::Codegen,
+                ty: Type::Uint(32),
+                value: BigInt::from(0),
+            },
+        },
+    );
+
+    // lamports
+    cfg.add(
+        vartab,
+        Instr::WriteBuffer {
+            buf: instruction.clone(),
+            value: lamports,
+            offset: Expression::NumberLiteral {
+                loc: Loc::Codegen,
+                ty: Type::Uint(32),
+                value: BigInt::from(4),
+            },
+        },
+    );
+
+    // space
+    cfg.add(
+        vartab,
+        Instr::WriteBuffer {
+            buf: instruction.clone(),
+            value: space,
+            offset: Expression::NumberLiteral {
+                loc: Loc::Codegen,
+                ty: Type::Uint(32),
+                value: BigInt::from(12),
+            },
+        },
+    );
+
+    // owner
+    cfg.add(
+        vartab,
+        Instr::WriteBuffer {
+            buf: instruction.clone(),
+            value: owner,
+            offset: Expression::NumberLiteral {
+                loc: Loc::Codegen,
+                ty: Type::Uint(32),
+                value: BigInt::from(20),
+            },
+        },
+    );
+
+    instruction
+}
+
+/// Encode the SystemProgram Transfer instruction
+pub(super) fn transfer_instruction(
+    lamports: Expression,
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+) -> Expression {
+    let instruction_var = vartab.temp_name("instruction", &Type::DynamicBytes);
+    let instruction = Expression::Variable {
+        loc: Loc::Codegen,
+        ty: Type::DynamicBytes,
+        var_no: instruction_var,
+    };
+
+    // The Transfer instruction is 12 bytes (4 + 8)
+    cfg.add(
+        vartab,
+        Instr::Set {
+            loc: Loc::Codegen,
+            res: instruction_var,
+            expr: Expression::AllocDynamicBytes {
+                loc: Loc::Codegen,
+                ty: Type::DynamicBytes,
+                size: Expression::NumberLiteral {
+                    loc: Loc::Codegen,
+                    ty: Type::Uint(32),
+                    value: BigInt::from(12),
+                }
+                .into(),
+                initializer: None,
+            },
+        },
+    );
+
+    // instruction Transfer
+    cfg.add(
+        vartab,
+        Instr::WriteBuffer {
+            buf: instruction.clone(),
+            value: Expression::NumberLiteral {
+                loc: Loc::Codegen,
+                ty: Type::Uint(32),
+                value: BigInt::from(2),
+            },
+            offset: Expression::NumberLiteral {
+                loc: Loc::Codegen,
+                ty: Type::Uint(32),
+                value: BigInt::from(0),
+            },
+        },
+    );
+
+    // lamports
+    cfg.add(
+        vartab,
+        Instr::WriteBuffer {
+            buf: instruction.clone(),
+            value: lamports,
+            offset: Expression::NumberLiteral {
+                loc: Loc::Codegen,
+                ty: Type::Uint(32),
+                value: BigInt::from(4),
+            },
+        },
+    );
+
+    instruction
+}
+
+/// Call the SystemProgram with an encoded instruction. If one of the accounts is a program derived
+/// address, the program signs for it with the seeds.
+pub(super) fn call_system_program(
+    metas: Expression,
+    instruction: Expression,
+    seeds: Option<Expression>,
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+) {
+    cfg.add(
+        vartab,
+        Instr::ExternalCall {
+            loc: Loc::Codegen,
+            success: None,
+            seeds,
+            address: Some(Expression::NumberLiteral {
+                loc: Loc::Codegen,
+                ty: Type::Address(false),
+                value: BigInt::from(0),
+            }), // SystemProgram 11111111111111111111111111111111
+            accounts: ExternalCallAccounts::Present(metas),
+            payload: instruction,
+            value: Expression::NumberLiteral {
+                loc: Loc::Codegen,
+                ty: Type::Uint(64),
+                value: BigInt::from(0),
+            },
+            gas: Expression::NumberLiteral {
+                loc: Loc::Codegen,
+                ty: Type::Uint(64),
+                value: BigInt::from(0),
+            },
+            callty: CallTy::Regular,
+            contract_function_no: None,
+            flags: None,
+        },
+    );
+}
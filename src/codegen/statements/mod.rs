@@ -2,8 +2,9 @@
 
 use super::{
     cfg::{ControlFlowGraph, Instr},
+    encoding::soroban_encoding::soroban_encode_arg,
     events::new_event_emitter,
-    expression::{assign_single, emit_function_call, expression},
+    expression::{assign_single, emit_function_call, expression, storage_type},
     revert::revert,
     unused_variable::{
         should_remove_assignment, should_remove_variable, SideEffectsCheckParameters,
@@ -18,6 +19,7 @@ use crate::sema::ast::{
 };
 use crate::sema::solana_accounts::BuiltinAccounts;
 use crate::sema::Recurse;
+use crate::Target;
 use num_bigint::BigInt;
 use num_traits::Zero;
 use solang_parser::pt::{self, CodeLocation, Loc, Loc::Codegen};
@@ -216,13 +218,38 @@ pub(crate) fn statement(
         Statement::Delete(_, ty, expr) => {
             let var_expr = expression(expr, cfg, contract_no, Some(func), ns, vartab, opt);
 
-            cfg.add(
-                vartab,
-                Instr::ClearStorage {
-                    ty: ty.clone(),
-                    storage: var_expr,
-                },
-            );
+            if let Some(storage_type @ pt::StorageType::Transient(_)) = storage_type(expr, ns) {
+                // transient storage cannot be cleared, so the zero value is written instead
+                let ty = match ty {
+                    Type::Contract(_) => Type::Address(false),
+                    Type::UserType(no) => ns.user_types[*no].ty.clone(),
+                    _ => ty.clone(),
+                };
+
+                let mut value = ty.default(ns).unwrap();
+
+                if ns.target == Target::Soroban {
+                    value = soroban_encode_arg(value, cfg, vartab, ns);
+                }
+
+                cfg.add(
+                    vartab,
+                    Instr::SetStorage {
+                        ty,
+                        value,
+                        storage: var_expr,
+                        storage_type: Some(storage_type),
+                    },
+                );
+            } else {
+                cfg.add(
+                    vartab,
+                    Instr::ClearStorage {
+                        ty: ty.clone(),
+                        storage: var_expr,
+                    },
+                );
+            }
         }
         Statement::Break(_) => {
             cfg.add(
@@ -1062,11 +1089,13 @@ impl Type {
     /// for example a reference to a variable in storage.
     pub fn default(&self, ns: &Namespace) -> Option<Expression> {
         match self {
-            Type::Address(_) | Uint(_) | Type::Int(_) => Some(Expression::NumberLiteral {
-                loc: Codegen,
-                ty: self.clone(),
-                value: BigInt::from(0),
-            }),
+            Type::Address(_) | Uint(_) | Type::Int(_) | Type::Fixed(..) | Type::Ufixed(..) => {
+                Some(Expression::NumberLiteral {
+                    loc: Codegen,
+                    ty: self.clone(),
+                    value: BigInt::from(0),
+                })
+            }
             Type::Bool => Some(Expression::BoolLiteral {
                 loc: Codegen,
                 value: false,
@@ -15,6 +15,7 @@ use crate::sema::ast::{
     self, CallTy, ExternalCallAccounts, Function, Namespace, RetrieveType, TryCatch, Type,
     Type::Uint,
 };
+use crate::Target;
 use num_bigint::{BigInt, Sign};
 use num_traits::Zero;
 use solang_parser::pt::{self, CodeLocation, Loc::Codegen};
@@ -32,7 +33,7 @@ pub(super) fn try_catch(
     return_override: Option<&Instr>,
     opt: &Options,
 ) {
-    if !ns.target.is_polkadot() {
+    if !matches!(ns.target, Target::Polkadot { .. } | Target::PolkaVM) {
         unimplemented!()
     }
 
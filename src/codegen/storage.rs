@@ -1,22 +1,25 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::codegen::Expression;
+use crate::codegen::encoding::soroban_encoding::{
+    soroban_decode_arg, soroban_encode_arg, zero_val,
+};
+use crate::codegen::{Expression, HostFunctions};
 use crate::sema::ast;
 use num_bigint::BigInt;
 use num_traits::FromPrimitive;
 use num_traits::One;
 use num_traits::Zero;
 
-use super::expression::{expression, load_storage};
+use super::expression::{expression, load_storage, storage_type};
 use super::revert::PanicCode;
 use super::revert::SolidityError;
 use super::Options;
 use super::{
-    cfg::{ControlFlowGraph, Instr},
+    cfg::{ControlFlowGraph, Instr, InternalCallTy},
     vartable::Vartable,
 };
 use crate::codegen::revert::{assert_failure, log_runtime_error};
-use crate::sema::ast::{Function, Namespace, RetrieveType, Type};
+use crate::sema::ast::{ArrayLength, Function, Namespace, RetrieveType, Type};
 use solang_parser::pt;
 
 /// Given a storage slot which is the start of the array, calculate the
@@ -98,7 +101,6 @@ pub fn storage_slots_array_push(
 
     let var_expr = expression(&args[0], cfg, contract_no, func, ns, vartab, opt);
 
-    // TODO(Soroban): Storage type here is None, since arrays are not yet supported in Soroban
     let expr = load_storage(loc, &slot_ty, var_expr.clone(), cfg, vartab, None, ns);
 
     cfg.add(
@@ -212,7 +214,6 @@ pub fn storage_slots_array_pop(
 
     let ty = args[0].ty();
     let var_expr = expression(&args[0], cfg, contract_no, func, ns, vartab, opt);
-    // TODO(Soroban): Storage type here is None, since arrays are not yet supported in Soroban
     let expr = load_storage(loc, &length_ty, var_expr.clone(), cfg, vartab, None, ns);
 
     cfg.add(
@@ -473,3 +474,504 @@ pub fn array_pop(
         Expression::Undefined { ty }
     }
 }
+
+/// On Soroban, dynamic arrays and bytes in contract storage do not have a slot for each element.
+/// Each one is kept as a single host `Vec` or `Bytes` object, stored under the key of the
+/// variable. Returns true if the storage reference `ty` is stored like this.
+pub fn soroban_storage_object(ty: &Type) -> bool {
+    match ty {
+        Type::StorageRef(_, ty) => match ty.as_ref() {
+            Type::DynamicBytes => true,
+            Type::Array(_, dims) => dims.last() == Some(&ArrayLength::Dynamic),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// A dynamic array or bytes in Soroban contract storage, loaded as a host object. After the
+/// object has been modified, it has to be written back with [SorobanStorageObject::store].
+struct SorobanStorageObject {
+    /// The array or bytes type
+    ty: Type,
+    /// The contract data key of the object
+    storage: Expression,
+    storage_type: Option<pt::StorageType>,
+    /// The variable which holds the host object
+    var_no: usize,
+}
+
+impl SorobanStorageObject {
+    fn load(
+        array: &ast::Expression,
+        cfg: &mut ControlFlowGraph,
+        contract_no: usize,
+        func: Option<&Function>,
+        ns: &Namespace,
+        vartab: &mut Vartable,
+        opt: &Options,
+    ) -> Self {
+        let ty = array.ty().deref_any().clone();
+        let storage_type = storage_type(array, ns);
+        let storage = expression(array, cfg, contract_no, func, ns, vartab, opt);
+        let var_no = vartab.temp_anonymous(&Type::Uint(64));
+
+        // If nothing is stored yet, the result is a new empty object
+        cfg.add(
+            vartab,
+            Instr::LoadStorage {
+                res: var_no,
+                ty: ty.clone(),
+                storage: storage.clone(),
+                storage_type: storage_type.clone(),
+            },
+        );
+
+        SorobanStorageObject {
+            ty,
+            storage,
+            storage_type,
+            var_no,
+        }
+    }
+
+    fn object(&self) -> Expression {
+        Expression::Variable {
+            loc: pt::Loc::Codegen,
+            ty: Type::Uint(64),
+            var_no: self.var_no,
+        }
+    }
+
+    fn is_bytes(&self) -> bool {
+        self.ty == Type::DynamicBytes
+    }
+
+    fn elem_ty(&self) -> Type {
+        if self.is_bytes() {
+            Type::Bytes(1)
+        } else {
+            self.ty.array_elem()
+        }
+    }
+
+    /// Write the object back to contract storage
+    fn store(&self, cfg: &mut ControlFlowGraph, vartab: &mut Vartable) {
+        cfg.add(
+            vartab,
+            Instr::SetStorage {
+                ty: self.ty.clone(),
+                value: self.object(),
+                storage: self.storage.clone(),
+                storage_type: self.storage_type.clone(),
+            },
+        );
+    }
+
+    /// Replace the object with the result of a host function which modifies it
+    fn update(
+        &self,
+        function: HostFunctions,
+        args: Vec<Expression>,
+        cfg: &mut ControlFlowGraph,
+        vartab: &mut Vartable,
+    ) {
+        cfg.add(
+            vartab,
+            Instr::Call {
+                res: vec![self.var_no],
+                return_tys: vec![Type::Uint(64)],
+                call: InternalCallTy::HostFunction {
+                    name: function.name().to_string(),
+                },
+                args,
+            },
+        );
+    }
+
+    fn length(
+        &self,
+        cfg: &mut ControlFlowGraph,
+        vartab: &mut Vartable,
+        ns: &Namespace,
+    ) -> Expression {
+        let function = if self.is_bytes() {
+            HostFunctions::BytesLen
+        } else {
+            HostFunctions::VecLen
+        };
+
+        let length = soroban_host_call(function, vec![self.object()], cfg, vartab);
+
+        soroban_decode_value(length, &Type::Uint(32), cfg, vartab, ns)
+    }
+
+    /// Check the index is in bounds, and return it as a `U32Val`
+    fn index(
+        &self,
+        loc: &pt::Loc,
+        index: Expression,
+        cfg: &mut ControlFlowGraph,
+        vartab: &mut Vartable,
+        ns: &Namespace,
+        opt: &Options,
+    ) -> Expression {
+        let length = self.length(cfg, vartab, ns);
+        let coerced_ty = Type::Uint(std::cmp::max(index.ty().bits(ns), 32));
+        let pos = vartab.temp_anonymous(&coerced_ty);
+
+        cfg.add(
+            vartab,
+            Instr::Set {
+                loc: pt::Loc::Codegen,
+                res: pos,
+                expr: index.cast(&coerced_ty, ns),
+            },
+        );
+
+        let out_of_bounds = cfg.new_basic_block("out_of_bounds".to_string());
+        let in_bounds = cfg.new_basic_block("in_bounds".to_string());
+
+        cfg.add(
+            vartab,
+            Instr::BranchCond {
+                cond: Expression::MoreEqual {
+                    loc: *loc,
+                    signed: false,
+                    left: Box::new(Expression::Variable {
+                        loc: *loc,
+                        ty: coerced_ty.clone(),
+                        var_no: pos,
+                    }),
+                    right: Box::new(length.cast(&coerced_ty, ns)),
+                },
+                true_block: out_of_bounds,
+                false_block: in_bounds,
+            },
+        );
+
+        cfg.set_basic_block(out_of_bounds);
+        log_runtime_error(
+            opt.log_runtime_errors,
+            "array index out of bounds",
+            *loc,
+            cfg,
+            vartab,
+            ns,
+        );
+        let error = SolidityError::Panic(PanicCode::ArrayIndexOob);
+        assert_failure(loc, error, ns, cfg, vartab);
+
+        cfg.set_basic_block(in_bounds);
+
+        let index = Expression::Variable {
+            loc: *loc,
+            ty: coerced_ty,
+            var_no: pos,
+        }
+        .cast(&Type::Uint(32), ns);
+
+        soroban_encode_arg(index, cfg, vartab, ns)
+    }
+
+    /// The `Val` of a new element
+    fn zero_elem(&self) -> Expression {
+        let ty = if self.is_bytes() {
+            Type::Uint(32)
+        } else {
+            self.elem_ty()
+        };
+
+        Expression::NumberLiteral {
+            loc: pt::Loc::Codegen,
+            ty: Type::Uint(64),
+            value: BigInt::from(zero_val(&ty)),
+        }
+    }
+
+    fn encode_elem(
+        &self,
+        value: Expression,
+        cfg: &mut ControlFlowGraph,
+        vartab: &mut Vartable,
+        ns: &Namespace,
+    ) -> Expression {
+        // A byte is passed as a u32
+        let value = if self.is_bytes() {
+            Expression::ZeroExt {
+                loc: pt::Loc::Codegen,
+                ty: Type::Uint(32),
+                expr: Box::new(value),
+            }
+        } else {
+            value
+        };
+
+        soroban_encode_arg(value, cfg, vartab, ns)
+    }
+
+    fn decode_elem(
+        &self,
+        value: Expression,
+        cfg: &mut ControlFlowGraph,
+        vartab: &mut Vartable,
+        ns: &Namespace,
+    ) -> Expression {
+        if self.is_bytes() {
+            Expression::Trunc {
+                loc: pt::Loc::Codegen,
+                ty: Type::Bytes(1),
+                expr: Box::new(soroban_decode_value(
+                    value,
+                    &Type::Uint(32),
+                    cfg,
+                    vartab,
+                    ns,
+                )),
+            }
+        } else {
+            soroban_decode_value(value, &self.elem_ty(), cfg, vartab, ns)
+        }
+    }
+}
+
+/// Call a Soroban host function and return its result
+fn soroban_host_call(
+    function: HostFunctions,
+    args: Vec<Expression>,
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+) -> Expression {
+    let res = vartab.temp_anonymous(&Type::Uint(64));
+
+    cfg.add(
+        vartab,
+        Instr::Call {
+            res: vec![res],
+            return_tys: vec![Type::Uint(64)],
+            call: InternalCallTy::HostFunction {
+                name: function.name().to_string(),
+            },
+            args,
+        },
+    );
+
+    Expression::Variable {
+        loc: pt::Loc::Codegen,
+        ty: Type::Uint(64),
+        var_no: res,
+    }
+}
+
+/// Decode a `Val` returned by a host function as `ty`
+fn soroban_decode_value(
+    value: Expression,
+    ty: &Type,
+    cfg: &mut ControlFlowGraph,
+    vartab: &mut Vartable,
+    ns: &Namespace,
+) -> Expression {
+    let Expression::Variable { loc, var_no, .. } = value else {
+        unreachable!();
+    };
+
+    soroban_decode_arg(
+        Expression::Variable {
+            loc,
+            ty: ty.clone(),
+            var_no,
+        },
+        cfg,
+        vartab,
+        ns,
+    )
+}
+
+/// Push() method on dynamic array or bytes in Soroban storage
+pub fn soroban_array_push(
+    args: &[ast::Expression],
+    cfg: &mut ControlFlowGraph,
+    contract_no: usize,
+    func: Option<&Function>,
+    ns: &Namespace,
+    vartab: &mut Vartable,
+    opt: &Options,
+) -> Expression {
+    let array = SorobanStorageObject::load(&args[0], cfg, contract_no, func, ns, vartab, opt);
+
+    let value = if args.len() > 1 {
+        let value = expression(&args[1], cfg, contract_no, func, ns, vartab, opt);
+
+        array.encode_elem(value, cfg, vartab, ns)
+    } else {
+        array.zero_elem()
+    };
+
+    let function = if array.is_bytes() {
+        HostFunctions::BytesPush
+    } else {
+        HostFunctions::VecPushBack
+    };
+
+    array.update(function, vec![array.object(), value], cfg, vartab);
+    array.store(cfg, vartab);
+
+    Expression::Poison
+}
+
+/// Pop() method on dynamic array or bytes in Soroban storage
+pub fn soroban_array_pop(
+    loc: &pt::Loc,
+    args: &[ast::Expression],
+    return_ty: &Type,
+    cfg: &mut ControlFlowGraph,
+    contract_no: usize,
+    func: Option<&Function>,
+    ns: &Namespace,
+    vartab: &mut Vartable,
+    opt: &Options,
+) -> Expression {
+    let array = SorobanStorageObject::load(&args[0], cfg, contract_no, func, ns, vartab, opt);
+    let length = array.length(cfg, vartab, ns);
+
+    let empty_array = cfg.new_basic_block("empty_array".to_string());
+    let has_elements = cfg.new_basic_block("has_elements".to_string());
+
+    cfg.add(
+        vartab,
+        Instr::BranchCond {
+            cond: Expression::Equal {
+                loc: *loc,
+                left: Box::new(length),
+                right: Box::new(Expression::NumberLiteral {
+                    loc: *loc,
+                    ty: Type::Uint(32),
+                    value: BigInt::zero(),
+                }),
+            },
+            true_block: empty_array,
+            false_block: has_elements,
+        },
+    );
+
+    cfg.set_basic_block(empty_array);
+    log_runtime_error(
+        opt.log_runtime_errors,
+        "pop from empty storage array",
+        *loc,
+        cfg,
+        vartab,
+        ns,
+    );
+    let error = SolidityError::Panic(PanicCode::EmptyArrayPop);
+    assert_failure(loc, error, ns, cfg, vartab);
+
+    cfg.set_basic_block(has_elements);
+
+    let (back, pop) = if array.is_bytes() {
+        (HostFunctions::BytesBack, HostFunctions::BytesPop)
+    } else {
+        (HostFunctions::VecBack, HostFunctions::VecPopBack)
+    };
+
+    let val = if *return_ty != Type::Void {
+        let value = soroban_host_call(back, vec![array.object()], cfg, vartab);
+        let value = array.decode_elem(value, cfg, vartab, ns);
+        let res = vartab.temp_anonymous(return_ty);
+
+        cfg.add(
+            vartab,
+            Instr::Set {
+                loc: *loc,
+                res,
+                expr: value,
+            },
+        );
+
+        Expression::Variable {
+            loc: *loc,
+            ty: return_ty.clone(),
+            var_no: res,
+        }
+    } else {
+        Expression::Undefined {
+            ty: return_ty.clone(),
+        }
+    };
+
+    array.update(pop, vec![array.object()], cfg, vartab);
+    array.store(cfg, vartab);
+
+    val
+}
+
+/// Length of a dynamic array or bytes in Soroban storage
+pub fn soroban_array_length(
+    array: &ast::Expression,
+    ty: &Type,
+    cfg: &mut ControlFlowGraph,
+    contract_no: usize,
+    func: Option<&Function>,
+    ns: &Namespace,
+    vartab: &mut Vartable,
+    opt: &Options,
+) -> Expression {
+    let array = SorobanStorageObject::load(array, cfg, contract_no, func, ns, vartab, opt);
+
+    array.length(cfg, vartab, ns).cast(ty, ns)
+}
+
+/// Load an element of a dynamic array or bytes in Soroban storage
+pub fn soroban_array_get(
+    loc: &pt::Loc,
+    array: &ast::Expression,
+    index: &ast::Expression,
+    cfg: &mut ControlFlowGraph,
+    contract_no: usize,
+    func: Option<&Function>,
+    ns: &Namespace,
+    vartab: &mut Vartable,
+    opt: &Options,
+) -> Expression {
+    let array = SorobanStorageObject::load(array, cfg, contract_no, func, ns, vartab, opt);
+    let index = expression(index, cfg, contract_no, func, ns, vartab, opt);
+    let index = array.index(loc, index, cfg, vartab, ns, opt);
+
+    let function = if array.is_bytes() {
+        HostFunctions::BytesGet
+    } else {
+        HostFunctions::VecGet
+    };
+
+    let value = soroban_host_call(function, vec![array.object(), index], cfg, vartab);
+
+    array.decode_elem(value, cfg, vartab, ns)
+}
+
+/// Store an element of a dynamic array or bytes in Soroban storage
+pub fn soroban_array_set(
+    loc: &pt::Loc,
+    array: &ast::Expression,
+    index: &ast::Expression,
+    value: Expression,
+    cfg: &mut ControlFlowGraph,
+    contract_no: usize,
+    func: Option<&Function>,
+    ns: &Namespace,
+    vartab: &mut Vartable,
+    opt: &Options,
+) {
+    let array = SorobanStorageObject::load(array, cfg, contract_no, func, ns, vartab, opt);
+    let index = expression(index, cfg, contract_no, func, ns, vartab, opt);
+    let index = array.index(loc, index, cfg, vartab, ns, opt);
+    let value = array.encode_elem(value, cfg, vartab, ns);
+
+    let function = if array.is_bytes() {
+        HostFunctions::BytesPut
+    } else {
+        HostFunctions::VecPut
+    };
+
+    array.update(function, vec![array.object(), index, value], cfg, vartab);
+    array.store(cfg, vartab);
+}
@@ -564,7 +564,13 @@ fn expression_reduce(expr: &Expression, vars: &Variables, ns: &mut Namespace) ->
 fn track(ty: &Type) -> bool {
     matches!(
         ty,
-        Type::Uint(_) | Type::Int(_) | Type::Bool | Type::Value | Type::UserType(_)
+        Type::Uint(_)
+            | Type::Int(_)
+            | Type::Fixed(..)
+            | Type::Ufixed(..)
+            | Type::Bool
+            | Type::Value
+            | Type::UserType(_)
     )
 }
 
@@ -61,6 +61,12 @@ fn test_builtin_conversion() {
         ast::Builtin::WriteBytes,
         ast::Builtin::ExtendTtl,
         ast::Builtin::ExtendInstanceTtl,
+        ast::Builtin::Secp256k1Recover,
+        ast::Builtin::NetworkId,
+        ast::Builtin::SysvarRent,
+        ast::Builtin::SysvarEpochSchedule,
+        ast::Builtin::CurrentInstructionIndex,
+        ast::Builtin::InstructionAt,
     ];
 
     let output: Vec<codegen::Builtin> = vec![
@@ -119,6 +125,12 @@ fn test_builtin_conversion() {
         codegen::Builtin::WriteBytes,
         codegen::Builtin::ExtendTtl,
         codegen::Builtin::ExtendInstanceTtl,
+        codegen::Builtin::Secp256k1Recover,
+        codegen::Builtin::NetworkId,
+        codegen::Builtin::SysvarRent,
+        codegen::Builtin::SysvarEpochSchedule,
+        codegen::Builtin::CurrentInstructionIndex,
+        codegen::Builtin::InstructionAt,
     ];
 
     for (i, item) in input.iter().enumerate() {
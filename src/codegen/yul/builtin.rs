@@ -169,7 +169,7 @@ pub(crate) fn process_builtin(
         YulBuiltInFunction::TLoad => {
             let slot = expression(&args[0], contract_no, ns, vartab, cfg, opt);
             let res = vartab.temp_anonymous(&Type::Uint(256));
-            cfg.add(vartab, Instr::LoadStorage { res, ty: Type::Uint(256), storage: slot, storage_type: Some(StorageType::Temporary(None)) });
+            cfg.add(vartab, Instr::LoadStorage { res, ty: Type::Uint(256), storage: slot, storage_type: Some(StorageType::Transient(None)) });
             Expression::Variable {
                 loc: *loc,
                 ty: Type::Uint(256),
@@ -180,7 +180,7 @@ pub(crate) fn process_builtin(
         YulBuiltInFunction::TStore => {
             let slot = expression(&args[0], contract_no, ns, vartab, cfg, opt);
             let value = expression(&args[1], contract_no, ns, vartab, cfg, opt);
-            cfg.add(vartab, Instr::SetStorage { ty: Type::Uint(256), value, storage: slot, storage_type: Some(StorageType::Temporary(None)) });
+            cfg.add(vartab, Instr::SetStorage { ty: Type::Uint(256), value, storage: slot, storage_type: Some(StorageType::Transient(None)) });
             Expression::Poison
         }
 
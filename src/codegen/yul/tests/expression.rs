@@ -148,6 +148,8 @@ fn contract_constant_variable() {
         using: vec![],
         layout: vec![],
         fixed_layout_size: Default::default(),
+        transient_layout: vec![],
+        transient_layout_size: Default::default(),
         functions: vec![],
         all_functions: Default::default(),
         virtual_functions: Default::default(),
@@ -155,6 +157,7 @@ fn contract_constant_variable() {
         variables: vec![var],
         creates: vec![],
         emits_events: vec![],
+        custom_errors: vec![],
         initializer: None,
         default_constructor: None,
         cfg: vec![],
@@ -287,6 +290,8 @@ fn slot_suffix() {
         using: vec![],
         layout: vec![layout],
         fixed_layout_size: Default::default(),
+        transient_layout: vec![],
+        transient_layout_size: Default::default(),
         functions: vec![],
         all_functions: Default::default(),
         virtual_functions: Default::default(),
@@ -294,6 +299,7 @@ fn slot_suffix() {
         variables: vec![],
         creates: vec![],
         emits_events: vec![],
+        custom_errors: vec![],
         initializer: None,
         default_constructor: None,
         cfg: vec![],
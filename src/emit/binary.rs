@@ -18,7 +18,7 @@ use wasm_opt::OptimizationOptions;
 
 use crate::codegen::{cfg::ReturnCode, Options};
 use crate::emit::stylus;
-use crate::emit::{polkadot, TargetRuntime};
+use crate::emit::{polkadot, polkavm, TargetRuntime};
 use crate::emit::{solana, BinaryOp, Generate};
 use crate::linker::link;
 use crate::Target;
@@ -209,6 +209,7 @@ impl<'a> Binary<'a> {
                 soroban::SorobanTarget::build(context, &std_lib, contract, ns, opt, _contract_no)
             }
             Target::Stylus => stylus::StylusTarget::build(context, &std_lib, contract, ns, opt),
+            Target::PolkaVM => polkavm::PolkaVMTarget::build(context, &std_lib, contract, ns, opt),
             _ => unimplemented!("target not implemented"),
         }
     }
@@ -251,7 +252,7 @@ impl<'a> Binary<'a> {
             )
             .unwrap();
 
-        let code = target_machine
+        let out = target_machine
             .write_to_memory_buffer(
                 &self.module,
                 if generate == Generate::Assembly {
@@ -260,22 +261,19 @@ impl<'a> Binary<'a> {
                     FileType::Object
                 },
             )
-            .map(|out| {
-                let slice = out.as_slice();
-
-                if generate == Generate::Linked {
-                    link(slice, &self.name, self.ns.target).to_vec()
-                } else {
-                    slice.to_vec()
-                }
-            })
             .map_err(|s| s.to_string())?;
 
+        let code = if generate == Generate::Linked {
+            link(out.as_slice(), &self.name, self.ns.target)?
+        } else {
+            out.as_slice().to_vec()
+        };
+
         #[cfg(feature = "wasm_opt")]
         if let Some(level) = self
             .options
             .wasm_opt
-            .filter(|_| self.ns.target.is_polkadot())
+            .filter(|_| matches!(self.ns.target, Target::Polkadot { .. }))
         {
             let mut infile = tempdir().map_err(|e| e.to_string())?.keep();
             infile.push("code.wasm");
@@ -357,6 +355,7 @@ impl<'a> Binary<'a> {
     ) -> Self {
         LLVM_INIT.get_or_init(|| {
             inkwell::targets::Target::initialize_webassembly(&Default::default());
+            inkwell::targets::Target::initialize_riscv(&Default::default());
 
             extern "C" {
                 fn LLVMInitializeSBFTarget();
@@ -814,7 +813,7 @@ impl<'a> Binary<'a> {
 
         if self.ns.target == Target::Soroban {
             match returns.iter().next() {
-                Some(ret) => return self.llvm_type(ret).fn_type(&args, false),
+                Some(ret) => return self.llvm_var_ty(ret).fn_type(&args, false),
                 None => return self.context.void_type().fn_type(&args, false),
             }
         }
@@ -889,7 +888,8 @@ impl<'a> Binary<'a> {
 
     /// Return the llvm type for a variable holding the type, not the type itself
     pub(crate) fn llvm_var_ty(&self, ty: &Type) -> BasicTypeEnum<'a> {
-        if self.ns.target == Target::Soroban {
+        // Soroban values are passed as Vals, except for structs in memory
+        if self.ns.target == Target::Soroban && !matches!(ty, Type::Struct(_)) {
             return self.llvm_type(ty);
         }
 
@@ -910,6 +910,12 @@ impl<'a> Binary<'a> {
     /// Return the llvm type for field in struct or array
     pub(crate) fn llvm_field_ty(&self, ty: &Type) -> BasicTypeEnum<'a> {
         let llvm_ty = self.llvm_type(ty);
+
+        // Soroban strings and bytes are host objects
+        if self.ns.target == Target::Soroban && matches!(ty, Type::String | Type::DynamicBytes) {
+            return llvm_ty;
+        }
+
         match ty.deref_memory() {
             Type::Array(_, dim) if dim.last() == Some(&ArrayLength::Dynamic) => self
                 .context
@@ -946,7 +952,7 @@ impl<'a> Binary<'a> {
         } else {
             match ty {
                 Type::Bool => BasicTypeEnum::IntType(self.context.bool_type()),
-                Type::Int(n) | Type::Uint(n) => {
+                Type::Int(n) | Type::Uint(n) | Type::Fixed(n, _) | Type::Ufixed(n, _) => {
                     BasicTypeEnum::IntType(self.context.custom_width_int_type(*n as u32))
                 }
                 Type::Value => BasicTypeEnum::IntType(
@@ -1365,6 +1371,22 @@ fn load_stdlib<'a>(context: &'a Context, target: &Target) -> Module<'a> {
         return module;
     }
 
+    if *target == Target::PolkaVM {
+        let memory = MemoryBuffer::create_from_memory_range(RISCV_IR[0], "riscv_bc");
+
+        let module = Module::parse_bitcode_from_buffer(&memory, context).unwrap();
+
+        for bc in RISCV_IR.iter().skip(1) {
+            let memory = MemoryBuffer::create_from_memory_range(bc, "riscv_bc");
+
+            module
+                .link_in_module(Module::parse_bitcode_from_buffer(&memory, context).unwrap())
+                .unwrap();
+        }
+
+        return module;
+    }
+
     let memory = MemoryBuffer::create_from_memory_range(WASM_IR[0], "wasm_bc");
 
     let module = Module::parse_bitcode_from_buffer(&memory, context).unwrap();
@@ -1405,4 +1427,13 @@ static WASM_IR: [&[u8]; 4] = [
     include_bytes!("../../target/wasm/format.bc"),
 ];
 
+// The revive pallet does not provide ripemd160 either
+static RISCV_IR: [&[u8]; 5] = [
+    include_bytes!("../../target/riscv/stdlib.bc"),
+    include_bytes!("../../target/riscv/heap.bc"),
+    include_bytes!("../../target/riscv/bigint.bc"),
+    include_bytes!("../../target/riscv/format.bc"),
+    include_bytes!("../../target/riscv/ripemd160.bc"),
+];
+
 static RIPEMD160_IR: &[u8] = include_bytes!("../../target/wasm/ripemd160.bc");
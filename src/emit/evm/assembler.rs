@@ -0,0 +1,345 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use num_bigint::{BigInt, Sign};
+use std::collections::HashMap;
+
+/// EVM opcodes which are used by the backend. `PUSHn`, `DUPn`, `SWAPn` and `LOGn` are
+/// represented by their own [Item] variants, since they take an argument.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Opcode {
+    Stop = 0x00,
+    Add = 0x01,
+    Mul = 0x02,
+    Sub = 0x03,
+    Div = 0x04,
+    SDiv = 0x05,
+    Mod = 0x06,
+    SMod = 0x07,
+    AddMod = 0x08,
+    MulMod = 0x09,
+    Exp = 0x0a,
+    SignExtend = 0x0b,
+    Lt = 0x10,
+    Gt = 0x11,
+    SLt = 0x12,
+    SGt = 0x13,
+    Eq = 0x14,
+    IsZero = 0x15,
+    And = 0x16,
+    Or = 0x17,
+    Xor = 0x18,
+    Not = 0x19,
+    Shl = 0x1b,
+    Shr = 0x1c,
+    Sar = 0x1d,
+    Keccak256 = 0x20,
+    Address = 0x30,
+    Balance = 0x31,
+    Origin = 0x32,
+    Caller = 0x33,
+    CallValue = 0x34,
+    CallDataLoad = 0x35,
+    CallDataSize = 0x36,
+    CallDataCopy = 0x37,
+    CodeSize = 0x38,
+    CodeCopy = 0x39,
+    GasPrice = 0x3a,
+    ExtCodeSize = 0x3b,
+    ExtCodeCopy = 0x3c,
+    ReturnDataSize = 0x3d,
+    ReturnDataCopy = 0x3e,
+    ExtCodeHash = 0x3f,
+    BlockHash = 0x40,
+    Coinbase = 0x41,
+    Timestamp = 0x42,
+    Number = 0x43,
+    PrevRandao = 0x44,
+    GasLimit = 0x45,
+    ChainId = 0x46,
+    BaseFee = 0x48,
+    Pop = 0x50,
+    MLoad = 0x51,
+    MStore = 0x52,
+    MStore8 = 0x53,
+    SLoad = 0x54,
+    SStore = 0x55,
+    Jump = 0x56,
+    JumpI = 0x57,
+    Gas = 0x5a,
+    TLoad = 0x5c,
+    TStore = 0x5d,
+    MCopy = 0x5e,
+    Create = 0xf0,
+    Call = 0xf1,
+    Return = 0xf3,
+    DelegateCall = 0xf4,
+    Create2 = 0xf5,
+    StaticCall = 0xfa,
+    Revert = 0xfd,
+    Invalid = 0xfe,
+    SelfDestruct = 0xff,
+}
+
+impl Opcode {
+    /// The number of stack items consumed and produced by the opcode
+    pub fn stack_effect(self) -> (usize, usize) {
+        match self {
+            Opcode::Stop | Opcode::Invalid => (0, 0),
+            Opcode::Add
+            | Opcode::Mul
+            | Opcode::Sub
+            | Opcode::Div
+            | Opcode::SDiv
+            | Opcode::Mod
+            | Opcode::SMod
+            | Opcode::Exp
+            | Opcode::SignExtend
+            | Opcode::Lt
+            | Opcode::Gt
+            | Opcode::SLt
+            | Opcode::SGt
+            | Opcode::Eq
+            | Opcode::And
+            | Opcode::Or
+            | Opcode::Xor
+            | Opcode::Shl
+            | Opcode::Shr
+            | Opcode::Sar
+            | Opcode::Keccak256 => (2, 1),
+            Opcode::AddMod | Opcode::MulMod => (3, 1),
+            Opcode::IsZero
+            | Opcode::Not
+            | Opcode::Balance
+            | Opcode::ExtCodeSize
+            | Opcode::ExtCodeHash
+            | Opcode::BlockHash
+            | Opcode::CallDataLoad
+            | Opcode::MLoad
+            | Opcode::SLoad
+            | Opcode::TLoad => (1, 1),
+            Opcode::Address
+            | Opcode::Origin
+            | Opcode::Caller
+            | Opcode::CallValue
+            | Opcode::CallDataSize
+            | Opcode::CodeSize
+            | Opcode::GasPrice
+            | Opcode::ReturnDataSize
+            | Opcode::Coinbase
+            | Opcode::Timestamp
+            | Opcode::Number
+            | Opcode::PrevRandao
+            | Opcode::GasLimit
+            | Opcode::ChainId
+            | Opcode::BaseFee
+            | Opcode::Gas => (0, 1),
+            Opcode::CallDataCopy | Opcode::CodeCopy | Opcode::ReturnDataCopy | Opcode::MCopy => {
+                (3, 0)
+            }
+            Opcode::ExtCodeCopy => (4, 0),
+            Opcode::Pop | Opcode::Jump | Opcode::SelfDestruct => (1, 0),
+            Opcode::MStore
+            | Opcode::MStore8
+            | Opcode::SStore
+            | Opcode::TStore
+            | Opcode::JumpI
+            | Opcode::Return
+            | Opcode::Revert => (2, 0),
+            Opcode::Create => (3, 1),
+            Opcode::Create2 => (4, 1),
+            Opcode::Call => (7, 1),
+            Opcode::DelegateCall | Opcode::StaticCall => (6, 1),
+        }
+    }
+}
+
+/// A jump destination in the code. Labels are resolved when the code is assembled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Label(usize);
+
+/// Constant data which is appended after the code, e.g. string literals or the code
+/// of contracts which can be created.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DataId(usize);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Item {
+    Op(Opcode),
+    /// Push a constant. The value is stored big endian without leading zeros, so an
+    /// empty vector is a `PUSH0`.
+    Push(Vec<u8>),
+    /// Push the code offset of a label
+    PushLabel(Label),
+    /// Push the code offset of constant data
+    PushData(DataId),
+    /// Push the length of the entire code, including the data
+    PushCodeLength,
+    Dup(u8),
+    Swap(u8),
+    Log(u8),
+    /// Jump destination
+    Label(Label),
+}
+
+impl Item {
+    /// The number of stack items consumed and produced
+    pub fn stack_effect(&self) -> (usize, usize) {
+        match self {
+            Item::Op(op) => op.stack_effect(),
+            Item::Push(_) | Item::PushLabel(_) | Item::PushData(_) | Item::PushCodeLength => (0, 1),
+            Item::Dup(n) => (*n as usize, *n as usize + 1),
+            Item::Swap(n) => (*n as usize + 1, *n as usize + 1),
+            Item::Log(n) => (*n as usize + 2, 0),
+            Item::Label(_) => (0, 0),
+        }
+    }
+}
+
+/// Collects instructions and data, and produces the final bytecode. Jump destinations are
+/// pushed with fixed width immediates, so that the layout can be calculated in a single pass.
+#[derive(Default)]
+pub struct Assembler {
+    pub items: Vec<Item>,
+    data: Vec<Vec<u8>>,
+    next_label: usize,
+}
+
+impl Assembler {
+    pub fn new_label(&mut self) -> Label {
+        let label = Label(self.next_label);
+        self.next_label += 1;
+        label
+    }
+
+    /// Add constant data; identical data is only included once
+    pub fn add_data(&mut self, bytes: &[u8]) -> DataId {
+        if let Some(no) = self.data.iter().position(|d| d == bytes) {
+            DataId(no)
+        } else {
+            self.data.push(bytes.to_vec());
+            DataId(self.data.len() - 1)
+        }
+    }
+
+    /// Resolve all the labels and data offsets, and produce the bytecode. Returns `None` if
+    /// the code is too large for code offsets of four bytes.
+    pub fn assemble(&self) -> Option<Vec<u8>> {
+        // Try two byte offsets first; only very large contracts need three bytes.
+        (2..=4).find_map(|width| self.assemble_with_width(width))
+    }
+
+    fn assemble_with_width(&self, width: usize) -> Option<Vec<u8>> {
+        let mut labels = HashMap::new();
+        let mut offset = 0;
+
+        for item in &self.items {
+            if let Item::Label(label) = item {
+                labels.insert(*label, offset);
+            }
+
+            offset += match item {
+                Item::Op(_) | Item::Dup(_) | Item::Swap(_) | Item::Log(_) | Item::Label(_) => 1,
+                Item::Push(bytes) => 1 + bytes.len(),
+                Item::PushLabel(_) | Item::PushData(_) | Item::PushCodeLength => 1 + width,
+            };
+        }
+
+        let mut data_offsets = Vec::new();
+
+        for data in &self.data {
+            data_offsets.push(offset);
+            offset += data.len();
+        }
+
+        let code_length = offset;
+
+        if code_length >> (width * 8) != 0 {
+            return None;
+        }
+
+        let mut code = Vec::with_capacity(code_length);
+
+        let push_offset = |code: &mut Vec<u8>, offset: usize| {
+            code.push(0x5f + width as u8);
+            code.extend_from_slice(&offset.to_be_bytes()[std::mem::size_of::<usize>() - width..]);
+        };
+
+        for item in &self.items {
+            match item {
+                Item::Op(op) => code.push(*op as u8),
+                Item::Push(bytes) => {
+                    assert!(bytes.len() <= 32);
+                    code.push(0x5f + bytes.len() as u8);
+                    code.extend_from_slice(bytes);
+                }
+                Item::PushLabel(label) => push_offset(&mut code, labels[label]),
+                Item::PushData(data) => push_offset(&mut code, data_offsets[data.0]),
+                Item::PushCodeLength => push_offset(&mut code, code_length),
+                Item::Dup(n) => {
+                    assert!((1..=16).contains(n));
+                    code.push(0x7f + n);
+                }
+                Item::Swap(n) => {
+                    assert!((1..=16).contains(n));
+                    code.push(0x8f + n);
+                }
+                Item::Log(n) => {
+                    assert!(*n <= 4);
+                    code.push(0xa0 + n);
+                }
+                // JUMPDEST
+                Item::Label(_) => code.push(0x5b),
+            }
+        }
+
+        for data in &self.data {
+            code.extend_from_slice(data);
+        }
+
+        Some(code)
+    }
+}
+
+/// Encode a constant for a push instruction; negative values are encoded in two's complement
+pub fn push_bytes(value: &BigInt) -> Vec<u8> {
+    let value = if value.sign() == Sign::Minus {
+        (BigInt::from(1) << 256) + value
+    } else {
+        value.clone()
+    };
+
+    let (_, bytes) = value.to_bytes_be();
+
+    let bytes: Vec<u8> = bytes.into_iter().skip_while(|b| *b == 0).collect();
+
+    assert!(bytes.len() <= 32, "constant does not fit into 256 bits");
+
+    bytes
+}
+
+#[test]
+fn push_encoding() {
+    assert_eq!(push_bytes(&BigInt::from(0)), Vec::<u8>::new());
+    assert_eq!(push_bytes(&BigInt::from(0x1234)), vec![0x12, 0x34]);
+    assert_eq!(push_bytes(&BigInt::from(-1)), vec![0xff; 32]);
+}
+
+#[test]
+fn labels() {
+    let mut asm = Assembler::default();
+
+    let label = asm.new_label();
+    let data = asm.add_data(b"hello");
+
+    asm.items.push(Item::PushLabel(label));
+    asm.items.push(Item::Op(Opcode::Jump));
+    asm.items.push(Item::Label(label));
+    asm.items.push(Item::PushData(data));
+    asm.items.push(Item::Op(Opcode::Stop));
+
+    assert_eq!(
+        asm.assemble().unwrap(),
+        vec![0x61, 0x00, 0x04, 0x56, 0x5b, 0x61, 0x00, 0x09, 0x00, b'h', b'e', b'l', b'l', b'o']
+    );
+}
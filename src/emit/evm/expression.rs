@@ -0,0 +1,1482 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::assembler::{Item, Opcode};
+use super::subroutines::Subroutine;
+use super::{
+    field_offset, int_type, is_byte, is_inline, is_slice, is_vector, mem_size, Unit, SELECTOR_SLOT,
+    SLICE_CALLDATA_SHIFT, SLICE_LENGTH_SHIFT, ZERO_SLOT,
+};
+use crate::codegen::revert::PanicCode;
+use crate::codegen::{Builtin, Expression};
+use crate::sema::ast::{ArrayLength, Diagnostic, FormatArg, RetrieveType, StringLocation, Type};
+use num_bigint::BigInt;
+use num_traits::One;
+use solang_parser::pt::Loc;
+
+impl Unit<'_> {
+    /// Evaluate an expression and push its value
+    pub(super) fn expression(&mut self, expr: &Expression) {
+        match expr {
+            Expression::BoolLiteral { value, .. } => self.push_u64(*value as u64),
+            Expression::NumberLiteral { ty, value, .. } => {
+                let value = match int_type(ty, self.ns) {
+                    Some((bits, false)) if bits < 256 => value & ((BigInt::one() << bits) - 1),
+                    _ => value.clone(),
+                };
+
+                self.push(&value);
+            }
+            Expression::BytesLiteral { ty, value, .. } => {
+                if is_vector(ty, self.ns) {
+                    self.vector_literal(value);
+                } else if is_slice(ty) {
+                    self.vector_literal(value);
+                    self.vector_to_slice();
+                } else {
+                    self.push(&BigInt::from_bytes_be(num_bigint::Sign::Plus, value));
+                }
+            }
+            Expression::Variable { var_no, .. } => self.load_var(*var_no),
+            Expression::FunctionArg { arg_no, .. } => {
+                let slot = self.frame.params[*arg_no];
+                self.load_slot(slot);
+            }
+            Expression::Poison | Expression::Undefined { .. } => self.push_u64(0),
+            Expression::InternalFunctionCfg { cfg_no, .. } => {
+                let label = self.function_label(*cfg_no);
+                self.push_label(label);
+            }
+
+            Expression::Add {
+                ty,
+                overflowing,
+                left,
+                right,
+                ..
+            } => {
+                self.expression(left);
+                self.expression(right);
+
+                if *overflowing {
+                    self.op(Opcode::Add);
+                    self.normalize(ty);
+                } else {
+                    self.checked_add(ty);
+                }
+            }
+            Expression::Subtract {
+                ty,
+                overflowing,
+                left,
+                right,
+                ..
+            } => {
+                self.expression(right);
+                self.expression(left);
+
+                if *overflowing {
+                    self.op(Opcode::Sub);
+                    self.normalize(ty);
+                } else {
+                    self.checked_sub(ty);
+                }
+            }
+            Expression::Multiply {
+                ty,
+                overflowing,
+                left,
+                right,
+                ..
+            } => {
+                self.expression(left);
+                self.expression(right);
+
+                if *overflowing {
+                    self.op(Opcode::Mul);
+                    self.normalize(ty);
+                } else {
+                    self.checked_mul(ty);
+                }
+            }
+            Expression::UnsignedDivide {
+                ty, left, right, ..
+            }
+            | Expression::SignedDivide {
+                ty, left, right, ..
+            }
+            | Expression::UnsignedModulo {
+                ty, left, right, ..
+            }
+            | Expression::SignedModulo {
+                ty, left, right, ..
+            } => {
+                self.expression(right);
+                self.expression(left);
+
+                self.dup(2);
+                self.op(Opcode::IsZero);
+                self.panic_if(PanicCode::DivisionByZero as u8);
+
+                self.op(match expr {
+                    Expression::UnsignedDivide { .. } => Opcode::Div,
+                    Expression::SignedDivide { .. } => Opcode::SDiv,
+                    Expression::UnsignedModulo { .. } => Opcode::Mod,
+                    _ => Opcode::SMod,
+                });
+                self.normalize(ty);
+            }
+            Expression::Power {
+                ty,
+                overflowing,
+                base,
+                exp,
+                ..
+            } => {
+                if *overflowing {
+                    self.expression(exp);
+                    self.expression(base);
+                    self.op(Opcode::Exp);
+                    self.normalize(ty);
+                } else {
+                    self.expression(base);
+                    self.expression(exp);
+                    self.checked_power(ty);
+                }
+            }
+            Expression::Negate {
+                ty,
+                overflowing,
+                expr,
+                ..
+            } => {
+                self.expression(expr);
+
+                if let (false, Some((bits, true))) = (*overflowing, int_type(ty, self.ns)) {
+                    self.dup(1);
+                    self.push(&-(BigInt::one() << (bits - 1) as u32));
+                    self.op(Opcode::Eq);
+                    self.panic_if(PanicCode::MathOverflow as u8);
+                }
+
+                self.push_u64(0);
+                self.op(Opcode::Sub);
+                self.normalize(ty);
+            }
+            Expression::BitwiseAnd { left, right, .. }
+            | Expression::BitwiseOr { left, right, .. }
+            | Expression::BitwiseXor { left, right, .. } => {
+                self.expression(left);
+                self.expression(right);
+                self.op(match expr {
+                    Expression::BitwiseAnd { .. } => Opcode::And,
+                    Expression::BitwiseOr { .. } => Opcode::Or,
+                    _ => Opcode::Xor,
+                });
+            }
+            Expression::BitwiseNot { ty, expr, .. } => {
+                self.expression(expr);
+                self.op(Opcode::Not);
+                self.normalize(ty);
+            }
+            Expression::ShiftLeft {
+                ty, left, right, ..
+            } => {
+                self.expression(left);
+                self.expression(right);
+                self.op(Opcode::Shl);
+                self.normalize(ty);
+            }
+            Expression::ShiftRight {
+                left,
+                right,
+                signed,
+                ..
+            } => {
+                self.expression(left);
+                self.expression(right);
+                self.op(if *signed { Opcode::Sar } else { Opcode::Shr });
+            }
+            Expression::Equal { left, right, .. } => {
+                self.expression(left);
+                self.expression(right);
+                self.op(Opcode::Eq);
+            }
+            Expression::NotEqual { left, right, .. } => {
+                self.expression(left);
+                self.expression(right);
+                self.op(Opcode::Eq);
+                self.op(Opcode::IsZero);
+            }
+            Expression::Less {
+                signed,
+                left,
+                right,
+                ..
+            } => {
+                self.expression(right);
+                self.expression(left);
+                self.op(if *signed { Opcode::SLt } else { Opcode::Lt });
+            }
+            Expression::More {
+                signed,
+                left,
+                right,
+                ..
+            } => {
+                self.expression(right);
+                self.expression(left);
+                self.op(if *signed { Opcode::SGt } else { Opcode::Gt });
+            }
+            Expression::LessEqual {
+                signed,
+                left,
+                right,
+                ..
+            } => {
+                self.expression(right);
+                self.expression(left);
+                self.op(if *signed { Opcode::SGt } else { Opcode::Gt });
+                self.op(Opcode::IsZero);
+            }
+            Expression::MoreEqual {
+                signed,
+                left,
+                right,
+                ..
+            } => {
+                self.expression(right);
+                self.expression(left);
+                self.op(if *signed { Opcode::SLt } else { Opcode::Lt });
+                self.op(Opcode::IsZero);
+            }
+            Expression::Not { expr, .. } => {
+                self.expression(expr);
+                self.op(Opcode::IsZero);
+            }
+
+            Expression::Cast { ty, expr, .. } => {
+                let from = expr.ty();
+
+                self.expression(expr);
+
+                match (&from, ty) {
+                    (Type::Slice(elem_ty), _) if is_vector(ty, self.ns) => {
+                        self.copy_slice(mem_size(elem_ty, self.ns));
+                    }
+                    (_, Type::Slice(_)) if is_vector(&from, self.ns) => self.vector_to_slice(),
+                    (Type::Bytes(from), Type::Bytes(to)) if from < to => {
+                        self.push_u64(8 * (to - from) as u64);
+                        self.op(Opcode::Shl);
+                    }
+                    (Type::Bytes(from), Type::Bytes(to)) if from > to => {
+                        self.push_u64(8 * (from - to) as u64);
+                        self.op(Opcode::Shr);
+                    }
+                    _ => {
+                        let from_int = int_type(&from, self.ns);
+
+                        if from_int.is_some() && from_int != int_type(ty, self.ns) {
+                            self.normalize(ty);
+                        }
+                    }
+                }
+            }
+            Expression::ZeroExt { expr, .. } => {
+                let from = expr.ty();
+
+                self.expression(expr);
+
+                if let Some((bits, true)) = int_type(&from, self.ns) {
+                    if bits < 256 {
+                        self.push(&((BigInt::one() << bits) - 1));
+                        self.op(Opcode::And);
+                    }
+                }
+            }
+            Expression::SignExt { ty, expr, .. } | Expression::Trunc { ty, expr, .. } => {
+                self.expression(expr);
+                self.normalize(ty);
+            }
+            Expression::BytesCast { ty, from, expr, .. } => {
+                self.expression(expr);
+
+                match (from, ty) {
+                    (Type::Bytes(n), Type::DynamicBytes) => {
+                        self.push_u64(*n as u64);
+                        self.alloc_vector(1);
+                        self.swap(1);
+                        self.push_u64(256 - 8 * *n as u64);
+                        self.op(Opcode::Shl);
+                        self.dup(2);
+                        self.push_u64(32);
+                        self.op(Opcode::Add);
+                        self.op(Opcode::MStore);
+                    }
+                    (Type::DynamicBytes, Type::Bytes(n)) => {
+                        self.dup(1);
+                        self.op(Opcode::MLoad);
+                        self.push_u64(*n as u64);
+                        self.op(Opcode::Eq);
+                        self.op(Opcode::IsZero);
+                        self.panic_if(PanicCode::Generic as u8);
+                        self.push_u64(32);
+                        self.op(Opcode::Add);
+                        self.read_be(*n);
+                    }
+                    _ => unreachable!("bytes cast from {from:?} to {ty:?}"),
+                }
+            }
+            Expression::ByteSwap { expr, .. } => {
+                if let Expression::Builtin {
+                    kind: Builtin::ReadFromBuffer,
+                    args,
+                    tys,
+                    ..
+                } = expr.as_ref()
+                {
+                    // reading a big endian value does not need the byte swap
+                    self.read_buffer_be(&args[0], &args[1], tys[0].bytes(self.ns));
+                    self.normalize(&tys[0]);
+                } else {
+                    let ty = expr.ty();
+                    let bytes = ty.bytes(self.ns);
+
+                    self.expression(expr);
+                    self.byte_swap(bytes);
+                    self.normalize(&ty);
+                }
+            }
+
+            Expression::Load { ty, expr, .. } => {
+                if let Expression::Builtin {
+                    kind: Builtin::GetAddress,
+                    ..
+                } = expr.as_ref()
+                {
+                    self.op(Opcode::Address);
+                } else if let Expression::Subscript {
+                    ty: elem_ty,
+                    array_ty,
+                    expr: array,
+                    index,
+                    ..
+                } = expr.as_ref()
+                {
+                    if is_slice(array_ty.deref_memory()) {
+                        // [slice] => [address, in calldata]
+                        self.expression(array);
+                        self.dup(1);
+                        self.slice_in_calldata();
+                        self.swap(1);
+                        self.slice_address();
+                        self.element_offset(index, mem_size(elem_ty.deref_memory(), self.ns));
+                        self.swap(1);
+                        self.memory_or_calldata(Opcode::MLoad, Opcode::CallDataLoad);
+                        self.word_to_value(ty);
+                    } else {
+                        self.expression(expr);
+                        self.load_memory(ty);
+                    }
+                } else {
+                    self.expression(expr);
+                    self.load_memory(ty);
+                }
+            }
+            Expression::GetRef { expr, .. } => {
+                self.expression(expr);
+                self.alloc(32);
+                self.swap(1);
+                self.dup(2);
+                self.op(Opcode::MStore);
+            }
+            Expression::StructMember { expr, member, .. } => {
+                let ty = expr.ty();
+                let offset = field_offset(ty.deref_memory(), *member, self.ns);
+
+                self.expression(expr);
+
+                if offset != 0 {
+                    self.push_u64(offset);
+                    self.op(Opcode::Add);
+                }
+            }
+            Expression::Subscript {
+                ty: elem_ty,
+                array_ty,
+                expr,
+                index,
+                ..
+            } => {
+                if array_ty.is_storage_bytes() {
+                    self.expression(expr);
+                    self.expression(index);
+                    self.call_subroutine(Subroutine::StorageByteLoad);
+                } else if let Type::StorageRef(_, mapping) = array_ty {
+                    let Type::Mapping(mapping) = mapping.as_ref() else {
+                        unreachable!("storage arrays are indexed by slot arithmetic on EVM");
+                    };
+
+                    self.expression(expr);
+                    self.mapping_key(index, &mapping.key);
+                    self.mapping_slot(&mapping.key);
+                } else {
+                    self.expression(expr);
+                    self.data_address(array_ty.deref_memory());
+                    self.element_offset(index, mem_size(elem_ty.deref_memory(), self.ns));
+                }
+            }
+            Expression::AdvancePointer {
+                pointer,
+                bytes_offset,
+            } => {
+                self.expression(pointer);
+                self.data_address(&pointer.ty());
+                self.expression(bytes_offset);
+                self.op(Opcode::Add);
+            }
+            Expression::VectorData { pointer } => {
+                self.expression(pointer);
+                self.data_address(&pointer.ty());
+            }
+
+            Expression::StructLiteral { ty, values, .. } => {
+                self.alloc(mem_size(ty, self.ns));
+
+                for (member, value) in values.iter().enumerate() {
+                    let offset = field_offset(ty, member, self.ns);
+
+                    self.expression(value);
+                    self.dup(2);
+                    if offset != 0 {
+                        self.push_u64(offset);
+                        self.op(Opcode::Add);
+                    }
+                    self.store_memory(&value.ty());
+                }
+            }
+            Expression::ArrayLiteral { ty, values, .. }
+            | Expression::ConstArrayLiteral { ty, values, .. } => {
+                let elem_ty = ty.elem_ty();
+                let elem_size = mem_size(&elem_ty, self.ns);
+
+                let data = if is_vector(ty, self.ns) {
+                    self.push_u64(values.len() as u64);
+                    self.alloc_vector(elem_size);
+                    32
+                } else {
+                    self.alloc(mem_size(ty, self.ns));
+                    0
+                };
+
+                for (no, value) in values.iter().enumerate() {
+                    self.expression(value);
+                    self.dup(2);
+                    self.push_u64(data + no as u64 * elem_size);
+                    self.op(Opcode::Add);
+                    self.store_memory(&elem_ty);
+                }
+            }
+            Expression::AllocDynamicBytes {
+                ty,
+                size,
+                initializer,
+                ..
+            } => {
+                let elem_size = match ty {
+                    Type::String | Type::DynamicBytes => 1,
+                    _ => mem_size(&ty.array_elem(), self.ns),
+                };
+
+                self.expression(size);
+                self.alloc_vector(elem_size);
+
+                if let Some(initializer) = initializer.as_ref().filter(|i| !i.is_empty()) {
+                    self.copy_data(initializer);
+                }
+
+                if is_slice(ty) {
+                    self.vector_to_slice();
+                }
+            }
+            Expression::ReturnData { .. } => {
+                self.op(Opcode::ReturnDataSize);
+                self.alloc_vector(1);
+                self.op(Opcode::ReturnDataSize);
+                self.push_u64(0);
+                self.dup(3);
+                self.push_u64(32);
+                self.op(Opcode::Add);
+                self.op(Opcode::ReturnDataCopy);
+            }
+
+            Expression::Keccak256 { exprs, .. } => {
+                if let [expr] = exprs.as_slice() {
+                    let ty = expr.ty();
+
+                    if is_vector(&ty, self.ns) || is_slice(&ty) {
+                        self.memory_bytes(expr);
+                        self.op(Opcode::Keccak256);
+                        return;
+                    }
+                }
+
+                assert!(
+                    exprs
+                        .iter()
+                        .all(|e| !is_vector(&e.ty(), self.ns) && !is_slice(&e.ty())),
+                    "keccak256 of several vectors"
+                );
+
+                let size = 32 * exprs.len() as u64;
+
+                if size <= 64 {
+                    for (no, expr) in exprs.iter().enumerate() {
+                        self.expression(expr);
+                        self.push_u64(32 * no as u64);
+                        self.op(Opcode::MStore);
+                    }
+                    self.push_u64(size);
+                    self.push_u64(0);
+                } else {
+                    self.alloc(size);
+                    for (no, expr) in exprs.iter().enumerate() {
+                        self.expression(expr);
+                        self.dup(2);
+                        self.push_u64(32 * no as u64);
+                        self.op(Opcode::Add);
+                        self.op(Opcode::MStore);
+                    }
+                    self.push_u64(size);
+                    self.swap(1);
+                }
+                self.op(Opcode::Keccak256);
+            }
+            Expression::StringCompare { left, right, .. } => {
+                for location in [left, right] {
+                    match location {
+                        StringLocation::CompileTime(bytes) => self.vector_literal(bytes),
+                        StringLocation::RunTime(expr) => self.expression(expr),
+                    }
+                    self.hash_vector();
+                }
+                self.op(Opcode::Eq);
+            }
+            Expression::FormatString { args, .. } => self.format_string(args),
+            Expression::StorageArrayLength { array, .. } => {
+                self.expression(array);
+                self.op(Opcode::SLoad);
+            }
+            Expression::Builtin {
+                loc,
+                kind,
+                args,
+                tys,
+            } => self.builtin(loc, kind, args, tys),
+            Expression::RationalNumberLiteral { .. } => {
+                unreachable!("rational numbers are evaluated at compile time")
+            }
+        }
+    }
+
+    fn checked_add(&mut self, ty: &Type) {
+        let (bits, signed) = int_type(ty, self.ns).expect("integer type");
+
+        match (signed, bits) {
+            (false, 256) => {
+                // [a, b] => [a, a + b]
+                self.dup(2);
+                self.op(Opcode::Add);
+                self.swap(1);
+                self.dup(2);
+                self.op(Opcode::Lt);
+                self.panic_if(PanicCode::MathOverflow as u8);
+            }
+            (false, _) => {
+                self.op(Opcode::Add);
+                self.check_unsigned_range(bits);
+            }
+            (true, 256) => {
+                // the sum is smaller than a if and only if b is negative
+                self.dup(2);
+                self.dup(2);
+                self.op(Opcode::Add);
+                self.dup(3);
+                self.dup(2);
+                self.op(Opcode::SLt);
+                self.push_u64(0);
+                self.dup(4);
+                self.op(Opcode::SLt);
+                self.op(Opcode::Xor);
+                self.panic_if(PanicCode::MathOverflow as u8);
+                self.swap(2);
+                self.pop(2);
+            }
+            (true, _) => {
+                self.op(Opcode::Add);
+                self.check_signed_range(bits);
+            }
+        }
+    }
+
+    /// Subtract; the left operand is on top of the stack
+    fn checked_sub(&mut self, ty: &Type) {
+        let (bits, signed) = int_type(ty, self.ns).expect("integer type");
+
+        match (signed, bits) {
+            (false, _) => {
+                self.dup(2);
+                self.dup(2);
+                self.op(Opcode::Lt);
+                self.panic_if(PanicCode::MathOverflow as u8);
+                self.op(Opcode::Sub);
+            }
+            (true, 256) => {
+                // [b, a]: the difference is larger than a if and only if b is negative
+                self.dup(2);
+                self.dup(2);
+                self.op(Opcode::Sub);
+                self.dup(2);
+                self.dup(2);
+                self.op(Opcode::SGt);
+                self.push_u64(0);
+                self.dup(5);
+                self.op(Opcode::SLt);
+                self.op(Opcode::Xor);
+                self.panic_if(PanicCode::MathOverflow as u8);
+                self.swap(2);
+                self.pop(2);
+            }
+            (true, _) => {
+                self.op(Opcode::Sub);
+                self.check_signed_range(bits);
+            }
+        }
+    }
+
+    fn checked_mul(&mut self, ty: &Type) {
+        let (bits, signed) = int_type(ty, self.ns).expect("integer type");
+
+        if bits <= 128 {
+            // the product of two values cannot overflow 256 bits
+            self.op(Opcode::Mul);
+
+            if signed {
+                self.check_signed_range(bits);
+            } else {
+                self.check_unsigned_range(bits);
+            }
+
+            return;
+        }
+
+        // [a, b] => [a, b, a * b], and check that (a * b) / a == b
+        self.dup(2);
+        self.dup(2);
+        self.op(Opcode::Mul);
+        self.dup(3);
+        self.dup(2);
+        self.op(if signed { Opcode::SDiv } else { Opcode::Div });
+        self.dup(3);
+        self.op(Opcode::Eq);
+        self.op(Opcode::IsZero);
+        self.dup(4);
+        self.op(Opcode::IsZero);
+        self.op(Opcode::IsZero);
+        self.op(Opcode::And);
+
+        if signed {
+            // MIN * -1 is not detected by the division
+            self.dup(4);
+            self.op(Opcode::Not);
+            self.op(Opcode::IsZero);
+            self.dup(4);
+            self.push(&-(BigInt::one() << 255u32));
+            self.op(Opcode::Eq);
+            self.op(Opcode::And);
+            self.op(Opcode::Or);
+        }
+
+        self.panic_if(PanicCode::MathOverflow as u8);
+        self.swap(2);
+        self.pop(2);
+
+        if bits < 256 {
+            if signed {
+                self.check_signed_range(bits);
+            } else {
+                self.check_unsigned_range(bits);
+            }
+        }
+    }
+
+    /// Raise base to the power of exp, which are on the stack, reverting on overflow
+    fn checked_power(&mut self, ty: &Type) {
+        let (bits, signed) = int_type(ty, self.ns).expect("integer type");
+
+        if !signed {
+            self.push(&((BigInt::one() << bits) - 1));
+            self.call_subroutine(Subroutine::Power);
+            return;
+        }
+
+        // [b, e] => [neg, abs(b), e]; the result is negative if b is negative and e is odd
+        self.push_u64(0);
+        self.dup(3);
+        self.op(Opcode::SLt);
+        self.dup(2);
+        self.push_u64(1);
+        self.op(Opcode::And);
+        self.op(Opcode::And);
+        self.swap(2);
+        self.push_u64(0);
+        self.dup(2);
+        self.op(Opcode::SLt);
+        self.push_u64(0);
+        self.op(Opcode::Sub);
+        self.swap(1);
+        self.dup(2);
+        self.op(Opcode::Xor);
+        self.op(Opcode::Sub);
+        self.swap(1);
+
+        // a negative result may be one larger in magnitude
+        self.dup(3);
+        self.push(&((BigInt::one() << (bits - 1)) - 1));
+        self.op(Opcode::Add);
+        self.call_subroutine(Subroutine::Power);
+
+        // [neg, r] => [(r ^ -neg) + neg]
+        self.swap(1);
+        self.dup(1);
+        self.push_u64(0);
+        self.op(Opcode::Sub);
+        self.swap(2);
+        self.dup(3);
+        self.op(Opcode::Xor);
+        self.op(Opcode::Add);
+        self.swap(1);
+        self.op(Opcode::Pop);
+    }
+
+    /// Revert if the unsigned value on top of the stack does not fit in the given number of bits
+    fn check_unsigned_range(&mut self, bits: u16) {
+        self.dup(1);
+        self.push(&((BigInt::one() << bits) - 1));
+        self.op(Opcode::Lt);
+        self.panic_if(PanicCode::MathOverflow as u8);
+    }
+
+    /// Revert if the value on top of the stack is not a sign extended value of the given bits
+    fn check_signed_range(&mut self, bits: u16) {
+        self.dup(1);
+        self.dup(1);
+        self.push_u64(bits as u64 / 8 - 1);
+        self.op(Opcode::SignExtend);
+        self.op(Opcode::Eq);
+        self.op(Opcode::IsZero);
+        self.panic_if(PanicCode::MathOverflow as u8);
+    }
+
+    /// Load a value of the given type from the memory address on top of the stack
+    pub(super) fn load_memory(&mut self, ty: &Type) {
+        if is_inline(ty, self.ns) {
+            // the address is the value
+        } else if is_vector(ty, self.ns) {
+            // a null pointer is an empty vector
+            self.op(Opcode::MLoad);
+            self.dup(1);
+            self.op(Opcode::IsZero);
+            self.push_u64(ZERO_SLOT);
+            self.op(Opcode::Mul);
+            self.op(Opcode::Or);
+        } else {
+            self.op(Opcode::MLoad);
+            self.word_to_value(ty);
+        }
+    }
+
+    /// Turn a word read from memory or calldata into a value of the given type; a byte is the
+    /// first byte of the word
+    fn word_to_value(&mut self, ty: &Type) {
+        if is_byte(ty) {
+            self.push_u64(248);
+            self.op(Opcode::Shr);
+            if ty.is_signed_int(self.ns) {
+                self.push_u64(0);
+                self.op(Opcode::SignExtend);
+            }
+        }
+    }
+
+    /// Store a value of the given type. The stack has the value, with the address on top.
+    pub(super) fn store_memory(&mut self, ty: &Type) {
+        let ty = ty.deref_memory();
+
+        if is_inline(ty, self.ns) {
+            self.push_u64(mem_size(ty, self.ns));
+            self.swap(2);
+            self.swap(1);
+            self.op(Opcode::MCopy);
+        } else if is_byte(ty) {
+            self.op(Opcode::MStore8);
+        } else {
+            self.op(Opcode::MStore);
+        }
+    }
+
+    /// Replace the pointer on top of the stack with the address of the elements, if it is a vector
+    /// or a slice. Only a slice of memory has elements at that address.
+    pub(super) fn data_address(&mut self, ty: &Type) {
+        if is_vector(ty, self.ns) {
+            self.push_u64(32);
+            self.op(Opcode::Add);
+        } else if is_slice(ty) {
+            self.slice_address();
+        }
+    }
+
+    /// Add the offset of the element at the index to the address on top of the stack
+    fn element_offset(&mut self, index: &Expression, elem_size: u64) {
+        self.expression(index);
+
+        if elem_size != 1 {
+            self.push_u64(elem_size);
+            self.op(Opcode::Mul);
+        }
+
+        self.op(Opcode::Add);
+    }
+
+    /// Push the address of an offset into a buffer, which may be a vector or a plain pointer
+    fn buffer_address(&mut self, buf: &Expression, offset: &Expression) {
+        self.expression(buf);
+        self.data_address(&buf.ty());
+        self.expression(offset);
+        self.op(Opcode::Add);
+    }
+
+    /// Read a big endian value of the given number of bytes at an offset into a buffer. A buffer
+    /// which is a slice may be in calldata.
+    fn read_buffer_be(&mut self, buf: &Expression, offset: &Expression, bytes: u8) {
+        if is_slice(&buf.ty()) {
+            // [slice] => [address, in calldata]
+            self.expression(buf);
+            self.dup(1);
+            self.slice_address();
+            self.expression(offset);
+            self.op(Opcode::Add);
+            self.swap(1);
+            self.slice_in_calldata();
+            self.memory_or_calldata(Opcode::MLoad, Opcode::CallDataLoad);
+            self.leading_bytes(bytes);
+        } else {
+            self.buffer_address(buf, offset);
+            self.read_be(bytes);
+        }
+    }
+
+    /// Read a big endian value of the given number of bytes from the address on top of the stack
+    pub(super) fn read_be(&mut self, bytes: u8) {
+        self.op(Opcode::MLoad);
+        self.leading_bytes(bytes);
+    }
+
+    /// Keep the given number of leading bytes of the word on top of the stack, as a number
+    fn leading_bytes(&mut self, bytes: u8) {
+        if bytes < 32 {
+            self.push_u64(256 - 8 * bytes as u64);
+            self.op(Opcode::Shr);
+        }
+    }
+
+    /// Write the value as big endian with the given number of bytes, without touching the bytes
+    /// which follow. The stack has the value, with the address on top.
+    pub(super) fn write_be(&mut self, bytes: u8) {
+        match bytes {
+            32 => self.op(Opcode::MStore),
+            1 => self.op(Opcode::MStore8),
+            _ => {
+                let shift = 256 - 8 * bytes as u64;
+
+                self.dup(1);
+                self.op(Opcode::MLoad);
+                self.push(&((BigInt::one() << shift) - 1));
+                self.op(Opcode::And);
+                self.dup(3);
+                self.push_u64(shift);
+                self.op(Opcode::Shl);
+                self.op(Opcode::Or);
+                self.swap(2);
+                self.op(Opcode::Pop);
+                self.op(Opcode::MStore);
+            }
+        }
+    }
+
+    /// Reverse the lowest bytes of the value on top of the stack
+    fn byte_swap(&mut self, bytes: u8) {
+        self.call_subroutine(Subroutine::ByteSwap);
+
+        if bytes < 32 {
+            self.push_u64(256 - 8 * bytes as u64);
+            self.op(Opcode::Shr);
+        }
+    }
+
+    /// Write a value into a buffer, in the little endian byte order unless it is a byte swapped
+    /// value or a big endian type
+    pub(super) fn write_buffer(
+        &mut self,
+        buf: &Expression,
+        offset: &Expression,
+        value: &Expression,
+    ) {
+        match value {
+            Expression::ByteSwap {
+                expr,
+                le_to_be: true,
+            } => {
+                let ty = expr.ty();
+                let bytes = ty.bytes(self.ns);
+
+                self.expression(expr);
+                if ty.is_signed_int(self.ns) && bytes < 32 {
+                    self.push(&((BigInt::one() << (8 * bytes as u16)) - 1));
+                    self.op(Opcode::And);
+                }
+                self.buffer_address(buf, offset);
+                self.write_be(bytes);
+            }
+            _ => {
+                let ty = value.ty();
+                let bytes = ty.bytes(self.ns);
+
+                self.expression(value);
+
+                if !is_big_endian(&ty) && bytes > 1 {
+                    self.byte_swap(bytes);
+                }
+
+                self.buffer_address(buf, offset);
+                self.write_be(bytes);
+            }
+        }
+    }
+
+    /// Read a value from a buffer; integers are stored little endian
+    fn read_buffer(&mut self, ty: &Type, buf: &Expression, offset: &Expression) {
+        let bytes = ty.bytes(self.ns);
+
+        self.read_buffer_be(buf, offset, bytes);
+
+        if !is_big_endian(ty) && bytes > 1 {
+            self.byte_swap(bytes);
+        }
+
+        if ty.is_signed_int(self.ns) {
+            self.normalize(ty);
+        }
+    }
+
+    /// Allocate a vector with the given contents
+    pub(super) fn vector_literal(&mut self, bytes: &[u8]) {
+        if bytes.is_empty() {
+            self.push_u64(ZERO_SLOT);
+            return;
+        }
+
+        self.push_u64(bytes.len() as u64);
+        self.alloc_vector(1);
+        self.copy_data(bytes);
+    }
+
+    /// Copy constant data into the vector on top of the stack
+    fn copy_data(&mut self, bytes: &[u8]) {
+        let data = self.asm.add_data(bytes);
+
+        self.push_u64(bytes.len() as u64);
+        self.emit(Item::PushData(data));
+        self.dup(3);
+        self.push_u64(32);
+        self.op(Opcode::Add);
+        self.op(Opcode::CodeCopy);
+    }
+
+    /// Replace the vector on top of the stack with the keccak256 hash of its contents
+    pub(super) fn hash_vector(&mut self) {
+        self.dup(1);
+        self.op(Opcode::MLoad);
+        self.swap(1);
+        self.push_u64(32);
+        self.op(Opcode::Add);
+        self.op(Opcode::Keccak256);
+    }
+
+    /// Push the size and the address of the bytes of a vector or a byte slice, with the address
+    /// on top. A slice of calldata is copied into memory first.
+    pub(super) fn memory_bytes(&mut self, expr: &Expression) {
+        self.expression(expr);
+
+        if !is_slice(&expr.ty()) {
+            self.dup(1);
+            self.op(Opcode::MLoad);
+            self.swap(1);
+            self.push_u64(32);
+            self.op(Opcode::Add);
+            return;
+        }
+
+        // [slice] => [length, address]
+        self.dup(1);
+        self.slice_length();
+        self.swap(1);
+        self.dup(1);
+        self.slice_address();
+        self.swap(1);
+        self.slice_in_calldata();
+
+        let in_memory = self.asm.new_label();
+
+        self.op(Opcode::IsZero);
+        self.jump_if(in_memory);
+        // [length, offset] => [length, copy]
+        self.dup(2);
+        self.alloc_dynamic();
+        self.dup(3);
+        self.dup(3);
+        self.dup(3);
+        self.op(Opcode::CallDataCopy);
+        self.swap(1);
+        self.op(Opcode::Pop);
+        self.label(in_memory);
+    }
+
+    /// Replace the vector on top of the stack with a slice of all its elements
+    pub(super) fn vector_to_slice(&mut self) {
+        self.dup(1);
+        self.op(Opcode::MLoad);
+        self.push_u64(SLICE_LENGTH_SHIFT);
+        self.op(Opcode::Shl);
+        self.swap(1);
+        self.push_u64(32);
+        self.op(Opcode::Add);
+        self.op(Opcode::Or);
+    }
+
+    /// Replace the slice on top of the stack with the address of its first element
+    fn slice_address(&mut self) {
+        self.push_u64(u64::MAX);
+        self.op(Opcode::And);
+    }
+
+    /// Replace the slice on top of the stack with its number of elements
+    fn slice_length(&mut self) {
+        self.push_u64(SLICE_LENGTH_SHIFT);
+        self.op(Opcode::Shr);
+        self.push_u64(u64::MAX);
+        self.op(Opcode::And);
+    }
+
+    /// Replace the slice on top of the stack with 1 if its elements are in calldata, else 0
+    pub(super) fn slice_in_calldata(&mut self) {
+        self.push_u64(SLICE_CALLDATA_SHIFT);
+        self.op(Opcode::Shr);
+    }
+
+    /// Execute the memory opcode, or the calldata opcode with the same arguments if the flag on
+    /// top of the stack is set
+    pub(super) fn memory_or_calldata(&mut self, memory: Opcode, calldata: Opcode) {
+        let in_calldata = self.asm.new_label();
+        let done = self.asm.new_label();
+
+        self.jump_if(in_calldata);
+        let height = self.height;
+        self.op(memory);
+        self.jump(done);
+        self.label(in_calldata);
+        self.height = height;
+        self.op(calldata);
+        self.label(done);
+    }
+
+    /// Replace the slice on top of the stack with a new vector holding a copy of its elements
+    fn copy_slice(&mut self, elem_size: u64) {
+        self.dup(1);
+        self.slice_length();
+        self.alloc_vector(elem_size);
+        // [slice, vector] => copy(vector + 32, address, length * elem_size)
+        self.dup(2);
+        self.slice_length();
+        if elem_size != 1 {
+            self.push_u64(elem_size);
+            self.op(Opcode::Mul);
+        }
+        self.dup(3);
+        self.slice_address();
+        self.dup(3);
+        self.push_u64(32);
+        self.op(Opcode::Add);
+        self.dup(5);
+        self.slice_in_calldata();
+        self.memory_or_calldata(Opcode::MCopy, Opcode::CallDataCopy);
+        self.swap(1);
+        self.op(Opcode::Pop);
+    }
+
+    /// Concatenate the given number of byte slices on top of the stack into a new vector
+    fn concat(&mut self, count: usize) {
+        let first = self.height - count;
+
+        self.push_u64(0);
+        for pos in first..first + count {
+            self.dup_pos(pos);
+            self.slice_length();
+            self.op(Opcode::Add);
+        }
+        self.alloc_vector(1);
+
+        self.dup(1);
+        self.push_u64(32);
+        self.op(Opcode::Add);
+
+        for pos in first..first + count {
+            // [.., result, dest]
+            self.dup_pos(pos);
+            self.slice_length();
+            self.dup_pos(pos);
+            self.slice_address();
+            self.dup(3);
+            self.dup_pos(pos);
+            self.slice_in_calldata();
+            self.memory_or_calldata(Opcode::MCopy, Opcode::CallDataCopy);
+            self.dup_pos(pos);
+            self.slice_length();
+            self.op(Opcode::Add);
+        }
+
+        self.op(Opcode::Pop);
+        self.swap(count);
+        self.pop(count);
+    }
+
+    fn format_string(&mut self, args: &[(FormatArg, Expression)]) {
+        for (spec, arg) in args {
+            let ty = arg.ty();
+
+            match (spec, &ty) {
+                (FormatArg::StringLiteral, _) => {
+                    let Expression::BytesLiteral { value, .. } = arg else {
+                        unreachable!("string literal expected");
+                    };
+                    self.vector_literal(value);
+                }
+                (_, Type::String | Type::DynamicBytes) => self.expression(arg),
+                (_, Type::Bool) => {
+                    self.expression(arg);
+                    self.vector_literal(b"true");
+                    self.vector_literal(b"false");
+                    self.swap(1);
+                    self.dup(2);
+                    self.op(Opcode::Xor);
+                    self.dup(3);
+                    self.op(Opcode::Mul);
+                    self.op(Opcode::Xor);
+                    self.swap(1);
+                    self.op(Opcode::Pop);
+                }
+                (FormatArg::Binary | FormatArg::Hex, _) => {
+                    let (prefix, radix) = if *spec == FormatArg::Hex {
+                        (b"0x", 16)
+                    } else {
+                        (b"0b", 2)
+                    };
+
+                    self.vector_literal(prefix);
+                    self.vector_to_slice();
+                    self.expression(arg);
+                    if let Some((bits, true)) = int_type(&ty, self.ns) {
+                        if bits < 256 {
+                            self.push(&((BigInt::one() << bits) - 1));
+                            self.op(Opcode::And);
+                        }
+                    }
+                    self.push_u64(1);
+                    self.call_subroutine(Subroutine::FormatInt(radix));
+                    self.vector_to_slice();
+                    self.concat(2);
+                }
+                (_, Type::Bytes(_) | Type::Address(_) | Type::Contract(_)) => {
+                    let digits = 2 * ty.bytes(self.ns) as u64;
+
+                    self.vector_literal(b"0x");
+                    self.vector_to_slice();
+                    self.expression(arg);
+                    self.push_u64(digits);
+                    self.call_subroutine(Subroutine::FormatInt(16));
+                    self.vector_to_slice();
+                    self.concat(2);
+                }
+                _ if ty.is_signed_int(self.ns) => {
+                    // [v] => [neg, abs(v)]
+                    self.expression(arg);
+                    self.push_u64(0);
+                    self.dup(2);
+                    self.op(Opcode::SLt);
+                    self.swap(1);
+                    self.dup(2);
+                    self.push_u64(0);
+                    self.op(Opcode::Sub);
+                    self.swap(1);
+                    self.dup(2);
+                    self.op(Opcode::Xor);
+                    self.op(Opcode::Sub);
+                    self.push_u64(1);
+                    self.call_subroutine(Subroutine::FormatInt(10));
+                    self.vector_to_slice();
+
+                    // the minus sign, or the empty vector
+                    self.vector_literal(b"-");
+                    self.push_u64(ZERO_SLOT);
+                    self.op(Opcode::Xor);
+                    self.dup(3);
+                    self.op(Opcode::Mul);
+                    self.push_u64(ZERO_SLOT);
+                    self.op(Opcode::Xor);
+                    self.vector_to_slice();
+                    self.swap(1);
+                    self.concat(2);
+                    self.swap(1);
+                    self.op(Opcode::Pop);
+                }
+                _ => {
+                    self.expression(arg);
+                    self.push_u64(1);
+                    self.call_subroutine(Subroutine::FormatInt(10));
+                }
+            }
+
+            if args.len() > 1 {
+                self.vector_to_slice();
+            }
+        }
+
+        match args.len() {
+            0 => self.push_u64(ZERO_SLOT),
+            1 => (),
+            n => self.concat(n),
+        }
+    }
+
+    fn builtin(&mut self, loc: &Loc, kind: &Builtin, args: &[Expression], tys: &[Type]) {
+        let op = match kind {
+            Builtin::Value => Some(Opcode::CallValue),
+            Builtin::Sender => Some(Opcode::Caller),
+            Builtin::Origin => Some(Opcode::Origin),
+            Builtin::Gasleft => Some(Opcode::Gas),
+            Builtin::Gasprice => Some(Opcode::GasPrice),
+            Builtin::GasLimit => Some(Opcode::GasLimit),
+            Builtin::Timestamp => Some(Opcode::Timestamp),
+            Builtin::BlockNumber => Some(Opcode::Number),
+            Builtin::BlockCoinbase => Some(Opcode::Coinbase),
+            Builtin::BlockDifficulty | Builtin::PrevRandao => Some(Opcode::PrevRandao),
+            Builtin::BaseFee => Some(Opcode::BaseFee),
+            Builtin::ChainId => Some(Opcode::ChainId),
+            _ => None,
+        };
+
+        if let Some(op) = op {
+            self.op(op);
+            return;
+        }
+
+        match kind {
+            Builtin::BlockHash
+            | Builtin::Balance
+            | Builtin::ExtCodeSize
+            | Builtin::ContractCodehash => {
+                self.expression(&args[0]);
+                self.op(match kind {
+                    Builtin::BlockHash => Opcode::BlockHash,
+                    Builtin::Balance => Opcode::Balance,
+                    Builtin::ExtCodeSize => Opcode::ExtCodeSize,
+                    _ => Opcode::ExtCodeHash,
+                });
+            }
+            Builtin::ContractCode => {
+                self.expression(&args[0]);
+                self.dup(1);
+                self.op(Opcode::ExtCodeSize);
+                self.dup(1);
+                self.alloc_vector(1);
+                // [addr, size, vector] => extcodecopy(addr, vector + 32, 0, size)
+                self.swap(1);
+                self.push_u64(0);
+                self.dup(3);
+                self.push_u64(32);
+                self.op(Opcode::Add);
+                self.dup(5);
+                self.op(Opcode::ExtCodeCopy);
+                self.swap(1);
+                self.op(Opcode::Pop);
+            }
+            Builtin::Calldata => {
+                self.op(Opcode::CallDataSize);
+                self.alloc_vector(1);
+                self.op(Opcode::CallDataSize);
+                self.push_u64(0);
+                self.dup(3);
+                self.push_u64(32);
+                self.op(Opcode::Add);
+                self.op(Opcode::CallDataCopy);
+            }
+            Builtin::Signature => {
+                self.push_u64(SELECTOR_SLOT);
+                self.op(Opcode::MLoad);
+            }
+            Builtin::GetAddress => {
+                self.alloc(32);
+                self.op(Opcode::Address);
+                self.dup(2);
+                self.op(Opcode::MStore);
+            }
+            Builtin::ArrayLength => {
+                let ty = args[0].ty();
+
+                match (&args[0], ty.deref_memory()) {
+                    (
+                        Expression::Builtin {
+                            kind: Builtin::Calldata,
+                            ..
+                        },
+                        _,
+                    ) => self.op(Opcode::CallDataSize),
+                    (_, Type::Array(_, dims)) if dims.last() != Some(&ArrayLength::Dynamic) => {
+                        self.push(ty.array_length().unwrap());
+                    }
+                    (_, Type::Slice(_)) => {
+                        self.expression(&args[0]);
+                        self.slice_length();
+                    }
+                    _ => {
+                        self.expression(&args[0]);
+                        self.op(Opcode::MLoad);
+                    }
+                }
+            }
+            Builtin::ArraySlice => {
+                // A slice refers to the elements of the array, so nothing is copied. The bounds
+                // have already been checked.
+                let elem_size = mem_size(&tys[0].array_elem(), self.ns);
+
+                if let Expression::Builtin {
+                    kind: Builtin::Calldata,
+                    ..
+                } = &args[0]
+                {
+                    // msg.data is read in place
+                    self.push(&(BigInt::one() << SLICE_CALLDATA_SHIFT));
+                } else {
+                    let array_ty = args[0].ty();
+
+                    self.expression(&args[0]);
+
+                    if is_slice(&array_ty) {
+                        // keep the address and where the elements are
+                        self.push(&!(BigInt::from(u64::MAX) << SLICE_LENGTH_SHIFT));
+                        self.op(Opcode::And);
+                    } else {
+                        self.data_address(&array_ty);
+                    }
+                }
+
+                // [base] => [base + start * elem_size + (end - start) << 64]
+                self.element_offset(&args[1], elem_size);
+                self.expression(&args[1]);
+                self.expression(&args[2]);
+                self.op(Opcode::Sub);
+                self.push_u64(SLICE_LENGTH_SHIFT);
+                self.op(Opcode::Shl);
+                self.op(Opcode::Add);
+            }
+            Builtin::ReadFromBuffer => self.read_buffer(&tys[0], &args[0], &args[1]),
+            Builtin::AddMod | Builtin::MulMod => {
+                self.expression(&args[2]);
+                self.expression(&args[1]);
+                self.expression(&args[0]);
+                self.op(if *kind == Builtin::AddMod {
+                    Opcode::AddMod
+                } else {
+                    Opcode::MulMod
+                });
+            }
+            Builtin::Keccak256 => {
+                self.memory_bytes(&args[0]);
+                self.op(Opcode::Keccak256);
+            }
+            Builtin::Sha256 | Builtin::Ripemd160 => {
+                let precompile = if *kind == Builtin::Sha256 { 2 } else { 3 };
+
+                self.memory_bytes(&args[0]);
+                self.push_u64(32);
+                self.push_u64(0);
+                self.dup(4);
+                self.dup(4);
+                self.push_u64(precompile);
+                self.op(Opcode::Gas);
+                self.op(Opcode::StaticCall);
+                self.op(Opcode::IsZero);
+                self.panic_if(PanicCode::Generic as u8);
+                self.pop(2);
+                self.push_u64(0);
+                self.op(Opcode::MLoad);
+            }
+            Builtin::Concat => {
+                for arg in args {
+                    self.expression(arg);
+
+                    if !is_slice(&arg.ty()) {
+                        self.vector_to_slice();
+                    }
+                }
+                self.concat(args.len());
+            }
+            _ => {
+                self.diagnostics.push(Diagnostic::error(
+                    *loc,
+                    format!("builtin '{kind:?}' is not available on EVM"),
+                ));
+                // the code is discarded, but keep the stack balanced
+                self.push_u64(0);
+            }
+        }
+    }
+
+    /// Evaluate a mapping key, and push it the way it is hashed: value types as a word, and
+    /// strings and bytes as a vector
+    fn mapping_key(&mut self, index: &Expression, key_ty: &Type) {
+        self.expression(index);
+
+        if let Type::StorageRef(_, ty) = index.ty() {
+            if matches!(ty.as_ref(), Type::String | Type::DynamicBytes) {
+                self.call_subroutine(Subroutine::StorageBytesLoad);
+            } else {
+                self.op(Opcode::SLoad);
+            }
+        }
+
+        if let Type::Bytes(n) = key_ty {
+            if *n < 32 {
+                self.push_u64(256 - 8 * *n as u64);
+                self.op(Opcode::Shl);
+            }
+        }
+    }
+}
+
+/// Types which are stored big endian in buffers
+fn is_big_endian(ty: &Type) -> bool {
+    matches!(
+        ty,
+        Type::Bytes(_) | Type::Address(_) | Type::Contract(_) | Type::FunctionSelector
+    )
+}
@@ -0,0 +1,590 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::assembler::{Item, Opcode};
+use super::subroutines::Subroutine;
+use super::{is_slice, Unit, RETURN_AREA};
+use crate::codegen::cfg::{ControlFlowGraph, Instr, InternalCallTy, ReturnCode};
+use crate::codegen::revert::PanicCode;
+use crate::codegen::Expression;
+use crate::sema::ast::{CallTy, RetrieveType, Type};
+use solang_parser::pt::StorageType;
+
+impl Unit<'_> {
+    /// Emit an instruction. Instructions leave the stack height unchanged.
+    pub(super) fn instruction(&mut self, instr: &Instr, cfg: &ControlFlowGraph) {
+        match instr {
+            Instr::Nop | Instr::Print { .. } => (),
+            Instr::Set { res, expr, .. } => {
+                self.expression(expr);
+                self.store_var(*res);
+            }
+            Instr::Branch { block } => {
+                let label = self.frame.blocks[*block];
+                self.jump(label);
+            }
+            Instr::BranchCond {
+                cond,
+                true_block,
+                false_block,
+            } => {
+                let true_label = self.frame.blocks[*true_block];
+                let false_label = self.frame.blocks[*false_block];
+
+                self.expression(cond);
+                self.jump_if(true_label);
+                self.jump(false_label);
+            }
+            Instr::Switch {
+                cond,
+                cases,
+                default,
+            } => {
+                let default = self.frame.blocks[*default];
+                let targets: Vec<_> = cases.iter().map(|_| self.asm.new_label()).collect();
+
+                self.expression(cond);
+
+                for ((value, _), target) in cases.iter().zip(&targets) {
+                    self.dup(1);
+                    self.expression(value);
+                    self.op(Opcode::Eq);
+                    self.jump_if(*target);
+                }
+
+                self.op(Opcode::Pop);
+                self.jump(default);
+
+                // the condition has to be popped before jumping to the block
+                for ((_, block), target) in cases.iter().zip(targets) {
+                    let block = self.frame.blocks[*block];
+
+                    self.height = self.frame.size + 1;
+                    self.label(target);
+                    self.op(Opcode::Pop);
+                    self.jump(block);
+                }
+            }
+            Instr::Call {
+                res, call, args, ..
+            } => {
+                if let InternalCallTy::Builtin { .. } = call {
+                    // ecrecover is the only builtin function which is called like this on EVM
+                    self.ecrecover(args);
+                    self.store_var(res[0]);
+                    return;
+                }
+
+                let height = self.height;
+                let ret = self.asm.new_label();
+
+                self.push_label(ret);
+
+                for arg in args {
+                    self.expression(arg);
+                }
+
+                match call {
+                    InternalCallTy::Static { cfg_no } => {
+                        let label = self.function_label(*cfg_no);
+                        self.jump(label);
+                    }
+                    InternalCallTy::Dynamic(expr) => {
+                        self.expression(expr);
+                        self.op(Opcode::Jump);
+                    }
+                    InternalCallTy::HostFunction { name } => {
+                        unreachable!("host function {name} on EVM")
+                    }
+                    InternalCallTy::Builtin { .. } => unreachable!(),
+                }
+
+                self.height = height;
+                self.label(ret);
+
+                for (no, res) in res.iter().enumerate() {
+                    self.push_u64(RETURN_AREA + 32 * no as u64);
+                    self.op(Opcode::MLoad);
+                    self.store_var(*res);
+                }
+            }
+            Instr::Return { value } => {
+                for (no, value) in value.iter().enumerate() {
+                    self.expression(value);
+                    self.push_u64(RETURN_AREA + 32 * no as u64);
+                    self.op(Opcode::MStore);
+                }
+
+                self.release_frame();
+                self.pop(self.frame.size - 1);
+                self.op(Opcode::Jump);
+            }
+            Instr::Store { dest, data } => {
+                self.expression(data);
+                self.expression(dest);
+                self.store_memory(&data.ty());
+            }
+            Instr::AssertFailure { encoded_args } => {
+                if let Some(data) = encoded_args {
+                    self.memory_bytes(data);
+                } else {
+                    self.push_u64(0);
+                    self.push_u64(0);
+                }
+                self.op(Opcode::Revert);
+            }
+            Instr::LoadStorage {
+                res,
+                ty,
+                storage,
+                storage_type,
+            } => {
+                self.expression(storage);
+                // transient variables are always value types, which fit in a single slot
+                if let Some(StorageType::Transient(_)) = storage_type {
+                    self.op(Opcode::TLoad);
+                } else {
+                    self.load_storage(ty);
+                }
+                self.store_var(*res);
+            }
+            Instr::SetStorage {
+                ty,
+                value,
+                storage,
+                storage_type,
+            } => {
+                self.expression(value);
+                self.expression(storage);
+                if let Some(StorageType::Transient(_)) = storage_type {
+                    self.op(Opcode::TStore);
+                } else {
+                    self.store_storage(ty);
+                }
+            }
+            Instr::ClearStorage { ty, storage } => {
+                self.expression(storage);
+                self.clear_storage(ty);
+            }
+            Instr::SetStorageBytes {
+                value,
+                storage,
+                offset,
+            } => {
+                self.expression(storage);
+                self.expression(offset);
+                self.expression(value);
+                self.call_subroutine(Subroutine::StorageByteStore);
+            }
+            Instr::PushStorage {
+                res,
+                value,
+                storage,
+                ..
+            } => {
+                // only bytes are pushed this way on EVM; arrays use slot arithmetic
+                self.expression(storage);
+                self.dup(1);
+                self.op(Opcode::SLoad);
+                self.dup(1);
+                self.push_u64(1);
+                self.op(Opcode::Add);
+                self.dup(3);
+                self.op(Opcode::SStore);
+                match value {
+                    Some(value) => self.expression(value),
+                    None => self.push_u64(0),
+                }
+                // [slot, len, value] => [value, slot, len, value]
+                self.dup(1);
+                self.swap(3);
+                self.swap(2);
+                self.swap(1);
+                self.call_subroutine(Subroutine::StorageByteStore);
+                self.store_var(*res);
+            }
+            Instr::PopStorage { res, storage, .. } => {
+                // [slot] => [slot, len - 1]
+                self.expression(storage);
+                self.dup(1);
+                self.op(Opcode::SLoad);
+                self.dup(1);
+                self.op(Opcode::IsZero);
+                self.panic_if(PanicCode::EmptyArrayPop as u8);
+                self.push_u64(1);
+                self.swap(1);
+                self.op(Opcode::Sub);
+
+                if let Some(res) = res {
+                    self.dup(2);
+                    self.dup(2);
+                    self.call_subroutine(Subroutine::StorageByteLoad);
+                    self.store_var(*res);
+                }
+
+                self.dup(2);
+                self.dup(2);
+                self.push_u64(0);
+                self.call_subroutine(Subroutine::StorageByteStore);
+                self.swap(1);
+                self.op(Opcode::SStore);
+            }
+            Instr::PushMemory {
+                res,
+                ty,
+                array,
+                value,
+            } => self.push_memory(*res, ty, *array, value),
+            Instr::PopMemory { res, ty, array, .. } => {
+                let elem_ty = ty.array_elem();
+                let elem_size = super::mem_size(&elem_ty, self.ns);
+
+                // [vector, len - 1]
+                self.load_var(*array);
+                self.dup(1);
+                self.op(Opcode::MLoad);
+                self.dup(1);
+                self.op(Opcode::IsZero);
+                self.panic_if(PanicCode::EmptyArrayPop as u8);
+                self.push_u64(1);
+                self.swap(1);
+                self.op(Opcode::Sub);
+                self.dup(1);
+                self.dup(3);
+                self.op(Opcode::MStore);
+
+                if elem_size != 1 {
+                    self.push_u64(elem_size);
+                    self.op(Opcode::Mul);
+                }
+                self.op(Opcode::Add);
+                self.push_u64(32);
+                self.op(Opcode::Add);
+                self.load_memory(&elem_ty);
+                self.store_var(*res);
+            }
+            Instr::Constructor {
+                success,
+                res,
+                contract_no,
+                encoded_args,
+                value,
+                salt,
+                ..
+            } => {
+                self.create(*contract_no, encoded_args, value.as_ref(), salt.as_ref());
+                self.after_call(*success);
+                self.store_var(*res);
+            }
+            Instr::ExternalCall {
+                success,
+                address,
+                payload,
+                value,
+                gas,
+                callty,
+                ..
+            } => {
+                // [payload] => [payload, out size, out offset, in size, in offset]
+                self.expression(payload);
+                self.push_u64(0);
+                self.push_u64(0);
+                self.dup(3);
+                self.op(Opcode::MLoad);
+                self.dup(4);
+                self.push_u64(32);
+                self.op(Opcode::Add);
+
+                if *callty == CallTy::Regular {
+                    self.expression(value);
+                }
+
+                self.expression(address.as_ref().expect("call needs an address"));
+                self.expression(gas);
+                self.op(match callty {
+                    CallTy::Regular => Opcode::Call,
+                    CallTy::Static => Opcode::StaticCall,
+                    CallTy::Delegate => Opcode::DelegateCall,
+                });
+                self.swap(1);
+                self.op(Opcode::Pop);
+
+                self.call_result(*success);
+            }
+            Instr::ValueTransfer {
+                success,
+                address,
+                value,
+            } => {
+                // The EVM adds a stipend of 2300 gas when value is transferred; only give
+                // that much gas when there is no value
+                self.push_u64(0);
+                self.push_u64(0);
+                self.push_u64(0);
+                self.push_u64(0);
+                self.expression(value);
+                self.expression(address);
+                self.dup(2);
+                self.op(Opcode::IsZero);
+                self.push_u64(2300);
+                self.op(Opcode::Mul);
+                self.op(Opcode::Call);
+
+                self.call_result(*success);
+            }
+            Instr::SelfDestruct { recipient } => {
+                self.expression(recipient);
+                self.op(Opcode::SelfDestruct);
+            }
+            Instr::EmitEvent { data, topics, .. } => {
+                for topic in topics.iter().rev() {
+                    self.expression(topic);
+                    self.vector_data(topic);
+                    self.op(Opcode::MLoad);
+                }
+
+                self.memory_bytes(data);
+                self.emit(Item::Log(topics.len() as u8));
+            }
+            Instr::WriteBuffer { buf, offset, value } => self.write_buffer(buf, offset, value),
+            Instr::MemCopy {
+                source,
+                destination,
+                bytes,
+            } => {
+                self.expression(bytes);
+                self.expression(source);
+                self.vector_data(source);
+                self.expression(destination);
+                self.vector_data(destination);
+
+                if let Some(slice) = source_slice(source) {
+                    self.expression(slice);
+                    self.slice_in_calldata();
+                    self.memory_or_calldata(Opcode::MCopy, Opcode::CallDataCopy);
+                } else {
+                    self.op(Opcode::MCopy);
+                }
+            }
+            Instr::ReturnData { data, data_len } => {
+                self.expression(data_len);
+                self.expression(data);
+                self.vector_data(data);
+                self.op(Opcode::Return);
+            }
+            Instr::ReturnCode { code } => match (code, self.runtime) {
+                (ReturnCode::Success, Some(runtime)) => {
+                    let data = self.asm.add_data(runtime);
+
+                    self.push_u64(runtime.len() as u64);
+                    self.emit(Item::PushData(data));
+                    self.push_u64(0);
+                    self.op(Opcode::CodeCopy);
+                    self.push_u64(runtime.len() as u64);
+                    self.push_u64(0);
+                    self.op(Opcode::Return);
+                }
+                (ReturnCode::Success, None) => self.op(Opcode::Stop),
+                _ => {
+                    self.push_u64(0);
+                    self.push_u64(0);
+                    self.op(Opcode::Revert);
+                }
+            },
+            Instr::Unimplemented { .. } => self.op(Opcode::Invalid),
+            Instr::AccountAccess { .. } => {
+                unreachable!("account access in function '{}' on EVM", cfg.name)
+            }
+        }
+    }
+
+    /// Adjust the pointer on top of the stack to the data of a vector or slice, if the expression
+    /// is one
+    fn vector_data(&mut self, expr: &Expression) {
+        self.data_address(&expr.ty());
+    }
+
+    /// Replace the vector in a variable with a copy which has the value appended
+    fn push_memory(&mut self, res: usize, ty: &Type, array: usize, value: &Expression) {
+        let elem_ty = ty.array_elem();
+        let elem_size = super::mem_size(&elem_ty, self.ns);
+
+        // [old, len, new]
+        self.load_var(array);
+        self.dup(1);
+        self.op(Opcode::MLoad);
+        self.dup(1);
+        self.push_u64(1);
+        self.op(Opcode::Add);
+        self.alloc_vector(elem_size);
+
+        // copy the existing elements
+        self.dup(2);
+        if elem_size != 1 {
+            self.push_u64(elem_size);
+            self.op(Opcode::Mul);
+        }
+        self.dup(4);
+        self.push_u64(32);
+        self.op(Opcode::Add);
+        self.dup(3);
+        self.push_u64(32);
+        self.op(Opcode::Add);
+        self.op(Opcode::MCopy);
+
+        // the result is the value, and for structs and fixed arrays the new element
+        self.expression(value);
+        self.dup(1);
+        self.dup(4);
+        if elem_size != 1 {
+            self.push_u64(elem_size);
+            self.op(Opcode::Mul);
+        }
+        self.dup(4);
+        self.op(Opcode::Add);
+        self.push_u64(32);
+        self.op(Opcode::Add);
+
+        if super::is_inline(&elem_ty, self.ns) {
+            // [old, len, new, value, value, address] => [old, len, new, address, value, address]
+            self.swap(2);
+            self.op(Opcode::Pop);
+            self.dup(2);
+        }
+
+        self.store_memory(&elem_ty);
+        self.store_var(res);
+        self.store_var(array);
+        self.pop(2);
+    }
+
+    /// Create a contract. The arguments are appended to the creation code.
+    fn create(
+        &mut self,
+        contract_no: usize,
+        encoded_args: &Expression,
+        value: Option<&Expression>,
+        salt: Option<&Expression>,
+    ) {
+        let code = match super::build(contract_no, self.ns, self.opt) {
+            Ok(code) => code.creation,
+            Err(diagnostics) => {
+                self.diagnostics.extend(diagnostics);
+                return;
+            }
+        };
+        let code_len = code.len() as u64;
+        let data = self.asm.add_data(&code);
+
+        // [args, buffer]
+        self.expression(encoded_args);
+        self.dup(1);
+        self.op(Opcode::MLoad);
+        self.push_u64(code_len);
+        self.op(Opcode::Add);
+        self.alloc_dynamic();
+
+        self.push_u64(code_len);
+        self.emit(Item::PushData(data));
+        self.dup(3);
+        self.op(Opcode::CodeCopy);
+
+        self.dup(2);
+        self.op(Opcode::MLoad);
+        self.dup(3);
+        self.push_u64(32);
+        self.op(Opcode::Add);
+        self.dup(3);
+        self.push_u64(code_len);
+        self.op(Opcode::Add);
+        self.op(Opcode::MCopy);
+
+        if let Some(salt) = salt {
+            self.expression(salt);
+        }
+
+        self.dup(if salt.is_some() { 3 } else { 2 });
+        self.op(Opcode::MLoad);
+        self.push_u64(code_len);
+        self.op(Opcode::Add);
+        self.dup(if salt.is_some() { 3 } else { 2 });
+
+        match value {
+            Some(value) => self.expression(value),
+            None => self.push_u64(0),
+        }
+
+        self.op(if salt.is_some() {
+            Opcode::Create2
+        } else {
+            Opcode::Create
+        });
+        self.swap(2);
+        self.pop(2);
+    }
+
+    /// After creating a contract, either set the success variable or revert with the return
+    /// data of the constructor when it failed. The address stays on the stack.
+    fn after_call(&mut self, success: Option<usize>) {
+        self.dup(1);
+        self.op(Opcode::IsZero);
+
+        if let Some(success) = success {
+            self.op(Opcode::IsZero);
+            self.store_var(success);
+        } else {
+            let label = self.subroutine_label(Subroutine::Bubble);
+            self.jump_if(label);
+        }
+    }
+
+    /// Store the success flag of a call, or revert with its return data if it failed
+    fn call_result(&mut self, success: Option<usize>) {
+        if let Some(success) = success {
+            self.store_var(success);
+        } else {
+            self.op(Opcode::IsZero);
+            let label = self.subroutine_label(Subroutine::Bubble);
+            self.jump_if(label);
+        }
+    }
+
+    /// Recover the signer address with the ecrecover precompile
+    fn ecrecover(&mut self, args: &[Expression]) {
+        self.alloc(128);
+
+        for (no, arg) in args.iter().enumerate() {
+            self.expression(arg);
+            self.dup(2);
+            self.push_u64(32 * no as u64);
+            self.op(Opcode::Add);
+            self.op(Opcode::MStore);
+        }
+
+        // the output is left untouched if the signature is invalid
+        self.push_u64(0);
+        self.push_u64(0);
+        self.op(Opcode::MStore);
+
+        self.push_u64(32);
+        self.push_u64(0);
+        self.push_u64(128);
+        self.dup(4);
+        self.push_u64(1);
+        self.op(Opcode::Gas);
+        self.op(Opcode::StaticCall);
+        self.pop(2);
+        self.push_u64(0);
+        self.op(Opcode::MLoad);
+    }
+}
+
+/// The slice a memory copy reads from, whose elements may be in calldata
+fn source_slice(source: &Expression) -> Option<&Expression> {
+    match source {
+        Expression::AdvancePointer { pointer, .. } | Expression::VectorData { pointer } => {
+            source_slice(pointer)
+        }
+        _ if is_slice(&source.ty()) => Some(source),
+        _ => None,
+    }
+}
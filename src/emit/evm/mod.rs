@@ -0,0 +1,800 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Native EVM bytecode generation.
+//!
+//! The control flow graph is lowered directly to EVM bytecode, without going through LLVM.
+//! Function parameters and local variables live on the EVM stack; when a function has too
+//! many variables to be reachable with `DUPn`/`SWAPn`, the least used ones are moved to a
+//! frame in memory.
+//!
+//! Memory layout:
+//! - `0x00..0x40`: scratch space for hashing and precompile calls
+//! - `0x40`: the free memory pointer
+//! - `0x60`: a zero word, which doubles as the empty vector
+//! - `0x80`: the function selector, written by the dispatcher
+//! - `0xa0..`: return values of internal functions, one word each
+//!
+//! The heap follows the return area. The only memory which is freed is the frame of a function
+//! which returns while its frame is still the last allocation; the frame is zeroed first, so
+//! memory beyond the free memory pointer is always zero and newly allocated memory is zeroed.
+//!
+//! Values on the stack are 256 bit words. Unsigned values are zero extended and signed values
+//! are sign extended. Memory vectors (strings, bytes and dynamic arrays) are a length word
+//! followed by the elements, and structs and fixed length arrays are stored inline.
+//!
+//! Slices do not own their elements; they are a view of memory or of calldata, packed in a word:
+//! the address of the first element in the lowest 64 bits, the number of elements in the next
+//! 64 bits, and bit 128 set if the elements are in calldata.
+
+mod assembler;
+mod expression;
+mod instructions;
+mod storage;
+mod subroutines;
+
+use crate::codegen::cfg::{ASTFunction, ControlFlowGraph, Instr};
+use crate::codegen::dispatch::polkadot::DispatchType;
+use crate::codegen::{Expression, Options};
+use crate::sema::ast::{ArrayLength, Contract, Diagnostic, Namespace, Type};
+use assembler::{Assembler, Item, Label, Opcode};
+use num_bigint::BigInt;
+use solang_parser::pt::Loc;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use subroutines::Subroutine;
+
+const FREE_POINTER: u64 = 0x40;
+const ZERO_SLOT: u64 = 0x60;
+const SELECTOR_SLOT: u64 = 0x80;
+const RETURN_AREA: u64 = 0xa0;
+
+/// The position of the length in a slice
+const SLICE_LENGTH_SHIFT: u64 = 64;
+/// The position of the calldata flag in a slice
+const SLICE_CALLDATA_SHIFT: u64 = 128;
+
+/// The maximum size of the runtime code, see EIP-170
+const MAX_CODE_SIZE: usize = 24576;
+/// The maximum size of the creation code, see EIP-3860
+const MAX_INITCODE_SIZE: usize = 2 * MAX_CODE_SIZE;
+
+/// The deployable code of a contract
+pub struct EvmCode {
+    /// Code which is executed on deployment. It runs the constructor and returns the runtime
+    /// code. Constructor arguments are appended to it.
+    pub creation: Vec<u8>,
+    /// Code which is stored on chain and executed for each call
+    pub runtime: Vec<u8>,
+}
+
+/// Generate the EVM bytecode for a contract. Contracts which cannot be represented in EVM
+/// bytecode, e.g. because a function needs too many stack slots or the code exceeds the size
+/// limits, produce errors rather than code.
+pub fn build(
+    contract_no: usize,
+    ns: &Namespace,
+    opt: &Options,
+) -> Result<EvmCode, Vec<Diagnostic>> {
+    let contract = &ns.contracts[contract_no];
+
+    let runtime = Unit::new(contract_no, ns, opt, None).build()?;
+
+    if runtime.len() > MAX_CODE_SIZE {
+        return Err(vec![Diagnostic::error(
+            contract.loc,
+            format!(
+                "contract '{}' has {} bytes of runtime code, which exceeds the limit of {} bytes (EIP-170)",
+                contract.id, runtime.len(), MAX_CODE_SIZE
+            ),
+        )]);
+    }
+
+    let creation = Unit::new(contract_no, ns, opt, Some(&runtime)).build()?;
+
+    if creation.len() > MAX_INITCODE_SIZE {
+        return Err(vec![Diagnostic::error(
+            contract.loc,
+            format!(
+                "contract '{}' has {} bytes of creation code, which exceeds the limit of {} bytes (EIP-3860)",
+                contract.id, creation.len(), MAX_INITCODE_SIZE
+            ),
+        )]);
+    }
+
+    Ok(EvmCode { creation, runtime })
+}
+
+/// Where a parameter or variable of the function being emitted is kept
+#[derive(Clone, Copy, Debug)]
+enum Slot {
+    /// Position on the stack, counted from the bottom of the frame
+    Stack(usize),
+    /// Offset from the frame pointer
+    Memory(u64),
+}
+
+#[derive(Default)]
+struct Frame {
+    params: Vec<Slot>,
+    vars: HashMap<usize, Slot>,
+    /// Stack position of the frame pointer, if anything lives in memory
+    frame_pointer: Option<usize>,
+    /// Number of bytes of the frame in memory
+    memory_size: u64,
+    /// Stack height at the start of each basic block
+    size: usize,
+    blocks: Vec<Label>,
+}
+
+/// A unit of code: either the creation code or the runtime code of a contract
+struct Unit<'a> {
+    ns: &'a Namespace,
+    opt: &'a Options,
+    contract: &'a Contract,
+    /// When generating creation code, this is the runtime code which the constructor returns
+    runtime: Option<&'a [u8]>,
+    asm: Assembler,
+    /// Current stack height, relative to the bottom of the current frame
+    height: usize,
+    /// Set when a stack slot was out of reach of `DUPn` or `SWAPn`
+    too_deep: bool,
+    functions: HashMap<usize, Label>,
+    function_queue: Vec<usize>,
+    subroutines: HashMap<Subroutine, Label>,
+    subroutine_queue: Vec<Subroutine>,
+    frame: Frame,
+    heap_start: u64,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Unit<'a> {
+    fn new(
+        contract_no: usize,
+        ns: &'a Namespace,
+        opt: &'a Options,
+        runtime: Option<&'a [u8]>,
+    ) -> Self {
+        let contract = &ns.contracts[contract_no];
+
+        let max_returns = contract
+            .cfg
+            .iter()
+            .flat_map(|cfg| {
+                cfg.blocks
+                    .iter()
+                    .flat_map(|bb| bb.instr.iter())
+                    .map(|instr| match instr {
+                        Instr::Return { value } => value.len(),
+                        Instr::Call { res, .. } => res.len(),
+                        _ => 0,
+                    })
+                    .chain(std::iter::once(cfg.returns.len()))
+            })
+            .max()
+            .unwrap_or_default() as u64;
+
+        Unit {
+            ns,
+            opt,
+            contract,
+            runtime,
+            asm: Assembler::default(),
+            height: 0,
+            too_deep: false,
+            functions: HashMap::new(),
+            function_queue: Vec::new(),
+            subroutines: HashMap::new(),
+            subroutine_queue: Vec::new(),
+            frame: Frame::default(),
+            heap_start: RETURN_AREA + 32 * max_returns,
+            diagnostics: Vec::new(),
+        }
+    }
+
+    fn build(mut self) -> Result<Vec<u8>, Vec<Diagnostic>> {
+        if self.runtime.is_some() {
+            self.creation_entry();
+        } else {
+            self.runtime_entry();
+        }
+
+        loop {
+            if let Some(cfg_no) = self.function_queue.pop() {
+                self.function(cfg_no);
+            } else if let Some(subroutine) = self.subroutine_queue.pop() {
+                self.subroutine(subroutine);
+            } else {
+                break;
+            }
+        }
+
+        if !self.diagnostics.is_empty() {
+            return Err(self.diagnostics);
+        }
+
+        self.asm.assemble().ok_or_else(|| {
+            vec![Diagnostic::error(
+                self.contract.loc,
+                format!("contract '{}' is too large", self.contract.id),
+            )]
+        })
+    }
+
+    fn dispatch_cfg(&self, ty: DispatchType) -> usize {
+        let name = ty.to_string();
+
+        self.contract
+            .cfg
+            .iter()
+            .position(|cfg| cfg.name == name)
+            .expect("contract should have a dispatch function")
+    }
+
+    /// The runtime code copies the call data into memory and calls the function dispatcher
+    fn runtime_entry(&mut self) {
+        self.init_free_pointer();
+
+        let dispatch = self.function_label(self.dispatch_cfg(DispatchType::Call));
+        let ret = self.asm.new_label();
+
+        self.push_label(ret);
+        self.op(Opcode::CallDataSize);
+        self.alloc_vector(1);
+        self.op(Opcode::CallDataSize);
+        self.push_u64(0);
+        self.dup(3);
+        self.push_u64(32);
+        self.op(Opcode::Add);
+        self.op(Opcode::CallDataCopy);
+        self.push_u64(32);
+        self.op(Opcode::Add);
+        self.op(Opcode::CallDataSize);
+        self.op(Opcode::CallValue);
+        self.push_u64(SELECTOR_SLOT);
+        self.jump(dispatch);
+
+        // The dispatcher never returns
+        self.height = 0;
+        self.label(ret);
+        self.op(Opcode::Stop);
+    }
+
+    /// The creation code initializes storage, and then calls the constructor with the arguments
+    /// which are appended to the code
+    fn creation_entry(&mut self) {
+        self.init_free_pointer();
+
+        if let Some(initializer) = self.contract.initializer {
+            let label = self.function_label(initializer);
+            let ret = self.asm.new_label();
+
+            self.push_label(ret);
+            self.jump(label);
+            self.label(ret);
+            self.height = 0;
+        }
+
+        let dispatch = self.function_label(self.dispatch_cfg(DispatchType::Deploy));
+        let ret = self.asm.new_label();
+
+        self.push_label(ret);
+        self.emit(Item::PushCodeLength);
+        self.op(Opcode::CodeSize);
+        self.op(Opcode::Sub);
+        self.dup(1);
+        self.alloc_vector(1);
+        self.dup(2);
+        self.emit(Item::PushCodeLength);
+        self.dup(3);
+        self.push_u64(32);
+        self.op(Opcode::Add);
+        self.op(Opcode::CodeCopy);
+        self.push_u64(32);
+        self.op(Opcode::Add);
+        self.swap(1);
+        self.op(Opcode::CallValue);
+        self.push_u64(SELECTOR_SLOT);
+        self.jump(dispatch);
+
+        self.height = 0;
+        self.label(ret);
+        self.op(Opcode::Stop);
+    }
+
+    fn init_free_pointer(&mut self) {
+        self.push_u64(self.heap_start);
+        self.push_u64(FREE_POINTER);
+        self.op(Opcode::MStore);
+    }
+
+    /// Get the label of a function, and queue it for code generation if needed
+    fn function_label(&mut self, cfg_no: usize) -> Label {
+        if let Some(label) = self.functions.get(&cfg_no) {
+            return *label;
+        }
+
+        let label = self.asm.new_label();
+        self.functions.insert(cfg_no, label);
+        self.function_queue.push(cfg_no);
+        label
+    }
+
+    /// Emit a function. Functions are called with the return address followed by the arguments
+    /// on the stack, and return their values in the return area.
+    fn function(&mut self, cfg_no: usize) {
+        let contract = self.contract;
+        let cfg = &contract.cfg[cfg_no];
+        let label = self.functions[&cfg_no];
+
+        let mut uses = HashMap::new();
+
+        for instr in cfg.blocks.iter().flat_map(|bb| bb.instr.iter()) {
+            instr.recurse_expressions(&mut uses, count_variable_uses);
+        }
+
+        let start = self.asm.items.len();
+
+        // Try to keep as many variables on the stack as possible; if any stack slot is out of
+        // reach, retry with fewer variables on the stack
+        for (max_stack_vars, spill_params) in [
+            (16, false),
+            (12, false),
+            (8, false),
+            (4, false),
+            (0, false),
+            (0, true),
+        ] {
+            self.too_deep = false;
+            self.label(label);
+            self.prologue(cfg, &uses, max_stack_vars, spill_params);
+
+            for (block_no, block) in cfg.blocks.iter().enumerate() {
+                let block_label = self.frame.blocks[block_no];
+                self.label(block_label);
+                self.height = self.frame.size;
+
+                for instr in &block.instr {
+                    self.instruction(instr, cfg);
+                }
+            }
+
+            if !self.too_deep {
+                return;
+            }
+
+            self.asm.items.truncate(start);
+        }
+
+        let loc = self.function_loc(cfg);
+
+        self.diagnostics.push(Diagnostic::error(
+            loc,
+            format!(
+                "function '{}' needs too many stack slots, consider splitting it into smaller functions",
+                cfg.name
+            ),
+        ));
+    }
+
+    /// The location of the function which a control flow graph was generated for
+    fn function_loc(&self, cfg: &ControlFlowGraph) -> Loc {
+        match cfg.function_no {
+            ASTFunction::SolidityFunction(function_no) => {
+                self.ns.functions[function_no].loc_prototype
+            }
+            ASTFunction::YulFunction(function_no) => self.ns.yul_functions[function_no].loc,
+            ASTFunction::None => self.contract.loc,
+        }
+    }
+
+    /// Set up the stack frame of a function: place the variables and initialize them
+    fn prologue(
+        &mut self,
+        cfg: &ControlFlowGraph,
+        uses: &HashMap<usize, usize>,
+        max_stack_vars: usize,
+        spill_params: bool,
+    ) {
+        let params = cfg.params.len();
+
+        let mut vars: Vec<usize> = cfg.vars.keys().copied().collect();
+        vars.sort_by_key(|var_no| Reverse(uses.get(var_no).copied().unwrap_or_default()));
+
+        let memory_vars = vars.len().saturating_sub(max_stack_vars);
+        let spilled_params = if spill_params { params } else { 0 };
+
+        let mut frame = Frame {
+            blocks: cfg.blocks.iter().map(|_| self.asm.new_label()).collect(),
+            ..Default::default()
+        };
+
+        // the return address and the parameters are on the stack
+        self.height = 1 + params;
+
+        frame.memory_size = 32 * (spilled_params + memory_vars) as u64;
+
+        if spill_params {
+            self.alloc(frame.memory_size);
+
+            for param_no in (0..params).rev() {
+                self.swap(1);
+                self.dup(2);
+                self.push_u64(32 * param_no as u64);
+                self.op(Opcode::Add);
+                self.op(Opcode::MStore);
+            }
+
+            frame.params = (0..params).map(|no| Slot::Memory(32 * no as u64)).collect();
+            frame.frame_pointer = Some(1);
+        } else {
+            frame.params = (0..params).map(|no| Slot::Stack(1 + no)).collect();
+        }
+
+        for (no, var_no) in vars.iter().enumerate() {
+            if no < max_stack_vars {
+                let ty = &cfg.vars[var_no].ty;
+
+                frame.vars.insert(*var_no, Slot::Stack(self.height));
+                self.push_u64(if is_vector(ty, self.ns) { ZERO_SLOT } else { 0 });
+            }
+        }
+
+        if memory_vars > 0 && !spill_params {
+            self.alloc(frame.memory_size);
+            frame.frame_pointer = Some(self.height - 1);
+        }
+
+        for (no, var_no) in vars.iter().enumerate().skip(max_stack_vars) {
+            let offset = 32 * (spilled_params + no - max_stack_vars) as u64;
+
+            frame.vars.insert(*var_no, Slot::Memory(offset));
+
+            if is_vector(&cfg.vars[var_no].ty, self.ns) {
+                self.push_u64(ZERO_SLOT);
+                self.dup_pos(frame.frame_pointer.unwrap());
+                self.push_u64(offset);
+                self.op(Opcode::Add);
+                self.op(Opcode::MStore);
+            }
+        }
+
+        frame.size = self.height;
+        self.frame = frame;
+    }
+
+    /// Push the value of a variable
+    fn load_var(&mut self, var_no: usize) {
+        let slot = *self
+            .frame
+            .vars
+            .get(&var_no)
+            .unwrap_or_else(|| panic!("variable {var_no} not in frame"));
+
+        self.load_slot(slot);
+    }
+
+    /// Pop the top of the stack into a variable
+    fn store_var(&mut self, var_no: usize) {
+        let slot = *self
+            .frame
+            .vars
+            .get(&var_no)
+            .unwrap_or_else(|| panic!("variable {var_no} not in frame"));
+
+        self.store_slot(slot);
+    }
+
+    fn load_slot(&mut self, slot: Slot) {
+        match slot {
+            Slot::Stack(pos) => self.dup_pos(pos),
+            Slot::Memory(offset) => {
+                self.frame_address(offset);
+                self.op(Opcode::MLoad);
+            }
+        }
+    }
+
+    fn store_slot(&mut self, slot: Slot) {
+        match slot {
+            Slot::Stack(pos) => {
+                let depth = self.height - 1 - pos;
+                self.swap(depth);
+                self.op(Opcode::Pop);
+            }
+            Slot::Memory(offset) => {
+                self.frame_address(offset);
+                self.op(Opcode::MStore);
+            }
+        }
+    }
+
+    /// Free the memory frame of the function, if nothing was allocated after it. Memory which
+    /// was allocated later may still be referenced by the caller, so it is left alone.
+    fn release_frame(&mut self) {
+        let Some(frame_pointer) = self.frame.frame_pointer else {
+            return;
+        };
+
+        let in_use = self.asm.new_label();
+
+        self.push_u64(FREE_POINTER);
+        self.op(Opcode::MLoad);
+        self.frame_address(self.frame.memory_size);
+        self.op(Opcode::Eq);
+        self.op(Opcode::IsZero);
+        self.jump_if(in_use);
+
+        // reading past the end of the call data gives zeros
+        self.push_u64(self.frame.memory_size);
+        self.op(Opcode::CallDataSize);
+        self.dup_pos(frame_pointer);
+        self.op(Opcode::CallDataCopy);
+        self.dup_pos(frame_pointer);
+        self.push_u64(FREE_POINTER);
+        self.op(Opcode::MStore);
+
+        self.label(in_use);
+    }
+
+    fn frame_address(&mut self, offset: u64) {
+        self.dup_pos(self.frame.frame_pointer.expect("frame pointer"));
+
+        if offset != 0 {
+            self.push_u64(offset);
+            self.op(Opcode::Add);
+        }
+    }
+
+    fn emit(&mut self, item: Item) {
+        let (consumed, produced) = item.stack_effect();
+
+        self.height = (self.height + produced)
+            .checked_sub(consumed)
+            .expect("stack underflow");
+
+        self.asm.items.push(item);
+    }
+
+    fn op(&mut self, op: Opcode) {
+        self.emit(Item::Op(op));
+    }
+
+    fn push(&mut self, value: &BigInt) {
+        self.emit(Item::Push(assembler::push_bytes(value)));
+    }
+
+    fn push_u64(&mut self, value: u64) {
+        self.push(&BigInt::from(value));
+    }
+
+    fn push_label(&mut self, label: Label) {
+        self.emit(Item::PushLabel(label));
+    }
+
+    fn label(&mut self, label: Label) {
+        self.emit(Item::Label(label));
+    }
+
+    fn jump(&mut self, label: Label) {
+        self.push_label(label);
+        self.op(Opcode::Jump);
+    }
+
+    /// Jump if the top of the stack is non-zero
+    fn jump_if(&mut self, label: Label) {
+        self.push_label(label);
+        self.op(Opcode::JumpI);
+    }
+
+    fn dup(&mut self, n: usize) {
+        if n > 16 {
+            self.too_deep = true;
+        }
+
+        self.emit(Item::Dup(n.clamp(1, 16) as u8));
+    }
+
+    fn swap(&mut self, n: usize) {
+        if n > 16 {
+            self.too_deep = true;
+        }
+
+        self.emit(Item::Swap(n.clamp(1, 16) as u8));
+    }
+
+    /// Duplicate the item at the given stack position
+    fn dup_pos(&mut self, pos: usize) {
+        self.dup(self.height - pos);
+    }
+
+    fn pop(&mut self, n: usize) {
+        for _ in 0..n {
+            self.op(Opcode::Pop);
+        }
+    }
+
+    /// Call a subroutine; the arguments are on the stack
+    fn call_subroutine(&mut self, subroutine: Subroutine) {
+        let (args, returns) = subroutine.signature();
+        let label = self.subroutine_label(subroutine);
+        let ret = self.asm.new_label();
+        let height = self.height;
+
+        self.push_label(ret);
+        self.jump(label);
+        self.label(ret);
+
+        self.height = height - args + returns;
+    }
+
+    fn subroutine_label(&mut self, subroutine: Subroutine) -> Label {
+        if let Some(label) = self.subroutines.get(&subroutine) {
+            return *label;
+        }
+
+        let label = self.asm.new_label();
+        self.subroutines.insert(subroutine, label);
+        self.subroutine_queue.push(subroutine);
+        label
+    }
+
+    /// Revert with a `Panic(uint256)` error if the top of the stack is non-zero
+    fn panic_if(&mut self, code: u8) {
+        let label = self.subroutine_label(Subroutine::Panic(code));
+        self.jump_if(label);
+    }
+
+    /// Allocate a fixed number of bytes and push the pointer
+    fn alloc(&mut self, size: u64) {
+        self.push_u64(FREE_POINTER);
+        self.op(Opcode::MLoad);
+        self.dup(1);
+        self.push_u64(size.next_multiple_of(32));
+        self.op(Opcode::Add);
+        self.push_u64(FREE_POINTER);
+        self.op(Opcode::MStore);
+    }
+
+    /// Allocate the number of bytes on top of the stack, and replace it with the pointer
+    fn alloc_dynamic(&mut self) {
+        self.push_u64(31);
+        self.op(Opcode::Add);
+        self.push(&!BigInt::from(31));
+        self.op(Opcode::And);
+        self.push_u64(FREE_POINTER);
+        self.op(Opcode::MLoad);
+        self.swap(1);
+        self.dup(2);
+        self.op(Opcode::Add);
+        self.push_u64(FREE_POINTER);
+        self.op(Opcode::MStore);
+    }
+
+    /// Allocate a vector with the number of elements on top of the stack, and replace it with
+    /// the pointer. The elements are zeroed.
+    fn alloc_vector(&mut self, elem_size: u64) {
+        self.dup(1);
+
+        if elem_size != 1 {
+            self.push_u64(elem_size);
+            self.op(Opcode::Mul);
+        }
+
+        self.push_u64(32);
+        self.op(Opcode::Add);
+        self.alloc_dynamic();
+        // store the length
+        self.swap(1);
+        self.dup(2);
+        self.op(Opcode::MStore);
+    }
+
+    /// Make the top of the stack a canonical value of the given type, after an operation
+    /// which may have set the higher bits
+    fn normalize(&mut self, ty: &Type) {
+        if let Some((bits, signed)) = int_type(ty, self.ns) {
+            if bits < 256 {
+                if signed {
+                    self.push_u64(bits as u64 / 8 - 1);
+                    self.op(Opcode::SignExtend);
+                } else {
+                    self.push(&((BigInt::from(1) << bits) - 1));
+                    self.op(Opcode::And);
+                }
+            }
+        }
+    }
+}
+
+/// Count how often each variable is read, to decide which variables should be on the stack
+fn count_variable_uses(expr: &Expression, uses: &mut HashMap<usize, usize>) -> bool {
+    if let Expression::Variable { var_no, .. } = expr {
+        *uses.entry(*var_no).or_default() += 1;
+    }
+
+    true
+}
+
+/// The width and signedness of types which are represented as integers
+fn int_type(ty: &Type, ns: &Namespace) -> Option<(u16, bool)> {
+    match ty {
+        Type::Int(bits) => Some((*bits, true)),
+        Type::Uint(bits) => Some((*bits, false)),
+        Type::Fixed(bits, _) => Some((*bits, true)),
+        Type::Ufixed(bits, _) => Some((*bits, false)),
+        Type::Bool
+        | Type::Bytes(_)
+        | Type::Address(_)
+        | Type::Contract(_)
+        | Type::Enum(_)
+        | Type::Value
+        | Type::FunctionSelector
+        | Type::StorageRef(..) => Some((ty.bits(ns), false)),
+        Type::UserType(no) => int_type(&ns.user_types[*no].ty, ns),
+        _ => None,
+    }
+}
+
+/// Memory vectors: a length word followed by the elements
+fn is_vector(ty: &Type, ns: &Namespace) -> bool {
+    match ty {
+        Type::String | Type::DynamicBytes => true,
+        Type::Array(_, dims) => dims.last() == Some(&ArrayLength::Dynamic),
+        Type::UserType(no) => is_vector(&ns.user_types[*no].ty, ns),
+        _ => false,
+    }
+}
+
+/// Slices: a view of elements in memory or calldata
+fn is_slice(ty: &Type) -> bool {
+    matches!(ty, Type::Slice(_))
+}
+
+/// Structs and fixed length arrays are stored inline in memory
+fn is_inline(ty: &Type, ns: &Namespace) -> bool {
+    match ty {
+        Type::Struct(_) => true,
+        Type::Array(_, dims) => dims.last() != Some(&ArrayLength::Dynamic),
+        Type::UserType(no) => is_inline(&ns.user_types[*no].ty, ns),
+        _ => false,
+    }
+}
+
+/// Types which occupy a single byte in memory, so that byte arrays are packed
+fn is_byte(ty: &Type) -> bool {
+    matches!(ty, Type::Uint(8) | Type::Int(8) | Type::Bytes(1))
+}
+
+/// The number of bytes a value of the type occupies in memory
+fn mem_size(ty: &Type, ns: &Namespace) -> u64 {
+    match ty {
+        _ if is_byte(ty) => 1,
+        Type::Struct(str_ty) => str_ty
+            .definition(ns)
+            .fields
+            .iter()
+            .map(|field| mem_size(&field.ty, ns))
+            .sum(),
+        Type::Array(_, dims) if dims.last() != Some(&ArrayLength::Dynamic) => {
+            let len = ty.array_length().and_then(num_traits::ToPrimitive::to_u64);
+
+            len.expect("fixed length array") * mem_size(&ty.array_elem(), ns)
+        }
+        Type::UserType(no) => mem_size(&ns.user_types[*no].ty, ns),
+        _ => 32,
+    }
+}
+
+/// The offset of a field in a struct in memory
+fn field_offset(ty: &Type, member: usize, ns: &Namespace) -> u64 {
+    match ty {
+        Type::Struct(str_ty) => str_ty.definition(ns).fields[..member]
+            .iter()
+            .map(|field| mem_size(&field.ty, ns))
+            .sum(),
+        // selector and address
+        Type::ExternalFunction { .. } => 32 * member as u64,
+        Type::UserType(no) => field_offset(&ns.user_types[*no].ty, member, ns),
+        _ => unreachable!("not a struct: {ty:?}"),
+    }
+}
@@ -0,0 +1,395 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::assembler::Opcode;
+use super::subroutines::Subroutine;
+use super::{field_offset, is_vector, mem_size, Unit, ZERO_SLOT};
+use crate::sema::ast::{ArrayLength, Namespace, Type};
+use num_traits::ToPrimitive;
+
+/// How a type is laid out in storage. Value types take a slot each, strings and bytes keep
+/// their length in the slot and the data at the keccak hash of the slot, and dynamic arrays
+/// keep their length in the slot and the elements at the keccak hash of the slot.
+enum Layout {
+    Value,
+    Bytes,
+    ExternalFunction,
+    Struct(Vec<(Type, u64)>),
+    FixedArray { elem: Type, len: u64 },
+    DynamicArray { elem: Type },
+    Mapping,
+}
+
+fn layout(ty: &Type, ns: &Namespace) -> Layout {
+    match ty {
+        Type::String | Type::DynamicBytes => Layout::Bytes,
+        Type::ExternalFunction { .. } => Layout::ExternalFunction,
+        Type::Struct(str_ty) => {
+            let mut offset = 0;
+            let mut fields = Vec::new();
+
+            for field in &str_ty.definition(ns).fields {
+                if !field.infinite_size {
+                    fields.push((field.ty.clone(), offset));
+                    offset += storage_slots(&field.ty, ns);
+                }
+            }
+
+            Layout::Struct(fields)
+        }
+        Type::Array(_, dims) if dims.last() == Some(&ArrayLength::Dynamic) => {
+            Layout::DynamicArray {
+                elem: ty.array_elem(),
+            }
+        }
+        Type::Array(..) => Layout::FixedArray {
+            elem: ty.array_elem(),
+            len: ty.array_length().and_then(ToPrimitive::to_u64).unwrap(),
+        },
+        Type::Mapping(..) => Layout::Mapping,
+        Type::UserType(no) => layout(&ns.user_types[*no].ty, ns),
+        _ => Layout::Value,
+    }
+}
+
+fn storage_slots(ty: &Type, ns: &Namespace) -> u64 {
+    ty.storage_slots(ns).to_u64().expect("storage too large")
+}
+
+impl Unit<'_> {
+    /// Replace the slot on top of the stack with the value stored there
+    pub(super) fn load_storage(&mut self, ty: &Type) {
+        let ty = ty.deref_any();
+
+        match layout(ty, self.ns) {
+            Layout::Value => self.op(Opcode::SLoad),
+            Layout::Bytes => self.call_subroutine(Subroutine::StorageBytesLoad),
+            Layout::ExternalFunction => {
+                // the selector is stored above the address
+                self.op(Opcode::SLoad);
+                self.alloc(64);
+                self.dup(2);
+                self.push_u64(160);
+                self.op(Opcode::Shr);
+                self.dup(2);
+                self.op(Opcode::MStore);
+                self.swap(1);
+                self.push(&((num_bigint::BigInt::from(1) << 160) - 1));
+                self.op(Opcode::And);
+                self.dup(2);
+                self.push_u64(32);
+                self.op(Opcode::Add);
+                self.op(Opcode::MStore);
+            }
+            Layout::Struct(fields) => {
+                self.alloc(mem_size(ty, self.ns));
+
+                for (member, (field_ty, slot_offset)) in fields.iter().enumerate() {
+                    self.dup(2);
+                    self.add_offset(*slot_offset);
+                    self.load_storage(field_ty);
+                    self.dup(2);
+                    self.add_offset(field_offset(ty, member, self.ns));
+                    self.store_memory(field_ty);
+                }
+
+                self.swap(1);
+                self.op(Opcode::Pop);
+            }
+            Layout::FixedArray { elem, len } => {
+                let elem_size = mem_size(&elem, self.ns);
+                let elem_slots = storage_slots(&elem, self.ns);
+                let slot = self.height - 1;
+
+                // [slot] => [slot, array, len]
+                self.alloc(mem_size(ty, self.ns));
+                self.push_u64(len);
+
+                self.for_each(slot + 2, 0, |unit, index| {
+                    unit.element_slot(slot, index, elem_slots);
+                    unit.load_storage(&elem);
+                    unit.element_address(slot + 1, 0, index, elem_size);
+                    unit.store_memory(&elem);
+                });
+
+                self.op(Opcode::Pop);
+                self.swap(1);
+                self.op(Opcode::Pop);
+            }
+            Layout::DynamicArray { elem } => {
+                let elem_size = mem_size(&elem, self.ns);
+                let elem_slots = storage_slots(&elem, self.ns);
+                let vector = self.height - 1;
+
+                // [slot] => [vector, len, data_slot]
+                self.dup(1);
+                self.op(Opcode::SLoad);
+                self.dup(1);
+                self.alloc_vector(elem_size);
+                self.swap(2);
+                self.storage_data_slot();
+
+                self.for_each(vector + 1, 0, |unit, index| {
+                    unit.element_slot(vector + 2, index, elem_slots);
+                    unit.load_storage(&elem);
+                    unit.element_address(vector, 32, index, elem_size);
+                    unit.store_memory(&elem);
+                });
+
+                self.pop(2);
+            }
+            Layout::Mapping => unreachable!("mappings cannot be loaded"),
+        }
+    }
+
+    /// Store a value in storage. The stack has the value, with the slot on top.
+    pub(super) fn store_storage(&mut self, ty: &Type) {
+        let ty = ty.deref_any();
+
+        match layout(ty, self.ns) {
+            Layout::Value => self.op(Opcode::SStore),
+            Layout::Bytes => self.call_subroutine(Subroutine::StorageBytesStore),
+            Layout::ExternalFunction => {
+                self.dup(2);
+                self.op(Opcode::MLoad);
+                self.push_u64(160);
+                self.op(Opcode::Shl);
+                self.dup(3);
+                self.push_u64(32);
+                self.op(Opcode::Add);
+                self.op(Opcode::MLoad);
+                self.op(Opcode::Or);
+                self.swap(1);
+                self.op(Opcode::SStore);
+                self.op(Opcode::Pop);
+            }
+            Layout::Struct(fields) => {
+                for (member, (field_ty, slot_offset)) in fields.iter().enumerate() {
+                    self.dup(2);
+                    self.add_offset(field_offset(ty, member, self.ns));
+                    self.load_memory(field_ty);
+                    self.dup(2);
+                    self.add_offset(*slot_offset);
+                    self.store_storage(field_ty);
+                }
+
+                self.pop(2);
+            }
+            Layout::FixedArray { elem, len } => {
+                let elem_size = mem_size(&elem, self.ns);
+                let elem_slots = storage_slots(&elem, self.ns);
+                let array = self.height - 2;
+
+                self.push_u64(len);
+
+                self.for_each(array + 2, 0, |unit, index| {
+                    unit.element_address(array, 0, index, elem_size);
+                    unit.load_memory(&elem);
+                    unit.element_slot(array + 1, index, elem_slots);
+                    unit.store_storage(&elem);
+                });
+
+                self.pop(3);
+            }
+            Layout::DynamicArray { elem } => {
+                let elem_size = mem_size(&elem, self.ns);
+                let elem_slots = storage_slots(&elem, self.ns);
+                let vector = self.height - 2;
+
+                // [vector, slot] => [vector, len, old_len, data_slot]
+                self.dup(2);
+                self.op(Opcode::MLoad);
+                self.dup(2);
+                self.op(Opcode::SLoad);
+                self.dup(2);
+                self.dup(4);
+                self.op(Opcode::SStore);
+                self.swap(1);
+                self.swap(2);
+                self.storage_data_slot();
+
+                self.for_each(vector + 1, 0, |unit, index| {
+                    unit.element_address(vector, 32, index, elem_size);
+                    unit.load_memory(&elem);
+                    unit.element_slot(vector + 3, index, elem_slots);
+                    unit.store_storage(&elem);
+                });
+
+                // clear the elements beyond the new length
+                self.for_each(vector + 2, vector + 1, |unit, index| {
+                    unit.element_slot(vector + 3, index, elem_slots);
+                    unit.clear_storage(&elem);
+                });
+
+                self.pop(4);
+            }
+            Layout::Mapping => unreachable!("mappings cannot be stored"),
+        }
+    }
+
+    /// Clear the storage at the slot on top of the stack
+    pub(super) fn clear_storage(&mut self, ty: &Type) {
+        let ty = ty.deref_any();
+
+        match layout(ty, self.ns) {
+            Layout::Value | Layout::ExternalFunction => {
+                self.push_u64(0);
+                self.swap(1);
+                self.op(Opcode::SStore);
+            }
+            Layout::Bytes => {
+                self.push_u64(ZERO_SLOT);
+                self.swap(1);
+                self.call_subroutine(Subroutine::StorageBytesStore);
+            }
+            Layout::Struct(fields) => {
+                for (field_ty, slot_offset) in &fields {
+                    self.dup(1);
+                    self.add_offset(*slot_offset);
+                    self.clear_storage(field_ty);
+                }
+
+                self.op(Opcode::Pop);
+            }
+            Layout::FixedArray { elem, len } => {
+                let elem_slots = storage_slots(&elem, self.ns);
+                let slot = self.height - 1;
+
+                self.push_u64(len);
+
+                self.for_each(slot + 1, 0, |unit, index| {
+                    unit.element_slot(slot, index, elem_slots);
+                    unit.clear_storage(&elem);
+                });
+
+                self.pop(2);
+            }
+            Layout::DynamicArray { elem } => {
+                let elem_slots = storage_slots(&elem, self.ns);
+                let len = self.height - 1;
+
+                // [slot] => [len, data_slot]
+                self.dup(1);
+                self.op(Opcode::SLoad);
+                self.push_u64(0);
+                self.dup(3);
+                self.op(Opcode::SStore);
+                self.swap(1);
+                self.storage_data_slot();
+
+                self.for_each(len, 0, |unit, index| {
+                    unit.element_slot(len + 1, index, elem_slots);
+                    unit.clear_storage(&elem);
+                });
+
+                self.pop(2);
+            }
+            // mappings cannot be cleared, since their keys are unknown
+            Layout::Mapping => self.op(Opcode::Pop),
+        }
+    }
+
+    /// Replace the slot and mapping key on top of the stack with the slot of the mapping entry.
+    /// Value type keys are hashed as a word, and strings and bytes as their contents.
+    pub(super) fn mapping_slot(&mut self, key_ty: &Type) {
+        if !is_vector(key_ty, self.ns) {
+            self.push_u64(0);
+            self.op(Opcode::MStore);
+            self.push_u64(32);
+            self.op(Opcode::MStore);
+            self.push_u64(64);
+            self.push_u64(0);
+            self.op(Opcode::Keccak256);
+            return;
+        }
+
+        // [slot, key] => [slot, key, len, buffer]
+        self.dup(1);
+        self.op(Opcode::MLoad);
+        self.dup(1);
+        self.push_u64(32);
+        self.op(Opcode::Add);
+        self.alloc_dynamic();
+        self.dup(2);
+        self.dup(4);
+        self.push_u64(32);
+        self.op(Opcode::Add);
+        self.dup(3);
+        self.op(Opcode::MCopy);
+        self.dup(4);
+        self.dup(3);
+        self.dup(3);
+        self.op(Opcode::Add);
+        self.op(Opcode::MStore);
+        self.dup(2);
+        self.push_u64(32);
+        self.op(Opcode::Add);
+        self.swap(1);
+        self.op(Opcode::Keccak256);
+        self.swap(3);
+        self.pop(3);
+    }
+
+    /// Loop with an index from the value at the start position (or zero) up to the value at the
+    /// end position. The body gets the stack position of the index, and must leave the stack as
+    /// it found it.
+    fn for_each(&mut self, end: usize, start: usize, mut body: impl FnMut(&mut Self, usize)) {
+        let head = self.asm.new_label();
+        let done = self.asm.new_label();
+
+        if start == 0 {
+            self.push_u64(0);
+        } else {
+            self.dup_pos(start);
+        }
+
+        let index = self.height - 1;
+
+        self.label(head);
+        self.dup_pos(end);
+        self.dup_pos(index);
+        self.op(Opcode::Lt);
+        self.op(Opcode::IsZero);
+        self.jump_if(done);
+
+        body(self, index);
+
+        self.push_u64(1);
+        self.op(Opcode::Add);
+        self.jump(head);
+
+        self.height = index + 1;
+        self.label(done);
+        self.op(Opcode::Pop);
+    }
+
+    /// Push the slot of an element: the slot at the given position, plus index times the
+    /// number of slots of an element
+    fn element_slot(&mut self, slot: usize, index: usize, elem_slots: u64) {
+        self.dup_pos(index);
+        if elem_slots != 1 {
+            self.push_u64(elem_slots);
+            self.op(Opcode::Mul);
+        }
+        self.dup_pos(slot);
+        self.op(Opcode::Add);
+    }
+
+    /// Push the memory address of an element of the array at the given position
+    fn element_address(&mut self, array: usize, data: u64, index: usize, elem_size: u64) {
+        self.dup_pos(index);
+        if elem_size != 1 {
+            self.push_u64(elem_size);
+            self.op(Opcode::Mul);
+        }
+        self.dup_pos(array);
+        self.op(Opcode::Add);
+        self.add_offset(data);
+    }
+
+    fn add_offset(&mut self, offset: u64) {
+        if offset != 0 {
+            self.push_u64(offset);
+            self.op(Opcode::Add);
+        }
+    }
+}
@@ -0,0 +1,502 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::assembler::Opcode;
+use super::Unit;
+use crate::codegen::revert::{PanicCode, PANIC_SELECTOR};
+use num_bigint::{BigInt, Sign};
+
+/// Shared pieces of code which are too large to emit inline. A subroutine is called with its
+/// arguments on the stack, followed by the return address. It returns by jumping to the
+/// return address, with the results on the stack.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(super) enum Subroutine {
+    /// Revert with `Panic(uint256)`; this is a jump target which never returns
+    Panic(u8),
+    /// Revert with the return data of the last call; this is a jump target as well
+    Bubble,
+    /// Reverse the byte order of a 256 bit word
+    ByteSwap,
+    /// `base ** exp`, reverting if the result is larger than `limit`
+    Power,
+    /// Load a string or bytes from storage into a new memory vector
+    StorageBytesLoad,
+    /// Store a memory vector in storage as string or bytes
+    StorageBytesStore,
+    /// Read a byte from a storage bytes
+    StorageByteLoad,
+    /// Overwrite a byte of a storage bytes
+    StorageByteStore,
+    /// Format an unsigned integer with the given radix, with at least the given number of
+    /// digits
+    FormatInt(u8),
+}
+
+impl Subroutine {
+    /// The number of arguments and return values
+    pub(super) fn signature(self) -> (usize, usize) {
+        match self {
+            Subroutine::Panic(_) | Subroutine::Bubble => (0, 0),
+            Subroutine::ByteSwap => (1, 1),
+            Subroutine::Power => (3, 1),
+            Subroutine::StorageBytesLoad => (1, 1),
+            Subroutine::StorageBytesStore => (2, 0),
+            Subroutine::StorageByteLoad => (2, 1),
+            Subroutine::StorageByteStore => (3, 0),
+            Subroutine::FormatInt(_) => (2, 1),
+        }
+    }
+}
+
+impl Unit<'_> {
+    pub(super) fn subroutine(&mut self, subroutine: Subroutine) {
+        let (args, _) = subroutine.signature();
+
+        let label = self.subroutines[&subroutine];
+
+        self.label(label);
+        self.height = args + 1;
+
+        match subroutine {
+            Subroutine::Panic(code) => {
+                self.height = 0;
+                self.push(&(BigInt::from_bytes_be(Sign::Plus, &PANIC_SELECTOR) << 224));
+                self.push_u64(0);
+                self.op(Opcode::MStore);
+                self.push_u64(code.into());
+                self.push_u64(4);
+                self.op(Opcode::MStore);
+                self.push_u64(0x24);
+                self.push_u64(0);
+                self.op(Opcode::Revert);
+            }
+            Subroutine::Bubble => {
+                self.height = 0;
+                self.op(Opcode::ReturnDataSize);
+                self.push_u64(0);
+                self.push_u64(0);
+                self.op(Opcode::ReturnDataCopy);
+                self.op(Opcode::ReturnDataSize);
+                self.push_u64(0);
+                self.op(Opcode::Revert);
+            }
+            Subroutine::ByteSwap => {
+                self.swap(1);
+
+                for shift in [8u16, 16, 32, 64, 128] {
+                    // every other group of `shift` bits
+                    let mut mask = BigInt::from(0);
+                    for group in 0..(128 / shift) {
+                        mask |= ((BigInt::from(1) << shift) - 1) << (2 * shift * group);
+                    }
+
+                    self.dup(1);
+                    self.push_u64(shift.into());
+                    self.op(Opcode::Shr);
+                    self.push(&mask);
+                    self.op(Opcode::And);
+                    self.swap(1);
+                    self.push(&mask);
+                    self.op(Opcode::And);
+                    self.push_u64(shift.into());
+                    self.op(Opcode::Shl);
+                    self.op(Opcode::Or);
+                }
+
+                self.swap(1);
+                self.op(Opcode::Jump);
+            }
+            Subroutine::Power => self.power(),
+            Subroutine::FormatInt(radix) => self.format_int(radix),
+            Subroutine::StorageBytesLoad => self.storage_bytes_load(),
+            Subroutine::StorageBytesStore => self.storage_bytes_store(),
+            Subroutine::StorageByteLoad => {
+                // [slot, index, ret]
+                self.swap(2);
+                self.swap(1);
+                // [ret, slot, index]
+                self.storage_byte_address(1, 2);
+                // [ret, slot, index, word_slot, shift]
+                self.dup(2);
+                self.op(Opcode::SLoad);
+                self.swap(1);
+                self.op(Opcode::Shr);
+                self.push_u64(0xff);
+                self.op(Opcode::And);
+                // [ret, slot, index, word_slot, byte]
+                self.swap(3);
+                self.pop(3);
+                self.swap(1);
+                self.op(Opcode::Jump);
+            }
+            Subroutine::StorageByteStore => {
+                // [slot, index, value, ret]
+                self.swap(3);
+                self.swap(2);
+                self.swap(1);
+                // [ret, slot, index, value]
+                self.storage_byte_address(1, 2);
+                // [ret, slot, index, value, word_slot, shift]
+                self.dup(2);
+                self.op(Opcode::SLoad);
+                // clear the byte
+                self.push_u64(0xff);
+                self.dup(3);
+                self.op(Opcode::Shl);
+                self.op(Opcode::Not);
+                self.op(Opcode::And);
+                // and set it
+                self.dup(4);
+                self.dup(3);
+                self.op(Opcode::Shl);
+                self.op(Opcode::Or);
+                self.dup(3);
+                self.op(Opcode::SStore);
+                self.pop(5);
+                self.op(Opcode::Jump);
+            }
+        }
+    }
+
+    /// Check that the index of a storage bytes is in bounds, and push the slot of the word
+    /// containing the byte and the position of the byte in the word
+    fn storage_byte_address(&mut self, slot: usize, index: usize) {
+        self.dup_pos(slot);
+        self.op(Opcode::SLoad);
+        self.dup_pos(index);
+        self.op(Opcode::Lt);
+        self.op(Opcode::IsZero);
+        self.panic_if(PanicCode::ArrayIndexOob as u8);
+
+        self.dup_pos(slot);
+        self.storage_data_slot();
+        self.dup_pos(index);
+        self.push_u64(5);
+        self.op(Opcode::Shr);
+        self.op(Opcode::Add);
+
+        // bytes are stored big endian, so the first byte is in the highest bits
+        self.dup_pos(index);
+        self.push_u64(31);
+        self.op(Opcode::And);
+        self.push_u64(31);
+        self.op(Opcode::Sub);
+        self.push_u64(3);
+        self.op(Opcode::Shl);
+    }
+
+    /// Unsigned exponentiation by squaring, with overflow checks
+    fn power(&mut self) {
+        const LIMIT: usize = 1;
+        const BASE: usize = 2;
+        const EXP: usize = 3;
+
+        // [base, exp, limit, ret] => [ret, limit, base, exp, result]
+        self.swap(3);
+        self.swap(1);
+        self.swap(2);
+        self.push_u64(1);
+
+        let head = self.asm.new_label();
+        let skip = self.asm.new_label();
+        let done = self.asm.new_label();
+
+        self.label(head);
+        self.dup_pos(EXP);
+        self.op(Opcode::IsZero);
+        self.jump_if(done);
+
+        self.push_u64(1);
+        self.dup_pos(EXP);
+        self.op(Opcode::And);
+        self.op(Opcode::IsZero);
+        self.jump_if(skip);
+
+        // result * base > limit
+        self.dup_pos(BASE);
+        self.dup_pos(LIMIT);
+        self.op(Opcode::Div);
+        self.dup(2);
+        self.op(Opcode::Gt);
+        self.dup_pos(BASE);
+        self.op(Opcode::IsZero);
+        self.op(Opcode::IsZero);
+        self.op(Opcode::And);
+        self.panic_if(PanicCode::MathOverflow as u8);
+        self.dup_pos(BASE);
+        self.op(Opcode::Mul);
+
+        self.label(skip);
+        self.swap(1);
+        self.push_u64(1);
+        self.op(Opcode::Shr);
+        self.swap(1);
+
+        self.dup_pos(EXP);
+        self.op(Opcode::IsZero);
+        self.jump_if(done);
+
+        // base * base > limit
+        self.dup_pos(BASE);
+        self.dup_pos(LIMIT);
+        self.op(Opcode::Div);
+        self.dup_pos(BASE);
+        self.op(Opcode::Gt);
+        self.dup_pos(BASE);
+        self.op(Opcode::IsZero);
+        self.op(Opcode::IsZero);
+        self.op(Opcode::And);
+        self.panic_if(PanicCode::MathOverflow as u8);
+        self.swap(2);
+        self.dup(1);
+        self.op(Opcode::Mul);
+        self.swap(2);
+        self.jump(head);
+
+        self.height = 5;
+        self.label(done);
+        self.swap(3);
+        self.pop(3);
+        self.swap(1);
+        self.op(Opcode::Jump);
+    }
+
+    /// Convert an integer to a string of digits. The number of digits is counted first, and
+    /// then the digits are written from the end.
+    fn format_int(&mut self, radix: u8) {
+        const VALUE: usize = 1;
+        const MIN_DIGITS: usize = 2;
+        const DIGITS: usize = 3;
+        const VECTOR: usize = 4;
+
+        // [value, min_digits, ret] => [ret, value, min_digits, digits, value]
+        self.swap(2);
+        self.swap(1);
+        self.push_u64(0);
+        self.dup_pos(VALUE);
+
+        let count = self.asm.new_label();
+        let write = self.asm.new_label();
+
+        self.label(count);
+        self.swap(1);
+        self.push_u64(1);
+        self.op(Opcode::Add);
+        self.swap(1);
+        self.push_u64(radix.into());
+        self.swap(1);
+        self.op(Opcode::Div);
+        self.dup(1);
+        self.op(Opcode::IsZero);
+        self.op(Opcode::IsZero);
+        self.dup_pos(MIN_DIGITS);
+        self.dup_pos(DIGITS);
+        self.op(Opcode::Lt);
+        self.op(Opcode::Or);
+        self.jump_if(count);
+
+        // [ret, value, min_digits, digits, vector, end, value]
+        self.op(Opcode::Pop);
+        self.dup(1);
+        self.alloc_vector(1);
+        self.dup(1);
+        self.push_u64(32);
+        self.op(Opcode::Add);
+        self.dup_pos(DIGITS);
+        self.op(Opcode::Add);
+        self.dup_pos(VALUE);
+
+        self.label(write);
+        self.swap(1);
+        self.push_u64(1);
+        self.swap(1);
+        self.op(Opcode::Sub);
+        self.swap(1);
+        self.push_u64(radix.into());
+        self.dup(2);
+        self.op(Opcode::Mod);
+        // digits above 9 are lower case letters
+        self.dup(1);
+        self.push_u64(9);
+        self.op(Opcode::Lt);
+        self.push_u64(39);
+        self.op(Opcode::Mul);
+        self.op(Opcode::Add);
+        self.push_u64(48);
+        self.op(Opcode::Add);
+        self.dup(3);
+        self.op(Opcode::MStore8);
+        self.push_u64(radix.into());
+        self.swap(1);
+        self.op(Opcode::Div);
+        self.dup_pos(VECTOR);
+        self.push_u64(32);
+        self.op(Opcode::Add);
+        self.dup(3);
+        self.op(Opcode::Gt);
+        self.jump_if(write);
+
+        self.pop(2);
+        self.swap(3);
+        self.pop(3);
+        self.swap(1);
+        self.op(Opcode::Jump);
+    }
+
+    /// Load a string or bytes from storage. The length is stored in the slot, and the data in
+    /// 32 byte chunks starting at the keccak hash of the slot.
+    fn storage_bytes_load(&mut self) {
+        const SLOT: usize = 1;
+        const LEN: usize = 2;
+        const VECTOR: usize = 3;
+        const DATA_SLOT: usize = 4;
+        const OFFSET: usize = 5;
+
+        // [slot, ret] => [ret, slot]
+        self.swap(1);
+        self.dup_pos(SLOT);
+        self.op(Opcode::SLoad);
+        self.dup(1);
+        self.alloc_vector(1);
+        self.dup_pos(SLOT);
+        self.storage_data_slot();
+        self.push_u64(0);
+
+        let head = self.asm.new_label();
+        let done = self.asm.new_label();
+
+        self.label(head);
+        self.dup_pos(LEN);
+        self.dup_pos(OFFSET);
+        self.op(Opcode::Lt);
+        self.op(Opcode::IsZero);
+        self.jump_if(done);
+
+        self.dup_pos(DATA_SLOT);
+        self.op(Opcode::SLoad);
+        self.dup_pos(VECTOR);
+        self.push_u64(32);
+        self.op(Opcode::Add);
+        self.dup_pos(OFFSET);
+        self.op(Opcode::Add);
+        self.op(Opcode::MStore);
+        self.next_chunk();
+        self.jump(head);
+
+        self.height = 6;
+        self.label(done);
+        self.pop(2);
+        self.swap(2);
+        self.pop(2);
+        self.swap(1);
+        self.op(Opcode::Jump);
+    }
+
+    /// Store a memory vector as string or bytes. Chunks beyond the new length are cleared.
+    fn storage_bytes_store(&mut self) {
+        const VECTOR: usize = 1;
+        const LEN: usize = 3;
+        const OLD_LEN: usize = 4;
+        const DATA_SLOT: usize = 5;
+        const OFFSET: usize = 6;
+
+        // [vector, slot, ret] => [ret, vector, slot]
+        self.swap(2);
+        self.swap(1);
+        self.dup_pos(VECTOR);
+        self.op(Opcode::MLoad);
+        self.dup(2);
+        self.op(Opcode::SLoad);
+        self.dup(2);
+        self.dup(4);
+        self.op(Opcode::SStore);
+        self.dup(3);
+        self.storage_data_slot();
+        self.push_u64(0);
+
+        let head = self.asm.new_label();
+        let full = self.asm.new_label();
+        let clear = self.asm.new_label();
+        let done = self.asm.new_label();
+
+        self.label(head);
+        self.dup_pos(LEN);
+        self.dup_pos(OFFSET);
+        self.op(Opcode::Lt);
+        self.op(Opcode::IsZero);
+        self.jump_if(clear);
+
+        self.dup_pos(VECTOR);
+        self.push_u64(32);
+        self.op(Opcode::Add);
+        self.dup_pos(OFFSET);
+        self.op(Opcode::Add);
+        self.op(Opcode::MLoad);
+
+        // bytes beyond the length in the last chunk are cleared
+        self.push_u64(32);
+        self.dup_pos(OFFSET);
+        self.dup_pos(LEN);
+        self.op(Opcode::Sub);
+        self.op(Opcode::Lt);
+        self.op(Opcode::IsZero);
+        self.jump_if(full);
+
+        self.dup_pos(OFFSET);
+        self.dup_pos(LEN);
+        self.op(Opcode::Sub);
+        self.push_u64(32);
+        self.op(Opcode::Sub);
+        self.push_u64(3);
+        self.op(Opcode::Shl);
+        self.dup(1);
+        self.swap(2);
+        self.swap(1);
+        self.op(Opcode::Shr);
+        self.swap(1);
+        self.op(Opcode::Shl);
+
+        self.label(full);
+        self.dup_pos(DATA_SLOT);
+        self.op(Opcode::SStore);
+        self.next_chunk();
+        self.jump(head);
+
+        self.height = 7;
+        self.label(clear);
+        self.dup_pos(OLD_LEN);
+        self.dup_pos(OFFSET);
+        self.op(Opcode::Lt);
+        self.op(Opcode::IsZero);
+        self.jump_if(done);
+
+        self.push_u64(0);
+        self.dup_pos(DATA_SLOT);
+        self.op(Opcode::SStore);
+        self.next_chunk();
+        self.jump(clear);
+
+        self.height = 7;
+        self.label(done);
+        self.pop(6);
+        self.op(Opcode::Jump);
+    }
+
+    /// Advance the storage slot and memory offset on top of the stack to the next chunk
+    fn next_chunk(&mut self) {
+        self.swap(1);
+        self.push_u64(1);
+        self.op(Opcode::Add);
+        self.swap(1);
+        self.push_u64(32);
+        self.op(Opcode::Add);
+    }
+
+    /// Replace the slot on top of the stack with the slot where its data starts
+    pub(super) fn storage_data_slot(&mut self) {
+        self.push_u64(0);
+        self.op(Opcode::MStore);
+        self.push_u64(32);
+        self.push_u64(0);
+        self.op(Opcode::Keccak256);
+    }
+}
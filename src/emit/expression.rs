@@ -6,6 +6,7 @@ use crate::codegen::{Builtin, Expression};
 use crate::emit::binary::Binary;
 use crate::emit::math::{build_binary_op_with_overflow_check, multiply, power};
 use crate::emit::strings::{format_string, string_location};
+use crate::emit::stylus::StylusTarget;
 use crate::emit::{loop_builder::LoopBuilder, BinaryOp, TargetRuntime, Variable};
 use crate::emit_context;
 use crate::sema::ast::{ArrayLength, RetrieveType, StructType, Type};
@@ -1715,6 +1716,68 @@ pub(super) fn expression<'a, T: TargetRuntime<'a> + ?Sized>(
                 bin.vector_new(size, elem_size, initializer.as_ref(), ty)
             }
         }
+        Expression::Builtin {
+            tys,
+            kind: Builtin::ArraySlice,
+            args,
+            ..
+        } => {
+            let array = expression(target, bin, &args[0], vartab, function);
+            let start = expression(target, bin, &args[1], vartab, function).into_int_value();
+            let end = expression(target, bin, &args[2], vartab, function).into_int_value();
+
+            let Type::Slice(elem_ty) = &tys[0] else {
+                unreachable!()
+            };
+
+            // bounds checking already done; the slice points into the array
+            let offset = bin
+                .builder
+                .build_int_mul(
+                    start,
+                    bin.llvm_type(elem_ty)
+                        .size_of()
+                        .unwrap()
+                        .const_cast(bin.context.i32_type(), false),
+                    "offset",
+                )
+                .unwrap();
+
+            let data = unsafe {
+                bin.builder
+                    .build_gep(
+                        bin.context.i8_type(),
+                        bin.vector_bytes(array),
+                        &[offset],
+                        "slice_data",
+                    )
+                    .unwrap()
+            };
+
+            let len = bin
+                .builder
+                .build_int_z_extend(
+                    bin.builder.build_int_sub(end, start, "len").unwrap(),
+                    bin.context
+                        .custom_width_int_type(bin.ns.target.ptr_size().into()),
+                    "slice_len",
+                )
+                .unwrap();
+
+            let slice_ty = bin.llvm_type(&tys[0]).into_struct_type();
+
+            let slice = bin
+                .builder
+                .build_insert_value(slice_ty.get_undef(), data, 0, "slice")
+                .unwrap()
+                .into_struct_value();
+
+            bin.builder
+                .build_insert_value(slice, len, 1, "slice")
+                .unwrap()
+                .into_struct_value()
+                .into()
+        }
         Expression::Builtin {
             kind: Builtin::ArrayLength,
             args,
@@ -1770,6 +1833,15 @@ pub(super) fn expression<'a, T: TargetRuntime<'a> + ?Sized>(
                     .unwrap()
             }
         }
+        Expression::Keccak256 { exprs, .. } if bin.ns.target == Target::Stylus => {
+            let values: Vec<_> = exprs
+                .iter()
+                .map(|e| (expression(target, bin, e, vartab, function), e.ty()))
+                .collect();
+            let values: Vec<_> = values.iter().map(|(v, ty)| (*v, ty)).collect();
+
+            StylusTarget.storage_slot_hash(bin, &values).into()
+        }
         Expression::Keccak256 { exprs, .. } => {
             let mut length = bin.context.i32_type().const_zero();
             let mut values: Vec<(BasicValueEnum, IntValue, Type)> = Vec::new();
@@ -2172,6 +2244,11 @@ pub(super) fn expression<'a, T: TargetRuntime<'a> + ?Sized>(
                 .unwrap()
                 .into()
         }
+        // Soroban bytes are host objects, which are hashed by the host
+        Expression::Builtin {
+            kind: Builtin::Keccak256 | Builtin::Sha256,
+            ..
+        } if bin.ns.target == Target::Soroban => target.builtin(bin, e, vartab, function),
         Expression::Builtin {
             kind: hash @ Builtin::Ripemd160,
             args,
@@ -2420,6 +2497,8 @@ fn runtime_cast<'a>(
         (
             Type::Uint(_)
             | Type::Int(_)
+            | Type::Fixed(..)
+            | Type::Ufixed(..)
             | Type::Value
             | Type::Bytes(_)
             | Type::UserType(_)
@@ -2427,6 +2506,8 @@ fn runtime_cast<'a>(
             | Type::FunctionSelector,
             Type::Uint(_)
             | Type::Int(_)
+            | Type::Fixed(..)
+            | Type::Ufixed(..)
             | Type::Value
             | Type::Bytes(_)
             | Type::Enum(_)
@@ -2530,6 +2611,28 @@ fn runtime_cast<'a>(
             )
             .unwrap()
             .into(),
+        (Type::Slice(elem_ty), Type::DynamicBytes | Type::Array(..)) => {
+            let elem_size = bin
+                .llvm_type(elem_ty)
+                .size_of()
+                .unwrap()
+                .const_cast(bin.context.i32_type(), false);
+
+            bin.builder
+                .build_call(
+                    bin.module.get_function("vector_new").unwrap(),
+                    &[
+                        bin.vector_len(val).into(),
+                        elem_size.into(),
+                        bin.vector_bytes(val).into(),
+                    ],
+                    "",
+                )
+                .unwrap()
+                .try_as_basic_value()
+                .left()
+                .unwrap()
+        }
         (Type::DynamicBytes | Type::String, Type::Slice(_)) => {
             let slice_ty = bin.llvm_type(to);
             let slice = bin.build_alloca(function, slice_ty, "slice");
@@ -2904,7 +3007,7 @@ fn byte_swap_expression<'a, T: TargetRuntime<'a> + ?Sized>(
     function: FunctionValue<'a>,
 ) -> BasicValueEnum<'a> {
     match ty {
-        Type::Int(_) | Type::Uint(_) => {
+        Type::Int(_) | Type::Uint(_) | Type::Fixed(..) | Type::Ufixed(..) => {
             let value = expression(target, bin, expr, vartab, function).into_int_value();
 
             let llvm_ty = bin.llvm_type(ty);
@@ -465,15 +465,17 @@ pub(super) fn process_instruction<'a, T: TargetRuntime<'a> + ?Sized>(
             // Soroban doesn't write return values to imported memory
             if !res.is_empty() && bin.ns.target != Target::Soroban {
                 for v in f.returns.iter() {
-                    parms.push(if bin.ns.target == Target::Solana {
-                        bin.build_alloca(function, bin.llvm_var_ty(&v.ty), v.name_as_str())
-                            .into()
-                    } else {
-                        bin.builder
-                            .build_alloca(bin.llvm_var_ty(&v.ty), v.name_as_str())
-                            .unwrap()
-                            .into()
-                    });
+                    parms.push(
+                        if matches!(bin.ns.target, Target::Solana | Target::PolkaVM) {
+                            bin.build_alloca(function, bin.llvm_var_ty(&v.ty), v.name_as_str())
+                                .into()
+                        } else {
+                            bin.builder
+                                .build_alloca(bin.llvm_var_ty(&v.ty), v.name_as_str())
+                                .unwrap()
+                                .into()
+                        },
+                    );
                 }
             }
 
@@ -555,15 +557,17 @@ pub(super) fn process_instruction<'a, T: TargetRuntime<'a> + ?Sized>(
 
             if !res.is_empty() {
                 for v in callee.returns.iter() {
-                    parms.push(if bin.ns.target == Target::Solana {
-                        bin.build_alloca(function, bin.llvm_var_ty(&v.ty), v.name_as_str())
-                            .into()
-                    } else {
-                        bin.builder
-                            .build_alloca(bin.llvm_var_ty(&v.ty), v.name_as_str())
-                            .unwrap()
-                            .into()
-                    });
+                    parms.push(
+                        if matches!(bin.ns.target, Target::Solana | Target::PolkaVM) {
+                            bin.build_alloca(function, bin.llvm_var_ty(&v.ty), v.name_as_str())
+                                .into()
+                        } else {
+                            bin.builder
+                                .build_alloca(bin.llvm_var_ty(&v.ty), v.name_as_str())
+                                .unwrap()
+                                .into()
+                        },
+                    );
                 }
             }
 
@@ -770,9 +774,15 @@ pub(super) fn process_instruction<'a, T: TargetRuntime<'a> + ?Sized>(
             constructor_no: _,
         } => {
             let encoded_args = expression(target, bin, encoded_args, &w.vars, function);
-            let encoded_args_len = bin.vector_len(encoded_args).as_basic_value_enum();
+            // On Soroban, the arguments are a vector object rather than a buffer
+            let encoded_args_len = if bin.ns.target == Target::Soroban {
+                bin.context.i32_type().const_zero().as_basic_value_enum()
+            } else {
+                bin.vector_len(encoded_args).as_basic_value_enum()
+            };
 
-            let address_stack = bin.build_alloca(function, bin.address_type(), "address");
+            let address_ty = bin.llvm_type(&Type::Address(false));
+            let address_stack = bin.build_alloca(function, address_ty, "address");
 
             let gas = expression(target, bin, gas, &w.vars, function).into_int_value();
             let value = value
@@ -879,7 +889,7 @@ pub(super) fn process_instruction<'a, T: TargetRuntime<'a> + ?Sized>(
 
             w.vars.get_mut(res).unwrap().value = bin
                 .builder
-                .build_load(bin.address_type(), address_stack, "address")
+                .build_load(address_ty, address_stack, "address")
                 .unwrap();
         }
         Instr::ExternalCall {
@@ -1139,6 +1149,13 @@ pub(super) fn process_instruction<'a, T: TargetRuntime<'a> + ?Sized>(
             target.return_abi_data(bin, data, data_len);
         }
 
+        Instr::ReturnCode {
+            code: ReturnCode::CustomError(code),
+        } => {
+            let ty = bin.return_values[&ReturnCode::Success].get_type();
+            target.return_code(bin, ty.const_int(*code as u64, false));
+        }
+
         Instr::ReturnCode { code } => {
             target.return_code(bin, bin.return_values[code]);
         }
@@ -16,12 +16,14 @@ use solang_parser::pt::{Loc, StorageType};
 
 pub mod binary;
 mod cfg;
+pub mod evm;
 mod expression;
 mod functions;
 mod instructions;
 mod loop_builder;
 mod math;
 pub mod polkadot;
+pub mod polkavm;
 pub mod solana;
 pub mod stylus;
 
@@ -321,28 +323,35 @@ pub enum Generate {
 impl Target {
     /// LLVM Target name
     fn llvm_target_name(&self) -> &'static str {
-        if *self == Target::Solana {
-            "sbf"
-        } else {
-            "wasm32"
+        match self {
+            Target::Solana => "sbf",
+            Target::PolkaVM => "riscv64",
+            _ => "wasm32",
         }
     }
 
     /// LLVM Target triple
     fn llvm_target_triple(&self) -> TargetTriple {
-        TargetTriple::create(if *self == Target::Solana {
-            "sbf-unknown-unknown"
-        } else {
-            "wasm32-unknown-unknown-wasm"
+        TargetTriple::create(match self {
+            Target::Solana => "sbf-unknown-unknown",
+            Target::PolkaVM => "riscv64-unknown-none-elf",
+            _ => "wasm32-unknown-unknown-wasm",
         })
     }
 
     /// LLVM Target triple
     fn llvm_features(&self) -> &'static str {
-        if *self == Target::Solana {
-            "+solana"
-        } else {
-            ""
+        match self {
+            Target::Solana => "+solana",
+            // PolkaVM implements RV64E with these extensions, so the registers x16 to x31
+            // must not be used
+            Target::PolkaVM => {
+                "+m,+a,+c,+zbb,+reserve-x16,+reserve-x17,+reserve-x18,+reserve-x19,\
+                 +reserve-x20,+reserve-x21,+reserve-x22,+reserve-x23,+reserve-x24,\
+                 +reserve-x25,+reserve-x26,+reserve-x27,+reserve-x28,+reserve-x29,\
+                 +reserve-x30,+reserve-x31"
+            }
+            _ => "",
         }
     }
 }
@@ -362,17 +371,31 @@ impl ast::Contract {
 
     /// Generate the final program code for the contract
     pub fn emit(&self, ns: &ast::Namespace, opt: &Options, contract_no: usize) -> Vec<u8> {
-        if ns.target == Target::EVM {
-            return vec![];
-        }
+        // link errors are reported when the contract itself is compiled with try_emit
+        self.try_emit(ns, opt, contract_no).unwrap_or_default()
+    }
 
+    /// Generate the final program code for the contract, or the reason it could not be built
+    pub fn try_emit(
+        &self,
+        ns: &ast::Namespace,
+        opt: &Options,
+        contract_no: usize,
+    ) -> Result<Vec<u8>, String> {
         self.code
-            .get_or_init(move || {
+            .get_or_try_init(move || {
+                if ns.target == Target::EVM {
+                    // errors are reported when the contract is compiled with evm::build
+                    return Ok(evm::build(contract_no, ns, opt)
+                        .map(|code| code.creation)
+                        .unwrap_or_default());
+                }
+
                 let context = inkwell::context::Context::create();
                 let bin = self.binary(ns, &context, opt, contract_no);
-                bin.code(Generate::Linked).expect("llvm build")
+                bin.code(Generate::Linked)
             })
-            .to_vec()
+            .cloned()
     }
 }
 
@@ -93,6 +93,8 @@ impl PolkadotTarget {
             "set_storage",
             "get_storage",
             "clear_storage",
+            "set_transient_storage",
+            "get_transient_storage",
             "hash_keccak_256",
             "hash_sha2_256",
             "hash_blake2_128",
@@ -119,6 +121,16 @@ impl PolkadotTarget {
             "is_contract",
             "set_code_hash",
             "caller_is_root",
+            "ecdsa_recover",
+            "ecdsa_to_eth_address",
+            "sr25519_verify",
+            "own_code_hash",
+            "xcm_execute",
+            "xcm_send",
+            "lock_delegate_dependency",
+            "unlock_delegate_dependency",
+            "take_storage",
+            "contains_storage",
         ]);
 
         bin
@@ -215,6 +227,22 @@ impl PolkadotTarget {
         external!("debug_message", i32_type, u8_ptr, u32_val);
         external!("clear_storage", i32_type, u8_ptr, u32_val);
         external!("get_storage", i32_type, u8_ptr, u32_val, u8_ptr, u32_ptr);
+        external!(
+            "set_transient_storage",
+            i32_type,
+            u8_ptr,
+            u32_val,
+            u8_ptr,
+            u32_val
+        );
+        external!(
+            "get_transient_storage",
+            i32_type,
+            u8_ptr,
+            u32_val,
+            u8_ptr,
+            u32_ptr
+        );
         external!("seal_return", void_type, u32_val, u8_ptr, u32_val);
         external!(
             "instantiate",
@@ -271,6 +299,16 @@ impl PolkadotTarget {
         external!("is_contract", i32_type, u8_ptr);
         external!("set_code_hash", i32_type, u8_ptr);
         external!("caller_is_root", i32_type,);
+        external!("ecdsa_recover", i32_type, u8_ptr, u8_ptr, u8_ptr);
+        external!("ecdsa_to_eth_address", i32_type, u8_ptr, u8_ptr);
+        external!("sr25519_verify", i32_type, u8_ptr, u8_ptr, u32_val, u8_ptr);
+        external!("own_code_hash", void_type, u8_ptr, u32_ptr);
+        external!("xcm_execute", i32_type, u8_ptr, u32_val);
+        external!("xcm_send", i32_type, u8_ptr, u32_val, u8_ptr, u32_val, u8_ptr);
+        external!("lock_delegate_dependency", void_type, u8_ptr);
+        external!("unlock_delegate_dependency", void_type, u8_ptr);
+        external!("take_storage", i32_type, u8_ptr, u32_val, u8_ptr, u32_ptr);
+        external!("contains_storage", i32_type, u8_ptr, u32_val);
     }
 
     /// Emits the "deploy" function if `storage_initializer` is `Some`, otherwise emits the "call" function.
@@ -196,14 +196,7 @@ impl StorageSlot for PolkadotTarget {
                             "size_of",
                         )
                         .unwrap();
-                    let init = bin
-                        .builder
-                        .build_int_to_ptr(
-                            bin.context.i32_type().const_all_ones(),
-                            bin.context.ptr_type(AddressSpace::default()),
-                            "invalid",
-                        )
-                        .unwrap();
+                    let init = bin.vector_init_empty;
 
                     let dest = call!("vector_new", &[size.into(), elem_size.into(), init.into()])
                         .try_as_basic_value()
@@ -909,3 +902,94 @@ impl StorageSlot for PolkadotTarget {
         }
     }
 }
+
+impl PolkadotTarget {
+    /// Read a transient variable. Transient variables are value types, so they always fit in
+    /// the scratch buffer. A variable which was not set in this transaction reads as zero.
+    pub(super) fn get_transient_storage<'a>(
+        &self,
+        bin: &Binary<'a>,
+        ty: &Type,
+        slot_ptr: PointerValue<'a>,
+    ) -> BasicValueEnum<'a> {
+        emit_context!(bin);
+
+        let llvm_ty = bin.llvm_var_ty(ty.deref_any());
+        let (scratch_buf, scratch_len) = scratch_buf!();
+
+        bin.builder
+            .build_store(
+                scratch_len,
+                llvm_ty
+                    .size_of()
+                    .unwrap()
+                    .const_cast(bin.context.i32_type(), false),
+            )
+            .unwrap();
+
+        let ret = call!(
+            "get_transient_storage",
+            &[
+                slot_ptr.into(),
+                i32_const!(32).into(),
+                scratch_buf.into(),
+                scratch_len.into()
+            ]
+        )
+        .try_as_basic_value()
+        .left()
+        .unwrap()
+        .into_int_value();
+
+        let exists = bin
+            .builder
+            .build_int_compare(IntPredicate::EQ, ret, i32_zero!(), "transient_exists")
+            .unwrap();
+
+        let value = bin
+            .builder
+            .build_load(llvm_ty, scratch_buf, "transient")
+            .unwrap();
+
+        bin.builder
+            .build_select(exists, value, llvm_ty.const_zero(), "transient")
+            .unwrap()
+    }
+
+    /// Write a transient variable
+    pub(super) fn set_transient_storage<'a>(
+        &self,
+        bin: &Binary<'a>,
+        ty: &Type,
+        slot_ptr: PointerValue<'a>,
+        dest: BasicValueEnum<'a>,
+    ) {
+        emit_context!(bin);
+
+        let llvm_ty = bin.llvm_var_ty(ty.deref_any());
+
+        let value = if dest.is_pointer_value() {
+            dest.into_pointer_value()
+        } else {
+            let value = bin.builder.build_alloca(llvm_ty, "transient").unwrap();
+
+            bin.builder.build_store(value, dest).unwrap();
+
+            value
+        };
+
+        call!(
+            "set_transient_storage",
+            &[
+                slot_ptr.into(),
+                i32_const!(32).into(),
+                value.into(),
+                llvm_ty
+                    .size_of()
+                    .unwrap()
+                    .const_cast(bin.context.i32_type(), false)
+                    .into()
+            ]
+        );
+    }
+}
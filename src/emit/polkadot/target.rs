@@ -67,7 +67,7 @@ impl<'a> TargetRuntime<'a> for PolkadotTarget {
         bin.builder
             .build_store(
                 scratch_len,
-                bin.context.i64_type().const_int(len as u64, false),
+                bin.context.i32_type().const_int(len as u64, false),
             )
             .unwrap();
 
@@ -1447,6 +1447,12 @@ impl<'a> TargetRuntime<'a> for PolkadotTarget {
         // to store it
         let slot_ptr = bin.builder.build_alloca(slot.get_type(), "slot").unwrap();
 
+        if let Some(StorageType::Transient(_)) = storage_type {
+            bin.builder.build_store(slot_ptr, *slot).unwrap();
+
+            return self.get_transient_storage(bin, ty, slot_ptr);
+        }
+
         self.storage_load_slot(bin, ty, slot, slot_ptr, function, &storage_type)
     }
 
@@ -1462,6 +1468,12 @@ impl<'a> TargetRuntime<'a> for PolkadotTarget {
     ) {
         let slot_ptr = bin.builder.build_alloca(slot.get_type(), "slot").unwrap();
 
+        if let Some(StorageType::Transient(_)) = storage_type {
+            bin.builder.build_store(slot_ptr, *slot).unwrap();
+
+            return self.set_transient_storage(bin, ty, slot_ptr, dest);
+        }
+
         self.storage_store_slot(bin, ty, slot, slot_ptr, dest, function, &storage_type);
     }
 
@@ -1582,6 +1594,268 @@ impl<'a> TargetRuntime<'a> for PolkadotTarget {
                     .unwrap();
                 None
             }
+            "ecdsa_recover" => {
+                // bytes32 is kept in reverse byte order, the runtime expects the hash as it was hashed
+                let message_hash = bin
+                    .builder
+                    .build_alloca(args[1].into_int_value().get_type(), "message_hash")
+                    .unwrap();
+                bin.builder
+                    .build_store(message_hash, args[1].into_int_value())
+                    .unwrap();
+                let message_hash_be = bin
+                    .builder
+                    .build_alloca(args[1].into_int_value().get_type(), "message_hash_be")
+                    .unwrap();
+                call!(
+                    "__leNtobeN",
+                    &[
+                        message_hash.into(),
+                        message_hash_be.into(),
+                        i32_const!(32).into()
+                    ]
+                );
+                let public_key = call!("__malloc", &[i32_const!(33).into()])
+                    .try_as_basic_value()
+                    .left()
+                    .unwrap()
+                    .into_pointer_value();
+                let ret = call!(
+                    "ecdsa_recover",
+                    &[
+                        args[0].into_pointer_value().into(),
+                        message_hash_be.into(),
+                        public_key.into()
+                    ],
+                    "seal_ecdsa_recover"
+                )
+                .try_as_basic_value()
+                .left()
+                .unwrap()
+                .into_int_value();
+                bin.builder
+                    .build_store(args[2].into_pointer_value(), ret)
+                    .unwrap();
+                bin.builder
+                    .build_store(args[3].into_pointer_value(), public_key)
+                    .unwrap();
+                None
+            }
+            "ecdsa_to_eth_address" => {
+                let eth_address = bin
+                    .builder
+                    .build_alloca(bin.context.i8_type().array_type(20), "eth_address")
+                    .unwrap();
+                let ret = call!(
+                    "ecdsa_to_eth_address",
+                    &[args[0].into_pointer_value().into(), eth_address.into()],
+                    "seal_ecdsa_to_eth_address"
+                )
+                .try_as_basic_value()
+                .left()
+                .unwrap()
+                .into_int_value();
+                bin.builder
+                    .build_store(args[1].into_pointer_value(), ret)
+                    .unwrap();
+                call!(
+                    "__beNtoleN",
+                    &[
+                        eth_address.into(),
+                        args[2].into_pointer_value().into(),
+                        i32_const!(20).into()
+                    ]
+                );
+                None
+            }
+            "sr25519_verify" => {
+                let public_key = bin
+                    .builder
+                    .build_alloca(bin.address_type(), "public_key")
+                    .unwrap();
+                bin.builder
+                    .build_store(public_key, args[1].into_array_value())
+                    .unwrap();
+                let message = bin.vector_bytes(args[2].into_pointer_value().into());
+                let message_len = bin.vector_len(args[2].into_pointer_value().into());
+                let ret = call!(
+                    "sr25519_verify",
+                    &[
+                        args[0].into_pointer_value().into(),
+                        public_key.into(),
+                        message_len.into(),
+                        message.into()
+                    ],
+                    "seal_sr25519_verify"
+                )
+                .try_as_basic_value()
+                .left()
+                .unwrap()
+                .into_int_value();
+                let valid = bin
+                    .builder
+                    .build_int_compare(IntPredicate::EQ, ret, i32_zero!(), "valid")
+                    .unwrap();
+                bin.builder
+                    .build_store(args[3].into_pointer_value(), valid)
+                    .unwrap();
+                None
+            }
+            "own_code_hash" => {
+                let code_hash = call!("__malloc", &[i32_const!(32).into()])
+                    .try_as_basic_value()
+                    .left()
+                    .unwrap()
+                    .into_pointer_value();
+                let (_, len_ptr) = scratch_buf!();
+                bin.builder.build_store(len_ptr, i32_const!(32)).unwrap();
+                call!("own_code_hash", &[code_hash.into(), len_ptr.into()]);
+                bin.builder
+                    .build_store(args[0].into_pointer_value(), code_hash)
+                    .unwrap();
+                None
+            }
+            "xcm_execute" => {
+                let message = bin.vector_bytes(args[0].into_pointer_value().into());
+                let message_len = bin.vector_len(args[0].into_pointer_value().into());
+                let ret = call!(
+                    "xcm_execute",
+                    &[message.into(), message_len.into()],
+                    "seal_xcm_execute"
+                )
+                .try_as_basic_value()
+                .left()
+                .unwrap()
+                .into_int_value();
+                bin.builder
+                    .build_store(args[1].into_pointer_value(), ret)
+                    .unwrap();
+                None
+            }
+            "xcm_send" => {
+                let destination = bin.vector_bytes(args[0].into_pointer_value().into());
+                let destination_len = bin.vector_len(args[0].into_pointer_value().into());
+                let message = bin.vector_bytes(args[1].into_pointer_value().into());
+                let message_len = bin.vector_len(args[1].into_pointer_value().into());
+                let message_hash = bin
+                    .builder
+                    .build_alloca(bin.context.i8_type().array_type(32), "message_hash")
+                    .unwrap();
+                let ret = call!(
+                    "xcm_send",
+                    &[
+                        destination.into(),
+                        destination_len.into(),
+                        message.into(),
+                        message_len.into(),
+                        message_hash.into()
+                    ],
+                    "seal_xcm_send"
+                )
+                .try_as_basic_value()
+                .left()
+                .unwrap()
+                .into_int_value();
+                bin.builder
+                    .build_store(args[2].into_pointer_value(), ret)
+                    .unwrap();
+                call!(
+                    "__beNtoleN",
+                    &[
+                        message_hash.into(),
+                        args[3].into_pointer_value().into(),
+                        i32_const!(32).into()
+                    ]
+                );
+                None
+            }
+            "lock_delegate_dependency" | "unlock_delegate_dependency" => {
+                call!(
+                    builtin_func.id.name.as_str(),
+                    &[args[0].into_pointer_value().into()]
+                );
+                None
+            }
+            "take_storage" => {
+                let key = bin.vector_bytes(args[0].into_pointer_value().into());
+                let key_len = bin.vector_len(args[0].into_pointer_value().into());
+                let (value_ptr, value_len_ptr) = scratch_buf!();
+                bin.builder
+                    .build_store(value_len_ptr, i32_const!(SCRATCH_SIZE as u64))
+                    .unwrap();
+                let ret = call!(
+                    "take_storage",
+                    &[
+                        key.into(),
+                        key_len.into(),
+                        value_ptr.into(),
+                        value_len_ptr.into()
+                    ],
+                    "seal_take_storage"
+                )
+                .try_as_basic_value()
+                .left()
+                .unwrap()
+                .into_int_value();
+
+                // the length is not written if the key does not exist
+                let found = bin
+                    .builder
+                    .build_int_compare(IntPredicate::EQ, ret, i32_zero!(), "found")
+                    .unwrap();
+                let value_len = bin
+                    .builder
+                    .build_load(bin.context.i32_type(), value_len_ptr, "value_len")
+                    .unwrap()
+                    .into_int_value();
+                let value_len = bin
+                    .builder
+                    .build_select(found, value_len, i32_zero!(), "value_len")
+                    .unwrap();
+                let value = call!(
+                    "vector_new",
+                    &[value_len.into(), i32_const!(1).into(), value_ptr.into()]
+                )
+                .try_as_basic_value()
+                .left()
+                .unwrap();
+
+                bin.builder
+                    .build_store(args[1].into_pointer_value(), ret)
+                    .unwrap();
+                bin.builder
+                    .build_store(args[2].into_pointer_value(), value.into_pointer_value())
+                    .unwrap();
+                None
+            }
+            "contains_storage" => {
+                let key = bin.vector_bytes(args[0].into_pointer_value().into());
+                let key_len = bin.vector_len(args[0].into_pointer_value().into());
+                let size = call!(
+                    "contains_storage",
+                    &[key.into(), key_len.into()],
+                    "seal_contains_storage"
+                )
+                .try_as_basic_value()
+                .left()
+                .unwrap()
+                .into_int_value();
+
+                // u32::MAX is the sentinel value for a key which does not exist
+                let exists = bin
+                    .builder
+                    .build_int_compare(
+                        IntPredicate::NE,
+                        size,
+                        i32_const!(u32::MAX as u64),
+                        "exists",
+                    )
+                    .unwrap();
+                bin.builder
+                    .build_store(args[1].into_pointer_value(), exists)
+                    .unwrap();
+                None
+            }
             _ => unimplemented!(),
         }
     }
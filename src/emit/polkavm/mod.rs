@@ -0,0 +1,520 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::ffi::CString;
+use std::fmt::Write;
+
+use crate::codegen::dispatch::polkadot::DispatchType;
+use crate::codegen::polkadot::SCRATCH_SIZE;
+use crate::codegen::{Options, STORAGE_INITIALIZER};
+use crate::emit::functions::emit_functions;
+use crate::emit::{Binary, TargetRuntime};
+use crate::emit_context;
+use crate::sema::ast::{Contract, Namespace};
+use inkwell::context::Context;
+use inkwell::module::{Linkage, Module};
+use inkwell::types::{BasicMetadataTypeEnum, FunctionType};
+use inkwell::values::{
+    BasicMetadataValueEnum, BasicValue, BasicValueEnum, FunctionValue, IntValue, PointerValue,
+};
+use inkwell::AddressSpace;
+
+mod spill;
+mod target;
+
+pub struct PolkaVMTarget;
+
+/// Host functions of the revive pallet are declared with this prefix, so that they do not collide
+/// with the exported `call` function or the storage functions below.
+const IMPORT_PREFIX: &str = "revive_";
+
+/// Storage flag for transient storage
+const TRANSIENT: u64 = 1;
+
+/// The revive pallet writes nothing to an output pointer with this value
+const SENTINEL: u64 = u32::MAX as u64;
+
+impl PolkaVMTarget {
+    pub fn build<'a>(
+        context: &'a Context,
+        std_lib: &Module<'a>,
+        contract: &'a Contract,
+        ns: &'a Namespace,
+        opt: &'a Options,
+    ) -> Binary<'a> {
+        let filename = ns.files[contract.loc.file_no()].file_name();
+        let mut bin = Binary::new(
+            context,
+            ns,
+            &contract.id.name,
+            filename.as_str(),
+            opt,
+            std_lib,
+            None,
+        );
+
+        bin.set_early_value_aborts(contract);
+
+        let scratch_len = bin.module.add_global(
+            context.i32_type(),
+            Some(AddressSpace::default()),
+            "scratch_len",
+        );
+        scratch_len.set_linkage(Linkage::Internal);
+        scratch_len.set_initializer(&context.i32_type().get_undef());
+
+        bin.scratch_len = Some(scratch_len);
+
+        let scratch = bin.module.add_global(
+            context.i8_type().array_type(SCRATCH_SIZE),
+            Some(AddressSpace::default()),
+            "scratch",
+        );
+        scratch.set_linkage(Linkage::Internal);
+        scratch.set_initializer(&context.i8_type().array_type(SCRATCH_SIZE).get_undef());
+        bin.scratch = Some(scratch);
+
+        let mut target = PolkaVMTarget;
+
+        target.declare_imports(&bin);
+        target.define_polkadot_api(&bin);
+
+        emit_functions(&mut target, &mut bin, contract);
+
+        let function_name = CString::new(STORAGE_INITIALIZER).unwrap();
+        let mut storage_initializers = bin
+            .functions
+            .values()
+            .filter(|f| f.get_name() == function_name.as_c_str());
+        let storage_initializer = *storage_initializers
+            .next()
+            .expect("storage initializer is always present");
+        assert!(storage_initializers.next().is_none());
+
+        target.emit_dispatch(Some(storage_initializer), &mut bin);
+        target.emit_dispatch(None, &mut bin);
+
+        let imports = bin
+            .module
+            .get_functions()
+            .map(|f| f.get_name().to_str().unwrap().to_owned())
+            .filter(|name| name.starts_with(IMPORT_PREFIX))
+            .collect::<Vec<_>>();
+
+        let mut export_list = vec!["deploy", "call"];
+        export_list.extend(imports.iter().map(String::as_str));
+
+        bin.internalize(&export_list);
+
+        target.emit_metadata(&bin);
+
+        spill::spill_arguments(&bin);
+
+        bin
+    }
+
+    fn declare_imports(&self, bin: &Binary) {
+        let ctx = bin.context;
+        let u8_ptr = ctx.ptr_type(AddressSpace::default()).into();
+        let u32_val = ctx.i32_type().into();
+        let u32_ptr = ctx.ptr_type(AddressSpace::default()).into();
+        let u64_val = ctx.i64_type().into();
+
+        // Every host function which returns something returns a full register
+        macro_rules! import {
+            ($name:literal, $fn_type:ident $(,)? $( $args:expr ),*) => {
+                bin.module.add_function(
+                    &format!("{IMPORT_PREFIX}{}", $name),
+                    ctx.$fn_type().fn_type(&[$($args),*], false),
+                    Some(Linkage::External),
+                );
+            };
+        }
+
+        import!(
+            "set_storage",
+            i64_type,
+            u32_val,
+            u8_ptr,
+            u32_val,
+            u8_ptr,
+            u32_val
+        );
+        import!(
+            "get_storage",
+            i64_type,
+            u32_val,
+            u8_ptr,
+            u32_val,
+            u8_ptr,
+            u32_ptr
+        );
+        import!("clear_storage", i64_type, u32_val, u8_ptr, u32_val);
+        import!("contains_storage", i64_type, u32_val, u8_ptr, u32_val);
+        import!(
+            "take_storage",
+            i64_type,
+            u32_val,
+            u8_ptr,
+            u32_val,
+            u8_ptr,
+            u32_ptr
+        );
+        import!("call", i64_type, u64_val, u64_val, u64_val, u64_val, u64_val, u64_val);
+        import!(
+            "delegate_call",
+            i64_type,
+            u64_val,
+            u64_val,
+            u64_val,
+            u64_val,
+            u64_val,
+            u64_val
+        );
+        import!(
+            "instantiate",
+            i64_type,
+            u64_val,
+            u64_val,
+            u64_val,
+            u64_val,
+            u64_val,
+            u64_val
+        );
+        import!("terminate", void_type, u8_ptr);
+        import!("call_data_size", i64_type);
+        import!("call_data_copy", void_type, u8_ptr, u32_val, u32_val);
+        import!("seal_return", void_type, u32_val, u8_ptr, u32_val);
+        import!("return_data_size", i64_type);
+        import!("return_data_copy", void_type, u8_ptr, u32_ptr, u32_val);
+        import!("caller", void_type, u8_ptr);
+        import!("origin", void_type, u8_ptr);
+        import!("address", void_type, u8_ptr);
+        import!("balance", void_type, u8_ptr);
+        import!("balance_of", void_type, u8_ptr, u8_ptr);
+        import!("value_transferred", void_type, u8_ptr);
+        import!("minimum_balance", void_type, u8_ptr);
+        import!("chain_id", void_type, u8_ptr);
+        import!("base_fee", void_type, u8_ptr);
+        import!("block_number", void_type, u8_ptr);
+        import!("now", void_type, u8_ptr);
+        import!("gas_limit", i64_type);
+        import!("gas_price", i64_type);
+        import!("ref_time_left", i64_type);
+        import!("deposit_event", void_type, u8_ptr, u32_val, u8_ptr, u32_val);
+        import!("hash_keccak_256", void_type, u8_ptr, u32_val, u8_ptr);
+        import!("hash_blake2_128", void_type, u8_ptr, u32_val, u8_ptr);
+        import!("hash_blake2_256", void_type, u8_ptr, u32_val, u8_ptr);
+        import!("is_contract", i64_type, u8_ptr);
+        import!("caller_is_root", i64_type);
+        import!("set_code_hash", void_type, u8_ptr);
+        import!("own_code_hash", void_type, u8_ptr);
+        import!("ecdsa_to_eth_address", i64_type, u8_ptr, u8_ptr);
+        import!(
+            "call_chain_extension",
+            i64_type,
+            u32_val,
+            u8_ptr,
+            u32_val,
+            u8_ptr,
+            u32_ptr
+        );
+    }
+
+    /// The storage layout of the Polkadot target is reused as is. It calls the host functions of
+    /// the contracts pallet by name, so define those on top of the host functions of revive.
+    fn define_polkadot_api<'a>(&self, bin: &Binary<'a>) {
+        emit_context!(bin);
+
+        let ctx = bin.context;
+        let u8_ptr: BasicMetadataTypeEnum = ptr!().into();
+        let u32_val: BasicMetadataTypeEnum = ctx.i32_type().into();
+        let u32_ptr: BasicMetadataTypeEnum = ptr!().into();
+
+        let define = |name: &str, ty: FunctionType<'a>| {
+            let function = bin.module.add_function(name, ty, Some(Linkage::Internal));
+            let entry = ctx.append_basic_block(function, "entry");
+            bin.builder.position_at_end(entry);
+            function
+        };
+
+        // Forward the arguments to a storage host function and truncate its result to 32 bits
+        let storage =
+            |name: &str, import: &str, flags: u64, params: &[BasicMetadataTypeEnum<'a>]| {
+                let function = define(name, ctx.i32_type().fn_type(params, false));
+
+                let mut args: Vec<BasicMetadataValueEnum> = vec![i32_const!(flags).into()];
+                args.extend(function.get_param_iter().map(BasicMetadataValueEnum::from));
+
+                let ret = call!(&format!("{IMPORT_PREFIX}{import}"), &args)
+                    .try_as_basic_value()
+                    .left()
+                    .unwrap()
+                    .into_int_value();
+
+                let ret = bin
+                    .builder
+                    .build_int_truncate(ret, ctx.i32_type(), "ret")
+                    .unwrap();
+
+                bin.builder.build_return(Some(&ret)).unwrap();
+            };
+
+        storage(
+            "get_storage",
+            "get_storage",
+            0,
+            &[u8_ptr, u32_val, u8_ptr, u32_ptr],
+        );
+        storage(
+            "set_storage",
+            "set_storage",
+            0,
+            &[u8_ptr, u32_val, u8_ptr, u32_val],
+        );
+        storage("clear_storage", "clear_storage", 0, &[u8_ptr, u32_val]);
+        storage(
+            "get_transient_storage",
+            "get_storage",
+            TRANSIENT,
+            &[u8_ptr, u32_val, u8_ptr, u32_ptr],
+        );
+        storage(
+            "set_transient_storage",
+            "set_storage",
+            TRANSIENT,
+            &[u8_ptr, u32_val, u8_ptr, u32_val],
+        );
+
+        let function = define(
+            "hash_keccak_256",
+            ctx.void_type().fn_type(&[u8_ptr, u32_val, u8_ptr], false),
+        );
+        let args = function
+            .get_param_iter()
+            .map(|arg| arg.into())
+            .collect::<Vec<BasicMetadataValueEnum>>();
+        call!(&format!("{IMPORT_PREFIX}hash_keccak_256"), &args);
+        bin.builder.build_return(None).unwrap();
+
+        let function = define(
+            "seal_return",
+            ctx.void_type().fn_type(&[u32_val, u8_ptr, u32_val], false),
+        );
+        let args = function
+            .get_param_iter()
+            .map(|arg| arg.into())
+            .collect::<Vec<BasicMetadataValueEnum>>();
+        call!(&format!("{IMPORT_PREFIX}seal_return"), &args);
+        bin.builder.build_unreachable().unwrap();
+
+        // The revive pallet has no debug buffer, so the messages are dropped
+        define(
+            "debug_message",
+            ctx.i32_type().fn_type(&[u8_ptr, u32_val], false),
+        );
+        bin.builder.build_return(Some(&i32_zero!())).unwrap();
+    }
+
+    fn public_function_prelude<'a>(
+        &self,
+        bin: &Binary<'a>,
+        function: FunctionValue<'a>,
+        storage_initializer: Option<FunctionValue>,
+    ) -> (PointerValue<'a>, IntValue<'a>) {
+        emit_context!(bin);
+
+        let entry = bin.context.append_basic_block(function, "entry");
+
+        bin.builder.position_at_end(entry);
+
+        // init our heap
+        call!("__init_heap", &[]);
+
+        // Call the storage initializers on deploy
+        if let Some(initializer) = storage_initializer {
+            bin.builder.build_call(initializer, &[], "").unwrap();
+        }
+
+        let size = call!(
+            &format!("{IMPORT_PREFIX}call_data_size"),
+            &[],
+            "call_data_size"
+        )
+        .try_as_basic_value()
+        .left()
+        .unwrap()
+        .into_int_value();
+
+        let args_length = bin
+            .builder
+            .build_int_truncate(size, bin.context.i32_type(), "input_len")
+            .unwrap();
+
+        let input = call!("__malloc", &[args_length.into()])
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_pointer_value();
+
+        call!(
+            &format!("{IMPORT_PREFIX}call_data_copy"),
+            &[input.into(), args_length.into(), i32_zero!().into()]
+        );
+
+        // store the length in case someone wants it via msg.data
+        bin.builder
+            .build_store(bin.calldata_len.as_pointer_value(), args_length)
+            .unwrap();
+
+        (input, args_length)
+    }
+
+    /// Emits the "deploy" function if `storage_initializer` is `Some`, otherwise emits the "call" function.
+    fn emit_dispatch(&mut self, storage_initializer: Option<FunctionValue>, bin: &mut Binary) {
+        let ty = bin.context.void_type().fn_type(&[], false);
+        let export_name = if storage_initializer.is_some() {
+            "deploy"
+        } else {
+            "call"
+        };
+        let func = bin.module.add_function(export_name, ty, None);
+        let (input, input_length) = self.public_function_prelude(bin, func, storage_initializer);
+        let args = vec![
+            BasicMetadataValueEnum::PointerValue(input),
+            BasicMetadataValueEnum::IntValue(input_length),
+            BasicMetadataValueEnum::IntValue(self.value_transferred(bin)),
+            BasicMetadataValueEnum::PointerValue(bin.selector.as_pointer_value()),
+        ];
+        let dispatch_cfg_name = &storage_initializer
+            .map(|_| DispatchType::Deploy)
+            .unwrap_or(DispatchType::Call)
+            .to_string();
+        let cfg = bin.module.get_function(dispatch_cfg_name).unwrap();
+        bin.builder
+            .build_call(cfg, &args, dispatch_cfg_name)
+            .unwrap();
+
+        bin.builder.build_unreachable().unwrap();
+    }
+
+    /// polkavm-linker finds the imports and exports of the program through the metadata sections
+    /// which the polkavm-derive crate generates for Rust programs. Emit the same sections as
+    /// module level assembly: every used host function gets a stub with an `ecalli` instruction
+    /// which refers to its metadata, and the `deploy` and `call` functions are exported.
+    fn emit_metadata(&self, bin: &Binary) {
+        let mut asm = String::new();
+
+        for function in bin.module.get_functions() {
+            let name = function.get_name().to_str().unwrap();
+
+            let Some(import) = name.strip_prefix(IMPORT_PREFIX) else {
+                continue;
+            };
+
+            if function
+                .as_global_value()
+                .as_pointer_value()
+                .get_first_use()
+                .is_none()
+            {
+                continue;
+            }
+
+            let ty = function.get_type();
+
+            writeln!(
+                asm,
+                r#".pushsection .text.{name},"ax",@progbits
+.globl {name}
+.hidden {name}
+.type {name},@function
+{name}:
+.insn r 0xb, 0, 0, zero, zero, zero
+auipc zero, %pcrel_hi(.L{name}.meta)
+.4byte 0
+ret
+.popsection
+.pushsection .polkavm_metadata,"a",@progbits
+.L{name}.meta:
+.byte 2
+.4byte 0
+.4byte {}
+.8byte .L{name}.sym
+.byte {}
+.byte {}
+.byte 0
+.4byte 0
+.L{name}.sym:
+.ascii "{import}"
+.popsection"#,
+                import.len(),
+                ty.count_param_types(),
+                u8::from(ty.get_return_type().is_some()),
+            )
+            .unwrap();
+        }
+
+        for export in ["deploy", "call"] {
+            writeln!(
+                asm,
+                r#".pushsection .polkavm_exports,"Ra",@note
+.byte 1
+auipc zero, %pcrel_hi(.L{export}.meta)
+.4byte 0
+auipc zero, %pcrel_hi({export})
+.4byte 0
+.popsection
+.pushsection .polkavm_metadata,"a",@progbits
+.L{export}.meta:
+.byte 1
+.4byte 0
+.4byte {}
+.8byte .L{export}.sym
+.byte 0
+.byte 0
+.L{export}.sym:
+.ascii "{export}"
+.popsection"#,
+                export.len(),
+            )
+            .unwrap();
+        }
+
+        bin.module.set_inline_assembly(&asm);
+    }
+}
+
+/// Pack two 32 bit values into one register, like the host functions of revive with more
+/// than six arguments expect them
+fn pack_hi_lo<'a>(bin: &Binary<'a>, hi: IntValue<'a>, lo: IntValue<'a>) -> IntValue<'a> {
+    let i64_type = bin.context.i64_type();
+
+    let hi = bin
+        .builder
+        .build_int_z_extend_or_bit_cast(hi, i64_type, "hi")
+        .unwrap();
+    let lo = bin
+        .builder
+        .build_int_z_extend_or_bit_cast(lo, i64_type, "lo")
+        .unwrap();
+    let hi = bin
+        .builder
+        .build_left_shift(hi, i64_type.const_int(32, false), "hi")
+        .unwrap();
+
+    bin.builder.build_or(hi, lo, "packed").unwrap()
+}
+
+/// Pointers are passed to the host as 32 bit values
+fn ptr_to_u32<'a>(bin: &Binary<'a>, ptr: PointerValue<'a>) -> IntValue<'a> {
+    bin.builder
+        .build_ptr_to_int(ptr, bin.context.i32_type(), "ptr")
+        .unwrap()
+}
+
+/// Host functions return a full register, but the return codes fit in 32 bits
+fn return_code<'a>(bin: &Binary<'a>, ret: BasicValueEnum<'a>) -> IntValue<'a> {
+    bin.builder
+        .build_int_truncate(ret.into_int_value(), bin.context.i32_type(), "ret")
+        .unwrap()
+}
@@ -0,0 +1,218 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::emit::binary::Binary;
+use inkwell::llvm_sys::core::{
+    LLVMAppendExistingBasicBlock, LLVMGetCalledFunctionType, LLVMGetCalledValue,
+    LLVMGetNumArgOperands, LLVMGetOperand, LLVMIsAFunction, LLVMReplaceAllUsesWith,
+};
+use inkwell::llvm_sys::debuginfo::{LLVMInstructionGetDebugLoc, LLVMInstructionSetDebugLoc};
+use inkwell::types::{BasicType, BasicTypeEnum, FunctionType, StructType};
+use inkwell::values::{
+    AsValueRef, BasicValueEnum, FunctionValue, InstructionOpcode, InstructionValue, PointerValue,
+};
+use inkwell::AddressSpace;
+
+/// PolkaVM only has the registers a0 to a5 for passing arguments, and LLVM does not support
+/// passing arguments on the stack with the embedded ABI.
+const ARGUMENT_REGISTERS: u32 = 6;
+
+/// Functions which need more argument registers than PolkaVM has take a pointer to a struct
+/// holding their arguments instead. The calls to these functions are rewritten to store the
+/// arguments in such a struct.
+pub(super) fn spill_arguments(bin: &Binary) {
+    let mut spilled = Vec::new();
+
+    for function in bin.module.get_functions().collect::<Vec<_>>() {
+        if function.count_basic_blocks() == 0
+            || function.get_name().to_bytes().starts_with(b"llvm.")
+            || fn_registers(function.get_type()) <= ARGUMENT_REGISTERS
+        {
+            continue;
+        }
+
+        spilled.push(spill_function(bin, function));
+    }
+
+    // Collect the calls first, since rewriting them changes the instruction lists
+    let mut calls = Vec::new();
+
+    for function in bin.module.get_functions() {
+        for bb in function.get_basic_blocks() {
+            for inst in bb.get_instructions() {
+                if inst.get_opcode() == InstructionOpcode::Call {
+                    calls.push((function, inst));
+                }
+            }
+        }
+    }
+
+    for (caller, inst) in calls {
+        let callee = unsafe { LLVMGetCalledValue(inst.as_value_ref()) };
+        let fn_ty = unsafe { FunctionType::new(LLVMGetCalledFunctionType(inst.as_value_ref())) };
+
+        if let Some((_, new_function, args_ty)) = spilled
+            .iter()
+            .find(|(function, ..)| function.as_value_ref() == callee)
+        {
+            let args = spill_call_args(bin, caller, inst, *args_ty);
+
+            let call = bin
+                .builder
+                .build_call(*new_function, &[args.into()], "")
+                .unwrap();
+
+            replace_call(inst, call.as_value_ref());
+        } else if unsafe { LLVMIsAFunction(callee) }.is_null()
+            && fn_registers(fn_ty) > ARGUMENT_REGISTERS
+        {
+            let args_ty = args_struct(bin, fn_ty);
+            let args = spill_call_args(bin, caller, inst, args_ty);
+
+            let call = bin
+                .builder
+                .build_indirect_call(
+                    spilled_fn_type(bin, fn_ty),
+                    unsafe { PointerValue::new(callee) },
+                    &[args.into()],
+                    "",
+                )
+                .unwrap();
+
+            replace_call(inst, call.as_value_ref());
+        }
+    }
+
+    for (function, new_function, _) in spilled {
+        // function pointers to the old function
+        function.replace_all_uses_with(new_function);
+
+        unsafe { function.delete() };
+    }
+}
+
+/// Move the body of the function to a new function, which takes its arguments through a pointer
+fn spill_function<'a>(
+    bin: &Binary<'a>,
+    function: FunctionValue<'a>,
+) -> (FunctionValue<'a>, FunctionValue<'a>, StructType<'a>) {
+    let fn_ty = function.get_type();
+    let args_ty = args_struct(bin, fn_ty);
+
+    let name = function.get_name().to_str().unwrap().to_owned();
+    function.as_global_value().set_name("");
+
+    let new_function = bin.module.add_function(
+        &name,
+        spilled_fn_type(bin, fn_ty),
+        Some(function.get_linkage()),
+    );
+
+    for bb in function.get_basic_blocks() {
+        bb.remove_from_function().unwrap();
+
+        unsafe { LLVMAppendExistingBasicBlock(new_function.as_value_ref(), bb.as_mut_ptr()) };
+    }
+
+    if let Some(subprogram) = function.get_subprogram() {
+        new_function.set_subprogram(subprogram);
+    }
+
+    let args = new_function.get_first_param().unwrap().into_pointer_value();
+    let entry = new_function.get_first_basic_block().unwrap();
+
+    match entry.get_first_instruction() {
+        Some(inst) => bin.builder.position_before(&inst),
+        None => bin.builder.position_at_end(entry),
+    }
+
+    for (i, param) in function.get_params().into_iter().enumerate() {
+        let field = bin
+            .builder
+            .build_struct_gep(args_ty, args, i as u32, "arg")
+            .unwrap();
+
+        let value = bin
+            .builder
+            .build_load(param.get_type(), field, "arg")
+            .unwrap();
+
+        unsafe { LLVMReplaceAllUsesWith(param.as_value_ref(), value.as_value_ref()) };
+    }
+
+    (function, new_function, args_ty)
+}
+
+/// Store the arguments of the call in a struct, and leave the builder positioned before the call
+fn spill_call_args<'a>(
+    bin: &Binary<'a>,
+    caller: FunctionValue<'a>,
+    inst: InstructionValue<'a>,
+    args_ty: StructType<'a>,
+) -> PointerValue<'a> {
+    let args = bin.build_alloca(caller, args_ty, "args");
+
+    bin.builder.position_before(&inst);
+
+    let arg_count = unsafe { LLVMGetNumArgOperands(inst.as_value_ref()) };
+
+    for i in 0..arg_count {
+        let arg = unsafe { BasicValueEnum::new(LLVMGetOperand(inst.as_value_ref(), i)) };
+
+        let field = bin
+            .builder
+            .build_struct_gep(args_ty, args, i, "arg")
+            .unwrap();
+
+        bin.builder.build_store(field, arg).unwrap();
+    }
+
+    args
+}
+
+/// Replace the old call with the new one, keeping the debug location
+fn replace_call(inst: InstructionValue, call: inkwell::llvm_sys::prelude::LLVMValueRef) {
+    unsafe {
+        LLVMInstructionSetDebugLoc(call, LLVMInstructionGetDebugLoc(inst.as_value_ref()));
+        LLVMReplaceAllUsesWith(inst.as_value_ref(), call);
+    }
+
+    inst.erase_from_basic_block();
+}
+
+fn args_struct<'a>(bin: &Binary<'a>, fn_ty: FunctionType<'a>) -> StructType<'a> {
+    bin.context.struct_type(&fn_ty.get_param_types(), false)
+}
+
+fn spilled_fn_type<'a>(bin: &Binary<'a>, fn_ty: FunctionType<'a>) -> FunctionType<'a> {
+    let args = bin.context.ptr_type(AddressSpace::default()).into();
+
+    match fn_ty.get_return_type() {
+        Some(ty) => ty.fn_type(&[args], false),
+        None => bin.context.void_type().fn_type(&[args], false),
+    }
+}
+
+/// Number of argument registers needed for calling a function of this type
+fn fn_registers(fn_ty: FunctionType) -> u32 {
+    let params: u32 = fn_ty.get_param_types().into_iter().map(registers).sum();
+
+    // Large return values are written to memory, which is passed as a hidden argument
+    match fn_ty.get_return_type() {
+        Some(BasicTypeEnum::IntType(ty)) if ty.get_bit_width() > 128 => params + 1,
+        Some(ty @ (BasicTypeEnum::StructType(_) | BasicTypeEnum::ArrayType(_)))
+            if registers(ty) > 2 =>
+        {
+            params + 1
+        }
+        _ => params,
+    }
+}
+
+fn registers(ty: BasicTypeEnum) -> u32 {
+    match ty {
+        BasicTypeEnum::IntType(ty) if ty.get_bit_width() > 64 && ty.get_bit_width() <= 128 => 2,
+        BasicTypeEnum::ArrayType(ty) => ty.len() * registers(ty.get_element_type()),
+        BasicTypeEnum::StructType(ty) => ty.get_field_types().into_iter().map(registers).sum(),
+        _ => 1,
+    }
+}
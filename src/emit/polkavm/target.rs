@@ -0,0 +1,1153 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::codegen::cfg::HashTy;
+use crate::codegen::polkadot::SCRATCH_SIZE;
+use crate::emit::binary::Binary;
+use crate::emit::expression::expression;
+use crate::emit::polkadot::PolkadotTarget;
+use crate::emit::polkavm::{
+    pack_hi_lo, ptr_to_u32, return_code, PolkaVMTarget, IMPORT_PREFIX, SENTINEL,
+};
+use crate::emit::{ContractArgs, TargetRuntime, Variable};
+use crate::sema::ast;
+use crate::sema::ast::{Function, Type};
+use crate::{codegen, emit_context};
+use inkwell::types::{BasicTypeEnum, IntType};
+use inkwell::values::{
+    ArrayValue, BasicMetadataValueEnum, BasicValue, BasicValueEnum, FunctionValue, IntValue,
+    PointerValue,
+};
+use inkwell::{AddressSpace, IntPredicate};
+use solang_parser::pt::{Loc, StorageType};
+use std::collections::HashMap;
+use tiny_keccak::{Hasher, Keccak};
+
+/// Call flag for calls which may not modify any state
+const READ_ONLY: u64 = 16;
+
+/// The storage is laid out exactly like on Polkadot, so the storage functions are shared. They
+/// call the host functions of the contracts pallet, which are defined on top of those of revive.
+impl<'a> TargetRuntime<'a> for PolkaVMTarget {
+    fn set_storage_extfunc(
+        &self,
+        bin: &Binary<'a>,
+        function: FunctionValue,
+        slot: PointerValue,
+        dest: PointerValue,
+        dest_ty: BasicTypeEnum,
+    ) {
+        PolkadotTarget.set_storage_extfunc(bin, function, slot, dest, dest_ty)
+    }
+
+    fn get_storage_extfunc(
+        &self,
+        bin: &Binary<'a>,
+        function: FunctionValue,
+        slot: PointerValue<'a>,
+    ) -> PointerValue<'a> {
+        PolkadotTarget.get_storage_extfunc(bin, function, slot)
+    }
+
+    fn set_storage_string(
+        &self,
+        bin: &Binary<'a>,
+        function: FunctionValue<'a>,
+        slot: PointerValue<'a>,
+        dest: BasicValueEnum<'a>,
+    ) {
+        PolkadotTarget.set_storage_string(bin, function, slot, dest)
+    }
+
+    fn get_storage_int(
+        &self,
+        bin: &Binary<'a>,
+        function: FunctionValue,
+        slot: PointerValue<'a>,
+        ty: IntType<'a>,
+    ) -> IntValue<'a> {
+        PolkadotTarget.get_storage_int(bin, function, slot, ty)
+    }
+
+    fn get_storage_string(
+        &self,
+        bin: &Binary<'a>,
+        function: FunctionValue,
+        slot: PointerValue<'a>,
+    ) -> PointerValue<'a> {
+        PolkadotTarget.get_storage_string(bin, function, slot)
+    }
+
+    fn get_storage_bytes_subscript(
+        &self,
+        bin: &Binary<'a>,
+        function: FunctionValue,
+        slot: IntValue<'a>,
+        index: IntValue<'a>,
+        loc: Loc,
+    ) -> IntValue<'a> {
+        PolkadotTarget.get_storage_bytes_subscript(bin, function, slot, index, loc)
+    }
+
+    fn set_storage_bytes_subscript(
+        &self,
+        bin: &Binary<'a>,
+        function: FunctionValue,
+        slot: IntValue<'a>,
+        index: IntValue<'a>,
+        val: IntValue<'a>,
+        loc: Loc,
+    ) {
+        PolkadotTarget.set_storage_bytes_subscript(bin, function, slot, index, val, loc)
+    }
+
+    fn storage_subscript(
+        &self,
+        bin: &Binary<'a>,
+        function: FunctionValue<'a>,
+        ty: &Type,
+        slot: IntValue<'a>,
+        index: BasicValueEnum<'a>,
+    ) -> IntValue<'a> {
+        PolkadotTarget.storage_subscript(bin, function, ty, slot, index)
+    }
+
+    fn storage_push(
+        &self,
+        bin: &Binary<'a>,
+        function: FunctionValue<'a>,
+        ty: &Type,
+        slot: IntValue<'a>,
+        val: Option<BasicValueEnum<'a>>,
+    ) -> BasicValueEnum<'a> {
+        PolkadotTarget.storage_push(bin, function, ty, slot, val)
+    }
+
+    fn storage_pop(
+        &self,
+        bin: &Binary<'a>,
+        function: FunctionValue<'a>,
+        ty: &Type,
+        slot: IntValue<'a>,
+        load: bool,
+        loc: Loc,
+    ) -> Option<BasicValueEnum<'a>> {
+        PolkadotTarget.storage_pop(bin, function, ty, slot, load, loc)
+    }
+
+    fn storage_array_length(
+        &self,
+        bin: &Binary<'a>,
+        function: FunctionValue,
+        slot: IntValue<'a>,
+        elem_ty: &Type,
+    ) -> IntValue<'a> {
+        PolkadotTarget.storage_array_length(bin, function, slot, elem_ty)
+    }
+
+    fn storage_load(
+        &self,
+        bin: &Binary<'a>,
+        ty: &Type,
+        slot: &mut IntValue<'a>,
+        function: FunctionValue<'a>,
+        storage_type: &Option<StorageType>,
+    ) -> BasicValueEnum<'a> {
+        PolkadotTarget.storage_load(bin, ty, slot, function, storage_type)
+    }
+
+    fn storage_store(
+        &self,
+        bin: &Binary<'a>,
+        ty: &Type,
+        existing: bool,
+        slot: &mut IntValue<'a>,
+        dest: BasicValueEnum<'a>,
+        function: FunctionValue<'a>,
+        storage_type: &Option<StorageType>,
+    ) {
+        PolkadotTarget.storage_store(bin, ty, existing, slot, dest, function, storage_type)
+    }
+
+    fn storage_delete(
+        &self,
+        bin: &Binary<'a>,
+        ty: &Type,
+        slot: &mut IntValue<'a>,
+        function: FunctionValue<'a>,
+    ) {
+        PolkadotTarget.storage_delete(bin, ty, slot, function)
+    }
+
+    fn keccak256_hash(
+        &self,
+        bin: &Binary<'a>,
+        src: PointerValue,
+        length: IntValue,
+        dest: PointerValue,
+    ) {
+        PolkadotTarget.keccak256_hash(bin, src, length, dest)
+    }
+
+    /// The revive pallet has no debug buffer
+    fn print(&self, _bin: &Binary, _string_ptr: PointerValue, _string_len: IntValue) {}
+
+    fn return_empty_abi(&self, bin: &Binary) {
+        PolkadotTarget.return_empty_abi(bin)
+    }
+
+    fn return_code<'b>(&self, bin: &'b Binary, _ret: IntValue<'b>) {
+        emit_context!(bin);
+
+        // we can't return specific errors
+        self.assert_failure(bin, ptr!().const_zero(), i32_zero!());
+    }
+
+    fn assert_failure(&self, bin: &Binary, data: PointerValue, length: IntValue) {
+        PolkadotTarget.assert_failure(bin, data, length)
+    }
+
+    fn return_abi_data<'b>(
+        &self,
+        bin: &Binary<'b>,
+        data: PointerValue<'b>,
+        data_len: BasicValueEnum<'b>,
+    ) {
+        PolkadotTarget.return_abi_data(bin, data, data_len)
+    }
+
+    fn create_contract<'b>(
+        &mut self,
+        bin: &Binary<'b>,
+        function: FunctionValue<'b>,
+        success: Option<&mut BasicValueEnum<'b>>,
+        contract_no: usize,
+        address: PointerValue<'b>,
+        encoded_args: BasicValueEnum<'b>,
+        encoded_args_len: BasicValueEnum<'b>,
+        contract_args: ContractArgs<'b>,
+        _loc: Loc,
+    ) {
+        emit_context!(bin);
+
+        let created_contract = &bin.ns.contracts[contract_no];
+
+        let code = created_contract.emit(bin.ns, bin.options, contract_no);
+
+        let mut hasher = Keccak::v256();
+        hasher.update(&code);
+        let mut hash = [0u8; 32];
+        hasher.finalize(&mut hash);
+
+        let codehash = bin.emit_global_string(
+            &format!("binary_{}_codehash", created_contract.id),
+            &hash,
+            true,
+        );
+
+        // The input of instantiate is the code hash, followed by the constructor arguments
+        let encoded_args_len = encoded_args_len.into_int_value();
+        let input_len = bin
+            .builder
+            .build_int_add(encoded_args_len, i32_const!(32), "input_len")
+            .unwrap();
+        let input = call!("__malloc", &[input_len.into()])
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_pointer_value();
+        call!(
+            "__memcpy",
+            &[input.into(), codehash.into(), i32_const!(32).into()]
+        );
+        let args = unsafe {
+            bin.builder
+                .build_gep(bin.context.i8_type(), input, &[i32_const!(32)], "args")
+                .unwrap()
+        };
+        call!(
+            "__memcpy",
+            &[
+                args.into(),
+                bin.vector_bytes(encoded_args).into(),
+                encoded_args_len.into()
+            ]
+        );
+
+        let value_ptr = bin.build_alloca(function, bin.value_type(), "balance");
+        let value = contract_args
+            .value
+            .unwrap_or_else(|| bin.value_type().const_zero());
+        bin.builder.build_store(value_ptr, value).unwrap();
+
+        // Without a salt, the address is derived from the nonce of the deployer
+        let salt = if let Some(salt) = contract_args.salt {
+            let salt_buf = bin.build_alloca(function, bin.context.i8_type().array_type(32), "salt");
+            bin.builder.build_store(salt_buf, salt).unwrap();
+            ptr_to_u32(bin, salt_buf)
+        } else {
+            i32_const!(SENTINEL)
+        };
+
+        let (scratch_buf, scratch_len) = scratch_buf!();
+        bin.builder
+            .build_store(scratch_len, i32_const!(SCRATCH_SIZE as u64))
+            .unwrap();
+
+        let ret = call!(
+            &format!("{IMPORT_PREFIX}instantiate"),
+            &[
+                self.ref_time(bin, contract_args.gas.unwrap()).into(),
+                i64_const!(u64::MAX).into(),
+                pack_hi_lo(
+                    bin,
+                    ptr_to_u32(bin, self.deposit_limit(bin)),
+                    ptr_to_u32(bin, value_ptr)
+                )
+                .into(),
+                pack_hi_lo(bin, input_len, ptr_to_u32(bin, input)).into(),
+                pack_hi_lo(
+                    bin,
+                    ptr_to_u32(bin, scratch_len),
+                    ptr_to_u32(bin, scratch_buf)
+                )
+                .into(),
+                pack_hi_lo(bin, ptr_to_u32(bin, address), salt).into(),
+            ],
+            "instantiate"
+        )
+        .try_as_basic_value()
+        .left()
+        .unwrap();
+
+        *success.unwrap() = return_code(bin, ret).into();
+    }
+
+    /// Call external binary
+    fn external_call<'b>(
+        &self,
+        bin: &Binary<'b>,
+        function: FunctionValue<'b>,
+        success: Option<&mut BasicValueEnum<'b>>,
+        payload: PointerValue<'b>,
+        payload_len: IntValue<'b>,
+        address: Option<BasicValueEnum<'b>>,
+        contract_args: ContractArgs<'b>,
+        call_type: ast::CallTy,
+        _loc: Loc,
+    ) {
+        emit_context!(bin);
+
+        let (scratch_buf, scratch_len) = scratch_buf!();
+        bin.builder
+            .build_store(scratch_len, i32_const!(SCRATCH_SIZE as u64))
+            .unwrap();
+
+        let flags = contract_args.flags.unwrap_or(i32_zero!());
+        let callee = ptr_to_u32(bin, address.unwrap().into_pointer_value());
+        let ref_time = self.ref_time(bin, contract_args.gas.unwrap());
+        let deposit_limit = ptr_to_u32(bin, self.deposit_limit(bin));
+        let input_data = pack_hi_lo(bin, payload_len, ptr_to_u32(bin, payload));
+        let output_data = pack_hi_lo(
+            bin,
+            ptr_to_u32(bin, scratch_len),
+            ptr_to_u32(bin, scratch_buf),
+        );
+
+        let ret = match call_type {
+            ast::CallTy::Regular | ast::CallTy::Static => {
+                let (flags, value) = if call_type == ast::CallTy::Static {
+                    let flags = bin
+                        .builder
+                        .build_or(flags, i32_const!(READ_ONLY), "flags")
+                        .unwrap();
+
+                    (flags, bin.value_type().const_zero())
+                } else {
+                    (flags, contract_args.value.unwrap())
+                };
+
+                let value_ptr = bin.build_alloca(function, bin.value_type(), "balance");
+                bin.builder.build_store(value_ptr, value).unwrap();
+
+                call!(
+                    &format!("{IMPORT_PREFIX}call"),
+                    &[
+                        pack_hi_lo(bin, flags, callee).into(),
+                        ref_time.into(),
+                        i64_const!(u64::MAX).into(),
+                        pack_hi_lo(bin, deposit_limit, ptr_to_u32(bin, value_ptr)).into(),
+                        input_data.into(),
+                        output_data.into(),
+                    ],
+                    "call"
+                )
+            }
+            // On revive, delegate_call takes the address of the callee rather than a code hash
+            ast::CallTy::Delegate => call!(
+                &format!("{IMPORT_PREFIX}delegate_call"),
+                &[
+                    pack_hi_lo(bin, flags, callee).into(),
+                    ref_time.into(),
+                    i64_const!(u64::MAX).into(),
+                    bin.builder
+                        .build_int_z_extend(deposit_limit, bin.context.i64_type(), "deposit")
+                        .unwrap()
+                        .into(),
+                    input_data.into(),
+                    output_data.into(),
+                ],
+                "delegate_call"
+            ),
+        }
+        .try_as_basic_value()
+        .left()
+        .unwrap();
+
+        *success.unwrap() = return_code(bin, ret).into();
+    }
+
+    /// Send value to address
+    fn value_transfer<'b>(
+        &self,
+        bin: &Binary<'b>,
+        _function: FunctionValue,
+        success: Option<&mut BasicValueEnum<'b>>,
+        address: PointerValue<'b>,
+        value: IntValue<'b>,
+        _loc: Loc,
+    ) {
+        emit_context!(bin);
+
+        let value_ptr = bin
+            .builder
+            .build_alloca(bin.value_type(), "balance")
+            .unwrap();
+        bin.builder.build_store(value_ptr, value).unwrap();
+
+        let (_, scratch_len) = scratch_buf!();
+        bin.builder.build_store(scratch_len, i32_zero!()).unwrap();
+
+        // A call without input transfers the value
+        let ret = call!(
+            &format!("{IMPORT_PREFIX}call"),
+            &[
+                pack_hi_lo(bin, i32_zero!(), ptr_to_u32(bin, address)).into(),
+                i64_const!(u64::MAX).into(),
+                i64_const!(u64::MAX).into(),
+                pack_hi_lo(
+                    bin,
+                    ptr_to_u32(bin, self.deposit_limit(bin)),
+                    ptr_to_u32(bin, value_ptr)
+                )
+                .into(),
+                pack_hi_lo(bin, i32_zero!(), i32_zero!()).into(),
+                pack_hi_lo(bin, ptr_to_u32(bin, scratch_len), i32_const!(SENTINEL)).into(),
+            ],
+            "transfer"
+        )
+        .try_as_basic_value()
+        .left()
+        .unwrap();
+
+        *success.unwrap() = return_code(bin, ret).into();
+    }
+
+    fn return_data<'b>(&self, bin: &Binary<'b>, _function: FunctionValue) -> PointerValue<'b> {
+        emit_context!(bin);
+
+        // The output of a call which trapped is not written to the scratch buffer, so ask the
+        // host for the return data
+        let size = call!(
+            &format!("{IMPORT_PREFIX}return_data_size"),
+            &[],
+            "return_data_size"
+        )
+        .try_as_basic_value()
+        .left()
+        .unwrap()
+        .into_int_value();
+        let size = bin
+            .builder
+            .build_int_truncate(size, bin.context.i32_type(), "return_data_size")
+            .unwrap();
+
+        let v = bin.vector_new(size, i32_const!(1), None, &Type::DynamicBytes);
+
+        let (_, scratch_len) = scratch_buf!();
+        bin.builder.build_store(scratch_len, size).unwrap();
+
+        call!(
+            &format!("{IMPORT_PREFIX}return_data_copy"),
+            &[
+                bin.vector_bytes(v).into(),
+                scratch_len.into(),
+                i32_zero!().into()
+            ]
+        );
+
+        v.into_pointer_value()
+    }
+
+    /// The value is 256 bits on revive
+    fn value_transferred<'b>(&self, bin: &Binary<'b>) -> IntValue<'b> {
+        emit_context!(bin);
+
+        let value = bin.builder.build_alloca(bin.value_type(), "value").unwrap();
+
+        call!(
+            &format!("{IMPORT_PREFIX}value_transferred"),
+            &[value.into()],
+            "value_transferred"
+        );
+
+        bin.builder
+            .build_load(bin.value_type(), value, "value_transferred")
+            .unwrap()
+            .into_int_value()
+    }
+
+    /// Terminate execution, destroy contract and send remaining funds to addr
+    fn selfdestruct<'b>(&self, bin: &Binary<'b>, addr: ArrayValue<'b>) {
+        emit_context!(bin);
+
+        let address = bin
+            .builder
+            .build_alloca(bin.address_type(), "address")
+            .unwrap();
+
+        bin.builder.build_store(address, addr).unwrap();
+
+        call!(
+            &format!("{IMPORT_PREFIX}terminate"),
+            &[address.into()],
+            "terminated"
+        );
+
+        bin.builder.build_unreachable().unwrap();
+    }
+
+    /// Crypto Hash
+    fn hash<'b>(
+        &self,
+        bin: &Binary<'b>,
+        function: FunctionValue<'b>,
+        hash: HashTy,
+        input: PointerValue<'b>,
+        input_len: IntValue<'b>,
+    ) -> IntValue<'b> {
+        emit_context!(bin);
+
+        let hashlen = match hash {
+            HashTy::Ripemd160 => 20,
+            HashTy::Blake2_128 => 16,
+            HashTy::Keccak256 | HashTy::Sha256 | HashTy::Blake2_256 => 32,
+        };
+
+        let res = bin
+            .builder
+            .build_array_alloca(bin.context.i8_type(), i32_const!(hashlen), "res")
+            .unwrap();
+
+        match hash {
+            HashTy::Keccak256 => {
+                call!(
+                    &format!("{IMPORT_PREFIX}hash_keccak_256"),
+                    &[input.into(), input_len.into(), res.into()],
+                    "hash"
+                );
+            }
+            HashTy::Blake2_128 => {
+                call!(
+                    &format!("{IMPORT_PREFIX}hash_blake2_128"),
+                    &[input.into(), input_len.into(), res.into()],
+                    "hash"
+                );
+            }
+            HashTy::Blake2_256 => {
+                call!(
+                    &format!("{IMPORT_PREFIX}hash_blake2_256"),
+                    &[input.into(), input_len.into(), res.into()],
+                    "hash"
+                );
+            }
+            // The revive pallet does not provide ripemd160
+            HashTy::Ripemd160 => {
+                call!(
+                    "ripemd160",
+                    &[input.into(), input_len.into(), res.into()],
+                    "hash"
+                );
+            }
+            // sha256 is the precompile at address 2, like on Ethereum
+            HashTy::Sha256 => {
+                let mut precompile = [0u8; 20];
+                precompile[19] = 2;
+                let precompile = bin.emit_global_string("sha256_precompile", &precompile, true);
+
+                let value_ptr = bin.build_alloca(function, bin.value_type(), "value");
+                bin.builder
+                    .build_store(value_ptr, bin.value_type().const_zero())
+                    .unwrap();
+
+                let res_len = bin.build_alloca(function, bin.context.i32_type(), "res_len");
+                bin.builder
+                    .build_store(res_len, i32_const!(hashlen))
+                    .unwrap();
+
+                call!(
+                    &format!("{IMPORT_PREFIX}call"),
+                    &[
+                        pack_hi_lo(bin, i32_zero!(), ptr_to_u32(bin, precompile)).into(),
+                        i64_const!(u64::MAX).into(),
+                        i64_const!(u64::MAX).into(),
+                        pack_hi_lo(
+                            bin,
+                            ptr_to_u32(bin, self.deposit_limit(bin)),
+                            ptr_to_u32(bin, value_ptr)
+                        )
+                        .into(),
+                        pack_hi_lo(bin, input_len, ptr_to_u32(bin, input)).into(),
+                        pack_hi_lo(bin, ptr_to_u32(bin, res_len), ptr_to_u32(bin, res)).into(),
+                    ],
+                    "sha256"
+                );
+            }
+        }
+
+        // bytes32 needs to reverse bytes
+        let temp = bin
+            .builder
+            .build_alloca(bin.llvm_type(&ast::Type::Bytes(hashlen as u8)), "hash")
+            .unwrap();
+
+        call!(
+            "__beNtoleN",
+            &[res.into(), temp.into(), i32_const!(hashlen).into()]
+        );
+
+        bin.builder
+            .build_load(
+                bin.llvm_type(&ast::Type::Bytes(hashlen as u8)),
+                temp,
+                "hash",
+            )
+            .unwrap()
+            .into_int_value()
+    }
+
+    /// Emit event
+    fn emit_event<'b>(
+        &self,
+        bin: &Binary<'b>,
+        _function: FunctionValue<'b>,
+        data: BasicValueEnum<'b>,
+        topics: &[BasicValueEnum<'b>],
+    ) {
+        emit_context!(bin);
+
+        // The topics are passed as an array of 32 byte values. Each topic is ABI encoded, so
+        // it is exactly 32 bytes long.
+        let topic_buf = if topics.is_empty() {
+            ptr!().const_null()
+        } else {
+            let topic_buf = bin
+                .builder
+                .build_array_alloca(
+                    bin.context.i8_type(),
+                    i32_const!(32 * topics.len() as u64),
+                    "topics",
+                )
+                .unwrap();
+
+            for (i, topic) in topics.iter().enumerate() {
+                let dest = unsafe {
+                    bin.builder
+                        .build_gep(
+                            bin.context.i8_type(),
+                            topic_buf,
+                            &[i32_const!(32 * i as u64)],
+                            "dest",
+                        )
+                        .unwrap()
+                };
+
+                call!(
+                    "__memcpy",
+                    &[
+                        dest.into(),
+                        bin.vector_bytes(*topic).into(),
+                        i32_const!(32).into(),
+                    ]
+                );
+            }
+
+            topic_buf
+        };
+
+        call!(
+            &format!("{IMPORT_PREFIX}deposit_event"),
+            &[
+                topic_buf.into(),
+                i32_const!(topics.len() as u64).into(),
+                bin.vector_bytes(data).into(),
+                bin.vector_len(data).into(),
+            ]
+        );
+    }
+
+    /// builtin expressions
+    fn builtin<'b>(
+        &self,
+        bin: &Binary<'b>,
+        expr: &codegen::Expression,
+        vartab: &HashMap<usize, Variable<'b>>,
+        function: FunctionValue<'b>,
+    ) -> BasicValueEnum<'b> {
+        emit_context!(bin);
+
+        // Host functions which write a value to the scratch buffer
+        macro_rules! get_host_value {
+            ($name:literal, $ty:expr) => {{
+                let (scratch_buf, _) = scratch_buf!();
+
+                call!(
+                    &format!("{IMPORT_PREFIX}{}", $name),
+                    &[scratch_buf.into()],
+                    $name
+                );
+
+                bin.builder.build_load($ty, scratch_buf, $name).unwrap()
+            }};
+        }
+
+        // Host functions which return a value in a register
+        macro_rules! get_register_value {
+            ($name:literal) => {{
+                call!(&format!("{IMPORT_PREFIX}{}", $name), &[], $name)
+                    .try_as_basic_value()
+                    .left()
+                    .unwrap()
+            }};
+        }
+
+        match expr {
+            codegen::Expression::Builtin {
+                kind: codegen::Builtin::Calldata,
+                ..
+            } => {
+                let calldata_len = bin
+                    .builder
+                    .build_load(
+                        bin.context.i32_type(),
+                        bin.calldata_len.as_pointer_value(),
+                        "calldata_len",
+                    )
+                    .unwrap()
+                    .into_int_value();
+
+                let v = bin.vector_new(calldata_len, i32_const!(1), None, &Type::DynamicBytes);
+
+                call!(
+                    &format!("{IMPORT_PREFIX}call_data_copy"),
+                    &[
+                        bin.vector_bytes(v).into(),
+                        calldata_len.into(),
+                        i32_zero!().into()
+                    ]
+                );
+
+                v
+            }
+            // The block number and timestamp are 256 bit values, of which we use the lower 64 bits
+            codegen::Expression::Builtin {
+                kind: codegen::Builtin::BlockNumber,
+                ..
+            } => get_host_value!("block_number", bin.context.i64_type()),
+            codegen::Expression::Builtin {
+                kind: codegen::Builtin::Timestamp,
+                ..
+            } => get_host_value!("now", bin.context.i64_type()),
+            codegen::Expression::Builtin {
+                kind: codegen::Builtin::Gasleft,
+                ..
+            } => get_register_value!("ref_time_left"),
+            codegen::Expression::Builtin {
+                kind: codegen::Builtin::GasLimit,
+                ..
+            } => get_register_value!("gas_limit"),
+            codegen::Expression::Builtin {
+                kind: codegen::Builtin::Gasprice,
+                args,
+                ..
+            } => {
+                // gasprice is available as "tx.gasprice" which will give you the price for one unit
+                // of gas, or "tx.gasprice(uint64)" which will give you the price of N gas units
+                let price = get_register_value!("gas_price").into_int_value();
+                let price = bin
+                    .builder
+                    .build_int_z_extend(price, bin.value_type(), "price")
+                    .unwrap();
+
+                if args.is_empty() {
+                    price.into()
+                } else {
+                    let gas = expression(self, bin, &args[0], vartab, function).into_int_value();
+                    let gas = bin
+                        .builder
+                        .build_int_z_extend(gas, bin.value_type(), "gas")
+                        .unwrap();
+
+                    bin.builder
+                        .build_int_mul(price, gas, "price")
+                        .unwrap()
+                        .into()
+                }
+            }
+            codegen::Expression::Builtin {
+                kind: codegen::Builtin::Sender,
+                ..
+            } => get_host_value!("caller", bin.address_type()),
+            codegen::Expression::Builtin {
+                kind: codegen::Builtin::Origin,
+                ..
+            } => get_host_value!("origin", bin.address_type()),
+            codegen::Expression::Builtin {
+                kind: codegen::Builtin::Value,
+                ..
+            } => self.value_transferred(bin).into(),
+            codegen::Expression::Builtin {
+                kind: codegen::Builtin::MinimumBalance,
+                ..
+            } => get_host_value!("minimum_balance", bin.value_type()),
+            codegen::Expression::Builtin {
+                kind: codegen::Builtin::ChainId,
+                ..
+            } => get_host_value!("chain_id", bin.context.custom_width_int_type(256)),
+            codegen::Expression::Builtin {
+                kind: codegen::Builtin::BaseFee,
+                ..
+            } => get_host_value!("base_fee", bin.context.custom_width_int_type(256)),
+            codegen::Expression::Builtin {
+                kind: codegen::Builtin::GetAddress,
+                ..
+            } => {
+                let (scratch_buf, _) = scratch_buf!();
+
+                call!(
+                    &format!("{IMPORT_PREFIX}address"),
+                    &[scratch_buf.into()],
+                    "address"
+                );
+
+                // The scratch buffer is a global buffer which gets overwritten by many host
+                // functions, so codegen loads the address right away.
+                scratch_buf.as_basic_value_enum()
+            }
+            codegen::Expression::Builtin {
+                kind: codegen::Builtin::Balance,
+                args,
+                ..
+            } => {
+                let address = expression(self, bin, &args[0], vartab, function).into_array_value();
+
+                let address_ptr = bin
+                    .builder
+                    .build_alloca(bin.address_type(), "address")
+                    .unwrap();
+
+                bin.builder.build_store(address_ptr, address).unwrap();
+
+                let (scratch_buf, _) = scratch_buf!();
+
+                call!(
+                    &format!("{IMPORT_PREFIX}balance_of"),
+                    &[address_ptr.into(), scratch_buf.into()],
+                    "balance_of"
+                );
+
+                bin.builder
+                    .build_load(bin.value_type(), scratch_buf, "balance")
+                    .unwrap()
+            }
+            _ => unreachable!("{:?}", expr),
+        }
+    }
+
+    fn builtin_function(
+        &self,
+        bin: &Binary<'a>,
+        _function: FunctionValue<'a>,
+        builtin_func: &Function,
+        args: &[BasicMetadataValueEnum<'a>],
+        _first_arg_type: Option<BasicTypeEnum>,
+    ) -> Option<BasicValueEnum<'a>> {
+        emit_context!(bin);
+
+        match builtin_func.id.name.as_str() {
+            "chain_extension" => {
+                let input_ptr = bin.vector_bytes(args[1].into_pointer_value().into());
+                let input_len = bin.vector_len(args[1].into_pointer_value().into());
+                let (output_ptr, output_len_ptr) = scratch_buf!();
+                let len = 16384; // 16KB for the output buffer should be enough for virtually any case.
+                bin.builder
+                    .build_store(output_len_ptr, i32_const!(len))
+                    .unwrap();
+                call!("__bzero8", &[output_ptr.into(), i32_const!(len / 8).into()]);
+                let ret = call!(
+                    &format!("{IMPORT_PREFIX}call_chain_extension"),
+                    &[
+                        args[0].into_int_value().into(),
+                        input_ptr.into(),
+                        input_len.into(),
+                        output_ptr.into(),
+                        output_len_ptr.into()
+                    ]
+                )
+                .try_as_basic_value()
+                .left()
+                .unwrap();
+
+                let buf_len = bin
+                    .builder
+                    .build_load(bin.context.i32_type(), output_len_ptr, "buf_len")
+                    .unwrap()
+                    .into_int_value();
+                let buf = call!(
+                    "vector_new",
+                    &[buf_len.into(), i32_const!(1).into(), output_ptr.into(),]
+                )
+                .try_as_basic_value()
+                .left()
+                .unwrap();
+
+                bin.builder
+                    .build_store(args[2].into_pointer_value(), return_code(bin, ret))
+                    .unwrap();
+                bin.builder
+                    .build_store(args[3].into_pointer_value(), buf.into_pointer_value())
+                    .unwrap();
+
+                None
+            }
+            "is_contract" => {
+                let address = bin
+                    .builder
+                    .build_alloca(bin.address_type(), "maybe_contract")
+                    .unwrap();
+                bin.builder
+                    .build_store(address, args[0].into_array_value())
+                    .unwrap();
+                let ret = call!(
+                    &format!("{IMPORT_PREFIX}is_contract"),
+                    &[address.into()],
+                    "seal_is_contract"
+                )
+                .try_as_basic_value()
+                .left()
+                .unwrap()
+                .into_int_value();
+                let is_contract = bin
+                    .builder
+                    .build_int_compare(
+                        IntPredicate::NE,
+                        ret,
+                        ret.get_type().const_zero(),
+                        "is_contract",
+                    )
+                    .unwrap();
+                bin.builder
+                    .build_store(args[1].into_pointer_value(), is_contract)
+                    .unwrap();
+                None
+            }
+            "set_code_hash" => {
+                // On revive, set_code_hash traps if the code hash does not exist
+                call!(
+                    &format!("{IMPORT_PREFIX}set_code_hash"),
+                    &[args[0].into_pointer_value().into()],
+                    "seal_set_code_hash"
+                );
+                bin.builder
+                    .build_store(args[1].into_pointer_value(), i32_zero!())
+                    .unwrap();
+                None
+            }
+            "caller_is_root" => {
+                let ret = call!(
+                    &format!("{IMPORT_PREFIX}caller_is_root"),
+                    &[],
+                    "seal_caller_is_root"
+                )
+                .try_as_basic_value()
+                .left()
+                .unwrap()
+                .into_int_value();
+                let is_root = bin
+                    .builder
+                    .build_int_compare(
+                        IntPredicate::NE,
+                        ret,
+                        ret.get_type().const_zero(),
+                        "is_root",
+                    )
+                    .unwrap();
+                bin.builder
+                    .build_store(args[0].into_pointer_value(), is_root)
+                    .unwrap();
+                None
+            }
+            "ecdsa_to_eth_address" => {
+                let eth_address = bin
+                    .builder
+                    .build_alloca(bin.context.i8_type().array_type(20), "eth_address")
+                    .unwrap();
+                let ret = call!(
+                    &format!("{IMPORT_PREFIX}ecdsa_to_eth_address"),
+                    &[args[0].into_pointer_value().into(), eth_address.into()],
+                    "seal_ecdsa_to_eth_address"
+                )
+                .try_as_basic_value()
+                .left()
+                .unwrap();
+                bin.builder
+                    .build_store(args[1].into_pointer_value(), return_code(bin, ret))
+                    .unwrap();
+                call!(
+                    "__beNtoleN",
+                    &[
+                        eth_address.into(),
+                        args[2].into_pointer_value().into(),
+                        i32_const!(20).into()
+                    ]
+                );
+                None
+            }
+            "own_code_hash" => {
+                let code_hash = call!("__malloc", &[i32_const!(32).into()])
+                    .try_as_basic_value()
+                    .left()
+                    .unwrap()
+                    .into_pointer_value();
+                call!(
+                    &format!("{IMPORT_PREFIX}own_code_hash"),
+                    &[code_hash.into()]
+                );
+                bin.builder
+                    .build_store(args[0].into_pointer_value(), code_hash)
+                    .unwrap();
+                None
+            }
+            "take_storage" => {
+                let key = bin.vector_bytes(args[0].into_pointer_value().into());
+                let key_len = bin.vector_len(args[0].into_pointer_value().into());
+                let (value_ptr, value_len_ptr) = scratch_buf!();
+                bin.builder
+                    .build_store(value_len_ptr, i32_const!(SCRATCH_SIZE as u64))
+                    .unwrap();
+                let ret = call!(
+                    &format!("{IMPORT_PREFIX}take_storage"),
+                    &[
+                        i32_zero!().into(),
+                        key.into(),
+                        key_len.into(),
+                        value_ptr.into(),
+                        value_len_ptr.into()
+                    ],
+                    "seal_take_storage"
+                )
+                .try_as_basic_value()
+                .left()
+                .unwrap();
+                let ret = return_code(bin, ret);
+
+                // the length is not written if the key does not exist
+                let found = bin
+                    .builder
+                    .build_int_compare(IntPredicate::EQ, ret, i32_zero!(), "found")
+                    .unwrap();
+                let value_len = bin
+                    .builder
+                    .build_load(bin.context.i32_type(), value_len_ptr, "value_len")
+                    .unwrap()
+                    .into_int_value();
+                let value_len = bin
+                    .builder
+                    .build_select(found, value_len, i32_zero!(), "value_len")
+                    .unwrap();
+                let value = call!(
+                    "vector_new",
+                    &[value_len.into(), i32_const!(1).into(), value_ptr.into()]
+                )
+                .try_as_basic_value()
+                .left()
+                .unwrap();
+
+                bin.builder
+                    .build_store(args[1].into_pointer_value(), ret)
+                    .unwrap();
+                bin.builder
+                    .build_store(args[2].into_pointer_value(), value.into_pointer_value())
+                    .unwrap();
+                None
+            }
+            "contains_storage" => {
+                let key = bin.vector_bytes(args[0].into_pointer_value().into());
+                let key_len = bin.vector_len(args[0].into_pointer_value().into());
+                let size = call!(
+                    &format!("{IMPORT_PREFIX}contains_storage"),
+                    &[i32_zero!().into(), key.into(), key_len.into()],
+                    "seal_contains_storage"
+                )
+                .try_as_basic_value()
+                .left()
+                .unwrap();
+
+                // u32::MAX is the sentinel value for a key which does not exist
+                let exists = bin
+                    .builder
+                    .build_int_compare(
+                        IntPredicate::NE,
+                        return_code(bin, size),
+                        i32_const!(SENTINEL),
+                        "exists",
+                    )
+                    .unwrap();
+                bin.builder
+                    .build_store(args[1].into_pointer_value(), exists)
+                    .unwrap();
+                None
+            }
+            _ => unimplemented!(),
+        }
+    }
+}
+
+impl PolkaVMTarget {
+    /// A gas limit of zero means no limit
+    fn ref_time<'b>(&self, bin: &Binary<'b>, gas: IntValue<'b>) -> IntValue<'b> {
+        let unlimited = bin
+            .builder
+            .build_int_compare(
+                IntPredicate::EQ,
+                gas,
+                gas.get_type().const_zero(),
+                "unlimited",
+            )
+            .unwrap();
+
+        bin.builder
+            .build_select(
+                unlimited,
+                bin.context.i64_type().const_all_ones(),
+                gas,
+                "ref_time",
+            )
+            .unwrap()
+            .into_int_value()
+    }
+
+    /// The storage deposit limit is a 256 bit value; all ones means no limit
+    fn deposit_limit<'b>(&self, bin: &Binary<'b>) -> PointerValue<'b> {
+        bin.emit_global_string("deposit_limit", &[0xff; 32], true)
+    }
+}
@@ -20,7 +20,10 @@ use crate::emit::functions::emit_functions;
 use crate::emit::loop_builder::LoopBuilder;
 use crate::emit::{Binary, ContractArgs, TargetRuntime};
 
-pub struct SolanaTarget();
+pub struct SolanaTarget {
+    /// Number of bytes needed for the transient variables of the contract
+    transient_size: u32,
+}
 
 // Implement the Solana target which uses BPF
 impl SolanaTarget {
@@ -31,7 +34,9 @@ impl SolanaTarget {
         ns: &'a ast::Namespace,
         opt: &'a Options,
     ) -> Binary<'a> {
-        let mut target = SolanaTarget();
+        let mut target = SolanaTarget {
+            transient_size: contract.transient_layout_size.to_u32().unwrap(),
+        };
         let filename = ns.files[contract.loc.file_no()].file_name();
         let mut bin = Binary::new(
             context,
@@ -83,6 +88,8 @@ impl SolanaTarget {
             "sol_sha256",
             "sol_keccak256",
             "sol_log_data",
+            "sol_get_rent_sysvar",
+            "sol_get_epoch_schedule_sysvar",
         ]);
 
         bin
@@ -289,6 +296,86 @@ impl SolanaTarget {
             .into_pointer_value()
     }
 
+    /// Returns the transient variables. Solana has no transient storage, so they are kept on
+    /// the heap until the instruction finishes. The memory is allocated and zeroed on first use.
+    fn transient_storage_data<'b>(&self, bin: &Binary<'b>) -> PointerValue<'b> {
+        let parameters = self.sol_parameters(bin);
+        let ptr_ty = bin.context.ptr_type(AddressSpace::default());
+
+        let field = unsafe {
+            bin.builder
+                .build_gep(
+                    bin.module.get_struct_type("struct.SolParameters").unwrap(),
+                    parameters,
+                    &[
+                        bin.context.i32_type().const_int(0, false),
+                        bin.context.i32_type().const_int(8, false),
+                    ],
+                    "transient",
+                )
+                .unwrap()
+        };
+
+        let existing = bin
+            .builder
+            .build_load(ptr_ty, field, "transient")
+            .unwrap()
+            .into_pointer_value();
+
+        let entry = bin.builder.get_insert_block().unwrap();
+        let function = entry.get_parent().unwrap();
+        let allocate = bin.context.append_basic_block(function, "transient_alloc");
+        let done = bin.context.append_basic_block(function, "transient_done");
+
+        let is_null = bin.builder.build_is_null(existing, "is_null").unwrap();
+
+        bin.builder
+            .build_conditional_branch(is_null, allocate, done)
+            .unwrap();
+
+        bin.builder.position_at_end(allocate);
+
+        // __bzero8 clears 8 bytes at a time, so round up the allocation
+        let words = (self.transient_size as u64).div_ceil(8);
+        let size = bin.context.i32_type().const_int(words * 8, false);
+
+        let new = bin
+            .builder
+            .build_call(
+                bin.module.get_function("__malloc").unwrap(),
+                &[size.into()],
+                "",
+            )
+            .unwrap()
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_pointer_value();
+
+        bin.builder
+            .build_call(
+                bin.module.get_function("__bzero8").unwrap(),
+                &[
+                    new.into(),
+                    bin.context.i32_type().const_int(words, false).into(),
+                ],
+                "",
+            )
+            .unwrap();
+
+        bin.builder.build_store(field, new).unwrap();
+
+        bin.builder.build_unconditional_branch(done).unwrap();
+
+        bin.builder.position_at_end(done);
+
+        let data = bin.builder.build_phi(ptr_ty, "transient").unwrap();
+
+        data.add_incoming(&[(&existing, entry), (&new, allocate)]);
+
+        data.as_basic_value().into_pointer_value()
+    }
+
     /// Free binary storage and zero out
     fn storage_free<'b>(
         &self,
@@ -761,14 +848,14 @@ impl SolanaTarget {
             .unwrap()
             .const_cast(bin.context.i32_type(), false);
 
-        let account = self.contract_storage_account(bin);
+        let parameters = self.sol_parameters(bin);
 
         // account_data_alloc will return offset = 0 if the string is length 0
         let rc = bin
             .builder
             .build_call(
                 bin.module.get_function("account_data_alloc").unwrap(),
-                &[account.into(), entry_length.into(), offset_ptr.into()],
+                &[parameters.into(), entry_length.into(), offset_ptr.into()],
                 "rc",
             )
             .unwrap()
@@ -846,7 +933,11 @@ impl SolanaTarget {
                 .builder
                 .build_call(
                     bin.module.get_function("account_data_alloc").unwrap(),
-                    &[account.into(), new_string_length.into(), offset_ptr.into()],
+                    &[
+                        parameters.into(),
+                        new_string_length.into(),
+                        offset_ptr.into(),
+                    ],
                     "alloc",
                 )
                 .unwrap()
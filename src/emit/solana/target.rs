@@ -300,7 +300,7 @@ impl<'a> TargetRuntime<'a> for SolanaTarget {
         val: Option<BasicValueEnum<'a>>,
     ) -> BasicValueEnum<'a> {
         let data = self.contract_storage_data(bin);
-        let account = self.contract_storage_account(bin);
+        let parameters = self.sol_parameters(bin);
 
         let member = unsafe {
             bin.builder
@@ -341,7 +341,7 @@ impl<'a> TargetRuntime<'a> for SolanaTarget {
             .build_call(
                 bin.module.get_function("account_data_realloc").unwrap(),
                 &[
-                    account.into(),
+                    parameters.into(),
                     offset.into(),
                     new_length.into(),
                     member.into(),
@@ -412,7 +412,7 @@ impl<'a> TargetRuntime<'a> for SolanaTarget {
         loc: Loc,
     ) -> Option<BasicValueEnum<'a>> {
         let data = self.contract_storage_data(bin);
-        let account = self.contract_storage_account(bin);
+        let parameters = self.sol_parameters(bin);
 
         let member = unsafe {
             bin.builder
@@ -493,7 +493,7 @@ impl<'a> TargetRuntime<'a> for SolanaTarget {
             .build_call(
                 bin.module.get_function("account_data_realloc").unwrap(),
                 &[
-                    account.into(),
+                    parameters.into(),
                     offset.into(),
                     new_length.into(),
                     member.into(),
@@ -569,9 +569,13 @@ impl<'a> TargetRuntime<'a> for SolanaTarget {
         ty: &ast::Type,
         slot: &mut IntValue<'a>,
         function: FunctionValue<'a>,
-        _storage_type: &Option<StorageType>,
+        storage_type: &Option<StorageType>,
     ) -> BasicValueEnum<'a> {
-        let data = self.contract_storage_data(bin);
+        let data = if let Some(StorageType::Transient(_)) = storage_type {
+            self.transient_storage_data(bin)
+        } else {
+            self.contract_storage_data(bin)
+        };
 
         // the slot is simply the offset after the magic
         let member = unsafe {
@@ -821,10 +825,14 @@ impl<'a> TargetRuntime<'a> for SolanaTarget {
         offset: &mut IntValue<'a>,
         val: BasicValueEnum<'a>,
         function: FunctionValue<'a>,
-        _: &Option<StorageType>,
+        storage_type: &Option<StorageType>,
     ) {
-        let data = self.contract_storage_data(bin);
-        let account = self.contract_storage_account(bin);
+        let data = if let Some(StorageType::Transient(_)) = storage_type {
+            self.transient_storage_data(bin)
+        } else {
+            self.contract_storage_data(bin)
+        };
+        let parameters = self.sol_parameters(bin);
 
         // the slot is simply the offset after the magic
         let member = unsafe {
@@ -893,7 +901,7 @@ impl<'a> TargetRuntime<'a> for SolanaTarget {
                     .builder
                     .build_call(
                         bin.module.get_function("account_data_alloc").unwrap(),
-                        &[account.into(), new_string_length.into(), member.into()],
+                        &[parameters.into(), new_string_length.into(), member.into()],
                         "alloc",
                     )
                     .unwrap()
@@ -948,7 +956,7 @@ impl<'a> TargetRuntime<'a> for SolanaTarget {
                     .builder
                     .build_call(
                         bin.module.get_function("account_data_alloc").unwrap(),
-                        &[account.into(), new_string_length.into(), member.into()],
+                        &[parameters.into(), new_string_length.into(), member.into()],
                         "alloc",
                     )
                     .unwrap()
@@ -1040,7 +1048,7 @@ impl<'a> TargetRuntime<'a> for SolanaTarget {
                     .build_call(
                         bin.module.get_function("account_data_realloc").unwrap(),
                         &[
-                            account.into(),
+                            parameters.into(),
                             offset.into(),
                             new_length.into(),
                             member.into(),
@@ -1674,6 +1682,70 @@ impl<'a> TargetRuntime<'a> for SolanaTarget {
                     .build_load(bin.context.i64_type(), slot, "timestamp")
                     .unwrap()
             }
+            codegen::Expression::Builtin {
+                kind: kind @ (codegen::Builtin::SysvarRent | codegen::Builtin::SysvarEpochSchedule),
+                args,
+                ..
+            } => {
+                assert_eq!(args.len(), 0);
+
+                // The sysvar is read with a syscall, so no account is needed
+                let name = if *kind == codegen::Builtin::SysvarRent {
+                    "sol_rent"
+                } else {
+                    "sol_epoch_schedule"
+                };
+
+                bin.builder
+                    .build_call(bin.module.get_function(name).unwrap(), &[], "sysvar")
+                    .unwrap()
+                    .try_as_basic_value()
+                    .left()
+                    .unwrap()
+            }
+            codegen::Expression::Builtin {
+                kind: codegen::Builtin::CurrentInstructionIndex,
+                args,
+                ..
+            } => {
+                assert_eq!(args.len(), 0);
+
+                let parameters = self.sol_parameters(bin);
+
+                bin.builder
+                    .build_call(
+                        bin.module
+                            .get_function("sol_current_instruction_index")
+                            .unwrap(),
+                        &[parameters.into()],
+                        "index",
+                    )
+                    .unwrap()
+                    .try_as_basic_value()
+                    .left()
+                    .unwrap()
+            }
+            codegen::Expression::Builtin {
+                kind: codegen::Builtin::InstructionAt,
+                args,
+                ..
+            } => {
+                assert_eq!(args.len(), 1);
+
+                let index = expression(self, bin, &args[0], vartab, function);
+                let parameters = self.sol_parameters(bin);
+
+                bin.builder
+                    .build_call(
+                        bin.module.get_function("sol_instruction_at").unwrap(),
+                        &[index.into(), parameters.into()],
+                        "instruction",
+                    )
+                    .unwrap()
+                    .try_as_basic_value()
+                    .left()
+                    .unwrap()
+            }
             codegen::Expression::Builtin {
                 kind: codegen::Builtin::GetAddress,
                 args,
@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub(super) mod target;
-use crate::codegen::{cfg::ControlFlowGraph, HostFunctions, Options};
+use crate::codegen::{cfg::ControlFlowGraph, revert::soroban_error_code, HostFunctions, Options};
 
 use crate::emit::cfg::emit_cfg;
 use crate::{emit::Binary, sema::ast};
@@ -10,12 +10,20 @@ use inkwell::{
     context::Context,
     module::{Linkage, Module},
     types::FunctionType,
+    AddressSpace,
 };
+use num_traits::ToPrimitive;
 use soroban_sdk::xdr::{
     Limited, Limits, ScEnvMetaEntry, ScEnvMetaEntryInterfaceVersion, ScSpecEntry,
-    ScSpecFunctionInputV0, ScSpecFunctionV0, ScSpecTypeDef, StringM, WriteXdr,
+    ScSpecEventDataFormat, ScSpecEventParamLocationV0, ScSpecEventParamV0, ScSpecEventV0,
+    ScSpecFunctionInputV0, ScSpecFunctionV0, ScSpecTypeDef, ScSpecTypeUdt, ScSpecUdtEnumCaseV0,
+    ScSpecUdtEnumV0, ScSpecUdtErrorEnumCaseV0, ScSpecUdtErrorEnumV0, ScSpecUdtStructFieldV0,
+    ScSpecUdtStructV0, StringM, WriteXdr,
 };
 
+/// Name of the global which holds the transient variables
+const TRANSIENT_STORAGE: &str = "transient_storage";
+
 const SOROBAN_ENV_INTERFACE_VERSION: ScEnvMetaEntryInterfaceVersion =
     ScEnvMetaEntryInterfaceVersion {
         protocol: 23,
@@ -86,17 +94,69 @@ impl HostFunctions {
                 .i64_type()
                 .fn_type(&[ty.into(), ty.into(), ty.into()], false),
 
+            HostFunctions::MapGet => bin
+                .context
+                .i64_type()
+                .fn_type(&[ty.into(), ty.into()], false),
+
             HostFunctions::VecPushBack => bin
                 .context
                 .i64_type()
                 .fn_type(&[ty.into(), ty.into()], false),
 
+            HostFunctions::VecPut | HostFunctions::BytesPut => bin
+                .context
+                .i64_type()
+                .fn_type(&[ty.into(), ty.into(), ty.into()], false),
+
+            HostFunctions::VecGet
+            | HostFunctions::BytesGet
+            | HostFunctions::BytesPush
+            | HostFunctions::BytesNewFromLinearMemory => bin
+                .context
+                .i64_type()
+                .fn_type(&[ty.into(), ty.into()], false),
+
+            HostFunctions::VecLen
+            | HostFunctions::VecPopBack
+            | HostFunctions::VecBack
+            | HostFunctions::BytesLen
+            | HostFunctions::BytesPop
+            | HostFunctions::BytesBack => bin.context.i64_type().fn_type(&[ty.into()], false),
+
+            HostFunctions::BytesNew => bin.context.i64_type().fn_type(&[], false),
+
             HostFunctions::StringNewFromLinearMemory => bin
                 .context
                 .i64_type()
                 .fn_type(&[ty.into(), ty.into()], false),
             HostFunctions::StrKeyToAddr => bin.context.i64_type().fn_type(&[ty.into()], false),
             HostFunctions::GetCurrentContractAddress => bin.context.i64_type().fn_type(&[], false),
+            HostFunctions::ContractEvent => bin
+                .context
+                .i64_type()
+                .fn_type(&[ty.into(), ty.into()], false),
+            HostFunctions::FailWithError => bin.context.i64_type().fn_type(&[ty.into()], false),
+            HostFunctions::GetLedgerSequence
+            | HostFunctions::GetLedgerTimestamp
+            | HostFunctions::GetLedgerNetworkId => bin.context.i64_type().fn_type(&[], false),
+            HostFunctions::ComputeHashSha256 | HostFunctions::ComputeHashKeccak256 => {
+                bin.context.i64_type().fn_type(&[ty.into()], false)
+            }
+            HostFunctions::VerifySigEd25519 | HostFunctions::RecoverKeyEcdsaSecp256k1 => bin
+                .context
+                .i64_type()
+                .fn_type(&[ty.into(), ty.into(), ty.into()], false),
+            // the range of the PRNG is passed as plain u64, not as Vals
+            HostFunctions::PrngU64InInclusiveRange => bin
+                .context
+                .i64_type()
+                .fn_type(&[ty.into(), ty.into()], false),
+            HostFunctions::BytesCopyToLinearMemory
+            | HostFunctions::CreateContractWithConstructor => bin
+                .context
+                .i64_type()
+                .fn_type(&[ty.into(), ty.into(), ty.into(), ty.into()], false),
             HostFunctions::ObjToI128Lo64 => bin.context.i64_type().fn_type(&[ty.into()], false),
             HostFunctions::ObjToI128Hi64 => bin.context.i64_type().fn_type(&[ty.into()], false),
             HostFunctions::ObjToU128Lo64 => bin.context.i64_type().fn_type(&[ty.into()], false),
@@ -109,6 +169,18 @@ impl HostFunctions {
                 .context
                 .i64_type()
                 .fn_type(&[ty.into(), ty.into()], false),
+            HostFunctions::ObjToU256HiHi
+            | HostFunctions::ObjToU256HiLo
+            | HostFunctions::ObjToU256LoHi
+            | HostFunctions::ObjToU256LoLo
+            | HostFunctions::ObjToI256HiHi
+            | HostFunctions::ObjToI256HiLo
+            | HostFunctions::ObjToI256LoHi
+            | HostFunctions::ObjToI256LoLo => bin.context.i64_type().fn_type(&[ty.into()], false),
+            HostFunctions::ObjFromU256Pieces | HostFunctions::ObjFromI256Pieces => bin
+                .context
+                .i64_type()
+                .fn_type(&[ty.into(), ty.into(), ty.into(), ty.into()], false),
         }
     }
 }
@@ -137,7 +209,11 @@ impl SorobanTarget {
 
         let mut export_list = Vec::new();
         Self::declare_externals(&mut bin);
+        Self::declare_transient_storage(&mut bin, contract);
         Self::emit_functions_with_spec(contract, &mut bin, context, contract_no, &mut export_list);
+        Self::emit_event_spec_entries(contract, &mut bin, context);
+        Self::emit_udt_spec_entries(contract, &mut bin, context);
+        Self::emit_error_spec_entry(&mut bin, context, contract_no);
         bin.internalize(export_list.as_slice());
 
         //Self::emit_initializer(&mut binary, ns, contract.constructors(ns).first());
@@ -248,27 +324,8 @@ impl SorobanTarget {
                             .unwrap_or_else(|| i.to_string())
                             .try_into()
                             .expect("function input name exceeds limit"),
-                        type_: {
-                            let ty = if let ast::Type::Ref(ty) = &p.ty {
-                                ty.as_ref()
-                            } else {
-                                &p.ty
-                            };
-
-                            match ty {
-                                ast::Type::Uint(32) => ScSpecTypeDef::U32,
-                                ast::Type::Int(32) => ScSpecTypeDef::I32,
-                                ast::Type::Uint(64) => ScSpecTypeDef::U64,
-                                &ast::Type::Int(64) => ScSpecTypeDef::I64,
-                                ast::Type::Int(128) => ScSpecTypeDef::I128,
-                                ast::Type::Uint(128) => ScSpecTypeDef::U128,
-                                ast::Type::Bool => ScSpecTypeDef::Bool,
-                                ast::Type::Address(_) => ScSpecTypeDef::Address,
-                                ast::Type::Bytes(_) => ScSpecTypeDef::Bytes,
-                                ast::Type::String => ScSpecTypeDef::String,
-                                _ => panic!("unsupported input type {:?}", p.ty),
-                            }
-                        }, // TODO: Map type.
+                        type_: spec_type_def(&p.ty, bin.ns)
+                            .unwrap_or_else(|| panic!("unsupported input type {:?}", p.ty)),
                         doc: StringM::default(), // TODO: Add doc.
                     })
                     .collect::<Vec<_>>()
@@ -290,12 +347,17 @@ impl SorobanTarget {
                             ast::Type::Uint(64) => ScSpecTypeDef::U64,
                             ast::Type::Int(128) => ScSpecTypeDef::I128,
                             ast::Type::Uint(128) => ScSpecTypeDef::U128,
+                            ast::Type::Int(256) => ScSpecTypeDef::I256,
+                            ast::Type::Uint(256) => ScSpecTypeDef::U256,
                             ast::Type::Int(_) => ScSpecTypeDef::I32,
                             ast::Type::Bool => ScSpecTypeDef::Bool,
                             ast::Type::Address(_) => ScSpecTypeDef::Address,
-                            ast::Type::Bytes(_) => ScSpecTypeDef::Bytes,
+                            ast::Type::Bytes(_) | ast::Type::DynamicBytes => ScSpecTypeDef::Bytes,
                             ast::Type::String => ScSpecTypeDef::String,
                             ast::Type::Void => ScSpecTypeDef::Void,
+                            ast::Type::Struct(_) | ast::Type::Enum(_) => {
+                                spec_type_def(&ty, bin.ns).unwrap()
+                            }
                             _ => panic!("unsupported return type {ty:?}"),
                         }
                     }) // TODO: Map type.
@@ -311,6 +373,205 @@ impl SorobanTarget {
         }
     }
 
+    // Events are described in the contract spec, so that tooling can decode the topics and data
+    // published by `emit`. The layout here must match the one in codegen::events::soroban.
+    fn emit_event_spec_entries<'a>(
+        contract: &'a ast::Contract,
+        bin: &mut Binary<'a>,
+        context: &'a Context,
+    ) {
+        for event_no in &contract.emits_events {
+            let event = &bin.ns.events[*event_no];
+            let name = &event.id.name;
+
+            let prefix_topics = if event.anonymous {
+                Vec::new()
+            } else {
+                vec![name
+                    .clone()
+                    .try_into()
+                    .unwrap_or_else(|_| panic!("event name {name:?} exceeds limit"))]
+            };
+
+            let mut spec = Limited::new(Vec::new(), Limits::none());
+            ScSpecEntry::EventV0(ScSpecEventV0 {
+                doc: StringM::default(),
+                lib: StringM::default(),
+                name: name
+                    .clone()
+                    .try_into()
+                    .unwrap_or_else(|_| panic!("event name {name:?} exceeds limit")),
+                prefix_topics: prefix_topics
+                    .try_into()
+                    .expect("event prefix topic count exceeds limit"),
+                params: event
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, field)| ScSpecEventParamV0 {
+                        doc: StringM::default(),
+                        name: field
+                            .id
+                            .as_ref()
+                            .map(|id| id.to_string())
+                            .unwrap_or_else(|| i.to_string())
+                            .try_into()
+                            .expect("event field name exceeds limit"),
+                        type_: spec_type_def(&field.ty, bin.ns)
+                            .expect("event field types are checked in sema"),
+                        location: if field.indexed {
+                            ScSpecEventParamLocationV0::TopicList
+                        } else {
+                            ScSpecEventParamLocationV0::Data
+                        },
+                    })
+                    .collect::<Vec<_>>()
+                    .try_into()
+                    .expect("event field count exceeds limit"),
+                data_format: ScSpecEventDataFormat::Vec,
+            })
+            .write_xdr(&mut spec)
+            .unwrap_or_else(|_| panic!("writing spec to xdr for event {name}"));
+
+            Self::add_custom_section(context, &bin.module, "contractspecv0", spec.inner);
+        }
+    }
+
+    // Structs and enums used by the public functions and events are described in the contract
+    // spec, so that generated clients can use typed values for them.
+    fn emit_udt_spec_entries<'a>(
+        contract: &'a ast::Contract,
+        bin: &mut Binary<'a>,
+        context: &'a Context,
+    ) {
+        let ns = bin.ns;
+        let mut types = Vec::new();
+
+        for cfg in contract.cfg.iter().filter(|cfg| cfg.public) {
+            for param in cfg.params.iter().chain(cfg.returns.iter()) {
+                collect_udts(&param.ty, ns, &mut types);
+            }
+        }
+
+        for event_no in &contract.emits_events {
+            for field in &ns.events[*event_no].fields {
+                collect_udts(&field.ty, ns, &mut types);
+            }
+        }
+
+        for ty in types {
+            let entry = match ty {
+                ast::Type::Struct(ast::StructType::UserDefined(struct_no)) => {
+                    let def = &ns.structs[struct_no];
+
+                    ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+                        doc: StringM::default(),
+                        lib: StringM::default(),
+                        name: def
+                            .id
+                            .name
+                            .clone()
+                            .try_into()
+                            .unwrap_or_else(|_| panic!("struct name {} exceeds limit", def.id)),
+                        fields: def
+                            .fields
+                            .iter()
+                            .map(|field| ScSpecUdtStructFieldV0 {
+                                doc: StringM::default(),
+                                name: field
+                                    .name_as_str()
+                                    .try_into()
+                                    .expect("struct field name exceeds limit"),
+                                type_: spec_type_def(&field.ty, ns).unwrap_or_else(|| {
+                                    panic!("unsupported struct field type {:?}", field.ty)
+                                }),
+                            })
+                            .collect::<Vec<_>>()
+                            .try_into()
+                            .expect("struct field count exceeds limit"),
+                    })
+                }
+                ast::Type::Enum(enum_no) => {
+                    let def = &ns.enums[enum_no];
+
+                    ScSpecEntry::UdtEnumV0(ScSpecUdtEnumV0 {
+                        doc: StringM::default(),
+                        lib: StringM::default(),
+                        name: def
+                            .id
+                            .name
+                            .clone()
+                            .try_into()
+                            .unwrap_or_else(|_| panic!("enum name {} exceeds limit", def.id)),
+                        cases: def
+                            .values
+                            .keys()
+                            .enumerate()
+                            .map(|(value, name)| ScSpecUdtEnumCaseV0 {
+                                doc: StringM::default(),
+                                name: name
+                                    .as_str()
+                                    .try_into()
+                                    .expect("enum case name exceeds limit"),
+                                value: value as u32,
+                            })
+                            .collect::<Vec<_>>()
+                            .try_into()
+                            .expect("enum case count exceeds limit"),
+                    })
+                }
+                _ => unreachable!(),
+            };
+
+            let mut spec = Limited::new(Vec::new(), Limits::none());
+            entry
+                .write_xdr(&mut spec)
+                .expect("writing type spec entry to xdr");
+
+            Self::add_custom_section(context, &bin.module, "contractspecv0", spec.inner);
+        }
+    }
+
+    // Custom errors fail with a contract error code. The codes of the errors the contract can see
+    // are described as a single error enum, so that clients can match on them.
+    fn emit_error_spec_entry<'a>(bin: &mut Binary<'a>, context: &'a Context, contract_no: usize) {
+        let ns = bin.ns;
+
+        let cases = ns.contracts[contract_no]
+            .custom_errors
+            .iter()
+            .map(|error_no| {
+                let err = &ns.errors[*error_no];
+
+                ScSpecUdtErrorEnumCaseV0 {
+                    doc: StringM::default(),
+                    name: err
+                        .name
+                        .as_str()
+                        .try_into()
+                        .unwrap_or_else(|_| panic!("error name {:?} exceeds limit", err.name)),
+                    value: soroban_error_code(contract_no, *error_no, ns),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if cases.is_empty() {
+            return;
+        }
+
+        let mut spec = Limited::new(Vec::new(), Limits::none());
+        ScSpecEntry::UdtErrorEnumV0(ScSpecUdtErrorEnumV0 {
+            doc: StringM::default(),
+            lib: StringM::default(),
+            name: "Error".try_into().unwrap(),
+            cases: cases.try_into().expect("error count exceeds limit"),
+        })
+        .write_xdr(&mut spec)
+        .expect("writing error spec entry to xdr");
+
+        Self::add_custom_section(context, &bin.module, "contractspecv0", spec.inner);
+    }
+
     fn add_custom_section<'a>(
         context: &'a Context,
         module: &Module<'a>,
@@ -334,6 +595,25 @@ impl SorobanTarget {
             .expect("adding spec as metadata");
     }
 
+    /// Soroban instantiates the contract for every invocation, so transient variables are kept in
+    /// a global which starts out zeroed each time. A zero value means the variable was not set.
+    fn declare_transient_storage(bin: &mut Binary, contract: &ast::Contract) {
+        if let Some(size) = contract.transient_layout_size.to_u32() {
+            if size == 0 {
+                return;
+            }
+
+            let ty = bin.context.i64_type().array_type(size);
+
+            let gv = bin
+                .module
+                .add_global(ty, Some(AddressSpace::default()), TRANSIENT_STORAGE);
+
+            gv.set_linkage(Linkage::Internal);
+            gv.set_initializer(&ty.const_zero());
+        }
+    }
+
     fn declare_externals(bin: &mut Binary) {
         let host_functions = [
             HostFunctions::PutContractData,
@@ -355,15 +635,51 @@ impl SorobanTarget {
             HostFunctions::ObjToU128Hi64,
             HostFunctions::ObjFromI128Pieces,
             HostFunctions::ObjFromU128Pieces,
+            HostFunctions::ObjToU256HiHi,
+            HostFunctions::ObjToU256HiLo,
+            HostFunctions::ObjToU256LoHi,
+            HostFunctions::ObjToU256LoLo,
+            HostFunctions::ObjToI256HiHi,
+            HostFunctions::ObjToI256HiLo,
+            HostFunctions::ObjToI256LoHi,
+            HostFunctions::ObjToI256LoLo,
+            HostFunctions::ObjFromU256Pieces,
+            HostFunctions::ObjFromI256Pieces,
             HostFunctions::RequireAuth,
             HostFunctions::AuthAsCurrContract,
             HostFunctions::MapNewFromLinearMemory,
             HostFunctions::MapNew,
             HostFunctions::MapPut,
+            HostFunctions::MapGet,
             HostFunctions::VecPushBack,
+            HostFunctions::VecPut,
+            HostFunctions::VecGet,
+            HostFunctions::VecLen,
+            HostFunctions::VecPopBack,
+            HostFunctions::VecBack,
+            HostFunctions::BytesNewFromLinearMemory,
+            HostFunctions::BytesNew,
+            HostFunctions::BytesPut,
+            HostFunctions::BytesGet,
+            HostFunctions::BytesLen,
+            HostFunctions::BytesPush,
+            HostFunctions::BytesPop,
+            HostFunctions::BytesBack,
             HostFunctions::StringNewFromLinearMemory,
             HostFunctions::StrKeyToAddr,
             HostFunctions::GetCurrentContractAddress,
+            HostFunctions::ContractEvent,
+            HostFunctions::FailWithError,
+            HostFunctions::GetLedgerSequence,
+            HostFunctions::GetLedgerTimestamp,
+            HostFunctions::GetLedgerNetworkId,
+            HostFunctions::ComputeHashSha256,
+            HostFunctions::ComputeHashKeccak256,
+            HostFunctions::VerifySigEd25519,
+            HostFunctions::RecoverKeyEcdsaSecp256k1,
+            HostFunctions::PrngU64InInclusiveRange,
+            HostFunctions::BytesCopyToLinearMemory,
+            HostFunctions::CreateContractWithConstructor,
         ];
 
         for func in &host_functions {
@@ -375,3 +691,61 @@ impl SorobanTarget {
         }
     }
 }
+
+/// Collect the structs and enums a type refers to, with the types of struct fields first
+fn collect_udts(ty: &ast::Type, ns: &ast::Namespace, types: &mut Vec<ast::Type>) {
+    let ty = if let ast::Type::Ref(ty) = ty {
+        ty.as_ref()
+    } else {
+        ty
+    };
+
+    if types.contains(ty) {
+        return;
+    }
+
+    match ty {
+        ast::Type::Struct(ast::StructType::UserDefined(struct_no)) => {
+            for field in &ns.structs[*struct_no].fields {
+                collect_udts(&field.ty, ns, types);
+            }
+
+            types.push(ty.clone());
+        }
+        ast::Type::Enum(_) => types.push(ty.clone()),
+        _ => (),
+    }
+}
+
+/// Map a Solidity type to its type in the contract spec
+fn spec_type_def(ty: &ast::Type, ns: &ast::Namespace) -> Option<ScSpecTypeDef> {
+    let ty = if let ast::Type::Ref(ty) = ty {
+        ty.as_ref()
+    } else {
+        ty
+    };
+
+    Some(match ty {
+        ast::Type::Uint(32) => ScSpecTypeDef::U32,
+        ast::Type::Int(32) => ScSpecTypeDef::I32,
+        ast::Type::Uint(64) => ScSpecTypeDef::U64,
+        ast::Type::Int(64) => ScSpecTypeDef::I64,
+        ast::Type::Int(128) => ScSpecTypeDef::I128,
+        ast::Type::Uint(128) => ScSpecTypeDef::U128,
+        ast::Type::Int(256) => ScSpecTypeDef::I256,
+        ast::Type::Uint(256) => ScSpecTypeDef::U256,
+        ast::Type::Bool => ScSpecTypeDef::Bool,
+        ast::Type::Address(_) => ScSpecTypeDef::Address,
+        ast::Type::Bytes(_) | ast::Type::DynamicBytes => ScSpecTypeDef::Bytes,
+        ast::Type::String => ScSpecTypeDef::String,
+        ast::Type::Struct(ast::StructType::UserDefined(struct_no)) => {
+            ScSpecTypeDef::Udt(ScSpecTypeUdt {
+                name: ns.structs[*struct_no].id.name.clone().try_into().ok()?,
+            })
+        }
+        ast::Type::Enum(enum_no) => ScSpecTypeDef::Udt(ScSpecTypeUdt {
+            name: ns.enums[*enum_no].id.name.clone().try_into().ok()?,
+        }),
+        _ => return None,
+    })
+}
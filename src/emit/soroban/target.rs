@@ -4,13 +4,14 @@ use crate::codegen::cfg::HashTy;
 use crate::codegen::Builtin;
 use crate::codegen::Expression;
 use crate::emit::binary::Binary;
-use crate::emit::soroban::{HostFunctions, SorobanTarget};
+use crate::emit::expression::expression;
+use crate::emit::soroban::{HostFunctions, SorobanTarget, TRANSIENT_STORAGE};
 use crate::emit::ContractArgs;
 use crate::emit::{TargetRuntime, Variable};
 use crate::emit_context;
 use crate::sema::ast;
 use crate::sema::ast::CallTy;
-use crate::sema::ast::{Function, Type};
+use crate::sema::ast::{ArrayLength, Function, Type};
 
 use inkwell::types::{BasicTypeEnum, IntType};
 use inkwell::values::{
@@ -21,6 +22,7 @@ use inkwell::values::{
 use solang_parser::pt::{Loc, StorageType};
 
 use num_traits::ToPrimitive;
+use sha2::{Digest, Sha256};
 
 use std::collections::HashMap;
 
@@ -45,9 +47,30 @@ impl<'a> TargetRuntime<'a> for SorobanTarget {
         function: FunctionValue<'a>,
         storage_type: &Option<StorageType>,
     ) -> BasicValueEnum<'a> {
-        let storage_type = storage_type_to_int(storage_type);
         emit_context!(bin);
 
+        if let Some(StorageType::Transient(_)) = storage_type {
+            let ptr = transient_slot(bin, *slot);
+
+            let value = bin
+                .builder
+                .build_load(bin.context.i64_type(), ptr, "transient")
+                .unwrap()
+                .into_int_value();
+
+            let is_set = bin
+                .builder
+                .build_int_compare(inkwell::IntPredicate::NE, value, i64_zero!(), "is_set")
+                .unwrap();
+
+            return bin
+                .builder
+                .build_select(is_set, value, type_to_tagged_zero_val(bin, ty), "transient")
+                .unwrap();
+        }
+
+        let storage_type = storage_type_to_int(storage_type);
+
         let slot = if slot.is_const() {
             slot.as_basic_value_enum()
                 .into_int_value()
@@ -99,7 +122,22 @@ impl<'a> TargetRuntime<'a> for SorobanTarget {
 
         // === ELSE block: return default ===
         bin.builder.position_at_end(else_bb);
-        let default_value = type_to_tagged_zero_val(bin, ty);
+        // Dynamic arrays and bytes are host objects, so start with an empty one
+        let default_value = match ty {
+            Type::DynamicBytes => call!(HostFunctions::BytesNew.name(), &[], "bytes_new")
+                .try_as_basic_value()
+                .left()
+                .unwrap()
+                .into_int_value(),
+            Type::Array(_, dims) if dims.last() == Some(&ArrayLength::Dynamic) => {
+                call!(HostFunctions::VectorNew.name(), &[], "vec_new")
+                    .try_as_basic_value()
+                    .left()
+                    .unwrap()
+                    .into_int_value()
+            }
+            _ => type_to_tagged_zero_val(bin, ty),
+        };
 
         bin.builder.build_unconditional_branch(merge_bb).unwrap();
 
@@ -127,6 +165,14 @@ impl<'a> TargetRuntime<'a> for SorobanTarget {
     ) {
         emit_context!(bin);
 
+        if let Some(StorageType::Transient(_)) = storage_type {
+            let ptr = transient_slot(bin, *slot);
+
+            bin.builder.build_store(ptr, dest).unwrap();
+
+            return;
+        }
+
         let storage_type = storage_type_to_int(storage_type);
 
         let function_value = bin
@@ -413,7 +459,51 @@ impl<'a> TargetRuntime<'a> for SorobanTarget {
         contract_args: ContractArgs<'b>,
         loc: Loc,
     ) {
-        unimplemented!()
+        emit_context!(bin);
+
+        let created_contract = &bin.ns.contracts[contract_no];
+
+        // The wasm of the created contract is not embedded, only its hash. The code must be
+        // uploaded to the network before the contract can be created.
+        let code = created_contract.emit(bin.ns, bin.options, contract_no);
+        let code_hash = bin.emit_global_string(
+            &format!("binary_{}_codehash", created_contract.id),
+            &Sha256::digest(&code),
+            true,
+        );
+        let code_hash = memory_to_bytes_object(bin, code_hash, 32);
+
+        let salt = int_to_bytes_object(bin, contract_args.salt.unwrap(), 32);
+
+        let deployer = call!(
+            HostFunctions::GetCurrentContractAddress.name(),
+            &[],
+            "deployer"
+        )
+        .try_as_basic_value()
+        .left()
+        .unwrap();
+
+        let created = call!(
+            HostFunctions::CreateContractWithConstructor.name(),
+            &[
+                deployer.into(),
+                code_hash.into(),
+                salt.into(),
+                encoded_args.into(),
+            ],
+            "created"
+        )
+        .try_as_basic_value()
+        .left()
+        .unwrap();
+
+        bin.builder.build_store(address, created).unwrap();
+
+        // A failed deployment traps, so there is nothing to catch
+        if let Some(success) = success {
+            *success = bin.context.bool_type().const_int(1, false).into();
+        }
     }
 
     /// call external function
@@ -691,6 +781,95 @@ impl<'a> TargetRuntime<'a> for SorobanTarget {
 
                 value.into()
             }
+            Expression::Builtin {
+                kind: hash @ (Builtin::Keccak256 | Builtin::Sha256),
+                args,
+                ..
+            } => {
+                let data = expression(self, bin, &args[0], vartab, function);
+
+                let name = if *hash == Builtin::Sha256 {
+                    HostFunctions::ComputeHashSha256.name()
+                } else {
+                    HostFunctions::ComputeHashKeccak256.name()
+                };
+
+                let digest = call!(name, &[data.into()], "hash")
+                    .try_as_basic_value()
+                    .left()
+                    .unwrap()
+                    .into_int_value();
+
+                bytes_object_to_int(bin, digest, 32).into()
+            }
+            Expression::Builtin {
+                kind: Builtin::Secp256k1Recover,
+                args,
+                ..
+            } => {
+                let digest = expression(self, bin, &args[0], vartab, function).into_int_value();
+                let digest = int_to_bytes_object(bin, digest, 32);
+                let signature = expression(self, bin, &args[1], vartab, function);
+                let recovery_id =
+                    expression(self, bin, &args[2], vartab, function).into_int_value();
+                let recovery_id = bin
+                    .builder
+                    .build_int_z_extend(recovery_id, bin.context.i64_type(), "recovery_id")
+                    .unwrap();
+
+                call!(
+                    HostFunctions::RecoverKeyEcdsaSecp256k1.name(),
+                    &[
+                        digest.into(),
+                        signature.into(),
+                        u32_val(bin, recovery_id).into()
+                    ],
+                    "public_key"
+                )
+                .try_as_basic_value()
+                .left()
+                .unwrap()
+            }
+            Expression::Builtin {
+                kind: Builtin::Timestamp,
+                ..
+            } => {
+                let timestamp = call!(HostFunctions::GetLedgerTimestamp.name(), &[], "timestamp")
+                    .try_as_basic_value()
+                    .left()
+                    .unwrap()
+                    .into_int_value();
+
+                decode_u64_val(bin, timestamp, function).into()
+            }
+            Expression::Builtin {
+                kind: Builtin::BlockNumber,
+                ..
+            } => {
+                let sequence = call!(HostFunctions::GetLedgerSequence.name(), &[], "sequence")
+                    .try_as_basic_value()
+                    .left()
+                    .unwrap()
+                    .into_int_value();
+
+                // the ledger sequence is a U32Val
+                bin.builder
+                    .build_right_shift(sequence, i64_const!(32), false, "sequence")
+                    .unwrap()
+                    .into()
+            }
+            Expression::Builtin {
+                kind: Builtin::NetworkId,
+                ..
+            } => {
+                let network_id = call!(HostFunctions::GetLedgerNetworkId.name(), &[], "network_id")
+                    .try_as_basic_value()
+                    .left()
+                    .unwrap()
+                    .into_int_value();
+
+                bytes_object_to_int(bin, network_id, 32).into()
+            }
             _ => unimplemented!("unsupported builtin"),
         }
     }
@@ -730,7 +909,14 @@ impl<'a> TargetRuntime<'a> for SorobanTarget {
         data: BasicValueEnum<'b>,
         topics: &[BasicValueEnum<'b>],
     ) {
-        unimplemented!()
+        emit_context!(bin);
+
+        // Codegen has already collected the topics into a single vector object
+        call!(
+            HostFunctions::ContractEvent.name(),
+            &[topics[0].into(), data.into()],
+            "contract_event"
+        );
     }
 
     /// Return ABI encoded data
@@ -750,12 +936,31 @@ fn storage_type_to_int(storage_type: &Option<StorageType>) -> u64 {
             StorageType::Temporary(_) => 0,
             StorageType::Persistent(_) => 1,
             StorageType::Instance(_) => 2,
+            StorageType::Transient(_) => unreachable!("transient storage is not a ledger entry"),
         }
     } else {
         1
     }
 }
 
+/// Pointer to the entry for a transient variable in the transient storage global
+fn transient_slot<'a>(bin: &Binary<'a>, slot: IntValue<'a>) -> PointerValue<'a> {
+    let storage = bin.module.get_global(TRANSIENT_STORAGE).unwrap();
+
+    let ty = storage.get_value_type().into_array_type();
+
+    unsafe {
+        bin.builder
+            .build_gep(
+                ty,
+                storage.as_pointer_value(),
+                &[bin.context.i64_type().const_zero(), slot],
+                "transient_slot",
+            )
+            .unwrap()
+    }
+}
+
 fn encode_value<'a>(value: IntValue<'a>, shift: u64, add: u64, bin: &'a Binary) -> IntValue<'a> {
     let shifted = bin
         .builder
@@ -774,6 +979,155 @@ fn encode_value<'a>(value: IntValue<'a>, shift: u64, add: u64, bin: &'a Binary)
         .unwrap()
 }
 
+/// Decode a U64Val, which is either small enough to fit in the Val or a host object
+fn decode_u64_val<'a>(
+    bin: &Binary<'a>,
+    val: IntValue<'a>,
+    function: FunctionValue<'a>,
+) -> IntValue<'a> {
+    emit_context!(bin);
+
+    let tag = bin.builder.build_and(val, i64_const!(0xff), "tag").unwrap();
+    // Tag::U64Small is 6
+    let is_small = bin
+        .builder
+        .build_int_compare(inkwell::IntPredicate::EQ, tag, i64_const!(6), "is_small")
+        .unwrap();
+
+    let small_bb = bin.context.append_basic_block(function, "u64_small");
+    let object_bb = bin.context.append_basic_block(function, "u64_object");
+    let merge_bb = bin.context.append_basic_block(function, "u64_decoded");
+
+    bin.builder
+        .build_conditional_branch(is_small, small_bb, object_bb)
+        .unwrap();
+
+    bin.builder.position_at_end(small_bb);
+    let small = bin
+        .builder
+        .build_right_shift(val, i64_const!(8), false, "small")
+        .unwrap();
+    bin.builder.build_unconditional_branch(merge_bb).unwrap();
+
+    bin.builder.position_at_end(object_bb);
+    let object = call!(HostFunctions::ObjToU64.name(), &[val.into()], "obj_to_u64")
+        .try_as_basic_value()
+        .left()
+        .unwrap();
+    bin.builder.build_unconditional_branch(merge_bb).unwrap();
+
+    bin.builder.position_at_end(merge_bb);
+    let phi = bin
+        .builder
+        .build_phi(bin.context.i64_type(), "u64")
+        .unwrap();
+    phi.add_incoming(&[(&small, small_bb), (&object, object_bb)]);
+
+    phi.as_basic_value().into_int_value()
+}
+
+/// Copy the contents of a bytes object into a `bytesN` value. The bytes are big endian, while
+/// `bytesN` values are stored little endian.
+fn bytes_object_to_int<'a>(bin: &Binary<'a>, object: IntValue<'a>, len: u32) -> IntValue<'a> {
+    emit_context!(bin);
+
+    let buf = bin
+        .builder
+        .build_array_alloca(bin.context.i8_type(), i32_const!(len as u64), "buf")
+        .unwrap();
+    let buf_pos = bin
+        .builder
+        .build_ptr_to_int(buf, bin.context.i64_type(), "buf_pos")
+        .unwrap();
+
+    call!(
+        HostFunctions::BytesCopyToLinearMemory.name(),
+        &[
+            object.into(),
+            u32_val(bin, i64_zero!()).into(),
+            u32_val(bin, buf_pos).into(),
+            u32_val(bin, i64_const!(len as u64)).into(),
+        ],
+        "bytes_copy"
+    );
+
+    let ty = bin.context.custom_width_int_type(len * 8);
+    let value = bin.builder.build_alloca(ty, "value").unwrap();
+
+    call!(
+        "__beNtoleN",
+        &[buf.into(), value.into(), i32_const!(len as u64).into()]
+    );
+
+    bin.builder
+        .build_load(ty, value, "value")
+        .unwrap()
+        .into_int_value()
+}
+
+/// Create a bytes object holding a `bytesN` value, in big endian order
+fn int_to_bytes_object<'a>(bin: &Binary<'a>, value: IntValue<'a>, len: u32) -> IntValue<'a> {
+    emit_context!(bin);
+
+    let temp = bin.builder.build_alloca(value.get_type(), "value").unwrap();
+    bin.builder.build_store(temp, value).unwrap();
+
+    let buf = bin
+        .builder
+        .build_array_alloca(bin.context.i8_type(), i32_const!(len as u64), "buf")
+        .unwrap();
+
+    call!(
+        "__leNtobeN",
+        &[temp.into(), buf.into(), i32_const!(len as u64).into()]
+    );
+
+    memory_to_bytes_object(bin, buf, len)
+}
+
+/// Create a Bytes object from `len` bytes of linear memory
+fn memory_to_bytes_object<'a>(bin: &Binary<'a>, buf: PointerValue<'a>, len: u32) -> IntValue<'a> {
+    emit_context!(bin);
+
+    let buf_pos = bin
+        .builder
+        .build_ptr_to_int(buf, bin.context.i64_type(), "buf_pos")
+        .unwrap();
+
+    call!(
+        HostFunctions::BytesNewFromLinearMemory.name(),
+        &[
+            u32_val(bin, buf_pos).into(),
+            u32_val(bin, i64_const!(len as u64)).into(),
+        ],
+        "bytes_new"
+    )
+    .try_as_basic_value()
+    .left()
+    .unwrap()
+    .into_int_value()
+}
+
+/// Encode a 64 bit value as a U32Val
+fn u32_val<'a>(bin: &Binary<'a>, value: IntValue<'a>) -> IntValue<'a> {
+    let shifted = bin
+        .builder
+        .build_left_shift(
+            value,
+            bin.context.i64_type().const_int(32, false),
+            "shifted",
+        )
+        .unwrap();
+
+    bin.builder
+        .build_int_add(
+            shifted,
+            bin.context.i64_type().const_int(4, false),
+            "u32_val",
+        )
+        .unwrap()
+}
+
 fn is_val_true<'ctx>(bin: &Binary<'ctx>, val: IntValue<'ctx>) -> IntValue<'ctx> {
     let tag_mask = bin.context.i64_type().const_int(0xff, false);
     let tag_true = bin.context.i64_type().const_int(1, false);
@@ -797,6 +1151,7 @@ pub fn type_to_tagged_zero_val<'ctx>(bin: &Binary<'ctx>, ty: &Type) -> IntValue<
     let tag = match ty {
         Type::Bool => 0,        // Tag::False
         Type::Uint(32) => 4,    // Tag::U32Val
+        Type::Enum(_) => 4,     // Tag::U32Val
         Type::Int(32) => 5,     // Tag::I32Val
         Type::Uint(64) => 6,    // Tag::U64Small
         Type::Int(64) => 7,     // Tag::I64Small
@@ -804,7 +1159,7 @@ pub fn type_to_tagged_zero_val<'ctx>(bin: &Binary<'ctx>, ty: &Type) -> IntValue<
         Type::Int(128) => 11,   // Tag::I128Small
         Type::Uint(256) => 12,  // Tag::U256Small
         Type::Int(256) => 13,   // Tag::I256Small
-        Type::String => 73,     // Tag::StringObject
+        Type::String => 14,     // Tag::SymbolSmall, strings are stored as symbols
         Type::Address(_) => 77, // Tag::AddressObject
         Type::Void => 2,        // Tag::Void
         _ => {
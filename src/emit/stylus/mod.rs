@@ -11,9 +11,10 @@ use inkwell::module::{Linkage, Module};
 use inkwell::values::{BasicMetadataValueEnum, BasicValue, FunctionValue, IntValue, PointerValue};
 use inkwell::AddressSpace;
 
-mod cargo_stylus;
+pub mod cargo_stylus;
 mod storage;
 mod target;
+pub mod validate;
 
 pub struct StylusTarget;
 
@@ -4,6 +4,7 @@
 
 use crate::emit::binary::Binary;
 use crate::emit::storage::StorageSlot;
+use crate::emit::stylus::target::{load_slot, store_slot};
 use crate::emit::stylus::StylusTarget;
 use crate::emit::TargetRuntime;
 use crate::emit_context;
@@ -25,7 +26,7 @@ impl StorageSlot for StylusTarget {
         storage_type: &Option<StorageType>,
     ) {
         emit_context!(bin);
-        if let Some(StorageType::Temporary(_)) = storage_type {
+        if let Some(StorageType::Transient(_)) = storage_type {
             call!("transient_store_bytes32", &[slot.into(), dest.into()]);
         } else {
             call!("storage_cache_bytes32", &[slot.into(), dest.into()]);
@@ -44,7 +45,7 @@ impl StorageSlot for StylusTarget {
             .builder
             .build_alloca(bin.value_type(), "slot_ptr")
             .unwrap();
-        if let Some(StorageType::Temporary(_)) = storage_type {
+        if let Some(StorageType::Transient(_)) = storage_type {
             call!("transient_load_bytes32", &[slot.into(), slot_ptr.into()]);
         } else {
             call!("storage_load_bytes32", &[slot.into(), slot_ptr.into()]);
@@ -195,11 +196,7 @@ impl StorageSlot for StylusTarget {
                         slot_ptr,
                     );
 
-                    let mut elem_slot = bin
-                        .builder
-                        .build_load(slot.get_type(), slot_ptr, "elem_slot")
-                        .unwrap()
-                        .into_int_value();
+                    let mut elem_slot = load_slot(bin, slot_ptr);
 
                     bin.emit_loop_cond_first_with_int(
                         function,
@@ -291,7 +288,7 @@ impl StorageSlot for StylusTarget {
                 new.into()
             }
             Type::String | Type::DynamicBytes => {
-                bin.builder.build_store(slot_ptr, *slot).unwrap();
+                store_slot(bin, slot_ptr, *slot);
 
                 let ret = self.get_storage_string(bin, function, slot_ptr);
 
@@ -303,7 +300,7 @@ impl StorageSlot for StylusTarget {
                 ret.into()
             }
             Type::InternalFunction { .. } => {
-                bin.builder.build_store(slot_ptr, *slot).unwrap();
+                store_slot(bin, slot_ptr, *slot);
 
                 let ptr_ty = bin
                     .context
@@ -317,7 +314,7 @@ impl StorageSlot for StylusTarget {
                     .into()
             }
             Type::ExternalFunction { .. } => {
-                bin.builder.build_store(slot_ptr, *slot).unwrap();
+                store_slot(bin, slot_ptr, *slot);
 
                 let ret = self.get_storage_extfunc(bin, function, slot_ptr);
 
@@ -329,7 +326,7 @@ impl StorageSlot for StylusTarget {
                 ret.into()
             }
             Type::Address(_) | Type::Contract(_) => {
-                bin.builder.build_store(slot_ptr, *slot).unwrap();
+                store_slot(bin, slot_ptr, *slot);
 
                 let ret = self.get_storage_address(bin, slot_ptr, storage_type);
 
@@ -345,7 +342,7 @@ impl StorageSlot for StylusTarget {
                 self.storage_load_slot(bin, ty, slot, slot_ptr, function, storage_type)
             }
             _ => {
-                bin.builder.build_store(slot_ptr, *slot).unwrap();
+                store_slot(bin, slot_ptr, *slot);
 
                 let ret = self.get_storage_type_int(
                     bin,
@@ -500,15 +497,7 @@ impl StorageSlot for StylusTarget {
                         new_slot,
                     );
 
-                    let mut elem_slot = bin
-                        .builder
-                        .build_load(
-                            bin.llvm_type(&slot_ty).into_int_type(),
-                            new_slot,
-                            "elem_slot",
-                        )
-                        .unwrap()
-                        .into_int_value();
+                    let mut elem_slot = load_slot(bin, new_slot);
 
                     bin.emit_loop_cond_first_with_int(
                         function,
@@ -649,12 +638,12 @@ impl StorageSlot for StylusTarget {
                 }
             }
             Type::String | Type::DynamicBytes => {
-                bin.builder.build_store(slot_ptr, *slot).unwrap();
+                store_slot(bin, slot_ptr, *slot);
 
                 self.set_storage_string(bin, function, slot_ptr, dest);
             }
             Type::ExternalFunction { .. } => {
-                bin.builder.build_store(slot_ptr, *slot).unwrap();
+                store_slot(bin, slot_ptr, *slot);
 
                 self.set_storage_extfunc(
                     bin,
@@ -680,13 +669,13 @@ impl StorageSlot for StylusTarget {
                     )
                     .unwrap();
 
-                bin.builder.build_store(slot_ptr, *slot).unwrap();
+                store_slot(bin, slot_ptr, *slot);
 
                 self.set_storage(bin, slot_ptr, m, ptr_ty.as_basic_type_enum(), storage_type);
             }
             Type::Address(_) | Type::Contract(_) => {
                 if dest.is_pointer_value() {
-                    bin.builder.build_store(slot_ptr, *slot).unwrap();
+                    store_slot(bin, slot_ptr, *slot);
 
                     self.set_storage(
                         bin,
@@ -705,7 +694,7 @@ impl StorageSlot for StylusTarget {
                         .build_store(address, dest.into_array_value())
                         .unwrap();
 
-                    bin.builder.build_store(slot_ptr, *slot).unwrap();
+                    store_slot(bin, slot_ptr, *slot);
 
                     self.set_storage(
                         bin,
@@ -723,7 +712,7 @@ impl StorageSlot for StylusTarget {
             _ => {
                 // dbg!(ty);
 
-                bin.builder.build_store(slot_ptr, *slot).unwrap();
+                store_slot(bin, slot_ptr, *slot);
 
                 let dest = if dest.is_int_value() {
                     let m = bin.build_alloca(function, dest.get_type(), "");
@@ -757,7 +746,7 @@ impl StorageSlot for StylusTarget {
         function: FunctionValue<'a>,
         storage_type: &Option<StorageType>,
     ) {
-        if let Some(StorageType::Temporary(_)) = storage_type {
+        if let Some(StorageType::Transient(_)) = storage_type {
             return;
         }
 
@@ -796,7 +785,7 @@ impl StorageSlot for StylusTarget {
                 } else {
                     // dynamic length array.
                     // load length
-                    bin.builder.build_store(slot_ptr, *slot).unwrap();
+                    store_slot(bin, slot_ptr, *slot);
 
                     let buf = bin.builder.build_alloca(bin.value_type(), "buf").unwrap();
 
@@ -819,11 +808,7 @@ impl StorageSlot for StylusTarget {
                         buf,
                     );
 
-                    let mut entry_slot = bin
-                        .builder
-                        .build_load(slot.get_type(), buf, "entry_slot")
-                        .unwrap()
-                        .into_int_value();
+                    let mut entry_slot = load_slot(bin, buf);
 
                     // now loop from first slot to first slot + length
                     bin.emit_loop_cond_first_with_int(
@@ -894,7 +879,7 @@ impl StorageSlot for StylusTarget {
                 // nothing to do, step over it
             }
             _ => {
-                bin.builder.build_store(slot_ptr, *slot).unwrap();
+                store_slot(bin, slot_ptr, *slot);
 
                 self.storage_delete_single_slot(bin, slot_ptr);
             }
@@ -4,6 +4,7 @@
 #![warn(clippy::renamed_function_params)]
 
 use crate::codegen::cfg::HashTy;
+use crate::codegen::revert::PanicCode;
 use crate::codegen::{Builtin, Expression};
 use crate::emit::binary::Binary;
 use crate::emit::storage::StorageSlot;
@@ -13,7 +14,7 @@ use crate::emit_context;
 use crate::sema::ast::{self, CallTy};
 use crate::sema::ast::{Function, Type};
 use ethers_core::utils::keccak256;
-use inkwell::types::{BasicTypeEnum, IntType};
+use inkwell::types::{BasicType, BasicTypeEnum, IntType};
 use inkwell::values::{
     ArrayValue, BasicMetadataValueEnum, BasicValue, BasicValueEnum, FunctionValue, IntValue,
     PointerValue,
@@ -36,7 +37,7 @@ impl<'a> StylusTarget {
         let value_ptr = bin.builder.build_alloca(ty, "value").unwrap();
 
         match storage_type {
-            Some(StorageType::Temporary(_)) => {
+            Some(StorageType::Transient(_)) => {
                 call!("transient_load_bytes32", &[slot.into(), value_ptr.into()]);
             }
             _ => {
@@ -49,6 +50,180 @@ impl<'a> StylusTarget {
             .unwrap()
             .into_int_value()
     }
+
+    /// Read the length of the storage bytes or string whose slot is stored at `slot_ptr`
+    fn storage_bytes_len(&self, bin: &Binary<'a>, slot_ptr: PointerValue<'a>) -> IntValue<'a> {
+        emit_context!(bin);
+
+        let len_ptr = bin
+            .builder
+            .build_alloca(bin.value_type(), "len_ptr")
+            .unwrap();
+        call!("storage_load_bytes32", &[slot_ptr.into(), len_ptr.into()]);
+        bin.builder
+            .build_load(bin.context.i32_type(), len_ptr, "len")
+            .unwrap()
+            .into_int_value()
+    }
+
+    /// Load the 32 byte chunk of a storage bytes or string which holds the byte at `index`.
+    /// Returns a pointer to the slot of the chunk, and a pointer to the chunk itself.
+    fn storage_bytes_chunk(
+        &self,
+        bin: &Binary<'a>,
+        slot_ptr: PointerValue<'a>,
+        index: IntValue<'a>,
+    ) -> (PointerValue<'a>, PointerValue<'a>) {
+        emit_context!(bin);
+
+        let i_chunk = bin
+            .builder
+            .build_int_unsigned_div(index, i32_const!(32), "i_chunk")
+            .unwrap();
+        let chunk_slot = bin
+            .builder
+            .build_int_add(
+                next_slot(bin, slot_ptr, 32),
+                bin.builder
+                    .build_int_z_extend(i_chunk, bin.value_type(), "i_chunk_as_u256")
+                    .unwrap(),
+                "chunk_slot",
+            )
+            .unwrap();
+
+        let chunk_slot_ptr = bin
+            .builder
+            .build_alloca(bin.value_type(), "chunk_slot")
+            .unwrap();
+        store_slot(bin, chunk_slot_ptr, chunk_slot);
+        let chunk_ptr = bin
+            .builder
+            .build_alloca(bin.value_type(), "chunk_ptr")
+            .unwrap();
+        call!(
+            "storage_load_bytes32",
+            &[chunk_slot_ptr.into(), chunk_ptr.into()]
+        );
+
+        (chunk_slot_ptr, chunk_ptr)
+    }
+
+    /// Hash mapping keys and slots the way Solidity does: strings and bytes are hashed as they
+    /// are, any other value as its 32 byte `abi.encode` word. The digest is returned as a slot
+    /// number.
+    pub(crate) fn storage_slot_hash(
+        &self,
+        bin: &Binary<'a>,
+        values: &[(BasicValueEnum<'a>, &Type)],
+    ) -> IntValue<'a> {
+        emit_context!(bin);
+
+        let mut length = i32_zero!();
+        let mut parts = Vec::new();
+
+        for (value, ty) in values {
+            let part = if matches!(ty, Type::String | Type::DynamicBytes) {
+                (bin.vector_bytes(*value), bin.vector_len(*value))
+            } else {
+                (self.abi_word(bin, ty, *value), i32_const!(32))
+            };
+
+            length = bin.builder.build_int_add(length, part.1, "length").unwrap();
+            parts.push(part);
+        }
+
+        let src = bin
+            .builder
+            .build_array_alloca(bin.context.i8_type(), length, "keccak_src")
+            .unwrap();
+        let mut offset = i32_zero!();
+
+        for (ptr, len) in parts {
+            call!(
+                "__memcpy",
+                &[
+                    ptr_plus_offset(bin, src, offset).into(),
+                    ptr.into(),
+                    len.into()
+                ]
+            );
+
+            offset = bin.builder.build_int_add(offset, len, "offset").unwrap();
+        }
+
+        let dst = bin
+            .builder
+            .build_alloca(bin.value_type(), "keccak_dst")
+            .unwrap();
+        self.keccak256_hash(bin, src, length, dst);
+
+        load_slot(bin, dst)
+    }
+
+    /// Write a value as `abi.encode` would: 32 big-endian bytes, with integers extended to
+    /// 256 bits, addresses left-padded and fixed length bytes right-padded.
+    fn abi_word(&self, bin: &Binary<'a>, ty: &Type, value: BasicValueEnum<'a>) -> PointerValue<'a> {
+        let ty = match ty {
+            Type::UserType(no) => &bin.ns.user_types[*no].ty,
+            Type::StorageRef(..) => &Type::Uint(256),
+            _ => ty,
+        };
+
+        let word_ptr = bin.builder.build_alloca(bin.value_type(), "word").unwrap();
+
+        if matches!(ty, Type::Address(_) | Type::Contract(_)) {
+            bin.builder
+                .build_store(word_ptr, bin.value_type().const_zero())
+                .unwrap();
+
+            let offset = bin
+                .context
+                .i32_type()
+                .const_int(32 - bin.ns.address_length as u64, false);
+            bin.builder
+                .build_store(ptr_plus_offset(bin, word_ptr, offset), value)
+                .unwrap();
+
+            return word_ptr;
+        }
+
+        let value = value.into_int_value();
+
+        let word = if ty.is_signed_int(bin.ns) {
+            bin.builder
+                .build_int_s_extend_or_bit_cast(value, bin.value_type(), "word")
+                .unwrap()
+        } else {
+            bin.builder
+                .build_int_z_extend_or_bit_cast(value, bin.value_type(), "word")
+                .unwrap()
+        };
+
+        // bytesN values are left-aligned
+        let word = if let Type::Bytes(n) = ty {
+            bin.builder
+                .build_left_shift(
+                    word,
+                    bin.value_type().const_int((32 - *n as u64) * 8, false),
+                    "word",
+                )
+                .unwrap()
+        } else {
+            word
+        };
+
+        let word = bin
+            .builder
+            .build_call(bin.llvm_bswap(256), &[word.into()], "word_be")
+            .unwrap()
+            .try_as_basic_value()
+            .left()
+            .unwrap();
+
+        bin.builder.build_store(word_ptr, word).unwrap();
+
+        word_ptr
+    }
 }
 
 impl<'a> TargetRuntime<'a> for StylusTarget {
@@ -186,9 +361,7 @@ impl<'a> TargetRuntime<'a> for StylusTarget {
                     .builder
                     .build_int_add(*slot, i_chunk_as_u256, "slot_plus_i_chunk")
                     .unwrap();
-                bin.builder
-                    .build_store(slot_ptr, slot_plus_i_chunk)
-                    .unwrap();
+                store_slot(bin, slot_ptr, slot_plus_i_chunk);
 
                 let offset = bin
                     .builder
@@ -276,9 +449,7 @@ impl<'a> TargetRuntime<'a> for StylusTarget {
                     .builder
                     .build_int_add(*slot, i_chunk_as_u256, "slot_plus_i_chunk")
                     .unwrap();
-                bin.builder
-                    .build_store(slot_ptr, slot_plus_i_chunk)
-                    .unwrap();
+                store_slot(bin, slot_ptr, slot_plus_i_chunk);
 
                 let chunk_ptr = bin
                     .builder
@@ -310,6 +481,7 @@ impl<'a> TargetRuntime<'a> for StylusTarget {
         .into_pointer_value()
     }
 
+    // An external function is a selector followed by an address, which fits in a single slot
     fn set_storage_extfunc(
         &self,
         bin: &Binary<'a>,
@@ -318,7 +490,23 @@ impl<'a> TargetRuntime<'a> for StylusTarget {
         dest: PointerValue,
         dest_ty: BasicTypeEnum,
     ) {
-        unimplemented!()
+        emit_context!(bin);
+
+        let value_ptr = bin
+            .builder
+            .build_alloca(bin.value_type(), "value_ptr")
+            .unwrap();
+        bin.builder
+            .build_store(value_ptr, bin.value_type().const_zero())
+            .unwrap();
+
+        let len = dest_ty
+            .size_of()
+            .unwrap()
+            .const_cast(bin.context.i32_type(), false);
+        call!("__memcpy", &[value_ptr.into(), dest.into(), len.into()]);
+
+        call!("storage_cache_bytes32", &[slot.into(), value_ptr.into()]);
     }
 
     fn get_storage_extfunc(
@@ -327,7 +515,25 @@ impl<'a> TargetRuntime<'a> for StylusTarget {
         function: FunctionValue,
         slot: PointerValue<'a>,
     ) -> PointerValue<'a> {
-        unimplemented!()
+        emit_context!(bin);
+
+        let value_ptr = bin
+            .builder
+            .build_alloca(bin.value_type(), "value_ptr")
+            .unwrap();
+        call!("storage_load_bytes32", &[slot.into(), value_ptr.into()]);
+
+        // This is the size of the external function struct
+        let len = i32_const!(bin.ns.address_length as u64 + 4);
+
+        let ef = call!("__malloc", &[len.into()])
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_pointer_value();
+        call!("__memcpy", &[ef.into(), value_ptr.into(), len.into()]);
+
+        ef
     }
 
     fn get_storage_bytes_subscript(
@@ -338,7 +544,52 @@ impl<'a> TargetRuntime<'a> for StylusTarget {
         index: IntValue<'a>,
         loc: Loc,
     ) -> IntValue<'a> {
-        unimplemented!()
+        emit_context!(bin);
+
+        let slot_ptr = bin.builder.build_alloca(slot.get_type(), "slot").unwrap();
+        store_slot(bin, slot_ptr, slot);
+
+        let len = self.storage_bytes_len(bin, slot_ptr);
+
+        // do bounds check on index
+        let in_range = bin
+            .builder
+            .build_int_compare(IntPredicate::ULT, index, len, "index_in_range")
+            .unwrap();
+
+        let retrieve_block = bin.context.append_basic_block(function, "in_range");
+        let bang_block = bin.context.append_basic_block(function, "bang_block");
+
+        bin.builder
+            .build_conditional_branch(in_range, retrieve_block, bang_block)
+            .unwrap();
+
+        bin.builder.position_at_end(bang_block);
+        bin.log_runtime_error(
+            self,
+            "storage array index out of bounds".to_string(),
+            Some(loc),
+        );
+        let (revert_out, revert_out_len) = bin.panic_data_const(PanicCode::ArrayIndexOob);
+        self.assert_failure(bin, revert_out, revert_out_len);
+
+        bin.builder.position_at_end(retrieve_block);
+
+        let (_, chunk_ptr) = self.storage_bytes_chunk(bin, slot_ptr, index);
+
+        let offset = bin
+            .builder
+            .build_int_unsigned_rem(index, i32_const!(32), "offset")
+            .unwrap();
+
+        bin.builder
+            .build_load(
+                bin.context.i8_type(),
+                ptr_plus_offset(bin, chunk_ptr, offset),
+                "value",
+            )
+            .unwrap()
+            .into_int_value()
     }
 
     fn set_storage_bytes_subscript(
@@ -356,7 +607,7 @@ impl<'a> TargetRuntime<'a> for StylusTarget {
             .builder
             .build_alloca(slot.get_type(), "len_slot_ptr")
             .unwrap();
-        bin.builder.build_store(len_slot_ptr, slot).unwrap();
+        store_slot(bin, len_slot_ptr, slot);
 
         // smoelius: Read length.
         let len_ptr = bin
@@ -392,9 +643,7 @@ impl<'a> TargetRuntime<'a> for StylusTarget {
             .builder
             .build_int_add(chunk_slot, i_chunk_as_u256, "slot_plus_i_chunk")
             .unwrap();
-        bin.builder
-            .build_store(chunk_slot_ptr, slot_plus_i_chunk)
-            .unwrap();
+        store_slot(bin, chunk_slot_ptr, slot_plus_i_chunk);
         let chunk_ptr = bin
             .builder
             .build_alloca(bin.value_type(), "chunk_ptr")
@@ -463,7 +712,41 @@ impl<'a> TargetRuntime<'a> for StylusTarget {
         slot: IntValue<'a>,
         index: BasicValueEnum<'a>,
     ) -> IntValue<'a> {
-        unimplemented!()
+        // Use the Solidity slot layout: mapping values live at keccak256(key, slot), array
+        // elements follow each other from keccak256(slot) for dynamic arrays, or from the slot
+        // itself for fixed length arrays.
+        if let Type::Mapping(ast::Mapping { key, .. }) = ty.deref_any() {
+            return self.storage_slot_hash(
+                bin,
+                &[(index, key.as_ref()), (slot.into(), &Type::Uint(256))],
+            );
+        }
+
+        let slot_ptr = bin.builder.build_alloca(slot.get_type(), "slot").unwrap();
+        store_slot(bin, slot_ptr, slot);
+
+        let elem_slots = bin.number_literal(256, &ty.storage_array_elem().storage_slots(bin.ns));
+
+        let base = if ty.array_length().is_some() {
+            slot
+        } else {
+            next_slot(bin, slot_ptr, 32)
+        };
+
+        let index = bin
+            .builder
+            .build_int_z_extend_or_bit_cast(index.into_int_value(), bin.value_type(), "index")
+            .unwrap();
+
+        bin.builder
+            .build_int_add(
+                base,
+                bin.builder
+                    .build_int_mul(index, elem_slots, "elem_offset")
+                    .unwrap(),
+                "elem_slot",
+            )
+            .unwrap()
     }
 
     fn storage_push(
@@ -480,7 +763,7 @@ impl<'a> TargetRuntime<'a> for StylusTarget {
 
         // smoelius: Read length.
         let slot_ptr = bin.builder.build_alloca(slot.get_type(), "slot").unwrap();
-        bin.builder.build_store(slot_ptr, slot).unwrap();
+        store_slot(bin, slot_ptr, slot);
         let len_ptr = bin
             .builder
             .build_alloca(bin.context.i32_type(), "len_ptr")
@@ -516,7 +799,7 @@ impl<'a> TargetRuntime<'a> for StylusTarget {
             .builder
             .build_alloca(slot.get_type(), "chunk_slot")
             .unwrap();
-        bin.builder.build_store(chunk_slot_ptr, chunk_slot).unwrap();
+        store_slot(bin, chunk_slot_ptr, chunk_slot);
         let chunk_ptr = bin
             .builder
             .build_alloca(bin.value_type(), "chunk_ptr")
@@ -574,7 +857,83 @@ impl<'a> TargetRuntime<'a> for StylusTarget {
         load: bool,
         loc: Loc,
     ) -> Option<BasicValueEnum<'a>> {
-        unimplemented!()
+        emit_context!(bin);
+
+        let slot_ptr = bin.builder.build_alloca(slot.get_type(), "slot").unwrap();
+        store_slot(bin, slot_ptr, slot);
+
+        let len = self.storage_bytes_len(bin, slot_ptr);
+
+        // do bounds check on index
+        let in_range = bin
+            .builder
+            .build_int_compare(IntPredicate::NE, i32_zero!(), len, "index_in_range")
+            .unwrap();
+
+        let retrieve_block = bin.context.append_basic_block(function, "in_range");
+        let bang_block = bin.context.append_basic_block(function, "bang_block");
+
+        bin.builder
+            .build_conditional_branch(in_range, retrieve_block, bang_block)
+            .unwrap();
+
+        bin.builder.position_at_end(bang_block);
+        bin.log_runtime_error(self, "pop from empty storage array".to_string(), Some(loc));
+        let (revert_out, revert_out_len) = bin.panic_data_const(PanicCode::EmptyArrayPop);
+        self.assert_failure(bin, revert_out, revert_out_len);
+
+        bin.builder.position_at_end(retrieve_block);
+
+        let new_len = bin
+            .builder
+            .build_int_sub(len, i32_const!(1), "new_len")
+            .unwrap();
+
+        // smoelius: Read the last byte and clear it from its chunk.
+        let (chunk_slot_ptr, chunk_ptr) = self.storage_bytes_chunk(bin, slot_ptr, new_len);
+
+        let offset = bin
+            .builder
+            .build_int_unsigned_rem(new_len, i32_const!(32), "offset")
+            .unwrap();
+        let byte_ptr = ptr_plus_offset(bin, chunk_ptr, offset);
+
+        let val = if load {
+            Some(
+                bin.builder
+                    .build_load(bin.llvm_type(ty), byte_ptr, "popped_value")
+                    .unwrap(),
+            )
+        } else {
+            None
+        };
+
+        bin.builder
+            .build_store(byte_ptr, bin.context.i8_type().const_zero())
+            .unwrap();
+        call!(
+            "storage_cache_bytes32",
+            &[chunk_slot_ptr.into(), chunk_ptr.into()]
+        );
+
+        // smoelius: Write updated length to storage.
+        let len_ptr = bin
+            .builder
+            .build_alloca(bin.value_type(), "len_ptr")
+            .unwrap();
+        bin.builder
+            .build_store(
+                len_ptr,
+                bin.builder
+                    .build_int_z_extend(new_len, bin.value_type(), "new_len")
+                    .unwrap(),
+            )
+            .unwrap();
+        call!("storage_cache_bytes32", &[slot_ptr.into(), len_ptr.into()]);
+
+        call!("storage_flush_cache", &[i32_const!(1).into()]);
+
+        val
     }
 
     fn storage_array_length(
@@ -587,7 +946,7 @@ impl<'a> TargetRuntime<'a> for StylusTarget {
         emit_context!(bin);
 
         let slot_ptr = bin.builder.build_alloca(slot.get_type(), "slot").unwrap();
-        bin.builder.build_store(slot_ptr, slot).unwrap();
+        store_slot(bin, slot_ptr, slot);
 
         let len_ptr = bin
             .builder
@@ -1031,14 +1390,68 @@ impl<'a> TargetRuntime<'a> for StylusTarget {
     /// send value to address
     fn value_transfer<'b>(
         &self,
-        _bin: &Binary<'b>,
+        bin: &Binary<'b>,
         _function: FunctionValue,
-        _success: Option<&mut BasicValueEnum<'b>>,
-        _address: PointerValue<'b>,
-        _value: IntValue<'b>,
-        loc: Loc,
+        success: Option<&mut BasicValueEnum<'b>>,
+        address: PointerValue<'b>,
+        value: IntValue<'b>,
+        _loc: Loc,
     ) {
-        unimplemented!()
+        emit_context!(bin);
+
+        let return_data_len = bin
+            .builder
+            .build_alloca(bin.llvm_type(&ast::Type::Uint(32)), "return_data_len")
+            .unwrap();
+
+        let value_ptr = bin.builder.build_alloca(bin.value_type(), "value").unwrap();
+        bin.builder.build_store(value_ptr, value).unwrap();
+
+        // smoelius: Value is little-endian and must be byte-swapped.
+        let value_be = bin.builder.build_alloca(bin.value_type(), "value").unwrap();
+        call!(
+            "__leNtobeN",
+            &[
+                value_ptr.into(),
+                value_be.into(),
+                i32_const!(bin.ns.value_length as u64).into()
+            ]
+        );
+
+        // A transfer is a call with empty calldata, which only gets the 2300 gas stipend like
+        // `transfer` and `send` in Solidity.
+        let status = call!(
+            "call_contract",
+            &[
+                address.into(),
+                ptr!().const_null().into(),
+                i32_zero!().into(),
+                value_be.into(),
+                bin.context.i64_type().const_int(2300, false).into(),
+                return_data_len.into()
+            ],
+            "value_transfer"
+        )
+        .try_as_basic_value()
+        .left()
+        .unwrap()
+        .into_int_value();
+
+        let temp = bin
+            .builder
+            .build_load(bin.context.i32_type(), return_data_len, "return_data_len")
+            .unwrap();
+        bin.builder
+            .build_store(bin.return_data_len.unwrap().as_pointer_value(), temp)
+            .unwrap();
+
+        // The status is nonzero on failure, which matches the return code convention of
+        // `check_transfer_ret`.
+        *success.unwrap() = bin
+            .builder
+            .build_int_z_extend(status, bin.context.i32_type(), "success")
+            .unwrap()
+            .into();
     }
 
     /// builtin expressions
@@ -1634,6 +2047,7 @@ impl<'a> TargetRuntime<'a> for StylusTarget {
 }
 
 use local::{gas_calculation, next_slot, ptr_plus_offset, status_inverted};
+pub(super) use local::{load_slot, store_slot};
 
 mod local {
     #![warn(unused_variables)]
@@ -1699,8 +2113,35 @@ mod local {
             ]
         );
 
+        load_slot(bin, digest_ptr)
+    }
+
+    /// Slots are numbered as in Solidity, but the storage hostios and keccak256 see them as
+    /// 32 big-endian bytes. Store the slot number in that form.
+    pub fn store_slot<'a>(bin: &Binary<'a>, slot_ptr: PointerValue<'a>, slot: IntValue<'a>) {
+        let slot = bin
+            .builder
+            .build_call(bin.llvm_bswap(256), &[slot.into()], "slot_be")
+            .unwrap()
+            .try_as_basic_value()
+            .left()
+            .unwrap();
+
+        bin.builder.build_store(slot_ptr, slot).unwrap();
+    }
+
+    /// Load a slot number from 32 big-endian bytes, e.g. a keccak256 digest
+    pub fn load_slot<'a>(bin: &Binary<'a>, slot_ptr: PointerValue<'a>) -> IntValue<'a> {
+        let slot = bin
+            .builder
+            .build_load(bin.value_type(), slot_ptr, "slot_be")
+            .unwrap();
+
         bin.builder
-            .build_load(ty, digest_ptr, "digest")
+            .build_call(bin.llvm_bswap(256), &[slot.into()], "slot")
+            .unwrap()
+            .try_as_basic_value()
+            .left()
             .unwrap()
             .into_int_value()
     }
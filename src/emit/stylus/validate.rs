@@ -0,0 +1,324 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Offline checks of a linked Stylus program against the rules enforced on activation, so that
+//! problems are reported before the program is deployed.
+
+use super::cargo_stylus::compress_wasm;
+use wasmparser::{
+    CompositeType, ExternalKind, FuncType, Parser, Payload, TypeRef, ValType, Validator,
+    WasmFeatures,
+};
+
+/// Maximum size of the compressed program, including the EOF prefix
+pub const MAX_CODE_SIZE: usize = 24 * 1024;
+
+/// Maximum size of the uncompressed program
+pub const MAX_WASM_SIZE: usize = 128 * 1024;
+
+/// Host functions provided in the `vm_hooks` module
+const VM_HOOKS: &[&str] = &[
+    "account_balance",
+    "account_code",
+    "account_code_size",
+    "account_codehash",
+    "block_basefee",
+    "block_coinbase",
+    "block_gas_limit",
+    "block_number",
+    "block_timestamp",
+    "call_contract",
+    "chainid",
+    "contract_address",
+    "create1",
+    "create2",
+    "delegate_call_contract",
+    "emit_log",
+    "evm_gas_left",
+    "evm_ink_left",
+    "exit_early",
+    "math_add_mod",
+    "math_div",
+    "math_mod",
+    "math_mul_mod",
+    "math_pow",
+    "msg_reentrant",
+    "msg_sender",
+    "msg_value",
+    "native_keccak256",
+    "pay_for_memory_grow",
+    "read_args",
+    "read_return_data",
+    "return_data_size",
+    "static_call_contract",
+    "storage_cache_bytes32",
+    "storage_flush_cache",
+    "storage_load_bytes32",
+    "transient_load_bytes32",
+    "transient_store_bytes32",
+    "tx_gas_price",
+    "tx_ink_price",
+    "tx_origin",
+    "write_result",
+];
+
+/// Host functions provided in the `console` module, which only exists on chains in debug mode
+const CONSOLE: &[&str] = &[
+    "log_f32", "log_f64", "log_i32", "log_i64", "log_txt", "tee_f32", "tee_f64", "tee_i32",
+    "tee_i64",
+];
+
+/// The wasm features Stylus accepts. Floating point, SIMD and threads are not deterministic
+/// enough, or not supported by the prover.
+fn stylus_features() -> WasmFeatures {
+    WasmFeatures {
+        mutable_global: true,
+        saturating_float_to_int: true,
+        sign_extension: true,
+        reference_types: false,
+        multi_value: true,
+        bulk_memory: true,
+        simd: false,
+        relaxed_simd: false,
+        threads: false,
+        tail_call: false,
+        floats: false,
+        multi_memory: false,
+        exceptions: false,
+        memory64: false,
+        extended_const: false,
+        component_model: false,
+        function_references: false,
+        memory_control: false,
+        gc: false,
+        component_model_values: false,
+        component_model_nested_names: false,
+    }
+}
+
+/// Check a linked Stylus program. On failure, every problem found is returned as a message.
+pub fn validate(wasm: &[u8]) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+
+    if wasm.len() > MAX_WASM_SIZE {
+        errors.push(format!(
+            "program is {} bytes, which exceeds the limit of {MAX_WASM_SIZE} bytes",
+            wasm.len()
+        ));
+    }
+
+    match compress_wasm(wasm) {
+        Ok((_, code)) if code.len() > MAX_CODE_SIZE => errors.push(format!(
+            "compressed program is {} bytes, which exceeds the limit of {MAX_CODE_SIZE} bytes",
+            code.len()
+        )),
+        Ok(_) => (),
+        Err(err) => errors.push(format!("{err:#}")),
+    }
+
+    if let Err(err) = Validator::new_with_features(stylus_features()).validate_all(wasm) {
+        errors.push(format!("program is not valid for Stylus: {err}"));
+    }
+
+    if let Err(err) = check_module(wasm, &mut errors) {
+        errors.push(format!("cannot parse program: {err}"));
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Check the imports, memories and exports of the program
+fn check_module(wasm: &[u8], errors: &mut Vec<String>) -> wasmparser::Result<()> {
+    let mut types = Vec::new();
+    let mut functions = Vec::new();
+    let mut memories = 0;
+    let mut entrypoint = None;
+    let mut memory_exported = false;
+
+    for payload in Parser::new(0).parse_all(wasm) {
+        match payload? {
+            Payload::TypeSection(s) => {
+                for rec_group in s {
+                    for ty in rec_group?.into_types() {
+                        types.push(match ty.composite_type {
+                            CompositeType::Func(f) => Some(f),
+                            _ => None,
+                        });
+                    }
+                }
+            }
+            Payload::ImportSection(s) => {
+                for import in s {
+                    let import = import?;
+                    let allowed = match import.module {
+                        "vm_hooks" => VM_HOOKS,
+                        "console" => CONSOLE,
+                        _ => &[],
+                    };
+
+                    match import.ty {
+                        TypeRef::Func(ty) if allowed.contains(&import.name) => functions.push(ty),
+                        TypeRef::Func(ty) => {
+                            errors.push(format!(
+                                "import '{}.{}' is not provided by Stylus",
+                                import.module, import.name
+                            ));
+                            functions.push(ty);
+                        }
+                        _ => errors.push(format!(
+                            "import '{}.{}' is not a function; only host functions may be imported",
+                            import.module, import.name
+                        )),
+                    }
+                }
+            }
+            Payload::FunctionSection(s) => {
+                for ty in s {
+                    functions.push(ty?);
+                }
+            }
+            Payload::MemorySection(s) => memories += s.count(),
+            Payload::ExportSection(s) => {
+                for export in s {
+                    let export = export?;
+
+                    match (export.name, export.kind) {
+                        ("user_entrypoint", ExternalKind::Func) => entrypoint = Some(export.index),
+                        ("memory", ExternalKind::Memory) => memory_exported = true,
+                        _ => (),
+                    }
+                }
+            }
+            Payload::StartSection { .. } => {
+                errors.push("program must not have a start function".to_string())
+            }
+            _ => (),
+        }
+    }
+
+    if memories != 1 {
+        errors.push(format!(
+            "program must define exactly one memory, found {memories}"
+        ));
+    } else if !memory_exported {
+        errors.push("program must export its memory as 'memory'".to_string());
+    }
+
+    match entrypoint {
+        Some(index) => {
+            let ty = functions
+                .get(index as usize)
+                .and_then(|ty| types.get(*ty as usize))
+                .and_then(Option::as_ref);
+
+            if !ty.is_some_and(is_entrypoint_type) {
+                errors.push("'user_entrypoint' must have the signature (i32) -> i32".to_string());
+            }
+        }
+        None => errors.push("program does not export 'user_entrypoint'".to_string()),
+    }
+
+    Ok(())
+}
+
+fn is_entrypoint_type(ty: &FuncType) -> bool {
+    ty.params() == [ValType::I32] && ty.results() == [ValType::I32]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate;
+    use wasm_encoder::{
+        CodeSection, EntityType, ExportKind, ExportSection, Function, FunctionSection,
+        ImportSection, Instruction, MemorySection, MemoryType, Module, TypeSection, ValType,
+    };
+
+    /// A program which imports the given function and exports an entrypoint which returns 0
+    fn program(import: (&str, &str), entrypoint_ty: (Vec<ValType>, Vec<ValType>)) -> Vec<u8> {
+        let mut module = Module::new();
+
+        let mut types = TypeSection::new();
+        types.function([ValType::I32], []);
+        types.function(entrypoint_ty.0, entrypoint_ty.1.clone());
+        module.section(&types);
+
+        let mut imports = ImportSection::new();
+        imports.import(import.0, import.1, EntityType::Function(0));
+        module.section(&imports);
+
+        let mut functions = FunctionSection::new();
+        functions.function(1);
+        module.section(&functions);
+
+        let mut memories = MemorySection::new();
+        memories.memory(MemoryType {
+            minimum: 1,
+            maximum: None,
+            memory64: false,
+            shared: false,
+        });
+        module.section(&memories);
+
+        let mut exports = ExportSection::new();
+        exports.export("memory", ExportKind::Memory, 0);
+        exports.export("user_entrypoint", ExportKind::Func, 1);
+        module.section(&exports);
+
+        let mut code = CodeSection::new();
+        let mut body = Function::new([]);
+        for ty in &entrypoint_ty.1 {
+            body.instruction(&match ty {
+                ValType::F32 => Instruction::F32Const(0.0),
+                _ => Instruction::I32Const(0),
+            });
+        }
+        body.instruction(&Instruction::End);
+        code.function(&body);
+        module.section(&code);
+
+        module.finish()
+    }
+
+    #[test]
+    fn valid_program() {
+        let wasm = program(
+            ("vm_hooks", "read_args"),
+            (vec![ValType::I32], vec![ValType::I32]),
+        );
+
+        assert_eq!(validate(&wasm), Ok(()));
+    }
+
+    #[test]
+    fn unknown_import() {
+        let wasm = program(
+            ("env", "read_args"),
+            (vec![ValType::I32], vec![ValType::I32]),
+        );
+
+        assert_eq!(
+            validate(&wasm),
+            Err(vec![
+                "import 'env.read_args' is not provided by Stylus".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn floats_and_entrypoint_type() {
+        let wasm = program(("vm_hooks", "read_args"), (vec![], vec![ValType::F32]));
+
+        let errors = validate(&wasm).unwrap_err();
+
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0]
+            .starts_with("program is not valid for Stylus: floating-point support is disabled"));
+        assert_eq!(
+            errors[1],
+            "'user_entrypoint' must have the signature (i32) -> i32"
+        );
+    }
+}
@@ -1,6 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::sema::ast;
+use crate::Target;
 use itertools::Itertools;
 use normalize_path::NormalizePath;
 use solang_parser::pt::Loc;
@@ -11,6 +12,14 @@ use std::io::prelude::*;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+/// Libraries which ship with the compiler. These can be imported by name when compiling for their
+/// target, unless a file by the same name is found first.
+const BUILTIN_LIBRARIES: &[(&str, Target, &str)] = &[(
+    "soroban/token.sol",
+    Target::Soroban,
+    include_str!("../soroban-library/token.sol"),
+)];
+
 #[derive(Default)]
 pub struct FileResolver {
     /// Set of import paths search for imports
@@ -182,6 +191,7 @@ impl FileResolver {
         &mut self,
         parent: Option<&ResolvedFile>,
         filename: &OsStr,
+        target: Target,
     ) -> Result<ResolvedFile, String> {
         let path_filename = PathBuf::from(filename);
 
@@ -253,7 +263,7 @@ impl FileResolver {
         }
 
         match result.len() {
-            0 => Err(format!("file not found '{}'", path_filename.display())),
+            0 => self.builtin_library(&path_filename, target),
             1 => Ok(result.pop().unwrap()),
             _ => Err(format!(
                 "found multiple files matching '{}': {}",
@@ -266,6 +276,30 @@ impl FileResolver {
         }
     }
 
+    /// Load a library which ships with the compiler into the cache
+    fn builtin_library(&mut self, path: &Path, target: Target) -> Result<ResolvedFile, String> {
+        let Some((name, library_target, contents)) = BUILTIN_LIBRARIES
+            .iter()
+            .find(|(name, ..)| path == Path::new(name))
+        else {
+            return Err(format!("file not found '{}'", path.display()));
+        };
+
+        if *library_target != target {
+            return Err(format!(
+                "'{}' is only available when compiling for {}",
+                path.display(),
+                library_target
+            ));
+        }
+
+        if !self.cached_paths.contains_key(path) {
+            self.set_file_contents(name, contents.to_string());
+        }
+
+        Ok(self.files[self.cached_paths[path]].clone())
+    }
+
     /// Get line and the target symbol's offset from loc
     pub fn get_line_and_offset_from_loc(
         &self,
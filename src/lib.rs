@@ -4,6 +4,11 @@ pub mod abi;
 pub mod codegen;
 #[cfg(feature = "llvm")]
 pub mod emit;
+/// EVM bytecode is generated without LLVM, so the EVM backend is available without it
+#[cfg(not(feature = "llvm"))]
+pub mod emit {
+    pub mod evm;
+}
 pub mod file_resolver;
 #[cfg(feature = "llvm")]
 mod linker;
@@ -34,6 +39,9 @@ pub enum Target {
     EVM,
     Soroban,
     Stylus,
+    /// Polkadot Hub with the `revive` pallet, which runs PolkaVM (RISC-V) binaries, see
+    /// <https://contracts.polkadot.io/>
+    PolkaVM,
 }
 
 impl fmt::Display for Target {
@@ -44,6 +52,7 @@ impl fmt::Display for Target {
             Target::EVM => write!(f, "EVM"),
             Target::Soroban => write!(f, "Soroban"),
             Target::Stylus => write!(f, "Stylus"),
+            Target::PolkaVM => write!(f, "PolkaVM"),
         }
     }
 }
@@ -58,6 +67,7 @@ impl PartialEq for Target {
             Target::EVM => matches!(other, Target::EVM),
             Target::Soroban => matches!(other, Target::Soroban),
             Target::Stylus => matches!(other, Target::Stylus),
+            Target::PolkaVM => matches!(other, Target::PolkaVM),
         }
     }
 }
@@ -72,6 +82,11 @@ impl Target {
         matches!(self, Target::Polkadot { .. } | Target::Stylus)
     }
 
+    /// Does the target encode calls, return values and events as Ethereum ABI tuples
+    pub fn is_ethereum_abi(&self) -> bool {
+        matches!(self, Target::EVM | Target::PolkaVM)
+    }
+
     /// Create the target Polkadot with default parameters
     pub const fn default_polkadot() -> Self {
         Target::Polkadot {
@@ -87,6 +102,7 @@ impl Target {
             "polkadot" => Some(Target::default_polkadot()),
             "evm" => Some(Target::EVM),
             "stylus" => Some(Target::Stylus),
+            "polkavm" => Some(Target::PolkaVM),
             _ => None,
         }
     }
@@ -96,6 +112,10 @@ impl Target {
         match self {
             // Solana uses ELF dynamic shared object (BPF)
             Target::Solana => "so",
+            // EVM bytecode is written as hex, like solc does
+            Target::EVM => "bin",
+            // PolkaVM program blob
+            Target::PolkaVM => "polkavm",
             // Everything else generates webassembly
             _ => "wasm",
         }
@@ -106,6 +126,8 @@ impl Target {
         match *self {
             // Solana is BPF, which is 64 bit
             Target::Solana => 64,
+            // PolkaVM is 64 bit RISC-V
+            Target::PolkaVM => 64,
             // All others are WebAssembly in 32 bit mode
             _ => 32,
         }
@@ -150,12 +172,29 @@ pub fn compile(
 
     // emit the contracts
     let mut results = Vec::new();
+    let mut errors = Vec::new();
 
     for contract_no in 0..ns.contracts.len() {
         let contract = &ns.contracts[contract_no];
 
         if contract.instantiable {
-            let code = contract.emit(&ns, opts, contract_no);
+            let code = if target == Target::EVM {
+                match emit::evm::build(contract_no, &ns, opts) {
+                    Ok(code) => code.creation,
+                    Err(mut diagnostics) => {
+                        errors.append(&mut diagnostics);
+                        continue;
+                    }
+                }
+            } else {
+                match contract.try_emit(&ns, opts, contract_no) {
+                    Ok(code) => code,
+                    Err(message) => {
+                        errors.push(sema::ast::Diagnostic::error(contract.loc, message));
+                        continue;
+                    }
+                }
+            };
 
             let (abistr, _) = abi::generate_abi(contract_no, &ns, &code, false, &authors, version);
 
@@ -163,6 +202,13 @@ pub fn compile(
         };
     }
 
+    if !errors.is_empty() {
+        ns.diagnostics.append(&mut errors);
+        ns.diagnostics.sort_and_dedup();
+
+        return (Vec::new(), ns);
+    }
+
     (results, ns)
 }
 
@@ -193,7 +239,7 @@ pub fn parse_and_resolve_with_options(
         ns.strict_soroban_types = opts.strict_soroban_types;
     }
 
-    match resolver.resolve_file(None, filename) {
+    match resolver.resolve_file(None, filename, target) {
         Err(message) => {
             ns.diagnostics.push(sema::ast::Diagnostic {
                 ty: sema::ast::ErrorType::ParserError,
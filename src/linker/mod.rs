@@ -2,6 +2,7 @@
 
 mod bpf;
 mod polkadot_wasm;
+mod polkavm;
 mod soroban_wasm;
 mod stylus_wasm;
 use crate::Target;
@@ -12,19 +13,20 @@ use std::sync::Mutex;
 static LINKER_MUTEX: Lazy<Mutex<i32>> = Lazy::new(|| Mutex::new(0i32));
 
 /// Take an object file and turn it into a final linked binary ready for deployment
-pub fn link(input: &[u8], name: &str, target: Target) -> Vec<u8> {
+pub fn link(input: &[u8], name: &str, target: Target) -> Result<Vec<u8>, String> {
     // The lld linker is totally not thread-safe; it uses many globals
     // We should fix this one day
     let _lock = LINKER_MUTEX.lock().unwrap();
 
     match target {
-        Target::Solana => bpf::link(input, name),
-        Target::Soroban => soroban_wasm::link(input, name),
+        Target::Solana => Ok(bpf::link(input, name)),
+        Target::Soroban => Ok(soroban_wasm::link(input, name)),
         Target::Polkadot {
             address_length: _,
             value_length: _,
-        } => polkadot_wasm::link(input, name),
-        Target::Stylus => stylus_wasm::link(input, name),
+        } => Ok(polkadot_wasm::link(input, name)),
+        Target::Stylus => Ok(stylus_wasm::link(input, name)),
+        Target::PolkaVM => polkavm::link(input, name),
         _ => panic!("linker not implemented for target {target:?}"),
     }
 }
@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use polkavm_linker::{program_from_elf, Config};
+
+/// Link the RISC-V object file into a PolkaVM program blob. Unlike the other targets, this does
+/// not need lld: the polkavm linker reads the relocatable object file directly.
+pub fn link(input: &[u8], name: &str) -> Result<Vec<u8>, String> {
+    let mut config = Config::default();
+
+    // The default of 8KiB is too small for contracts with large stack frames
+    config.set_min_stack_size(32 * 1024);
+
+    program_from_elf(config, input)
+        .map_err(|err| format!("failed to link PolkaVM program {name}: {err}"))
+}
@@ -19,6 +19,8 @@ impl Converter<'_> {
             ast::Type::Bool => Type::Bool,
             ast::Type::Int(width) => Type::Int(*width),
             ast::Type::Uint(width) => Type::Uint(*width),
+            ast::Type::Fixed(width, _) => Type::Int(*width),
+            ast::Type::Ufixed(width, _) => Type::Uint(*width),
             ast::Type::Value => Type::Uint(self.value_length() as u16 * 8),
             ast::Type::Address(_) | ast::Type::Contract(_) => Type::Array(
                 Box::new(Type::Uint(8)),
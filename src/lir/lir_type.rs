@@ -15,6 +15,9 @@ pub enum StructType {
     SolAccountInfo,
     SolAccountMeta,
     SolParameters,
+    SolRent,
+    SolEpochSchedule,
+    SolInstruction,
     ExternalFunction,
     /// Vector is used here to represent String and DynamicBytes
     Vector(Box<Type>),
@@ -73,6 +76,9 @@ impl From<&ast::StructType> for StructType {
             ast::StructType::AccountMeta => StructType::SolAccountMeta,
             ast::StructType::ExternalFunction => StructType::ExternalFunction,
             ast::StructType::SolParameters => StructType::SolParameters,
+            ast::StructType::Rent => StructType::SolRent,
+            ast::StructType::EpochSchedule => StructType::SolEpochSchedule,
+            ast::StructType::Instruction => StructType::SolInstruction,
             ast::StructType::UserDefined(i) => StructType::UserDefined(*i),
         }
     }
@@ -86,6 +92,9 @@ impl fmt::Display for StructType {
             StructType::SolAccountMeta => write!(f, "SolAccountMeta"),
             StructType::ExternalFunction => write!(f, "ExternalFunction"),
             StructType::SolParameters => write!(f, "SolParameters"),
+            StructType::SolRent => write!(f, "SolRent"),
+            StructType::SolEpochSchedule => write!(f, "SolEpochSchedule"),
+            StructType::SolInstruction => write!(f, "SolInstruction"),
             StructType::Vector(elem_ty) => write!(f, "vector<{elem_ty}>"),
         }
     }
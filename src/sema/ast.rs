@@ -32,6 +32,10 @@ pub enum Type {
     Bool,
     Int(u16),
     Uint(u16),
+    /// Fixed point number `fixed<M>x<N>`, represented as a `int<M>` scaled by 10^N
+    Fixed(u16, u8),
+    /// Fixed point number `ufixed<M>x<N>`, represented as a `uint<M>` scaled by 10^N
+    Ufixed(u16, u8),
     Rational,
     Bytes(u8),
     DynamicBytes,
@@ -130,7 +134,7 @@ pub trait RetrieveType {
 impl Type {
     pub fn get_type_size(&self) -> u16 {
         match self {
-            Type::Int(n) | Type::Uint(n) => *n,
+            Type::Int(n) | Type::Uint(n) | Type::Fixed(n, _) | Type::Ufixed(n, _) => *n,
             Type::Bool => 1,
             _ => unimplemented!("size of type not known"),
         }
@@ -209,6 +213,9 @@ pub enum StructType {
     AccountMeta,
     ExternalFunction,
     SolParameters,
+    Rent,
+    EpochSchedule,
+    Instruction,
 }
 
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -404,6 +411,8 @@ pub struct Function {
     pub symtable: Symtable,
     /// What events are emitted by the body of this function
     pub emits_events: Vec<usize>,
+    /// What custom errors the body of this function reverts with
+    pub reverts_errors: Vec<usize>,
     /// For overloaded functions this is the mangled (unique) name.
     pub mangled_name: String,
     /// Solana constructors may have seeds specified using @seed tags
@@ -413,6 +422,8 @@ pub struct Function {
     /// This indexmap stores the accounts this functions needs to be called on Solana
     /// The string is the account's name
     pub solana_accounts: RefCell<IndexMap<String, SolanaAccount>>,
+    /// Constraints on the Solana accounts, checked when the instruction is entered
+    pub account_constraints: Vec<AccountConstraint>,
     /// List of contracts this function creates
     pub creates: Vec<(pt::Loc, usize)>,
 }
@@ -428,6 +439,82 @@ pub struct SolanaAccount {
     pub generated: bool,
 }
 
+/// An Anchor style constraint on an account of a Solana instruction, declared with an annotation.
+/// The account is referred to by its name in `solana_accounts`.
+#[derive(Debug)]
+pub enum AccountConstraint {
+    /// `@owner(account, program_id)`: the account must be owned by the given program
+    Owner {
+        loc: pt::Loc,
+        account: String,
+        owner: Expression,
+    },
+    /// `@seeds(account, [seeds], bump)`: the account must be the program derived address of the seeds
+    Seeds {
+        loc: pt::Loc,
+        account: String,
+        seeds: Vec<Expression>,
+        bump: Expression,
+    },
+    /// `@hasOne(account)`: like `has_one` in Anchor, the address stored in the contract variable
+    /// with the name of the account, a field of the data account, must be the key of the account
+    HasOne {
+        loc: pt::Loc,
+        account: String,
+        field: Expression,
+    },
+    /// `@initIfNeeded(account, payer, space)`: create the account, if it does not exist yet
+    InitIfNeeded {
+        loc: pt::Loc,
+        account: String,
+        payer: String,
+        space: Expression,
+    },
+}
+
+impl AccountConstraint {
+    /// The name of the account this constraint applies to
+    pub fn account(&self) -> &str {
+        match self {
+            AccountConstraint::Owner { account, .. }
+            | AccountConstraint::Seeds { account, .. }
+            | AccountConstraint::HasOne { account, .. }
+            | AccountConstraint::InitIfNeeded { account, .. } => account,
+        }
+    }
+
+    /// The annotation this constraint was declared with
+    pub fn name(&self) -> &'static str {
+        match self {
+            AccountConstraint::Owner { .. } => "owner",
+            AccountConstraint::Seeds { .. } => "seeds",
+            AccountConstraint::HasOne { .. } => "hasOne",
+            AccountConstraint::InitIfNeeded { .. } => "initIfNeeded",
+        }
+    }
+
+    pub fn loc(&self) -> pt::Loc {
+        match self {
+            AccountConstraint::Owner { loc, .. }
+            | AccountConstraint::Seeds { loc, .. }
+            | AccountConstraint::HasOne { loc, .. }
+            | AccountConstraint::InitIfNeeded { loc, .. } => *loc,
+        }
+    }
+
+    /// The expressions used by this constraint
+    pub fn expressions(&self) -> Vec<&Expression> {
+        match self {
+            AccountConstraint::Owner { owner, .. } => vec![owner],
+            AccountConstraint::Seeds { seeds, bump, .. } => {
+                seeds.iter().chain(std::iter::once(bump)).collect()
+            }
+            AccountConstraint::HasOne { field, .. } => vec![field],
+            AccountConstraint::InitIfNeeded { space, .. } => vec![space],
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct ConstructorAnnotations {
     // (annotation location, annotation expression)
@@ -518,10 +605,12 @@ impl Function {
             body: Vec::new(),
             symtable: Symtable::default(),
             emits_events: Vec::new(),
+            reverts_errors: Vec::new(),
             mangled_name,
             annotations: ConstructorAnnotations::default(),
             mangled_name_contracts: HashSet::new(),
             solana_accounts: IndexMap::new().into(),
+            account_constraints: Vec::new(),
             creates: Vec::new(),
         }
     }
@@ -605,6 +694,8 @@ impl From<&pt::Type> for Type {
             pt::Type::Payable => Type::Address(true),
             pt::Type::Int(n) => Type::Int(*n),
             pt::Type::Uint(n) => Type::Uint(*n),
+            pt::Type::Fixed(m, n) => Type::Fixed(*m, *n),
+            pt::Type::Ufixed(m, n) => Type::Ufixed(*m, *n),
             pt::Type::Bytes(n) => Type::Bytes(*n),
             pt::Type::String => Type::String,
             pt::Type::Rational => Type::Rational,
@@ -650,6 +741,13 @@ pub struct Variable {
     pub storage_type: Option<pt::StorageType>,
 }
 
+impl Variable {
+    /// Is this a `transient` state variable, which lives in EIP-1153 transient storage
+    pub fn is_transient(&self) -> bool {
+        matches!(self.storage_type, Some(pt::StorageType::Transient(_)))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Symbol {
     Enum(pt::Loc, usize),
@@ -873,6 +971,9 @@ pub struct Contract {
     pub using: Vec<Using>,
     pub layout: Vec<Layout>,
     pub fixed_layout_size: BigInt,
+    /// Layout of the `transient` variables. These have their own slots, separate from storage
+    pub transient_layout: Vec<Layout>,
+    pub transient_layout_size: BigInt,
     pub functions: Vec<usize>,
     pub all_functions: BTreeMap<usize, usize>,
     /// maps the name of virtual functions to a vector of overriden functions.
@@ -885,6 +986,9 @@ pub struct Contract {
     pub creates: Vec<usize>,
     /// List of events this contract may emit
     pub emits_events: Vec<usize>,
+    /// List of custom errors this contract may revert with. On Solana and Soroban, the code of an
+    /// error is its position in this list.
+    pub custom_errors: Vec<usize>,
     pub initializer: Option<usize>,
     pub default_constructor: Option<(Function, usize)>,
     pub cfg: Vec<ControlFlowGraph>,
@@ -1214,6 +1318,14 @@ pub enum Expression {
         array: Box<Expression>,
         index: Box<Expression>,
     },
+    /// A view of part of an array, i.e. `array[start:end]`. The type is always a slice
+    ArraySlice {
+        loc: pt::Loc,
+        ty: Type,
+        array: Box<Expression>,
+        start: Option<Box<Expression>>,
+        end: Option<Box<Expression>>,
+    },
     NamedMember {
         loc: pt::Loc,
         ty: Type,
@@ -1499,6 +1611,17 @@ impl Recurse for Expression {
                     left.recurse(cx, f);
                     right.recurse(cx, f);
                 }
+                Expression::ArraySlice {
+                    array, start, end, ..
+                } => {
+                    array.recurse(cx, f);
+                    if let Some(start) = start {
+                        start.recurse(cx, f);
+                    }
+                    if let Some(end) = end {
+                        end.recurse(cx, f);
+                    }
+                }
 
                 Expression::AllocDynamicBytes { length, .. } => length.recurse(cx, f),
                 Expression::StorageArrayLength { array, .. } => array.recurse(cx, f),
@@ -1623,6 +1746,7 @@ impl CodeLocation for Expression {
             | Expression::Negate { loc, .. }
             | Expression::ConditionalOperator { loc, .. }
             | Expression::Subscript { loc, .. }
+            | Expression::ArraySlice { loc, .. }
             | Expression::StructMember { loc, .. }
             | Expression::Or { loc, .. }
             | Expression::AllocDynamicBytes { loc, .. }
@@ -1843,6 +1967,14 @@ pub enum Builtin {
     AuthAsCurrContract,
     ExtendTtl,
     ExtendInstanceTtl,
+    Secp256k1Recover,
+    Ed25519Verify,
+    PrngU64,
+    NetworkId,
+    SysvarRent,
+    SysvarEpochSchedule,
+    CurrentInstructionIndex,
+    InstructionAt,
 }
 
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -36,7 +36,7 @@ pub struct Prototype {
 }
 
 // A list of all Solidity builtins functions
-pub static BUILTIN_FUNCTIONS: Lazy<[Prototype; 29]> = Lazy::new(|| {
+pub static BUILTIN_FUNCTIONS: Lazy<[Prototype; 36]> = Lazy::new(|| {
     [
         Prototype {
             builtin: Builtin::ExtendInstanceTtl,
@@ -100,7 +100,7 @@ pub static BUILTIN_FUNCTIONS: Lazy<[Prototype; 29]> = Lazy::new(|| {
             name: "selfdestruct",
             params: vec![Type::Address(true)],
             ret: vec![Type::Unreachable],
-            target: vec![Target::EVM, Target::default_polkadot()],
+            target: vec![Target::EVM, Target::default_polkadot(), Target::PolkaVM],
             doc: "Destroys current account and deposits any remaining balance to address",
             constant: false,
         },
@@ -144,7 +144,7 @@ pub static BUILTIN_FUNCTIONS: Lazy<[Prototype; 29]> = Lazy::new(|| {
             name: "blake2_128",
             params: vec![Type::DynamicBytes],
             ret: vec![Type::Bytes(16)],
-            target: vec![Target::default_polkadot()],
+            target: vec![Target::default_polkadot(), Target::PolkaVM],
             doc: "Calculates blake2-128 hash",
             constant: true,
         },
@@ -155,7 +155,7 @@ pub static BUILTIN_FUNCTIONS: Lazy<[Prototype; 29]> = Lazy::new(|| {
             name: "blake2_256",
             params: vec![Type::DynamicBytes],
             ret: vec![Type::Bytes(32)],
-            target: vec![Target::default_polkadot()],
+            target: vec![Target::default_polkadot(), Target::PolkaVM],
             doc: "Calculates blake2-256 hash",
             constant: true,
         },
@@ -166,7 +166,12 @@ pub static BUILTIN_FUNCTIONS: Lazy<[Prototype; 29]> = Lazy::new(|| {
             name: "gasleft",
             params: vec![],
             ret: vec![Type::Uint(64)],
-            target: vec![Target::default_polkadot(), Target::EVM, Target::Stylus],
+            target: vec![
+                Target::default_polkadot(),
+                Target::PolkaVM,
+                Target::EVM,
+                Target::Stylus,
+            ],
             doc: "Return remaining gas left in current call",
             constant: false,
         },
@@ -298,6 +303,50 @@ pub static BUILTIN_FUNCTIONS: Lazy<[Prototype; 29]> = Lazy::new(|| {
             doc: "ed25519 signature verification",
             constant: false,
         },
+        Prototype {
+            builtin: Builtin::SysvarRent,
+            namespace: Some("sysvar"),
+            method: vec![],
+            name: "rent",
+            params: vec![],
+            ret: vec![Type::Struct(StructType::Rent)],
+            target: vec![Target::Solana],
+            doc: "Returns the Rent sysvar",
+            constant: false,
+        },
+        Prototype {
+            builtin: Builtin::SysvarEpochSchedule,
+            namespace: Some("sysvar"),
+            method: vec![],
+            name: "epochSchedule",
+            params: vec![],
+            ret: vec![Type::Struct(StructType::EpochSchedule)],
+            target: vec![Target::Solana],
+            doc: "Returns the EpochSchedule sysvar",
+            constant: false,
+        },
+        Prototype {
+            builtin: Builtin::CurrentInstructionIndex,
+            namespace: Some("tx"),
+            method: vec![],
+            name: "currentIndex",
+            params: vec![],
+            ret: vec![Type::Uint(16)],
+            target: vec![Target::Solana],
+            doc: "Index of the currently executing instruction in the transaction",
+            constant: false,
+        },
+        Prototype {
+            builtin: Builtin::InstructionAt,
+            namespace: Some("tx"),
+            method: vec![],
+            name: "instructionAt",
+            params: vec![Type::Uint(16)],
+            ret: vec![Type::Struct(StructType::Instruction)],
+            target: vec![Target::Solana],
+            doc: "Returns the instruction at the given index in the transaction",
+            constant: false,
+        },
         Prototype {
             builtin: Builtin::UserTypeWrap,
             namespace: None,
@@ -369,11 +418,44 @@ pub static BUILTIN_FUNCTIONS: Lazy<[Prototype; 29]> = Lazy::new(|| {
             doc: "Authorizes sub-contract calls for the next contract call on behalf of the current contract.",
             constant: false,
         },
+        Prototype {
+            builtin: Builtin::Secp256k1Recover,
+            namespace: None,
+            method: vec![],
+            name: "secp256k1_recover",
+            params: vec![Type::Bytes(32), Type::DynamicBytes, Type::Uint(32)],
+            ret: vec![Type::DynamicBytes],
+            target: vec![Target::Soroban],
+            doc: "Recover the SEC-1 encoded public key from a secp256k1 signature of the message digest",
+            constant: false,
+        },
+        Prototype {
+            builtin: Builtin::Ed25519Verify,
+            namespace: None,
+            method: vec![],
+            name: "ed25519_verify",
+            params: vec![Type::DynamicBytes, Type::DynamicBytes, Type::DynamicBytes],
+            ret: vec![Type::Void],
+            target: vec![Target::Soroban],
+            doc: "Verify an ed25519 signature of the message with the public key. Traps if the signature is invalid.",
+            constant: false,
+        },
+        Prototype {
+            builtin: Builtin::PrngU64,
+            namespace: None,
+            method: vec![],
+            name: "prng_u64",
+            params: vec![Type::Uint(64), Type::Uint(64)],
+            ret: vec![Type::Uint(64)],
+            target: vec![Target::Soroban],
+            doc: "Return a pseudo-random number in the inclusive range, from the PRNG of the current frame",
+            constant: false,
+        },
     ]
 });
 
 // A list of all Solidity builtins variables
-pub static BUILTIN_VARIABLE: Lazy<[Prototype; 17]> = Lazy::new(|| {
+pub static BUILTIN_VARIABLE: Lazy<[Prototype; 18]> = Lazy::new(|| {
     [
         Prototype {
             builtin: Builtin::BlockCoinbase,
@@ -404,7 +486,7 @@ pub static BUILTIN_VARIABLE: Lazy<[Prototype; 17]> = Lazy::new(|| {
             name: "gaslimit",
             params: vec![],
             ret: vec![Type::Uint(64)],
-            target: vec![Target::EVM, Target::Stylus],
+            target: vec![Target::EVM, Target::Stylus, Target::PolkaVM],
             doc: "The gas limit",
             constant: false,
         },
@@ -441,6 +523,17 @@ pub static BUILTIN_VARIABLE: Lazy<[Prototype; 17]> = Lazy::new(|| {
             doc: "Current timestamp in unix epoch (seconds since 1970)",
             constant: false,
         },
+        Prototype {
+            builtin: Builtin::NetworkId,
+            namespace: Some("block"),
+            method: vec![],
+            name: "network_id",
+            params: vec![],
+            ret: vec![Type::Bytes(32)],
+            target: vec![Target::Soroban],
+            doc: "The sha256 hash of the network passphrase",
+            constant: false,
+        },
         Prototype {
             builtin: Builtin::MinimumBalance,
             namespace: Some("block"),
@@ -448,7 +541,7 @@ pub static BUILTIN_VARIABLE: Lazy<[Prototype; 17]> = Lazy::new(|| {
             name: "minimum_balance",
             params: vec![],
             ret: vec![Type::Value],
-            target: vec![Target::default_polkadot()],
+            target: vec![Target::default_polkadot(), Target::PolkaVM],
             doc: "Minimum balance required for an account",
             constant: false,
         },
@@ -459,7 +552,7 @@ pub static BUILTIN_VARIABLE: Lazy<[Prototype; 17]> = Lazy::new(|| {
             name: "chainid",
             params: vec![],
             ret: vec![Type::Uint(256)],
-            target: vec![Target::EVM, Target::Stylus],
+            target: vec![Target::EVM, Target::Stylus, Target::PolkaVM],
             doc: "Current chain id",
             constant: false,
         },
@@ -470,7 +563,7 @@ pub static BUILTIN_VARIABLE: Lazy<[Prototype; 17]> = Lazy::new(|| {
             name: "basefee",
             params: vec![],
             ret: vec![Type::Uint(256)],
-            target: vec![Target::EVM, Target::Stylus],
+            target: vec![Target::EVM, Target::Stylus, Target::PolkaVM],
             doc: "Current block's base fee",
             constant: false,
         },
@@ -536,7 +629,12 @@ pub static BUILTIN_VARIABLE: Lazy<[Prototype; 17]> = Lazy::new(|| {
             name: "gasprice",
             params: vec![],
             ret: vec![Type::Value],
-            target: vec![Target::default_polkadot(), Target::EVM, Target::Stylus],
+            target: vec![
+                Target::default_polkadot(),
+                Target::PolkaVM,
+                Target::EVM,
+                Target::Stylus,
+            ],
             doc: "gas price for one gas unit",
             constant: false,
         },
@@ -547,7 +645,7 @@ pub static BUILTIN_VARIABLE: Lazy<[Prototype; 17]> = Lazy::new(|| {
             name: "origin",
             params: vec![],
             ret: vec![Type::Address(false)],
-            target: vec![Target::EVM, Target::Stylus],
+            target: vec![Target::EVM, Target::Stylus, Target::PolkaVM],
             doc: "Original address of sender current transaction",
             constant: false,
         },
@@ -1074,6 +1172,18 @@ pub(super) fn resolve_call(
 
             diagnostics.extend(candidate_diagnostics);
 
+            // hash functions can read a byte slice in place
+            if matches!(
+                func.builtin,
+                Builtin::Keccak256
+                    | Builtin::Sha256
+                    | Builtin::Ripemd160
+                    | Builtin::Blake2_128
+                    | Builtin::Blake2_256
+            ) {
+                cast_args = cast_args.into_iter().map(uncast_byte_slice).collect();
+            }
+
             return Ok(Expression::Builtin {
                 loc: *loc,
                 tys: func.ret.to_vec(),
@@ -1088,6 +1198,24 @@ pub(super) fn resolve_call(
     Err(())
 }
 
+/// Is this a slice of bytes, e.g. `msg.data[4:]`
+fn is_byte_slice(ty: &Type) -> bool {
+    matches!(ty, Type::Slice(elem) if elem.as_ref() == &Type::Bytes(1))
+}
+
+/// Remove the implicit copy of a byte slice into `bytes`, for builtins which can read the
+/// slice directly
+fn uncast_byte_slice(expr: Expression) -> Expression {
+    match expr {
+        Expression::Cast {
+            to: Type::DynamicBytes,
+            expr,
+            ..
+        } if is_byte_slice(&expr.ty()) => *expr,
+        expr => expr,
+    }
+}
+
 /// Resolve a builtin namespace call. The takes the unresolved arguments, since it has
 /// to handle the special case "abi.decode(foo, (int32, bool, address))" where the
 /// second argument is a type list. The generic expression resolver cannot deal with
@@ -1121,7 +1249,11 @@ pub(super) fn resolve_namespace_call(
                 ResolveTo::Type(&ty),
             )?;
 
-            resolved_args.push(expr.cast(loc, &ty, true, ns, diagnostics)?);
+            if is_byte_slice(&expr.ty()) && kind == Builtin::BytesConcat {
+                resolved_args.push(expr);
+            } else {
+                resolved_args.push(expr.cast(loc, &ty, true, ns, diagnostics)?);
+            }
         }
 
         return Ok(Expression::Builtin {
@@ -1191,8 +1323,14 @@ pub(super) fn resolve_namespace_call(
             symtable,
             diagnostics,
             ResolveTo::Type(&Type::DynamicBytes),
-        )?
-        .cast(&args[0].loc(), &Type::DynamicBytes, true, ns, diagnostics)?;
+        )?;
+
+        // a byte slice can be decoded in place
+        let data = if is_byte_slice(&data.ty()) {
+            data
+        } else {
+            data.cast(&args[0].loc(), &Type::DynamicBytes, true, ns, diagnostics)?
+        };
 
         let mut tys = Vec::new();
         let mut broken = false;
@@ -1729,6 +1867,20 @@ impl Namespace {
             loc,
         };
 
+        let parameter = |name: &str, ty: Type| Parameter {
+            loc,
+            id: Some(identifier(name)),
+            ty,
+            ty_loc: Some(loc),
+            readonly: false,
+            indexed: false,
+            infinite_size: false,
+            recursive: false,
+            annotation: None,
+        };
+        let byte_array =
+            |len: u32| Type::Array(Type::Uint(8).into(), vec![ArrayLength::Fixed(len.into())]);
+
         let file_no = self.files.len();
         self.files.push(File {
             path: PathBuf::from("polkadot"),
@@ -1921,7 +2073,184 @@ impl Namespace {
                 }],
                 self,
             ),
+            // ecdsa_recover API
+            Function::new(
+                loc,
+                loc,
+                identifier("ecdsa_recover"),
+                None,
+                Vec::new(),
+                pt::FunctionTy::Function,
+                Some(pt::Mutability::Pure(loc)),
+                pt::Visibility::Public(Some(loc)),
+                vec![
+                    parameter("signature", byte_array(65)),
+                    parameter("message_hash", Type::Bytes(32)),
+                ],
+                vec![
+                    parameter("return_code", Type::Uint(32)),
+                    parameter("public_key", byte_array(33)),
+                ],
+                self,
+            ),
+            // ecdsa_to_eth_address API
+            Function::new(
+                loc,
+                loc,
+                identifier("ecdsa_to_eth_address"),
+                None,
+                Vec::new(),
+                pt::FunctionTy::Function,
+                Some(pt::Mutability::Pure(loc)),
+                pt::Visibility::Public(Some(loc)),
+                vec![parameter("public_key", byte_array(33))],
+                vec![
+                    parameter("return_code", Type::Uint(32)),
+                    parameter("eth_address", Type::Bytes(20)),
+                ],
+                self,
+            ),
+            // sr25519_verify API
+            Function::new(
+                loc,
+                loc,
+                identifier("sr25519_verify"),
+                None,
+                Vec::new(),
+                pt::FunctionTy::Function,
+                Some(pt::Mutability::Pure(loc)),
+                pt::Visibility::Public(Some(loc)),
+                vec![
+                    parameter("signature", byte_array(64)),
+                    parameter("public_key", Type::Address(false)),
+                    parameter("message", Type::DynamicBytes),
+                ],
+                vec![parameter("valid", Type::Bool)],
+                self,
+            ),
+            // own_code_hash API
+            Function::new(
+                loc,
+                loc,
+                identifier("own_code_hash"),
+                None,
+                Vec::new(),
+                pt::FunctionTy::Function,
+                Some(pt::Mutability::View(loc)),
+                pt::Visibility::Public(Some(loc)),
+                vec![],
+                vec![parameter("code_hash", byte_array(32))],
+                self,
+            ),
+            // xcm_execute API
+            Function::new(
+                loc,
+                loc,
+                identifier("xcm_execute"),
+                None,
+                Vec::new(),
+                pt::FunctionTy::Function,
+                None,
+                pt::Visibility::Public(Some(loc)),
+                vec![parameter("message", Type::DynamicBytes)],
+                vec![parameter("return_code", Type::Uint(32))],
+                self,
+            ),
+            // xcm_send API
+            Function::new(
+                loc,
+                loc,
+                identifier("xcm_send"),
+                None,
+                Vec::new(),
+                pt::FunctionTy::Function,
+                None,
+                pt::Visibility::Public(Some(loc)),
+                vec![
+                    parameter("destination", Type::DynamicBytes),
+                    parameter("message", Type::DynamicBytes),
+                ],
+                vec![
+                    parameter("return_code", Type::Uint(32)),
+                    parameter("message_hash", Type::Bytes(32)),
+                ],
+                self,
+            ),
+            // lock_delegate_dependency API
+            Function::new(
+                loc,
+                loc,
+                identifier("lock_delegate_dependency"),
+                None,
+                Vec::new(),
+                pt::FunctionTy::Function,
+                None,
+                pt::Visibility::Public(Some(loc)),
+                vec![parameter("code_hash", byte_array(32))],
+                vec![],
+                self,
+            ),
+            // unlock_delegate_dependency API
+            Function::new(
+                loc,
+                loc,
+                identifier("unlock_delegate_dependency"),
+                None,
+                Vec::new(),
+                pt::FunctionTy::Function,
+                None,
+                pt::Visibility::Public(Some(loc)),
+                vec![parameter("code_hash", byte_array(32))],
+                vec![],
+                self,
+            ),
+            // take_storage API
+            Function::new(
+                loc,
+                loc,
+                identifier("take_storage"),
+                None,
+                Vec::new(),
+                pt::FunctionTy::Function,
+                None,
+                pt::Visibility::Public(Some(loc)),
+                vec![parameter("key", Type::DynamicBytes)],
+                vec![
+                    parameter("return_code", Type::Uint(32)),
+                    parameter("value", Type::DynamicBytes),
+                ],
+                self,
+            ),
+            // contains_storage API
+            Function::new(
+                loc,
+                loc,
+                identifier("contains_storage"),
+                None,
+                Vec::new(),
+                pt::FunctionTy::Function,
+                Some(pt::Mutability::View(loc)),
+                pt::Visibility::Public(Some(loc)),
+                vec![parameter("key", Type::DynamicBytes)],
+                vec![parameter("contains_storage", Type::Bool)],
+                self,
+            ),
         ] {
+            // The revive pallet does not provide these host functions
+            if self.target == Target::PolkaVM
+                && matches!(
+                    func.id.name.as_str(),
+                    "ecdsa_recover"
+                        | "sr25519_verify"
+                        | "xcm_execute"
+                        | "xcm_send"
+                        | "lock_delegate_dependency"
+                        | "unlock_delegate_dependency"
+                )
+            {
+                continue;
+            }
+
             func.has_body = true;
             let func_no = self.functions.len();
             let id = identifier(&func.id.name);
@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::sema::ast::{Namespace, Parameter, StructDecl, StructType, Type};
+use crate::sema::ast::{ArrayLength, Namespace, Parameter, StructDecl, StructType, Type};
 use once_cell::sync::Lazy;
 use solang_parser::pt;
 
@@ -10,7 +10,7 @@ pub struct BuiltinStructDeclaration {
     pub struct_type: StructType,
 }
 
-pub static BUILTIN_STRUCTS: Lazy<[BuiltinStructDeclaration; 3]> = Lazy::new(|| {
+pub static BUILTIN_STRUCTS: Lazy<[BuiltinStructDeclaration; 6]> = Lazy::new(|| {
     [
         BuiltinStructDeclaration {
             struct_decl: StructDecl {
@@ -236,16 +236,235 @@ pub static BUILTIN_STRUCTS: Lazy<[BuiltinStructDeclaration; 3]> = Lazy::new(|| {
             },
             struct_type: StructType::ExternalFunction,
         },
+        BuiltinStructDeclaration {
+            struct_decl: StructDecl {
+                tags: Vec::new(),
+                loc: pt::Loc::Builtin,
+                contract: None,
+                id: pt::Identifier {
+                    name: "Rent".to_string(),
+                    loc: pt::Loc::Builtin,
+                },
+                fields: vec![
+                    Parameter {
+                        loc: pt::Loc::Builtin,
+                        id: Some(pt::Identifier {
+                            name: String::from("lamports_per_byte_year"),
+                            loc: pt::Loc::Builtin,
+                        }),
+                        ty: Type::Uint(64),
+                        ty_loc: None,
+                        indexed: false,
+                        readonly: true,
+                        infinite_size: false,
+                        recursive: false,
+                        annotation: None,
+                    },
+                    Parameter {
+                        loc: pt::Loc::Builtin,
+                        id: Some(pt::Identifier {
+                            name: String::from("exemption_threshold"),
+                            loc: pt::Loc::Builtin,
+                        }),
+                        ty: Type::Uint(64),
+                        ty_loc: None,
+                        indexed: false,
+                        readonly: true,
+                        infinite_size: false,
+                        recursive: false,
+                        annotation: None,
+                    },
+                    Parameter {
+                        loc: pt::Loc::Builtin,
+                        id: Some(pt::Identifier {
+                            name: String::from("burn_percent"),
+                            loc: pt::Loc::Builtin,
+                        }),
+                        ty: Type::Uint(8),
+                        ty_loc: None,
+                        indexed: false,
+                        readonly: true,
+                        infinite_size: false,
+                        recursive: false,
+                        annotation: None,
+                    },
+                ],
+                offsets: Vec::new(),
+                storage_offsets: Vec::new(),
+            },
+            struct_type: StructType::Rent,
+        },
+        BuiltinStructDeclaration {
+            struct_decl: StructDecl {
+                tags: Vec::new(),
+                loc: pt::Loc::Builtin,
+                contract: None,
+                id: pt::Identifier {
+                    name: "EpochSchedule".to_string(),
+                    loc: pt::Loc::Builtin,
+                },
+                fields: vec![
+                    Parameter {
+                        loc: pt::Loc::Builtin,
+                        id: Some(pt::Identifier {
+                            name: String::from("slots_per_epoch"),
+                            loc: pt::Loc::Builtin,
+                        }),
+                        ty: Type::Uint(64),
+                        ty_loc: None,
+                        indexed: false,
+                        readonly: true,
+                        infinite_size: false,
+                        recursive: false,
+                        annotation: None,
+                    },
+                    Parameter {
+                        loc: pt::Loc::Builtin,
+                        id: Some(pt::Identifier {
+                            name: String::from("leader_schedule_slot_offset"),
+                            loc: pt::Loc::Builtin,
+                        }),
+                        ty: Type::Uint(64),
+                        ty_loc: None,
+                        indexed: false,
+                        readonly: true,
+                        infinite_size: false,
+                        recursive: false,
+                        annotation: None,
+                    },
+                    Parameter {
+                        loc: pt::Loc::Builtin,
+                        id: Some(pt::Identifier {
+                            name: String::from("warmup"),
+                            loc: pt::Loc::Builtin,
+                        }),
+                        ty: Type::Bool,
+                        ty_loc: None,
+                        indexed: false,
+                        readonly: true,
+                        infinite_size: false,
+                        recursive: false,
+                        annotation: None,
+                    },
+                    Parameter {
+                        loc: pt::Loc::Builtin,
+                        id: Some(pt::Identifier {
+                            name: String::from("first_normal_epoch"),
+                            loc: pt::Loc::Builtin,
+                        }),
+                        ty: Type::Uint(64),
+                        ty_loc: None,
+                        indexed: false,
+                        readonly: true,
+                        infinite_size: false,
+                        recursive: false,
+                        annotation: None,
+                    },
+                    Parameter {
+                        loc: pt::Loc::Builtin,
+                        id: Some(pt::Identifier {
+                            name: String::from("first_normal_slot"),
+                            loc: pt::Loc::Builtin,
+                        }),
+                        ty: Type::Uint(64),
+                        ty_loc: None,
+                        indexed: false,
+                        readonly: true,
+                        infinite_size: false,
+                        recursive: false,
+                        annotation: None,
+                    },
+                ],
+                offsets: Vec::new(),
+                storage_offsets: Vec::new(),
+            },
+            struct_type: StructType::EpochSchedule,
+        },
+        BuiltinStructDeclaration {
+            struct_decl: StructDecl {
+                tags: Vec::new(),
+                loc: pt::Loc::Builtin,
+                contract: None,
+                id: pt::Identifier {
+                    name: "Instruction".to_string(),
+                    loc: pt::Loc::Builtin,
+                },
+                fields: vec![
+                    Parameter {
+                        loc: pt::Loc::Builtin,
+                        id: Some(pt::Identifier {
+                            name: String::from("program_id"),
+                            loc: pt::Loc::Builtin,
+                        }),
+                        ty: Type::Address(false),
+                        ty_loc: None,
+                        indexed: false,
+                        readonly: true,
+                        infinite_size: false,
+                        recursive: false,
+                        annotation: None,
+                    },
+                    Parameter {
+                        loc: pt::Loc::Builtin,
+                        id: Some(pt::Identifier {
+                            name: String::from("accounts"),
+                            loc: pt::Loc::Builtin,
+                        }),
+                        ty: Type::Array(
+                            Box::new(Type::Struct(StructType::AccountMeta)),
+                            vec![ArrayLength::Dynamic],
+                        ),
+                        ty_loc: None,
+                        indexed: false,
+                        readonly: true,
+                        infinite_size: false,
+                        recursive: false,
+                        annotation: None,
+                    },
+                    Parameter {
+                        loc: pt::Loc::Builtin,
+                        id: Some(pt::Identifier {
+                            name: String::from("data"),
+                            loc: pt::Loc::Builtin,
+                        }),
+                        ty: Type::DynamicBytes,
+                        ty_loc: None,
+                        indexed: false,
+                        readonly: true,
+                        infinite_size: false,
+                        recursive: false,
+                        annotation: None,
+                    },
+                ],
+                offsets: Vec::new(),
+                storage_offsets: Vec::new(),
+            },
+            struct_type: StructType::Instruction,
+        },
     ]
 });
 
 impl StructType {
+    /// The Solana sysvar types. These are not declared in any file, so that they do not clash
+    /// with types of the same name; they are only found if no declared type has their name.
+    pub fn solana_sysvar(name: &str) -> Option<StructType> {
+        match name {
+            "Rent" => Some(StructType::Rent),
+            "EpochSchedule" => Some(StructType::EpochSchedule),
+            "Instruction" => Some(StructType::Instruction),
+            _ => None,
+        }
+    }
+
     pub fn definition<'a>(&'a self, ns: &'a Namespace) -> &'a StructDecl {
         match self {
             StructType::UserDefined(struct_no) => &ns.structs[*struct_no],
             StructType::AccountInfo => &BUILTIN_STRUCTS[0].struct_decl,
             StructType::AccountMeta => &BUILTIN_STRUCTS[1].struct_decl,
             StructType::ExternalFunction => &BUILTIN_STRUCTS[2].struct_decl,
+            StructType::Rent => &BUILTIN_STRUCTS[3].struct_decl,
+            StructType::EpochSchedule => &BUILTIN_STRUCTS[4].struct_decl,
+            StructType::Instruction => &BUILTIN_STRUCTS[5].struct_decl,
             StructType::SolParameters => unreachable!("SolParameters is defined in a solana.c"),
         }
     }
@@ -7,10 +7,11 @@ use super::{
     expression::{compatible_mutability, ExprContext},
     functions, statements,
     symtable::Symtable,
-    using, variables, ContractDefinition,
+    types, using, variables, ContractDefinition,
 };
 use crate::sema::ast::SolanaAccount;
 use crate::sema::expression::constructor::match_constructor_to_args;
+use crate::Target;
 use crate::{sema::ast::Namespace, sema::unused_variable::emit_warning_local_variable};
 use indexmap::{IndexMap, IndexSet};
 use num_bigint::BigInt;
@@ -41,6 +42,8 @@ impl ast::Contract {
             using: Vec::new(),
             layout: Vec::new(),
             fixed_layout_size: BigInt::zero(),
+            transient_layout: Vec::new(),
+            transient_layout_size: BigInt::zero(),
             tags,
             functions: Vec::new(),
             all_functions: BTreeMap::new(),
@@ -49,6 +52,7 @@ impl ast::Contract {
             variables: Vec::new(),
             creates: Vec::new(),
             emits_events: Vec::new(),
+            custom_errors: Vec::new(),
             initializer: None,
             default_constructor: None,
             cfg: Vec::new(),
@@ -93,6 +97,7 @@ pub fn resolve(contracts: &[ContractDefinition], file_no: usize, ns: &mut ast::N
         mangle_function_names(contract_no, ns);
         verify_unique_selector(contract_no, ns);
         polkadot_requires_public_functions(contract_no, ns);
+        soroban_public_function_types(contract_no, ns);
         unique_constructor_names(contract_no, ns);
         check_mangled_function_names(contract_no, ns);
     }
@@ -737,6 +742,38 @@ fn polkadot_requires_public_functions(contract_no: usize, ns: &mut ast::Namespac
     }
 }
 
+/// On Soroban, the arguments and return values of public functions are passed as host values,
+/// so their types must have a conversion to and from `Val`.
+fn soroban_public_function_types(contract_no: usize, ns: &mut ast::Namespace) {
+    if ns.target != Target::Soroban {
+        return;
+    }
+
+    let mut diagnostics = Vec::new();
+
+    for func_no in &ns.contracts[contract_no].functions {
+        let func = &ns.functions[*func_no];
+
+        if !func.is_public() {
+            continue;
+        }
+
+        for param in func.params.iter().chain(func.returns.iter()) {
+            if !types::soroban_value_type(&param.ty, ns) {
+                diagnostics.push(ast::Diagnostic::error(
+                    param.ty_loc.unwrap_or(param.loc),
+                    format!(
+                        "type '{}' is not supported in public functions on Soroban",
+                        param.ty.to_string(ns)
+                    ),
+                ));
+            }
+        }
+    }
+
+    ns.diagnostics.append(&mut diagnostics);
+}
+
 /// Constructors and functions are no different pallet contracts.
 /// This function checks that all constructors and function names are unique.
 /// Overloading (mangled function or constructor names) is taken into account.
@@ -240,6 +240,24 @@ impl Dot {
             );
         }
 
+        for constraint in &func.account_constraints {
+            let mut labels = vec![format!("@{} {}", constraint.name(), constraint.account())];
+
+            if let AccountConstraint::InitIfNeeded { payer, .. } = constraint {
+                labels.push(format!("payer {payer}"));
+            }
+
+            let constraint_node = self.add_node(
+                Node::new("account_constraint", labels),
+                Some(node),
+                Some(String::from("account constraint")),
+            );
+
+            for expr in constraint.expressions() {
+                self.add_expression(expr, Some(func), ns, constraint_node, "arg".into());
+            }
+        }
+
         // bases
         for (base_no, (_, _, args)) in &func.bases {
             let node = self.add_node(
@@ -1046,6 +1064,33 @@ impl Dot {
                 self.add_expression(array, func, ns, node, String::from("array"));
                 self.add_expression(index, func, ns, node, String::from("index"));
             }
+            Expression::ArraySlice {
+                loc,
+                ty,
+                array,
+                start,
+                end,
+            } => {
+                let node = self.add_node(
+                    Node::new(
+                        "array_slice",
+                        vec![
+                            format!("array slice {}", ty.to_string(ns)),
+                            ns.loc_to_string(PathDisplay::FullPath, loc),
+                        ],
+                    ),
+                    Some(parent),
+                    Some(parent_rel),
+                );
+
+                self.add_expression(array, func, ns, node, String::from("array"));
+                if let Some(start) = start {
+                    self.add_expression(start, func, ns, node, String::from("start"));
+                }
+                if let Some(end) = end {
+                    self.add_expression(end, func, ns, node, String::from("end"));
+                }
+            }
             Expression::StructMember {
                 loc,
                 ty,
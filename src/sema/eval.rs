@@ -1,8 +1,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::{
-    ast::{Builtin, Diagnostic, Expression, Namespace, Type},
+    ast::{Builtin, Diagnostic, Expression, Namespace, RetrieveType, Type},
     diagnostics::Diagnostics,
+    expression::integers::{fixed_range, fixed_to_string},
     Recurse,
 };
 use num_bigint::BigInt;
@@ -13,6 +14,7 @@ use num_traits::ToPrimitive;
 use num_traits::Zero;
 use solang_parser::pt;
 use solang_parser::pt::{CodeLocation, Loc};
+use std::cmp::Ordering;
 use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Shl, Shr, Sub};
 
 /// This enum specifies the error `eval_const_number` is returning
@@ -47,14 +49,24 @@ pub fn eval_const_number(
                 - eval_const_number(right, ns, diagnostics)?.1,
         )),
         Expression::Multiply {
-            loc, left, right, ..
+            loc,
+            ty,
+            left,
+            right,
+            ..
         } => Ok((
             *loc,
-            eval_const_number(left, ns, diagnostics)?.1
-                * eval_const_number(right, ns, diagnostics)?.1,
+            fixed_multiply(
+                ty,
+                &eval_const_number(left, ns, diagnostics)?.1,
+                &eval_const_number(right, ns, diagnostics)?.1,
+            ),
         )),
         Expression::Divide {
-            loc, left, right, ..
+            loc,
+            ty,
+            left,
+            right,
         } => {
             let divisor = eval_const_number(right, ns, diagnostics)?.1;
 
@@ -63,7 +75,10 @@ pub fn eval_const_number(
 
                 Err(EvaluationError::MathError)
             } else {
-                Ok((*loc, eval_const_number(left, ns, diagnostics)?.1 / divisor))
+                Ok((
+                    *loc,
+                    fixed_divide(ty, &eval_const_number(left, ns, diagnostics)?.1, &divisor),
+                ))
             }
         }
         Expression::Modulo {
@@ -160,9 +175,10 @@ pub fn eval_const_number(
         Expression::SignExt { loc, expr, .. } => {
             Ok((*loc, eval_const_number(expr, ns, diagnostics)?.1))
         }
-        Expression::Cast { loc, expr, .. } => {
-            Ok((*loc, eval_const_number(expr, ns, diagnostics)?.1))
-        }
+        Expression::Cast { loc, to, expr } => Ok((
+            *loc,
+            fixed_rescale(&expr.ty(), to, eval_const_number(expr, ns, diagnostics)?.1),
+        )),
         Expression::Not { loc, expr: n } => Ok((*loc, !eval_const_number(n, ns, diagnostics)?.1)),
         Expression::BitwiseNot { loc, expr, .. } => {
             Ok((*loc, !eval_const_number(expr, ns, diagnostics)?.1))
@@ -246,6 +262,37 @@ pub fn eval_const_number(
     }
 }
 
+/// Multiply two numbers of type `ty`. Fixed point numbers are scaled, so the product
+/// is divided by the scale, rounding towards zero.
+fn fixed_multiply(ty: &Type, left: &BigInt, right: &BigInt) -> BigInt {
+    match ty.fixed_decimals() {
+        Some(decimals) => left.mul(right).div(BigInt::from(10).pow(decimals as u32)),
+        None => left.mul(right),
+    }
+}
+
+/// Divide two numbers of type `ty`, rounding towards zero. Fixed point numbers are scaled,
+/// so the dividend is multiplied by the scale first.
+fn fixed_divide(ty: &Type, left: &BigInt, right: &BigInt) -> BigInt {
+    match ty.fixed_decimals() {
+        Some(decimals) => left.mul(BigInt::from(10).pow(decimals as u32)).div(right),
+        None => left.div(right),
+    }
+}
+
+/// Convert a number from type `from` to type `to`. When either is a fixed point type, the
+/// value is rescaled to the decimals of the target type, rounding towards zero.
+fn fixed_rescale(from: &Type, to: &Type, value: BigInt) -> BigInt {
+    let from_decimals = from.fixed_decimals().unwrap_or(0) as u32;
+    let to_decimals = to.fixed_decimals().unwrap_or(0) as u32;
+
+    match from_decimals.cmp(&to_decimals) {
+        Ordering::Less => value.mul(BigInt::from(10).pow(to_decimals - from_decimals)),
+        Ordering::Greater => value.div(BigInt::from(10).pow(from_decimals - to_decimals)),
+        Ordering::Equal => value,
+    }
+}
+
 /// Resolve an expression where a compile-time constant(rational) is expected
 pub fn eval_const_rational(
     expr: &Expression,
@@ -298,9 +345,13 @@ pub fn eval_const_rational(
                 Ok((*loc, eval_const_rational(l, ns)?.1 % divisor))
             }
         }
-        Expression::NumberLiteral { loc, value, .. } => {
-            Ok((*loc, BigRational::from_integer(value.clone())))
-        }
+        Expression::NumberLiteral { loc, ty, value } => match ty.fixed_decimals() {
+            Some(decimals) => Ok((
+                *loc,
+                BigRational::new(value.clone(), BigInt::from(10).pow(decimals as u32)),
+            )),
+            None => Ok((*loc, BigRational::from_integer(value.clone()))),
+        },
         Expression::RationalNumberLiteral { loc, value, .. } => Ok((*loc, value.clone())),
         Expression::Cast { loc, expr, .. } => Ok((*loc, eval_const_rational(expr, ns)?.1)),
         Expression::Negate { loc, expr, .. } => Ok((*loc, -eval_const_rational(expr, ns)?.1)),
@@ -463,7 +514,7 @@ pub(crate) fn eval_constants_in_expression(
                     Some(Expression::NumberLiteral {
                         loc: *loc,
                         ty: ty.clone(),
-                        value: left.mul(right),
+                        value: fixed_multiply(ty, left, right),
                     }),
                     true,
                 )
@@ -493,7 +544,7 @@ pub(crate) fn eval_constants_in_expression(
                         Some(Expression::NumberLiteral {
                             loc: *loc,
                             ty: ty.clone(),
-                            value: left.div(right),
+                            value: fixed_divide(ty, left, right),
                         }),
                         true,
                     )
@@ -766,6 +817,28 @@ pub(crate) fn eval_constants_in_expression(
 
 /// Function that takes a BigInt and an expected type. If the number of bits in the type required to represent the BigInt is not sufficient, it will return a diagnostic.
 pub(crate) fn overflow_diagnostic(result: &BigInt, ty: &Type, loc: &Loc) -> Option<Diagnostic> {
+    if let Type::Fixed(bits, decimals) | Type::Ufixed(bits, decimals) = ty {
+        let (min, max) = fixed_range(ty);
+
+        return if *result < min || *result > max {
+            let name = if matches!(ty, Type::Fixed(..)) {
+                "fixed"
+            } else {
+                "ufixed"
+            };
+
+            Some(Diagnostic::error(
+                *loc,
+                format!(
+                    "value {} does not fit into type {name}{bits}x{decimals}.",
+                    fixed_to_string(result, *decimals)
+                ),
+            ))
+        } else {
+            None
+        };
+    }
+
     if result.bits() > 1024 {
         // Do not try to print large values. For example:
         // uint x = 80 ** 0x100000;
@@ -111,6 +111,8 @@ pub(super) fn bitwise_or(
         diagnostics,
     )?;
 
+    no_fixed_operand(loc, &ty, ns, diagnostics)?;
+
     Ok(Expression::BitwiseOr {
         loc: *loc,
         ty: ty.clone(),
@@ -155,6 +157,8 @@ pub(super) fn bitwise_and(
         diagnostics,
     )?;
 
+    no_fixed_operand(loc, &ty, ns, diagnostics)?;
+
     Ok(Expression::BitwiseAnd {
         loc: *loc,
         ty: ty.clone(),
@@ -199,6 +203,8 @@ pub(super) fn bitwise_xor(
         diagnostics,
     )?;
 
+    no_fixed_operand(loc, &ty, ns, diagnostics)?;
+
     Ok(Expression::BitwiseXor {
         loc: *loc,
         ty: ty.clone(),
@@ -335,7 +341,7 @@ pub(super) fn multiply(
     }
 
     // If we don't know what type the result is going to be, make any possible result fit.
-    if resolve_to == ResolveTo::Unknown {
+    if resolve_to == ResolveTo::Unknown && !ty.is_fixed() {
         let bits = std::cmp::min(256, ty.bits(ns) * 2);
 
         if ty.is_signed_int(ns) {
@@ -503,6 +509,9 @@ pub(super) fn power(
     let base_type = base.ty();
     let exp_type = exp.ty();
 
+    no_fixed_operand(loc, &base_type, ns, diagnostics)?;
+    no_fixed_operand(loc, &exp_type, ns, diagnostics)?;
+
     // solc-0.5.13 does not allow either base or exp to be signed
     if base_type.is_signed_int(ns) || exp_type.is_signed_int(ns) {
         diagnostics.push(Diagnostic::error(
@@ -532,6 +541,27 @@ pub(super) fn power(
     })
 }
 
+/// Bitwise operators and exponentiation are not defined on fixed point numbers
+fn no_fixed_operand(
+    loc: &pt::Loc,
+    ty: &Type,
+    ns: &Namespace,
+    diagnostics: &mut Diagnostics,
+) -> Result<(), ()> {
+    if ty.is_fixed() {
+        diagnostics.push(Diagnostic::error(
+            *loc,
+            format!(
+                "operator not allowed on fixed point type '{}'",
+                ty.deref_any().to_string(ns)
+            ),
+        ));
+        Err(())
+    } else {
+        Ok(())
+    }
+}
+
 /// Test for equality; first check string equality, then integer equality
 pub(super) fn equal(
     loc: &pt::Loc,
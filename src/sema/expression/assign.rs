@@ -217,6 +217,24 @@ pub(super) fn assign_expr(
                             ns: &Namespace,
                             diagnostics: &mut Diagnostics|
      -> Result<Expression, ()> {
+        if ty.is_fixed()
+            && matches!(
+                expr,
+                pt::Expression::AssignOr(..)
+                    | pt::Expression::AssignAnd(..)
+                    | pt::Expression::AssignXor(..)
+            )
+        {
+            diagnostics.push(Diagnostic::error(
+                *loc,
+                format!(
+                    "operator not allowed on fixed point type '{}'",
+                    ty.to_string(ns)
+                ),
+            ));
+            return Err(());
+        }
+
         let set = match expr {
             pt::Expression::AssignShiftLeft(..) | pt::Expression::AssignShiftRight(..) => {
                 let left_length = type_bits_and_sign(ty, loc, true, ns, diagnostics)?;
@@ -348,7 +366,11 @@ pub(super) fn assign_expr(
         }
         Expression::Variable { var_no, .. } => {
             match var_ty {
-                Type::Bytes(_) | Type::Int(_) | Type::Uint(_) => (),
+                Type::Bytes(_)
+                | Type::Int(_)
+                | Type::Uint(_)
+                | Type::Fixed(..)
+                | Type::Ufixed(..) => (),
                 _ => {
                     diagnostics.push(Diagnostic::error(
                         var.loc(),
@@ -370,7 +392,11 @@ pub(super) fn assign_expr(
         }
         _ => match &var_ty {
             Type::Ref(r_ty) => match r_ty.as_ref() {
-                Type::Bytes(_) | Type::Int(_) | Type::Uint(_) => Ok(Expression::Assign {
+                Type::Bytes(_)
+                | Type::Int(_)
+                | Type::Uint(_)
+                | Type::Fixed(..)
+                | Type::Ufixed(..) => Ok(Expression::Assign {
                     loc: *loc,
                     ty: *r_ty.clone(),
                     left: Box::new(var.clone()),
@@ -383,7 +409,14 @@ pub(super) fn assign_expr(
                 }),
                 // If the variable is a Type::Ref(Type::Ref(..)), we must load it first.
                 Type::Ref(inner)
-                    if matches!(**inner, Type::Bytes(_) | Type::Int(_) | Type::Uint(_)) =>
+                    if matches!(
+                        **inner,
+                        Type::Bytes(_)
+                            | Type::Int(_)
+                            | Type::Uint(_)
+                            | Type::Fixed(..)
+                            | Type::Ufixed(..)
+                    ) =>
                 {
                     Ok(Expression::Assign {
                         loc: *loc,
@@ -419,7 +452,11 @@ pub(super) fn assign_expr(
                 }
 
                 match r_ty.as_ref() {
-                    Type::Bytes(_) | Type::Int(_) | Type::Uint(_) => Ok(Expression::Assign {
+                    Type::Bytes(_)
+                    | Type::Int(_)
+                    | Type::Uint(_)
+                    | Type::Fixed(..)
+                    | Type::Ufixed(..) => Ok(Expression::Assign {
                         loc: *loc,
                         ty: *r_ty.clone(),
                         left: Box::new(var.clone()),
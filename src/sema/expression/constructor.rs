@@ -10,6 +10,7 @@ use crate::sema::expression::{ExprContext, ResolveTo};
 use crate::sema::namespace::ResolveTypeContext;
 use crate::sema::symtable::Symtable;
 use crate::sema::unused_variable::used_variable;
+use crate::Target;
 use solang_parser::diagnostics::Diagnostic;
 use solang_parser::pt;
 use solang_parser::pt::{CodeLocation, Visibility};
@@ -39,6 +40,8 @@ fn constructor(
         return Err(());
     }
 
+    soroban_salt_check(loc, &call_args, ns, diagnostics)?;
+
     // The current contract cannot be constructed with new. In order to create
     // the contract, we need the code hash of the contract. Part of that code
     // will be code we're emitted here. So we end up with a crypto puzzle.
@@ -91,6 +94,25 @@ fn constructor(
     }
 }
 
+/// The address of a contract created on Soroban is derived from the creating contract and a salt.
+/// There is no nonce which could be used instead, so the salt must be given.
+fn soroban_salt_check(
+    loc: &pt::Loc,
+    call_args: &CallArgs,
+    ns: &Namespace,
+    diagnostics: &mut Diagnostics,
+) -> Result<(), ()> {
+    if ns.target == Target::Soroban && call_args.salt.is_none() {
+        diagnostics.push(Diagnostic::error(
+            *loc,
+            "creating a contract on Soroban requires a 'salt' call argument".to_string(),
+        ));
+        return Err(());
+    }
+
+    Ok(())
+}
+
 /// Try and find constructor for arguments
 pub fn match_constructor_to_args(
     loc: &pt::Loc,
@@ -275,6 +297,8 @@ pub fn constructor_named_args(
         return Err(());
     }
 
+    soroban_salt_check(loc, &call_args, ns, diagnostics)?;
+
     // The current contract cannot be constructed with new. In order to create
     // the contract, we need the code hash of the contract. Part of that code
     // will be code we're emitted here. So we end up with a crypto puzzle.
@@ -4,7 +4,8 @@ use crate::sema::ast::{Expression, Namespace, Type};
 use crate::sema::diagnostics::Diagnostics;
 use crate::sema::expression::ResolveTo;
 use num_bigint::{BigInt, Sign};
-use num_traits::Zero;
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
 use solang_parser::diagnostics::Diagnostic;
 use solang_parser::pt;
 
@@ -132,6 +133,9 @@ pub fn coerce_number(
         (Type::FunctionSelector, _) | (_, Type::FunctionSelector) if allow_bytes => {
             return Ok(Type::Bytes(ns.target.selector_length()));
         }
+        (Type::Fixed(..) | Type::Ufixed(..), _) | (_, Type::Fixed(..) | Type::Ufixed(..)) => {
+            return coerce_fixed(l, l_loc, r, r_loc, ns, diagnostics);
+        }
         (Type::Rational, Type::Int(_)) => {
             return Ok(Type::Rational);
         }
@@ -178,6 +182,119 @@ pub fn coerce_number(
     })
 }
 
+/// Find the common type when at least one side is a fixed point type. Integers and rationals
+/// are converted to the fixed point type; of two different fixed point types, one must be
+/// convertible to the other without loss.
+fn coerce_fixed(
+    l: &Type,
+    l_loc: &pt::Loc,
+    r: &Type,
+    r_loc: &pt::Loc,
+    ns: &Namespace,
+    diagnostics: &mut Diagnostics,
+) -> Result<Type, ()> {
+    match (l, r) {
+        _ if l == r => Ok(l.clone()),
+        (Type::Fixed(..) | Type::Ufixed(..), Type::Fixed(..) | Type::Ufixed(..)) => {
+            if fixed_convertible(r, l) {
+                Ok(l.clone())
+            } else if fixed_convertible(l, r) {
+                Ok(r.clone())
+            } else {
+                diagnostics.push(Diagnostic::error(
+                    *l_loc,
+                    format!(
+                        "cannot combine '{}' and '{}' without loss of precision, use an explicit conversion",
+                        l.to_string(ns),
+                        r.to_string(ns)
+                    ),
+                ));
+                Err(())
+            }
+        }
+        (Type::Fixed(..) | Type::Ufixed(..), Type::Int(_) | Type::Uint(_) | Type::Rational) => {
+            Ok(l.clone())
+        }
+        (Type::Int(_) | Type::Uint(_) | Type::Rational, Type::Fixed(..) | Type::Ufixed(..)) => {
+            Ok(r.clone())
+        }
+        (Type::Fixed(..) | Type::Ufixed(..), _) => {
+            diagnostics.push(Diagnostic::error(
+                *r_loc,
+                format!("expression of type {} not allowed", r.to_string(ns)),
+            ));
+            Err(())
+        }
+        _ => {
+            diagnostics.push(Diagnostic::error(
+                *l_loc,
+                format!("expression of type {} not allowed", l.to_string(ns)),
+            ));
+            Err(())
+        }
+    }
+}
+
+/// Bits, decimals and sign of a fixed point or integer type. Integers are fixed point
+/// numbers with no decimals.
+fn fixed_params(ty: &Type) -> Option<(u16, u8, bool)> {
+    match ty {
+        Type::Fixed(bits, decimals) => Some((*bits, *decimals, true)),
+        Type::Ufixed(bits, decimals) => Some((*bits, *decimals, false)),
+        Type::Int(bits) => Some((*bits, 0, true)),
+        Type::Uint(bits) => Some((*bits, 0, false)),
+        _ => None,
+    }
+}
+
+/// The smallest and largest value of a fixed point or integer type, in its scaled
+/// integer representation
+pub(crate) fn fixed_range(ty: &Type) -> (BigInt, BigInt) {
+    let (bits, _, signed) = fixed_params(ty).expect("not a fixed point type");
+
+    if signed {
+        let max = BigInt::one() << (bits as usize - 1);
+
+        (-max.clone(), max - 1)
+    } else {
+        (BigInt::zero(), (BigInt::one() << bits as usize) - 1)
+    }
+}
+
+/// Format the scaled integer representation of a fixed point number as a decimal
+pub(crate) fn fixed_to_string(value: &BigInt, decimals: u8) -> String {
+    let scale = BigInt::from(10).pow(decimals as u32);
+    let sign = if value.sign() == Sign::Minus { "-" } else { "" };
+    let (integer, fraction) = value.abs().div_rem(&scale);
+
+    if fraction.is_zero() {
+        format!("{sign}{integer}")
+    } else {
+        let fraction = format!("{:0>width$}", fraction, width = decimals as usize);
+
+        format!("{sign}{integer}.{}", fraction.trim_end_matches('0'))
+    }
+}
+
+/// Can every value of fixed point or integer type `from` be represented in `to`?
+pub(super) fn fixed_convertible(from: &Type, to: &Type) -> bool {
+    let (Some((_, from_decimals, _)), Some((_, to_decimals, _))) =
+        (fixed_params(from), fixed_params(to))
+    else {
+        return false;
+    };
+
+    if to_decimals < from_decimals {
+        return false;
+    }
+
+    let scale = BigInt::from(10).pow((to_decimals - from_decimals) as u32);
+    let (from_min, from_max) = fixed_range(from);
+    let (to_min, to_max) = fixed_range(to);
+
+    from_min * &scale >= to_min && from_max * scale <= to_max
+}
+
 /// Try to convert a BigInt into a Expression::NumberLiteral.
 /// The `hex_str_len` parameter is used to specify a custom length for 0-prefixed hex-literals.
 pub fn bigint_to_expression(
@@ -189,6 +306,14 @@ pub fn bigint_to_expression(
     hex_str_len: Option<usize>,
 ) -> Result<Expression, ()> {
     if let ResolveTo::Type(resolve_to) = resolve_to {
+        if let Type::Fixed(_, decimals) | Type::Ufixed(_, decimals) = resolve_to {
+            return Ok(Expression::NumberLiteral {
+                loc: *loc,
+                ty: resolve_to.clone(),
+                value: n * BigInt::from(10).pow(*decimals as u32),
+            });
+        }
+
         if *resolve_to != Type::Unresolved {
             if !(resolve_to.is_integer(ns) || matches!(resolve_to, Type::Bytes(_)) && n.is_zero()) {
                 diagnostics.push(Diagnostic::cast_error(
@@ -133,7 +133,7 @@ pub(crate) fn hex_number_literal(
     if n.starts_with("0x") && !n.chars().any(|c| c == '_') && n.len() == 42 {
         let address = to_hexstr_eip55(n);
 
-        if ns.target == Target::EVM || ns.target == Target::Stylus {
+        if matches!(ns.target, Target::EVM | Target::Stylus | Target::PolkaVM) {
             return if address == *n {
                 let s: String = address.chars().skip(2).collect();
 
@@ -203,7 +203,7 @@ pub(super) fn address_literal(
     ns: &Namespace,
     diagnostics: &mut Diagnostics,
 ) -> Result<Expression, ()> {
-    if ns.target.is_polkadot() {
+    if matches!(ns.target, Target::Polkadot { .. }) {
         match address.from_base58() {
             Ok(v) => {
                 if v.len() != ns.address_length + 3 {
@@ -20,6 +20,7 @@ use super::eval::eval_const_rational;
 use super::symtable::{Symtable, VarScope};
 use crate::sema::contracts::is_base;
 use crate::sema::eval::eval_const_number;
+use crate::sema::expression::integers::{fixed_convertible, fixed_range, fixed_to_string};
 use crate::sema::{symtable::LoopScopes, using::user_defined_operator_binding};
 use num_bigint::{BigInt, Sign};
 use num_rational::BigRational;
@@ -186,6 +187,34 @@ impl Expression {
 
         // Special case: when converting literal sign can change if it fits
         match (self, &from, to) {
+            // The value of a fixed point literal is scaled, so it must be converted at runtime
+            (Expression::NumberLiteral { .. }, Type::Fixed(..) | Type::Ufixed(..), _) => (),
+            (
+                Expression::NumberLiteral { value, .. },
+                Type::Int(_) | Type::Uint(_),
+                Type::Fixed(_, decimals) | Type::Ufixed(_, decimals),
+            ) => {
+                let scaled = value * BigInt::from(10).pow(*decimals as u32);
+                let (min, max) = fixed_range(to);
+
+                return if scaled < min || scaled > max {
+                    diagnostics.push(Diagnostic::cast_error(
+                        *loc,
+                        format!(
+                            "value {} does not fit into type '{}'",
+                            value,
+                            to.to_string(ns)
+                        ),
+                    ));
+                    Err(())
+                } else {
+                    Ok(Expression::NumberLiteral {
+                        loc: *loc,
+                        ty: to.clone(),
+                        value: scaled,
+                    })
+                };
+            }
             (Expression::NumberLiteral { value, .. }, p, &Type::Uint(to_len))
                 if p.is_primitive() =>
             {
@@ -890,6 +919,74 @@ impl Expression {
                     })
                 }
             }
+            (Type::Rational, Type::Fixed(_, decimals) | Type::Ufixed(_, decimals)) => {
+                match eval_const_rational(self, ns) {
+                    Ok((_, value)) => {
+                        let scaled =
+                            &value * BigRational::from(BigInt::from(10).pow(*decimals as u32));
+
+                        if implicit && !scaled.is_integer() {
+                            diagnostics.push(Diagnostic::cast_error(
+                                *loc,
+                                format!(
+                                    "value cannot be represented exactly in type '{}', use an explicit conversion to round towards zero",
+                                    to.to_string(ns)
+                                ),
+                            ));
+                            return Err(());
+                        }
+
+                        // explicit conversions round towards zero
+                        let scaled = scaled.trunc().to_integer();
+                        let (min, max) = fixed_range(to);
+
+                        if scaled < min || scaled > max {
+                            diagnostics.push(Diagnostic::cast_error(
+                                *loc,
+                                format!(
+                                    "value {} does not fit into type '{}'",
+                                    fixed_to_string(&scaled, *decimals),
+                                    to.to_string(ns)
+                                ),
+                            ));
+                            return Err(());
+                        }
+
+                        Ok(Expression::NumberLiteral {
+                            loc: *loc,
+                            ty: to.clone(),
+                            value: scaled,
+                        })
+                    }
+                    Err(diag) => {
+                        diagnostics.push(diag);
+                        Err(())
+                    }
+                }
+            }
+            (
+                Type::Fixed(..) | Type::Ufixed(..) | Type::Int(_) | Type::Uint(_),
+                Type::Fixed(..) | Type::Ufixed(..),
+            )
+            | (Type::Fixed(..) | Type::Ufixed(..), Type::Int(_) | Type::Uint(_)) => {
+                if implicit && !fixed_convertible(from, to) {
+                    diagnostics.push(Diagnostic::cast_error(
+                        *loc,
+                        format!(
+                            "implicit conversion from {} to {} not allowed",
+                            from.to_string(ns),
+                            to.to_string(ns)
+                        ),
+                    ));
+                    Err(())
+                } else {
+                    Ok(Expression::Cast {
+                        loc: *loc,
+                        to: to.clone(),
+                        expr: Box::new(self.clone()),
+                    })
+                }
+            }
             (Type::Rational, Type::Uint(_) | Type::Int(_) | Type::Value) => {
                 match eval_const_rational(self, ns) {
                     Ok((_, big_number)) => {
@@ -1238,6 +1335,24 @@ impl Expression {
             {
                 Ok(self.clone())
             }
+            // slice bytes1 -> bytes, which copies the slice into a new vector
+            (Type::Slice(from), Type::DynamicBytes) if from.as_ref() == &Type::Bytes(1) => {
+                Ok(Expression::Cast {
+                    loc: *loc,
+                    to: to.clone(),
+                    expr: Box::new(self.clone()),
+                })
+            }
+            // slice T -> T[], which copies the slice into a new vector
+            (Type::Slice(from), Type::Array(to_elem, dims))
+                if dims.len() == 1 && dims[0] == ArrayLength::Dynamic && from == to_elem =>
+            {
+                Ok(Expression::Cast {
+                    loc: *loc,
+                    to: to.clone(),
+                    expr: Box::new(self.clone()),
+                })
+            }
             (Type::FunctionSelector, Type::Bytes(n)) => {
                 let selector_length = ns.target.selector_length();
                 if *n == selector_length {
@@ -14,7 +14,7 @@ use crate::sema::expression::{
         rational_number_literal, string_literal, unit_literal,
     },
     member_access::member_access,
-    subscript::array_subscript,
+    subscript::{array_slice, array_subscript},
     variable::variable,
     {user_defined_operator, ExprContext, ResolveTo},
 };
@@ -332,14 +332,16 @@ pub fn expression(
 
             Err(())
         }
-        pt::Expression::ArraySlice(loc, ..) => {
-            diagnostics.push(Diagnostic::error(
-                *loc,
-                "slice not supported yet".to_string(),
-            ));
-
-            Err(())
-        }
+        pt::Expression::ArraySlice(loc, array, start, end) => array_slice(
+            loc,
+            array,
+            start.as_deref(),
+            end.as_deref(),
+            context,
+            ns,
+            symtable,
+            diagnostics,
+        ),
         pt::Expression::ArraySubscript(loc, array, Some(index)) => {
             array_subscript(loc, array, index, context, ns, symtable, diagnostics)
         }
@@ -527,8 +529,17 @@ fn negate(
 
             let expr_type = expr.ty();
 
-            if let Expression::NumberLiteral { value, .. } = expr {
-                bigint_to_expression(loc, &-value, ns, diagnostics, resolve_to, None)
+            if let Expression::NumberLiteral { value, ty, .. } = expr {
+                if ty.is_fixed() {
+                    // already scaled to the fixed point type
+                    Ok(Expression::NumberLiteral {
+                        loc: *loc,
+                        ty,
+                        value: -value,
+                    })
+                } else {
+                    bigint_to_expression(loc, &-value, ns, diagnostics, resolve_to, None)
+                }
             } else if let Expression::RationalNumberLiteral { ty, value: r, .. } = expr {
                 Ok(Expression::RationalNumberLiteral {
                     loc: *loc,
@@ -536,7 +547,9 @@ fn negate(
                     value: -r,
                 })
             } else {
-                type_bits_and_sign(&expr_type, loc, false, ns, diagnostics)?;
+                if !expr_type.is_fixed() {
+                    type_bits_and_sign(&expr_type, loc, false, ns, diagnostics)?;
+                }
 
                 if !expr_type.is_signed_int(ns) {
                     diagnostics.push(Diagnostic::error(
@@ -50,6 +50,7 @@ impl RetrieveType for Expression {
             | Expression::PostDecrement { ty, .. }
             | Expression::Assign { ty, .. }
             | Expression::Subscript { ty, .. }
+            | Expression::ArraySlice { ty, .. }
             | Expression::ZeroExt { to: ty, .. }
             | Expression::SignExt { to: ty, .. }
             | Expression::Trunc { to: ty, .. }
@@ -1,10 +1,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::sema::ast::{Expression, Mapping, Namespace, RetrieveType, Type};
+use crate::sema::ast::{ArrayLength, Expression, Mapping, Namespace, RetrieveType, Type};
 use crate::sema::diagnostics::Diagnostics;
+use crate::sema::eval::eval_const_number;
 use crate::sema::expression::resolve_expression::expression;
 use crate::sema::expression::{ExprContext, ResolveTo};
 use crate::sema::symtable::Symtable;
+use crate::Target;
 use solang_parser::diagnostics::Diagnostic;
 use solang_parser::pt;
 use solang_parser::pt::CodeLocation;
@@ -139,6 +141,147 @@ pub(super) fn array_subscript(
     }
 }
 
+/// Resolve an array slice expression, i.e. `array[start:end]`. Either bound may be omitted.
+pub(super) fn array_slice(
+    loc: &pt::Loc,
+    array: &pt::Expression,
+    start: Option<&pt::Expression>,
+    end: Option<&pt::Expression>,
+    context: &mut ExprContext,
+    ns: &mut Namespace,
+    symtable: &mut Symtable,
+    diagnostics: &mut Diagnostics,
+) -> Result<Expression, ()> {
+    let array = expression(
+        array,
+        context,
+        ns,
+        symtable,
+        diagnostics,
+        ResolveTo::Unknown,
+    )?;
+    let array_ty = array.ty();
+
+    if ns.target == Target::Soroban {
+        diagnostics.push(Diagnostic::error(
+            *loc,
+            "array slices are not supported on Soroban".to_string(),
+        ));
+        return Err(());
+    }
+
+    if array_ty.is_contract_storage() {
+        diagnostics.push(Diagnostic::error(
+            array.loc(),
+            "array slices are only supported on calldata and memory arrays".to_string(),
+        ));
+        return Err(());
+    }
+
+    let deref_ty = array_ty.deref_memory();
+
+    let elem_ty = match deref_ty {
+        Type::DynamicBytes => Type::Bytes(1),
+        Type::Slice(elem) => *elem.clone(),
+        Type::Array(elem, dims)
+            if dims.len() == 1
+                && dims[0] == ArrayLength::Dynamic
+                && !elem.is_reference_type(ns) =>
+        {
+            *elem.clone()
+        }
+        Type::String => {
+            diagnostics.push(Diagnostic::error(
+                array.loc(),
+                "array slice is not permitted on string".to_string(),
+            ));
+            return Err(());
+        }
+        _ => {
+            diagnostics.push(Diagnostic::error(
+                array.loc(),
+                format!(
+                    "array slice is only supported on bytes and dynamic arrays of value types, not '{}'",
+                    array_ty.to_string(ns)
+                ),
+            ));
+            return Err(());
+        }
+    };
+
+    let array = array.cast(&array.loc(), deref_ty, true, ns, diagnostics)?;
+
+    let start = start
+        .map(|bound| slice_bound(bound, context, ns, symtable, diagnostics))
+        .transpose()?;
+    let end = end
+        .map(|bound| slice_bound(bound, context, ns, symtable, diagnostics))
+        .transpose()?;
+
+    if let (Some(start), Some(end)) = (&start, &end) {
+        let mut eval_diagnostics = Diagnostics::default();
+
+        if let (Ok((_, start_value)), Ok((_, end_value))) = (
+            eval_const_number(start, ns, &mut eval_diagnostics),
+            eval_const_number(end, ns, &mut eval_diagnostics),
+        ) {
+            if start_value > end_value {
+                diagnostics.push(Diagnostic::error(
+                    *loc,
+                    format!("array slice start {start_value} is greater than end {end_value}"),
+                ));
+                return Err(());
+            }
+        }
+    }
+
+    Ok(Expression::ArraySlice {
+        loc: *loc,
+        ty: Type::Slice(Box::new(elem_ty)),
+        array: Box::new(array),
+        start: start.map(Box::new),
+        end: end.map(Box::new),
+    })
+}
+
+/// Resolve the start or end of an array slice, which must be an unsigned integer
+fn slice_bound(
+    bound: &pt::Expression,
+    context: &mut ExprContext,
+    ns: &mut Namespace,
+    symtable: &mut Symtable,
+    diagnostics: &mut Diagnostics,
+) -> Result<Expression, ()> {
+    let bound = expression(
+        bound,
+        context,
+        ns,
+        symtable,
+        diagnostics,
+        ResolveTo::Type(&Type::Uint(32)),
+    )?;
+
+    bound.check_constant_overflow(diagnostics);
+
+    let bound_ty = bound.ty();
+
+    match bound_ty.deref_any() {
+        Type::Uint(_) => (),
+        _ => {
+            diagnostics.push(Diagnostic::error(
+                bound.loc(),
+                format!(
+                    "array slice bound must be an unsigned integer, not '{}'",
+                    bound_ty.to_string(ns)
+                ),
+            ));
+            return Err(());
+        }
+    }
+
+    bound.cast(&bound.loc(), bound_ty.deref_any(), true, ns, diagnostics)
+}
+
 /// Calculate storage subscript
 fn mapping_subscript(
     loc: &pt::Loc,
@@ -103,6 +103,50 @@ pub fn add_external_functions(contract_no: usize, ns: &mut Namespace) {
     }
 
     ns.contracts[contract_no].emits_events = emits_events;
+
+    // The custom errors are the file level errors and those of the contract and its bases, in
+    // declaration order, so that their codes do not change when errors are added to other
+    // contracts. Errors of libraries or other contracts which the functions revert with follow.
+    let bases = ns.contract_bases(contract_no);
+
+    let mut custom_errors = ns
+        .errors
+        .iter()
+        .enumerate()
+        .filter(|(_, err)| err.contract.is_none_or(|c| bases.contains(&c)))
+        .map(|(error_no, _)| error_no)
+        .collect::<Vec<_>>();
+
+    custom_errors.sort_by_key(|error_no| ns.errors[*error_no].loc);
+
+    let mut reverts_errors = Vec::new();
+
+    for function_no in ns.contracts[contract_no].all_functions.keys() {
+        let func = &ns.functions[*function_no];
+
+        // modifiers of library functions are not listed in all_functions
+        let modifiers = func.modifiers.iter().filter_map(|modifier| {
+            if let Expression::InternalFunctionCall { function, .. } = modifier {
+                if let Expression::InternalFunction { function_no, .. } = function.as_ref() {
+                    return Some(*function_no);
+                }
+            }
+            None
+        });
+
+        for function_no in std::iter::once(*function_no).chain(modifiers) {
+            for error_no in &ns.functions[function_no].reverts_errors {
+                if !custom_errors.contains(error_no) && !reverts_errors.contains(error_no) {
+                    reverts_errors.push(*error_no);
+                }
+            }
+        }
+    }
+
+    reverts_errors.sort_by_key(|error_no| ns.errors[*error_no].loc);
+    custom_errors.extend(reverts_errors);
+
+    ns.contracts[contract_no].custom_errors = custom_errors;
 }
 
 fn check_expression(expr: &Expression, call_list: &mut CallList) -> bool {
@@ -8,11 +8,12 @@ use super::{
     expression::{ExprContext, ResolveTo},
     Symtable,
 };
-use crate::sema::ast::{ConstructorAnnotations, SolanaAccount};
+use crate::sema::ast::{AccountConstraint, ConstructorAnnotations, SolanaAccount};
 use crate::sema::eval::{eval_const_number, EvaluationError};
 use crate::sema::expression::literals::number_literal;
 use crate::sema::expression::resolve_expression::expression;
 use crate::sema::solana_accounts::BuiltinAccounts;
+use crate::sema::unused_variable::used_variable;
 use crate::Target;
 use indexmap::map::Entry;
 use num_traits::ToPrimitive;
@@ -186,8 +187,9 @@ fn function_selector(
     }
 }
 
-/// Collect the seeds, bump, payer, and space for constructors. This is a no-op on Polkadot/EVM since
-/// there should be no seed or bump annotations permitted on other targets.
+/// Collect the seeds, bump, payer, and space for constructors, and the payer and account constraints
+/// for functions. This is a no-op on Polkadot/EVM since there should be no seed or bump annotations
+/// permitted on other targets.
 ///
 /// These annotations need a symbol table.
 pub(super) fn function_body_annotations(
@@ -208,6 +210,7 @@ pub(super) fn function_body_annotations(
     let mut has_annotation = false;
 
     let mut annotations = ConstructorAnnotations::default();
+    let mut constraints = Vec::new();
     let is_solana_constructor =
         ns.target == Target::Solana && ns.functions[function_no].ty == pt::FunctionTy::Constructor;
 
@@ -268,7 +271,22 @@ pub(super) fn function_body_annotations(
                     &mut has_annotation,
                 );
             }
-            "payer" if is_solana_constructor => {
+            "payer" if ns.target == Target::Solana => {
+                let func = &ns.functions[function_no];
+
+                // On functions, the payer tops up the rent when the data account grows
+                if !func.is_constructor()
+                    && (func.ty != pt::FunctionTy::Function
+                        || !matches!(func.visibility, Visibility::External(..)))
+                {
+                    diagnostics.push(Diagnostic::error(
+                        note.loc,
+                        "@payer annotation is only valid on constructors and functions declared as external"
+                            .to_string(),
+                    ));
+                    continue;
+                }
+
                 account_declaration(
                     &note.loc,
                     note.value.as_ref().unwrap(),
@@ -281,6 +299,19 @@ pub(super) fn function_body_annotations(
             "account" | "signer" | "mutableAccount" | "mutableSigner"
             // We already deal with these cases in `function_prototype_annotation`
                 if ns.target == Target::Solana => (),
+            "owner" | "seeds" | "hasOne" | "initIfNeeded" if ns.target == Target::Solana => {
+                if let Some(constraint) = account_constraint(
+                    function_no,
+                    note,
+                    &constraints,
+                    &mut diagnostics,
+                    context,
+                    ns,
+                    symtable,
+                ) {
+                    constraints.push(constraint);
+                }
+            }
 
             _ => diagnostics.push(Diagnostic::error(
                 note.loc,
@@ -380,9 +411,281 @@ pub(super) fn function_body_annotations(
         ));
     }
 
+    // An account created by the program must sign the CreateAccount instruction. If the account
+    // does not sign the transaction, the program can sign for it with the seeds of its address.
+    for constraint in &constraints {
+        if let AccountConstraint::InitIfNeeded { loc, account, .. } = constraint {
+            let is_signer = ns.functions[function_no].solana_accounts.borrow()[account].is_signer;
+
+            if !is_signer
+                && !constraints.iter().any(|other| {
+                    matches!(other, AccountConstraint::Seeds { .. }) && other.account() == account
+                })
+            {
+                diagnostics.push(Diagnostic::error(
+                    *loc,
+                    format!(
+                        "account '{account}' must be a signer or have a @seeds annotation to be initialized"
+                    ),
+                ));
+            }
+        }
+    }
+
     ns.diagnostics.extend(diagnostics);
 
     ns.functions[function_no].annotations = annotations;
+    ns.functions[function_no].account_constraints = constraints;
+}
+
+/// Resolve an account constraint annotation, like `@owner(acc, programId)`. The account must have
+/// been declared with an account annotation.
+fn account_constraint(
+    function_no: usize,
+    annotation: &pt::Annotation,
+    constraints: &[AccountConstraint],
+    diagnostics: &mut Diagnostics,
+    context: &mut ExprContext,
+    ns: &mut Namespace,
+    symtable: &mut Symtable,
+) -> Option<AccountConstraint> {
+    let func = &ns.functions[function_no];
+    let name = annotation.id.name.as_str();
+
+    if func.ty != pt::FunctionTy::Function || !matches!(func.visibility, Visibility::External(..)) {
+        diagnostics.push(Diagnostic::error(
+            annotation.loc,
+            "account constraints are only valid in functions declared as external".to_string(),
+        ));
+        return None;
+    }
+
+    let count = match name {
+        "hasOne" => 1,
+        "owner" => 2,
+        _ => 3,
+    };
+    let args = annotation_arguments(annotation, count, diagnostics)?;
+    let (account, declaration) = constraint_account(args[0], func, diagnostics)?;
+
+    if let Some(prev) = constraints
+        .iter()
+        .find(|prev| prev.name() == name && prev.account() == account)
+    {
+        duplicate_annotation(
+            diagnostics,
+            name,
+            annotation.loc,
+            prev.loc(),
+            &format!("account '{account}'"),
+        );
+        return None;
+    }
+
+    let loc = annotation.loc;
+
+    match name {
+        "owner" => {
+            let owner = constraint_expression(
+                args[1],
+                &Type::Address(false),
+                diagnostics,
+                context,
+                ns,
+                symtable,
+            )?;
+
+            Some(AccountConstraint::Owner {
+                loc,
+                account,
+                owner,
+            })
+        }
+        "seeds" => {
+            let pt::Expression::ArrayLiteral(_, values) = args[1] else {
+                diagnostics.push(Diagnostic::error(
+                    args[1].loc(),
+                    "expression must be an array literal".into(),
+                ));
+                return None;
+            };
+
+            // The bump is the last seed
+            if values.len() >= 16 {
+                diagnostics.push(Diagnostic::error(
+                    args[1].loc(),
+                    "a program derived address can have at most 16 seeds, including the bump"
+                        .into(),
+                ));
+                return None;
+            }
+
+            let ty = Type::Slice(Box::new(Type::Bytes(1)));
+            let seeds = values
+                .iter()
+                .map(|seed| constraint_expression(seed, &ty, diagnostics, context, ns, symtable))
+                .collect::<Vec<_>>();
+            let bump =
+                constraint_expression(args[2], &Type::Bytes(1), diagnostics, context, ns, symtable);
+
+            Some(AccountConstraint::Seeds {
+                loc,
+                account,
+                seeds: seeds.into_iter().collect::<Option<Vec<_>>>()?,
+                bump: bump?,
+            })
+        }
+        "hasOne" => {
+            // Like `has_one` in Anchor, the field of the data account has the name of the account
+            let field = expression(
+                args[0],
+                context,
+                ns,
+                symtable,
+                diagnostics,
+                ResolveTo::Type(&Type::Address(false)),
+            )
+            .ok()?;
+
+            if !matches!(field, Expression::StorageVariable { .. }) {
+                diagnostics.push(Diagnostic::error(
+                    args[0].loc(),
+                    format!("'@hasOne' annotation expects a contract storage variable named '{account}'"),
+                ));
+                return None;
+            }
+
+            used_variable(ns, &field, symtable);
+
+            let field = field
+                .cast(&args[0].loc(), &Type::Address(false), true, ns, diagnostics)
+                .ok()?;
+
+            Some(AccountConstraint::HasOne {
+                loc,
+                account,
+                field,
+            })
+        }
+        "initIfNeeded" => {
+            if !declaration.is_writer {
+                diagnostics.push(Diagnostic::error(
+                    args[0].loc(),
+                    format!("account '{account}' must be mutable to be initialized"),
+                ));
+                return None;
+            }
+
+            let (payer, payer_declaration) = constraint_account(args[1], func, diagnostics)?;
+
+            if !payer_declaration.is_signer || !payer_declaration.is_writer {
+                diagnostics.push(Diagnostic::error_with_note(
+                    args[1].loc(),
+                    format!("payer account '{payer}' must be declared with @mutableSigner"),
+                    payer_declaration.loc,
+                    "declaration".into(),
+                ));
+                return None;
+            }
+
+            let space = constraint_expression(
+                args[2],
+                &Type::Uint(64),
+                diagnostics,
+                context,
+                ns,
+                symtable,
+            )?;
+
+            Some(AccountConstraint::InitIfNeeded {
+                loc,
+                account,
+                payer,
+                space,
+            })
+        }
+        _ => unreachable!(),
+    }
+}
+
+/// Annotations with more than one argument have a list as value
+fn annotation_arguments<'a>(
+    annotation: &'a pt::Annotation,
+    count: usize,
+    diagnostics: &mut Diagnostics,
+) -> Option<Vec<&'a pt::Expression>> {
+    let args = match annotation.value.as_ref().unwrap() {
+        pt::Expression::List(_, list) => list
+            .iter()
+            .map(|(_, param)| param.as_ref().map(|param| &param.ty))
+            .collect::<Option<Vec<_>>>(),
+        value => Some(vec![value]),
+    };
+
+    match args {
+        Some(args) if args.len() == count => Some(args),
+        _ => {
+            diagnostics.push(Diagnostic::error(
+                annotation.loc,
+                format!(
+                    "'@{}' annotation expects {count} argument{}",
+                    annotation.id.name,
+                    if count == 1 { "" } else { "s" }
+                ),
+            ));
+            None
+        }
+    }
+}
+
+/// Find the declared account a constraint refers to
+fn constraint_account(
+    expr: &pt::Expression,
+    func: &Function,
+    diagnostics: &mut Diagnostics,
+) -> Option<(String, SolanaAccount)> {
+    let pt::Expression::Variable(id) = expr else {
+        diagnostics.push(Diagnostic::error(
+            expr.loc(),
+            "expected account name".to_string(),
+        ));
+        return None;
+    };
+
+    if let Some(account) = func.solana_accounts.borrow().get(&id.name) {
+        Some((id.name.clone(), *account))
+    } else {
+        diagnostics.push(Diagnostic::error(
+            id.loc,
+            format!("account '{}' not declared", id.name),
+        ));
+        None
+    }
+}
+
+/// Resolve an argument of a constraint to the given type. The arguments may use the function
+/// parameters.
+fn constraint_expression(
+    expr: &pt::Expression,
+    ty: &Type,
+    diagnostics: &mut Diagnostics,
+    context: &mut ExprContext,
+    ns: &mut Namespace,
+    symtable: &mut Symtable,
+) -> Option<Expression> {
+    let resolved = expression(
+        expr,
+        context,
+        ns,
+        symtable,
+        diagnostics,
+        ResolveTo::Type(ty),
+    )
+    .ok()?;
+
+    used_variable(ns, &resolved, symtable);
+
+    resolved.cast(&expr.loc(), ty, true, ns, diagnostics).ok()
 }
 
 /// Resolve the body annotations
@@ -484,7 +484,7 @@ pub fn contract_function(
 
     if func.ty == pt::FunctionTy::Constructor {
         // In the eth solidity only one constructor is allowed
-        if ns.target == Target::EVM {
+        if ns.target.is_ethereum_abi() {
             if let Some(prev_func_no) = ns.contracts[contract_no]
                 .functions
                 .iter()
@@ -910,7 +910,15 @@ pub fn resolve_params(
                     }
                 }
 
-                let ty = if !ty.can_have_data_location() {
+                let ty = if let Some(pt::StorageLocation::Transient(loc)) = p.storage {
+                    diagnostics.push(Diagnostic::error(
+                        loc,
+                        "data location 'transient' is only allowed for state variables".to_string(),
+                    ));
+                    success = false;
+
+                    ty
+                } else if !ty.can_have_data_location() {
                     if let Some(storage) = &p.storage {
                         diagnostics.push(Diagnostic::error(
                             storage.loc(),
@@ -1037,7 +1045,15 @@ pub fn resolve_returns(
                         success = false
                     }
                 }
-                let ty = if !ty.can_have_data_location() {
+                let ty = if let Some(pt::StorageLocation::Transient(loc)) = r.storage {
+                    diagnostics.push(Diagnostic::error(
+                        loc,
+                        "data location 'transient' is only allowed for state variables".to_string(),
+                    ));
+                    success = false;
+
+                    ty
+                } else if !ty.can_have_data_location() {
                     if let Some(storage) = &r.storage {
                         diagnostics.push(Diagnostic::error(
                             storage.loc(),
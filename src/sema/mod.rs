@@ -276,7 +276,7 @@ fn resolve_import(
         // import "solana"
         builtin_file_no
     } else {
-        match resolver.resolve_file(parent, &os_filename) {
+        match resolver.resolve_file(parent, &os_filename, ns.target) {
             Err(message) => {
                 ns.diagnostics
                     .push(ast::Diagnostic::error(filename.loc, message));
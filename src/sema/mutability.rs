@@ -9,7 +9,7 @@ use super::{
     yul::ast::{YulExpression, YulStatement},
     Recurse,
 };
-use crate::sema::ast::SolanaAccount;
+use crate::sema::ast::{AccountConstraint, SolanaAccount};
 use crate::sema::solana_accounts::BuiltinAccounts;
 use crate::sema::yul::builtin::YulBuiltInFunction;
 use crate::Target;
@@ -183,6 +183,17 @@ fn check_mutability(func: &Function, ns: &Namespace) -> Diagnostics {
         }
     }
 
+    for constraint in &func.account_constraints {
+        for expr in constraint.expressions() {
+            expr.recurse(&mut state, read_expression);
+        }
+
+        // Creating an account changes state
+        if let AccountConstraint::InitIfNeeded { loc, .. } = constraint {
+            state.write(loc);
+        }
+    }
+
     recurse_statements(&func.body, ns, &mut state);
 
     if pt::FunctionTy::Function == func.ty && !func.is_accessor {
@@ -289,8 +300,10 @@ fn recurse_statements(stmts: &[Statement], ns: &Namespace, state: &mut StateChec
             Statement::Expression(_, _, expr) => {
                 expr.recurse(state, read_expression);
             }
-            Statement::Delete(loc, _, _) => {
-                state.data_account |= DataAccountUsage::WRITE;
+            Statement::Delete(loc, _, expr) => {
+                if !is_transient(expr, ns) {
+                    state.data_account |= DataAccountUsage::WRITE;
+                }
                 state.write(loc)
             }
             Statement::Destructure(_, fields, expr) => {
@@ -334,10 +347,25 @@ fn recurse_statements(stmts: &[Statement], ns: &Namespace, state: &mut StateChec
     }
 }
 
+/// Transient variables do not live in the data account on Solana
+fn is_transient(expr: &Expression, ns: &Namespace) -> bool {
+    match expr {
+        Expression::StorageVariable {
+            contract_no,
+            var_no,
+            ..
+        } => ns.contracts[*contract_no].variables[*var_no].is_transient(),
+        Expression::StorageLoad { expr, .. } => is_transient(expr, ns),
+        _ => false,
+    }
+}
+
 fn read_expression(expr: &Expression, state: &mut StateCheck) -> bool {
     match expr {
-        Expression::StorageLoad { loc, .. } => {
-            state.data_account |= DataAccountUsage::READ;
+        Expression::StorageLoad { loc, expr, .. } => {
+            if !is_transient(expr, state.ns) {
+                state.data_account |= DataAccountUsage::READ;
+            }
             state.read(loc)
         }
         Expression::PreIncrement { expr, .. }
@@ -356,7 +384,9 @@ fn read_expression(expr: &Expression, state: &mut StateCheck) -> bool {
             state.read(loc);
         }
         Expression::StorageVariable { loc, .. } => {
-            state.data_account |= DataAccountUsage::READ;
+            if !is_transient(expr, state.ns) {
+                state.data_account |= DataAccountUsage::READ;
+            }
             state.read(loc);
         }
         Expression::Builtin {
@@ -390,7 +420,13 @@ fn read_expression(expr: &Expression, state: &mut StateCheck) -> bool {
                 | Builtin::Balance
                 | Builtin::Accounts
                 | Builtin::ContractCode
-                | Builtin::ContractCodehash,
+                | Builtin::ContractCodehash
+                | Builtin::NetworkId
+                | Builtin::SysvarRent
+                | Builtin::SysvarEpochSchedule
+                | Builtin::CurrentInstructionIndex
+                | Builtin::InstructionAt
+                | Builtin::PrngU64,
             ..
         } => state.read(loc),
 
@@ -469,7 +505,9 @@ fn write_expression(expr: &Expression, state: &mut StateCheck) -> bool {
             }
         }
         Expression::StorageVariable { loc, .. } => {
-            state.data_account |= DataAccountUsage::WRITE;
+            if !is_transient(expr, state.ns) {
+                state.data_account |= DataAccountUsage::WRITE;
+            }
             state.write(loc);
             return false;
         }
@@ -3,7 +3,7 @@
 use super::{
     ast::{
         ArrayLength, Diagnostic, Mapping, Mutability, Namespace, Note, Parameter, RetrieveType,
-        Symbol, Type,
+        StructType, Symbol, Type,
     },
     builtin,
     diagnostics::Diagnostics,
@@ -43,6 +43,8 @@ impl Namespace {
             Target::Solana => (32, 8),
             Target::Soroban => (32, 64),
             Target::Stylus => (20, 32),
+            // pallet-revive uses Ethereum addresses and 256 bit balances
+            Target::PolkaVM => (20, 32),
         };
 
         let mut ns = Namespace {
@@ -72,7 +74,7 @@ impl Namespace {
 
         match target {
             Target::Solana => ns.add_solana_builtins(),
-            Target::Polkadot { .. } => ns.add_polkadot_builtins(),
+            Target::Polkadot { .. } | Target::PolkaVM => ns.add_polkadot_builtins(),
             Target::Soroban => ns.add_soroban_builtins(),
             Target::Stylus => ns.add_stylus_builtins(),
             _ => {}
@@ -1194,6 +1196,13 @@ impl Namespace {
                         Type::Address(true)
                     }
                 }
+                pt::Type::Fixed(..) | pt::Type::Ufixed(..) if self.target == Target::Soroban => {
+                    diagnostics.push(Diagnostic::decl_error(
+                        id.loc(),
+                        "fixed point types are not supported on Soroban".to_string(),
+                    ));
+                    return Err(());
+                }
                 _ => {
                     let mut ty = Type::from(ty);
                     // Apply Soroban integer width rounding if target is Soroban
@@ -1219,8 +1228,21 @@ impl Namespace {
             _ => unreachable!(),
         };
 
+        let unqualified = namespace.is_empty();
+
         let s = self.resolve_namespace(namespace, file_no, contract_no, &id, diagnostics)?;
 
+        let sysvar;
+
+        let s = match s {
+            None if unqualified && self.target == Target::Solana => {
+                sysvar = StructType::solana_sysvar(&id.name)
+                    .map(|str_ty| Symbol::Struct(pt::Loc::Builtin, str_ty));
+                sysvar.as_ref()
+            }
+            s => s,
+        };
+
         match s {
             None => {
                 diagnostics.push(Diagnostic::decl_error(
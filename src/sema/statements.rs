@@ -962,15 +962,14 @@ fn revert_pos_arg(
             ));
         }
 
-        if ns.target == Target::Solana {
-            ns.diagnostics.push(Diagnostic::error(
-                *loc,
-                format!("revert with custom errors not supported on {}", ns.target),
-            ));
-        }
-
         ns.errors[error_no].used = true;
 
+        if let Some(function_no) = context.function_no {
+            if !ns.functions[function_no].reverts_errors.contains(&error_no) {
+                ns.functions[function_no].reverts_errors.push(error_no);
+            }
+        }
+
         Ok(Statement::Revert {
             loc: *loc,
             error_no: Some(error_no),
@@ -1134,13 +1133,6 @@ fn revert_named_arg(
             }
         }
 
-        if ns.target == Target::Solana {
-            ns.diagnostics.push(Diagnostic::error(
-                *loc,
-                format!("revert with custom errors not supported on {}", ns.target),
-            ));
-        }
-
         let mut args = Vec::new();
         let error = &ns.errors[error_no];
 
@@ -1159,6 +1151,12 @@ fn revert_named_arg(
 
         ns.errors[error_no].used = true;
 
+        if let Some(function_no) = context.function_no {
+            if !ns.functions[function_no].reverts_errors.contains(&error_no) {
+                ns.functions[function_no].reverts_errors.push(error_no);
+            }
+        }
+
         Ok(Statement::Revert {
             loc: *loc,
             error_no: Some(error_no),
@@ -1952,6 +1950,14 @@ fn resolve_var_decl_ty(
     )?;
 
     if let Some(storage) = storage {
+        if let pt::StorageLocation::Transient(loc) = storage {
+            diagnostics.push(Diagnostic::error(
+                *loc,
+                "data location 'transient' is only allowed for state variables".to_string(),
+            ));
+            return Err(());
+        }
+
         if !var_ty.can_have_data_location() {
             diagnostics.push(Diagnostic::error(
                 storage.loc(),
@@ -236,7 +236,13 @@ fn type_decl(
     // - Need for circular checks (type a is b; type b is a;)
     if !matches!(
         ty,
-        Type::Address(_) | Type::Bool | Type::Int(_) | Type::Uint(_) | Type::Bytes(_)
+        Type::Address(_)
+            | Type::Bool
+            | Type::Int(_)
+            | Type::Uint(_)
+            | Type::Fixed(..)
+            | Type::Ufixed(..)
+            | Type::Bytes(_)
     ) {
         ns.diagnostics.push(Diagnostic::error(
             def.ty.loc(),
@@ -832,6 +838,15 @@ fn event_decl(
                 "mapping type is not permitted as event field".to_string(),
             ));
             ty = Type::Unresolved;
+        } else if ns.target == Target::Soroban && !soroban_value_type(&ty, ns) {
+            ns.diagnostics.push(Diagnostic::error(
+                field.ty.loc(),
+                format!(
+                    "type '{}' is not supported as event field on Soroban",
+                    ty.to_string(ns)
+                ),
+            ));
+            ty = Type::Unresolved;
         }
 
         let name = if let Some(name) = &field.name {
@@ -918,6 +933,26 @@ fn event_decl(
     (doc, fields)
 }
 
+/// Can values of the type be converted to and from a Soroban `Val`, so that they can be passed
+/// to and from the host
+pub(super) fn soroban_value_type(ty: &Type, ns: &Namespace) -> bool {
+    match ty {
+        Type::Uint(32 | 64 | 128 | 256)
+        | Type::Int(32 | 64 | 128 | 256)
+        | Type::Bool
+        | Type::Address(_)
+        | Type::DynamicBytes
+        | Type::String
+        | Type::Enum(_)
+        | Type::Unresolved => true,
+        Type::Struct(StructType::UserDefined(struct_no)) => ns.structs[*struct_no]
+            .fields
+            .iter()
+            .all(|field| soroban_value_type(&field.ty, ns)),
+        _ => false,
+    }
+}
+
 /// Resolve an error definition which can be defined in a contract or outside, e.g:
 /// error Foo(int bar, bool foo);
 /// contract {
@@ -1213,6 +1248,8 @@ impl Type {
             Type::Address(true) => "address payable".to_string(),
             Type::Int(n) => format!("int{n}"),
             Type::Uint(n) => format!("uint{n}"),
+            Type::Fixed(m, n) => format!("fixed{m}x{n}"),
+            Type::Ufixed(m, n) => format!("ufixed{m}x{n}"),
             Type::Rational => "rational".to_string(),
             Type::Value => format!("uint{}", ns.value_length * 8),
             Type::Bytes(n) => format!("bytes{n}"),
@@ -1313,6 +1350,7 @@ impl Type {
             Type::Address(_) => true,
             Type::Int(_) => true,
             Type::Uint(_) => true,
+            Type::Fixed(..) | Type::Ufixed(..) => true,
             Type::Bytes(_) => true,
             Type::Rational => true,
             Type::Value => true,
@@ -1329,6 +1367,8 @@ impl Type {
             Type::Contract(_) | Type::Address(_) => "address".to_string(),
             Type::Int(n) => format!("int{n}"),
             Type::Uint(n) => format!("uint{n}"),
+            Type::Fixed(m, n) => format!("fixed{m}x{n}"),
+            Type::Ufixed(m, n) => format!("ufixed{m}x{n}"),
             Type::Rational => "rational".to_string(),
             Type::Bytes(n) => format!("bytes{n}"),
             Type::DynamicBytes => "bytes".to_string(),
@@ -1404,6 +1444,7 @@ impl Type {
             Type::Address(_) => false,
             Type::Int(_) => false,
             Type::Uint(_) => false,
+            Type::Fixed(..) | Type::Ufixed(..) => false,
             Type::Rational => false,
             Type::Bytes(_) => false,
             Type::Enum(_) => false,
@@ -1485,7 +1526,9 @@ impl Type {
             Type::Contract(_) | Type::Address(_) => BigInt::from(ns.address_length),
             Type::Bytes(n) => BigInt::from(*n),
             Type::Value => BigInt::from(ns.value_length),
-            Type::Uint(n) | Type::Int(n) => BigInt::from(n / 8),
+            Type::Uint(n) | Type::Int(n) | Type::Fixed(n, _) | Type::Ufixed(n, _) => {
+                BigInt::from(n / 8)
+            }
             Type::Rational => unreachable!(),
             Type::Array(_, dims) if dims.first() == Some(&ArrayLength::Dynamic) => {
                 (ns.target.ptr_size() / 8).into()
@@ -1549,7 +1592,9 @@ impl Type {
             Type::Value => {
                 BigInt::from(ns.value_length.next_power_of_two())
             }
-            Type::Int(n) | Type::Uint(n) => BigInt::from(n / 8),
+            Type::Int(n) | Type::Uint(n) | Type::Fixed(n, _) | Type::Ufixed(n, _) => {
+                BigInt::from(n / 8)
+            }
             Type::Rational => unreachable!(),
             Type::Array(ty, dims) => {
                 if dims.contains(&ArrayLength::Dynamic) {
@@ -1623,6 +1668,7 @@ impl Type {
             Type::Uint(n) | Type::Int(n) if *n <= 16 => 2,
             Type::Uint(n) | Type::Int(n) if *n <= 32 => 4,
             Type::Uint(_) | Type::Int(_) => 8,
+            Type::Fixed(n, _) | Type::Ufixed(n, _) => Type::Uint(*n).align_of(ns),
             Type::Struct(str_ty) => str_ty
                 .definition(ns)
                 .fields
@@ -1642,6 +1688,7 @@ impl Type {
             Type::Bool => 1,
             Type::Int(n) => (*n).div_ceil(8) as u8,
             Type::Uint(n) => (*n).div_ceil(8) as u8,
+            Type::Fixed(n, _) | Type::Ufixed(n, _) => (*n).div_ceil(8) as u8,
             Type::Rational => unreachable!(),
             Type::Bytes(n) => *n,
             Type::Enum(n) => ns.enums[*n].ty.bytes(ns),
@@ -1660,6 +1707,7 @@ impl Type {
             Type::Bool => 1,
             Type::Int(n) => *n,
             Type::Uint(n) => *n,
+            Type::Fixed(n, _) | Type::Ufixed(n, _) => *n,
             Type::Rational => unreachable!(),
             Type::Bytes(n) => *n as u16 * 8,
             Type::Enum(n) => ns.enums[*n].ty.bits(ns),
@@ -1687,6 +1735,7 @@ impl Type {
     pub fn is_signed_int(&self, ns: &Namespace) -> bool {
         match self {
             Type::Int(_) => true,
+            Type::Fixed(..) => true,
             Type::Ref(r) => r.is_signed_int(ns),
             Type::StorageRef(_, r) => r.is_signed_int(ns),
             Type::UserType(user) => ns.user_types[*user].ty.is_signed_int(ns),
@@ -1707,6 +1756,26 @@ impl Type {
         }
     }
 
+    /// Is this a fixed point type, i.e. `fixed<M>x<N>` or `ufixed<M>x<N>`
+    pub fn is_fixed(&self) -> bool {
+        match self {
+            Type::Fixed(..) | Type::Ufixed(..) => true,
+            Type::Ref(r) => r.is_fixed(),
+            Type::StorageRef(_, r) => r.is_fixed(),
+            _ => false,
+        }
+    }
+
+    /// The number of decimals of a fixed point type
+    pub fn fixed_decimals(&self) -> Option<u8> {
+        match self {
+            Type::Fixed(_, n) | Type::Ufixed(_, n) => Some(*n),
+            Type::Ref(r) => r.fixed_decimals(),
+            Type::StorageRef(_, r) => r.fixed_decimals(),
+            _ => None,
+        }
+    }
+
     pub fn is_rational(&self) -> bool {
         match self {
             Type::Rational => true,
@@ -1726,7 +1795,9 @@ impl Type {
                 Type::Contract(_) | Type::Address(_) => BigInt::from(ns.address_length),
                 Type::Bytes(n) => BigInt::from(*n),
                 Type::Value => BigInt::from(ns.value_length),
-                Type::Uint(n) | Type::Int(n) => BigInt::from(n / 8),
+                Type::Uint(n) | Type::Int(n) | Type::Fixed(n, _) | Type::Ufixed(n, _) => {
+                    BigInt::from(n / 8)
+                }
                 Type::Rational => unreachable!(),
                 Type::Array(_, dims) if dims.contains(&ArrayLength::Dynamic) => {
                     let size = dynamic_array_size(dims);
@@ -1810,7 +1881,9 @@ impl Type {
                 Type::Contract(_) | Type::Address(_) => BigInt::from(ns.address_length),
                 Type::Bytes(n) => BigInt::from(*n),
                 Type::Value => BigInt::from(ns.value_length),
-                Type::Uint(n) | Type::Int(n) => BigInt::from(n / 8),
+                Type::Uint(n) | Type::Int(n) | Type::Fixed(n, _) | Type::Ufixed(n, _) => {
+                    BigInt::from(n / 8)
+                }
                 Type::Rational => unreachable!(),
                 Type::Array(_, dims) if dims.contains(&ArrayLength::Dynamic) => BigInt::from(4),
                 Type::Array(ty, _) => {
@@ -1854,6 +1927,7 @@ impl Type {
             Type::Address(_) => false,
             Type::Int(_) => false,
             Type::Uint(_) => false,
+            Type::Fixed(..) | Type::Ufixed(..) => false,
             Type::Rational => false,
             Type::Bytes(_) => false,
             Type::Enum(_) => false,
@@ -2091,6 +2165,8 @@ impl Type {
             Type::Address(_) => "address".to_string(),
             Type::Int(n) => format!("int{n}"),
             Type::Uint(n) => format!("uint{n}"),
+            Type::Fixed(m, n) => format!("fixed{m}x{n}"),
+            Type::Ufixed(m, n) => format!("ufixed{m}x{n}"),
             Type::Bytes(n) => format!("bytes{n}"),
             Type::DynamicBytes => "bytes".to_string(),
             Type::String => "string".to_string(),
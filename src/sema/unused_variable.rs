@@ -137,6 +137,18 @@ pub fn used_variable(ns: &mut Namespace, exp: &Expression, symtable: &mut Symtab
             used_variable(ns, index, symtable);
         }
 
+        Expression::ArraySlice {
+            array, start, end, ..
+        } => {
+            used_variable(ns, array, symtable);
+            if let Some(start) = start {
+                used_variable(ns, start, symtable);
+            }
+            if let Some(end) = end {
+                used_variable(ns, end, symtable);
+            }
+        }
+
         Expression::Builtin {
             kind: Builtin::ArrayLength,
             args,
@@ -11,6 +11,7 @@ use super::{
     expression::{ExprContext, ResolveTo},
     symtable::{Symtable, VariableInitializer, VariableUsage},
     tags::resolve_tags,
+    types::soroban_value_type,
     ContractDefinition,
 };
 use crate::sema::expression::resolve_expression::expression;
@@ -251,7 +252,53 @@ pub fn variable_decl<'a>(
         }
     }
 
-    if ns.target == Target::Soroban {
+    if let Some(pt::StorageType::Transient(loc)) = &storage_type {
+        let loc = loc.unwrap_or(def.loc);
+
+        if constant || has_immutable.is_some() {
+            ns.diagnostics.push(Diagnostic::error(
+                loc,
+                "transient storage cannot be used for constant or immutable variables".to_string(),
+            ));
+            return None;
+        }
+
+        if contract_no.is_some()
+            && (ty.is_reference_type(ns)
+                || matches!(
+                    ty.deref_any(),
+                    Type::InternalFunction { .. } | Type::ExternalFunction { .. }
+                ))
+        {
+            ns.diagnostics.push(Diagnostic::error(
+                loc,
+                format!(
+                    "transient storage is not supported for variable of type '{}'",
+                    ty.to_string(ns)
+                ),
+            ));
+            return None;
+        }
+
+        if let Some(initializer) = &def.initializer {
+            ns.diagnostics.push(Diagnostic::error(
+                initializer.loc(),
+                "transient storage variables cannot have an initializer".to_string(),
+            ));
+            return None;
+        }
+    } else if ns.target == Target::Soroban {
+        if !constant && !soroban_storage_type(&ty, ns) {
+            ns.diagnostics.push(Diagnostic::error(
+                def.ty.loc(),
+                format!(
+                    "type '{}' is not supported for storage variables on Soroban",
+                    ty.to_string(ns)
+                ),
+            ));
+            return None;
+        }
+
         if storage_type.is_none() {
             ns.diagnostics.push(Diagnostic::warning(
                 def.loc,
@@ -553,6 +600,18 @@ pub fn variable_decl<'a>(
     ret
 }
 
+/// Can the type be held in Soroban storage. Storage arrays and mappings hold each element as a
+/// separate `Val`, so only their element, key and value types need a `Val` conversion
+fn soroban_storage_type(ty: &Type, ns: &Namespace) -> bool {
+    match ty {
+        Type::Mapping(Mapping { key, value, .. }) => {
+            soroban_value_type(key, ns) && soroban_storage_type(value, ns)
+        }
+        Type::Array(elem_ty, dims) if dims.len() == 1 => soroban_value_type(elem_ty, ns),
+        _ => soroban_value_type(ty, ns),
+    }
+}
+
 /// For accessor functions, create the parameter list and the return expression
 fn collect_parameters(
     ty: &Type,
@@ -20,7 +20,7 @@ impl YulBuiltinPrototype {
     pub fn is_available(&self, target: &Target) -> bool {
         match target {
             Target::EVM => self.availability[0],
-            Target::Polkadot { .. } => self.availability[1],
+            Target::Polkadot { .. } | Target::PolkaVM => self.availability[1],
             Target::Solana => self.availability[2],
             Target::Soroban => unimplemented!(),
             Target::Stylus => self.availability[0], // TODO: stylus should have its own availability
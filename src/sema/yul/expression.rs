@@ -624,6 +624,7 @@ fn resolve_suffix_access(
         | YulExpression::YulLocalVariable(..)
         | YulExpression::SolidityLocalVariable(_, _, Some(StorageLocation::Memory(_)), ..)
         | YulExpression::SolidityLocalVariable(_, _, Some(StorageLocation::Calldata(_)), ..)
+        | YulExpression::SolidityLocalVariable(_, _, Some(StorageLocation::Transient(_)), ..)
         | YulExpression::SolidityLocalVariable(_, _, None, ..)
         | YulExpression::BuiltInCall(..)
         | YulExpression::FunctionCall(..)
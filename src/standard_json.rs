@@ -11,12 +11,26 @@ pub struct EwasmContract {
     pub wasm: String,
 }
 
+#[derive(Serialize)]
+pub struct EvmBytecode {
+    pub object: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvmContract {
+    pub bytecode: EvmBytecode,
+    pub deployed_bytecode: EvmBytecode,
+}
+
 #[derive(Serialize)]
 pub struct JsonContract {
     pub abi: Vec<ABI>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ewasm: Option<EwasmContract>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub evm: Option<EvmContract>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub minimum_space: Option<u32>,
 }
 
@@ -5,7 +5,7 @@
 #include <stdbool.h>
 #include "stdlib.h"
 
-#ifndef __wasm__
+#if !defined(__wasm__) && !defined(__riscv)
 #include "solana_sdk.h"
 #endif
 
@@ -35,6 +35,21 @@ void __init_heap()
     first->allocated = false;
     first->length = (uint32_t)(__builtin_wasm_memory_size(0) * 0x10000 - (size_t)first - sizeof(struct chunk));
 }
+#elif defined(__riscv)
+// PolkaVM programs have no memory growth, so the heap is a fixed size array in the data section
+#define HEAP_SIZE (64 * 1024)
+
+static uint64_t heap[HEAP_SIZE / sizeof(uint64_t)];
+
+#define HEAP_START ((struct chunk *)heap)
+
+void __init_heap()
+{
+    struct chunk *first = HEAP_START;
+    first->next = first->prev = NULL;
+    first->allocated = false;
+    first->length = HEAP_SIZE - sizeof(struct chunk);
+}
 #else
 #define HEAP_START ((struct chunk *)0x300000000)
 
@@ -113,6 +128,8 @@ void *__attribute__((noinline)) __malloc(uint32_t size)
         // go bang
 #ifdef __wasm__
         __builtin_unreachable();
+#elif defined(__riscv)
+        __builtin_trap();
 #else
         sol_log("out of heap memory");
         sol_panic();
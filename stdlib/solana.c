@@ -35,6 +35,7 @@ uint64_t entrypoint(const uint8_t *input)
 
     params.ka_clock = NULL;
     params.ka_instructions = NULL;
+    params.transient = NULL;
 
     for (int account_no = 0; account_no < params.ka_num; account_no++)
     {
@@ -50,6 +51,19 @@ uint64_t entrypoint(const uint8_t *input)
         }
     }
 
+    // The runtime lets the data account grow in place by a limited amount, see account_data_grow()
+    params.data_len_limit = 0;
+
+    if (params.ka_num > 0 && params.ka[0].is_writable)
+    {
+        params.data_len_limit = params.ka[0].data_len + MAX_PERMITTED_DATA_INCREASE;
+
+        if (params.data_len_limit > MAX_PERMITTED_DATA_LENGTH)
+        {
+            params.data_len_limit = MAX_PERMITTED_DATA_LENGTH;
+        }
+    }
+
     __init_heap();
 
     return solang_dispatch(&params);
@@ -195,6 +209,179 @@ struct clock_layout *sol_clock(SolParameters *params)
     return clock_data;
 }
 
+extern struct vector *vector_new(uint32_t members, uint32_t size, uint8_t *initial);
+extern uint64_t sol_get_rent_sysvar(void *ret);
+extern uint64_t sol_get_epoch_schedule_sysvar(void *ret);
+
+// The syscall writes the exemption threshold as a double, which is replaced with a fixed point
+// number with 32 fractional bits to match the layout of the Rent builtin struct
+struct rent
+{
+    uint64_t lamports_per_byte_year;
+    uint64_t exemption_threshold;
+    uint8_t burn_percent;
+};
+
+struct epoch_schedule
+{
+    uint64_t slots_per_epoch;
+    uint64_t leader_schedule_slot_offset;
+    bool warmup;
+    uint64_t first_normal_epoch;
+    uint64_t first_normal_slot;
+};
+
+// Convert a positive double to a fixed point number with 32 fractional bits, rounding down. Floating
+// point is not available on BPF, so this is done on the bits of the double. The mantissa has 52
+// fractional bits, so it is shifted by the exponent less 20.
+static uint64_t double_to_fixed32(uint64_t bits)
+{
+    int64_t exponent = (int64_t)((bits >> 52) & 0x7ff) - 1023;
+    uint64_t mantissa = (bits & 0xfffffffffffffull) | (1ull << 52);
+
+    if ((bits >> 63) || exponent < -43)
+    {
+        return 0;
+    }
+    else if (exponent > 31)
+    {
+        return UINT64_MAX;
+    }
+    else if (exponent >= 20)
+    {
+        return mantissa << (exponent - 20);
+    }
+    else
+    {
+        return mantissa >> (20 - exponent);
+    }
+}
+
+struct rent *sol_rent()
+{
+    struct rent *rent = __malloc(sizeof(struct rent));
+
+    if (sol_get_rent_sysvar(rent))
+    {
+        sol_log("failed to get rent sysvar");
+        sol_panic();
+    }
+
+    rent->exemption_threshold = double_to_fixed32(rent->exemption_threshold);
+
+    return rent;
+}
+
+struct epoch_schedule *sol_epoch_schedule()
+{
+    struct epoch_schedule *schedule = __malloc(sizeof(struct epoch_schedule));
+
+    if (sol_get_epoch_schedule_sysvar(schedule))
+    {
+        sol_log("failed to get epoch schedule sysvar");
+        sol_panic();
+    }
+
+    return schedule;
+}
+
+// This is the layout of the Instruction builtin struct
+struct instruction
+{
+    SolPubkey program_id;
+    struct vector *accounts;
+    struct vector *data;
+};
+
+static const SolAccountInfo *sol_instructions_account(SolParameters *params)
+{
+    if (!params->ka_instructions)
+    {
+        sol_log("instructions sysvar account missing from transaction");
+        sol_panic();
+    }
+
+    return params->ka_instructions;
+}
+
+// Abort if the instructions sysvar is shorter than the data which is about to be read
+static void sol_instructions_check(const SolAccountInfo *acc, uint64_t end)
+{
+    if (end > acc->data_len)
+    {
+        sol_log("instructions sysvar data too short");
+        sol_panic();
+    }
+}
+
+// The current instruction index is stored in the last two bytes of the instructions sysvar
+uint16_t sol_current_instruction_index(SolParameters *params)
+{
+    const SolAccountInfo *acc = sol_instructions_account(params);
+
+    sol_instructions_check(acc, 2);
+
+    return *(uint16_t *)(acc->data + acc->data_len - 2);
+}
+
+struct instruction *sol_instruction_at(uint16_t index, SolParameters *params)
+{
+    const SolAccountInfo *acc = sol_instructions_account(params);
+    uint16_t *offsets = (uint16_t *)acc->data;
+
+    sol_instructions_check(acc, 2);
+
+    if (index >= offsets[0])
+    {
+        sol_log("instruction index out of bounds");
+        sol_panic();
+    }
+
+    sol_instructions_check(acc, 2 + 2 * ((uint64_t)index + 1));
+
+    uint64_t offset = offsets[1 + index];
+
+    sol_instructions_check(acc, offset + 2);
+
+    uint8_t *instr = acc->data + offset;
+    uint16_t num_accounts = *(uint16_t *)instr;
+
+    // the accounts, the program id and the length of the data
+    offset += 2 + (uint64_t)num_accounts * (1 + SIZE_PUBKEY) + SIZE_PUBKEY + 2;
+
+    sol_instructions_check(acc, offset);
+
+    instr += 2;
+
+    struct instruction *res = __malloc(sizeof(struct instruction));
+
+    res->accounts = vector_new(num_accounts, sizeof(SolAccountMeta), VECTOR_EMPTY);
+
+    SolAccountMeta *metas = (SolAccountMeta *)res->accounts->data;
+
+    // each account is a byte of flags followed by the pubkey
+    for (uint16_t i = 0; i < num_accounts; i++)
+    {
+        metas[i].is_signer = (instr[0] & 1) != 0;
+        metas[i].is_writable = (instr[0] & 2) != 0;
+        metas[i].pubkey = (SolPubkey *)(instr + 1);
+
+        instr += 1 + SIZE_PUBKEY;
+    }
+
+    __memcpy(&res->program_id, instr, SIZE_PUBKEY);
+
+    instr += SIZE_PUBKEY;
+
+    uint16_t data_len = *(uint16_t *)instr;
+
+    sol_instructions_check(acc, offset + data_len);
+
+    res->data = vector_new(data_len, 1, instr + 2);
+
+    return res;
+}
+
 struct account_data_header
 {
     uint32_t magic;
@@ -219,8 +406,38 @@ struct chunk
 
 #define ROUND_UP(n, d) (((n) + (d) - 1) & ~(d - 1))
 
-uint64_t account_data_alloc(SolAccountInfo *ai, uint32_t size, uint32_t *res)
+// Grow the data account so it is at least size bytes long. The account is serialized with
+// MAX_PERMITTED_DATA_INCREASE bytes of spare room after its data, so it can be grown in place by
+// updating the serialized length. The runtime resizes the account when the instruction completes.
+// The account must hold enough lamports to stay rent exempt at its new size by then.
+static uint64_t account_data_grow(SolParameters *params, uint64_t size)
 {
+    SolAccountInfo *ai = &params->ka[0];
+
+    // Grow by at least 1KiB so that pushing to a storage array does not grow the account each time
+    uint64_t new_len = ROUND_UP(size, 1024);
+
+    if (new_len > params->data_len_limit)
+    {
+        new_len = params->data_len_limit;
+    }
+
+    if (new_len < size)
+    {
+        return ERROR_ACCOUNT_DATA_TOO_SMALL;
+    }
+
+    __memset(ai->data + ai->data_len, 0, new_len - ai->data_len);
+
+    *(uint64_t *)(ai->data - sizeof(uint64_t)) = new_len;
+    ai->data_len = new_len;
+
+    return 0;
+}
+
+uint64_t account_data_alloc(SolParameters *params, uint32_t size, uint32_t *res)
+{
+    SolAccountInfo *ai = &params->ka[0];
     void *data = ai->data;
     struct account_data_header *hdr = data;
 
@@ -248,7 +465,9 @@ uint64_t account_data_alloc(SolAccountInfo *ai, uint32_t size, uint32_t *res)
 
                 if (offset + alloc_size + sizeof(struct chunk) >= ai->data_len)
                 {
-                    return ERROR_ACCOUNT_DATA_TOO_SMALL;
+                    uint64_t rc = account_data_grow(params, offset + alloc_size + sizeof(struct chunk) + 1);
+                    if (rc)
+                        return rc;
                 }
 
                 chunk->offset_next = offset + alloc_size;
@@ -394,8 +613,10 @@ void account_data_free(void *data, uint32_t offset)
     }
 }
 
-uint64_t account_data_realloc(SolAccountInfo *ai, uint32_t offset, uint32_t size, uint32_t *res)
+uint64_t account_data_realloc(SolParameters *params, uint32_t offset, uint32_t size, uint32_t *res)
 {
+    SolAccountInfo *ai = &params->ka[0];
+
     if (!size)
     {
         account_data_free(ai->data, offset);
@@ -405,7 +626,7 @@ uint64_t account_data_realloc(SolAccountInfo *ai, uint32_t offset, uint32_t size
 
     if (!offset)
     {
-        return account_data_alloc(ai, size, res);
+        return account_data_alloc(params, size, res);
     }
 
     void *data = ai->data;
@@ -522,7 +743,8 @@ uint64_t account_data_realloc(SolAccountInfo *ai, uint32_t offset, uint32_t size
         }
         else
         {
-            if (offset + alloc_size + sizeof(struct chunk) < ai->data_len)
+            if (offset + alloc_size + sizeof(struct chunk) < ai->data_len ||
+                !account_data_grow(params, offset + alloc_size + sizeof(struct chunk) + 1))
             {
                 chunk->offset_next = offset + alloc_size;
                 chunk->length = size;
@@ -542,7 +764,7 @@ uint64_t account_data_realloc(SolAccountInfo *ai, uint32_t offset, uint32_t size
 
     uint32_t old_length = account_data_len(data, offset);
     uint32_t new_offset;
-    uint64_t rc = account_data_alloc(ai, size, &new_offset);
+    uint64_t rc = account_data_alloc(params, size, &new_offset);
     if (rc)
         return rc;
 
@@ -625,9 +847,10 @@ void validate_heap(void *data, uint32_t offs[100], uint32_t lens[100])
 int main()
 {
     uint8_t data[0x10000];
-    SolAccountInfo ai;
-    ai.data = data;
-    ai.data_len = sizeof(data);
+    SolParameters params;
+    params.ka[0].data = data;
+    params.ka[0].data_len = sizeof(data);
+    params.data_len_limit = sizeof(data);
     uint32_t offs[100], lens[100];
     uint32_t allocs = 0;
 
@@ -653,7 +876,7 @@ int main()
         if (offs[n] == 0)
         {
             // printf("STEP: alloc %d\n", n);
-            status = account_data_alloc(&ai, 100, &new_offset);
+            status = account_data_alloc(&params, 100, &new_offset);
             assert(status == 0);
             offs[n] = new_offset;
             memset(data + offs[n], n, 100);
@@ -662,15 +885,15 @@ int main()
         else if (rand() % 2)
         {
             // printf("STEP: free %d (0x%x)\n", n, offs[n]);
-            account_data_free(ai.data, offs[n]);
+            account_data_free(params.ka[0].data, offs[n]);
             offs[n] = 0;
         }
         else
         {
             // printf("STEP: realloc %d (0x%x)\n", n, offs[n]);
             int size = (rand() % 200) + 10;
-            int old_size = account_data_len(ai.data, offs[n]);
-            status = account_data_realloc(&ai, offs[n], size, &new_offset);
+            int old_size = account_data_len(params.ka[0].data, offs[n]);
+            status = account_data_realloc(&params, offs[n], size, &new_offset);
             assert(status == 0);
             offs[n] = new_offset;
             if (size > old_size)
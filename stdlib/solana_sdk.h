@@ -217,6 +217,8 @@ typedef struct
     SolPubkey *program_id; /** program_id of the currently executing program */
     const SolAccountInfo *ka_clock;
     const SolAccountInfo *ka_instructions;
+    uint64_t data_len_limit; /** The data account may not grow beyond this length during this instruction */
+    uint8_t *transient;      /** The transient variables, allocated on first use during this instruction */
 } SolParameters;
 
 /**
@@ -224,6 +226,11 @@ typedef struct
  */
 #define MAX_PERMITTED_DATA_INCREASE (1024 * 10)
 
+/**
+ * Maximum length of the data of an account
+ */
+#define MAX_PERMITTED_DATA_LENGTH (10 * 1024 * 1024)
+
 /**
  * De-serializes the input parameters into usable types
  *
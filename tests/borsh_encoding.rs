@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use anchor_syn::idl::types::{IdlType, IdlTypeDefinition, IdlTypeDefinitionTy};
+use anchor_lang_idl::types::{IdlArrayLen, IdlDefinedFields, IdlType, IdlTypeDef, IdlTypeDefTy};
 use byte_slice_cast::AsByteSlice;
 use num_bigint::{BigInt, Sign};
 use num_traits::ToPrimitive;
@@ -246,10 +246,10 @@ pub fn decode_at_offset(
     data: &[u8],
     offset: &mut usize,
     ty: &IdlType,
-    custom_types: &[IdlTypeDefinition],
+    custom_types: &[IdlTypeDef],
 ) -> BorshToken {
     match ty {
-        IdlType::PublicKey => {
+        IdlType::Pubkey => {
             let read = &data[*offset..(*offset + 32)];
             (*offset) += 32;
             BorshToken::Address(<[u8; 32]>::try_from(read).unwrap())
@@ -300,7 +300,7 @@ pub fn decode_at_offset(
             (*offset) += len;
             BorshToken::String(read_string.to_string())
         }
-        IdlType::Array(ty, len) => {
+        IdlType::Array(ty, IdlArrayLen::Value(len)) => {
             let mut read_items: Vec<BorshToken> = Vec::with_capacity(*len);
             for _ in 0..*len {
                 read_items.push(decode_at_offset(data, offset, ty, custom_types));
@@ -318,14 +318,11 @@ pub fn decode_at_offset(
             }
             BorshToken::Array(read_items)
         }
-        IdlType::Defined(value) => {
-            let current_ty = custom_types
-                .iter()
-                .find(|item| &item.name == value)
-                .unwrap();
+        IdlType::Defined { name, .. } => {
+            let current_ty = custom_types.iter().find(|item| &item.name == name).unwrap();
 
             match &current_ty.ty {
-                IdlTypeDefinitionTy::Enum { .. } => {
+                IdlTypeDefTy::Enum { .. } => {
                     let value = data[*offset];
                     (*offset) += 1;
                     BorshToken::Uint {
@@ -333,7 +330,9 @@ pub fn decode_at_offset(
                         value: BigInt::from(value),
                     }
                 }
-                IdlTypeDefinitionTy::Struct { fields } => {
+                IdlTypeDefTy::Struct {
+                    fields: Some(IdlDefinedFields::Named(fields)),
+                } => {
                     let mut read_items: Vec<BorshToken> = Vec::with_capacity(fields.len());
                     for item in fields {
                         read_items.push(decode_at_offset(data, offset, &item.ty, custom_types));
@@ -341,9 +340,8 @@ pub fn decode_at_offset(
 
                     BorshToken::Tuple(read_items)
                 }
-                IdlTypeDefinitionTy::Alias { value } => {
-                    decode_at_offset(data, offset, value, custom_types)
-                }
+                IdlTypeDefTy::Struct { .. } => unreachable!("Solidity structs have named fields"),
+                IdlTypeDefTy::Type { alias } => decode_at_offset(data, offset, alias, custom_types),
             }
         }
         IdlType::Bytes => {
@@ -356,12 +354,7 @@ pub fn decode_at_offset(
             BorshToken::Bytes(read_data.to_vec())
         }
 
-        IdlType::Option(_)
-        | IdlType::F32
-        | IdlType::F64
-        | IdlType::Generic(..)
-        | IdlType::DefinedWithTypeArgs { .. }
-        | IdlType::GenericLenArray(..) => {
+        _ => {
             unreachable!("Type not available in Solidity")
         }
     }
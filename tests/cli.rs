@@ -128,4 +128,180 @@ fn basic_compilation_from_toml() {
         .success();
 
     compile_cmd.current_dir(polkadot_test).assert().success();
+
+    let evm_test = tmp.path().join("evm_test");
+    let _new_cmd = cargo_bin_cmd!("solang")
+        .arg("new")
+        .arg(evm_test.clone())
+        .args(["--target", "evm"])
+        .assert()
+        .success();
+
+    compile_cmd.current_dir(evm_test.clone()).assert().success();
+    File::open(evm_test.join("flipper.bin")).expect("should exist");
+    File::open(evm_test.join("flipper.bin-runtime")).expect("should exist");
+}
+
+#[test]
+fn stylus_artifacts() {
+    let mut cmd = cargo_bin_cmd!("solang");
+
+    let tmp = TempDir::new_in("tests").unwrap();
+
+    cmd.args([
+        "compile",
+        "integration/stylus/counter.sol",
+        "--target",
+        "stylus",
+        "--release",
+        "--stylus-artifacts",
+        "--output",
+    ])
+    .arg(tmp.path())
+    .assert()
+    .success();
+
+    File::open(tmp.path().join("Counter.wasm")).expect("should exist");
+
+    let compressed = std::fs::read(tmp.path().join("Counter.compressed.wasm")).unwrap();
+    assert_eq!(compressed[..4], [0xef, 0xf0, 0x00, 0x00]);
+
+    let deploy = std::fs::read_to_string(tmp.path().join("Counter.deploy.hex")).unwrap();
+    let deploy = hex::decode(deploy).unwrap();
+
+    // the init code returns the compressed program which follows it
+    assert_eq!(deploy[43..], compressed);
+}
+
+#[test]
+fn idl_import() {
+    let tmp = TempDir::new_in("tests").unwrap();
+
+    let import = |file: &str, idl: &str| {
+        let path = tmp.path().join(file);
+        std::fs::write(&path, idl).unwrap();
+
+        cargo_bin_cmd!("solang")
+            .arg("idl")
+            .arg(&path)
+            .arg("--output")
+            .arg(tmp.path())
+            .assert()
+            .success();
+    };
+
+    let event = r#"event Incremented (
+	address indexed	authority,
+	uint64	count
+);
+"#;
+
+    let instruction = r#"	@selector([0x0b,0x12,0x68,0x09,0x68,0xae,0x3b,0x21])
+	function increment(uint64 by) external;
+}
+"#;
+
+    // legacy IDLs only have the address of a deployed program
+    import(
+        "legacy.json",
+        r#"{
+            "version": "0.1.0",
+            "name": "counter",
+            "instructions": [{
+                "name": "increment",
+                "accounts": [
+                    { "name": "counter", "isMut": true, "isSigner": false },
+                    { "name": "authority", "isMut": false, "isSigner": true }
+                ],
+                "args": [{ "name": "by", "type": "u64" }]
+            }],
+            "events": [{
+                "name": "Incremented",
+                "fields": [
+                    { "name": "authority", "type": "publicKey", "index": true },
+                    { "name": "count", "type": "u64", "index": false }
+                ]
+            }]
+        }"#,
+    );
+
+    assert_eq!(
+        std::fs::read_to_string(tmp.path().join("counter.sol")).unwrap(),
+        format!("{event}interface counter {{\n{instruction}")
+    );
+
+    import(
+        "legacy_deployed.json",
+        r#"{
+            "version": "0.1.0",
+            "name": "counter_deployed",
+            "instructions": [{
+                "name": "increment",
+                "accounts": [
+                    { "name": "counter", "isMut": true, "isSigner": false },
+                    { "name": "authority", "isMut": false, "isSigner": true }
+                ],
+                "args": [{ "name": "by", "type": "u64" }]
+            }],
+            "events": [{
+                "name": "Incremented",
+                "fields": [
+                    { "name": "authority", "type": "publicKey", "index": true },
+                    { "name": "count", "type": "u64", "index": false }
+                ]
+            }],
+            "metadata": { "address": "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS" }
+        }"#,
+    );
+
+    assert_eq!(
+        std::fs::read_to_string(tmp.path().join("counter_deployed.sol")).unwrap(),
+        format!(
+            "{event}@program_id(\"Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS\")\n\
+            interface counter_deployed {{\n{instruction}"
+        )
+    );
+
+    // Anchor v0.30 IDLs
+    import(
+        "anchor.json",
+        r#"{
+            "address": "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS",
+            "metadata": { "name": "counter_v30", "version": "0.1.0", "spec": "0.1.0" },
+            "instructions": [{
+                "name": "increment",
+                "discriminator": [11, 18, 104, 9, 104, 174, 59, 33],
+                "accounts": [
+                    { "name": "counter", "writable": true },
+                    { "name": "authority", "signer": true }
+                ],
+                "args": [{ "name": "by", "type": "u64" }]
+            }],
+            "events": [{
+                "name": "Incremented",
+                "discriminator": [26, 74, 111, 210, 29, 153, 48, 248]
+            }],
+            "types": [{
+                "name": "Incremented",
+                "type": {
+                    "kind": "struct",
+                    "fields": [
+                        { "name": "authority", "type": "pubkey" },
+                        { "name": "count", "type": "u64" }
+                    ]
+                }
+            }]
+        }"#,
+    );
+
+    // the new format does not record which event fields are indexed
+    let event = event.replace(" indexed", "");
+
+    assert_eq!(
+        std::fs::read_to_string(tmp.path().join("counter_v30.sol")).unwrap(),
+        format!(
+            "{event}@program_id(\"Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS\")\n\
+            interface counter_v30 {{\n{instruction}"
+        )
+    );
 }
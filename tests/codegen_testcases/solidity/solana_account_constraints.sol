@@ -0,0 +1,39 @@
+// RUN: --target solana --emit cfg
+
+@program_id("SoLDxXQ9GMoa15i4NavZc61XGkas2aom4aNiWT6KUER")
+contract Vault {
+    address authority;
+
+    // BEGIN-CHECK: Vault::Vault::function::deposit__bytes_bytes1
+    @mutableSigner(payer)
+    @mutableAccount(vault)
+    @account(authority)
+    @seeds(vault, [bytes("vault"), name], bump)
+    @initIfNeeded(vault, payer, 64)
+    @hasOne(authority)
+    function deposit(bytes name, bytes1 bump) external {
+        // CHECK: %pda.temp.3 = call builtin create_program_address [3] [ bytes((alloc bytes uint32 5 "vault")), bytes((arg #0)), bytes(bytes from:bytes1 ((arg #1))) ], address 0x69be8482447e433c6e5a483d817c70a6ed7b193df60e610a3607c9b9fe0eef6
+        // CHECK: branchcond (%pda.temp.3 == (load (load (struct %temp.2 field 0)))), block1, block2
+        // CHECK: block1: # vault_ConstraintSeeds_ok
+        // CHECK: branchcond ((load (load (struct %temp.4 field 1))) == uint64 0), block3, block4
+        // CHECK: block2: # vault_ConstraintSeeds_violated
+        // CHECK: return code: custom error 2006
+        // CHECK: block3: # vault_create
+        // CHECK: ty:struct AccountMeta[2] %metas.temp.6 = [2] [ struct { (load (struct %temp.7 field 0)), true, true }, struct { (load (struct %temp.4 field 0)), true, true } ]
+        // CHECK: writebuffer buffer:%instruction.temp.8 offset:uint32 4 value:uint64 1336320
+        // CHECK: writebuffer buffer:%instruction.temp.8 offset:uint32 12 value:uint64 64
+        // CHECK: _ = external call::regular address:address 0x0 payload:%instruction.temp.8 value:uint64 0 gas:uint64 0 accounts:%metas.temp.6 seeds:[1] [ [3] [ bytes((alloc bytes uint32 5 "vault")), bytes((arg #0)), bytes(bytes from:bytes1 ((arg #1))) ] ] contract|function:_ flags:
+        // CHECK: block4: # vault_exists
+        // CHECK: branchcond (address 0x69be8482447e433c6e5a483d817c70a6ed7b193df60e610a3607c9b9fe0eef6 == (load (load (struct %temp.4 field 3)))), block6, block7
+        // CHECK: block5: # vault_initialized
+        // CHECK: %temp.10 = load storage slot(uint32 16) ty:address
+        // CHECK: branchcond (%temp.10 == (load (load (struct %temp.9 field 0)))), block10, block11
+        // CHECK: branchcond (uint64 64 == (zext uint64 (builtin ArrayLength ((load (struct %temp.4 field 2)))))), block8, block9
+        // CHECK: return code: custom error 3007
+        // CHECK: return code: custom error 2019
+        // CHECK: block10: # authority_ConstraintHasOne_ok
+        // CHECK: print deposit
+        // CHECK: return code: custom error 2001
+        print("deposit");
+    }
+}
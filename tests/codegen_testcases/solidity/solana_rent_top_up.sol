@@ -0,0 +1,30 @@
+// RUN: --target solana --emit cfg
+
+contract Journal {
+    string[] entries;
+
+    // BEGIN-CHECK: Journal::Journal::function::add__string
+    @payer(payer)
+    function add(string entry) external {
+        // CHECK: push storage ty:string slot:uint32 16 = (arg #0)
+        // CHECK: ty:struct AccountInfo %temp.3 = (subscript struct AccountInfo[] (builtin Accounts ())[uint32 0])
+        // CHECK: ty:uint64 %lamports.temp.4 = (load (load (struct %temp.3 field 1)))
+        // CHECK: ty:uint64 %minimum_balance.temp.5 = (((zext uint64 (builtin ArrayLength ((load (struct %temp.3 field 2))))) + uint64 128) * uint64 6960)
+        // CHECK: branchcond (unsigned less %lamports.temp.4 < %minimum_balance.temp.5), block1, block2
+        // CHECK: block1: # top_up_rent
+        // CHECK: writebuffer buffer:%instruction.temp.6 offset:uint32 0 value:uint32 2
+        // CHECK: writebuffer buffer:%instruction.temp.6 offset:uint32 4 value:(overflowing %minimum_balance.temp.5 - %lamports.temp.4)
+        // CHECK: ty:struct AccountInfo %temp.8 = (subscript struct AccountInfo[] (builtin Accounts ())[uint32 1])
+        // CHECK: ty:struct AccountMeta[2] %metas.temp.7 = [2] [ struct { (load (struct %temp.8 field 0)), true, true }, struct { (load (struct %temp.3 field 0)), true, false } ]
+        // CHECK: _ = external call::regular address:address 0x0 payload:%instruction.temp.6 value:uint64 0 gas:uint64 0 accounts:%metas.temp.7 seeds: contract|function:_ flags:
+        // CHECK: block2: # rent_exempt
+        // CHECK: return
+        entries.push(entry);
+    }
+
+    // BEGIN-CHECK: Journal::Journal::function::count
+    function count() external view returns (uint64) {
+        // CHECK-ABSENT: top_up_rent
+        return entries.length;
+    }
+}
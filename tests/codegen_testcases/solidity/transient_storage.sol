@@ -0,0 +1,28 @@
+// RUN: --target polkadot --emit cfg
+contract transient_storage {
+    uint64 transient depth;
+    uint64 count;
+    bool transient locked;
+
+    // transient variables have their own slots, which start at zero
+    // BEGIN-CHECK: transient_storage::transient_storage::function::bump
+    function bump() public returns (uint64) {
+        depth += 1;
+        count += 1;
+        return depth + count;
+    }
+
+    // CHECK: load transient slot(uint256 0) ty:uint64
+    // CHECK: store transient slot(uint256 0) ty:uint64
+    // CHECK: load storage slot(uint256 0) ty:uint64
+    // CHECK: store storage slot(uint256 0) ty:uint64
+
+    // delete writes zero, since transient storage cannot be cleared
+    // BEGIN-CHECK: transient_storage::transient_storage::function::unlock
+    function unlock() public {
+        delete locked;
+    }
+
+    // CHECK: store transient slot(uint256 1) ty:bool = false
+    // NOT-CHECK: clear storage slot
+}
@@ -0,0 +1,32 @@
+contract C {
+    uint256 transient counter;
+    uint256 constant transient limit = 10;
+    uint256 immutable transient owner;
+    uint256 transient initialized = 1;
+    bytes transient data;
+    uint256[2] transient pair;
+    mapping(address => uint256) transient balances;
+    uint256 transient persistent both;
+
+    function f(uint256 transient a) public returns (uint256 transient) {
+        counter += a;
+        return counter;
+    }
+
+    function g() public {
+        uint256[] transient list;
+        delete counter;
+    }
+}
+
+// ---- Expect: diagnostics ----
+// error: 3:22-31: transient storage cannot be used for constant or immutable variables
+// error: 4:23-32: transient storage cannot be used for constant or immutable variables
+// error: 5:37-38: transient storage variables cannot have an initializer
+// error: 6:11-20: transient storage is not supported for variable of type 'bytes'
+// error: 7:16-25: transient storage is not supported for variable of type 'uint256[2]'
+// error: 8:33-42: transient storage is not supported for variable of type 'mapping(address => uint256)'
+// error: 9:23-33: mutliple storage type specifiers for 'both'
+// error: 11:24-33: data location 'transient' is only allowed for state variables
+// error: 11:61-70: data location 'transient' is only allowed for state variables
+// error: 17:19-28: data location 'transient' is only allowed for state variables
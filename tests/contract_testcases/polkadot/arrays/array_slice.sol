@@ -0,0 +1,30 @@
+contract c {
+    bytes b;
+    uint64[] arr;
+
+    function f1() public view returns (bytes memory) {
+        return b[1:];
+    }
+
+    function f2(string memory s) public pure returns (bytes memory) {
+        return s[0:1];
+    }
+
+    function f3(bytes memory x, int32 i) public pure returns (bytes memory) {
+        return x[i:];
+    }
+
+    function f4(bytes memory x) public pure returns (bytes memory) {
+        return x[3:1];
+    }
+
+    function f5(bool[2] memory x) public pure returns (bool[] memory) {
+        return x[0:1];
+    }
+}
+// ---- Expect: diagnostics ----
+// error: 6:16-17: array slices are only supported on calldata and memory arrays
+// error: 10:16-17: array slice is not permitted on string
+// error: 14:18-19: array slice bound must be an unsigned integer, not 'int32'
+// error: 18:16-22: array slice start 3 is greater than end 1
+// error: 22:16-17: array slice is only supported on bytes and dynamic arrays of value types, not 'bool[2]'
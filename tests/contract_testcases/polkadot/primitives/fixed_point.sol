@@ -0,0 +1,58 @@
+contract c {
+    fixed64x4 a = 1.5;
+    ufixed b = 2.125;
+    ufixed8x1 c1 = 25.6;
+    fixed16x2 d = 0.001;
+    ufixed32x2 e = -1;
+    fixed16x2 f = fixed16x2(0.019);
+    fixed8x1 g = 13;
+
+    function widen(fixed64x2 x, ufixed32x2 y, int32 z) public pure returns (fixed128x18) {
+        fixed128x18 r = x + y;
+        r += z;
+        return r * 2.5 / x;
+    }
+
+    function narrow(fixed128x18 x) public pure returns (ufixed64x2) {
+        ufixed64x2 w = x;
+        return w;
+    }
+
+    function to_int(fixed128x18 x) public pure returns (int64) {
+        int64 i = x;
+        return i + int64(x);
+    }
+
+    function bitwise(fixed128x18 x) public pure returns (fixed128x18) {
+        return x | x;
+    }
+
+    function power(fixed128x18 x) public pure returns (fixed128x18) {
+        return x ** 2;
+    }
+
+    function shift(fixed128x18 x) public pure returns (fixed128x18) {
+        return x << 1;
+    }
+
+    function assign(fixed128x18 x) public pure returns (fixed128x18) {
+        x &= x;
+        return x;
+    }
+
+    function mixed(fixed64x2 x, ufixed64x4 y) public pure returns (fixed64x2) {
+        return x + y;
+    }
+}
+// ---- Expect: diagnostics ----
+// error: 4:20-24: value 25.6 does not fit into type 'ufixed8x1'
+// error: 5:19-24: value cannot be represented exactly in type 'fixed16x2', use an explicit conversion to round towards zero
+// error: 6:20-22: value -1 does not fit into type ufixed32x2.
+// error: 8:18-20: value 13 does not fit into type fixed8x1.
+// error: 17:24-25: implicit conversion from fixed128x18 to ufixed64x2 not allowed
+// error: 22:19-20: implicit conversion from fixed128x18 to int64 not allowed
+// error: 27:16-21: operator not allowed on fixed point type 'fixed128x18'
+// error: 31:16-22: operator not allowed on fixed point type 'fixed128x18'
+// error: 35:16-17: expression of type fixed128x18 not allowed
+// error: 39:9-15: operator not allowed on fixed point type 'fixed128x18'
+// error: 44:16-17: cannot combine 'fixed64x2' and 'ufixed64x4' without loss of precision, use an explicit conversion
@@ -0,0 +1,22 @@
+contract guarded {
+    bool transient locked;
+    uint64 transient depth;
+
+    modifier nonReentrant() {
+        require(!locked, "reentrant call");
+        locked = true;
+        _;
+        locked = false;
+    }
+
+    function enter() public nonReentrant returns (uint64) {
+        depth += 1;
+        return depth;
+    }
+
+    function reset() public {
+        delete depth;
+    }
+}
+
+// ---- Expect: diagnostics ----
@@ -0,0 +1,20 @@
+contract addresses {
+	address constant owner = 0x5B38Da6a701c568545dCfcB03FcB875f56beddC4;
+
+	function checksum() public pure returns (address) {
+		return 0x5b38Da6a701c568545dCfcB03FcB875f56beddC4;
+	}
+
+	function ss58() public pure returns (address) {
+		return address"5GBWmgdFAMqm8ZgAHGobqDqX6tjLxJhv53ygjNtaaAn3sjeZ";
+	}
+
+	function length() public pure returns (uint) {
+		return owner.code.length + msg.sender.code.length;
+	}
+}
+
+// ---- Expect: diagnostics ----
+// error: 5:10-52: address literal has incorrect checksum, expected '0x5B38Da6a701c568545dCfcB03FcB875f56beddC4'
+// error: 9:10-67: address literal 5GBWmgdFAMqm8ZgAHGobqDqX6tjLxJhv53ygjNtaaAn3sjeZ not supported on PolkaVM
+// error: 13:10-15: 'address.code' is not supported on PolkaVM
@@ -0,0 +1,16 @@
+contract value {
+	function deposit() public payable returns (uint256) {
+		uint256 received = msg.value;
+		return received + address(this).balance;
+	}
+
+	function hashes(bytes b) public pure returns (bytes32, bytes16) {
+		return (blake2_256(b), blake2_128(b));
+	}
+
+	function gas() public view returns (uint64, uint256) {
+		return (gasleft(), block.minimum_balance);
+	}
+}
+
+// ---- Expect: diagnostics ----
@@ -0,0 +1,22 @@
+import "solana";
+
+contract Vault {
+    address authority;
+
+    @mutableSigner(payer)
+    @mutableAccount(vault)
+    @account(authority)
+    @seeds(vault, [bytes("vault"), name], bump)
+    @initIfNeeded(vault, payer, 64)
+    @owner(authority, address"11111111111111111111111111111111")
+    @hasOne(authority)
+    function deposit(bytes name, bytes1 bump) external {
+        authority = tx.accounts.authority.key;
+    }
+
+    @signer(user)
+    @owner(user, address(this))
+    function check() external view {}
+}
+
+// ---- Expect: diagnostics ----
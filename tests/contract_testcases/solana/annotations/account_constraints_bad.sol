@@ -0,0 +1,58 @@
+contract Vault {
+    address authority;
+    uint64 counter;
+
+    @account(vault)
+    @account(counter)
+    @owner(vault)
+    @seeds(vault, bytes("vault"), 1)
+    @hasOne(counter)
+    @hasOne(other)
+    @hasOne(vault, authority)
+    function one() external {}
+
+    @mutableAccount(vault)
+    @owner(vault, address(this))
+    @owner(vault, address(this))
+    @hasOne(vault)
+    function two(address vault) external {}
+
+    @account(vault)
+    @mutableSigner(payer)
+    @initIfNeeded(vault, payer, 64)
+    function three() external {}
+
+    @mutableSigner(vault)
+    @signer(payer)
+    @initIfNeeded(vault, payer, 64)
+    function eight() external {}
+
+    @mutableAccount(vault)
+    @mutableSigner(payer)
+    @initIfNeeded(vault, payer, 64)
+    function four() external {}
+
+    @owner(vault, address(this))
+    function six() public {}
+
+    @seeds(vault, [bytes("a"), bytes("b"), bytes("c"), bytes("d"), bytes("e"), bytes("f"), bytes("g"), bytes("h"),
+                   bytes("i"), bytes("j"), bytes("k"), bytes("l"), bytes("m"), bytes("n"), bytes("o"), bytes("p")], 1)
+    @mutableAccount(vault)
+    function seven() external {}
+}
+
+// ---- Expect: diagnostics ----
+// error: 7:5-18: '@owner' annotation expects 2 arguments
+// error: 8:19-33: expression must be an array literal
+// error: 9:13-20: implicit conversion from uint64 to address not allowed
+// error: 10:13-18: account 'other' not declared
+// error: 11:5-30: '@hasOne' annotation expects 1 argument
+// error: 16:5-33: duplicate @owner annotation for account 'vault'
+// 	note 15:5-33: previous @owner
+// error: 17:13-18: '@hasOne' annotation expects a contract storage variable named 'vault'
+// error: 22:19-24: account 'vault' must be mutable to be initialized
+// error: 27:26-31: payer account 'payer' must be declared with @mutableSigner
+// 	note 26:5-19: declaration
+// error: 32:5-36: account 'vault' must be a signer or have a @seeds annotation to be initialized
+// error: 35:5-33: account constraints are only valid in functions declared as external
+// error: 38:19-39:115: a program derived address can have at most 16 seeds, including the bump
@@ -0,0 +1,16 @@
+contract Vault {
+    address authority;
+
+    @mutableSigner(vault)
+    @mutableSigner(payer)
+    @initIfNeeded(vault, payer, 64)
+    function one() external view {}
+
+    @account(authority)
+    @hasOne(authority)
+    function two() external pure {}
+}
+
+// ---- Expect: diagnostics ----
+// error: 6:5-36: function declared 'view' but this expression writes to state
+// error: 10:13-22: function declared 'pure' but this expression reads from state
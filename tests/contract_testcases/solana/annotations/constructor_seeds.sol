@@ -63,6 +63,6 @@ contract c4 {
 // 	note 31:57-62: previous @bump
 // error: 33:2-14: unknown annotation seed for function
 // error: 34:2-10: unknown annotation bump for function
-// error: 35:2-62: unknown annotation payer for function
+// error: 35:2-62: @payer annotation is only valid on constructors and functions declared as external
 // error: 36:2-11: unknown annotation space for function
 // error: 45:2-16: @payer annotation required for constructor
@@ -0,0 +1,39 @@
+contract c {
+	int64[] values;
+
+	@payer(payer)
+	function add(int64 v) public {
+		values.push(v);
+	}
+
+	@payer(payer)
+	function internal_add(int64 v) internal {
+		values.push(v);
+	}
+
+	@payer(payer)
+	@payer(other)
+	function add_twice(int64 v) external {
+		values.push(v);
+	}
+
+	@payer(systemProgram)
+	function reserved(int64 v) external {
+		values.push(v);
+	}
+
+	@payer(payer)
+	@signer(payer)
+	function redeclared(int64 v) external {
+		values.push(v);
+	}
+}
+
+// ---- Expect: diagnostics ----
+// error: 4:2-15: @payer annotation is only valid on constructors and functions declared as external
+// error: 9:2-15: @payer annotation is only valid on constructors and functions declared as external
+// error: 15:2-15: duplicate @payer annotation for function
+// 	note 14:2-15: previous @payer
+// error: 20:9-22: 'systemProgram' is a reserved account name
+// error: 25:9-14: account 'payer' already defined
+// 	note 26:2-16: previous definition
@@ -0,0 +1,35 @@
+contract c {
+	int64 count;
+	string name;
+
+	@payer(payer)
+	@space(500)
+	constructor() {}
+
+	@payer(payer)
+	function set(string n) external {
+		name = n;
+		count += 1;
+	}
+
+	function get() external view returns (string, int64) {
+		return (name, count);
+	}
+}
+
+contract d {
+	int64 count;
+
+	@payer(payer)
+	@space(500)
+	constructor() {}
+
+	function inc() external {
+		count += 1;
+	}
+}
+
+// ---- Expect: diagnostics ----
+// warning: 6:9-12: storage variables with no fixed size can exceed the 468 bytes left of the 500 bytes of space, so the account may be grown while the contract runs
+// 	note 3:2-13: storage variable 'name' has no fixed size
+// warning: 21:2-13: storage variable 'count' has been assigned, but never read
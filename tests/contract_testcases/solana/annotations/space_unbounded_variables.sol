@@ -0,0 +1,44 @@
+contract c {
+	uint64[] list;
+	uint64 count;
+	mapping(uint64 => string) names;
+
+	@payer(payer)
+	@space(10 * 1024 * 1024)
+	constructor() {}
+
+	function add(uint64 n, string s) external {
+		list.push(n);
+		names[n] = s;
+		count += 1;
+	}
+
+	function get(uint64 n) external view returns (string, uint64) {
+		return (names[n], count + list.length);
+	}
+}
+
+contract d {
+	uint64 count;
+	string name;
+
+	@payer(payer)
+	@space(28)
+	constructor() {}
+
+	function set(string s) external {
+		name = s;
+		count += 1;
+	}
+
+	function get() external view returns (string, uint64) {
+		return (name, count);
+	}
+}
+
+// ---- Expect: diagnostics ----
+// warning: 7:9-25: storage variables with no fixed size can exceed the 10484720 bytes left of the 10485760 bytes of space, which cannot be grown
+// 	note 2:2-15: storage variable 'list' has no fixed size
+// 	note 4:2-33: storage variable 'names' has no fixed size
+// warning: 26:9-11: storage variables with no fixed size can exceed the 0 bytes left of the 28 bytes of space, so the account may be grown while the contract runs
+// 	note 23:2-13: storage variable 'name' has no fixed size
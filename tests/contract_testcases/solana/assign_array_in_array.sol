@@ -1,6 +1,6 @@
 // Ensure that subscript is assignable when member is array
 contract C {
-	function fixed() public {
+	function fixed_array() public {
 		uint256[3][4] memory iPj;
 		iPj[0] = [1,2,3];
 	}
@@ -25,7 +25,7 @@ contract C {
 }
 
 // ---- Expect: diagnostics ----
-// warning: 3:2-25: function can be declared 'pure'
+// warning: 3:2-31: function can be declared 'pure'
 // warning: 4:24-27: local variable 'iPj' has been assigned, but never read
 // warning: 8:2-27: function can be declared 'pure'
 // warning: 9:23-26: local variable 'iPj' has been assigned, but never read
@@ -1,13 +1,13 @@
 
 contract slice {
-    function foo(bytes foo) public {
+    function foo(bytes foo) public pure returns (bytes) {
         bytes x1 = foo[1:];
         bytes x2 = foo[1:2];
         bytes x3 = foo[:2];
         bytes x4 = foo[:];
+        return bytes.concat(x1, x2, x3, x4);
     }
 }
 // ---- Expect: diagnostics ----
 // warning: 3:24-27: declaration of 'foo' shadows function
 // 	note 3:14-17: previous declaration of function
-// error: 4:20-27: slice not supported yet
@@ -0,0 +1,3 @@
+import "soroban/token.sol";
+// ---- Expect: diagnostics ----
+// error: 1:8-27: 'soroban/token.sol' is only available when compiling for Soroban
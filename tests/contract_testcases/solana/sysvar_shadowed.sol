@@ -0,0 +1,19 @@
+import "solana";
+
+struct Instruction {
+    uint64 amount;
+}
+
+contract c {
+    function f(uint64 amount) public pure returns (uint64) {
+        Instruction memory instr = Instruction(amount);
+        return instr.amount;
+    }
+
+    function g() public view returns (uint64) {
+        Rent memory rent = sysvar.rent();
+        return rent.lamports_per_byte_year;
+    }
+}
+
+// ---- Expect: diagnostics ----
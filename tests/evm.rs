@@ -1,16 +1,187 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use ethabi::Token;
 use rayon::prelude::*;
-use solang::{file_resolver::FileResolver, parse_and_resolve, sema::ast, Target};
+use revm::{
+    db::{CacheDB, EmptyDB},
+    primitives::{AccountInfo, Address, Bytes, ExecutionResult, Log, Output, TxKind, U256},
+    Evm,
+};
+use solang::{
+    codegen::Options, compile, file_resolver::FileResolver, parse_and_resolve, sema::ast, Target,
+};
 use std::{
     ffi::OsStr,
     fs,
     path::{Path, PathBuf},
 };
+use tiny_keccak::{Hasher, Keccak};
 use walkdir::WalkDir;
 
 mod evm_tests;
 
+/// The account which sends all transactions
+const CALLER: Address = Address::repeat_byte(0x11);
+
+/// Deploys and calls contracts compiled for the EVM target
+pub struct EvmVm {
+    evm: Evm<'static, (), CacheDB<EmptyDB>>,
+    /// Creation code of every instantiable contract, in source order
+    contracts: Vec<Vec<u8>>,
+    /// Address of the contract which was deployed last
+    pub address: Address,
+    /// Logs emitted by the last transaction
+    pub logs: Vec<Log>,
+    /// Value sent with the next transaction
+    pub value: u128,
+}
+
+/// Compile the source and deploy the last contract without constructor arguments
+pub fn build_solidity(src: &str) -> EvmVm {
+    let mut vm = compile_solidity(src);
+
+    vm.deploy(&[]);
+
+    vm
+}
+
+/// Compile the source without deploying anything
+pub fn compile_solidity(src: &str) -> EvmVm {
+    let mut cache = FileResolver::default();
+
+    cache.set_file_contents("test.sol", src.to_string());
+
+    let (contracts, ns) = compile(
+        OsStr::new("test.sol"),
+        &mut cache,
+        Target::EVM,
+        &Options::default(),
+        vec!["unknown".to_string()],
+        "0.0.1",
+    );
+
+    ns.print_diagnostics_in_plain(&cache, false);
+
+    assert!(!contracts.is_empty());
+
+    let mut db = CacheDB::new(EmptyDB::default());
+
+    db.insert_account_info(
+        CALLER,
+        AccountInfo {
+            balance: U256::from(u128::MAX),
+            ..Default::default()
+        },
+    );
+
+    EvmVm {
+        evm: Evm::builder().with_db(db).build(),
+        contracts: contracts.into_iter().map(|(code, _)| code).collect(),
+        address: Address::ZERO,
+        logs: Vec::new(),
+        value: 0,
+    }
+}
+
+/// The four byte selector of a function signature
+pub fn selector(signature: &str) -> [u8; 4] {
+    let mut hasher = Keccak::v256();
+    let mut hash = [0u8; 32];
+
+    hasher.update(signature.as_bytes());
+    hasher.finalize(&mut hash);
+
+    hash[..4].try_into().unwrap()
+}
+
+impl EvmVm {
+    /// Deploy the last contract with the given constructor arguments
+    pub fn deploy(&mut self, args: &[Token]) {
+        let code = self.contracts.last().unwrap().clone();
+
+        let mut data = code;
+        data.extend(ethabi::encode(args));
+
+        match self.transact(TxKind::Create, data) {
+            Ok(Output::Create(_, Some(address))) => self.address = address,
+            res => panic!("deploy failed: {res:?}"),
+        }
+    }
+
+    /// Call a function and return its return data, which is expected to succeed
+    pub fn function(&mut self, signature: &str, args: &[Token]) -> Vec<u8> {
+        match self.call(signature, args) {
+            Ok(data) => data,
+            Err(data) => panic!("{signature} reverted with {}", hex::encode(data)),
+        }
+    }
+
+    /// Call a function which is expected to revert, and return the revert data
+    pub fn function_revert(&mut self, signature: &str, args: &[Token]) -> Vec<u8> {
+        match self.call(signature, args) {
+            Ok(data) => panic!("{signature} returned {}", hex::encode(data)),
+            Err(data) => data,
+        }
+    }
+
+    /// Call a function and decode its return values
+    pub fn function_returns(
+        &mut self,
+        signature: &str,
+        args: &[Token],
+        returns: &[ethabi::ParamType],
+    ) -> Vec<Token> {
+        let data = self.function(signature, args);
+
+        ethabi::decode(returns, &data).unwrap()
+    }
+
+    /// Call a function with the given arguments
+    pub fn call(&mut self, signature: &str, args: &[Token]) -> Result<Vec<u8>, Vec<u8>> {
+        let mut data = selector(signature).to_vec();
+        data.extend(ethabi::encode(args));
+
+        self.raw_call(data)
+    }
+
+    /// Call the deployed contract with raw call data
+    pub fn raw_call(&mut self, data: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
+        self.transact(TxKind::Call(self.address), data)
+            .map(|output| output.into_data().to_vec())
+    }
+
+    /// The balance of an account
+    pub fn balance(&mut self, address: Address) -> U256 {
+        self.evm
+            .db_mut()
+            .load_account(address)
+            .map(|account| account.info.balance)
+            .unwrap_or_default()
+    }
+
+    fn transact(&mut self, to: TxKind, data: Vec<u8>) -> Result<Output, Vec<u8>> {
+        let tx = self.evm.tx_mut();
+
+        tx.caller = CALLER;
+        tx.transact_to = to;
+        tx.data = Bytes::from(data);
+        tx.value = U256::from(std::mem::take(&mut self.value));
+        tx.gas_limit = 30_000_000;
+        tx.nonce = None;
+
+        self.logs.clear();
+
+        match self.evm.transact_commit().unwrap() {
+            ExecutionResult::Success { output, logs, .. } => {
+                self.logs = logs;
+                Ok(output)
+            }
+            ExecutionResult::Revert { output, .. } => Err(output.to_vec()),
+            ExecutionResult::Halt { reason, .. } => panic!("execution halted: {reason:?}"),
+        }
+    }
+}
+
 fn test_solidity(src: &str) -> ast::Namespace {
     let mut cache = FileResolver::default();
 
@@ -49,6 +220,41 @@ fn address() {
     assert!(!ns.diagnostics.any_errors());
 }
 
+#[test]
+fn code_size_limit() {
+    let mut cache = FileResolver::default();
+
+    let src = format!(
+        r#"
+        contract big {{
+            function get() public pure returns (bytes) {{
+                return hex"{}";
+            }}
+        }}"#,
+        "ab".repeat(25000)
+    );
+
+    cache.set_file_contents("test.sol", src);
+
+    let (contracts, ns) = compile(
+        OsStr::new("test.sol"),
+        &mut cache,
+        Target::EVM,
+        &Options::default(),
+        vec!["unknown".to_string()],
+        "0.0.1",
+    );
+
+    assert!(contracts.is_empty());
+
+    let errors = ns.diagnostics.errors();
+
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0]
+        .message
+        .ends_with("exceeds the limit of 24576 bytes (EIP-170)"));
+}
+
 #[test]
 fn try_catch() {
     let ns = test_solidity(
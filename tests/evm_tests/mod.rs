@@ -1,3 +1,4 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod pragma;
+mod runtime;
@@ -0,0 +1,1036 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{build_solidity, compile_solidity, selector};
+use ethabi::{ParamType, Token};
+use revm::primitives::U256;
+
+fn uint(v: u64) -> Token {
+    Token::Uint(v.into())
+}
+
+fn int(v: i64) -> Token {
+    let abs = ethabi::Int::from(v.unsigned_abs());
+
+    Token::Int(if v < 0 {
+        (!abs).overflowing_add(1.into()).0
+    } else {
+        abs
+    })
+}
+
+/// The revert data of a panic with the given code
+fn panic_data(code: u64) -> Vec<u8> {
+    let mut data = selector("Panic(uint256)").to_vec();
+    data.extend(ethabi::encode(&[uint(code)]));
+    data
+}
+
+#[test]
+fn flipper() {
+    let mut vm = compile_solidity(
+        r#"
+        contract flipper {
+            bool private value;
+
+            constructor(bool initvalue) {
+                value = initvalue;
+            }
+
+            function flip() public {
+                value = !value;
+            }
+
+            function get() public view returns (bool) {
+                return value;
+            }
+        }"#,
+    );
+
+    vm.deploy(&[Token::Bool(true)]);
+
+    let ret = vm.function_returns("get()", &[], &[ParamType::Bool]);
+    assert_eq!(ret, vec![Token::Bool(true)]);
+
+    vm.function("flip()", &[]);
+
+    let ret = vm.function_returns("get()", &[], &[ParamType::Bool]);
+    assert_eq!(ret, vec![Token::Bool(false)]);
+
+    // unknown selector
+    assert!(vm.raw_call(vec![1, 2, 3, 4]).is_err());
+}
+
+#[test]
+fn arithmetic() {
+    let mut vm = build_solidity(
+        r#"
+        contract math {
+            function add(uint8 a, uint8 b) public pure returns (uint8) {
+                return a + b;
+            }
+
+            function sub(int64 a, int64 b) public pure returns (int64) {
+                return a - b;
+            }
+
+            function mul(uint128 a, uint128 b) public pure returns (uint128) {
+                return a * b;
+            }
+
+            function div(int32 a, int32 b) public pure returns (int32, int32) {
+                return (a / b, a % b);
+            }
+
+            function pow(uint64 a, uint8 b) public pure returns (uint64) {
+                return a ** b;
+            }
+
+            function wrapping(uint8 a, uint8 b) public pure returns (uint8) {
+                unchecked {
+                    return a + b;
+                }
+            }
+
+            function shifts(int16 a, uint8 b) public pure returns (int16, int16) {
+                return (a << b, a >> b);
+            }
+        }"#,
+    );
+
+    let ret = vm.function_returns(
+        "add(uint8,uint8)",
+        &[uint(100), uint(27)],
+        &[ParamType::Uint(8)],
+    );
+    assert_eq!(ret, vec![uint(127)]);
+
+    let data = vm.function_revert("add(uint8,uint8)", &[uint(200), uint(100)]);
+    assert_eq!(data, panic_data(0x11));
+
+    let ret = vm.function_returns(
+        "wrapping(uint8,uint8)",
+        &[uint(200), uint(100)],
+        &[ParamType::Uint(8)],
+    );
+    assert_eq!(ret, vec![uint(44)]);
+
+    let ret = vm.function_returns(
+        "sub(int64,int64)",
+        &[int(-5), int(10)],
+        &[ParamType::Int(64)],
+    );
+    assert_eq!(ret, vec![int(-15)]);
+
+    let data = vm.function_revert("sub(int64,int64)", &[int(i64::MIN), int(1)]);
+    assert_eq!(data, panic_data(0x11));
+
+    let ret = vm.function_returns(
+        "mul(uint128,uint128)",
+        &[uint(u64::MAX), uint(u64::MAX)],
+        &[ParamType::Uint(128)],
+    );
+    assert_eq!(
+        ret,
+        vec![Token::Uint(
+            ethabi::Uint::from(u64::MAX) * ethabi::Uint::from(u64::MAX)
+        )]
+    );
+
+    let ret = vm.function_returns(
+        "div(int32,int32)",
+        &[int(-7), int(2)],
+        &[ParamType::Int(32), ParamType::Int(32)],
+    );
+    assert_eq!(ret, vec![int(-3), int(-1)]);
+
+    let data = vm.function_revert("div(int32,int32)", &[int(1), int(0)]);
+    assert_eq!(data, panic_data(0x12));
+
+    let ret = vm.function_returns(
+        "pow(uint64,uint8)",
+        &[uint(3), uint(40)],
+        &[ParamType::Uint(64)],
+    );
+    assert_eq!(ret, vec![uint(3u64.pow(40))]);
+
+    let data = vm.function_revert("pow(uint64,uint8)", &[uint(3), uint(41)]);
+    assert_eq!(data, panic_data(0x11));
+
+    let ret = vm.function_returns(
+        "shifts(int16,uint8)",
+        &[int(-100), uint(3)],
+        &[ParamType::Int(16), ParamType::Int(16)],
+    );
+    assert_eq!(ret, vec![int(-800), int(-13)]);
+
+    assert_eq!(vm.balance(vm.address), U256::ZERO);
+}
+
+#[test]
+fn storage_strings() {
+    let mut vm = build_solidity(
+        r#"
+        contract strings {
+            string name;
+            bytes data;
+
+            function set_name(string memory n) public {
+                name = n;
+            }
+
+            function get_name() public view returns (string memory) {
+                return name;
+            }
+
+            function push(bytes1 b) public {
+                data.push(b);
+            }
+
+            function pop() public returns (bytes1) {
+                return data.pop();
+            }
+
+            function get_data() public view returns (bytes memory, uint256) {
+                return (data, data.length);
+            }
+
+            function byte_at(uint256 i) public view returns (bytes1) {
+                return data[i];
+            }
+
+            function greet(string memory who) public pure returns (string memory) {
+                return string.concat("Hello, ", who, "!");
+            }
+        }"#,
+    );
+
+    for name in [
+        "short",
+        "a name which does not fit into a single storage slot",
+        "",
+    ] {
+        vm.function("set_name(string)", &[Token::String(name.to_string())]);
+
+        let ret = vm.function_returns("get_name()", &[], &[ParamType::String]);
+        assert_eq!(ret, vec![Token::String(name.to_string())]);
+    }
+
+    let mut expected = Vec::new();
+
+    for b in 0..40u8 {
+        vm.function("push(bytes1)", &[Token::FixedBytes(vec![b])]);
+        expected.push(b);
+    }
+
+    let ret = vm.function_returns("pop()", &[], &[ParamType::FixedBytes(1)]);
+    assert_eq!(ret, vec![Token::FixedBytes(vec![39])]);
+    expected.pop();
+
+    let ret = vm.function_returns("get_data()", &[], &[ParamType::Bytes, ParamType::Uint(256)]);
+    assert_eq!(ret, vec![Token::Bytes(expected), uint(39)]);
+
+    let ret = vm.function_returns("byte_at(uint256)", &[uint(33)], &[ParamType::FixedBytes(1)]);
+    assert_eq!(ret, vec![Token::FixedBytes(vec![33])]);
+
+    let data = vm.function_revert("byte_at(uint256)", &[uint(39)]);
+    assert_eq!(data, panic_data(0x32));
+
+    let ret = vm.function_returns(
+        "greet(string)",
+        &[Token::String("EVM".into())],
+        &[ParamType::String],
+    );
+    assert_eq!(ret, vec![Token::String("Hello, EVM!".into())]);
+}
+
+#[test]
+fn mappings_and_arrays() {
+    let mut vm = build_solidity(
+        r#"
+        contract balances {
+            mapping(address => uint64) balance;
+            mapping(string => mapping(uint32 => bool)) flags;
+            int32[] values;
+            uint16[3] buckets;
+
+            function set(address a, uint64 v) public {
+                balance[a] += v;
+            }
+
+            function get(address a) public view returns (uint64) {
+                return balance[a];
+            }
+
+            function set_flag(string memory k, uint32 n) public {
+                flags[k][n] = true;
+            }
+
+            function flag(string memory k, uint32 n) public view returns (bool) {
+                return flags[k][n];
+            }
+
+            function push(int32 v) public {
+                values.push(v);
+                buckets[values.length % 3] += uint16(uint32(v));
+            }
+
+            function all() public view returns (int32[] memory, uint16[3] memory) {
+                return (values, buckets);
+            }
+
+            function sum(int32[] memory list) public pure returns (int64 total) {
+                for (uint i = 0; i < list.length; i++) {
+                    total += list[i];
+                }
+            }
+
+            function remove() public {
+                delete values;
+            }
+
+            function squares(uint8 n) public pure returns (uint32[] memory) {
+                uint32[] memory res = new uint32[](n);
+                for (uint8 i = 0; i < n; i++) {
+                    res[i] = uint32(i) * i;
+                }
+                return res;
+            }
+        }"#,
+    );
+
+    let a = Token::Address([1u8; 20].into());
+    let b = Token::Address([2u8; 20].into());
+
+    vm.function("set(address,uint64)", &[a.clone(), uint(100)]);
+    vm.function("set(address,uint64)", &[a.clone(), uint(20)]);
+
+    let ret = vm.function_returns("get(address)", &[a], &[ParamType::Uint(64)]);
+    assert_eq!(ret, vec![uint(120)]);
+
+    let ret = vm.function_returns("get(address)", &[b], &[ParamType::Uint(64)]);
+    assert_eq!(ret, vec![uint(0)]);
+
+    vm.function(
+        "set_flag(string,uint32)",
+        &[Token::String("x".into()), uint(7)],
+    );
+
+    let ret = vm.function_returns(
+        "flag(string,uint32)",
+        &[Token::String("x".into()), uint(7)],
+        &[ParamType::Bool],
+    );
+    assert_eq!(ret, vec![Token::Bool(true)]);
+
+    let ret = vm.function_returns(
+        "flag(string,uint32)",
+        &[Token::String("y".into()), uint(7)],
+        &[ParamType::Bool],
+    );
+    assert_eq!(ret, vec![Token::Bool(false)]);
+
+    for v in [5, -3, 1000] {
+        vm.function("push(int32)", &[int(v)]);
+    }
+
+    let returns = [
+        ParamType::Array(Box::new(ParamType::Int(32))),
+        ParamType::FixedArray(Box::new(ParamType::Uint(16)), 3),
+    ];
+
+    let ret = vm.function_returns("all()", &[], &returns);
+    assert_eq!(
+        ret,
+        vec![
+            Token::Array(vec![int(5), int(-3), int(1000)]),
+            Token::FixedArray(vec![uint(1000), uint(5), uint(65533)]),
+        ]
+    );
+
+    let ret = vm.function_returns(
+        "sum(int32[])",
+        &[Token::Array(vec![int(1), int(-10), int(100)])],
+        &[ParamType::Int(64)],
+    );
+    assert_eq!(ret, vec![int(91)]);
+
+    vm.function("remove()", &[]);
+
+    let ret = vm.function_returns("all()", &[], &returns);
+    assert_eq!(ret[0], Token::Array(vec![]));
+
+    let ret = vm.function_returns(
+        "squares(uint8)",
+        &[uint(4)],
+        &[ParamType::Array(Box::new(ParamType::Uint(32)))],
+    );
+    assert_eq!(
+        ret,
+        vec![Token::Array(vec![uint(0), uint(1), uint(4), uint(9)])]
+    );
+}
+
+#[test]
+fn array_slices() {
+    let mut vm = build_solidity(
+        r#"
+        contract slices {
+            function middle(bytes memory payload) public pure returns (bytes memory) {
+                return payload[1:3];
+            }
+
+            function args(uint64 x, bool b) public pure returns (bytes memory) {
+                // skip the function selector
+                return msg.data[4:];
+            }
+
+            function decode(uint64 x) public pure returns (uint64) {
+                return abi.decode(msg.data[4:], (uint64));
+            }
+
+            function hash(bytes memory payload) public pure returns (bool) {
+                return keccak256(payload[2:]) == keccak256(bytes(hex"030405"));
+            }
+
+            function modify(bytes memory payload) public pure returns (bytes memory, bytes memory) {
+                bytes memory s = payload[1:];
+                s[0] = 0xff;
+                return (payload, s);
+            }
+
+            function sum(uint64[] memory values, uint32 start, uint32 end) public pure returns (uint64 total) {
+                uint64[] memory v = values[start:end];
+
+                for (uint32 i = 0; i < v.length; i++) {
+                    total += v[i];
+                }
+            }
+        }"#,
+    );
+
+    let payload = Token::Bytes(vec![1, 2, 3, 4, 5]);
+
+    let ret = vm.function_returns(
+        "middle(bytes)",
+        std::slice::from_ref(&payload),
+        &[ParamType::Bytes],
+    );
+    assert_eq!(ret, vec![Token::Bytes(vec![2, 3])]);
+
+    let args = [uint(0xdead_cafe), Token::Bool(true)];
+    let ret = vm.function_returns("args(uint64,bool)", &args, &[ParamType::Bytes]);
+    assert_eq!(ret, vec![Token::Bytes(ethabi::encode(&args))]);
+
+    let ret = vm.function_returns("decode(uint64)", &[uint(42)], &[ParamType::Uint(64)]);
+    assert_eq!(ret, vec![uint(42)]);
+
+    let ret = vm.function_returns(
+        "hash(bytes)",
+        std::slice::from_ref(&payload),
+        &[ParamType::Bool],
+    );
+    assert_eq!(ret, vec![Token::Bool(true)]);
+
+    let ret = vm.function_returns(
+        "modify(bytes)",
+        &[payload],
+        &[ParamType::Bytes, ParamType::Bytes],
+    );
+    assert_eq!(
+        ret,
+        vec![
+            Token::Bytes(vec![1, 2, 3, 4, 5]),
+            Token::Bytes(vec![0xff, 3, 4, 5])
+        ]
+    );
+
+    let values = Token::Array((1..=5).map(uint).collect());
+
+    let ret = vm.function_returns(
+        "sum(uint64[],uint32,uint32)",
+        &[values.clone(), uint(1), uint(4)],
+        &[ParamType::Uint(64)],
+    );
+    assert_eq!(ret, vec![uint(9)]);
+
+    let data = vm.function_revert("sum(uint64[],uint32,uint32)", &[values, uint(4), uint(1)]);
+    assert_eq!(data, panic_data(0x32));
+}
+
+#[test]
+fn calldata_slices() {
+    let mut vm = build_solidity(
+        r#"
+        contract views {
+            function decode(uint64 x, bytes memory b) public pure returns (uint64, bytes memory) {
+                return abi.decode(msg.data[4:], (uint64, bytes));
+            }
+
+            function hash(bytes memory b) public pure returns (bool) {
+                // the contents of b follow its offset and length
+                bytes32 k = keccak256(msg.data[68:68 + b.length]);
+                bytes32 s = sha256(msg.data[68:68 + b.length]);
+
+                return k == keccak256(b) && s == sha256(b);
+            }
+
+            function concat(bytes memory payload) public pure returns (bytes memory) {
+                return bytes.concat(msg.data[:4], payload[1:3], msg.data[68:68 + payload.length][3:]);
+            }
+
+            function at(bytes memory payload, uint32 i) public pure returns (bytes1, bytes1, uint256) {
+                // with i = 2, the last byte of the second argument is i
+                return (payload[1:][i], msg.data[4:][32 * i - 1], msg.data[4:].length);
+            }
+        }"#,
+    );
+
+    let payload = Token::Bytes(vec![1, 2, 3, 4, 5]);
+
+    let args = [uint(42), payload.clone()];
+    let ret = vm.function_returns(
+        "decode(uint64,bytes)",
+        &args,
+        &[ParamType::Uint(64), ParamType::Bytes],
+    );
+    assert_eq!(ret, args);
+
+    let ret = vm.function_returns(
+        "hash(bytes)",
+        std::slice::from_ref(&payload),
+        &[ParamType::Bool],
+    );
+    assert_eq!(ret, vec![Token::Bool(true)]);
+
+    let ret = vm.function_returns(
+        "concat(bytes)",
+        std::slice::from_ref(&payload),
+        &[ParamType::Bytes],
+    );
+    let mut expected = selector("concat(bytes)").to_vec();
+    expected.extend([2, 3, 4, 5]);
+    assert_eq!(ret, vec![Token::Bytes(expected)]);
+
+    let ret = vm.function_returns(
+        "at(bytes,uint32)",
+        &[payload.clone(), uint(2)],
+        &[
+            ParamType::FixedBytes(1),
+            ParamType::FixedBytes(1),
+            ParamType::Uint(256),
+        ],
+    );
+    assert_eq!(
+        ret,
+        vec![
+            Token::FixedBytes(vec![4]),
+            Token::FixedBytes(vec![2]),
+            uint(128)
+        ]
+    );
+
+    let data = vm.function_revert("at(bytes,uint32)", &[payload, uint(4)]);
+    assert_eq!(data, panic_data(0x32));
+}
+
+#[test]
+fn transient_storage() {
+    let mut vm = compile_solidity(
+        r#"
+        contract guarded {
+            uint64 transient depth;
+            bool transient entered;
+            uint64 counter;
+
+            function nested(uint64 n) public returns (uint64) {
+                depth += 1;
+                counter += 1;
+
+                if (n > 0) {
+                    return this.nested(n - 1);
+                }
+
+                return depth;
+            }
+
+            function lock() public returns (bool) {
+                require(!entered, "reentrant");
+                entered = true;
+                bool was = entered;
+                delete entered;
+                return was && !entered;
+            }
+
+            function get() public view returns (uint64, uint64) {
+                return (depth, counter);
+            }
+        }"#,
+    );
+
+    vm.deploy(&[]);
+
+    // the nested calls share the transient variable
+    let ret = vm.function_returns("nested(uint64)", &[uint(3)], &[ParamType::Uint(64)]);
+    assert_eq!(ret, vec![uint(4)]);
+
+    // the transient variable is gone after the transaction, the storage variable in
+    // the same slot is not
+    let ret = vm.function_returns("get()", &[], &[ParamType::Uint(64), ParamType::Uint(64)]);
+    assert_eq!(ret, vec![uint(0), uint(4)]);
+
+    let ret = vm.function_returns("lock()", &[], &[ParamType::Bool]);
+    assert_eq!(ret, vec![Token::Bool(true)]);
+
+    let ret = vm.function_returns("lock()", &[], &[ParamType::Bool]);
+    assert_eq!(ret, vec![Token::Bool(true)]);
+}
+
+#[test]
+fn structs() {
+    let mut vm = build_solidity(
+        r#"
+        contract people {
+            struct Person {
+                string name;
+                uint8 age;
+                bytes4 tag;
+            }
+
+            Person[] persons;
+            Person oldest;
+
+            function add(Person memory p) public {
+                persons.push(p);
+                if (p.age > oldest.age) {
+                    oldest = p;
+                }
+            }
+
+            function get(uint256 i) public view returns (Person memory) {
+                return persons[i];
+            }
+
+            function get_oldest() public view returns (string memory, uint8) {
+                return (oldest.name, oldest.age);
+            }
+
+            function count() public view returns (uint256) {
+                return persons.length;
+            }
+        }"#,
+    );
+
+    let person = |name: &str, age: u64, tag: [u8; 4]| {
+        Token::Tuple(vec![
+            Token::String(name.into()),
+            uint(age),
+            Token::FixedBytes(tag.to_vec()),
+        ])
+    };
+
+    vm.function(
+        "add((string,uint8,bytes4))",
+        &[person("Alice", 40, *b"abcd")],
+    );
+    vm.function("add((string,uint8,bytes4))", &[person("Bob", 70, *b"wxyz")]);
+    vm.function("add((string,uint8,bytes4))", &[person("Carol", 30, [0; 4])]);
+
+    let ret = vm.function_returns("count()", &[], &[ParamType::Uint(256)]);
+    assert_eq!(ret, vec![uint(3)]);
+
+    let ret = vm.function_returns(
+        "get(uint256)",
+        &[uint(1)],
+        &[ParamType::Tuple(vec![
+            ParamType::String,
+            ParamType::Uint(8),
+            ParamType::FixedBytes(4),
+        ])],
+    );
+    assert_eq!(ret, vec![person("Bob", 70, *b"wxyz")]);
+
+    let ret = vm.function_returns(
+        "get_oldest()",
+        &[],
+        &[ParamType::String, ParamType::Uint(8)],
+    );
+    assert_eq!(ret, vec![Token::String("Bob".into()), uint(70)]);
+}
+
+#[test]
+fn events() {
+    let mut vm = build_solidity(
+        r#"
+        contract emitter {
+            event Transfer(address indexed from, address indexed to, uint256 value);
+            event Message(string text, int8 level);
+
+            function transfer(address to, uint256 value) public {
+                emit Transfer(msg.sender, to, value);
+            }
+
+            function message(string memory text) public {
+                emit Message(text, -1);
+            }
+        }"#,
+    );
+
+    let to = [7u8; 20];
+
+    vm.function(
+        "transfer(address,uint256)",
+        &[Token::Address(to.into()), uint(1234)],
+    );
+
+    assert_eq!(vm.logs.len(), 1);
+
+    let log = &vm.logs[0];
+    let topics = log.topics();
+
+    assert_eq!(log.address, vm.address);
+    assert_eq!(topics.len(), 3);
+    assert_eq!(
+        topics[0].0[..4],
+        selector("Transfer(address,address,uint256)")
+    );
+    assert_eq!(topics[1].0[12..], [0x11u8; 20]);
+    assert_eq!(topics[2].0[12..], to);
+    assert_eq!(log.data.data.to_vec(), ethabi::encode(&[uint(1234)]));
+
+    vm.function("message(string)", &[Token::String("hi".into())]);
+
+    let log = &vm.logs[0];
+
+    assert_eq!(log.topics().len(), 1);
+    assert_eq!(
+        log.data.data.to_vec(),
+        ethabi::encode(&[Token::String("hi".into()), int(-1)])
+    );
+}
+
+#[test]
+fn internal_calls() {
+    let mut vm = build_solidity(
+        r#"
+        contract calls {
+            function fib(uint32 n) public pure returns (uint32) {
+                if (n < 2) {
+                    return n;
+                }
+                return fib(n - 1) + fib(n - 2);
+            }
+
+            function divmod(uint64 a, uint64 b) internal pure returns (uint64, uint64) {
+                return (a / b, a % b);
+            }
+
+            function apply(bool add, int64 a, int64 b) public pure returns (int64) {
+                function(int64, int64) internal pure returns (int64) f = minus;
+                if (add) {
+                    f = plus;
+                }
+                return f(a, b);
+            }
+
+            function plus(int64 a, int64 b) internal pure returns (int64) {
+                return a + b;
+            }
+
+            function minus(int64 a, int64 b) internal pure returns (int64) {
+                return a - b;
+            }
+
+            function digits(uint64 n) public pure returns (uint64 count, uint64 rest) {
+                (uint64 q, uint64 r) = divmod(n, 10);
+                while (q != 0) {
+                    count++;
+                    (q, r) = divmod(q, 10);
+                }
+                rest = r;
+            }
+
+            function format(int64 n, uint32 h, bool b) public pure returns (string memory) {
+                return "n={} h={:x} b={:b} {}".format(n, h, h, b);
+            }
+
+            function hashes(bytes memory b) public pure returns (bytes32, bytes32, bytes20) {
+                return (keccak256(b), sha256(b), ripemd160(b));
+            }
+        }"#,
+    );
+
+    let ret = vm.function_returns("fib(uint32)", &[uint(20)], &[ParamType::Uint(32)]);
+    assert_eq!(ret, vec![uint(6765)]);
+
+    let ret = vm.function_returns(
+        "apply(bool,int64,int64)",
+        &[Token::Bool(true), int(3), int(-8)],
+        &[ParamType::Int(64)],
+    );
+    assert_eq!(ret, vec![int(-5)]);
+
+    let ret = vm.function_returns(
+        "apply(bool,int64,int64)",
+        &[Token::Bool(false), int(3), int(-8)],
+        &[ParamType::Int(64)],
+    );
+    assert_eq!(ret, vec![int(11)]);
+
+    let ret = vm.function_returns(
+        "digits(uint64)",
+        &[uint(98765)],
+        &[ParamType::Uint(64), ParamType::Uint(64)],
+    );
+    assert_eq!(ret, vec![uint(4), uint(9)]);
+
+    let ret = vm.function_returns(
+        "format(int64,uint32,bool)",
+        &[int(-42), uint(255), Token::Bool(false)],
+        &[ParamType::String],
+    );
+    assert_eq!(
+        ret,
+        vec![Token::String("n=-42 h=0xff b=0b11111111 false".into())]
+    );
+
+    let ret = vm.function_returns(
+        "hashes(bytes)",
+        &[Token::Bytes(b"abc".to_vec())],
+        &[
+            ParamType::FixedBytes(32),
+            ParamType::FixedBytes(32),
+            ParamType::FixedBytes(20),
+        ],
+    );
+    assert_eq!(
+        ret,
+        vec![
+            Token::FixedBytes(
+                hex::decode("4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45")
+                    .unwrap()
+            ),
+            Token::FixedBytes(
+                hex::decode("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+                    .unwrap()
+            ),
+            Token::FixedBytes(hex::decode("8eb208f7e05d987a9b044a8e98c6b087f15a0bfc").unwrap()),
+        ]
+    );
+}
+
+#[test]
+fn memory_frames() {
+    // enough local variables that some of them live in a memory frame
+    let locals = (1..24)
+        .map(|i| format!("uint64 v{i} = v0 + {i};"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let sum = (1..24)
+        .map(|i| format!("v{i}"))
+        .collect::<Vec<_>>()
+        .join(" + ");
+
+    let mut vm = build_solidity(&format!(
+        r#"
+        contract frames {{
+            function spill(uint64 v0) internal pure returns (uint64) {{
+                {locals}
+                return {sum};
+            }}
+
+            function label(uint64 v0) internal pure returns (string memory) {{
+                {locals}
+                return "{{}}".format({sum});
+            }}
+
+            function run(uint64 n) public pure returns (uint64 total) {{
+                for (uint64 i = 0; i < n; i++) {{
+                    total += spill(i);
+                }}
+            }}
+
+            function labels() public pure returns (string memory, string memory) {{
+                uint64 x = spill(1);
+                uint64[] memory zeroed = new uint64[](64);
+                for (uint32 i = 0; i < zeroed.length; i++) {{
+                    require(zeroed[i] == 0);
+                }}
+                return (label(x), label(2));
+            }}
+        }}"#
+    ));
+
+    let spill = |a: u64| (1..24u64).map(|i| a + i).sum::<u64>();
+
+    // the frames are freed on return, otherwise the memory expansion runs out of gas
+    let n = 10000;
+    let ret = vm.function_returns("run(uint64)", &[uint(n)], &[ParamType::Uint(64)]);
+    assert_eq!(ret, vec![uint((0..n).map(spill).sum())]);
+
+    let ret = vm.function_returns("labels()", &[], &[ParamType::String, ParamType::String]);
+    assert_eq!(
+        ret,
+        vec![
+            Token::String(spill(spill(1)).to_string()),
+            Token::String(spill(2).to_string())
+        ]
+    );
+}
+
+#[test]
+fn external_calls() {
+    let mut vm = build_solidity(
+        r#"
+        contract child {
+            int32 x;
+
+            constructor(int32 a) {
+                x = a;
+            }
+
+            function get_x(int32 t) public view returns (int32) {
+                if (t == 0) {
+                    revert("cannot be zero");
+                }
+                return x * t;
+            }
+
+            function fail() public pure {
+                assert(false);
+            }
+        }
+
+        contract parent {
+            child c;
+
+            function create(int32 a) public returns (address) {
+                c = new child(a);
+                return address(c);
+            }
+
+            function call(int32 t) public view returns (int32) {
+                return c.get_x(t);
+            }
+
+            function fail() public view {
+                c.fail();
+            }
+
+            function pay(address payable to) public payable {
+                to.transfer(msg.value / 2);
+            }
+
+            function balance() public view returns (uint256) {
+                return address(this).balance;
+            }
+        }"#,
+    );
+
+    let ret = vm.function_returns("create(int32)", &[int(102)], &[ParamType::Address]);
+    assert!(matches!(ret[0], Token::Address(a) if a != [0u8; 20].into()));
+
+    let ret = vm.function_returns("call(int32)", &[int(-2)], &[ParamType::Int(32)]);
+    assert_eq!(ret, vec![int(-204)]);
+
+    // the revert reason of the callee is passed on
+    let data = vm.function_revert("call(int32)", &[int(0)]);
+    let mut expected = selector("Error(string)").to_vec();
+    expected.extend(ethabi::encode(&[Token::String("cannot be zero".into())]));
+    assert_eq!(data, expected);
+
+    let data = vm.function_revert("fail()", &[]);
+    assert_eq!(data, panic_data(0x01));
+
+    let to = [9u8; 20];
+
+    vm.value = 1000;
+    vm.function("pay(address)", &[Token::Address(to.into())]);
+
+    assert_eq!(vm.balance(to.into()), U256::from(500));
+
+    let ret = vm.function_returns("balance()", &[], &[ParamType::Uint(256)]);
+    assert_eq!(ret, vec![uint(500)]);
+
+    // functions which are not payable reject value
+    vm.value = 1;
+    assert!(vm.call("balance()", &[]).is_err());
+}
+
+#[test]
+fn fixed_point() {
+    let mut vm = build_solidity(
+        r#"
+        contract fixed_point {
+            fixed128x18 price = 1.5;
+
+            function set(fixed128x18 p) public {
+                price = p;
+            }
+
+            function get() public view returns (fixed128x18) {
+                return price;
+            }
+
+            function mul(ufixed256x18 a, ufixed256x18 b) public pure returns (ufixed256x18) {
+                return a * b;
+            }
+
+            function smul(fixed256x18 a, fixed256x18 b) public pure returns (fixed256x18) {
+                return a * b;
+            }
+
+            function div(ufixed256x18 a, ufixed256x18 b) public pure returns (ufixed256x18) {
+                return a / b;
+            }
+        }"#,
+    );
+
+    let exp10 = |n: usize| ethabi::Uint::exp10(n);
+
+    let ret = vm.function_returns("get()", &[], &[ParamType::Int(128)]);
+    assert_eq!(ret, vec![int(1_500_000_000_000_000_000)]);
+
+    vm.function("set(fixed128x18)", &[int(-2_250_000_000_000_000_000)]);
+
+    let ret = vm.function_returns("get()", &[], &[ParamType::Int(128)]);
+    assert_eq!(ret, vec![int(-2_250_000_000_000_000_000)]);
+
+    // 1e29 * 1e29 = 1e58, which needs a 512-bit intermediate
+    let ret = vm.function_returns(
+        "mul(ufixed256x18,ufixed256x18)",
+        &[Token::Uint(exp10(47)), Token::Uint(exp10(47))],
+        &[ParamType::Uint(256)],
+    );
+    assert_eq!(ret, vec![Token::Uint(exp10(76))]);
+
+    let minus = (!exp10(47)).overflowing_add(1.into()).0;
+    let ret = vm.function_returns(
+        "smul(fixed256x18,fixed256x18)",
+        &[Token::Int(minus), Token::Int(exp10(47))],
+        &[ParamType::Int(256)],
+    );
+    assert_eq!(
+        ret,
+        vec![Token::Int((!exp10(76)).overflowing_add(1.into()).0)]
+    );
+
+    let data = vm.function_revert(
+        "mul(ufixed256x18,ufixed256x18)",
+        &[Token::Uint(exp10(48)), Token::Uint(exp10(48))],
+    );
+    assert_eq!(data, panic_data(0x11));
+
+    // 1e40 / 0.5 = 2e40
+    let ret = vm.function_returns(
+        "div(ufixed256x18,ufixed256x18)",
+        &[Token::Uint(exp10(58)), Token::Uint(exp10(17) * 5)],
+        &[ParamType::Uint(256)],
+    );
+    assert_eq!(ret, vec![Token::Uint(exp10(58) * 2)]);
+
+    let data = vm.function_revert(
+        "div(ufixed256x18,ufixed256x18)",
+        &[Token::Uint(exp10(18)), uint(0)],
+    );
+    assert_eq!(data, panic_data(0x12));
+}
@@ -110,6 +110,10 @@ impl WasmCode {
 pub struct Contract {
     code: WasmCode,
     storage: HashMap<StorageKey, Vec<u8>>,
+    /// Transient storage, which is cleared after each transaction.
+    transient_storage: HashMap<StorageKey, Vec<u8>>,
+    /// Code hashes locked as delegate dependencies.
+    delegate_dependencies: HashSet<Hash>,
 }
 
 impl From<WasmCode> for Contract {
@@ -117,6 +121,8 @@ impl From<WasmCode> for Contract {
         Self {
             code,
             storage: HashMap::new(),
+            transient_storage: HashMap::new(),
+            delegate_dependencies: HashSet::new(),
         }
     }
 }
@@ -452,6 +458,53 @@ impl Runtime {
         }
     }
 
+    #[seal(0)]
+    fn get_transient_storage(
+        key_ptr: u32,
+        key_len: u32,
+        out_ptr: u32,
+        out_len_ptr: u32,
+    ) -> Result<u32, Trap> {
+        let key = StorageKey::try_from(read_buf(mem, key_ptr, key_len))
+            .expect("storage key size must be 32 bytes");
+        let value = match vm.contract().transient_storage.get(&key) {
+            Some(value) => value,
+            _ => return Ok(3), // In pallet-contracts, ReturnCode::KeyNotFound == 3
+        };
+        println!(
+            "get_transient_storage: {}={}",
+            hex::encode(key),
+            hex::encode(value)
+        );
+
+        write_buf(mem, out_ptr, value);
+        write_buf(mem, out_len_ptr, &(value.len() as u32).to_le_bytes());
+
+        Ok(0)
+    }
+
+    #[seal(0)]
+    fn set_transient_storage(
+        key_ptr: u32,
+        key_len: u32,
+        value_ptr: u32,
+        value_len: u32,
+    ) -> Result<u32, Trap> {
+        let key = StorageKey::try_from(read_buf(mem, key_ptr, key_len))
+            .expect("storage key size must be 32 bytes");
+        let value = mem[value_ptr as usize..(value_ptr + value_len) as usize].to_vec();
+        println!(
+            "set_transient_storage: {}={}",
+            hex::encode(key),
+            hex::encode(&value)
+        );
+
+        match vm.contract().transient_storage.insert(key, value) {
+            Some(value) => Ok(value.len() as u32),
+            _ => Ok(u32::MAX), // In pallets contract, u32::MAX is the "none sentinel"
+        }
+    }
+
     #[seal(0)]
     fn hash_keccak_256(input_ptr: u32, input_len: u32, output_ptr: u32) -> Result<(), Trap> {
         let mut hasher = Keccak::v256();
@@ -803,6 +856,192 @@ impl Runtime {
         }
         Ok(7) // ReturnCode::CodeNoteFound
     }
+
+    #[seal(0)]
+    fn ecdsa_recover(
+        signature_ptr: u32,
+        message_hash_ptr: u32,
+        output_ptr: u32,
+    ) -> Result<u32, Trap> {
+        let signature = read_buf(mem, signature_ptr, 65);
+        let message_hash = read_buf(mem, message_hash_ptr, 32);
+
+        // Ethereum style recovery ids are accepted as well
+        let recovery_id = match signature[64] {
+            v @ 27.. => v - 27,
+            v => v,
+        };
+
+        let recovered = k256::ecdsa::Signature::from_slice(&signature[..64])
+            .ok()
+            .zip(k256::ecdsa::RecoveryId::from_byte(recovery_id))
+            .and_then(|(signature, recovery_id)| {
+                k256::ecdsa::VerifyingKey::recover_from_prehash(
+                    &message_hash,
+                    &signature,
+                    recovery_id,
+                )
+                .ok()
+            });
+
+        match recovered {
+            Some(key) => {
+                write_buf(mem, output_ptr, key.to_encoded_point(true).as_bytes());
+                Ok(0)
+            }
+            None => Ok(11), // ReturnCode::EcdsaRecoveryFailed
+        }
+    }
+
+    #[seal(0)]
+    fn ecdsa_to_eth_address(key_ptr: u32, output_ptr: u32) -> Result<u32, Trap> {
+        let key = match k256::ecdsa::VerifyingKey::from_sec1_bytes(&read_buf(mem, key_ptr, 33)) {
+            Ok(key) => key,
+            Err(_) => return Ok(11), // ReturnCode::EcdsaRecoveryFailed
+        };
+
+        let mut hash = [0u8; 32];
+        let mut hasher = Keccak::v256();
+        hasher.update(&key.to_encoded_point(false).as_bytes()[1..]);
+        hasher.finalize(&mut hash);
+
+        write_buf(mem, output_ptr, &hash[12..]);
+        Ok(0)
+    }
+
+    #[seal(0)]
+    fn sr25519_verify(
+        signature_ptr: u32,
+        pub_key_ptr: u32,
+        message_len: u32,
+        message_ptr: u32,
+    ) -> Result<u32, Trap> {
+        let signature = schnorrkel::Signature::from_bytes(&read_buf(mem, signature_ptr, 64));
+        let public_key = schnorrkel::PublicKey::from_bytes(&read_buf(mem, pub_key_ptr, 32));
+        let message = read_buf(mem, message_ptr, message_len);
+
+        match (signature, public_key) {
+            (Ok(signature), Ok(public_key))
+                if public_key
+                    .verify_simple(b"substrate", &message, &signature)
+                    .is_ok() =>
+            {
+                Ok(0)
+            }
+            _ => Ok(12), // ReturnCode::Sr25519VerifyFailed
+        }
+    }
+
+    #[seal(0)]
+    fn own_code_hash(out_ptr: u32, out_len_ptr: u32) -> Result<(), Trap> {
+        let hash = vm.contract().code.hash;
+        assert!(read_len(mem, out_len_ptr) >= hash.as_ref().len());
+
+        write_buf(mem, out_ptr, hash.as_ref());
+        write_buf(
+            mem,
+            out_len_ptr,
+            &(hash.as_ref().len() as u32).to_le_bytes(),
+        );
+
+        Ok(())
+    }
+
+    /// Mock XCM executor which fails to execute empty messages.
+    #[seal(0)]
+    fn xcm_execute(msg_ptr: u32, msg_len: u32) -> Result<u32, Trap> {
+        let message = read_buf(mem, msg_ptr, msg_len);
+        println!("seal_xcm_execute: {}", hex::encode(&message));
+
+        if message.is_empty() {
+            return Ok(13); // ReturnCode::XcmExecutionFailed
+        }
+        Ok(0)
+    }
+
+    /// Mock XCM router which fails to send empty messages. The message hash is the blake2_256 hash
+    /// of the message.
+    #[seal(0)]
+    fn xcm_send(
+        dest_ptr: u32,
+        dest_len: u32,
+        msg_ptr: u32,
+        msg_len: u32,
+        output_ptr: u32,
+    ) -> Result<u32, Trap> {
+        let dest = read_buf(mem, dest_ptr, dest_len);
+        let message = read_buf(mem, msg_ptr, msg_len);
+        println!(
+            "seal_xcm_send: dest: {} message: {}",
+            hex::encode(&dest),
+            hex::encode(&message)
+        );
+
+        if dest.is_empty() || message.is_empty() {
+            return Ok(14); // ReturnCode::XcmSendFailed
+        }
+
+        write_buf(mem, output_ptr, blake2b(32, &[], &message).as_bytes());
+        Ok(0)
+    }
+
+    #[seal(0)]
+    fn lock_delegate_dependency(code_hash_ptr: u32) -> Result<(), Trap> {
+        let hash = read_hash(mem, code_hash_ptr);
+
+        // The own code hash can not be a dependency, and the code must exist
+        if vm.contract().code.hash == hash || !vm.blobs.iter().any(|code| code.hash == hash) {
+            return Err(TrapCode::UnreachableCodeReached.into());
+        }
+
+        if !vm.contract().delegate_dependencies.insert(hash) {
+            return Err(TrapCode::UnreachableCodeReached.into());
+        }
+        Ok(())
+    }
+
+    #[seal(0)]
+    fn unlock_delegate_dependency(code_hash_ptr: u32) -> Result<(), Trap> {
+        let hash = read_hash(mem, code_hash_ptr);
+
+        if !vm.contract().delegate_dependencies.remove(&hash) {
+            return Err(TrapCode::UnreachableCodeReached.into());
+        }
+        Ok(())
+    }
+
+    #[seal(0)]
+    fn take_storage(
+        key_ptr: u32,
+        key_len: u32,
+        out_ptr: u32,
+        out_len_ptr: u32,
+    ) -> Result<u32, Trap> {
+        let key = StorageKey::try_from(read_buf(mem, key_ptr, key_len))
+            .expect("storage key size must be 32 bytes");
+        let value = match vm.contract().storage.remove(&key) {
+            Some(value) => value,
+            _ => return Ok(3), // In pallet-contracts, ReturnCode::KeyNotFound == 3
+        };
+        println!("take_storage: {}={}", hex::encode(key), hex::encode(&value));
+        assert!(read_len(mem, out_len_ptr) >= value.len());
+
+        write_buf(mem, out_ptr, &value);
+        write_buf(mem, out_len_ptr, &(value.len() as u32).to_le_bytes());
+
+        Ok(0)
+    }
+
+    #[seal(1)]
+    fn contains_storage(key_ptr: u32, key_len: u32) -> Result<u32, Trap> {
+        let key = StorageKey::try_from(read_buf(mem, key_ptr, key_len))
+            .expect("storage key size must be 32 bytes");
+
+        match vm.contract().storage.get(&key) {
+            Some(value) => Ok(value.len() as u32),
+            _ => Ok(u32::MAX), // In pallets contract, u32::MAX is the "none sentinel"
+        }
+    }
 }
 
 /// Provides a mock implementation of substrates [contracts pallet][1]
@@ -819,6 +1058,13 @@ impl MockSubstrate {
         runtime.debug_buffer.clear();
         runtime.events.clear();
         runtime.called_accounts.clear();
+        for contract in runtime
+            .accounts
+            .iter_mut()
+            .filter_map(|a| a.contract.as_mut())
+        {
+            contract.transient_storage.clear();
+        }
         self.0 = runtime.call(export, callee, input, value).unwrap()?;
         self.0.data_mut().transferred_value = 0;
 
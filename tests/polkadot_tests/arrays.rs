@@ -1626,3 +1626,79 @@ fn abi_decode_dynamic_array3() {
 
     runtime.function("decode_empty", vec![]);
 }
+
+#[test]
+fn array_slices() {
+    let mut runtime = build_solidity(
+        r##"
+        contract slices {
+            function middle(bytes payload) public pure returns (bytes) {
+                return payload[1:3];
+            }
+
+            function tail(bytes payload, uint32 start) public pure returns (bytes) {
+                bytes memory t = payload[start:];
+                return t;
+            }
+
+            function hash(bytes payload) public pure returns (bool) {
+                return keccak256(payload[2:]) == keccak256(bytes(hex"030405"));
+            }
+
+            function concat(bytes payload) public pure returns (bytes) {
+                return bytes.concat(payload[:2], payload[3:4], payload[:][1:2]);
+            }
+
+            function decode(bytes payload) public pure returns (uint32) {
+                return abi.decode(payload[1:], (uint32));
+            }
+
+            function args(uint32 x, bool b) public pure returns (bytes) {
+                // skip the function selector
+                return msg.data[4:];
+            }
+
+            function sum(uint64[] values, uint32 start, uint32 end) public pure returns (uint64 total) {
+                uint64[] memory v = values[start:end];
+
+                for (uint32 i = 0; i < v.length; i++) {
+                    total += v[i];
+                }
+            }
+        }"##,
+    );
+
+    let payload = vec![1u8, 2, 3, 4, 5];
+
+    runtime.function("middle", payload.encode());
+    assert_eq!(runtime.output(), vec![2u8, 3].encode());
+
+    runtime.function("tail", (payload.clone(), 3u32).encode());
+    assert_eq!(runtime.output(), vec![4u8, 5].encode());
+
+    runtime.function("tail", (payload.clone(), 5u32).encode());
+    assert_eq!(runtime.output(), Vec::<u8>::new().encode());
+
+    runtime.function_expect_failure("tail", (payload.clone(), 6u32).encode());
+
+    runtime.function("hash", payload.encode());
+    assert_eq!(runtime.output(), true.encode());
+
+    runtime.function("concat", payload.encode());
+    assert_eq!(runtime.output(), vec![1u8, 2, 4, 2].encode());
+
+    let mut data = vec![0xffu8];
+    data.extend(0x1234_5678u32.encode());
+    runtime.function("decode", data.encode());
+    assert_eq!(runtime.output(), 0x1234_5678u32.encode());
+
+    runtime.function("args", (0xdead_cafeu32, true).encode());
+    assert_eq!(runtime.output(), (0xdead_cafeu32, true).encode().encode());
+
+    let values = vec![1u64, 2, 3, 4, 5];
+
+    runtime.function("sum", (values.clone(), 1u32, 4u32).encode());
+    assert_eq!(runtime.output(), 9u64.encode());
+
+    runtime.function_expect_failure("sum", (values, 4u32, 1u32).encode());
+}
@@ -1,6 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use blake2_rfc::blake2b::blake2b;
 use parity_scale_codec::{Decode, Encode};
+use tiny_keccak::{Hasher, Keccak};
 
 use crate::build_solidity;
 
@@ -866,3 +868,203 @@ fn caller_is_root() {
     runtime.function("test", [0; 32].to_vec());
     assert_eq!(runtime.output(), true.encode());
 }
+
+#[test]
+fn ecdsa_recover() {
+    let mut runtime = build_solidity(
+        r#"
+        import "polkadot";
+
+        contract Signatures {
+            function recover(uint8[65] signature, bytes32 hash) public pure returns (uint32, uint8[33]) {
+                return ecdsa_recover(signature, hash);
+            }
+
+            function eth_address(uint8[33] public_key) public pure returns (uint32, bytes20) {
+                return ecdsa_to_eth_address(public_key);
+            }
+        }"#,
+    );
+
+    let signing_key = k256::ecdsa::SigningKey::from_slice(&[7; 32]).unwrap();
+    let public_key = signing_key.verifying_key().to_encoded_point(true);
+    let hash = [0xab; 32];
+    let (signature, recovery_id) = signing_key.sign_prehash_recoverable(&hash).unwrap();
+
+    let mut input = signature.to_bytes().to_vec();
+    input.push(recovery_id.to_byte() + 27);
+    input.extend_from_slice(&hash);
+
+    runtime.function("recover", input.clone());
+    let (ret, recovered) = <(u32, [u8; 33])>::decode(&mut &runtime.output()[..]).unwrap();
+    assert_eq!(ret, 0);
+    assert_eq!(&recovered[..], public_key.as_bytes());
+
+    // the address is the last 20 bytes of the keccak256 hash of the uncompressed key
+    let mut hash = [0u8; 32];
+    let mut hasher = Keccak::v256();
+    hasher.update(
+        &signing_key
+            .verifying_key()
+            .to_encoded_point(false)
+            .as_bytes()[1..],
+    );
+    hasher.finalize(&mut hash);
+
+    runtime.function("eth_address", recovered.to_vec());
+    assert_eq!(runtime.output(), [&0u32.encode()[..], &hash[12..]].concat());
+
+    // a recovery id out of range can not be recovered
+    input[64] = 4;
+    runtime.function("recover", input);
+    assert_eq!(u32::decode(&mut &runtime.output()[..]).unwrap(), 11);
+}
+
+#[test]
+fn sr25519_verify() {
+    let mut runtime = build_solidity(
+        r#"
+        import "polkadot";
+
+        contract Signatures {
+            function verify(uint8[64] signature, address signer, bytes message) public pure returns (bool) {
+                return sr25519_verify(signature, signer, message);
+            }
+        }"#,
+    );
+
+    let keypair = schnorrkel::MiniSecretKey::from_bytes(&[3; 32])
+        .unwrap()
+        .expand_to_keypair(schnorrkel::ExpansionMode::Ed25519);
+    let message = b"polkadot".to_vec();
+    let signature = keypair.sign_simple(b"substrate", &message);
+
+    let mut input = signature.to_bytes().to_vec();
+    input.extend_from_slice(&keypair.public.to_bytes());
+
+    runtime.function("verify", [input.clone(), message.encode()].concat());
+    assert_eq!(runtime.output(), true.encode());
+
+    runtime.function("verify", [input, b"kusama".to_vec().encode()].concat());
+    assert_eq!(runtime.output(), false.encode());
+}
+
+#[test]
+fn delegate_dependencies() {
+    let mut runtime = build_solidity(
+        r#"
+        import "polkadot";
+
+        contract Proxy {
+            function code_hash() public view returns (uint8[32]) {
+                return own_code_hash();
+            }
+
+            function lock(uint8[32] code_hash) public {
+                lock_delegate_dependency(code_hash);
+            }
+
+            function unlock(uint8[32] code_hash) public {
+                unlock_delegate_dependency(code_hash);
+            }
+        }
+
+        contract Implementation {
+            function foo() public pure returns (uint32) {
+                return 102;
+            }
+        }"#,
+    );
+
+    let own_hash = runtime.blobs()[0].hash;
+    let dependency = runtime.blobs()[1].hash;
+
+    runtime.function("code_hash", vec![]);
+    assert_eq!(runtime.output(), own_hash.as_ref().to_vec());
+
+    runtime.function("lock", dependency.as_ref().to_vec());
+    assert!(runtime.contracts()[0]
+        .delegate_dependencies
+        .contains(&dependency));
+
+    // locking twice, locking the own code or unknown code fails
+    runtime.function_expect_failure("lock", dependency.as_ref().to_vec());
+    runtime.function_expect_failure("lock", own_hash.as_ref().to_vec());
+    runtime.function_expect_failure("lock", [0; 32].to_vec());
+
+    runtime.function("unlock", dependency.as_ref().to_vec());
+    assert!(runtime.contracts()[0].delegate_dependencies.is_empty());
+
+    runtime.function_expect_failure("unlock", dependency.as_ref().to_vec());
+}
+
+#[test]
+fn xcm() {
+    let mut runtime = build_solidity(
+        r#"
+        import "polkadot";
+
+        contract Bridge {
+            function execute(bytes message) public returns (uint32) {
+                return xcm_execute(message);
+            }
+
+            function send(bytes destination, bytes message) public returns (uint32, bytes32) {
+                return xcm_send(destination, message);
+            }
+        }"#,
+    );
+
+    let message = vec![0x04, 0x00, 0x01];
+
+    runtime.function("execute", message.encode());
+    assert_eq!(runtime.output(), 0u32.encode());
+
+    runtime.function("execute", Vec::<u8>::new().encode());
+    assert_eq!(runtime.output(), 13u32.encode());
+
+    let destination = vec![0x04, 0x01, 0x00];
+    runtime.function("send", (destination.clone(), message.clone()).encode());
+    let hash: [u8; 32] = blake2b(32, &[], &message).as_bytes().try_into().unwrap();
+    assert_eq!(runtime.output(), (0u32, hash).encode());
+
+    runtime.function("send", (destination, Vec::<u8>::new()).encode());
+    assert_eq!(runtime.output()[..4], 14u32.encode());
+}
+
+#[test]
+fn take_storage() {
+    let mut runtime = build_solidity(
+        r#"
+        import "polkadot";
+
+        contract Storage {
+            uint64 public counter = 42;
+
+            function contains() public view returns (bool) {
+                return contains_storage(new bytes(32));
+            }
+
+            function take() public returns (uint32, bytes) {
+                return take_storage(new bytes(32));
+            }
+        }"#,
+    );
+
+    runtime.constructor(0, vec![]);
+
+    runtime.function("contains", vec![]);
+    assert_eq!(runtime.output(), true.encode());
+
+    runtime.function("take", vec![]);
+    assert_eq!(runtime.output(), (0u32, 42u64.encode()).encode());
+
+    runtime.function("contains", vec![]);
+    assert_eq!(runtime.output(), false.encode());
+
+    runtime.function("take", vec![]);
+    assert_eq!(runtime.output(), (3u32, Vec::<u8>::new()).encode());
+
+    runtime.function("counter", vec![]);
+    assert_eq!(runtime.output(), 0u64.encode());
+}
@@ -2,6 +2,7 @@
 
 use crate::build_solidity;
 use parity_scale_codec::{Decode, Encode};
+use primitive_types::U256;
 
 #[test]
 fn various_constants() {
@@ -431,3 +432,182 @@ fn mul() {
         (10000000000u64, 0u64, 0u64, 0u64).encode()
     );
 }
+
+#[test]
+fn fixed_point() {
+    let mut runtime = build_solidity(
+        r#"
+        contract test {
+            function mul(fixed64x2 a, fixed64x2 b) public pure returns (fixed64x2) {
+                return a * b;
+            }
+
+            function div(fixed64x2 a, fixed64x2 b) public pure returns (fixed64x2) {
+                return a / b;
+            }
+
+            function to_int(fixed64x2 a) public pure returns (int64) {
+                return int64(a);
+            }
+
+            function constants() public pure {
+                fixed64x2 a = 1.25;
+                ufixed128x18 b = 0.000000000000000001;
+                fixed128x4 c = a;
+
+                assert(a + a == 2.5);
+                assert(a * 3 == 3.75);
+                assert(-a / 2 == -0.62);
+                assert(a % 1 == 0.25);
+                assert(c == 1.25);
+                assert(b * 1e18 == 1);
+                assert(fixed8x1(12.34) == 12.3);
+                assert(int64(-a) == -1);
+            }
+        }"#,
+    );
+
+    runtime.function("constants", Vec::new());
+
+    // 1.50 * 2.25 = 3.375, rounded towards zero
+    runtime.function("mul", (150i64, 225i64).encode());
+    assert_eq!(runtime.output(), 337i64.encode());
+
+    runtime.function("mul", (-150i64, 225i64).encode());
+    assert_eq!(runtime.output(), (-337i64).encode());
+
+    // 1.00 / 3.00 = 0.33
+    runtime.function("div", (100i64, 300i64).encode());
+    assert_eq!(runtime.output(), 33i64.encode());
+
+    runtime.function("div", (-100i64, 300i64).encode());
+    assert_eq!(runtime.output(), (-33i64).encode());
+
+    runtime.function("to_int", (-199i64).encode());
+    assert_eq!(runtime.output(), (-1i64).encode());
+
+    // the product does not fit into 64 bits
+    runtime.function_expect_failure("mul", (i64::MAX, 200i64).encode());
+}
+
+#[test]
+fn fixed_point_storage() {
+    let mut runtime = build_solidity(
+        r#"
+        contract test {
+            fixed64x2 price = 1.25;
+            ufixed128x18 total;
+
+            function set_price(fixed64x2 p) public {
+                price = p;
+            }
+
+            function get_price() public view returns (fixed64x2) {
+                return price;
+            }
+
+            function deposit(ufixed128x18 v) public returns (ufixed128x18) {
+                total += v * 2;
+                return total;
+            }
+        }"#,
+    );
+
+    runtime.constructor(0, Vec::new());
+
+    runtime.function("get_price", Vec::new());
+    assert_eq!(runtime.output(), 125i64.encode());
+
+    runtime.function("set_price", (-350i64).encode());
+    runtime.function("get_price", Vec::new());
+    assert_eq!(runtime.output(), (-350i64).encode());
+
+    // 0.5 * 2 + 1.5 * 2 = 4
+    runtime.function("deposit", 500_000_000_000_000_000u128.encode());
+    assert_eq!(runtime.output(), 1_000_000_000_000_000_000u128.encode());
+
+    runtime.function("deposit", 1_500_000_000_000_000_000u128.encode());
+    assert_eq!(runtime.output(), 4_000_000_000_000_000_000u128.encode());
+}
+
+#[test]
+fn fixed_point_wide() {
+    let mut runtime = build_solidity(
+        r#"
+        contract test {
+            function mul(ufixed256x18 a, ufixed256x18 b) public pure returns (ufixed256x18) {
+                return a * b;
+            }
+
+            function smul(fixed256x18 a, fixed256x18 b) public pure returns (fixed256x18) {
+                return a * b;
+            }
+
+            function div(ufixed256x18 a, ufixed256x18 b) public pure returns (ufixed256x18) {
+                return a / b;
+            }
+
+            function widen(ufixed256x0 a) public pure returns (ufixed256x18) {
+                return ufixed256x18(a);
+            }
+        }"#,
+    );
+
+    let neg = |v: U256| U256::zero().overflowing_sub(v).0;
+
+    // the raw product 10^94 needs more than 256 bits: 1e29 * 1e29 = 1e58
+    runtime.function("mul", (U256::exp10(47), U256::exp10(47)).encode());
+    assert_eq!(runtime.output(), U256::exp10(76).encode());
+
+    // 1.5 * 2.5 = 3.75
+    runtime.function(
+        "mul",
+        (
+            U256::from(15) * U256::exp10(17),
+            U256::from(25) * U256::exp10(17),
+        )
+            .encode(),
+    );
+    assert_eq!(
+        runtime.output(),
+        (U256::from(375) * U256::exp10(16)).encode()
+    );
+
+    runtime.function("smul", (neg(U256::exp10(47)), U256::exp10(47)).encode());
+    assert_eq!(runtime.output(), neg(U256::exp10(76)).encode());
+
+    runtime.function(
+        "smul",
+        (neg(U256::exp10(47)), neg(U256::exp10(47))).encode(),
+    );
+    assert_eq!(runtime.output(), U256::exp10(76).encode());
+
+    // 1e30 * 1e30 does not fit
+    runtime.function_expect_failure("mul", (U256::exp10(48), U256::exp10(48)).encode());
+    runtime.function_expect_failure("smul", (U256::exp10(47), U256::exp10(48)).encode());
+
+    // 1e40 / 0.5 = 2e40
+    runtime.function(
+        "div",
+        (U256::exp10(58), U256::from(5) * U256::exp10(17)).encode(),
+    );
+    assert_eq!(runtime.output(), (U256::from(2) * U256::exp10(58)).encode());
+
+    // 1 / 3 rounds towards zero
+    runtime.function(
+        "div",
+        (U256::exp10(18), U256::from(3) * U256::exp10(18)).encode(),
+    );
+    assert_eq!(
+        runtime.output(),
+        U256::from(333_333_333_333_333_333u64).encode()
+    );
+
+    runtime.function_expect_failure("div", (U256::exp10(76), U256::one()).encode());
+    runtime.function_expect_failure("div", (U256::one(), U256::zero()).encode());
+
+    runtime.function("widen", U256::from(5).encode());
+    assert_eq!(runtime.output(), (U256::from(5) * U256::exp10(18)).encode());
+
+    runtime.function_expect_failure("widen", U256::exp10(60).encode());
+}
@@ -51,3 +51,52 @@ fn storage_initializer_addr_type() {
     runtime.function("owner", Vec::new());
     assert_eq!(runtime.output(), runtime.caller());
 }
+
+#[test]
+fn transient_storage() {
+    let mut runtime = build_solidity(
+        r#"
+contract C {
+    uint64 transient depth;
+    address transient owner;
+    uint64 counter;
+
+    function nested(uint64 n) public returns (uint64) {
+        depth += 1;
+        counter += 1;
+        owner = address(this);
+
+        if (n > 0) {
+            return this.nested(n - 1);
+        }
+
+        return depth;
+    }
+
+    function get() public view returns (uint64, address, uint64) {
+        return (depth, owner, counter);
+    }
+
+    function clear() public returns (uint64) {
+        depth = 5;
+        delete depth;
+        return depth;
+    }
+}"#,
+    );
+
+    runtime.constructor(0, Vec::new());
+
+    // reentrant calls share the transient variables
+    runtime.function("nested", 2u64.encode());
+    assert_eq!(runtime.output(), 3u64.encode());
+
+    // transient variables are gone after the transaction, while the storage variable in the
+    // same slot is kept
+    runtime.function("get", Vec::new());
+    assert_eq!(runtime.output(), (0u64, [0u8; 32], 3u64).encode());
+    assert_eq!(runtime.storage().len(), 1);
+
+    runtime.function("clear", Vec::new());
+    assert_eq!(runtime.output(), 0u64.encode());
+}
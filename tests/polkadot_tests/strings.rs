@@ -273,6 +273,11 @@ fn string_abi_decode() {
             function test() public {
                 string dec = abi.decode(hex"0c414141", (string));
                 assert(dec == "AAA");
+
+                // the string is not padded, so the next value follows it directly
+                (string s, uint8 v) = abi.decode(hex"0c41414107", (string, uint8));
+                assert(s == "AAA");
+                assert(v == 7);
             }
         }"#,
     );
@@ -0,0 +1,1144 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! An in-process emulator of the host functions of the revive pallet, so that contracts compiled
+//! for the PolkaVM target can be deployed and called on the PolkaVM interpreter without a node.
+
+use ethabi::Token;
+use polkavm::{
+    BackendKind, CallError, Caller, Config, Engine, Linker, MemoryAccessError, Module,
+    ModuleConfig, ProgramBlob, RawInstance,
+};
+use sha2::{Digest, Sha256};
+use solang::{codegen::Options, compile, file_resolver::FileResolver, Target};
+use std::{collections::HashMap, ffi::OsStr};
+use tiny_keccak::{Hasher, Keccak};
+
+mod polkavm_tests;
+
+type Address = [u8; 20];
+type Bytes32 = [u8; 32];
+
+/// The output of a call which succeeded, or the revert data
+type CallResult = Result<Vec<u8>, Vec<u8>>;
+
+/// The account which sends all transactions
+const CALLER: Address = [0x11; 20];
+
+/// The precompile which calculates the sha256 hash of its input
+const SHA256: Address = {
+    let mut address = [0; 20];
+    address[19] = 0x02;
+    address
+};
+
+/// Output pointer for which nothing is written, and the result of a storage lookup of a key
+/// which does not exist
+const SENTINEL: u32 = u32::MAX;
+
+/// Call flag for calls which may not modify any state
+const READ_ONLY: u32 = 16;
+
+/// Storage flag for transient storage
+const TRANSIENT: u32 = 1;
+
+/// Return flag for reverting the state changes of the call
+const REVERT: u32 = 1;
+
+/// Return codes of host functions
+const SUCCESS: u64 = 0;
+const CALLEE_TRAPPED: u64 = 1;
+const CALLEE_REVERTED: u64 = 2;
+const KEY_NOT_FOUND: u64 = 3;
+
+const BLOCK_NUMBER: u64 = 42;
+const TIMESTAMP: u64 = 1_700_000_000;
+const CHAIN_ID: u64 = 420_420_420;
+const MINIMUM_BALANCE: u128 = 500;
+const GAS_LIMIT: u64 = 2_000_000_000_000;
+const GAS_PRICE: u64 = 1_000;
+
+fn keccak256(data: &[u8]) -> Bytes32 {
+    let mut hasher = Keccak::v256();
+    let mut hash = [0u8; 32];
+
+    hasher.update(data);
+    hasher.finalize(&mut hash);
+
+    hash
+}
+
+/// The four byte selector of a function signature
+pub fn selector(signature: &str) -> [u8; 4] {
+    keccak256(signature.as_bytes())[..4].try_into().unwrap()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub address: Address,
+    pub topics: Vec<Bytes32>,
+    pub data: Vec<u8>,
+}
+
+#[derive(Default, Clone)]
+struct Account {
+    address: Address,
+    balance: u128,
+    nonce: u64,
+    /// The code hash of the program of a contract
+    code_hash: Option<Bytes32>,
+    storage: HashMap<Vec<u8>, Vec<u8>>,
+}
+
+/// The kind of call, which determines whose storage is used and whether state may change
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CallKind {
+    Call,
+    Delegate,
+    Static,
+}
+
+/// Host functions end the execution of the program with this error
+#[derive(Debug)]
+enum Exit {
+    Return { flags: u32, data: Vec<u8> },
+    Terminate(Address),
+    Trap(String),
+}
+
+impl From<MemoryAccessError> for Exit {
+    fn from(error: MemoryAccessError) -> Self {
+        Exit::Trap(error.to_string())
+    }
+}
+
+/// The outcome of executing a program
+enum Outcome {
+    Success(Box<Runtime>),
+    Revert(Vec<u8>),
+}
+
+/// The runtime provides the state of the mocked chain during program execution.
+#[derive(Default, Clone)]
+struct Runtime {
+    accounts: Vec<Account>,
+    /// Uploaded programs by their code hash
+    programs: HashMap<Bytes32, Vec<u8>>,
+    /// Index of the account whose storage, address and balance are used
+    account: usize,
+    /// The program which is executed; differs from the code of `account` for delegate calls
+    code_hash: Bytes32,
+    /// The `msg.sender` of the current frame
+    caller: Address,
+    /// The `msg.value` of the current frame
+    value: u128,
+    /// Set for static calls, in which the state may not be modified
+    read_only: bool,
+    input: Vec<u8>,
+    /// The data passed to `seal_return`
+    output: Vec<u8>,
+    /// The output of the last call or instantiate
+    return_data: Vec<u8>,
+    transient_storage: HashMap<(usize, Vec<u8>), Vec<u8>>,
+    events: Vec<Event>,
+}
+
+impl Runtime {
+    fn find_account(&self, address: &Address) -> Option<usize> {
+        self.accounts.iter().position(|a| &a.address == address)
+    }
+
+    fn find_or_create_account(&mut self, address: &Address) -> usize {
+        self.find_account(address).unwrap_or_else(|| {
+            self.accounts.push(Account {
+                address: *address,
+                ..Default::default()
+            });
+            self.accounts.len() - 1
+        })
+    }
+
+    fn address(&self) -> Address {
+        self.accounts[self.account].address
+    }
+
+    fn balance_of(&self, address: &Address) -> u128 {
+        self.find_account(address)
+            .map(|index| self.accounts[index].balance)
+            .unwrap_or_default()
+    }
+
+    /// Create the runtime context for a call into `account`, running the program `code_hash`.
+    fn new_context(
+        &self,
+        account: usize,
+        code_hash: Bytes32,
+        caller: Address,
+        input: Vec<u8>,
+        value: u128,
+        read_only: bool,
+    ) -> Self {
+        let mut runtime = self.clone();
+        runtime.account = account;
+        runtime.code_hash = code_hash;
+        runtime.caller = caller;
+        runtime.value = value;
+        runtime.read_only = read_only;
+        runtime.input = input;
+        runtime.output = Vec::new();
+        runtime.return_data = Vec::new();
+        runtime
+    }
+
+    /// After a successful execution, merge the state of the callee back.
+    fn accept_state(&mut self, callee: Self) {
+        self.accounts = callee.accounts;
+        self.transient_storage = callee.transient_storage;
+        self.events = callee.events;
+    }
+
+    /// Move value from the current account to another one
+    fn transfer(&mut self, to: usize, value: u128) -> bool {
+        if value > self.accounts[self.account].balance {
+            return false;
+        }
+
+        self.accounts[self.account].balance -= value;
+        self.accounts[to].balance += value;
+
+        true
+    }
+
+    /// Run the `deploy` or `call` export of the program `self.code_hash` to completion.
+    ///
+    /// Traps are returned as errors.
+    fn execute(mut self, export: &str) -> Result<Outcome, String> {
+        let mut config = Config::new();
+        config.set_backend(Some(BackendKind::Interpreter));
+
+        let engine = Engine::new(&config).map_err(|e| e.to_string())?;
+        let blob = ProgramBlob::parse(self.programs[&self.code_hash].clone().into())
+            .map_err(|e| e.to_string())?;
+        let module =
+            Module::from_blob(&engine, &ModuleConfig::new(), blob).map_err(|e| e.to_string())?;
+
+        let mut linker = Linker::new();
+
+        Runtime::define(&mut linker);
+
+        let mut instance = linker
+            .instantiate_pre(&module)
+            .and_then(|pre| pre.instantiate())
+            .map_err(|e| e.to_string())?;
+
+        match instance.call_typed(&mut self, export, ()) {
+            Ok(()) => Ok(Outcome::Success(Box::new(self))),
+            Err(CallError::User(Exit::Return { flags, data })) => {
+                if flags & REVERT != 0 {
+                    Ok(Outcome::Revert(data))
+                } else {
+                    self.output = data;
+                    Ok(Outcome::Success(Box::new(self)))
+                }
+            }
+            Err(CallError::User(Exit::Terminate(beneficiary))) => {
+                let beneficiary = self.find_or_create_account(&beneficiary);
+                let balance = self.accounts[self.account].balance;
+
+                self.transfer(beneficiary, balance);
+                self.accounts[self.account].code_hash = None;
+                self.accounts[self.account].storage.clear();
+
+                Ok(Outcome::Success(Box::new(self)))
+            }
+            Err(CallError::User(Exit::Trap(message))) => Err(message),
+            Err(error) => Err(format!("{error:?}")),
+        }
+    }
+
+    /// Call the account at `callee` and execute its program, if any.
+    fn call(
+        &mut self,
+        kind: CallKind,
+        callee: &Address,
+        input: Vec<u8>,
+        value: u128,
+    ) -> Result<CallResult, String> {
+        println!(
+            "call: account={} input={} value={value}",
+            hex::encode(callee),
+            hex::encode(&input)
+        );
+
+        if callee == &SHA256 {
+            return Ok(Ok(Sha256::digest(&input).to_vec()));
+        }
+
+        let callee_index = self.find_or_create_account(callee);
+        let code_hash = self.accounts[callee_index].code_hash;
+
+        let context = match kind {
+            CallKind::Call | CallKind::Static => {
+                let mut context = self.new_context(
+                    self.account,
+                    code_hash.unwrap_or_default(),
+                    self.address(),
+                    input,
+                    value,
+                    self.read_only || kind == CallKind::Static,
+                );
+
+                if !context.transfer(callee_index, value) {
+                    return Ok(Err(Vec::new()));
+                }
+
+                // the account of the transferred value is now the callee
+                context.account = callee_index;
+
+                context
+            }
+            CallKind::Delegate => self.new_context(
+                self.account,
+                code_hash.unwrap_or_default(),
+                self.caller,
+                input,
+                self.value,
+                self.read_only,
+            ),
+        };
+
+        let result = if code_hash.is_some() {
+            context.execute("call")?
+        } else {
+            Outcome::Success(Box::new(context))
+        };
+
+        match result {
+            Outcome::Success(callee) => {
+                let output = callee.output.clone();
+                self.accept_state(*callee);
+                Ok(Ok(output))
+            }
+            Outcome::Revert(data) => Ok(Err(data)),
+        }
+    }
+
+    /// Deploy the program with the given code hash, and run its constructor
+    fn instantiate(
+        &mut self,
+        code_hash: Bytes32,
+        input: Vec<u8>,
+        value: u128,
+        salt: Option<Bytes32>,
+    ) -> Result<(Address, CallResult), String> {
+        if !self.programs.contains_key(&code_hash) {
+            return Err(format!("code hash {} not found", hex::encode(code_hash)));
+        }
+
+        let deployer = &mut self.accounts[self.account];
+
+        let address: Address = match salt {
+            Some(salt) => {
+                let mut data = vec![0xff];
+                data.extend_from_slice(&deployer.address);
+                data.extend_from_slice(&salt);
+                data.extend_from_slice(&code_hash);
+
+                keccak256(&data)[12..].try_into().unwrap()
+            }
+            None => {
+                let mut data = deployer.address.to_vec();
+                data.extend_from_slice(&deployer.nonce.to_be_bytes());
+
+                keccak256(&data)[12..].try_into().unwrap()
+            }
+        };
+
+        deployer.nonce += 1;
+
+        println!(
+            "instantiate: code_hash={} address={} input={} value={value}",
+            hex::encode(code_hash),
+            hex::encode(address),
+            hex::encode(&input)
+        );
+
+        let index = self.find_or_create_account(&address);
+
+        if self.accounts[index].code_hash.is_some() {
+            return Ok((address, Err(Vec::new())));
+        }
+
+        let mut context =
+            self.new_context(self.account, code_hash, self.address(), input, value, false);
+
+        if !context.transfer(index, value) {
+            return Ok((address, Err(Vec::new())));
+        }
+
+        // the new contract is the account of the constructor
+        context.account = index;
+        context.accounts[index].code_hash = Some(code_hash);
+
+        match context.execute("deploy")? {
+            Outcome::Success(callee) => {
+                let output = callee.output.clone();
+                self.accept_state(*callee);
+                Ok((address, Ok(output)))
+            }
+            Outcome::Revert(data) => Ok((address, Err(data))),
+        }
+    }
+
+    /// Storage of the current account, or the transient storage
+    fn storage(&mut self, flags: u32) -> StorageRef<'_> {
+        if flags & TRANSIENT != 0 {
+            StorageRef::Transient(self.account, &mut self.transient_storage)
+        } else {
+            StorageRef::Persistent(&mut self.accounts[self.account].storage)
+        }
+    }
+
+    fn check_writable(&self) -> Result<(), Exit> {
+        if self.read_only {
+            Err(Exit::Trap("state change in static call".to_string()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+enum StorageRef<'a> {
+    Persistent(&'a mut HashMap<Vec<u8>, Vec<u8>>),
+    Transient(usize, &'a mut HashMap<(usize, Vec<u8>), Vec<u8>>),
+}
+
+impl StorageRef<'_> {
+    fn get(&self, key: Vec<u8>) -> Option<Vec<u8>> {
+        match self {
+            StorageRef::Persistent(storage) => storage.get(&key).cloned(),
+            StorageRef::Transient(account, storage) => storage.get(&(*account, key)).cloned(),
+        }
+    }
+
+    fn set(&mut self, key: Vec<u8>, value: Option<Vec<u8>>) -> Option<Vec<u8>> {
+        match (self, value) {
+            (StorageRef::Persistent(storage), Some(value)) => storage.insert(key, value),
+            (StorageRef::Persistent(storage), None) => storage.remove(&key),
+            (StorageRef::Transient(account, storage), Some(value)) => {
+                storage.insert((*account, key), value)
+            }
+            (StorageRef::Transient(account, storage), None) => storage.remove(&(*account, key)),
+        }
+    }
+}
+
+/// The lower half of a register which holds two 32 bit values
+fn lo(packed: u64) -> u32 {
+    packed as u32
+}
+
+/// The upper half of a register which holds two 32 bit values
+fn hi(packed: u64) -> u32 {
+    (packed >> 32) as u32
+}
+
+fn read_address(instance: &RawInstance, ptr: u32) -> Result<Address, Exit> {
+    Ok(instance.read_memory(ptr, 20)?.try_into().unwrap())
+}
+
+fn read_bytes32(instance: &RawInstance, ptr: u32) -> Result<Bytes32, Exit> {
+    Ok(instance.read_memory(ptr, 32)?.try_into().unwrap())
+}
+
+/// Values are 256 bit little endian integers
+fn read_value(instance: &RawInstance, ptr: u32) -> Result<u128, Exit> {
+    let value = instance.read_memory(ptr, 32)?;
+
+    if value[16..].iter().any(|b| *b != 0) {
+        return Err(Exit::Trap("value does not fit in 128 bits".to_string()));
+    }
+
+    Ok(u128::from_le_bytes(value[..16].try_into().unwrap()))
+}
+
+fn write_u256(instance: &mut RawInstance, ptr: u32, value: u128) -> Result<(), Exit> {
+    let mut buf = value.to_le_bytes().to_vec();
+    buf.resize(32, 0);
+
+    Ok(instance.write_memory(ptr, &buf)?)
+}
+
+/// Write the output to a buffer, whose length is passed in and out through `out_len_ptr`
+fn write_output(
+    instance: &mut RawInstance,
+    out_ptr: u32,
+    out_len_ptr: u32,
+    data: &[u8],
+) -> Result<(), Exit> {
+    if out_ptr == SENTINEL {
+        return Ok(());
+    }
+
+    let capacity = instance.read_u32(out_len_ptr)?;
+
+    if data.len() > capacity as usize {
+        return Err(Exit::Trap(format!(
+            "output buffer of {capacity} bytes too small for {} bytes",
+            data.len()
+        )));
+    }
+
+    instance.write_memory(out_ptr, data)?;
+    instance.write_memory(out_len_ptr, &(data.len() as u32).to_le_bytes())?;
+
+    Ok(())
+}
+
+/// Write the output of a call to the caller, and turn the result into a return code
+fn finish_call(
+    caller: Caller<Runtime>,
+    result: Result<CallResult, String>,
+    output: u64,
+) -> Result<u64, Exit> {
+    let (code, data) = match result {
+        Ok(Ok(data)) => (SUCCESS, data),
+        Ok(Err(data)) => (CALLEE_REVERTED, data),
+        Err(trap) => {
+            println!("callee trapped: {trap}");
+            (CALLEE_TRAPPED, Vec::new())
+        }
+    };
+
+    write_output(caller.instance, lo(output), hi(output), &data)?;
+
+    caller.user_data.return_data = data;
+
+    Ok(code)
+}
+
+impl Runtime {
+    /// Define the host functions of the revive pallet which the emitted code may import
+    fn define(linker: &mut Linker<Runtime, Exit>) {
+        linker
+            .define_typed(
+                "set_storage",
+                |caller: Caller<Runtime>,
+                 flags: u32,
+                 key_ptr: u32,
+                 key_len: u32,
+                 value_ptr: u32,
+                 value_len: u32|
+                 -> Result<u64, Exit> {
+                    caller.user_data.check_writable()?;
+
+                    let key = caller.instance.read_memory(key_ptr, key_len)?;
+                    let value = caller.instance.read_memory(value_ptr, value_len)?;
+
+                    let old = caller.user_data.storage(flags).set(key, Some(value));
+
+                    Ok(old.map_or(SENTINEL, |v| v.len() as u32).into())
+                },
+            )
+            .unwrap()
+            .define_typed(
+                "get_storage",
+                |caller: Caller<Runtime>,
+                 flags: u32,
+                 key_ptr: u32,
+                 key_len: u32,
+                 out_ptr: u32,
+                 out_len_ptr: u32|
+                 -> Result<u64, Exit> {
+                    let key = caller.instance.read_memory(key_ptr, key_len)?;
+
+                    match caller.user_data.storage(flags).get(key) {
+                        Some(value) => {
+                            write_output(caller.instance, out_ptr, out_len_ptr, &value)?;
+                            Ok(SUCCESS)
+                        }
+                        None => Ok(KEY_NOT_FOUND),
+                    }
+                },
+            )
+            .unwrap()
+            .define_typed(
+                "clear_storage",
+                |caller: Caller<Runtime>,
+                 flags: u32,
+                 key_ptr: u32,
+                 key_len: u32|
+                 -> Result<u64, Exit> {
+                    caller.user_data.check_writable()?;
+
+                    let key = caller.instance.read_memory(key_ptr, key_len)?;
+
+                    let old = caller.user_data.storage(flags).set(key, None);
+
+                    Ok(old.map_or(SENTINEL, |v| v.len() as u32).into())
+                },
+            )
+            .unwrap()
+            .define_typed(
+                "contains_storage",
+                |caller: Caller<Runtime>,
+                 flags: u32,
+                 key_ptr: u32,
+                 key_len: u32|
+                 -> Result<u64, Exit> {
+                    let key = caller.instance.read_memory(key_ptr, key_len)?;
+
+                    let value = caller.user_data.storage(flags).get(key);
+
+                    Ok(value.map_or(SENTINEL, |v| v.len() as u32).into())
+                },
+            )
+            .unwrap()
+            .define_typed(
+                "take_storage",
+                |caller: Caller<Runtime>,
+                 flags: u32,
+                 key_ptr: u32,
+                 key_len: u32,
+                 out_ptr: u32,
+                 out_len_ptr: u32|
+                 -> Result<u64, Exit> {
+                    caller.user_data.check_writable()?;
+
+                    let key = caller.instance.read_memory(key_ptr, key_len)?;
+
+                    match caller.user_data.storage(flags).set(key, None) {
+                        Some(value) => {
+                            write_output(caller.instance, out_ptr, out_len_ptr, &value)?;
+                            Ok(SUCCESS)
+                        }
+                        None => Ok(KEY_NOT_FOUND),
+                    }
+                },
+            )
+            .unwrap()
+            .define_typed(
+                "call",
+                |caller: Caller<Runtime>,
+                 flags_and_callee: u64,
+                 _ref_time_limit: u64,
+                 _proof_size_limit: u64,
+                 deposit_and_value: u64,
+                 input_data: u64,
+                 output_data: u64|
+                 -> Result<u64, Exit> {
+                    let flags = hi(flags_and_callee);
+                    let callee = read_address(caller.instance, lo(flags_and_callee))?;
+                    let value = read_value(caller.instance, lo(deposit_and_value))?;
+                    let input = caller
+                        .instance
+                        .read_memory(lo(input_data), hi(input_data))?;
+
+                    if value != 0 {
+                        caller.user_data.check_writable()?;
+                    }
+
+                    let kind = if flags & READ_ONLY != 0 {
+                        CallKind::Static
+                    } else {
+                        CallKind::Call
+                    };
+
+                    let result = caller.user_data.call(kind, &callee, input, value);
+
+                    finish_call(caller, result, output_data)
+                },
+            )
+            .unwrap()
+            .define_typed(
+                "delegate_call",
+                |caller: Caller<Runtime>,
+                 flags_and_callee: u64,
+                 _ref_time_limit: u64,
+                 _proof_size_limit: u64,
+                 _deposit_ptr: u64,
+                 input_data: u64,
+                 output_data: u64|
+                 -> Result<u64, Exit> {
+                    let callee = read_address(caller.instance, lo(flags_and_callee))?;
+                    let input = caller
+                        .instance
+                        .read_memory(lo(input_data), hi(input_data))?;
+
+                    let result = caller.user_data.call(CallKind::Delegate, &callee, input, 0);
+
+                    finish_call(caller, result, output_data)
+                },
+            )
+            .unwrap()
+            .define_typed(
+                "instantiate",
+                |caller: Caller<Runtime>,
+                 _ref_time_limit: u64,
+                 _proof_size_limit: u64,
+                 deposit_and_value: u64,
+                 input_data: u64,
+                 output_data: u64,
+                 address_and_salt: u64|
+                 -> Result<u64, Exit> {
+                    caller.user_data.check_writable()?;
+
+                    let value = read_value(caller.instance, lo(deposit_and_value))?;
+                    let input = caller
+                        .instance
+                        .read_memory(lo(input_data), hi(input_data))?;
+
+                    // the code hash is followed by the constructor arguments
+                    let code_hash = input[..32].try_into().unwrap();
+                    let input = input[32..].to_vec();
+
+                    let salt = match lo(address_and_salt) {
+                        SENTINEL => None,
+                        salt_ptr => Some(read_bytes32(caller.instance, salt_ptr)?),
+                    };
+
+                    let (address, result) = caller
+                        .user_data
+                        .instantiate(code_hash, input, value, salt)
+                        .map_err(Exit::Trap)?;
+
+                    if result.is_ok() && hi(address_and_salt) != SENTINEL {
+                        caller
+                            .instance
+                            .write_memory(hi(address_and_salt), &address)?;
+                    }
+
+                    finish_call(caller, Ok(result), output_data)
+                },
+            )
+            .unwrap()
+            .define_typed(
+                "terminate",
+                |caller: Caller<Runtime>, beneficiary_ptr: u32| -> Result<(), Exit> {
+                    caller.user_data.check_writable()?;
+
+                    let beneficiary = read_address(caller.instance, beneficiary_ptr)?;
+
+                    Err(Exit::Terminate(beneficiary))
+                },
+            )
+            .unwrap()
+            .define_typed("call_data_size", |caller: Caller<Runtime>| -> u64 {
+                caller.user_data.input.len() as u64
+            })
+            .unwrap()
+            .define_typed(
+                "call_data_copy",
+                |caller: Caller<Runtime>,
+                 out_ptr: u32,
+                 out_len: u32,
+                 offset: u32|
+                 -> Result<(), Exit> {
+                    // the remainder of the output buffer is zeroed
+                    let mut data = caller
+                        .user_data
+                        .input
+                        .get(offset as usize..)
+                        .unwrap_or_default()
+                        .to_vec();
+                    data.resize(out_len as usize, 0);
+
+                    Ok(caller.instance.write_memory(out_ptr, &data)?)
+                },
+            )
+            .unwrap()
+            .define_typed(
+                "seal_return",
+                |caller: Caller<Runtime>,
+                 flags: u32,
+                 data_ptr: u32,
+                 data_len: u32|
+                 -> Result<(), Exit> {
+                    let data = caller.instance.read_memory(data_ptr, data_len)?;
+
+                    Err(Exit::Return { flags, data })
+                },
+            )
+            .unwrap()
+            .define_typed("return_data_size", |caller: Caller<Runtime>| -> u64 {
+                caller.user_data.return_data.len() as u64
+            })
+            .unwrap()
+            .define_typed(
+                "return_data_copy",
+                |caller: Caller<Runtime>,
+                 out_ptr: u32,
+                 out_len_ptr: u32,
+                 offset: u32|
+                 -> Result<(), Exit> {
+                    let capacity = caller.instance.read_u32(out_len_ptr)? as usize;
+                    let Some(data) = caller.user_data.return_data.get(offset as usize..) else {
+                        return Err(Exit::Trap("return data offset out of bounds".to_string()));
+                    };
+                    let data = data[..data.len().min(capacity)].to_vec();
+
+                    write_output(caller.instance, out_ptr, out_len_ptr, &data)
+                },
+            )
+            .unwrap()
+            .define_typed(
+                "caller",
+                |caller: Caller<Runtime>, out_ptr: u32| -> Result<(), Exit> {
+                    Ok(caller
+                        .instance
+                        .write_memory(out_ptr, &caller.user_data.caller)?)
+                },
+            )
+            .unwrap()
+            .define_typed(
+                "origin",
+                |caller: Caller<Runtime>, out_ptr: u32| -> Result<(), Exit> {
+                    Ok(caller.instance.write_memory(out_ptr, &CALLER)?)
+                },
+            )
+            .unwrap()
+            .define_typed(
+                "address",
+                |caller: Caller<Runtime>, out_ptr: u32| -> Result<(), Exit> {
+                    Ok(caller
+                        .instance
+                        .write_memory(out_ptr, &caller.user_data.address())?)
+                },
+            )
+            .unwrap()
+            .define_typed(
+                "balance",
+                |caller: Caller<Runtime>, out_ptr: u32| -> Result<(), Exit> {
+                    let balance = caller.user_data.accounts[caller.user_data.account].balance;
+
+                    write_u256(caller.instance, out_ptr, balance)
+                },
+            )
+            .unwrap()
+            .define_typed(
+                "balance_of",
+                |caller: Caller<Runtime>, address_ptr: u32, out_ptr: u32| -> Result<(), Exit> {
+                    let address = read_address(caller.instance, address_ptr)?;
+                    let balance = caller.user_data.balance_of(&address);
+
+                    write_u256(caller.instance, out_ptr, balance)
+                },
+            )
+            .unwrap()
+            .define_typed(
+                "value_transferred",
+                |caller: Caller<Runtime>, out_ptr: u32| -> Result<(), Exit> {
+                    write_u256(caller.instance, out_ptr, caller.user_data.value)
+                },
+            )
+            .unwrap()
+            .define_typed(
+                "minimum_balance",
+                |caller: Caller<Runtime>, out_ptr: u32| -> Result<(), Exit> {
+                    write_u256(caller.instance, out_ptr, MINIMUM_BALANCE)
+                },
+            )
+            .unwrap()
+            .define_typed(
+                "chain_id",
+                |caller: Caller<Runtime>, out_ptr: u32| -> Result<(), Exit> {
+                    write_u256(caller.instance, out_ptr, CHAIN_ID.into())
+                },
+            )
+            .unwrap()
+            .define_typed(
+                "base_fee",
+                |caller: Caller<Runtime>, out_ptr: u32| -> Result<(), Exit> {
+                    write_u256(caller.instance, out_ptr, GAS_PRICE.into())
+                },
+            )
+            .unwrap()
+            .define_typed(
+                "block_number",
+                |caller: Caller<Runtime>, out_ptr: u32| -> Result<(), Exit> {
+                    write_u256(caller.instance, out_ptr, BLOCK_NUMBER.into())
+                },
+            )
+            .unwrap()
+            .define_typed(
+                "now",
+                |caller: Caller<Runtime>, out_ptr: u32| -> Result<(), Exit> {
+                    write_u256(caller.instance, out_ptr, TIMESTAMP.into())
+                },
+            )
+            .unwrap()
+            .define_typed("gas_limit", || -> u64 { GAS_LIMIT })
+            .unwrap()
+            .define_typed("gas_price", || -> u64 { GAS_PRICE })
+            .unwrap()
+            .define_typed("ref_time_left", || -> u64 { GAS_LIMIT / 2 })
+            .unwrap()
+            .define_typed(
+                "deposit_event",
+                |caller: Caller<Runtime>,
+                 topics_ptr: u32,
+                 num_topic: u32,
+                 data_ptr: u32,
+                 data_len: u32|
+                 -> Result<(), Exit> {
+                    caller.user_data.check_writable()?;
+
+                    let topics = (0..num_topic)
+                        .map(|i| read_bytes32(caller.instance, topics_ptr + i * 32))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let data = caller.instance.read_memory(data_ptr, data_len)?;
+
+                    let address = caller.user_data.address();
+
+                    caller.user_data.events.push(Event {
+                        address,
+                        topics,
+                        data,
+                    });
+
+                    Ok(())
+                },
+            )
+            .unwrap()
+            .define_typed(
+                "hash_keccak_256",
+                |caller: Caller<Runtime>,
+                 input_ptr: u32,
+                 input_len: u32,
+                 out_ptr: u32|
+                 -> Result<(), Exit> {
+                    let input = caller.instance.read_memory(input_ptr, input_len)?;
+
+                    Ok(caller.instance.write_memory(out_ptr, &keccak256(&input))?)
+                },
+            )
+            .unwrap()
+            .define_typed(
+                "hash_blake2_128",
+                |caller: Caller<Runtime>,
+                 input_ptr: u32,
+                 input_len: u32,
+                 out_ptr: u32|
+                 -> Result<(), Exit> {
+                    let input = caller.instance.read_memory(input_ptr, input_len)?;
+                    let hash = blake2_rfc::blake2b::blake2b(16, &[], &input);
+
+                    Ok(caller.instance.write_memory(out_ptr, hash.as_bytes())?)
+                },
+            )
+            .unwrap()
+            .define_typed(
+                "hash_blake2_256",
+                |caller: Caller<Runtime>,
+                 input_ptr: u32,
+                 input_len: u32,
+                 out_ptr: u32|
+                 -> Result<(), Exit> {
+                    let input = caller.instance.read_memory(input_ptr, input_len)?;
+                    let hash = blake2_rfc::blake2b::blake2b(32, &[], &input);
+
+                    Ok(caller.instance.write_memory(out_ptr, hash.as_bytes())?)
+                },
+            )
+            .unwrap()
+            .define_typed(
+                "is_contract",
+                |caller: Caller<Runtime>, address_ptr: u32| -> Result<u64, Exit> {
+                    let address = read_address(caller.instance, address_ptr)?;
+                    let runtime = &caller.user_data;
+
+                    let is_contract = runtime
+                        .find_account(&address)
+                        .is_some_and(|index| runtime.accounts[index].code_hash.is_some());
+
+                    Ok(is_contract.into())
+                },
+            )
+            .unwrap()
+            .define_typed("caller_is_root", || -> u64 { 0 })
+            .unwrap()
+            .define_typed(
+                "own_code_hash",
+                |caller: Caller<Runtime>, out_ptr: u32| -> Result<(), Exit> {
+                    let runtime = &caller.user_data;
+                    let code_hash = runtime.accounts[runtime.account].code_hash.unwrap();
+
+                    Ok(caller.instance.write_memory(out_ptr, &code_hash)?)
+                },
+            )
+            .unwrap();
+    }
+}
+
+pub struct MockPolkaVM {
+    runtime: Runtime,
+    /// The code hashes of the compiled contracts, by name
+    programs: Vec<(String, Bytes32)>,
+    /// The contract which is called by [`MockPolkaVM::function`]
+    pub address: Address,
+    /// The value sent with the next call
+    pub value: u128,
+}
+
+/// Compile the source and deploy the last contract, with a constructor without arguments
+pub fn build_solidity(src: &str) -> MockPolkaVM {
+    let mut vm = compile_solidity(src);
+
+    let name = vm.programs.last().unwrap().0.clone();
+
+    vm.deploy(&name, &[]);
+
+    vm
+}
+
+/// Compile the source and upload the programs, without deploying anything
+pub fn compile_solidity(src: &str) -> MockPolkaVM {
+    let mut cache = FileResolver::default();
+
+    cache.set_file_contents("test.sol", src.to_string());
+
+    let (contracts, ns) = compile(
+        OsStr::new("test.sol"),
+        &mut cache,
+        Target::PolkaVM,
+        &Options::default(),
+        vec!["unknown".to_string()],
+        "0.0.1",
+    );
+
+    ns.print_diagnostics_in_plain(&cache, false);
+
+    assert!(!contracts.is_empty());
+
+    let mut runtime = Runtime {
+        accounts: vec![Account {
+            address: CALLER,
+            balance: u128::MAX,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    // compile() returns the instantiable contracts in the same order
+    let programs = ns
+        .contracts
+        .iter()
+        .filter(|contract| contract.instantiable)
+        .map(|contract| contract.id.name.clone())
+        .zip(contracts)
+        .map(|(name, (code, _))| {
+            let code_hash = keccak256(&code);
+
+            runtime.programs.insert(code_hash, code);
+
+            (name, code_hash)
+        })
+        .collect();
+
+    MockPolkaVM {
+        runtime,
+        programs,
+        address: Address::default(),
+        value: 0,
+    }
+}
+
+impl MockPolkaVM {
+    /// Deploy the contract with the given name and constructor arguments, and make it the
+    /// callee of subsequent calls.
+    pub fn deploy(&mut self, name: &str, args: &[Token]) -> Address {
+        let code_hash = self
+            .programs
+            .iter()
+            .find(|(contract, _)| contract == name)
+            .map(|(_, code_hash)| *code_hash)
+            .unwrap_or_else(|| panic!("contract {name} not found"));
+        let value = std::mem::take(&mut self.value);
+
+        let runtime = &mut self.runtime;
+
+        runtime.account = 0;
+        runtime.transient_storage.clear();
+        runtime.events.clear();
+
+        let (address, result) = runtime
+            .instantiate(code_hash, ethabi::encode(args), value, None)
+            .unwrap_or_else(|trap| panic!("trap: {trap}"));
+
+        if let Err(data) = result {
+            panic!("constructor of {name} reverted with {}", hex::encode(data));
+        }
+
+        self.address = address;
+
+        address
+    }
+
+    /// Call a function and return its return data, which is expected to succeed
+    pub fn function(&mut self, signature: &str, args: &[Token]) -> Vec<u8> {
+        match self.call(signature, args) {
+            Ok(data) => data,
+            Err(data) => panic!("{signature} reverted with {}", hex::encode(data)),
+        }
+    }
+
+    /// Call a function which is expected to revert, and return the revert data
+    pub fn function_revert(&mut self, signature: &str, args: &[Token]) -> Vec<u8> {
+        match self.call(signature, args) {
+            Ok(data) => panic!("{signature} returned {}", hex::encode(data)),
+            Err(data) => data,
+        }
+    }
+
+    /// Call a function and decode its return values
+    pub fn function_returns(
+        &mut self,
+        signature: &str,
+        args: &[Token],
+        returns: &[ethabi::ParamType],
+    ) -> Vec<Token> {
+        let data = self.function(signature, args);
+
+        ethabi::decode(returns, &data).unwrap()
+    }
+
+    /// Call a function with the given arguments
+    pub fn call(&mut self, signature: &str, args: &[Token]) -> Result<Vec<u8>, Vec<u8>> {
+        let mut data = selector(signature).to_vec();
+        data.extend(ethabi::encode(args));
+
+        self.raw_call(data)
+    }
+
+    /// Call the deployed contract with raw call data, as a transaction from the caller account.
+    ///
+    /// Panics if the program traps.
+    pub fn raw_call(&mut self, data: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
+        let value = std::mem::take(&mut self.value);
+        let runtime = &mut self.runtime;
+
+        runtime.account = 0;
+        runtime.transient_storage.clear();
+        runtime.events.clear();
+
+        runtime
+            .call(CallKind::Call, &self.address, data, value)
+            .unwrap_or_else(|trap| panic!("trap: {trap}"))
+    }
+
+    /// The balance of an account
+    pub fn balance(&self, address: &Address) -> u128 {
+        self.runtime.balance_of(address)
+    }
+
+    /// Whether there is a contract at the given address
+    pub fn is_contract(&self, address: &Address) -> bool {
+        self.runtime
+            .find_account(address)
+            .is_some_and(|index| self.runtime.accounts[index].code_hash.is_some())
+    }
+
+    /// Events emitted by the last transaction
+    pub fn events(&self) -> &[Event] {
+        &self.runtime.events
+    }
+}
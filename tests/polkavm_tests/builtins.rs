@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{build_solidity, selector};
+use ethabi::{ParamType, Token};
+use sha2::{Digest, Sha256};
+
+#[test]
+fn msg_and_block() {
+    let mut vm = build_solidity(
+        r#"
+        contract C {
+            function data(uint32 a) public pure returns (bytes memory) {
+                return msg.data;
+            }
+
+            function info() public view returns (address, address, uint64, uint64, uint256) {
+                return (msg.sender, address(this), block.number, block.timestamp, block.chainid);
+            }
+        }"#,
+    );
+
+    let ret = vm.function_returns(
+        "data(uint32)",
+        &[Token::Uint(7.into())],
+        &[ParamType::Bytes],
+    );
+    let mut data = selector("data(uint32)").to_vec();
+    data.extend(ethabi::encode(&[Token::Uint(7.into())]));
+    assert_eq!(ret, vec![Token::Bytes(data)]);
+
+    let ret = vm.function_returns(
+        "info()",
+        &[],
+        &[
+            ParamType::Address,
+            ParamType::Address,
+            ParamType::Uint(64),
+            ParamType::Uint(64),
+            ParamType::Uint(256),
+        ],
+    );
+    assert_eq!(
+        ret,
+        vec![
+            Token::Address([0x11; 20].into()),
+            Token::Address(vm.address.into()),
+            Token::Uint(42.into()),
+            Token::Uint(1_700_000_000.into()),
+            Token::Uint(420_420_420.into()),
+        ]
+    );
+}
+
+#[test]
+fn hashes() {
+    let mut vm = build_solidity(
+        r#"
+        contract C {
+            function hash(bytes memory b) public pure returns (bytes32, bytes32, bytes32) {
+                return (keccak256(b), sha256(b), blake2_256(b));
+            }
+        }"#,
+    );
+
+    let input = b"Hello, PolkaVM!".to_vec();
+
+    let ret = vm.function_returns(
+        "hash(bytes)",
+        &[Token::Bytes(input.clone())],
+        &[
+            ParamType::FixedBytes(32),
+            ParamType::FixedBytes(32),
+            ParamType::FixedBytes(32),
+        ],
+    );
+
+    let mut keccak = [0u8; 32];
+    let mut hasher = tiny_keccak::Keccak::v256();
+    tiny_keccak::Hasher::update(&mut hasher, &input);
+    tiny_keccak::Hasher::finalize(hasher, &mut keccak);
+
+    assert_eq!(
+        ret,
+        vec![
+            Token::FixedBytes(keccak.to_vec()),
+            Token::FixedBytes(Sha256::digest(&input).to_vec()),
+            Token::FixedBytes(
+                blake2_rfc::blake2b::blake2b(32, &[], &input)
+                    .as_bytes()
+                    .to_vec()
+            ),
+        ]
+    );
+}
+
+/// Functions with more arguments than PolkaVM has argument registers take their arguments
+/// through memory
+#[test]
+fn many_arguments() {
+    let mut vm = build_solidity(
+        r#"
+        contract C {
+            function sum(uint64 a, uint64 b, uint64 c, uint64 d, uint64 e, uint64 f, uint64 g, uint64 h) public pure returns (uint64) {
+                return add(a, b, c, d, e, f, g, h);
+            }
+
+            function add(uint64 a, uint64 b, uint64 c, uint64 d, uint64 e, uint64 f, uint64 g, uint64 h) internal pure returns (uint64) {
+                return a + 2 * b + 3 * c + 4 * d + 5 * e + 6 * f + 7 * g + 8 * h;
+            }
+        }"#,
+    );
+
+    let args = (1..=8).map(|n| Token::Uint(n.into())).collect::<Vec<_>>();
+
+    let ret = vm.function_returns(
+        "sum(uint64,uint64,uint64,uint64,uint64,uint64,uint64,uint64)",
+        &args,
+        &[ParamType::Uint(64)],
+    );
+    assert_eq!(ret, vec![Token::Uint(204.into())]);
+}
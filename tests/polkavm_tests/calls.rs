@@ -0,0 +1,159 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{build_solidity, compile_solidity, selector};
+use ethabi::{ParamType, Token};
+
+#[test]
+fn create_and_call() {
+    let mut vm = build_solidity(
+        r#"
+        contract Adder {
+            uint256 base;
+
+            constructor(uint256 b) {
+                base = b;
+            }
+
+            function add(uint256 a) public view returns (uint256) {
+                return base + a;
+            }
+
+            function fail(uint256 a) public pure {
+                require(a < 10, "too large");
+            }
+        }
+
+        contract Creator {
+            Adder adder;
+
+            function create(uint256 base) public returns (address) {
+                adder = new Adder(base);
+                return address(adder);
+            }
+
+            function add(uint256 a) public view returns (uint256) {
+                return adder.add(a);
+            }
+
+            function tryFail(uint256 a) public view returns (string memory) {
+                try adder.fail(a) {
+                    return "ok";
+                } catch Error(string memory reason) {
+                    return reason;
+                }
+            }
+        }"#,
+    );
+
+    let ret = vm.function_returns(
+        "create(uint256)",
+        &[Token::Uint(100.into())],
+        &[ParamType::Address],
+    );
+    let Token::Address(adder) = ret[0] else {
+        panic!("{ret:?}")
+    };
+    assert!(vm.is_contract(&adder.0));
+
+    let ret = vm.function_returns(
+        "add(uint256)",
+        &[Token::Uint(23.into())],
+        &[ParamType::Uint(256)],
+    );
+    assert_eq!(ret, vec![Token::Uint(123.into())]);
+
+    let ret = vm.function_returns(
+        "tryFail(uint256)",
+        &[Token::Uint(1.into())],
+        &[ParamType::String],
+    );
+    assert_eq!(ret, vec![Token::String("ok".into())]);
+
+    let ret = vm.function_returns(
+        "tryFail(uint256)",
+        &[Token::Uint(11.into())],
+        &[ParamType::String],
+    );
+    assert_eq!(ret, vec![Token::String("too large".into())]);
+}
+
+#[test]
+fn revert() {
+    let mut vm = build_solidity(
+        r#"
+        contract C {
+            error Custom(uint64 code);
+
+            function check(uint64 a) public pure returns (uint64) {
+                require(a != 1, "one");
+                if (a == 2) {
+                    revert Custom(a);
+                }
+                return a * 2;
+            }
+        }"#,
+    );
+
+    let ret = vm.function_returns(
+        "check(uint64)",
+        &[Token::Uint(4.into())],
+        &[ParamType::Uint(64)],
+    );
+    assert_eq!(ret, vec![Token::Uint(8.into())]);
+
+    let data = vm.function_revert("check(uint64)", &[Token::Uint(1.into())]);
+    assert_eq!(data[..4], selector("Error(string)"));
+    assert_eq!(
+        ethabi::decode(&[ParamType::String], &data[4..]).unwrap(),
+        vec![Token::String("one".into())]
+    );
+
+    let data = vm.function_revert("check(uint64)", &[Token::Uint(2.into())]);
+    assert_eq!(data[..4], selector("Custom(uint64)"));
+    assert_eq!(
+        ethabi::decode(&[ParamType::Uint(64)], &data[4..]).unwrap(),
+        vec![Token::Uint(2.into())]
+    );
+}
+
+#[test]
+fn value_transfer() {
+    let mut vm = compile_solidity(
+        r#"
+        contract Bank {
+            constructor() payable {}
+
+            function pay(address payable to, uint128 amount) public {
+                to.transfer(amount);
+            }
+
+            function received() public payable returns (uint256) {
+                return msg.value;
+            }
+        }"#,
+    );
+
+    vm.value = 1000;
+    let bank = vm.deploy("Bank", &[]);
+    assert_eq!(vm.balance(&bank), 1000);
+
+    let to = [0x22; 20];
+
+    vm.function(
+        "pay(address,uint128)",
+        &[Token::Address(to.into()), Token::Uint(300.into())],
+    );
+    assert_eq!(vm.balance(&bank), 700);
+    assert_eq!(vm.balance(&to), 300);
+
+    vm.value = 5;
+    let ret = vm.function_returns("received()", &[], &[ParamType::Uint(256)]);
+    assert_eq!(ret, vec![Token::Uint(5.into())]);
+    assert_eq!(vm.balance(&bank), 705);
+
+    // not enough balance
+    vm.function_revert(
+        "pay(address,uint128)",
+        &[Token::Address(to.into()), Token::Uint(1000.into())],
+    );
+}
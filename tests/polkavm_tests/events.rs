@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::build_solidity;
+use ethabi::Token;
+use tiny_keccak::{Hasher, Keccak};
+
+#[test]
+fn emit() {
+    let mut vm = build_solidity(
+        r#"
+        contract C {
+            event Transfer(address indexed from, address indexed to, uint256 value);
+            event Message(string text) anonymous;
+
+            function emitBoth(address to) public {
+                emit Transfer(msg.sender, to, 42);
+                emit Message("hello");
+            }
+        }"#,
+    );
+
+    let to = [0x33; 20];
+
+    vm.function("emitBoth(address)", &[Token::Address(to.into())]);
+
+    let events = vm.events();
+    assert_eq!(events.len(), 2);
+
+    let mut signature = [0u8; 32];
+    let mut hasher = Keccak::v256();
+    hasher.update(b"Transfer(address,address,uint256)");
+    hasher.finalize(&mut signature);
+
+    let mut from_topic = [0u8; 32];
+    from_topic[12..].copy_from_slice(&[0x11; 20]);
+    let mut to_topic = [0u8; 32];
+    to_topic[12..].copy_from_slice(&to);
+
+    assert_eq!(events[0].address, vm.address);
+    assert_eq!(events[0].topics, vec![signature, from_topic, to_topic]);
+    assert_eq!(events[0].data, ethabi::encode(&[Token::Uint(42.into())]));
+
+    assert!(events[1].topics.is_empty());
+    assert_eq!(
+        events[1].data,
+        ethabi::encode(&[Token::String("hello".into())])
+    );
+}
@@ -0,0 +1,6 @@
+// SPDX-License-Identifier: Apache-2.0
+
+mod builtins;
+mod calls;
+mod events;
+mod storage;
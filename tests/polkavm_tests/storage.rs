@@ -0,0 +1,140 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::build_solidity;
+use ethabi::{ParamType, Token};
+
+#[test]
+fn counter() {
+    let mut vm = build_solidity(
+        r#"
+        contract Counter {
+            uint64 public number = 7;
+
+            function increment() public {
+                number += 1;
+            }
+
+            function setNumber(uint64 n) public {
+                number = n;
+            }
+        }"#,
+    );
+
+    let ret = vm.function_returns("number()", &[], &[ParamType::Uint(64)]);
+    assert_eq!(ret, vec![Token::Uint(7.into())]);
+
+    vm.function("increment()", &[]);
+
+    let ret = vm.function_returns("number()", &[], &[ParamType::Uint(64)]);
+    assert_eq!(ret, vec![Token::Uint(8.into())]);
+
+    vm.function("setNumber(uint64)", &[Token::Uint(500.into())]);
+
+    let ret = vm.function_returns("number()", &[], &[ParamType::Uint(64)]);
+    assert_eq!(ret, vec![Token::Uint(500.into())]);
+
+    // unknown selector
+    assert!(vm.raw_call(vec![1, 2, 3, 4]).is_err());
+}
+
+#[test]
+fn strings_mappings_and_arrays() {
+    let mut vm = build_solidity(
+        r#"
+        contract Store {
+            string name;
+            mapping(uint256 => bytes) values;
+            int32[] list;
+
+            function set(string memory n, uint256 key, bytes memory value) public {
+                name = n;
+                values[key] = value;
+                list.push(int32(int256(key)));
+            }
+
+            function get(uint256 key) public view returns (string memory, bytes memory, uint256) {
+                return (name, values[key], list.length);
+            }
+
+            function pop() public returns (int32) {
+                return list.pop();
+            }
+
+            function clear(uint256 key) public {
+                delete name;
+                delete values[key];
+            }
+        }"#,
+    );
+
+    vm.function(
+        "set(string,uint256,bytes)",
+        &[
+            Token::String("polkavm".into()),
+            Token::Uint(3.into()),
+            Token::Bytes(vec![0xde, 0xad, 0xbe, 0xef]),
+        ],
+    );
+    vm.function(
+        "set(string,uint256,bytes)",
+        &[
+            Token::String("revive".into()),
+            Token::Uint(4.into()),
+            Token::Bytes(vec![1; 100]),
+        ],
+    );
+
+    let returns = [ParamType::String, ParamType::Bytes, ParamType::Uint(256)];
+
+    let ret = vm.function_returns("get(uint256)", &[Token::Uint(3.into())], &returns);
+    assert_eq!(
+        ret,
+        vec![
+            Token::String("revive".into()),
+            Token::Bytes(vec![0xde, 0xad, 0xbe, 0xef]),
+            Token::Uint(2.into()),
+        ]
+    );
+
+    let ret = vm.function_returns("pop()", &[], &[ParamType::Int(32)]);
+    assert_eq!(ret, vec![Token::Int(4.into())]);
+
+    vm.function("clear(uint256)", &[Token::Uint(3.into())]);
+
+    let ret = vm.function_returns("get(uint256)", &[Token::Uint(4.into())], &returns);
+    assert_eq!(
+        ret,
+        vec![
+            Token::String(String::new()),
+            Token::Bytes(vec![1; 100]),
+            Token::Uint(1.into()),
+        ]
+    );
+
+    let ret = vm.function_returns("get(uint256)", &[Token::Uint(3.into())], &returns);
+    assert_eq!(ret[1], Token::Bytes(Vec::new()));
+}
+
+#[test]
+fn transient_storage() {
+    let mut vm = build_solidity(
+        r#"
+        contract Lock {
+            bool transient locked;
+            uint256 public count;
+
+            function enter() public {
+                require(!locked, "locked");
+                locked = true;
+                count += 1;
+            }
+        }"#,
+    );
+
+    // transient storage is cleared after each transaction
+    vm.function("enter()", &[]);
+    vm.function("enter()", &[]);
+
+    let ret = vm.function_returns("count()", &[], &[ParamType::Uint(256)]);
+    assert_eq!(ret, vec![Token::Uint(2.into())]);
+}
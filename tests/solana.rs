@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::borsh_encoding::{decode_at_offset, encode_arguments, BorshToken};
-use anchor_syn::idl::types::{Idl, IdlAccountItem};
+use anchor_lang_idl::types::{Idl, IdlInstructionAccountItem};
 use base58::{FromBase58, ToBase58};
 use byteorder::{ByteOrder, LittleEndian, WriteBytesExt};
 use itertools::Itertools;
@@ -18,7 +18,6 @@ use solana_rbpf::{
     verifier::{RequisiteVerifier, TautologyVerifier},
     vm::{BuiltinProgram, Config, ContextObject, EbpfVm, ProgramResult, StableResult},
 };
-use solang::abi::anchor::function_discriminator;
 use solang::{
     abi::anchor::generate_anchor_idl,
     codegen::{OptimizationLevel, Options},
@@ -124,6 +123,12 @@ struct CreateAccountWithSeed {
     program_id: Account,
 }
 
+#[derive(Deserialize)]
+struct Transfer {
+    instruction: u32,
+    lamports: u64,
+}
+
 #[derive(Deserialize)]
 struct Allocate {
     instruction: u32,
@@ -381,7 +386,11 @@ fn deserialize_parameters(
 ) {
     for r in refs {
         if let Some(entry) = accounts_data.get_mut(&r.account) {
-            let data = input[r.data_offset..r.data_offset + r.length].to_vec();
+            // the program may have grown the account
+            let length =
+                u64::from_le_bytes(input[r.data_offset - 8..r.data_offset].try_into().unwrap())
+                    as usize;
+            let data = input[r.data_offset..r.data_offset + length].to_vec();
 
             entry.data = data;
             entry.lamports = u64::from_ne_bytes(
@@ -672,6 +681,56 @@ fn sol_sha256(
     *result = ProgramResult::Ok(0)
 }
 
+fn sol_get_rent_sysvar(
+    context: &mut SyscallContext,
+    dest: u64,
+    _arg2: u64,
+    _arg3: u64,
+    _arg4: u64,
+    _arg5: u64,
+    memory_mapping: &mut MemoryMapping,
+    result: &mut ProgramResult,
+) {
+    context.heap_verify();
+
+    // struct Rent { lamports_per_byte_year: u64, exemption_threshold: f64, burn_percent: u8 }
+    let rent = question_mark!(translate_slice_mut::<u8>(memory_mapping, dest, 24), result);
+
+    rent.fill(0);
+    LittleEndian::write_u64(&mut rent[0..], 3480);
+    // a fraction, so that the conversion to fixed point is tested
+    LittleEndian::write_f64(&mut rent[8..], 1.5);
+    rent[16] = 50;
+
+    *result = ProgramResult::Ok(0)
+}
+
+fn sol_get_epoch_schedule_sysvar(
+    context: &mut SyscallContext,
+    dest: u64,
+    _arg2: u64,
+    _arg3: u64,
+    _arg4: u64,
+    _arg5: u64,
+    memory_mapping: &mut MemoryMapping,
+    result: &mut ProgramResult,
+) {
+    context.heap_verify();
+
+    // struct EpochSchedule { slots_per_epoch: u64, leader_schedule_slot_offset: u64, warmup: bool,
+    //   first_normal_epoch: u64, first_normal_slot: u64 }
+    let schedule = question_mark!(translate_slice_mut::<u8>(memory_mapping, dest, 40), result);
+
+    schedule.fill(0);
+    LittleEndian::write_u64(&mut schedule[0..], 432000);
+    LittleEndian::write_u64(&mut schedule[8..], 432000);
+    schedule[16] = 1;
+    LittleEndian::write_u64(&mut schedule[24..], 14);
+    LittleEndian::write_u64(&mut schedule[32..], 524256);
+
+    *result = ProgramResult::Ok(0)
+}
+
 fn sol_keccak256(
     context: &mut SyscallContext,
     src: u64,
@@ -730,7 +789,7 @@ fn sol_create_program_address(
     let mut seeds = Vec::new();
 
     for (addr, len) in arrays {
-        assert!(*len < 32);
+        assert!(*len <= 32);
 
         let buf = question_mark!(translate_slice::<u8>(memory_mapping, *addr, *len), result);
 
@@ -777,7 +836,7 @@ fn sol_try_find_program_address(
     let mut seeds = Vec::new();
 
     for (addr, len) in arrays {
-        assert!(*len < 32);
+        assert!(*len <= 32);
 
         let buf = translate_slice::<u8>(memory_mapping, *addr, *len).unwrap();
 
@@ -1253,6 +1312,55 @@ fn sol_invoke_signed_c(
                         entry.owner = Some(assign.owner);
                     }
                 }
+                2 => {
+                    let transfer: Transfer = bincode::deserialize(&instruction.data).unwrap();
+
+                    assert_eq!(transfer.instruction, 2);
+
+                    let from = &instruction.accounts[0];
+                    let to = &instruction.accounts[1];
+
+                    assert!(from.is_signer && from.is_writable && to.is_writable);
+
+                    println!(
+                        "transfer {} lamports from {} to {}",
+                        transfer.lamports,
+                        from.pubkey.0.to_base58(),
+                        to.pubkey.0.to_base58()
+                    );
+
+                    let refs = context.refs.try_borrow().unwrap();
+
+                    let input = translate_slice_mut::<u8>(
+                        memory_mapping,
+                        ebpf::MM_INPUT_START,
+                        context.input_len as u64,
+                    )
+                    .unwrap();
+
+                    // the lamports are serialized before the length of the data
+                    for (account, credit) in [(from, false), (to, true)] {
+                        let offset = refs
+                            .iter()
+                            .find(|r| r.account == account.pubkey.0)
+                            .unwrap()
+                            .data_offset
+                            - 16;
+
+                        let lamports =
+                            u64::from_le_bytes(input[offset..offset + 8].try_into().unwrap());
+
+                        let lamports = if credit {
+                            lamports + transfer.lamports
+                        } else {
+                            lamports
+                                .checked_sub(transfer.lamports)
+                                .expect("insufficient lamports")
+                        };
+
+                        input[offset..offset + 8].copy_from_slice(&lamports.to_le_bytes());
+                    }
+                }
                 3 => {
                     let create_account: CreateAccountWithSeed =
                         bincode::deserialize(&instruction.data).unwrap();
@@ -1417,6 +1525,17 @@ impl VirtualMachine {
             .register_function(b"sol_get_return_data", sol_get_return_data)
             .unwrap();
 
+        loader
+            .register_function(b"sol_get_rent_sysvar", sol_get_rent_sysvar)
+            .unwrap();
+
+        loader
+            .register_function(
+                b"sol_get_epoch_schedule_sysvar",
+                sol_get_epoch_schedule_sysvar,
+            )
+            .unwrap();
+
         loader
             .register_function(b"sol_log_data", sol_log_data)
             .unwrap();
@@ -1654,7 +1773,7 @@ impl<'b> VmFunction<'_, 'b> {
 
         for account in &self.vm.stack[0].idl.as_ref().unwrap().instructions[self.idx].accounts {
             match account {
-                IdlAccountItem::IdlAccount(account) => {
+                IdlInstructionAccountItem::Single(account) => {
                     if account.name == "dataAccount" {
                         self.data_account = Some(metas.len());
                     }
@@ -1666,11 +1785,13 @@ impl<'b> VmFunction<'_, 'b> {
                                 .cloned()
                                 .unwrap_or_else(|| panic!("account '{}' is missing", account.name)),
                         ),
-                        is_writable: account.is_mut,
-                        is_signer: account.is_signer,
+                        is_writable: account.writable,
+                        is_signer: account.signer,
                     });
                 }
-                IdlAccountItem::IdlAccounts(_) => unimplemented!("Solang does not use IdlAccounts"),
+                IdlInstructionAccountItem::Composite(_) => {
+                    unimplemented!("Solang does not use composite accounts")
+                }
             }
         }
 
@@ -1710,7 +1831,7 @@ impl<'b> VmFunction<'_, 'b> {
     fn call_with_error_code(&mut self) -> Result<Option<BorshToken>, u64> {
         self.vm.return_data = None;
         let idl_instr = self.vm.stack[0].idl.as_ref().unwrap().instructions[self.idx].clone();
-        let mut calldata = function_discriminator(&idl_instr.name);
+        let mut calldata = idl_instr.discriminator.clone();
 
         if !self.has_remaining {
             assert_eq!(
@@ -1779,7 +1900,7 @@ impl<'b> VmFunction<'_, 'b> {
             );
         }
 
-        let mut calldata = function_discriminator(&idl_instr.name);
+        let mut calldata = idl_instr.discriminator.clone();
         if let Some(args) = self.arguments {
             let mut encoded_data = encode_arguments(args);
             calldata.append(&mut encoded_data);
@@ -2,7 +2,8 @@
 
 use crate::borsh_encoding::BorshToken;
 use crate::{account_new, build_solidity, create_program_address, AccountState};
-use anchor_syn::idl::types::{IdlAccount, IdlAccountItem, IdlInstruction};
+use anchor_lang_idl::types::{IdlInstruction, IdlInstructionAccount, IdlInstructionAccountItem};
+use solang::abi::anchor::function_discriminator;
 
 #[test]
 fn access_payer() {
@@ -91,14 +92,16 @@ contract hatchling {
     if let Some(idl) = &mut vm.stack[0].idl {
         idl.instructions.push(IdlInstruction {
             name: "wrong".to_string(),
-            docs: None,
-            accounts: vec![IdlAccountItem::IdlAccount(IdlAccount {
+            docs: vec![],
+            discriminator: function_discriminator("wrong"),
+            accounts: vec![IdlInstructionAccountItem::Single(IdlInstructionAccount {
                 name: "dataAccount".to_string(),
-                is_signer: false,
-                is_optional: None,
-                docs: None,
+                docs: vec![],
+                writable: true,
+                signer: false,
+                optional: false,
+                address: None,
                 pda: None,
-                is_mut: true,
                 relations: vec![],
             })],
             args: vec![],
@@ -0,0 +1,232 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::borsh_encoding::BorshToken;
+use crate::{account_new, build_solidity, create_program_address, AccountState, VirtualMachine};
+use num_bigint::BigInt;
+
+static CONSTRAINTS: &str = r#"
+    contract constraints {
+        address authority;
+
+        constructor(address a) {
+            authority = a;
+        }
+
+        @account(vault)
+        @owner(vault, address(this))
+        function owned() external view returns (uint64) {
+            return tx.accounts.vault.lamports;
+        }
+
+        @account(vault)
+        @seeds(vault, [bytes("vault"), name], bump)
+        function derived(bytes name, bytes1 bump) external view returns (uint64) {
+            return tx.accounts.vault.lamports;
+        }
+
+        @signer(authority)
+        @hasOne(authority)
+        function guarded() external view returns (address) {
+            return authority;
+        }
+
+        @mutableSigner(payer)
+        @mutableAccount(vault)
+        @seeds(vault, [bytes("vault")], bump)
+        @initIfNeeded(vault, payer, 64)
+        function open(bytes1 bump) external {
+            print("open");
+        }
+    }"#;
+
+fn insert_account(vm: &mut VirtualMachine, owner: Option<[u8; 32]>, lamports: u64) -> [u8; 32] {
+    let account = account_new();
+
+    vm.account_data.insert(
+        account,
+        AccountState {
+            data: vec![],
+            owner,
+            lamports,
+        },
+    );
+
+    account
+}
+
+fn lamports(value: u64) -> Option<BorshToken> {
+    Some(BorshToken::Uint {
+        width: 64,
+        value: BigInt::from(value),
+    })
+}
+
+#[test]
+fn owner() {
+    let mut vm = build_solidity(CONSTRAINTS);
+    let program_id = vm.stack[0].id;
+
+    let vault = insert_account(&mut vm, Some(program_id), 7);
+
+    let res = vm.function("owned").accounts(vec![("vault", vault)]).call();
+    assert_eq!(res, lamports(7));
+
+    let vault = insert_account(&mut vm, Some(account_new()), 7);
+
+    let res = vm
+        .function("owned")
+        .accounts(vec![("vault", vault)])
+        .must_fail();
+    assert_eq!(res.unwrap(), 2004);
+    assert!(vm.logs.contains(
+        "AnchorError caused by account: vault. Error Code: ConstraintOwner. Error Number: 2004. \
+        Error Message: An owner constraint was violated."
+    ));
+}
+
+#[test]
+fn seeds() {
+    let mut vm = build_solidity(CONSTRAINTS);
+    let program_id = vm.stack[0].id;
+
+    let pda = create_program_address(&program_id, &[b"vault", b"alice", &[254]]);
+
+    vm.account_data.insert(
+        pda.0,
+        AccountState {
+            data: vec![],
+            owner: None,
+            lamports: 3,
+        },
+    );
+
+    let args = [
+        BorshToken::Bytes(b"alice".to_vec()),
+        BorshToken::FixedBytes(vec![254]),
+    ];
+
+    let res = vm
+        .function("derived")
+        .arguments(&args)
+        .accounts(vec![("vault", pda.0)])
+        .call();
+    assert_eq!(res, lamports(3));
+
+    // the seeds of another account
+    let args = [
+        BorshToken::Bytes(b"bob".to_vec()),
+        BorshToken::FixedBytes(vec![254]),
+    ];
+
+    let res = vm
+        .function("derived")
+        .arguments(&args)
+        .accounts(vec![("vault", pda.0)])
+        .must_fail();
+    assert_eq!(res.unwrap(), 2006);
+    assert!(vm.logs.contains(
+        "AnchorError caused by account: vault. Error Code: ConstraintSeeds. Error Number: 2006. \
+        Error Message: A seeds constraint was violated."
+    ));
+}
+
+#[test]
+fn has_one() {
+    let mut vm = build_solidity(CONSTRAINTS);
+
+    let authority = insert_account(&mut vm, None, 0);
+    let data_account = vm.initialize_data_account();
+
+    vm.function("new")
+        .arguments(&[BorshToken::Address(authority)])
+        .accounts(vec![("dataAccount", data_account)])
+        .call();
+
+    let res = vm
+        .function("guarded")
+        .accounts(vec![
+            ("authority", authority),
+            ("dataAccount", data_account),
+        ])
+        .call();
+    assert_eq!(res, Some(BorshToken::Address(authority)));
+
+    // the key of the account does not match the authority stored in the data account
+    let other = insert_account(&mut vm, None, 0);
+
+    let res = vm
+        .function("guarded")
+        .accounts(vec![("authority", other), ("dataAccount", data_account)])
+        .must_fail();
+    assert_eq!(res.unwrap(), 2001);
+    assert!(vm.logs.contains(
+        "AnchorError caused by account: authority. Error Code: ConstraintHasOne. Error Number: 2001. \
+        Error Message: A has one constraint was violated."
+    ));
+}
+
+#[test]
+fn init_if_needed() {
+    let mut vm = build_solidity(CONSTRAINTS);
+    let program_id = vm.stack[0].id;
+
+    let payer = insert_account(&mut vm, None, 1_000_000_000);
+    let vault = create_program_address(&program_id, &[b"vault", &[255]]).0;
+
+    vm.account_data.insert(
+        vault,
+        AccountState {
+            data: vec![],
+            owner: Some([0; 32]),
+            lamports: 0,
+        },
+    );
+
+    let args = [BorshToken::FixedBytes(vec![255])];
+    let accounts = vec![
+        ("payer", payer),
+        ("vault", vault),
+        ("systemProgram", [0; 32]),
+    ];
+
+    // the account is created
+    vm.function("open")
+        .arguments(&args)
+        .accounts(accounts.clone())
+        .call();
+
+    assert_eq!(vm.account_data[&vault].data.len(), 64);
+    assert_eq!(vm.account_data[&vault].owner, Some(program_id));
+
+    // the account exists already, with the right owner and size
+    vm.account_data.get_mut(&vault).unwrap().lamports = 1_000_000;
+
+    vm.function("open")
+        .arguments(&args)
+        .accounts(accounts.clone())
+        .call();
+
+    // the account exists, but with a different size
+    vm.account_data.get_mut(&vault).unwrap().data = vec![0; 32];
+
+    let res = vm
+        .function("open")
+        .arguments(&args)
+        .accounts(accounts.clone())
+        .must_fail();
+    assert_eq!(res.unwrap(), 2019);
+    assert!(vm.logs.contains(
+        "AnchorError caused by account: vault. Error Code: ConstraintSpace. Error Number: 2019. \
+        Error Message: A space constraint was violated."
+    ));
+
+    // the account is owned by another program
+    vm.account_data.get_mut(&vault).unwrap().owner = Some(account_new());
+
+    let res = vm
+        .function("open")
+        .arguments(&args)
+        .accounts(accounts)
+        .must_fail();
+    assert_eq!(res.unwrap(), 3007);
+}
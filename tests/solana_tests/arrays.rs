@@ -2230,3 +2230,98 @@ contract MyTest {
         }
     );
 }
+
+#[test]
+fn array_slices() {
+    let mut vm = build_solidity(
+        r#"
+        contract slices {
+            function middle(bytes payload) public pure returns (bytes) {
+                return payload[1:3];
+            }
+
+            function args(uint32 x) public pure returns (bytes) {
+                // skip the 8 byte discriminator
+                return msg.data[8:];
+            }
+
+            function decode(bytes payload) public pure returns (uint32) {
+                return abi.decode(payload[1:], (uint32));
+            }
+
+            function sum(uint64[] values, uint32 start, uint32 end) public pure returns (uint64 total) {
+                uint64[] memory v = values[start:end];
+
+                for (uint32 i = 0; i < v.length; i++) {
+                    total += v[i];
+                }
+            }
+        }"#,
+    );
+
+    let data_account = vm.initialize_data_account();
+    vm.function("new")
+        .accounts(vec![("dataAccount", data_account)])
+        .call();
+
+    let returns = vm
+        .function("middle")
+        .arguments(&[BorshToken::Bytes(vec![1, 2, 3, 4, 5])])
+        .call()
+        .unwrap();
+    assert_eq!(returns, BorshToken::Bytes(vec![2, 3]));
+
+    let returns = vm
+        .function("args")
+        .arguments(&[BorshToken::Uint {
+            width: 32,
+            value: BigInt::from(0xdeadcafeu32),
+        }])
+        .call()
+        .unwrap();
+    assert_eq!(returns, BorshToken::Bytes(vec![0xfe, 0xca, 0xad, 0xde]));
+
+    let returns = vm
+        .function("decode")
+        .arguments(&[BorshToken::Bytes(vec![0xff, 0x78, 0x56, 0x34, 0x12])])
+        .call()
+        .unwrap();
+    assert_eq!(
+        returns,
+        BorshToken::Uint {
+            width: 32,
+            value: BigInt::from(0x1234_5678u32),
+        }
+    );
+
+    let values = BorshToken::Array(
+        (1u8..=5)
+            .map(|v| BorshToken::Uint {
+                width: 64,
+                value: BigInt::from(v),
+            })
+            .collect(),
+    );
+    let bound = |v: u32| BorshToken::Uint {
+        width: 32,
+        value: BigInt::from(v),
+    };
+
+    let returns = vm
+        .function("sum")
+        .arguments(&[values.clone(), bound(1), bound(4)])
+        .call()
+        .unwrap();
+    assert_eq!(
+        returns,
+        BorshToken::Uint {
+            width: 64,
+            value: BigInt::from(9u8),
+        }
+    );
+
+    vm.function("sum")
+        .arguments(&[values, bound(4), bound(1)])
+        .must_fail();
+    assert!(vm.logs.contains("runtime_error: array slice out of bounds"));
+}
@@ -1,7 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::build_solidity;
-use anchor_syn::idl::types::IdlInstruction;
+use anchor_lang_idl::types::IdlInstruction;
+use solang::abi::anchor::function_discriminator;
 
 #[test]
 fn fallback() {
@@ -24,7 +25,8 @@ fn fallback() {
 
         idl.instructions.push(IdlInstruction {
             name: "extinct".to_string(),
-            docs: None,
+            docs: vec![],
+            discriminator: function_discriminator("extinct"),
             accounts: vec![],
             args: vec![],
             returns: None,
@@ -0,0 +1,131 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{borsh_encoding::BorshToken, build_solidity};
+use num_bigint::BigInt;
+
+#[test]
+fn custom_errors() {
+    let mut vm = build_solidity(
+        r#"
+        /// @notice Caller is not allowed
+        error Unauthorized();
+
+        contract vault {
+            error InsufficientBalance(uint64 available, uint64 required);
+
+            uint64 balance = 10;
+
+            function withdraw(uint64 amount) public {
+                if (amount > balance) {
+                    revert InsufficientBalance(balance, amount);
+                }
+                balance -= amount;
+            }
+
+            function guard(bool ok) public pure {
+                if (!ok) {
+                    revert Unauthorized();
+                }
+            }
+        }"#,
+    );
+
+    let data_account = vm.initialize_data_account();
+    vm.function("new")
+        .accounts(vec![("dataAccount", data_account)])
+        .call();
+
+    vm.function("withdraw")
+        .arguments(&[BorshToken::Uint {
+            width: 64,
+            value: BigInt::from(4u8),
+        }])
+        .accounts(vec![("dataAccount", data_account)])
+        .call();
+
+    let res = vm
+        .function("withdraw")
+        .arguments(&[BorshToken::Uint {
+            width: 64,
+            value: BigInt::from(7u8),
+        }])
+        .accounts(vec![("dataAccount", data_account)])
+        .must_fail();
+    assert_eq!(res.unwrap(), 6001);
+    assert!(vm.logs.contains(
+        "AnchorError occurred. Error Code: InsufficientBalance. Error Number: 6001. \
+        Error Message: InsufficientBalance."
+    ));
+
+    vm.logs.clear();
+
+    vm.function("guard")
+        .arguments(&[BorshToken::Bool(true)])
+        .call();
+
+    let res = vm
+        .function("guard")
+        .arguments(&[BorshToken::Bool(false)])
+        .must_fail();
+    assert_eq!(res.unwrap(), 6000);
+    assert!(vm.logs.contains(
+        "AnchorError occurred. Error Code: Unauthorized. Error Number: 6000. \
+        Error Message: Caller is not allowed."
+    ));
+}
+
+#[test]
+fn error_codes_per_contract() {
+    let mut vm = build_solidity(
+        r#"
+        contract other {
+            error Unrelated();
+        }
+
+        contract base {
+            error Paused();
+        }
+
+        contract vault is base {
+            error InsufficientBalance(uint64 required);
+
+            function pause() public pure {
+                revert Paused();
+            }
+
+            function withdraw(uint64 amount) public pure {
+                revert InsufficientBalance(amount);
+            }
+        }"#,
+    );
+
+    let data_account = vm.initialize_data_account();
+    vm.function("new")
+        .accounts(vec![("dataAccount", data_account)])
+        .call();
+
+    // the errors of other contracts do not take up codes
+    let res = vm.function("pause").must_fail();
+    assert_eq!(res.unwrap(), 6000);
+
+    let res = vm
+        .function("withdraw")
+        .arguments(&[BorshToken::Uint {
+            width: 64,
+            value: BigInt::from(7u8),
+        }])
+        .must_fail();
+    assert_eq!(res.unwrap(), 6001);
+
+    // the IDL lists the same codes
+    let errors = vm.stack[0]
+        .idl
+        .as_ref()
+        .unwrap()
+        .errors
+        .iter()
+        .map(|err| (err.name.as_str(), err.code))
+        .collect::<Vec<_>>();
+
+    assert_eq!(errors, [("Paused", 6000), ("InsufficientBalance", 6001)]);
+}
@@ -5,6 +5,7 @@ mod abi_decode;
 mod abi_encode;
 mod accessor;
 mod account_access;
+mod account_constraints;
 mod account_info;
 mod account_serialization;
 mod arrays;
@@ -15,6 +16,7 @@ mod call;
 mod constant;
 mod create_contract;
 mod destructure;
+mod errors;
 mod events;
 mod expressions;
 mod hash;
@@ -32,7 +34,9 @@ mod simple;
 mod storage;
 mod strings;
 mod structs;
+mod sysvars;
 mod tags;
+mod token_programs;
 mod unused_variable_elimination;
 mod using;
 mod vector_to_slice;
@@ -4,7 +4,7 @@ use crate::{
     borsh_encoding::{visit_mut, VisitorMut},
     AccountMeta, BorshToken, Pubkey, VirtualMachineBuilder,
 };
-use anchor_syn::idl::types::IdlAccountItem;
+use anchor_lang_idl::types::IdlInstructionAccountItem;
 use once_cell::sync::Lazy;
 use rayon::iter::ParallelIterator;
 use rayon::prelude::IntoParallelIterator;
@@ -107,8 +107,8 @@ fn run_test_with_opts<T: IntoIterator<Item = Options>>(program: &str, calls: &Ca
                 .accounts
                 .iter()
                 .any(|acc| match acc {
-                    IdlAccountItem::IdlAccount(account) => account.name == "dataAccount",
-                    IdlAccountItem::IdlAccounts(_) => false,
+                    IdlInstructionAccountItem::Single(account) => account.name == "dataAccount",
+                    IdlInstructionAccountItem::Composite(_) => false,
                 });
 
             results_curr.push(if needs_account {
@@ -1722,3 +1722,106 @@ contract hatchling {
 
     assert_eq!(res, BorshToken::Address(program_id));
 }
+
+#[test]
+fn fixed_point_borsh() {
+    let mut vm = build_solidity(
+        r#"
+        contract fixed_point {
+            fixed128x18 price;
+
+            struct Quote {
+                ufixed64x2 amount;
+                fixed256x18 rate;
+            }
+
+            function set(fixed128x18 p) public {
+                price = p;
+            }
+
+            function get() public view returns (fixed128x18) {
+                return price;
+            }
+
+            function double(Quote q) public pure returns (Quote) {
+                q.amount *= 2;
+                q.rate *= 2;
+                return q;
+            }
+
+            function mul(ufixed256x18 a, ufixed256x18 b) public pure returns (ufixed256x18) {
+                return a * b;
+            }
+        }"#,
+    );
+
+    let data_account = vm.initialize_data_account();
+    vm.function("new")
+        .accounts(vec![("dataAccount", data_account)])
+        .call();
+
+    // -1.5
+    let price = BorshToken::Int {
+        width: 128,
+        value: BigInt::from(-1_500_000_000_000_000_000i64),
+    };
+
+    vm.function("set")
+        .arguments(&[price.clone()])
+        .accounts(vec![("dataAccount", data_account)])
+        .call();
+
+    let returns = vm
+        .function("get")
+        .accounts(vec![("dataAccount", data_account)])
+        .call()
+        .unwrap();
+    assert_eq!(returns, price);
+
+    // 12.34 and -0.5
+    let returns = vm
+        .function("double")
+        .arguments(&[BorshToken::Tuple(vec![
+            BorshToken::Uint {
+                width: 64,
+                value: BigInt::from(1234u16),
+            },
+            BorshToken::Int {
+                width: 256,
+                value: BigInt::from(-500_000_000_000_000_000i64),
+            },
+        ])])
+        .call()
+        .unwrap();
+    assert_eq!(
+        returns,
+        BorshToken::Tuple(vec![
+            BorshToken::Uint {
+                width: 64,
+                value: BigInt::from(2468u16),
+            },
+            BorshToken::Int {
+                width: 256,
+                value: BigInt::from(-1_000_000_000_000_000_000i64),
+            },
+        ])
+    );
+
+    // 1e29 * 1e29 = 1e58, which needs a 512-bit intermediate
+    let value = BorshToken::Uint {
+        width: 256,
+        value: BigInt::from(10).pow(47u32),
+    };
+    let returns = vm
+        .function("mul")
+        .arguments(&[value.clone(), value])
+        .call()
+        .unwrap();
+    assert_eq!(
+        returns,
+        BorshToken::Uint {
+            width: 256,
+            value: BigInt::from(10).pow(76u32),
+        }
+    );
+}
@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{build_solidity, BorshToken};
+use crate::{account_new, build_solidity, AccountState, BorshToken};
 use num_bigint::BigInt;
 use num_traits::{One, Zero};
 
@@ -1050,3 +1050,225 @@ fn complex_struct() {
 }
 
 // dereference struct storage member (read/write)
+
+#[test]
+fn grow_data_account() {
+    let mut vm = build_solidity(
+        r#"
+        contract journal {
+            string[] entries;
+
+            @payer(payer)
+            function add(string entry) external {
+                entries.push(entry);
+            }
+
+            function get(uint32 index) external view returns (string) {
+                return entries[index];
+            }
+        }"#,
+    );
+
+    let data_account = vm.initialize_data_account();
+    vm.account_data.get_mut(&data_account).unwrap().data = vec![0; 1024];
+
+    vm.function("new")
+        .accounts(vec![("dataAccount", data_account)])
+        .call();
+
+    let payer = account_new();
+    vm.account_data.insert(
+        payer,
+        AccountState {
+            data: vec![],
+            owner: None,
+            lamports: 1_000_000_000,
+        },
+    );
+
+    let entry = "x".repeat(200);
+
+    for _ in 0..10 {
+        vm.function("add")
+            .arguments(&[BorshToken::String(entry.clone())])
+            .accounts(vec![
+                ("dataAccount", data_account),
+                ("payer", payer),
+                ("systemProgram", [0; 32]),
+            ])
+            .call();
+    }
+
+    let len = vm.account_data[&data_account].data.len() as u64;
+    assert!(len > 1024);
+    assert_eq!(len % 1024, 0);
+
+    // the payer keeps the data account rent exempt
+    let rent = (128 + len) * 3480 * 2;
+    assert_eq!(vm.account_data[&data_account].lamports, rent);
+    assert_eq!(vm.account_data[&payer].lamports, 1_000_000_000 - rent);
+
+    let returns = vm
+        .function("get")
+        .arguments(&[BorshToken::Uint {
+            width: 32,
+            value: BigInt::from(9u8),
+        }])
+        .accounts(vec![("dataAccount", data_account)])
+        .call()
+        .unwrap();
+
+    assert_eq!(returns, BorshToken::String(entry));
+
+    // an account may not grow by more than 10 KiB during one instruction
+    let res = vm
+        .function("add")
+        .arguments(&[BorshToken::String("y".repeat(11 * 1024))])
+        .accounts(vec![
+            ("dataAccount", data_account),
+            ("payer", payer),
+            ("systemProgram", [0; 32]),
+        ])
+        .must_fail();
+
+    assert_eq!(res.unwrap(), 5u64 << 32);
+}
+
+#[test]
+fn transient_storage() {
+    let mut vm = build_solidity(
+        r#"
+        contract c {
+            uint64 transient depth;
+            uint64 count;
+
+            function bump() public returns (uint64) {
+                depth += 1;
+                depth += 1;
+                return depth;
+            }
+
+            function inc() public returns (uint64) {
+                count += 1;
+                depth = count + 10;
+                return depth;
+            }
+
+            function get() public view returns (uint64) {
+                return count;
+            }
+        }"#,
+    );
+
+    let data_account = vm.initialize_data_account();
+    vm.function("new")
+        .accounts(vec![("dataAccount", data_account)])
+        .call();
+
+    // transient variables do not need the data account, and start from zero in every
+    // instruction
+    for _ in 0..2 {
+        let returns = vm.function("bump").call().unwrap();
+        assert_eq!(
+            returns,
+            BorshToken::Uint {
+                width: 64,
+                value: BigInt::from(2u8),
+            }
+        );
+    }
+
+    let returns = vm
+        .function("inc")
+        .accounts(vec![("dataAccount", data_account)])
+        .call()
+        .unwrap();
+    assert_eq!(
+        returns,
+        BorshToken::Uint {
+            width: 64,
+            value: BigInt::from(11u8),
+        }
+    );
+
+    let returns = vm
+        .function("get")
+        .accounts(vec![("dataAccount", data_account)])
+        .call()
+        .unwrap();
+    assert_eq!(
+        returns,
+        BorshToken::Uint {
+            width: 64,
+            value: BigInt::one(),
+        }
+    );
+}
+
+#[test]
+fn grow_data_account_limit() {
+    let mut vm = build_solidity(
+        r#"
+        contract store {
+            bytes data;
+
+            @payer(payer)
+            function set(bytes d) external {
+                data = d;
+            }
+        }"#,
+    );
+
+    let payer = account_new();
+    vm.account_data.insert(
+        payer,
+        AccountState {
+            data: vec![],
+            owner: None,
+            lamports: 1_000_000_000,
+        },
+    );
+
+    // During one instruction, the data account can grow to its length at the start of the
+    // instruction plus 10 KiB. The length is not a multiple of 1 KiB, so growing by whole KiB
+    // would pass the limit.
+    let accounts = |data_account| {
+        vec![
+            ("dataAccount", data_account),
+            ("payer", payer),
+            ("systemProgram", [0; 32]),
+        ]
+    };
+
+    let data_account = vm.initialize_data_account();
+    vm.account_data.get_mut(&data_account).unwrap().data = vec![0; 1000];
+
+    vm.function("new")
+        .accounts(vec![("dataAccount", data_account)])
+        .call();
+
+    // the account grows up to the limit, but no further
+    vm.function("set")
+        .arguments(&[BorshToken::Bytes(vec![1; 11040])])
+        .accounts(accounts(data_account))
+        .call();
+
+    assert_eq!(vm.account_data[&data_account].data.len(), 1000 + 10 * 1024);
+
+    let data_account = vm.initialize_data_account();
+    vm.account_data.get_mut(&data_account).unwrap().data = vec![0; 1000];
+
+    vm.function("new")
+        .accounts(vec![("dataAccount", data_account)])
+        .call();
+
+    // the data does not fit within the limit
+    let res = vm
+        .function("set")
+        .arguments(&[BorshToken::Bytes(vec![1; 1000 + 10 * 1024])])
+        .accounts(accounts(data_account))
+        .must_fail();
+
+    assert_eq!(res.unwrap(), 5u64 << 32);
+    assert_eq!(vm.account_data[&data_account].data.len(), 1000);
+}
@@ -0,0 +1,276 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{account_new, build_solidity, Account, AccountState, BorshToken};
+use base58::FromBase58;
+use num_bigint::BigInt;
+use std::convert::TryInto;
+
+#[test]
+fn rent_and_epoch_schedule() {
+    let mut vm = build_solidity(
+        r#"
+        import "solana";
+
+        contract foo {
+            function rent() public view returns (uint64, uint64, uint8) {
+                Rent memory r = sysvar.rent();
+                return (r.lamports_per_byte_year, r.exemption_threshold, r.burn_percent);
+            }
+
+            function schedule() public view returns (uint64, bool, uint64) {
+                EpochSchedule memory s = sysvar.epochSchedule();
+                return (s.slots_per_epoch, s.warmup, s.first_normal_slot);
+            }
+        }"#,
+    );
+
+    let data_account = vm.initialize_data_account();
+    vm.function("new")
+        .accounts(vec![("dataAccount", data_account)])
+        .call();
+
+    let returns = vm.function("rent").call().unwrap().unwrap_tuple();
+
+    assert_eq!(
+        returns,
+        vec![
+            BorshToken::Uint {
+                width: 64,
+                value: BigInt::from(3480u16),
+            },
+            BorshToken::Uint {
+                width: 64,
+                value: BigInt::from(3u64 << 31),
+            },
+            BorshToken::Uint {
+                width: 8,
+                value: BigInt::from(50u8),
+            },
+        ]
+    );
+
+    let returns = vm.function("schedule").call().unwrap().unwrap_tuple();
+
+    assert_eq!(
+        returns,
+        vec![
+            BorshToken::Uint {
+                width: 64,
+                value: BigInt::from(432000u32),
+            },
+            BorshToken::Bool(true),
+            BorshToken::Uint {
+                width: 64,
+                value: BigInt::from(524256u32),
+            },
+        ]
+    );
+}
+
+#[test]
+fn instruction_introspection() {
+    let mut vm = build_solidity(
+        r#"
+        import "solana";
+
+        contract foo {
+            function current() public view returns (uint16) {
+                return tx.currentIndex();
+            }
+
+            function program(uint16 index) public view returns (address, bytes) {
+                Instruction memory instr = tx.instructionAt(index);
+                return (instr.program_id, instr.data);
+            }
+
+            function account(uint16 index, uint32 no) public view returns (address, bool, bool) {
+                AccountMeta[] memory metas = tx.instructionAt(index).accounts;
+                return (metas[no].pubkey, metas[no].is_writable, metas[no].is_signer);
+            }
+
+            function accounts(uint16 index) public view returns (uint32) {
+                return tx.instructionAt(index).accounts.length;
+            }
+        }"#,
+    );
+
+    let data_account = vm.initialize_data_account();
+    vm.function("new")
+        .accounts(vec![("dataAccount", data_account)])
+        .call();
+
+    let instructions_account: Account = "Sysvar1nstructions1111111111111111111111111"
+        .from_base58()
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+    let program_id = account_new();
+    let signer = account_new();
+    let other = account_new();
+
+    vm.account_data.insert(
+        instructions_account,
+        AccountState {
+            data: encode_instructions(
+                &[
+                    (
+                        program_id,
+                        vec![(signer, true, true), (other, false, true)],
+                        vec![1, 2, 3],
+                    ),
+                    (vm.stack[0].id, vec![], vec![]),
+                ],
+                1,
+            ),
+            owner: None,
+            lamports: 0,
+        },
+    );
+
+    let returns = vm
+        .function("current")
+        .accounts(vec![("SysvarInstruction", instructions_account)])
+        .call()
+        .unwrap();
+
+    assert_eq!(
+        returns,
+        BorshToken::Uint {
+            width: 16,
+            value: BigInt::from(1u8),
+        }
+    );
+
+    let index = |i: u8| BorshToken::Uint {
+        width: 16,
+        value: BigInt::from(i),
+    };
+
+    let returns = vm
+        .function("program")
+        .arguments(&[index(0)])
+        .accounts(vec![("SysvarInstruction", instructions_account)])
+        .call()
+        .unwrap()
+        .unwrap_tuple();
+
+    assert_eq!(
+        returns,
+        vec![
+            BorshToken::Address(program_id),
+            BorshToken::Bytes(vec![1, 2, 3]),
+        ]
+    );
+
+    let returns = vm
+        .function("accounts")
+        .arguments(&[index(0)])
+        .accounts(vec![("SysvarInstruction", instructions_account)])
+        .call()
+        .unwrap();
+
+    assert_eq!(
+        returns,
+        BorshToken::Uint {
+            width: 32,
+            value: BigInt::from(2u8),
+        }
+    );
+
+    let returns = vm
+        .function("account")
+        .arguments(&[
+            index(0),
+            BorshToken::Uint {
+                width: 32,
+                value: BigInt::from(1u8),
+            },
+        ])
+        .accounts(vec![("SysvarInstruction", instructions_account)])
+        .call()
+        .unwrap()
+        .unwrap_tuple();
+
+    assert_eq!(
+        returns,
+        vec![
+            BorshToken::Address(other),
+            BorshToken::Bool(true),
+            BorshToken::Bool(false),
+        ]
+    );
+
+    let returns = vm
+        .function("program")
+        .arguments(&[index(1)])
+        .accounts(vec![("SysvarInstruction", instructions_account)])
+        .call()
+        .unwrap()
+        .unwrap_tuple();
+
+    assert_eq!(
+        returns,
+        vec![
+            BorshToken::Address(vm.stack[0].id),
+            BorshToken::Bytes(vec![])
+        ]
+    );
+
+    vm.function("program")
+        .arguments(&[index(2)])
+        .accounts(vec![("SysvarInstruction", instructions_account)])
+        .must_fail();
+
+    assert!(vm.logs.contains("instruction index out of bounds"));
+
+    // the offset of the first instruction points past the end of the data
+    vm.account_data.get_mut(&instructions_account).unwrap().data = vec![1, 0, 0xff, 0, 0, 0];
+
+    vm.function("program")
+        .arguments(&[index(0)])
+        .accounts(vec![("SysvarInstruction", instructions_account)])
+        .must_fail();
+
+    assert!(vm.logs.contains("instructions sysvar data too short"));
+}
+
+type InstructionAccount = (Account, bool, bool);
+
+/// Serialize instructions the way the instructions sysvar does
+fn encode_instructions(
+    instructions: &[(Account, Vec<InstructionAccount>, Vec<u8>)],
+    current: u16,
+) -> Vec<u8> {
+    let mut data = Vec::new();
+
+    data.extend_from_slice(&(instructions.len() as u16).to_le_bytes());
+
+    let mut offset = 2 + 2 * instructions.len();
+    let mut encoded = Vec::new();
+
+    for (program_id, accounts, instr_data) in instructions {
+        data.extend_from_slice(&(offset as u16).to_le_bytes());
+
+        let mut instr = Vec::new();
+
+        instr.extend_from_slice(&(accounts.len() as u16).to_le_bytes());
+
+        for (key, is_signer, is_writable) in accounts {
+            instr.push(u8::from(*is_signer) | (u8::from(*is_writable) << 1));
+            instr.extend_from_slice(key);
+        }
+
+        instr.extend_from_slice(program_id);
+        instr.extend_from_slice(&(instr_data.len() as u16).to_le_bytes());
+        instr.extend_from_slice(instr_data);
+
+        offset += instr.len();
+        encoded.extend(instr);
+    }
+
+    data.extend(encoded);
+    data.extend_from_slice(&current.to_le_bytes());
+
+    data
+}
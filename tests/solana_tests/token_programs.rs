@@ -0,0 +1,489 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account_new, build_solidity_with_cache, create_program_address, AccountState, BorshToken,
+    Instruction, Pubkey, VirtualMachine,
+};
+use base58::FromBase58;
+use num_bigint::BigInt;
+use solang::file_resolver::FileResolver;
+
+const PAYER: [u8; 32] = [1; 32];
+const WALLET: [u8; 32] = [2; 32];
+const MINT: [u8; 32] = [3; 32];
+const FROM: [u8; 32] = [4; 32];
+const TO: [u8; 32] = [5; 32];
+
+fn program_id(id: &str) -> Pubkey {
+    Pubkey(id.from_base58().unwrap().try_into().unwrap())
+}
+
+fn token_2022_id() -> Pubkey {
+    program_id("TokenzQdBNbLqP5VEhdkS6EPFLC1PHnBrDyrdbdpY2w")
+}
+
+fn build_with_libraries(src: &str) -> VirtualMachine {
+    let mut cache = FileResolver::default();
+    cache.set_file_contents(
+        "associated_token.sol",
+        include_str!("../../solana-library/associated_token.sol").to_string(),
+    );
+    cache.set_file_contents(
+        "spl_token_2022.sol",
+        include_str!("../../solana-library/spl_token_2022.sol").to_string(),
+    );
+    cache.set_file_contents("test.sol", src.to_string());
+
+    let mut vm = build_solidity_with_cache(cache);
+
+    for account in [PAYER, WALLET, MINT, FROM, TO] {
+        vm.account_data.insert(account, AccountState::default());
+    }
+
+    let data_account = vm.initialize_data_account();
+    vm.function("new")
+        .accounts(vec![("dataAccount", data_account)])
+        .call();
+
+    vm
+}
+
+#[test]
+fn associated_token_account() {
+    let mut vm = build_with_libraries(
+        r#"
+        import './associated_token.sol';
+        import './spl_token_2022.sol';
+
+        contract Foo {
+            function address_of(address wallet, address mint) external pure returns (address, bytes1) {
+                return AssociatedToken.get_associated_token_address(wallet, mint, SplToken2022.tokenProgramId);
+            }
+
+            @mutableSigner(payer)
+            @mutableAccount(ata)
+            @account(wallet)
+            @account(mint)
+            function create(bool idempotent) external {
+                if (idempotent) {
+                    AssociatedToken.create_idempotent(tx.accounts.payer.key, tx.accounts.ata.key,
+                        tx.accounts.wallet.key, tx.accounts.mint.key, SplToken2022.tokenProgramId);
+                } else {
+                    AssociatedToken.create(tx.accounts.payer.key, tx.accounts.ata.key,
+                        tx.accounts.wallet.key, tx.accounts.mint.key, SplToken2022.tokenProgramId);
+                }
+            }
+        }"#,
+    );
+
+    let ata_program = program_id("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+    let returns = vm
+        .function("address_of")
+        .arguments(&[BorshToken::Address(WALLET), BorshToken::Address(MINT)])
+        .call()
+        .unwrap()
+        .unwrap_tuple();
+
+    let ata = create_program_address(
+        &ata_program.0,
+        &[&WALLET, &token_2022_id().0, &MINT, &[255]],
+    );
+
+    assert_eq!(
+        returns,
+        vec![
+            BorshToken::Address(ata.0),
+            BorshToken::uint8_fixed_array(vec![255])
+        ]
+    );
+
+    vm.account_data.insert(ata.0, AccountState::default());
+    vm.account_data
+        .insert(ata_program.0, AccountState::default());
+
+    fn check_accounts(instr: &Instruction) {
+        let ata = create_program_address(
+            &program_id("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL").0,
+            &[&WALLET, &token_2022_id().0, &MINT, &[255]],
+        );
+
+        let expected = [
+            (Pubkey(PAYER), true, true),
+            (ata, true, false),
+            (Pubkey(WALLET), false, false),
+            (Pubkey(MINT), false, false),
+            (Pubkey([0; 32]), false, false),
+            (token_2022_id(), false, false),
+        ];
+
+        assert_eq!(instr.accounts.len(), expected.len());
+
+        for (meta, (pubkey, is_writable, is_signer)) in instr.accounts.iter().zip(expected) {
+            assert_eq!(meta.pubkey, pubkey);
+            assert_eq!(meta.is_writable, is_writable);
+            assert_eq!(meta.is_signer, is_signer);
+        }
+    }
+
+    vm.call_params_check.insert(
+        ata_program.clone(),
+        |_: &VirtualMachine, instr: &Instruction, _: &[Pubkey]| {
+            assert_eq!(instr.data, vec![0]);
+            check_accounts(instr);
+        },
+    );
+
+    let accounts = vec![
+        ("payer", PAYER),
+        ("ata", ata.0),
+        ("wallet", WALLET),
+        ("mint", MINT),
+        ("associatedTokenProgram", ata_program.0),
+        ("systemProgram", [0; 32]),
+    ];
+
+    vm.function("create")
+        .arguments(&[BorshToken::Bool(false)])
+        .accounts(accounts.clone())
+        .call();
+
+    vm.call_params_check.insert(
+        ata_program,
+        |_: &VirtualMachine, instr: &Instruction, _: &[Pubkey]| {
+            assert_eq!(instr.data, vec![1]);
+            check_accounts(instr);
+        },
+    );
+
+    vm.function("create")
+        .arguments(&[BorshToken::Bool(true)])
+        .accounts(accounts)
+        .call();
+}
+
+#[test]
+fn token_2022_transfer() {
+    let mut vm = build_with_libraries(
+        r#"
+        import './spl_token_2022.sol';
+
+        contract Foo {
+            @mutableAccount(from)
+            @account(mint)
+            @mutableAccount(to)
+            @signer(owner)
+            function transfer(uint64 amount, uint8 decimals) external {
+                SplToken2022.transfer_checked(tx.accounts.from.key, tx.accounts.mint.key,
+                    tx.accounts.to.key, tx.accounts.owner.key, amount, decimals);
+            }
+
+            @mutableAccount(from)
+            @account(mint)
+            @mutableAccount(to)
+            @signer(owner)
+            function transfer_with_fee(uint64 amount, uint8 decimals, uint64 fee) external {
+                SplToken2022.transfer_checked_with_fee(tx.accounts.from.key, tx.accounts.mint.key,
+                    tx.accounts.to.key, tx.accounts.owner.key, amount, decimals, fee);
+            }
+        }"#,
+    );
+
+    fn check_accounts(instr: &Instruction) {
+        let expected = [
+            (Pubkey(FROM), true, false),
+            (Pubkey(MINT), false, false),
+            (Pubkey(TO), true, false),
+            (Pubkey(WALLET), false, true),
+        ];
+
+        assert_eq!(instr.accounts.len(), expected.len());
+
+        for (meta, (pubkey, is_writable, is_signer)) in instr.accounts.iter().zip(expected) {
+            assert_eq!(meta.pubkey, pubkey);
+            assert_eq!(meta.is_writable, is_writable);
+            assert_eq!(meta.is_signer, is_signer);
+        }
+    }
+
+    vm.account_data
+        .insert(token_2022_id().0, AccountState::default());
+
+    vm.call_params_check.insert(
+        token_2022_id(),
+        |_: &VirtualMachine, instr: &Instruction, _: &[Pubkey]| {
+            let mut data = vec![12];
+            data.extend_from_slice(&1_000_000u64.to_le_bytes());
+            data.push(6);
+
+            assert_eq!(instr.data, data);
+            check_accounts(instr);
+        },
+    );
+
+    let accounts = vec![
+        ("from", FROM),
+        ("mint", MINT),
+        ("to", TO),
+        ("owner", WALLET),
+    ];
+
+    vm.function("transfer")
+        .arguments(&[
+            BorshToken::Uint {
+                width: 64,
+                value: BigInt::from(1_000_000u64),
+            },
+            BorshToken::Uint {
+                width: 8,
+                value: BigInt::from(6u8),
+            },
+        ])
+        .accounts(accounts.clone())
+        .call();
+
+    vm.call_params_check.insert(
+        token_2022_id(),
+        |_: &VirtualMachine, instr: &Instruction, _: &[Pubkey]| {
+            let mut data = vec![26, 1];
+            data.extend_from_slice(&1_000_000u64.to_le_bytes());
+            data.push(6);
+            data.extend_from_slice(&500u64.to_le_bytes());
+
+            assert_eq!(instr.data, data);
+            check_accounts(instr);
+        },
+    );
+
+    vm.function("transfer_with_fee")
+        .arguments(&[
+            BorshToken::Uint {
+                width: 64,
+                value: BigInt::from(1_000_000u64),
+            },
+            BorshToken::Uint {
+                width: 8,
+                value: BigInt::from(6u8),
+            },
+            BorshToken::Uint {
+                width: 64,
+                value: BigInt::from(500u64),
+            },
+        ])
+        .accounts(accounts)
+        .call();
+}
+
+#[test]
+fn token_2022_extensions() {
+    let mut vm = build_with_libraries(
+        r#"
+        import './spl_token_2022.sol';
+
+        contract Foo {
+            @account(mint)
+            function extensions() external view returns (bool, bool, bool, bool) {
+                AccountInfo mint = tx.accounts.mint;
+
+                return (
+                    SplToken2022.has_extension(mint, SplToken2022.ExtensionType.TransferFeeConfig),
+                    SplToken2022.has_extension(mint, SplToken2022.ExtensionType.MintCloseAuthority),
+                    SplToken2022.has_extension(mint, SplToken2022.ExtensionType.MetadataPointer),
+                    SplToken2022.has_extension(mint, SplToken2022.ExtensionType.TokenMetadata)
+                );
+            }
+
+            @account(mint)
+            function transfer_fee_config() external view returns (SplToken2022.TransferFeeConfig) {
+                return SplToken2022.get_transfer_fee_config(tx.accounts.mint);
+            }
+
+            @account(mint)
+            function fee(uint64 epoch, uint64 amount) external view returns (uint64) {
+                SplToken2022.TransferFeeConfig config = SplToken2022.get_transfer_fee_config(tx.accounts.mint);
+
+                return SplToken2022.calculate_fee(config, epoch, amount);
+            }
+
+            @account(mint)
+            function metadata_pointer() external view returns (SplToken2022.MetadataPointer) {
+                return SplToken2022.get_metadata_pointer(tx.accounts.mint);
+            }
+
+            @account(mint)
+            function close_authority() external view returns (address) {
+                return SplToken2022.get_mint_close_authority(tx.accounts.mint);
+            }
+        }"#,
+    );
+
+    let fee_authority = account_new();
+    let withdraw_authority = account_new();
+    let close_authority = account_new();
+    let metadata = account_new();
+
+    // base mint, padded to the length of a token account and followed by the account type
+    let mut data = vec![0u8; 165];
+    data[44] = 6;
+    data[45] = 1;
+    data.push(1);
+
+    let mut transfer_fee_config = Vec::new();
+    transfer_fee_config.extend_from_slice(&fee_authority);
+    transfer_fee_config.extend_from_slice(&withdraw_authority);
+    transfer_fee_config.extend_from_slice(&1234u64.to_le_bytes());
+    transfer_fee_config.extend_from_slice(&0u64.to_le_bytes());
+    transfer_fee_config.extend_from_slice(&100u64.to_le_bytes());
+    transfer_fee_config.extend_from_slice(&50u16.to_le_bytes());
+    transfer_fee_config.extend_from_slice(&10u64.to_le_bytes());
+    transfer_fee_config.extend_from_slice(&5000u64.to_le_bytes());
+    transfer_fee_config.extend_from_slice(&250u16.to_le_bytes());
+
+    let mut metadata_pointer = [0u8; 64];
+    metadata_pointer[32..].copy_from_slice(&metadata);
+
+    for (ty, value) in [
+        (1u16, transfer_fee_config.as_slice()),
+        (3, close_authority.as_slice()),
+        (18, metadata_pointer.as_slice()),
+    ] {
+        data.extend_from_slice(&ty.to_le_bytes());
+        data.extend_from_slice(&(value.len() as u16).to_le_bytes());
+        data.extend_from_slice(value);
+    }
+
+    vm.account_data.insert(
+        MINT,
+        AccountState {
+            data,
+            owner: Some(token_2022_id().0),
+            lamports: 0,
+        },
+    );
+
+    let returns = vm
+        .function("extensions")
+        .accounts(vec![("mint", MINT)])
+        .call()
+        .unwrap()
+        .unwrap_tuple();
+
+    assert_eq!(
+        returns,
+        vec![
+            BorshToken::Bool(true),
+            BorshToken::Bool(true),
+            BorshToken::Bool(true),
+            BorshToken::Bool(false)
+        ]
+    );
+
+    let returns = vm
+        .function("transfer_fee_config")
+        .accounts(vec![("mint", MINT)])
+        .call()
+        .unwrap()
+        .unwrap_tuple();
+
+    let transfer_fee = |epoch: u64, maximum_fee: u64, basis_points: u16| {
+        BorshToken::Tuple(vec![
+            BorshToken::Uint {
+                width: 64,
+                value: BigInt::from(epoch),
+            },
+            BorshToken::Uint {
+                width: 64,
+                value: BigInt::from(maximum_fee),
+            },
+            BorshToken::Uint {
+                width: 16,
+                value: BigInt::from(basis_points),
+            },
+        ])
+    };
+
+    assert_eq!(
+        returns,
+        vec![
+            BorshToken::Address(fee_authority),
+            BorshToken::Address(withdraw_authority),
+            BorshToken::Uint {
+                width: 64,
+                value: BigInt::from(1234u64)
+            },
+            transfer_fee(0, 100, 50),
+            transfer_fee(10, 5000, 250),
+        ]
+    );
+
+    for (epoch, amount, fee) in [
+        (5u64, 10_001u64, 51u64),
+        (5, 1_000_000, 100),
+        (10, 10_001, 251),
+        (11, 1_000_000, 5000),
+        (11, 0, 0),
+    ] {
+        let returns = vm
+            .function("fee")
+            .arguments(&[
+                BorshToken::Uint {
+                    width: 64,
+                    value: BigInt::from(epoch),
+                },
+                BorshToken::Uint {
+                    width: 64,
+                    value: BigInt::from(amount),
+                },
+            ])
+            .accounts(vec![("mint", MINT)])
+            .call()
+            .unwrap();
+
+        assert_eq!(
+            returns,
+            BorshToken::Uint {
+                width: 64,
+                value: BigInt::from(fee)
+            }
+        );
+    }
+
+    let returns = vm
+        .function("metadata_pointer")
+        .accounts(vec![("mint", MINT)])
+        .call()
+        .unwrap()
+        .unwrap_tuple();
+
+    assert_eq!(
+        returns,
+        vec![BorshToken::Address([0; 32]), BorshToken::Address(metadata)]
+    );
+
+    let returns = vm
+        .function("close_authority")
+        .accounts(vec![("mint", MINT)])
+        .call()
+        .unwrap();
+
+    assert_eq!(returns, BorshToken::Address(close_authority));
+
+    // a mint without extensions
+    vm.account_data.get_mut(&MINT).unwrap().data.truncate(82);
+
+    let returns = vm
+        .function("extensions")
+        .accounts(vec![("mint", MINT)])
+        .call()
+        .unwrap()
+        .unwrap_tuple();
+
+    assert_eq!(returns, vec![BorshToken::Bool(false); 4]);
+
+    let res = vm
+        .function("close_authority")
+        .accounts(vec![("mint", MINT)])
+        .must_fail();
+
+    assert_eq!(res.unwrap(), 4294967296);
+}
@@ -8,17 +8,35 @@ use solang::file_resolver::FileResolver;
 use solang::sema::ast::Namespace;
 use solang::sema::diagnostics::Diagnostics;
 use solang::{compile, Target};
-use soroban_sdk::testutils::Logs;
-use soroban_sdk::{vec, Address, ConstructorArgs, Env, Symbol, Val};
+use soroban_sdk::testutils::{Address as _, AuthorizedInvocation, Events, Logs};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::xdr::ScErrorType;
+use soroban_sdk::{vec, Address, ConstructorArgs, Env, Error, Symbol, TryFromVal, Val};
 use std::ffi::OsStr;
 
-// TODO: register accounts, related balances, events, etc.
 pub struct SorobanEnv {
     env: Env,
     contracts: Vec<Address>,
     compiler_diagnostics: Diagnostics,
 }
 
+/// An event emitted by a contract. The first topic of an event is its name, unless the event is
+/// anonymous.
+#[derive(Debug)]
+pub struct ContractEvent {
+    pub contract: Address,
+    pub name: Option<String>,
+    pub topics: Vec<Val>,
+    pub data: Vec<Val>,
+}
+
+/// The resources consumed by an invocation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvocationCost {
+    pub cpu_instructions: u64,
+    pub memory_bytes: u64,
+}
+
 pub fn build_solidity<F>(src: &str, configure_env: F) -> SorobanEnv
 where
     F: FnOnce(&mut SorobanEnv),
@@ -29,6 +47,12 @@ where
 }
 
 fn build_wasm(src: &str) -> (Vec<u8>, Namespace) {
+    let (mut wasm, ns) = build_wasms(src);
+    (wasm.swap_remove(0), ns)
+}
+
+/// Build every contract in `src`, in the order they are declared
+fn build_wasms(src: &str) -> (Vec<Vec<u8>>, Namespace) {
     let tmp_file = OsStr::new("test.sol");
     let mut cache = FileResolver::default();
     cache.set_file_contents(tmp_file.to_str().unwrap(), src.to_string());
@@ -51,7 +75,7 @@ fn build_wasm(src: &str) -> (Vec<u8>, Namespace) {
         "0.0.1",
     );
     assert!(!wasm.is_empty());
-    (wasm[0].0.clone(), ns)
+    (wasm.into_iter().map(|(code, _)| code).collect(), ns)
 }
 
 impl SorobanEnv {
@@ -98,11 +122,91 @@ impl SorobanEnv {
             args_soroban.push_back(arg)
         }
         println!("args: {args_soroban:?}");
-        // To avoid running out of fuel
+        // To avoid running out of fuel, and so that the cost of this invocation can be measured
         self.env.cost_estimate().budget().reset_unlimited();
         self.env.invoke_contract(addr, &func, args_soroban)
     }
 
+    /// The cost of the last invocation
+    pub fn last_invocation_cost(&self) -> InvocationCost {
+        let budget = self.env.cost_estimate().budget();
+
+        InvocationCost {
+            cpu_instructions: budget.cpu_instruction_cost(),
+            memory_bytes: budget.memory_bytes_cost(),
+        }
+    }
+
+    /// The events emitted during the last invocation
+    pub fn events(&self) -> Vec<ContractEvent> {
+        self.env
+            .events()
+            .all()
+            .iter()
+            .map(|(contract, topics, data)| {
+                let mut topics: Vec<Val> = topics.iter().collect();
+
+                let name = topics
+                    .first()
+                    .and_then(|topic| Symbol::try_from_val(&self.env, topic).ok())
+                    .map(|name| name.to_string());
+
+                if name.is_some() {
+                    topics.remove(0);
+                }
+
+                let data = soroban_sdk::Vec::<Val>::try_from_val(&self.env, &data)
+                    .map(|fields| fields.iter().collect())
+                    .unwrap_or_else(|_| std::vec![data]);
+
+                ContractEvent {
+                    contract,
+                    name,
+                    topics,
+                    data,
+                }
+            })
+            .collect()
+    }
+
+    /// The authorizations which were required during the last invocation, as a tree for each
+    /// address
+    pub fn auths(&self) -> Vec<(Address, AuthorizedInvocation)> {
+        self.env.auths()
+    }
+
+    /// Create a new account. It has no balance until it is funded with [`Self::mint`].
+    pub fn create_account(&self) -> Address {
+        Address::generate(&self.env)
+    }
+
+    /// Issue a new Stellar asset, administered by `admin`. Returns the address of its Stellar
+    /// Asset Contract.
+    pub fn issue_asset(&self, admin: &Address) -> Address {
+        self.env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address()
+    }
+
+    /// Mint `amount` of `asset` to `to`, as authorized by the administrator of the asset
+    pub fn mint(&self, asset: &Address, to: &Address, amount: i128) {
+        StellarAssetClient::new(&self.env, asset)
+            .mock_all_auths()
+            .mint(to, &amount);
+    }
+
+    /// Create a new account which holds `amount` of `asset`
+    pub fn create_funded_account(&self, asset: &Address, amount: i128) -> Address {
+        let account = self.create_account();
+        self.mint(asset, &account, amount);
+        account
+    }
+
+    /// The balance of `asset` held by `id`
+    pub fn balance(&self, asset: &Address, id: &Address) -> i128 {
+        TokenClient::new(&self.env, asset).balance(id)
+    }
+
     /// Invoke a contract and expect an error. Returns the logs.
     pub fn invoke_contract_expect_error(
         &self,
@@ -116,6 +220,7 @@ impl SorobanEnv {
             args_soroban.push_back(arg)
         }
 
+        self.env.cost_estimate().budget().reset_unlimited();
         let _ = self
             .env
             .try_invoke_contract::<Val, Val>(addr, &func, args_soroban);
@@ -123,6 +228,29 @@ impl SorobanEnv {
         self.env.logs().all()
     }
 
+    /// Invoke a contract and expect it to fail with a contract error. Returns the error code.
+    pub fn invoke_contract_expect_contract_error(
+        &self,
+        addr: &Address,
+        function_name: &str,
+        args: Vec<Val>,
+    ) -> u32 {
+        let func = Symbol::new(&self.env, function_name);
+        let mut args_soroban = vec![&self.env];
+        for arg in args {
+            args_soroban.push_back(arg)
+        }
+
+        self.env.cost_estimate().budget().reset_unlimited();
+        match self
+            .env
+            .try_invoke_contract::<Val, Error>(addr, &func, args_soroban)
+        {
+            Err(Ok(error)) if error.is_type(ScErrorType::Contract) => error.get_code(),
+            res => panic!("expected a contract error, got {res:?}"),
+        }
+    }
+
     pub fn deploy_contract(&mut self, src: &str) -> Address {
         let wasm = build_wasm(src).0;
 
@@ -133,6 +261,18 @@ impl SorobanEnv {
         addr
     }
 
+    /// Deploy the first contract in `src`, after uploading the code of the other contracts so
+    /// that it can create them with `new`
+    pub fn deploy_contract_with_dependencies(&mut self, src: &str) -> Address {
+        let (wasms, _) = build_wasms(src);
+
+        for wasm in &wasms[1..] {
+            self.env.deployer().upload_contract_wasm(wasm.as_slice());
+        }
+
+        self.register_contract(wasms[0].clone())
+    }
+
     pub fn deploy_contract_with_args<A>(&mut self, src: &str, args: A) -> Address
     where
         A: ConstructorArgs,
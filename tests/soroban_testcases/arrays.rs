@@ -0,0 +1,208 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::build_solidity;
+use solang::{file_resolver::FileResolver, parse_and_resolve, Target};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{Address, IntoVal, String, Val};
+use std::ffi::OsStr;
+
+#[test]
+fn storage_arrays() {
+    let runtime = build_solidity(
+        r#"contract registry {
+            address[] members;
+            uint64[] temporary scores;
+            mapping(address => uint32[]) history;
+
+            function add(address a, uint64 s) public {
+                members.push(a);
+                scores.push(s);
+                history[a].push(uint32(s));
+            }
+
+            function count() public view returns (uint32) {
+                return uint32(members.length);
+            }
+
+            function score(uint32 i) public view returns (uint64) {
+                return scores[i];
+            }
+
+            function last(address a) public view returns (uint32) {
+                return history[a][history[a].length - 1];
+            }
+
+            function bump(uint32 i) public returns (uint64) {
+                scores[i] += 10;
+                scores[i]++;
+                return scores[i];
+            }
+
+            function remove() public returns (address) {
+                scores.pop();
+                return members.pop();
+            }
+        }"#,
+        |_| {},
+    );
+
+    let addr = runtime.contracts.last().unwrap();
+    let env = &runtime.env;
+    let alice = Address::generate(env);
+    let bob = Address::generate(env);
+
+    let res = runtime.invoke_contract(addr, "count", vec![]);
+    let expected: Val = 0_u32.into_val(env);
+    assert!(expected.shallow_eq(&res));
+
+    runtime.invoke_contract(
+        addr,
+        "add",
+        vec![alice.clone().into_val(env), 5_u64.into_val(env)],
+    );
+    runtime.invoke_contract(
+        addr,
+        "add",
+        vec![bob.clone().into_val(env), 7_u64.into_val(env)],
+    );
+    runtime.invoke_contract(
+        addr,
+        "add",
+        vec![alice.clone().into_val(env), 9_u64.into_val(env)],
+    );
+
+    let res = runtime.invoke_contract(addr, "count", vec![]);
+    let expected: Val = 3_u32.into_val(env);
+    assert!(expected.shallow_eq(&res));
+
+    let res = runtime.invoke_contract(addr, "score", vec![1_u32.into_val(env)]);
+    let expected: Val = 7_u64.into_val(env);
+    assert!(expected.shallow_eq(&res));
+
+    let res = runtime.invoke_contract(addr, "last", vec![alice.clone().into_val(env)]);
+    let expected: Val = 9_u32.into_val(env);
+    assert!(expected.shallow_eq(&res));
+
+    let res = runtime.invoke_contract(addr, "bump", vec![0_u32.into_val(env)]);
+    let expected: Val = 16_u64.into_val(env);
+    assert!(expected.shallow_eq(&res));
+
+    let res: Address = runtime
+        .invoke_contract(addr, "remove", vec![])
+        .into_val(env);
+    assert_eq!(res, alice);
+
+    let res = runtime.invoke_contract(addr, "count", vec![]);
+    let expected: Val = 2_u32.into_val(env);
+    assert!(expected.shallow_eq(&res));
+}
+
+#[test]
+fn storage_array_out_of_bounds() {
+    let runtime = build_solidity(
+        r#"contract c {
+            uint64[] values;
+
+            function get(uint32 i) public view returns (uint64) {
+                return values[i];
+            }
+
+            function remove() public {
+                values.pop();
+            }
+        }"#,
+        |_| {},
+    );
+
+    let addr = runtime.contracts.last().unwrap();
+
+    let res = runtime.invoke_contract_expect_error(addr, "get", vec![0_u32.into_val(&runtime.env)]);
+    assert!(res[0].contains("array index out of bounds"));
+
+    let res = runtime.invoke_contract_expect_error(addr, "remove", vec![]);
+    assert!(res.last().unwrap().contains("pop from empty storage array"));
+}
+
+#[test]
+fn storage_bytes_and_strings() {
+    let runtime = build_solidity(
+        r#"contract c {
+            bytes data;
+            string instance name;
+
+            function addBytes() public returns (uint32) {
+                data.push(0x41);
+                data.push(0x42);
+                data[0] = 0x43;
+                return data.length;
+            }
+
+            function checkBytes() public returns (bool) {
+                return data[0] == 0x43 && data.pop() == 0x42 && data.length == 1;
+            }
+
+            function setName(string memory n) public {
+                name = n;
+            }
+
+            function getName() public view returns (string memory) {
+                return name;
+            }
+        }"#,
+        |_| {},
+    );
+
+    let addr = runtime.contracts.last().unwrap();
+    let env = &runtime.env;
+
+    let res = runtime.invoke_contract(addr, "addBytes", vec![]);
+    let expected: Val = 2_u32.into_val(env);
+    assert!(expected.shallow_eq(&res));
+
+    let res = runtime.invoke_contract(addr, "checkBytes", vec![]);
+    let expected: Val = true.into_val(env);
+    assert!(expected.shallow_eq(&res));
+
+    let name = String::from_str(env, "solang");
+    runtime.invoke_contract(addr, "setName", vec![name.clone().into_val(env)]);
+
+    let res: String = runtime
+        .invoke_contract(addr, "getName", vec![])
+        .into_val(env);
+    assert_eq!(res, name);
+}
+
+#[test]
+fn unsupported_array_types() {
+    let mut cache = FileResolver::default();
+
+    cache.set_file_contents(
+        "test.sol",
+        r#"contract C {
+            uint64[][] persistent grid;
+
+            function f(uint64[] memory a) public returns (bytes4) {
+                return bytes4(0);
+            }
+        }"#
+        .to_string(),
+    );
+
+    let ns = parse_and_resolve(OsStr::new("test.sol"), &mut cache, Target::Soroban);
+
+    let errors = ns.diagnostics.errors();
+
+    assert_eq!(errors.len(), 3);
+    assert_eq!(
+        errors[0].message,
+        "type 'uint64[][]' is not supported for storage variables on Soroban"
+    );
+    assert_eq!(
+        errors[1].message,
+        "type 'uint64[]' is not supported in public functions on Soroban"
+    );
+    assert_eq!(
+        errors[2].message,
+        "type 'bytes4' is not supported in public functions on Soroban"
+    );
+}
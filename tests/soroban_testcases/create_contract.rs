@@ -0,0 +1,65 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::SorobanEnv;
+use soroban_sdk::{Address, Error, IntoVal, Symbol, Val};
+
+#[test]
+fn create_contract_with_new() {
+    let mut runtime = SorobanEnv::new();
+
+    let factory = runtime.deploy_contract_with_dependencies(
+        r#"contract factory {
+            function deploy(uint64 id, uint64 start) public returns (address) {
+                counter c = new counter{salt: bytes32(uint256(id))}(start);
+                return address(c);
+            }
+        }
+
+        contract counter {
+            uint64 count;
+
+            constructor(uint64 start) {
+                count = start;
+            }
+
+            function inc() public returns (uint64) {
+                count += 1;
+                return count;
+            }
+        }"#,
+    );
+
+    let env = &runtime.env;
+
+    let first: Address = runtime
+        .invoke_contract(
+            &factory,
+            "deploy",
+            vec![1_u64.into_val(env), 41_u64.into_val(env)],
+        )
+        .into_val(env);
+
+    let second: Address = runtime
+        .invoke_contract(
+            &factory,
+            "deploy",
+            vec![2_u64.into_val(env), 100_u64.into_val(env)],
+        )
+        .into_val(env);
+
+    assert_ne!(first, second);
+
+    // the constructor arguments were passed to each contract
+    let res = runtime.invoke_contract(&first, "inc", vec![]);
+    let expected: Val = 42_u64.into_val(env);
+    assert!(expected.shallow_eq(&res));
+
+    let res = runtime.invoke_contract(&second, "inc", vec![]);
+    let expected: Val = 101_u64.into_val(env);
+    assert!(expected.shallow_eq(&res));
+
+    // the same salt cannot be used twice
+    let args = soroban_sdk::vec![env, 1_u64.into_val(env), 7_u64.into_val(env)];
+    let res = env.try_invoke_contract::<Val, Error>(&factory, &Symbol::new(env, "deploy"), args);
+    assert!(res.is_err());
+}
@@ -0,0 +1,167 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::build_solidity;
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::testutils::Ledger;
+use soroban_sdk::{Bytes, Error, IntoVal, Symbol, Val};
+
+#[test]
+fn hashes() {
+    let runtime = build_solidity(
+        r#"contract c {
+            function check(bytes memory data) public pure returns (bool) {
+                return sha256(data) == 0x03ac674216f3e15c761ee1a5e255f067953623c8b388b4459e13f978d7c846f4
+                    && keccak256(data) == 0x387a8233c96e1fc0ad5e284353276177af2186e7afa85296f106336e376669f7;
+            }
+        }"#,
+        |_| {},
+    );
+
+    let addr = runtime.contracts.last().unwrap();
+    let env = &runtime.env;
+
+    let res = runtime.invoke_contract(
+        addr,
+        "check",
+        vec![Bytes::from_slice(env, b"1234").into_val(env)],
+    );
+    let expected: Val = true.into_val(env);
+    assert!(expected.shallow_eq(&res));
+
+    let res = runtime.invoke_contract(
+        addr,
+        "check",
+        vec![Bytes::from_slice(env, b"4321").into_val(env)],
+    );
+    let expected: Val = false.into_val(env);
+    assert!(expected.shallow_eq(&res));
+}
+
+#[test]
+fn secp256k1_recover() {
+    let runtime = build_solidity(
+        r#"contract c {
+            function recover(bytes memory signature, uint32 recoveryId) public pure returns (bytes memory) {
+                // sha256("1234")
+                bytes32 digest = 0x03ac674216f3e15c761ee1a5e255f067953623c8b388b4459e13f978d7c846f4;
+                return secp256k1_recover(digest, signature, recoveryId);
+            }
+        }"#,
+        |_| {},
+    );
+
+    let addr = runtime.contracts.last().unwrap();
+    let env = &runtime.env;
+
+    let mut signature = [0u8; 64];
+    signature[..32].copy_from_slice(
+        &hex::decode("46a24eba93079bc1442a5f0d6aa74025305faf5e04f58f35ae4961b5b268aef5").unwrap(),
+    );
+    signature[32..].copy_from_slice(
+        &hex::decode("23bf6d6a32fd70b66e1ff51edfd511cc018e8e7f4c8b3ddd4082c597b458a73c").unwrap(),
+    );
+
+    let res: Bytes = runtime
+        .invoke_contract(
+            addr,
+            "recover",
+            vec![
+                Bytes::from_slice(env, &signature).into_val(env),
+                0_u32.into_val(env),
+            ],
+        )
+        .into_val(env);
+
+    let public_key = hex::decode(
+        "0441bd7126787d1e15fba586e1f69c0055c5443e5847470c6402723c75be77c4e5\
+         77f561f389d6779fdfa8c887d020f5819006f3c13701d0db63f90e17adc3dce4",
+    )
+    .unwrap();
+    assert_eq!(res, Bytes::from_slice(env, &public_key));
+}
+
+#[test]
+fn ed25519_verify() {
+    let runtime = build_solidity(
+        r#"contract c {
+            function verify(bytes memory key, bytes memory message, bytes memory signature) public pure returns (bool) {
+                ed25519_verify(key, message, signature);
+                return true;
+            }
+        }"#,
+        |_| {},
+    );
+
+    let addr = runtime.contracts.last().unwrap();
+    let env = &runtime.env;
+
+    let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+    let message = b"solang on soroban";
+    let signature = signing_key.sign(message).to_bytes();
+
+    let args = |message: &[u8]| {
+        vec![
+            Bytes::from_slice(env, signing_key.verifying_key().as_bytes()).into_val(env),
+            Bytes::from_slice(env, message).into_val(env),
+            Bytes::from_slice(env, &signature).into_val(env),
+        ]
+    };
+
+    let res = runtime.invoke_contract(addr, "verify", args(message));
+    let expected: Val = true.into_val(env);
+    assert!(expected.shallow_eq(&res));
+
+    // a signature for another message traps
+    let args_soroban = soroban_sdk::Vec::from_slice(env, &args(b"something else"));
+    let res =
+        env.try_invoke_contract::<Val, Error>(addr, &Symbol::new(env, "verify"), args_soroban);
+    assert!(res.is_err());
+}
+
+#[test]
+fn ledger() {
+    let runtime = build_solidity(
+        r#"contract c {
+            function timestamp() public view returns (uint64) {
+                return block.timestamp;
+            }
+
+            function sequence() public view returns (uint64) {
+                return block.number;
+            }
+
+            function network() public view returns (bool) {
+                return block.network_id == 0x0707070707070707070707070707070707070707070707070707070707070707;
+            }
+
+            function roll() public view returns (uint64) {
+                return prng_u64(1, 6);
+            }
+        }"#,
+        |runtime| {
+            runtime.env.ledger().set_timestamp(1_700_000_000);
+            runtime.env.ledger().set_sequence_number(4242);
+            runtime.env.ledger().set_network_id([7u8; 32]);
+        },
+    );
+
+    let addr = runtime.contracts.last().unwrap();
+    let env = &runtime.env;
+
+    let res = runtime.invoke_contract(addr, "timestamp", vec![]);
+    let expected: Val = 1_700_000_000_u64.into_val(env);
+    assert!(expected.shallow_eq(&res));
+
+    let res = runtime.invoke_contract(addr, "sequence", vec![]);
+    let expected: Val = 4242_u64.into_val(env);
+    assert!(expected.shallow_eq(&res));
+
+    let res = runtime.invoke_contract(addr, "network", vec![]);
+    let expected: Val = true.into_val(env);
+    assert!(expected.shallow_eq(&res));
+
+    for _ in 0..10 {
+        let res: u64 = runtime.invoke_contract(addr, "roll", vec![]).into_val(env);
+        assert!((1..=6).contains(&res));
+    }
+}
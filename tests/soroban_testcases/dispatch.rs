@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::build_solidity;
+use soroban_sdk::{IntoVal, Val};
+
+#[test]
+fn function_numbers_differ_from_cfg_numbers() {
+    // the free function is not part of the contract, so the public functions of the contract
+    // have a different cfg number than function number
+    let runtime = build_solidity(
+        r#"function unused() pure returns (uint64) {
+            return 1;
+        }
+
+        contract c {
+            uint64 instance count;
+
+            function get() public pure returns (uint64) {
+                return 42;
+            }
+
+            function add(uint64 a) public returns (uint64) {
+                count += a;
+                return count;
+            }
+        }"#,
+        |_| {},
+    );
+
+    let addr = runtime.contracts.last().unwrap();
+    let env = &runtime.env;
+
+    let res = runtime.invoke_contract(addr, "get", vec![]);
+    let expected: Val = 42_u64.into_val(env);
+    assert!(expected.shallow_eq(&res));
+
+    let res = runtime.invoke_contract(addr, "add", vec![5_u64.into_val(env)]);
+    let expected: Val = 5_u64.into_val(env);
+    assert!(expected.shallow_eq(&res));
+}
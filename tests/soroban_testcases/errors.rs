@@ -0,0 +1,129 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{build_solidity, build_wasms};
+use soroban_sdk::xdr::{Limited, Limits, ReadXdr, ScSpecEntry};
+use soroban_sdk::{IntoVal, Val};
+use wasmparser::{Parser, Payload};
+
+#[test]
+fn custom_error_codes() {
+    let runtime = build_solidity(
+        r#"error Unauthorized();
+
+        contract vault {
+            error InsufficientBalance(uint64 available, uint64 required);
+
+            uint64 instance balance;
+
+            function deposit(uint64 amount) public returns (uint64) {
+                balance += amount;
+                return balance;
+            }
+
+            function withdraw(uint64 amount) public returns (uint64) {
+                if (amount > balance) {
+                    revert InsufficientBalance(balance, amount);
+                }
+                balance -= amount;
+                return balance;
+            }
+
+            function close() public pure {
+                revert Unauthorized();
+            }
+        }"#,
+        |_| {},
+    );
+
+    let addr = runtime.contracts.last().unwrap();
+    let env = &runtime.env;
+
+    let res = runtime.invoke_contract(addr, "deposit", vec![10_u64.into_val(env)]);
+    let expected: Val = 10_u64.into_val(env);
+    assert!(expected.shallow_eq(&res));
+
+    // errors are numbered from 1, with file level errors first
+    let code =
+        runtime.invoke_contract_expect_contract_error(addr, "withdraw", vec![11_u64.into_val(env)]);
+    assert_eq!(code, 2);
+
+    let code = runtime.invoke_contract_expect_contract_error(addr, "close", vec![]);
+    assert_eq!(code, 1);
+
+    let res = runtime.invoke_contract(addr, "withdraw", vec![4_u64.into_val(env)]);
+    let expected: Val = 6_u64.into_val(env);
+    assert!(expected.shallow_eq(&res));
+}
+
+#[test]
+fn error_spec() {
+    // the errors of other contracts do not take up codes, unless the contract reverts with them
+    let (wasms, _) = build_wasms(
+        r#"error Unauthorized();
+
+        contract other {
+            error Unrelated();
+        }
+
+        contract vault {
+            error InsufficientBalance(uint64 available, uint64 required);
+
+            function close() public pure {
+                revert Unauthorized();
+            }
+
+            function check(uint64 amount) public pure {
+                Checks.small(amount);
+            }
+        }
+
+        library Checks {
+            error TooLarge(uint64 amount);
+
+            function small(uint64 amount) internal pure {
+                if (amount > 100) {
+                    revert TooLarge(amount);
+                }
+            }
+        }"#,
+    );
+    let wasm = &wasms[1];
+
+    let mut entries = Vec::new();
+
+    for payload in Parser::new(0).parse_all(wasm) {
+        if let Payload::CustomSection(section) = payload.unwrap() {
+            if section.name() == "contractspecv0" {
+                let mut data = Limited::new(section.data(), Limits::none());
+                for entry in ScSpecEntry::read_xdr_iter(&mut data) {
+                    entries.push(entry.unwrap());
+                }
+            }
+        }
+    }
+
+    let errors = entries
+        .iter()
+        .find_map(|entry| match entry {
+            ScSpecEntry::UdtErrorEnumV0(e) => Some(e),
+            _ => None,
+        })
+        .expect("error spec entry");
+
+    assert_eq!(errors.name.to_utf8_string_lossy(), "Error");
+
+    let cases = errors
+        .cases
+        .iter()
+        .map(|c| (c.name.to_utf8_string_lossy(), c.value))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        cases,
+        [
+            ("Unauthorized".to_string(), 1),
+            ("InsufficientBalance".to_string(), 2),
+            ("TooLarge".to_string(), 3)
+        ]
+    );
+}
@@ -0,0 +1,237 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{build_solidity, build_wasm};
+use solang::{file_resolver::FileResolver, parse_and_resolve, Target};
+use soroban_sdk::testutils::{Address as _, Events};
+use soroban_sdk::xdr::{
+    Limited, Limits, ReadXdr, ScSpecEntry, ScSpecEventDataFormat, ScSpecEventParamLocationV0,
+    ScSpecTypeDef,
+};
+use soroban_sdk::{Address, IntoVal, Symbol, Val};
+use std::ffi::OsStr;
+use wasmparser::{Parser, Payload};
+
+#[test]
+fn emit_event() {
+    let runtime = build_solidity(
+        r#"contract token {
+            event Transfer(address indexed from, address indexed to, uint64 amount);
+            event Approval(address indexed owner, uint64 amount, bool infinite) anonymous;
+
+            function transfer(address from, address to, uint64 amount) public {
+                emit Transfer(from, to, amount);
+            }
+
+            function approve(address owner, uint64 amount) public {
+                emit Approval(owner, amount, false);
+            }
+        }"#,
+        |_| {},
+    );
+
+    let addr = runtime.contracts.last().unwrap();
+    let from = Address::generate(&runtime.env);
+    let to = Address::generate(&runtime.env);
+
+    runtime.invoke_contract(
+        addr,
+        "transfer",
+        vec![
+            from.clone().into_val(&runtime.env),
+            to.clone().into_val(&runtime.env),
+            100_u64.into_val(&runtime.env),
+        ],
+    );
+
+    let expected_topics: soroban_sdk::Vec<Val> = soroban_sdk::vec![
+        &runtime.env,
+        Symbol::new(&runtime.env, "Transfer").into_val(&runtime.env),
+        from.clone().into_val(&runtime.env),
+        to.into_val(&runtime.env),
+    ];
+    let expected_data: soroban_sdk::Vec<Val> =
+        soroban_sdk::vec![&runtime.env, 100_u64.into_val(&runtime.env)];
+
+    assert_eq!(
+        runtime.env.events().all(),
+        soroban_sdk::vec![
+            &runtime.env,
+            (
+                addr.clone(),
+                expected_topics,
+                expected_data.into_val(&runtime.env)
+            )
+        ]
+    );
+
+    runtime.invoke_contract(
+        addr,
+        "approve",
+        vec![
+            from.clone().into_val(&runtime.env),
+            7_u64.into_val(&runtime.env),
+        ],
+    );
+
+    // anonymous events do not have the event name as a topic
+    let expected_topics: soroban_sdk::Vec<Val> =
+        soroban_sdk::vec![&runtime.env, from.into_val(&runtime.env)];
+    let expected_data: soroban_sdk::Vec<Val> = soroban_sdk::vec![
+        &runtime.env,
+        7_u64.into_val(&runtime.env),
+        false.into_val(&runtime.env),
+    ];
+
+    assert_eq!(
+        runtime.env.events().all(),
+        soroban_sdk::vec![
+            &runtime.env,
+            (
+                addr.clone(),
+                expected_topics,
+                expected_data.into_val(&runtime.env)
+            )
+        ]
+    );
+}
+
+#[test]
+fn decoded_events() {
+    let runtime = build_solidity(
+        r#"contract token {
+            event Transfer(address indexed from, address indexed to, uint64 amount);
+            event Approval(address indexed owner, uint64 amount, bool infinite) anonymous;
+
+            function transferAndApprove(address from, address to, uint64 amount) public {
+                emit Transfer(from, to, amount);
+                emit Approval(from, amount * 2, true);
+            }
+        }"#,
+        |_| {},
+    );
+
+    let addr = runtime.contracts.last().unwrap();
+    let env = &runtime.env;
+    let from = runtime.create_account();
+    let to = runtime.create_account();
+
+    runtime.invoke_contract(
+        addr,
+        "transferAndApprove",
+        vec![
+            from.clone().into_val(env),
+            to.clone().into_val(env),
+            100_u64.into_val(env),
+        ],
+    );
+
+    let events = runtime.events();
+    assert_eq!(events.len(), 2);
+
+    assert_eq!(&events[0].contract, addr);
+    assert_eq!(events[0].name.as_deref(), Some("Transfer"));
+    let topics: Vec<Address> = events[0].topics.iter().map(|t| t.into_val(env)).collect();
+    assert_eq!(topics, vec![from.clone(), to]);
+    let amount: u64 = events[0].data[0].into_val(env);
+    assert_eq!(amount, 100);
+
+    assert_eq!(events[1].name, None);
+    let owner: Address = events[1].topics[0].into_val(env);
+    assert_eq!(owner, from);
+    let amount: u64 = events[1].data[0].into_val(env);
+    let infinite: bool = events[1].data[1].into_val(env);
+    assert_eq!((amount, infinite), (200, true));
+}
+
+#[test]
+fn event_spec() {
+    let (wasm, _) = build_wasm(
+        r#"contract token {
+            event Transfer(address indexed from, address indexed to, uint128 amount);
+
+            function transfer(address from, address to, uint128 amount) public {
+                emit Transfer(from, to, amount);
+            }
+        }"#,
+    );
+
+    let mut entries = Vec::new();
+
+    for payload in Parser::new(0).parse_all(&wasm) {
+        if let Payload::CustomSection(section) = payload.unwrap() {
+            if section.name() == "contractspecv0" {
+                let mut data = Limited::new(section.data(), Limits::none());
+                for entry in ScSpecEntry::read_xdr_iter(&mut data) {
+                    entries.push(entry.unwrap());
+                }
+            }
+        }
+    }
+
+    let event = entries
+        .iter()
+        .find_map(|entry| match entry {
+            ScSpecEntry::EventV0(event) => Some(event),
+            _ => None,
+        })
+        .expect("event spec entry");
+
+    assert_eq!(event.name.to_utf8_string_lossy(), "Transfer");
+    assert_eq!(event.prefix_topics.len(), 1);
+    assert_eq!(event.prefix_topics[0].to_utf8_string_lossy(), "Transfer");
+    assert_eq!(event.data_format, ScSpecEventDataFormat::Vec);
+
+    let params = event
+        .params
+        .iter()
+        .map(|p| (p.name.to_utf8_string_lossy(), p.type_.clone(), p.location))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        params,
+        [
+            (
+                "from".to_string(),
+                ScSpecTypeDef::Address,
+                ScSpecEventParamLocationV0::TopicList
+            ),
+            (
+                "to".to_string(),
+                ScSpecTypeDef::Address,
+                ScSpecEventParamLocationV0::TopicList
+            ),
+            (
+                "amount".to_string(),
+                ScSpecTypeDef::U128,
+                ScSpecEventParamLocationV0::Data
+            ),
+        ]
+    );
+}
+
+#[test]
+fn unsupported_event_field() {
+    let mut cache = FileResolver::default();
+
+    cache.set_file_contents(
+        "test.sol",
+        r#"contract C {
+            event Values(uint64 a, int64[] b);
+
+            function f() public {
+                emit Values(1, new int64[](0));
+            }
+        }"#
+        .to_string(),
+    );
+
+    let ns = parse_and_resolve(OsStr::new("test.sol"), &mut cache, Target::Soroban);
+
+    let errors = ns.diagnostics.errors();
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].message,
+        "type 'int64[]' is not supported as event field on Soroban"
+    );
+}
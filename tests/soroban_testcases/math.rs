@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::build_solidity;
-use soroban_sdk::{IntoVal, Val};
+use soroban_sdk::{IntoVal, Val, I256, U256};
 
 #[test]
 fn math() {
@@ -190,6 +190,143 @@ fn u128_ops() {
     assert!(expected.shallow_eq(&res));
 }
 
+#[test]
+fn u256_ops() {
+    let runtime = build_solidity(
+        r#"contract math {
+        uint256 total;
+
+        function add(uint256 a, uint256 b) public returns (uint256) {
+            return a + b;
+        }
+
+        function mul(uint256 a, uint256 b) public returns (uint256) {
+            return a * b;
+        }
+
+        function div(uint256 a, uint256 b) public returns (uint256) {
+            return a / b;
+        }
+
+        function deposit(uint256 amount) public returns (uint256) {
+            total += amount;
+            return total;
+        }
+    }"#,
+        |_| {},
+    );
+
+    let addr = runtime.contracts.last().unwrap();
+    let env = &runtime.env;
+
+    // small values fit in the Val itself
+    let res = runtime.invoke_contract(
+        addr,
+        "add",
+        vec![
+            U256::from_u32(env, 5).into_val(env),
+            U256::from_u32(env, 4).into_val(env),
+        ],
+    );
+    let res: U256 = res.into_val(env);
+    assert_eq!(res, U256::from_u32(env, 9));
+
+    // 2^200 * 3 needs all four pieces
+    let large = U256::from_parts(env, 1 << 8, 0, 0, 0);
+    let res: U256 = runtime
+        .invoke_contract(
+            addr,
+            "mul",
+            vec![
+                large.clone().into_val(env),
+                U256::from_u32(env, 3).into_val(env),
+            ],
+        )
+        .into_val(env);
+    assert_eq!(res, U256::from_parts(env, 3 << 8, 0, 0, 0));
+
+    let res: U256 = runtime
+        .invoke_contract(
+            addr,
+            "div",
+            vec![
+                large.clone().into_val(env),
+                U256::from_parts(env, 0, 0, 1, 0).into_val(env),
+            ],
+        )
+        .into_val(env);
+    assert_eq!(res, U256::from_parts(env, 0, 1 << 8, 0, 0));
+
+    runtime.invoke_contract(addr, "deposit", vec![large.clone().into_val(env)]);
+    let res: U256 = runtime
+        .invoke_contract(addr, "deposit", vec![U256::from_u32(env, 1).into_val(env)])
+        .into_val(env);
+    assert_eq!(res, U256::from_parts(env, 1 << 8, 0, 0, 1));
+
+    // overflow traps
+    let max = U256::from_parts(env, u64::MAX, u64::MAX, u64::MAX, u64::MAX);
+    let res = runtime.invoke_contract_expect_error(
+        addr,
+        "add",
+        vec![max.into_val(env), U256::from_u32(env, 1).into_val(env)],
+    );
+    assert!(res[0].contains("math overflow"));
+}
+
+#[test]
+fn i256_ops() {
+    let runtime = build_solidity(
+        r#"contract math {
+        function sub(int256 a, int256 b) public returns (int256) {
+            return a - b;
+        }
+
+        function neg(int256 a) public returns (int256) {
+            return -a;
+        }
+    }"#,
+        |_| {},
+    );
+
+    let addr = runtime.contracts.last().unwrap();
+    let env = &runtime.env;
+
+    let res: I256 = runtime
+        .invoke_contract(
+            addr,
+            "sub",
+            vec![
+                I256::from_i32(env, 4).into_val(env),
+                I256::from_i32(env, 5).into_val(env),
+            ],
+        )
+        .into_val(env);
+    assert_eq!(res, I256::from_i32(env, -1));
+
+    // -2^255 is the smallest value, and does not fit in the Val
+    let min = I256::from_parts(env, i64::MIN, 0, 0, 0);
+    let res: I256 = runtime
+        .invoke_contract(
+            addr,
+            "sub",
+            vec![
+                min.clone().into_val(env),
+                I256::from_i32(env, -1).into_val(env),
+            ],
+        )
+        .into_val(env);
+    assert_eq!(res, I256::from_parts(env, i64::MIN, 0, 0, 1));
+
+    let res: I256 = runtime
+        .invoke_contract(
+            addr,
+            "neg",
+            vec![I256::from_parts(env, -1, u64::MAX, 0, 0).into_val(env)],
+        )
+        .into_val(env);
+    assert_eq!(res, I256::from_parts(env, 0, 1, 0, 0));
+}
+
 #[test]
 fn bool_roundtrip() {
     let runtime = build_solidity(
@@ -407,3 +544,33 @@ fn i64_ops() {
     let expected: Val = (1_i64).into_val(&runtime.env);
     assert!(expected.shallow_eq(&res));
 }
+
+#[test]
+fn invocation_cost() {
+    let runtime = build_solidity(
+        r#"contract math {
+            function sum(uint64 n) public pure returns (uint64) {
+                uint64 total = 0;
+                for (uint64 i = 0; i < n; i++) {
+                    total += i;
+                }
+                return total;
+            }
+        }"#,
+        |_| {},
+    );
+
+    let addr = runtime.contracts.last().unwrap();
+
+    runtime.invoke_contract(addr, "sum", vec![10_u64.into_val(&runtime.env)]);
+    let short = runtime.last_invocation_cost();
+
+    let res = runtime.invoke_contract(addr, "sum", vec![1000_u64.into_val(&runtime.env)]);
+    let expected: Val = 499_500_u64.into_val(&runtime.env);
+    assert!(expected.shallow_eq(&res));
+    let long = runtime.last_invocation_cost();
+
+    // each invocation is metered on its own
+    assert!(short.cpu_instructions > 0);
+    assert!(long.cpu_instructions > short.cpu_instructions);
+}
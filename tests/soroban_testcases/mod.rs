@@ -1,12 +1,19 @@
 // SPDX-License-Identifier: Apache-2.0
+mod arrays;
 mod auth;
 mod constructor;
+mod create_contract;
 mod cross_contract_calls;
+mod crypto;
+mod dispatch;
+mod errors;
+mod events;
 mod integer_width_rounding;
 mod integer_width_warnings;
 mod mappings;
 mod math;
 mod print;
 mod storage;
+mod structs;
 mod token;
 mod ttl;
@@ -123,3 +123,41 @@ fn different_storage_types() {
     assert!(diags
         .contains_message("storage type not specified for `sesa3`, defaulting to `persistent`"));
 }
+
+#[test]
+fn transient_storage() {
+    let src = build_solidity(
+        r#"contract guard {
+            uint64 transient depth;
+            uint64 persistent count;
+
+            function bump() public returns (uint64) {
+                depth += 1;
+                count += 1;
+                return depth * 100 + count;
+            }
+
+            function clear() public returns (uint64) {
+                depth = 5;
+                delete depth;
+                return depth;
+            }
+        }"#,
+        |_| {},
+    );
+
+    let addr = src.contracts.last().unwrap();
+
+    let res = src.invoke_contract(addr, "bump", vec![]);
+    let expected: Val = 101_u64.into_val(&src.env);
+    assert!(expected.shallow_eq(&res));
+
+    // the transient variable starts from zero in every invocation
+    let res = src.invoke_contract(addr, "bump", vec![]);
+    let expected: Val = 102_u64.into_val(&src.env);
+    assert!(expected.shallow_eq(&res));
+
+    let res = src.invoke_contract(addr, "clear", vec![]);
+    let expected: Val = 0_u64.into_val(&src.env);
+    assert!(expected.shallow_eq(&res));
+}
@@ -0,0 +1,207 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{build_solidity, build_wasm};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::xdr::{Limited, Limits, ReadXdr, ScSpecEntry, ScSpecTypeDef, ScSpecTypeUdt};
+use soroban_sdk::{Address, IntoVal, Map, Symbol, Val};
+use wasmparser::{Parser, Payload};
+
+#[test]
+fn struct_in_storage() {
+    let runtime = build_solidity(
+        r#"contract c {
+            enum Status { Active, Frozen }
+            struct Info { uint64 balance; Status status; address owner; }
+
+            Info info;
+
+            function set(Info memory i) public {
+                info = i;
+            }
+
+            function get() public view returns (Info memory) {
+                return info;
+            }
+
+            function bump() public returns (uint64) {
+                info.balance += 5;
+                info.balance++;
+                info.status = Status.Frozen;
+                return info.balance;
+            }
+
+            function status() public view returns (Status) {
+                return info.status;
+            }
+        }"#,
+        |_| {},
+    );
+
+    let addr = runtime.contracts.last().unwrap();
+    let env = &runtime.env;
+    let owner = Address::generate(env);
+
+    // structs are maps from the field name to the value
+    let mut info: Map<Symbol, Val> = Map::new(env);
+    info.set(Symbol::new(env, "balance"), 100_u64.into_val(env));
+    info.set(Symbol::new(env, "status"), 0_u32.into_val(env));
+    info.set(Symbol::new(env, "owner"), owner.clone().into_val(env));
+
+    runtime.invoke_contract(addr, "set", vec![info.clone().into_val(env)]);
+
+    let res: Map<Symbol, Val> = runtime.invoke_contract(addr, "get", vec![]).into_val(env);
+    assert_eq!(res, info);
+
+    let res = runtime.invoke_contract(addr, "bump", vec![]);
+    let expected: Val = 106_u64.into_val(env);
+    assert!(expected.shallow_eq(&res));
+
+    // enums are passed as u32
+    let res = runtime.invoke_contract(addr, "status", vec![]);
+    let expected: Val = 1_u32.into_val(env);
+    assert!(expected.shallow_eq(&res));
+
+    let res: Map<Symbol, Val> = runtime.invoke_contract(addr, "get", vec![]).into_val(env);
+    let balance: u64 = res.get(Symbol::new(env, "balance")).unwrap().into_val(env);
+    let status: u32 = res.get(Symbol::new(env, "status")).unwrap().into_val(env);
+    let res_owner: Address = res.get(Symbol::new(env, "owner")).unwrap().into_val(env);
+    assert_eq!(balance, 106);
+    assert_eq!(status, 1);
+    assert_eq!(res_owner, owner);
+}
+
+#[test]
+fn unset_struct_is_zero() {
+    let runtime = build_solidity(
+        r#"contract c {
+            struct Point { int64 x; uint32 y; bool valid; }
+
+            Point p;
+
+            function x() public view returns (int64) {
+                return p.x;
+            }
+
+            function valid() public view returns (bool) {
+                return p.valid;
+            }
+
+            function move(int64 dx) public returns (int64) {
+                p.x += dx;
+                p.valid = true;
+                return p.x;
+            }
+        }"#,
+        |_| {},
+    );
+
+    let addr = runtime.contracts.last().unwrap();
+    let env = &runtime.env;
+
+    let res = runtime.invoke_contract(addr, "x", vec![]);
+    let expected: Val = 0_i64.into_val(env);
+    assert!(expected.shallow_eq(&res));
+
+    let res = runtime.invoke_contract(addr, "valid", vec![]);
+    let expected: Val = false.into_val(env);
+    assert!(expected.shallow_eq(&res));
+
+    let res = runtime.invoke_contract(addr, "move", vec![(-3_i64).into_val(env)]);
+    let expected: Val = (-3_i64).into_val(env);
+    assert!(expected.shallow_eq(&res));
+
+    let res = runtime.invoke_contract(addr, "valid", vec![]);
+    let expected: Val = true.into_val(env);
+    assert!(expected.shallow_eq(&res));
+}
+
+#[test]
+fn struct_spec() {
+    let (wasm, _) = build_wasm(
+        r#"contract c {
+            enum Status { Active, Frozen }
+            struct Info { uint64 balance; Status status; }
+
+            function get(Info memory i) public pure returns (Info memory) {
+                return i;
+            }
+        }"#,
+    );
+
+    let mut entries = Vec::new();
+
+    for payload in Parser::new(0).parse_all(&wasm) {
+        if let Payload::CustomSection(section) = payload.unwrap() {
+            if section.name() == "contractspecv0" {
+                let mut data = Limited::new(section.data(), Limits::none());
+                for entry in ScSpecEntry::read_xdr_iter(&mut data) {
+                    entries.push(entry.unwrap());
+                }
+            }
+        }
+    }
+
+    let udt = |name: &str| {
+        ScSpecTypeDef::Udt(ScSpecTypeUdt {
+            name: name.try_into().unwrap(),
+        })
+    };
+
+    let func = entries
+        .iter()
+        .find_map(|entry| match entry {
+            ScSpecEntry::FunctionV0(func) if func.name.to_utf8_string_lossy() == "get" => {
+                Some(func)
+            }
+            _ => None,
+        })
+        .expect("function spec entry");
+
+    assert_eq!(func.inputs[0].type_, udt("Info"));
+    assert_eq!(func.outputs[0], udt("Info"));
+
+    let info = entries
+        .iter()
+        .find_map(|entry| match entry {
+            ScSpecEntry::UdtStructV0(s) => Some(s),
+            _ => None,
+        })
+        .expect("struct spec entry");
+
+    assert_eq!(info.name.to_utf8_string_lossy(), "Info");
+
+    let fields = info
+        .fields
+        .iter()
+        .map(|f| (f.name.to_utf8_string_lossy(), f.type_.clone()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        fields,
+        [
+            ("balance".to_string(), ScSpecTypeDef::U64),
+            ("status".to_string(), udt("Status")),
+        ]
+    );
+
+    let status = entries
+        .iter()
+        .find_map(|entry| match entry {
+            ScSpecEntry::UdtEnumV0(e) => Some(e),
+            _ => None,
+        })
+        .expect("enum spec entry");
+
+    assert_eq!(status.name.to_utf8_string_lossy(), "Status");
+
+    let cases = status
+        .cases
+        .iter()
+        .map(|c| (c.name.to_utf8_string_lossy(), c.value))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        cases,
+        [("Active".to_string(), 0), ("Frozen".to_string(), 1)]
+    );
+}
@@ -1,7 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::SorobanEnv;
-use soroban_sdk::{testutils::Address as _, Address, IntoVal, Val};
+use crate::{build_solidity, SorobanEnv};
+use soroban_sdk::testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation};
+use soroban_sdk::{Address, IntoVal, String, Symbol, Val};
 
 #[test]
 fn token_end_to_end_test() {
@@ -168,3 +169,106 @@ fn token_end_to_end_test() {
     assert!(expected2.shallow_eq(&b2));
     assert!(expected3.shallow_eq(&b3));
 }
+
+#[test]
+fn stellar_asset_contract() {
+    let runtime = build_solidity(
+        r#"import "soroban/token.sol";
+
+        contract vault {
+            function deposit(address token, address from, int128 amount) public returns (int128) {
+                TokenInterface(token).transfer(from, address(this), amount);
+                return TokenInterface(token).balance(address(this));
+            }
+
+            function withdraw(address token, address to, int128 amount) public {
+                TokenInterface(token).transfer(address(this), to, amount);
+            }
+
+            function decimals(address token) public view returns (uint32) {
+                return TokenInterface(token).decimals();
+            }
+
+            function symbol(address token) public view returns (string memory) {
+                return TokenInterface(token).symbol();
+            }
+
+            function mint(address token, address to, int128 amount) public {
+                StellarAssetInterface(token).mint(to, amount);
+            }
+        }"#,
+        |_| {},
+    );
+
+    let addr = runtime.contracts.last().unwrap();
+    let env = &runtime.env;
+
+    // the vault calls the token, which checks the authorization of the user
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let admin = runtime.create_account();
+    let token = runtime.issue_asset(&admin);
+    let user = runtime.create_funded_account(&token, 1000);
+
+    let res = runtime.invoke_contract(
+        addr,
+        "deposit",
+        vec![
+            token.clone().into_val(env),
+            user.clone().into_val(env),
+            400_i128.into_val(env),
+        ],
+    );
+    let expected: Val = 400_i128.into_val(env);
+    assert!(expected.shallow_eq(&res));
+
+    // the transfer is authorized by the user, even though the vault was invoked
+    let transfer = AuthorizedInvocation {
+        function: AuthorizedFunction::Contract((
+            token.clone(),
+            Symbol::new(env, "transfer"),
+            soroban_sdk::vec![
+                env,
+                user.clone().into_val(env),
+                addr.clone().into_val(env),
+                400_i128.into_val(env),
+            ],
+        )),
+        sub_invocations: vec![],
+    };
+    assert_eq!(runtime.auths(), vec![(user.clone(), transfer)]);
+
+    runtime.invoke_contract(
+        addr,
+        "withdraw",
+        vec![
+            token.clone().into_val(env),
+            user.clone().into_val(env),
+            150_i128.into_val(env),
+        ],
+    );
+
+    assert_eq!(runtime.balance(&token, &user), 750);
+    assert_eq!(runtime.balance(&token, addr), 250);
+
+    let res = runtime.invoke_contract(addr, "decimals", vec![token.clone().into_val(env)]);
+    let expected: Val = 7_u32.into_val(env);
+    assert!(expected.shallow_eq(&res));
+
+    let res: String = runtime
+        .invoke_contract(addr, "symbol", vec![token.clone().into_val(env)])
+        .into_val(env);
+    let token_client = soroban_sdk::token::TokenClient::new(env, &token);
+    assert_eq!(res, token_client.symbol());
+
+    runtime.invoke_contract(
+        addr,
+        "mint",
+        vec![
+            token.clone().into_val(env),
+            user.clone().into_val(env),
+            50_i128.into_val(env),
+        ],
+    );
+    assert_eq!(runtime.balance(&token, &user), 800);
+}
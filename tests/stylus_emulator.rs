@@ -0,0 +1,1272 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! An in-process emulator of the Stylus hostio interface, so that contracts compiled for the
+//! Stylus target can be deployed and called without a devnode, `cargo-stylus` or `cast`.
+
+use brotli2::read::{BrotliDecoder, BrotliEncoder};
+use ethabi::Token;
+use primitive_types::{U256, U512};
+use regex::Regex;
+use solang::{codegen::Options, compile, file_resolver::FileResolver, Target};
+use std::{
+    collections::{HashMap, HashSet},
+    ffi::OsStr,
+    fs::read_to_string,
+    io::Read,
+    path::{Path, PathBuf},
+};
+use tiny_keccak::{Hasher, Keccak};
+use walkdir::WalkDir;
+use wasm_host_attr::wasm_host;
+use wasmi::core::Trap;
+use wasmi::{Engine, Linker, Module, Store};
+
+mod stylus_emulator_tests;
+
+type Address = [u8; 20];
+type Bytes32 = [u8; 32];
+
+/// The account which sends all transactions
+const CALLER: Address = [0x11; 20];
+
+/// The ArbWasm precompile, which activates deployed programs
+const ARB_WASM: Address = {
+    let mut address = [0; 20];
+    address[19] = 0x71;
+    address
+};
+
+/// The precompile which recovers the signer of a message
+const ECRECOVER: Address = {
+    let mut address = [0; 20];
+    address[19] = 0x01;
+    address
+};
+
+/// Chain id of the Arbitrum devnode
+const CHAIN_ID: u64 = 412346;
+
+/// Prefix of compressed Stylus programs, as found in the account code
+const EOF_PREFIX_NO_DICT: [u8; 4] = [0xef, 0xf0, 0x00, 0x00];
+
+/// Length of the EVM prelude of the init code, including the version byte
+const DEPLOYMENT_PRELUDE_LEN: usize = 43;
+
+fn keccak256(data: &[u8]) -> Bytes32 {
+    let mut hasher = Keccak::v256();
+    let mut hash = [0u8; 32];
+
+    hasher.update(data);
+    hasher.finalize(&mut hash);
+
+    hash
+}
+
+/// The four byte selector of a function signature
+pub fn selector(signature: &str) -> [u8; 4] {
+    keccak256(signature.as_bytes())[..4].try_into().unwrap()
+}
+
+/// Address of a contract created with `create1`, which is derived from the RLP encoding of the
+/// sender and its nonce.
+fn create1_address(sender: &Address, nonce: u64) -> Address {
+    let nonce_bytes = nonce.to_be_bytes();
+    let nonce_bytes = &nonce_bytes[nonce.leading_zeros() as usize / 8..];
+
+    let mut payload = vec![0x80 + 20];
+    payload.extend_from_slice(sender);
+
+    match nonce_bytes {
+        [] => payload.push(0x80),
+        [b] if *b < 0x80 => payload.push(*b),
+        _ => {
+            payload.push(0x80 + nonce_bytes.len() as u8);
+            payload.extend_from_slice(nonce_bytes);
+        }
+    }
+
+    let mut rlp = vec![0xc0 + payload.len() as u8];
+    rlp.extend(payload);
+
+    keccak256(&rlp)[12..].try_into().unwrap()
+}
+
+/// Address of a contract created with `create2`
+fn create2_address(sender: &Address, salt: &Bytes32, init_code: &[u8]) -> Address {
+    let mut data = vec![0xff];
+    data.extend_from_slice(sender);
+    data.extend_from_slice(salt);
+    data.extend_from_slice(&keccak256(init_code));
+
+    keccak256(&data)[12..].try_into().unwrap()
+}
+
+/// Compress a wasm program the way it is stored on chain
+fn compress_program(wasm: &[u8]) -> Vec<u8> {
+    let mut code = EOF_PREFIX_NO_DICT.to_vec();
+
+    BrotliEncoder::new(wasm, 1).read_to_end(&mut code).unwrap();
+
+    code
+}
+
+/// Recover the wasm program from init code as produced by `contract_deployment_calldata`
+fn decompress_init_code(init_code: &[u8]) -> Option<(Vec<u8>, Vec<u8>)> {
+    if init_code.len() < DEPLOYMENT_PRELUDE_LEN || init_code[0] != 0x7f {
+        return None;
+    }
+
+    let code = &init_code[DEPLOYMENT_PRELUDE_LEN..];
+    let compressed = code.strip_prefix(&EOF_PREFIX_NO_DICT)?;
+
+    let mut wasm = Vec::new();
+    BrotliDecoder::new(compressed).read_to_end(&mut wasm).ok()?;
+
+    Some((code.to_vec(), wasm))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Log {
+    pub address: Address,
+    pub topics: Vec<Bytes32>,
+    pub data: Vec<u8>,
+}
+
+/// A deployed Stylus program
+#[derive(Clone)]
+struct Program {
+    /// The compressed program, as returned by `account_code`
+    code: Vec<u8>,
+    wasm: Vec<u8>,
+}
+
+#[derive(Default, Clone)]
+struct Account {
+    address: Address,
+    balance: u128,
+    nonce: u64,
+    program: Option<Program>,
+    storage: HashMap<Bytes32, Bytes32>,
+}
+
+/// The kind of call, which determines whose storage is used and whether state may change
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CallKind {
+    Call,
+    Delegate,
+    Static,
+}
+
+/// The runtime provides the state of the mocked chain during program execution.
+#[derive(Default, Clone)]
+struct Runtime {
+    accounts: Vec<Account>,
+    /// Index of the account whose storage, address and balance are used
+    account: usize,
+    /// Index of the account whose program is executed; differs from `account` for delegate calls
+    code_account: usize,
+    /// The `msg.sender` of the current frame
+    caller: Address,
+    /// The `msg.value` of the current frame
+    value: u128,
+    /// Set for static calls, in which the state may not be modified
+    read_only: bool,
+    /// Accounts of all frames entered so far, needed for `msg_reentrant`
+    call_stack: Vec<usize>,
+    /// Will hold the memory reference of the executing program
+    memory: Option<wasmi::Memory>,
+    input: Vec<u8>,
+    /// The data passed to `write_result`
+    output: Vec<u8>,
+    /// The return data of the last call or create
+    return_data: Vec<u8>,
+    transient_storage: HashMap<(usize, Bytes32), Bytes32>,
+    /// Code hashes of programs activated through the ArbWasm precompile; only those can be called
+    activated: HashSet<Bytes32>,
+    logs: Vec<Log>,
+    /// Combined output of all `log_txt` calls
+    debug_buffer: String,
+}
+
+/// The outcome of executing a program
+enum Outcome {
+    Success(Box<Runtime>),
+    Revert(Vec<u8>),
+}
+
+impl Runtime {
+    fn find_account(&self, address: &Address) -> Option<usize> {
+        self.accounts.iter().position(|a| &a.address == address)
+    }
+
+    fn find_or_create_account(&mut self, address: &Address) -> usize {
+        self.find_account(address).unwrap_or_else(|| {
+            self.accounts.push(Account {
+                address: *address,
+                ..Default::default()
+            });
+            self.accounts.len() - 1
+        })
+    }
+
+    /// Create the runtime context for a call into `account`, running the program of `code_account`.
+    fn new_context(
+        &self,
+        account: usize,
+        code_account: usize,
+        caller: Address,
+        input: Vec<u8>,
+        value: u128,
+        read_only: bool,
+    ) -> Self {
+        let mut runtime = self.clone();
+        runtime.account = account;
+        runtime.code_account = code_account;
+        runtime.caller = caller;
+        runtime.value = value;
+        runtime.read_only = read_only;
+        if account == code_account {
+            runtime.call_stack.push(account);
+        }
+        runtime.memory = None;
+        runtime.input = input;
+        runtime.output = Vec::new();
+        runtime.return_data = Vec::new();
+        runtime
+    }
+
+    /// After a successful execution, merge the state of the callee back.
+    fn accept_state(&mut self, callee: Self) {
+        self.accounts = callee.accounts;
+        self.transient_storage = callee.transient_storage;
+        self.activated = callee.activated;
+        self.logs = callee.logs;
+        self.debug_buffer = callee.debug_buffer;
+    }
+
+    /// Run the program of `self.code_account` to completion.
+    ///
+    /// Traps are returned as errors, together with the debug buffer written so far.
+    fn execute(self) -> Result<(i32, Self), (wasmi::Error, String)> {
+        let wasm = match &self.accounts[self.code_account].program {
+            Some(program) => program.wasm.clone(),
+            None => return Ok((0, self)),
+        };
+
+        let engine = Engine::default();
+        let module = Module::new(&engine, &mut &wasm[..]).map_err(|e| (e, String::new()))?;
+        let mut store = Store::new(&engine, self);
+        let mut linker = <Linker<Runtime>>::new(&engine);
+
+        Runtime::define(&mut store, &mut linker);
+
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .map_err(|e| (e, String::new()))?
+            .ensure_no_start(&mut store)
+            .expect("we never emit a start function");
+
+        let memory = instance
+            .get_memory(&store, "memory")
+            .expect("stylus programs export their memory");
+        store.data_mut().memory = Some(memory);
+
+        let entrypoint = instance
+            .get_typed_func::<i32, i32>(&store, "user_entrypoint")
+            .expect("stylus programs export user_entrypoint");
+
+        let args_len = store.data().input.len() as i32;
+
+        match entrypoint.call(&mut store, args_len) {
+            Ok(status) => Ok((status, store.into_data())),
+            Err(trap) => Err((trap.into(), store.data().debug_buffer.clone())),
+        }
+    }
+
+    /// Call the account at `callee` and execute its program, if any.
+    fn call(
+        &mut self,
+        kind: CallKind,
+        callee: &Address,
+        input: Vec<u8>,
+        value: u128,
+    ) -> Result<Outcome, wasmi::Error> {
+        println!(
+            "call: account={} input={} value={value}",
+            hex::encode(callee),
+            hex::encode(&input)
+        );
+
+        if callee == &ARB_WASM {
+            return Ok(self.arb_wasm(&input));
+        }
+
+        if callee == &ECRECOVER {
+            return Ok(self.ecrecover(&input));
+        }
+
+        let callee_index = self.find_or_create_account(callee);
+
+        if let Some(program) = &self.accounts[callee_index].program {
+            if !self.activated.contains(&keccak256(&program.code)) {
+                return Ok(Outcome::Revert(Vec::new()));
+            }
+        }
+
+        let context = match kind {
+            CallKind::Call | CallKind::Static => {
+                let caller = self.accounts[self.account].address;
+
+                if value > self.accounts[self.account].balance {
+                    return Ok(Outcome::Revert(Vec::new()));
+                }
+
+                let mut context = self.new_context(
+                    callee_index,
+                    callee_index,
+                    caller,
+                    input,
+                    value,
+                    self.read_only || kind == CallKind::Static,
+                );
+
+                context.accounts[self.account].balance -= value;
+                context.accounts[callee_index].balance += value;
+                context
+            }
+            CallKind::Delegate => self.new_context(
+                self.account,
+                callee_index,
+                self.caller,
+                input,
+                self.value,
+                self.read_only,
+            ),
+        };
+
+        match context.execute() {
+            Ok((0, callee)) => Ok(Outcome::Success(callee.into())),
+            Ok((_, callee)) => {
+                self.debug_buffer = callee.debug_buffer;
+                Ok(Outcome::Revert(callee.output))
+            }
+            Err((err, debug_buffer)) => {
+                self.debug_buffer = debug_buffer;
+                Err(err)
+            }
+        }
+    }
+
+    /// Deploy the program contained in `init_code` at `address`.
+    ///
+    /// Stylus init code only returns the compressed program; constructors are not run.
+    fn create(&mut self, address: &Address, init_code: &[u8], value: u128) -> Option<()> {
+        let (code, wasm) = decompress_init_code(init_code)?;
+
+        if value > self.accounts[self.account].balance {
+            return None;
+        }
+
+        let index = self.find_or_create_account(address);
+
+        if self.accounts[index].program.is_some() {
+            return None;
+        }
+
+        self.accounts[self.account].balance -= value;
+        self.accounts[index].balance += value;
+        self.accounts[index].program = Some(Program { code, wasm });
+
+        Some(())
+    }
+
+    /// Emulates `activateProgram(address)` of the ArbWasm precompile
+    fn arb_wasm(&mut self, input: &[u8]) -> Outcome {
+        if input.len() != 36 || input[..4] != selector("activateProgram(address)") {
+            return Outcome::Revert(Vec::new());
+        }
+
+        let address: Address = input[16..36].try_into().unwrap();
+
+        let codehash = self
+            .find_account(&address)
+            .and_then(|index| self.accounts[index].program.as_ref())
+            .map(|program| keccak256(&program.code));
+
+        // Activating a program twice reverts
+        match codehash {
+            Some(codehash) if self.activated.insert(codehash) => {
+                let mut state = self.clone();
+                state.output = ethabi::encode(&[Token::Uint(1.into()), Token::Uint(0.into())]);
+                Outcome::Success(state.into())
+            }
+            _ => Outcome::Revert(Vec::new()),
+        }
+    }
+
+    /// Emulates the ecrecover precompile, which returns nothing if the signature is invalid
+    fn ecrecover(&mut self, input: &[u8]) -> Outcome {
+        let mut input = input.to_vec();
+        input.resize(128, 0);
+
+        let v = U256::from_big_endian(&input[32..64]);
+
+        let recovered = (v == 27.into() || v == 28.into())
+            .then(|| k256::ecdsa::RecoveryId::from_byte(v.as_u32() as u8 - 27))
+            .flatten()
+            .zip(k256::ecdsa::Signature::from_slice(&input[64..128]).ok())
+            .and_then(|(recovery_id, signature)| {
+                k256::ecdsa::VerifyingKey::recover_from_prehash(
+                    &input[..32],
+                    &signature,
+                    recovery_id,
+                )
+                .ok()
+            });
+
+        let mut state = self.clone();
+
+        if let Some(key) = recovered {
+            let point = key.to_encoded_point(false);
+            let hash = keccak256(&point.as_bytes()[1..]);
+
+            state.output = [0; 12].to_vec();
+            state.output.extend_from_slice(&hash[12..]);
+        }
+
+        Outcome::Success(state.into())
+    }
+
+    /// Finish a call or create made by the executing program, returning the hostio status.
+    fn finish_call(&mut self, outcome: Outcome) -> u32 {
+        match outcome {
+            Outcome::Success(callee) => {
+                self.return_data = callee.output.clone();
+                self.accept_state(*callee);
+                0
+            }
+            Outcome::Revert(data) => {
+                self.return_data = data;
+                1
+            }
+        }
+    }
+
+    fn storage(&mut self) -> &mut HashMap<Bytes32, Bytes32> {
+        &mut self.accounts[self.account].storage
+    }
+
+    fn address(&self) -> Address {
+        self.accounts[self.account].address
+    }
+
+    fn balance_of(&self, address: &Address) -> u128 {
+        self.find_account(address)
+            .map(|index| self.accounts[index].balance)
+            .unwrap_or_default()
+    }
+
+    fn code_of(&self, address: &Address) -> Vec<u8> {
+        self.find_account(address)
+            .and_then(|index| self.accounts[index].program.as_ref())
+            .map(|program| program.code.clone())
+            .unwrap_or_default()
+    }
+
+    fn check_writable(&self) -> Result<(), Trap> {
+        if self.read_only {
+            return Err(Trap::new("state modification in static call"));
+        }
+        Ok(())
+    }
+}
+
+fn read_buf(mem: &[u8], ptr: u32, len: u32) -> Vec<u8> {
+    mem[ptr as usize..(ptr + len) as usize].to_vec()
+}
+
+fn write_buf(mem: &mut [u8], ptr: u32, buf: &[u8]) {
+    mem[ptr as usize..ptr as usize + buf.len()].copy_from_slice(buf);
+}
+
+fn read_address(mem: &[u8], ptr: u32) -> Address {
+    mem[ptr as usize..ptr as usize + 20].try_into().unwrap()
+}
+
+fn read_bytes32(mem: &[u8], ptr: u32) -> Bytes32 {
+    mem[ptr as usize..ptr as usize + 32].try_into().unwrap()
+}
+
+/// Values are passed to hostios as 32 big endian bytes
+fn read_u256(mem: &[u8], ptr: u32) -> U256 {
+    U256::from_big_endian(&mem[ptr as usize..ptr as usize + 32])
+}
+
+fn write_u256(mem: &mut [u8], ptr: u32, value: U256) {
+    value.to_big_endian(&mut mem[ptr as usize..ptr as usize + 32]);
+}
+
+fn read_value(mem: &[u8], ptr: u32) -> Result<u128, Trap> {
+    let value = read_u256(mem, ptr);
+
+    if value > U256::from(u128::MAX) {
+        return Err(Trap::new("value exceeds balance type"));
+    }
+
+    Ok(value.as_u128())
+}
+
+fn reduce(value: U512) -> U256 {
+    U256::try_from(value).unwrap()
+}
+
+#[wasm_host]
+impl Runtime {
+    #[host("vm_hooks")]
+    fn read_args(dest: u32) -> Result<(), Trap> {
+        write_buf(mem, dest, &vm.input);
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn write_result(data: u32, len: u32) -> Result<(), Trap> {
+        vm.output = read_buf(mem, data, len);
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn storage_load_bytes32(key: u32, dest: u32) -> Result<(), Trap> {
+        let key = read_bytes32(mem, key);
+        let value = vm.storage().get(&key).copied().unwrap_or_default();
+        write_buf(mem, dest, &value);
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn storage_cache_bytes32(key: u32, value: u32) -> Result<(), Trap> {
+        vm.check_writable()?;
+        let key = read_bytes32(mem, key);
+        let value = read_bytes32(mem, value);
+        if value == Bytes32::default() {
+            vm.storage().remove(&key);
+        } else {
+            vm.storage().insert(key, value);
+        }
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn storage_flush_cache(_clear: u32) -> Result<(), Trap> {
+        // Writes go straight into the account storage, so there is nothing to flush
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn transient_load_bytes32(key: u32, dest: u32) -> Result<(), Trap> {
+        let key = (vm.account, read_bytes32(mem, key));
+        let value = vm.transient_storage.get(&key).copied().unwrap_or_default();
+        write_buf(mem, dest, &value);
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn transient_store_bytes32(key: u32, value: u32) -> Result<(), Trap> {
+        vm.check_writable()?;
+        let key = (vm.account, read_bytes32(mem, key));
+        let value = read_bytes32(mem, value);
+        vm.transient_storage.insert(key, value);
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn emit_log(data: u32, len: u32, topics: u32) -> Result<(), Trap> {
+        vm.check_writable()?;
+        let buf = read_buf(mem, data, len);
+        let topics_len = topics as usize * 32;
+        if topics > 4 || topics_len > buf.len() {
+            return Err(Trap::new("bad emit_log"));
+        }
+        let log = Log {
+            address: vm.address(),
+            topics: buf[..topics_len]
+                .chunks(32)
+                .map(|topic| topic.try_into().unwrap())
+                .collect(),
+            data: buf[topics_len..].to_vec(),
+        };
+        vm.logs.push(log);
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn call_contract(
+        contract: u32,
+        calldata: u32,
+        calldata_len: u32,
+        value: u32,
+        _gas: u64,
+        return_data_len: u32,
+    ) -> Result<u32, Trap> {
+        let callee = read_address(mem, contract);
+        let input = read_buf(mem, calldata, calldata_len);
+        let value = read_value(mem, value)?;
+        if value != 0 {
+            vm.check_writable()?;
+        }
+        let outcome = vm.call(CallKind::Call, &callee, input, value);
+        let status = match outcome {
+            Ok(outcome) => vm.finish_call(outcome),
+            Err(_) => vm.finish_call(Outcome::Revert(Vec::new())),
+        };
+        write_buf(
+            mem,
+            return_data_len,
+            &(vm.return_data.len() as u32).to_le_bytes(),
+        );
+        Ok(status)
+    }
+
+    #[host("vm_hooks")]
+    fn delegate_call_contract(
+        contract: u32,
+        calldata: u32,
+        calldata_len: u32,
+        _gas: u64,
+        return_data_len: u32,
+    ) -> Result<u32, Trap> {
+        let callee = read_address(mem, contract);
+        let input = read_buf(mem, calldata, calldata_len);
+        let outcome = vm.call(CallKind::Delegate, &callee, input, 0);
+        let status = match outcome {
+            Ok(outcome) => vm.finish_call(outcome),
+            Err(_) => vm.finish_call(Outcome::Revert(Vec::new())),
+        };
+        write_buf(
+            mem,
+            return_data_len,
+            &(vm.return_data.len() as u32).to_le_bytes(),
+        );
+        Ok(status)
+    }
+
+    #[host("vm_hooks")]
+    fn static_call_contract(
+        contract: u32,
+        calldata: u32,
+        calldata_len: u32,
+        _gas: u64,
+        return_data_len: u32,
+    ) -> Result<u32, Trap> {
+        let callee = read_address(mem, contract);
+        let input = read_buf(mem, calldata, calldata_len);
+        let outcome = vm.call(CallKind::Static, &callee, input, 0);
+        let status = match outcome {
+            Ok(outcome) => vm.finish_call(outcome),
+            Err(_) => vm.finish_call(Outcome::Revert(Vec::new())),
+        };
+        write_buf(
+            mem,
+            return_data_len,
+            &(vm.return_data.len() as u32).to_le_bytes(),
+        );
+        Ok(status)
+    }
+
+    #[host("vm_hooks")]
+    fn return_data_size() -> Result<u32, Trap> {
+        Ok(vm.return_data.len() as u32)
+    }
+
+    #[host("vm_hooks")]
+    fn read_return_data(dest: u32, offset: u32, size: u32) -> Result<u32, Trap> {
+        let start = (offset as usize).min(vm.return_data.len());
+        let end = (start + size as usize).min(vm.return_data.len());
+        write_buf(mem, dest, &vm.return_data[start..end]);
+        Ok((end - start) as u32)
+    }
+
+    #[host("vm_hooks")]
+    fn create1(
+        code: u32,
+        code_len: u32,
+        endowment: u32,
+        contract: u32,
+        revert_data_len: u32,
+    ) -> Result<(), Trap> {
+        vm.check_writable()?;
+        let init_code = read_buf(mem, code, code_len);
+        let value = read_value(mem, endowment)?;
+        let sender = vm.address();
+        let nonce = vm.accounts[vm.account].nonce;
+        vm.accounts[vm.account].nonce += 1;
+        let address = create1_address(&sender, nonce);
+        let address = match vm.create(&address, &init_code, value) {
+            Some(()) => address,
+            None => Address::default(),
+        };
+        vm.return_data.clear();
+        write_buf(mem, contract, &address);
+        write_buf(mem, revert_data_len, &0u32.to_le_bytes());
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn create2(
+        code: u32,
+        code_len: u32,
+        endowment: u32,
+        salt: u32,
+        contract: u32,
+        revert_data_len: u32,
+    ) -> Result<(), Trap> {
+        vm.check_writable()?;
+        let init_code = read_buf(mem, code, code_len);
+        let value = read_value(mem, endowment)?;
+        let salt = read_bytes32(mem, salt);
+        let address = create2_address(&vm.address(), &salt, &init_code);
+        vm.accounts[vm.account].nonce += 1;
+        let address = match vm.create(&address, &init_code, value) {
+            Some(()) => address,
+            None => Address::default(),
+        };
+        vm.return_data.clear();
+        write_buf(mem, contract, &address);
+        write_buf(mem, revert_data_len, &0u32.to_le_bytes());
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn msg_value(dest: u32) -> Result<(), Trap> {
+        write_u256(mem, dest, vm.value.into());
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn msg_sender(dest: u32) -> Result<(), Trap> {
+        write_buf(mem, dest, &vm.caller);
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn msg_reentrant() -> Result<u32, Trap> {
+        let (current, outer) = vm.call_stack.split_last().unwrap();
+        Ok(outer.contains(current) as u32)
+    }
+
+    #[host("vm_hooks")]
+    fn contract_address(dest: u32) -> Result<(), Trap> {
+        write_buf(mem, dest, &vm.address());
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn account_balance(address: u32, dest: u32) -> Result<(), Trap> {
+        let address = read_address(mem, address);
+        write_u256(mem, dest, vm.balance_of(&address).into());
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn account_code(address: u32, offset: u32, size: u32, dest: u32) -> Result<u32, Trap> {
+        let code = vm.code_of(&read_address(mem, address));
+        let start = (offset as usize).min(code.len());
+        let end = (start + size as usize).min(code.len());
+        write_buf(mem, dest, &code[start..end]);
+        Ok((end - start) as u32)
+    }
+
+    #[host("vm_hooks")]
+    fn account_code_size(address: u32) -> Result<u32, Trap> {
+        Ok(vm.code_of(&read_address(mem, address)).len() as u32)
+    }
+
+    #[host("vm_hooks")]
+    fn account_codehash(address: u32, dest: u32) -> Result<(), Trap> {
+        let code = vm.code_of(&read_address(mem, address));
+        let hash = if code.is_empty() {
+            Bytes32::default()
+        } else {
+            keccak256(&code)
+        };
+        write_buf(mem, dest, &hash);
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn block_basefee(dest: u32) -> Result<(), Trap> {
+        write_u256(mem, dest, 100_000_000.into());
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn block_coinbase(dest: u32) -> Result<(), Trap> {
+        write_buf(mem, dest, &[0xa4; 20]);
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn block_gas_limit() -> Result<u64, Trap> {
+        Ok(1 << 50)
+    }
+
+    #[host("vm_hooks")]
+    fn block_number() -> Result<u64, Trap> {
+        Ok(1)
+    }
+
+    #[host("vm_hooks")]
+    fn block_timestamp() -> Result<u64, Trap> {
+        Ok(1_700_000_000)
+    }
+
+    #[host("vm_hooks")]
+    fn chainid() -> Result<u64, Trap> {
+        Ok(CHAIN_ID)
+    }
+
+    #[host("vm_hooks")]
+    fn evm_gas_left() -> Result<u64, Trap> {
+        Ok(30_000_000)
+    }
+
+    #[host("vm_hooks")]
+    fn tx_gas_price(dest: u32) -> Result<(), Trap> {
+        write_u256(mem, dest, 100_000_000.into());
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn tx_origin(dest: u32) -> Result<(), Trap> {
+        write_buf(mem, dest, &CALLER);
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn native_keccak256(bytes: u32, len: u32, output: u32) -> Result<(), Trap> {
+        let hash = keccak256(&read_buf(mem, bytes, len));
+        write_buf(mem, output, &hash);
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn pay_for_memory_grow(_pages: u32) -> Result<(), Trap> {
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn math_div(value: u32, divisor: u32) -> Result<(), Trap> {
+        let divisor = read_u256(mem, divisor);
+        let result = if divisor.is_zero() {
+            U256::zero()
+        } else {
+            read_u256(mem, value) / divisor
+        };
+        write_u256(mem, value, result);
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn math_mod(value: u32, modulus: u32) -> Result<(), Trap> {
+        let modulus = read_u256(mem, modulus);
+        let result = if modulus.is_zero() {
+            U256::zero()
+        } else {
+            read_u256(mem, value) % modulus
+        };
+        write_u256(mem, value, result);
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn math_pow(value: u32, exponent: u32) -> Result<(), Trap> {
+        let (result, _) = read_u256(mem, value).overflowing_pow(read_u256(mem, exponent));
+        write_u256(mem, value, result);
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn math_add_mod(value: u32, addend: u32, modulus: u32) -> Result<(), Trap> {
+        let modulus = read_u256(mem, modulus);
+        let result = if modulus.is_zero() {
+            U256::zero()
+        } else {
+            let sum = U512::from(read_u256(mem, value)) + U512::from(read_u256(mem, addend));
+            reduce(sum % U512::from(modulus))
+        };
+        write_u256(mem, value, result);
+        Ok(())
+    }
+
+    #[host("vm_hooks")]
+    fn math_mul_mod(value: u32, multiplier: u32, modulus: u32) -> Result<(), Trap> {
+        let modulus = read_u256(mem, modulus);
+        let result = if modulus.is_zero() {
+            U256::zero()
+        } else {
+            let product = read_u256(mem, value).full_mul(read_u256(mem, multiplier));
+            reduce(product % U512::from(modulus))
+        };
+        write_u256(mem, value, result);
+        Ok(())
+    }
+
+    #[host("console")]
+    fn log_txt(text: u32, len: u32) -> Result<(), Trap> {
+        let text = read_buf(mem, text, len);
+        vm.debug_buffer.push_str(&String::from_utf8_lossy(&text));
+        vm.debug_buffer.push('\n');
+        Ok(())
+    }
+}
+
+/// Deploys and calls contracts compiled for the Stylus target, entirely in-process
+pub struct MockStylus {
+    runtime: Runtime,
+    /// Names and wasm programs of every instantiable contract, in source order
+    programs: Vec<(String, Vec<u8>)>,
+    /// Address of the contract which was deployed last
+    pub address: Address,
+    /// Value sent with the next transaction
+    pub value: u128,
+}
+
+/// Compile the source and deploy the last contract. Constructors are not run on Stylus.
+pub fn build_solidity(src: &str) -> MockStylus {
+    let mut vm = compile_solidity(src);
+
+    vm.deploy(vm.programs.len() - 1);
+
+    vm
+}
+
+/// Compile the source without deploying anything
+pub fn compile_solidity(src: &str) -> MockStylus {
+    let mut cache = FileResolver::default();
+
+    cache.set_file_contents("test.sol", src.to_string());
+
+    let programs = compile_programs("test.sol", &mut cache);
+
+    assert!(!programs.is_empty());
+
+    MockStylus::new(programs)
+}
+
+/// Compile a file of this repository, which may import other files relative to itself
+pub fn compile_file(path: &str) -> MockStylus {
+    let mut vm = MockStylus::new(Vec::new());
+
+    vm.add_file(path);
+
+    vm
+}
+
+/// Compile a file and return the programs of its instantiable contracts, or nothing if there
+/// were errors.
+fn compile_programs(filename: &str, cache: &mut FileResolver) -> Vec<(String, Vec<u8>)> {
+    let (contracts, ns) = compile(
+        OsStr::new(filename),
+        cache,
+        Target::Stylus,
+        &Options {
+            log_runtime_errors: true,
+            ..Default::default()
+        },
+        vec!["unknown".to_string()],
+        "0.0.1",
+    );
+
+    ns.print_diagnostics_in_plain(cache, false);
+
+    // compile() returns the instantiable contracts in the same order
+    ns.contracts
+        .iter()
+        .filter(|contract| contract.instantiable)
+        .map(|contract| contract.id.name.clone())
+        .zip(contracts.into_iter().map(|(code, _)| code))
+        .collect()
+}
+
+impl MockStylus {
+    fn new(programs: Vec<(String, Vec<u8>)>) -> Self {
+        let runtime = Runtime {
+            accounts: vec![Account {
+                address: CALLER,
+                balance: u128::MAX,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        MockStylus {
+            runtime,
+            programs,
+            address: Address::default(),
+            value: 0,
+        }
+    }
+
+    /// Compile another file of this repository, so that its contracts can be deployed too
+    pub fn add_file(&mut self, path: &str) {
+        let mut cache = FileResolver::default();
+
+        cache.add_import_path(Path::new(env!("CARGO_MANIFEST_DIR")));
+
+        let programs = compile_programs(path, &mut cache);
+
+        assert!(!programs.is_empty(), "{path} failed to compile");
+
+        self.programs.extend(programs);
+    }
+
+    /// Deploy and activate the contract with the given index, and make it the callee of
+    /// subsequent calls.
+    pub fn deploy(&mut self, contract_no: usize) -> Address {
+        let wasm = self.programs[contract_no].1.clone();
+
+        self.deploy_program(wasm, true)
+    }
+
+    /// Deploy the contract with the given name, and make it the callee of subsequent calls.
+    /// Programs which are not activated revert when called.
+    pub fn deploy_contract(&mut self, name: &str, activate: bool) -> Address {
+        let wasm = self
+            .programs
+            .iter()
+            .find(|(contract, _)| contract == name)
+            .map(|(_, wasm)| wasm.clone())
+            .unwrap_or_else(|| panic!("contract {name} not found"));
+
+        self.deploy_program(wasm, activate)
+    }
+
+    fn deploy_program(&mut self, wasm: Vec<u8>, activate: bool) -> Address {
+        let code = compress_program(&wasm);
+        let caller = &mut self.runtime.accounts[0];
+        let address = create1_address(&caller.address, caller.nonce);
+
+        caller.nonce += 1;
+
+        if activate {
+            self.runtime.activated.insert(keccak256(&code));
+        }
+        self.runtime.accounts.push(Account {
+            address,
+            program: Some(Program { code, wasm }),
+            ..Default::default()
+        });
+
+        self.address = address;
+
+        address
+    }
+
+    /// Call a function and return its return data, which is expected to succeed
+    pub fn function(&mut self, signature: &str, args: &[Token]) -> Vec<u8> {
+        match self.call(signature, args) {
+            Ok(data) => data,
+            Err(data) => panic!("{signature} reverted with {}", hex::encode(data)),
+        }
+    }
+
+    /// Call a function which is expected to revert, and return the revert data
+    pub fn function_revert(&mut self, signature: &str, args: &[Token]) -> Vec<u8> {
+        match self.call(signature, args) {
+            Ok(data) => panic!("{signature} returned {}", hex::encode(data)),
+            Err(data) => data,
+        }
+    }
+
+    /// Call a function and decode its return values
+    pub fn function_returns(
+        &mut self,
+        signature: &str,
+        args: &[Token],
+        returns: &[ethabi::ParamType],
+    ) -> Vec<Token> {
+        let data = self.function(signature, args);
+
+        ethabi::decode(returns, &data).unwrap()
+    }
+
+    /// Call a function with the given arguments
+    pub fn call(&mut self, signature: &str, args: &[Token]) -> Result<Vec<u8>, Vec<u8>> {
+        let mut data = selector(signature).to_vec();
+        data.extend(ethabi::encode(args));
+
+        self.raw_call(data)
+    }
+
+    /// Call the deployed contract with raw call data, as a transaction from the caller account.
+    ///
+    /// Panics if the program traps.
+    pub fn raw_call(&mut self, data: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
+        match self.transact(data) {
+            Ok(result) => result,
+            Err(trap) => panic!("trap: {trap}\n{}", self.runtime.debug_buffer),
+        }
+    }
+
+    /// Like [`MockStylus::raw_call`], but traps are returned as errors
+    pub fn transact(&mut self, data: Vec<u8>) -> Result<Result<Vec<u8>, Vec<u8>>, wasmi::Error> {
+        let value = std::mem::take(&mut self.value);
+        let runtime = &mut self.runtime;
+
+        runtime.account = 0;
+        runtime.call_stack.clear();
+        runtime.transient_storage.clear();
+        runtime.logs.clear();
+        runtime.debug_buffer.clear();
+
+        match runtime.call(CallKind::Call, &self.address, data, value)? {
+            Outcome::Success(callee) => {
+                let output = callee.output.clone();
+                runtime.accept_state(*callee);
+                Ok(Ok(output))
+            }
+            Outcome::Revert(data) => Ok(Err(data)),
+        }
+    }
+
+    /// The balance of an account
+    pub fn balance(&self, address: &Address) -> u128 {
+        self.runtime.balance_of(address)
+    }
+
+    /// Set the balance of an account
+    pub fn set_balance(&mut self, address: &Address, balance: u128) {
+        let index = self.runtime.find_or_create_account(address);
+
+        self.runtime.accounts[index].balance = balance;
+    }
+
+    /// The storage of the deployed contract
+    pub fn storage(&self) -> &HashMap<Bytes32, Bytes32> {
+        let index = self.runtime.find_account(&self.address).unwrap();
+
+        &self.runtime.accounts[index].storage
+    }
+
+    /// Logs emitted by the last transaction
+    pub fn logs(&self) -> &[Log] {
+        &self.runtime.logs
+    }
+
+    /// Combined `log_txt` output of the last transaction
+    pub fn debug_buffer(&self) -> &str {
+        &self.runtime.debug_buffer
+    }
+
+    /// Whether a contract exists and has been activated at the given address
+    pub fn is_activated(&self, address: &Address) -> bool {
+        self.runtime
+            .find_account(address)
+            .and_then(|index| self.runtime.accounts[index].program.as_ref())
+            .is_some_and(|program| self.runtime.activated.contains(&keccak256(&program.code)))
+    }
+}
+
+/// Compile the solc semantic tests which contain all the required words and none of the
+/// forbidden ones, and call each of their functions which take no arguments. Set `TESTNAME` to
+/// run a single test file.
+fn tests(required_forbidden_pairs: &[(&[&str], &[&str])]) {
+    let required_forbidden_pairs = required_forbidden_pairs
+        .iter()
+        .map(|(required, forbidden)| {
+            let required = required
+                .iter()
+                .map(|s| Regex::new(&format!(r"\<{s}\>")).unwrap())
+                .collect::<Vec<_>>();
+            let forbidden = std::iter::once("assembly")
+                .chain(forbidden.iter().copied())
+                .map(|s| Regex::new(&format!(r"\<{s}\>")).unwrap())
+                .collect::<Vec<_>>();
+            (required, forbidden)
+        })
+        .collect::<Vec<_>>();
+    let contract_re = Regex::new(r"\<contract ([A-Za-z_0-9]+)\>").unwrap();
+    let argless_function_re = Regex::new(r"\<function ([A-Za-z_0-9]+)\(\)").unwrap();
+    let mut paths = Vec::new();
+    if let Some(testname) = std::env::var_os("TESTNAME") {
+        paths.push(PathBuf::from(testname))
+    } else {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("testdata/solidity/test/libsolidity/semanticTests");
+        for result in WalkDir::new(dir) {
+            let entry = result.unwrap();
+            let path = entry.path();
+            if !path.is_file() || path.extension() != Some(OsStr::new("sol")) {
+                continue;
+            }
+            let contents = read_to_string(path).unwrap();
+            if !required_forbidden_pairs
+                .iter()
+                .any(|(required, forbidden)| {
+                    required.iter().all(|re| re.is_match(&contents))
+                        && !forbidden.iter().any(|re| re.is_match(&contents))
+                })
+            {
+                continue;
+            }
+            paths.push(path.to_path_buf());
+        }
+    }
+    let mut successes = Vec::new();
+    let mut failures = Vec::new();
+    for path in &paths {
+        let contents = read_to_string(path).unwrap();
+        let contracts = contract_re
+            .captures_iter(&contents)
+            .map(|captures| captures.get(1).unwrap().as_str())
+            .collect::<Vec<_>>();
+        let [contract] = contracts[..] else {
+            eprintln!(
+                "Skipping `{}` as it contains {} contracts",
+                path.display(),
+                contracts.len()
+            );
+            continue;
+        };
+        let argless_functions = argless_function_re
+            .captures_iter(&contents)
+            .map(|captures| captures.get(1).unwrap().as_str())
+            .collect::<Vec<_>>();
+        if argless_functions.is_empty() {
+            eprintln!(
+                "Skipping `{}` as it contains no argless functions",
+                path.display(),
+            );
+            continue;
+        }
+
+        eprintln!("Deploying `{}`", path.display());
+
+        let mut cache = FileResolver::default();
+        cache.set_file_contents("test.sol", contents.clone());
+
+        let programs = compile_programs("test.sol", &mut cache);
+        if programs.is_empty() {
+            eprintln!("Failed to compile `{}`", path.display());
+            continue;
+        }
+
+        let mut vm = MockStylus::new(programs);
+        vm.deploy_contract(contract, true);
+
+        for function in argless_functions {
+            eprintln!("Testing `{function}`");
+            match vm.transact(selector(&format!("{function}()")).to_vec()) {
+                Ok(Ok(_)) => successes.push((path.clone(), function.to_owned())),
+                Ok(Err(data)) => failures.push((
+                    path.clone(),
+                    function.to_owned(),
+                    format!("reverted with {}", hex::encode(data)),
+                )),
+                Err(trap) => failures.push((path.clone(), function.to_owned(), trap.to_string())),
+            }
+        }
+    }
+    eprintln!("Successes: {successes:#?}");
+    eprintln!("Failures: {failures:#?}");
+    assert!(failures.is_empty());
+}
@@ -0,0 +1,42 @@
+#![warn(clippy::pedantic)]
+
+use crate::{compile_file, ARB_WASM};
+use ethabi::{ParamType, Token};
+
+#[test]
+fn arb_wasm() {
+    let mut vm = compile_file("integration/stylus/arb_wasm.sol");
+
+    let address_d = vm.deploy_contract("D", false);
+
+    // `D` has not been activated yet, so it cannot be called
+    vm.function_revert("greet()", &[]);
+
+    let address_c = vm.deploy_contract("C", true);
+
+    vm.value = 1_000_000_000_000_000_000;
+    let ret = vm.function_returns(
+        "forwardActivateProgram(address,address)",
+        &[
+            Token::Address(ARB_WASM.into()),
+            Token::Address(address_d.into()),
+        ],
+        &[ParamType::Uint(16), ParamType::Uint(256)],
+    );
+    println!("{}", vm.debug_buffer());
+    assert_eq!(ret, vec![Token::Uint(1.into()), Token::Uint(0.into())]);
+
+    vm.address = address_d;
+    vm.function("greet()", &[]);
+    assert_eq!("Hello!\n", vm.debug_buffer());
+
+    // Activating a program twice reverts
+    vm.address = address_c;
+    vm.function_revert(
+        "forwardActivateProgram(address,address)",
+        &[
+            Token::Address(ARB_WASM.into()),
+            Token::Address(address_d.into()),
+        ],
+    );
+}
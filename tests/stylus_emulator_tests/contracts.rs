@@ -0,0 +1,233 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{build_solidity, keccak256, selector, CALLER};
+use ethabi::{ParamType, Token};
+
+fn uint(v: u64) -> Token {
+    Token::Uint(v.into())
+}
+
+fn address(a: [u8; 20]) -> Token {
+    Token::Address(a.into())
+}
+
+fn error(message: &str) -> Vec<u8> {
+    let mut data = selector("Error(string)").to_vec();
+    data.extend(ethabi::encode(&[Token::String(message.into())]));
+    data
+}
+
+fn slot(digest: &str) -> [u8; 32] {
+    hex::decode(digest).unwrap().try_into().unwrap()
+}
+
+#[test]
+fn mapping_layout() {
+    let mut vm = build_solidity(
+        r#"
+        contract C {
+            mapping(uint256 => uint256) m;
+            mapping(address => mapping(uint8 => uint256)) n;
+            uint256[] a;
+
+            function set(address who, uint256 k, uint256 v) public {
+                m[k] = v;
+                n[who][3] = v;
+                a.push(1);
+                a.push(v);
+            }
+        }"#,
+    );
+
+    vm.function(
+        "set(address,uint256,uint256)",
+        &[address([0xa1; 20]), uint(7), uint(42)],
+    );
+
+    // the slots are the ones solc uses: keccak256(abi.encode(7, 0)),
+    // keccak256(abi.encode(3, keccak256(abi.encode(address(0xa1..a1), 1)))) and
+    // uint256(keccak256(abi.encode(2))) + 1
+    for expected in [
+        "870253054e3d98b71abec8fff9ebf8a15d167f15909091a800d4acaab9266d2b",
+        "cc898675784a1f5352d976c6d63d81aa69f853d862a58bf88aa469ab9ff5af88",
+        "405787fa12a823e0f2b7631cc41b3ba8828b3321ca811111fa75cd3aa3bb5acf",
+    ] {
+        assert!(vm.storage().contains_key(&slot(expected)), "{expected}");
+    }
+
+    // the length of the array is in its own slot
+    let mut length_slot = [0u8; 32];
+    length_slot[31] = 2;
+
+    assert!(vm.storage().contains_key(&length_slot));
+}
+
+#[test]
+fn erc721() {
+    let mut vm = build_solidity(include_str!("../../integration/stylus/erc721.sol"));
+
+    let alice = [0xa1; 20];
+
+    vm.function(
+        "initialize(string,string)",
+        &[Token::String("Tokens".into()), Token::String("TOK".into())],
+    );
+
+    let ret = vm.function_returns("name()", &[], &[ParamType::String]);
+    assert_eq!(ret, vec![Token::String("Tokens".into())]);
+
+    vm.function(
+        "mint(address,uint256,string)",
+        &[address(CALLER), uint(1), Token::String("ipfs://1".into())],
+    );
+    vm.function(
+        "mint(address,uint256,string)",
+        &[address(CALLER), uint(2), Token::String("ipfs://2".into())],
+    );
+
+    let data = vm.function_revert(
+        "mint(address,uint256,string)",
+        &[address(alice), uint(2), Token::String(String::new())],
+    );
+    assert_eq!(data, error("already minted"));
+
+    let ret = vm.function_returns(
+        "balanceOf(address)",
+        &[address(CALLER)],
+        &[ParamType::Uint(256)],
+    );
+    assert_eq!(ret, vec![uint(2)]);
+
+    let ret = vm.function_returns("tokenURI(uint256)", &[uint(2)], &[ParamType::String]);
+    assert_eq!(ret, vec![Token::String("ipfs://2".into())]);
+
+    vm.function("approve(address,uint256)", &[address(alice), uint(1)]);
+
+    let ret = vm.function_returns("getApproved(uint256)", &[uint(1)], &[ParamType::Address]);
+    assert_eq!(ret, vec![address(alice)]);
+
+    vm.function(
+        "transferFrom(address,address,uint256)",
+        &[address(CALLER), address(alice), uint(1)],
+    );
+
+    let logs = vm.logs();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(
+        logs[0].topics[0],
+        keccak256(b"Transfer(address,address,uint256)")
+    );
+    assert_eq!(logs[0].topics[2][12..], alice);
+
+    let ret = vm.function_returns("ownerOf(uint256)", &[uint(1)], &[ParamType::Address]);
+    assert_eq!(ret, vec![address(alice)]);
+
+    // the approval is cleared by the transfer
+    let ret = vm.function_returns("getApproved(uint256)", &[uint(1)], &[ParamType::Address]);
+    assert_eq!(ret, vec![address([0; 20])]);
+
+    let ret = vm.function_returns(
+        "balanceOf(address)",
+        &[address(alice)],
+        &[ParamType::Uint(256)],
+    );
+    assert_eq!(ret, vec![uint(1)]);
+
+    // the token now belongs to alice
+    let data = vm.function_revert(
+        "transferFrom(address,address,uint256)",
+        &[address(alice), address(CALLER), uint(1)],
+    );
+    assert_eq!(data, error("not authorized"));
+
+    vm.function(
+        "setApprovalForAll(address,bool)",
+        &[address(alice), Token::Bool(true)],
+    );
+
+    let ret = vm.function_returns(
+        "isApprovedForAll(address,address)",
+        &[address(CALLER), address(alice)],
+        &[ParamType::Bool],
+    );
+    assert_eq!(ret, vec![Token::Bool(true)]);
+
+    vm.function("burn(uint256)", &[uint(2)]);
+
+    let data = vm.function_revert("ownerOf(uint256)", &[uint(2)]);
+    assert_eq!(data, error("nonexistent token"));
+
+    let ret = vm.function_returns(
+        "balanceOf(address)",
+        &[address(CALLER)],
+        &[ParamType::Uint(256)],
+    );
+    assert_eq!(ret, vec![uint(0)]);
+}
+
+#[test]
+fn order_book() {
+    let mut vm = build_solidity(include_str!("../../integration/stylus/order_book.sol"));
+
+    let place = "place(bool,uint64,uint64)";
+
+    // bids
+    vm.function(place, &[Token::Bool(true), uint(100), uint(10)]);
+    vm.function(place, &[Token::Bool(true), uint(105), uint(5)]);
+    // asks
+    vm.function(place, &[Token::Bool(false), uint(110), uint(7)]);
+    vm.function(place, &[Token::Bool(false), uint(102), uint(8)]);
+
+    let ret = vm.function_returns(
+        "depth()",
+        &[],
+        &[ParamType::Uint(256), ParamType::Uint(256)],
+    );
+    assert_eq!(ret, vec![uint(2), uint(2)]);
+
+    vm.function_revert(place, &[Token::Bool(true), uint(100), uint(0)]);
+
+    // 105 crosses 102 for 5, then 100 no longer crosses 102
+    let ret = vm.function_returns("matchOrders()", &[], &[ParamType::Uint(64)]);
+    assert_eq!(ret, vec![uint(5)]);
+
+    let ret = vm.function_returns(
+        "depth()",
+        &[],
+        &[ParamType::Uint(256), ParamType::Uint(256)],
+    );
+    assert_eq!(ret, vec![uint(1), uint(2)]);
+
+    let ret = vm.function_returns(
+        "order(uint256)",
+        &[uint(3)],
+        &[ParamType::Address, ParamType::Uint(64), ParamType::Uint(64)],
+    );
+    assert_eq!(ret, vec![address(CALLER), uint(102), uint(3)]);
+
+    vm.function("cancel(uint256)", &[uint(3)]);
+
+    let ret = vm.function_returns(
+        "depth()",
+        &[],
+        &[ParamType::Uint(256), ParamType::Uint(256)],
+    );
+    assert_eq!(ret, vec![uint(1), uint(1)]);
+
+    let ret = vm.function_returns(
+        "ordersOf(address)",
+        &[address(CALLER)],
+        &[ParamType::Array(Box::new(ParamType::Uint(256)))],
+    );
+    assert_eq!(
+        ret,
+        vec![Token::Array(vec![uint(0), uint(1), uint(2), uint(3)])]
+    );
+
+    let ret = vm.function_returns(
+        "filledOf(address)",
+        &[address(CALLER)],
+        &[ParamType::Uint(64)],
+    );
+    assert_eq!(ret, vec![uint(10)]);
+}
@@ -0,0 +1,28 @@
+//! This test is based on the following tutorial:
+//! <https://stylus-by-example.org/getting_started/using_the_cli>
+#![warn(clippy::pedantic)]
+
+use crate::compile_file;
+use ethabi::{ParamType, Token};
+
+#[test]
+fn counter() {
+    let mut vm = compile_file("integration/stylus/counter.sol");
+    vm.deploy_contract("Counter", true);
+
+    let ret = vm.function_returns("number()", &[], &[ParamType::Uint(256)]);
+    assert_eq!(ret, vec![Token::Uint(0.into())]);
+
+    vm.function("increment()", &[]);
+
+    let ret = vm.function_returns("number()", &[], &[ParamType::Uint(256)]);
+    assert_eq!(ret, vec![Token::Uint(1.into())]);
+
+    vm.function("setNumber(uint256)", &[Token::Uint(5.into())]);
+
+    let ret = vm.function_returns("number()", &[], &[ParamType::Uint(256)]);
+    assert_eq!(ret, vec![Token::Uint(5.into())]);
+
+    // unknown selector
+    assert!(vm.raw_call(vec![1, 2, 3, 4]).is_err());
+}
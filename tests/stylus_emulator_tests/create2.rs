@@ -0,0 +1,16 @@
+#![warn(clippy::pedantic)]
+
+use crate::compile_file;
+
+#[test]
+fn create2() {
+    let mut vm = compile_file("integration/stylus/create2.sol");
+    vm.deploy_contract("C", true);
+
+    vm.value = 1_000_000_000_000_000_000;
+    vm.function("test_create2()", &[]);
+    println!("{}", vm.debug_buffer());
+
+    // smoelius: The greeter created with `new` and the two created with `create2` all greet.
+    assert_eq!(3, vm.debug_buffer().matches("Greetings from 0x").count());
+}
@@ -0,0 +1,188 @@
+#![warn(clippy::pedantic)]
+
+// smoelius: This test mimics the initial steps of the interaction described here:
+// https://github.com/sky-ecosystem/sai?tab=readme-ov-file#sample-interaction-using-sai
+
+use crate::{compile_file, Address, MockStylus, CALLER};
+use ethabi::{ParamType, Token, Uint};
+
+const CHAIN_ID: u64 = 42161;
+
+const GOLD: &[u8; 4] = b"gold";
+const SPOT: &[u8; 4] = b"spot";
+const LINE_LOWER: &[u8; 4] = b"line";
+const LINE_UPPER: &[u8; 4] = b"Line";
+
+#[test]
+fn dss() {
+    let mut vm = compile_file("integration/stylus/Uniswap/test/ERC20.sol");
+    vm.add_file("integration/stylus/dss/vat.sol");
+    vm.add_file("integration/stylus/dss/dai.sol");
+    vm.add_file("integration/stylus/dss/join.sol");
+
+    let [erc20, vat, dai, gem_join, dai_join] = deploy_contracts(&mut vm);
+
+    configure_contracts(&mut vm, vat, dai, gem_join, dai_join);
+
+    // smoelius: Join the system by exchanging some ERC20.
+    vm.address = erc20;
+    vm.function(
+        "approve(address,uint256)",
+        &[address(gem_join), uint("2200000000000000000")],
+    );
+
+    vm.address = gem_join;
+    vm.function(
+        "join(address,uint256)",
+        &[address(CALLER), uint("2200000000000000000")],
+    );
+
+    // smoelius: Check that our ERC20 balance went down.
+    vm.address = erc20;
+    let ret = vm.function_returns(
+        "balanceOf(address)",
+        &[address(CALLER)],
+        &[ParamType::Uint(256)],
+    );
+    assert_eq!(ret, vec![uint("999997800000000000000000")]);
+
+    // smoelius: Check that the ERC20 is now held by the `Vat`.
+    vm.address = vat;
+    let ret = vm.function_returns(
+        "gem(bytes32,address)",
+        &[ilk(GOLD), address(CALLER)],
+        &[ParamType::Uint(256)],
+    );
+    assert_eq!(ret, vec![uint("2200000000000000000")]);
+
+    // smoelius: Lock the ERC20.
+    vm.function(
+        "frob(bytes32,address,address,address,int256,int256)",
+        &[
+            ilk(GOLD),
+            address(CALLER),
+            address(CALLER),
+            address(CALLER),
+            Token::Int(Uint::from_dec_str("1500000000000000000").unwrap()),
+            Token::Int(Uint::from_dec_str("89000000000000000000").unwrap()),
+        ],
+    );
+
+    // smoelius: Check our internal DAI balance.
+    let ret = vm.function_returns("dai(address)", &[address(CALLER)], &[ParamType::Uint(256)]);
+    assert_eq!(
+        ret,
+        vec![uint("89000000000000000000000000000000000000000000000")]
+    );
+
+    // smoelius: Convert the ERC20 to DAI.
+    vm.address = dai_join;
+    vm.function(
+        "exit(address,uint256)",
+        &[address(CALLER), uint("89000000000000000000")],
+    );
+
+    // smoelius: Check our external DAI balance.
+    vm.address = dai;
+    let ret = vm.function_returns(
+        "balanceOf(address)",
+        &[address(CALLER)],
+        &[ParamType::Uint(256)],
+    );
+    assert_eq!(ret, vec![uint("89000000000000000000")]);
+}
+
+fn deploy_contracts(vm: &mut MockStylus) -> [Address; 5] {
+    let erc20 = vm.deploy_contract("ERC20", true);
+    vm.function("initialize(uint256)", &[uint(INITIAL_BALANCE)]);
+
+    let ret = vm.function_returns("totalSupply()", &[], &[ParamType::Uint(256)]);
+    assert_eq!(ret, vec![uint(INITIAL_BALANCE)]);
+
+    let vat = vm.deploy_contract("Vat", true);
+    vm.function("initialize()", &[]);
+
+    let dai = vm.deploy_contract("Dai", true);
+    vm.function("initialize(uint256)", &[Token::Uint(CHAIN_ID.into())]);
+
+    let gem_join = vm.deploy_contract("GemJoin", true);
+    vm.function(
+        "initialize(address,bytes32,address)",
+        &[address(vat), ilk(GOLD), address(erc20)],
+    );
+
+    let dai_join = vm.deploy_contract("DaiJoin", true);
+    vm.function("initialize(address,address)", &[address(vat), address(dai)]);
+
+    [erc20, vat, dai, gem_join, dai_join]
+}
+
+const INITIAL_BALANCE: &str = concat!("1000000", "000000000000000000");
+
+fn configure_contracts(
+    vm: &mut MockStylus,
+    vat: Address,
+    dai: Address,
+    gem_join: Address,
+    dai_join: Address,
+) {
+    vm.address = vat;
+
+    // smoelius: Allow `GOLD` to be an ilk.
+    vm.function("init(bytes32)", &[ilk(GOLD)]);
+
+    // smoelius: Set `GOLD`'s spot price.
+    vm.function(
+        "file(bytes32,bytes32,uint256)",
+        &[
+            ilk(GOLD),
+            ilk(SPOT),
+            uint("1000000000000000000000000000000000000000000000"),
+        ],
+    );
+
+    // smoelius: Set `GOLD`'s debt ceiling.
+    vm.function(
+        "file(bytes32,bytes32,uint256)",
+        &[
+            ilk(GOLD),
+            ilk(LINE_LOWER),
+            uint("100000000000000000000000000000000000000000000000000000"),
+        ],
+    );
+
+    // smoelius: Set the global debt ceiling.
+    vm.function(
+        "file(bytes32,uint256)",
+        &[
+            ilk(LINE_UPPER),
+            uint("100000000000000000000000000000000000000000000000000000"),
+        ],
+    );
+
+    // smoelius: Authorize `gem_join` to call `slip` on the `Vat`.
+    vm.function("rely(address)", &[address(gem_join)]);
+
+    // smoelius: Authorize `dai_join` to `mint` DAI tokens.
+    vm.address = dai;
+    vm.function("rely(address)", &[address(dai_join)]);
+
+    // smoelius: Authorize `dai_join` to call `move` on the `Vat`.
+    vm.address = vat;
+    vm.function("hope(address)", &[address(dai_join)]);
+}
+
+fn uint(value: &str) -> Token {
+    Token::Uint(Uint::from_dec_str(value).unwrap())
+}
+
+fn address(address: Address) -> Token {
+    Token::Address(address.into())
+}
+
+/// A short string, left aligned in a `bytes32`
+fn ilk(name: &[u8]) -> Token {
+    let mut bytes = vec![0; 32];
+    bytes[..name.len()].copy_from_slice(name);
+    Token::FixedBytes(bytes)
+}
@@ -0,0 +1,12 @@
+#![warn(clippy::pedantic)]
+
+use crate::compile_file;
+
+#[test]
+fn ecrecover() {
+    let mut vm = compile_file("integration/stylus/ecrecover.sol");
+    vm.deploy_contract("C", true);
+
+    vm.function("test_ecrecover()", &[]);
+    println!("{}", vm.debug_buffer());
+}
@@ -0,0 +1,23 @@
+#![warn(clippy::pedantic)]
+
+use crate::compile_file;
+use ethabi::{ParamType, Token, Uint};
+
+#[test]
+fn erc20() {
+    let mut vm = compile_file("integration/stylus/Uniswap/test/ERC20.sol");
+    vm.deploy_contract("ERC20", true);
+
+    let balance = Uint::from_dec_str(concat!("1000000", "000000000000000000")).unwrap();
+
+    let ret = vm.function_returns("totalSupply()", &[], &[ParamType::Uint(256)]);
+    assert_eq!(ret, vec![Token::Uint(0.into())]);
+
+    vm.function("initialize(uint256)", &[Token::Uint(balance)]);
+
+    // smoelius: Calling `initialize` a second time should revert.
+    vm.function_revert("initialize(uint256)", &[Token::Uint(balance)]);
+
+    let ret = vm.function_returns("totalSupply()", &[], &[ParamType::Uint(256)]);
+    assert_eq!(ret, vec![Token::Uint(balance)]);
+}
@@ -0,0 +1,15 @@
+#![warn(clippy::pedantic)]
+
+use crate::compile_file;
+use ethabi::{ParamType, Token};
+
+#[test]
+fn milestone_1() {
+    let mut vm = compile_file("integration/stylus/milestone_1.sol");
+    vm.deploy_contract("C", true);
+
+    vm.function("test()", &[]);
+
+    let ret = vm.function_returns("get()", &[], &[ParamType::Uint(256)]);
+    assert_eq!(ret, vec![Token::Uint(3.into())]);
+}
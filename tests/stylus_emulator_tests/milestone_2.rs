@@ -0,0 +1,64 @@
+#![warn(clippy::pedantic)]
+
+use crate::{compile_file, keccak256, CHAIN_ID};
+use ethabi::{ParamType, Token};
+
+#[test]
+fn milestone_2() {
+    let mut vm = compile_file("integration/stylus/milestone_2.sol");
+    let address = vm.deploy_contract("C", true);
+
+    let ret = vm.function_returns(
+        "test_block()",
+        &[],
+        &[
+            ParamType::Uint(64),
+            ParamType::Uint(256),
+            ParamType::Address,
+            ParamType::Uint(256),
+            ParamType::Uint(256),
+            ParamType::Uint(256),
+            ParamType::Uint(256),
+        ],
+    );
+    let [gasleft, basefee, coinbase, gaslimit, number, timestamp, chainid] = &ret[..] else {
+        panic!("unexpected return values: {ret:?}");
+    };
+    assert!(gasleft.clone().into_uint().unwrap() > 0.into());
+    assert_eq!(basefee, &Token::Uint(100_000_000.into()));
+    assert_eq!(coinbase, &Token::Address([0xa4; 20].into()));
+    assert_eq!(gaslimit, &Token::Uint((1u64 << 50).into()));
+    assert_eq!(number, &Token::Uint(1.into()));
+    assert_eq!(timestamp, &Token::Uint(1_700_000_000.into()));
+    assert_eq!(chainid, &Token::Uint(CHAIN_ID.into()));
+
+    let ret = vm.function_returns(
+        "test_tstore()",
+        &[],
+        &[ParamType::Uint(256), ParamType::Uint(256)],
+    );
+    assert_eq!(ret, vec![Token::Uint(134.into()), Token::Uint(1.into())]);
+
+    // smoelius: `test_create1` activates `Greeter`, so the greeters created by `test_create2`,
+    // which have the same code, can be called.
+    vm.value = 1_000_000_000_000_000_000;
+    vm.function("test_create1()", &[]);
+    println!("{}", vm.debug_buffer());
+
+    vm.value = 1_000_000_000_000_000_000;
+    vm.function("test_create2()", &[]);
+    println!("{}", vm.debug_buffer());
+    assert_eq!(2, vm.debug_buffer().matches("Hello from 0x").count());
+
+    vm.value = 1_000_000_000_000_000_000;
+    vm.function("test_value_sender()", &[]);
+
+    let logs = vm.logs();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].address, address);
+    assert_eq!(logs[0].topics, vec![keccak256(b"Received(uint256)")]);
+    assert_eq!(
+        logs[0].data,
+        ethabi::encode(&[Token::Uint(1_000_000_000_000_000_000u128.into())])
+    );
+}
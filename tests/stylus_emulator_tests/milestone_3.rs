@@ -0,0 +1,47 @@
+#![warn(clippy::pedantic)]
+
+use crate::{compile_file, keccak256};
+use ethabi::{ParamType, Token};
+
+#[test]
+fn milestone_3() {
+    let mut vm = compile_file("integration/stylus/milestone_3.sol");
+    vm.deploy_contract("C", true);
+
+    vm.value = 1000;
+    vm.function("accept_donation()", &[]);
+
+    let ret = vm.function_returns(
+        "test()",
+        &[],
+        &[
+            ParamType::Uint(256),
+            ParamType::FixedBytes(32),
+            ParamType::FixedBytes(32),
+            ParamType::Uint(256),
+        ],
+    );
+    println!("{}", vm.debug_buffer());
+    let [balance, codehash, manual_codehash, gasprice] = &ret[..] else {
+        panic!("unexpected return values: {ret:?}");
+    };
+    assert_eq!(balance, &Token::Uint(1000.into()));
+    assert_eq!(codehash, manual_codehash);
+    assert_eq!(gasprice, &Token::Uint(100_000_000.into()));
+
+    let ret = vm.function_returns("getCode()", &[], &[ParamType::Bytes]);
+    let [Token::Bytes(code)] = &ret[..] else {
+        panic!("unexpected return values: {ret:?}");
+    };
+    assert_eq!(codehash, &Token::FixedBytes(keccak256(code).to_vec()));
+
+    vm.function("test_addmod()", &[]);
+
+    vm.function("test_mulmod()", &[]);
+
+    vm.function("test_div()", &[]);
+
+    vm.function("test_mod()", &[]);
+
+    vm.function("test_power()", &[]);
+}
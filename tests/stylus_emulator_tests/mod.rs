@@ -0,0 +1,15 @@
+mod api;
+mod arb_wasm;
+mod contracts;
+mod counter;
+mod create2;
+mod dss;
+mod ecrecover;
+mod erc20;
+mod milestone_1;
+mod milestone_2;
+mod milestone_3;
+mod runtime;
+mod storage;
+mod uniswap;
+mod value;
@@ -0,0 +1,274 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{build_solidity, keccak256, selector, CALLER};
+use ethabi::{ParamType, Token};
+
+fn uint(v: u64) -> Token {
+    Token::Uint(v.into())
+}
+
+#[test]
+fn storage_bytes() {
+    let mut vm = build_solidity(include_str!("../../integration/stylus/storage.sol"));
+
+    for b in b"hello" {
+        vm.function("push(bytes1)", &[Token::FixedBytes(vec![*b])]);
+    }
+
+    vm.function(
+        "set(uint32,bytes1)",
+        &[uint(0), Token::FixedBytes(b"j".to_vec())],
+    );
+
+    let ret = vm.function_returns("all()", &[], &[ParamType::Bytes]);
+    assert_eq!(ret, vec![Token::Bytes(b"jello".to_vec())]);
+
+    let ret = vm.function_returns("pop()", &[], &[ParamType::FixedBytes(1)]);
+    assert_eq!(ret, vec![Token::FixedBytes(b"o".to_vec())]);
+
+    let ret = vm.function_returns("get(uint32)", &[uint(3)], &[ParamType::FixedBytes(1)]);
+    assert_eq!(ret, vec![Token::FixedBytes(b"l".to_vec())]);
+
+    // out of bounds
+    vm.function_revert("get(uint32)", &[uint(4)]);
+
+    vm.function("push_number(uint64)", &[uint(40)]);
+    vm.function("push_number(uint64)", &[uint(2)]);
+
+    let ret = vm.function_returns("sum()", &[], &[ParamType::Uint(64)]);
+    assert_eq!(ret, vec![uint(42)]);
+
+    vm.function("set_callback()", &[]);
+
+    let ret = vm.function_returns("run_callback(uint64)", &[uint(21)], &[ParamType::Uint(64)]);
+    assert_eq!(ret, vec![uint(42)]);
+}
+
+#[test]
+fn events() {
+    let mut vm = build_solidity(
+        r#"
+        contract C {
+            event Transfer(address indexed from, uint64 amount);
+
+            function go(uint64 amount) public {
+                emit Transfer(msg.sender, amount);
+            }
+        }"#,
+    );
+
+    vm.function("go(uint64)", &[uint(102)]);
+
+    let logs = vm.logs();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].address, vm.address);
+    assert_eq!(logs[0].topics.len(), 2);
+    assert_eq!(logs[0].topics[0], keccak256(b"Transfer(address,uint64)"));
+    assert_eq!(logs[0].topics[1][12..], CALLER);
+    assert_eq!(logs[0].data, ethabi::encode(&[uint(102)]));
+}
+
+#[test]
+fn revert() {
+    let mut vm = build_solidity(
+        r#"
+        contract C {
+            uint64 x;
+
+            function set(uint64 v) public {
+                x = v;
+                require(v < 10, "too large");
+            }
+
+            function get() public view returns (uint64) {
+                return x;
+            }
+        }"#,
+    );
+
+    vm.function("set(uint64)", &[uint(7)]);
+
+    let data = vm.function_revert("set(uint64)", &[uint(70)]);
+
+    let mut expected = selector("Error(string)").to_vec();
+    expected.extend(ethabi::encode(&[Token::String("too large".into())]));
+    assert_eq!(data, expected);
+
+    // the storage write was rolled back
+    let ret = vm.function_returns("get()", &[], &[ParamType::Uint(64)]);
+    assert_eq!(ret, vec![uint(7)]);
+}
+
+#[test]
+fn value() {
+    let mut vm = build_solidity(
+        r#"
+        contract C {
+            function pay() public payable returns (uint256) {
+                return msg.value;
+            }
+
+            function balance() public view returns (uint256) {
+                return address(this).balance;
+            }
+        }"#,
+    );
+
+    vm.value = 1000;
+    let ret = vm.function_returns("pay()", &[], &[ParamType::Uint(256)]);
+    assert_eq!(ret, vec![uint(1000)]);
+
+    let ret = vm.function_returns("balance()", &[], &[ParamType::Uint(256)]);
+    assert_eq!(ret, vec![uint(1000)]);
+    assert_eq!(vm.balance(&vm.address), 1000);
+    assert_eq!(vm.balance(&CALLER), u128::MAX - 1000);
+}
+
+#[test]
+fn math() {
+    let mut vm = build_solidity(
+        r#"
+        contract C {
+            function f(uint256 x, uint256 y, uint256 k) public pure returns (uint256, uint256, uint256, uint256) {
+                return (x / y, x % y, addmod(x, y, k), mulmod(x, y, k));
+            }
+        }"#,
+    );
+
+    let ret = vm.function_returns(
+        "f(uint256,uint256,uint256)",
+        &[uint(1000), uint(7), uint(13)],
+        &vec![ParamType::Uint(256); 4],
+    );
+    assert_eq!(
+        ret,
+        vec![uint(142), uint(6), uint(1007 % 13), uint(7000 % 13)]
+    );
+}
+
+#[test]
+fn create_and_activate() {
+    let mut vm = build_solidity(
+        r#"
+        interface ArbWasm {
+            function activateProgram(address program) external payable returns (uint16 version, uint256 dataFee);
+        }
+
+        contract Greeter {
+            function greet() public view returns (uint64) {
+                print("Greetings from 0x{}!".format(this));
+                return 42;
+            }
+        }
+
+        contract C {
+            function test() public returns (uint64) {
+                Greeter greeter = new Greeter();
+
+                // programs must be activated before they can be called
+                (bool ok, ) = address(greeter).call(abi.encodeWithSignature("greet()"));
+                require(!ok, "called inactive program");
+
+                ArbWasm(address(0x71)).activateProgram(address(greeter));
+
+                return greeter.greet();
+            }
+        }"#,
+    );
+
+    let ret = vm.function_returns("test()", &[], &[ParamType::Uint(64)]);
+    assert_eq!(ret, vec![uint(42)]);
+
+    assert!(vm.debug_buffer().starts_with("Greetings from 0x"));
+}
+
+#[test]
+fn transfer_and_send() {
+    let mut vm = build_solidity(
+        r#"
+        contract C {
+            function pay(address payable to) public payable {
+                to.transfer(msg.value);
+            }
+
+            function try_pay(address payable to, uint256 amount) public returns (bool) {
+                print("sending {}".format(amount));
+                return to.send(amount);
+            }
+        }"#,
+    );
+
+    let to = [0x22; 20];
+
+    vm.value = 300;
+    vm.function("pay(address)", &[Token::Address(to.into())]);
+    assert_eq!(vm.balance(&to), 300);
+    assert_eq!(vm.balance(&vm.address), 0);
+
+    let address = vm.address;
+    vm.set_balance(&address, 100);
+
+    let ret = vm.function_returns(
+        "try_pay(address,uint256)",
+        &[Token::Address(to.into()), uint(60)],
+        &[ParamType::Bool],
+    );
+    assert_eq!(ret, vec![Token::Bool(true)]);
+    assert_eq!(vm.balance(&to), 360);
+    assert_eq!(vm.debug_buffer(), "sending 60\n");
+
+    // not enough balance
+    let ret = vm.function_returns(
+        "try_pay(address,uint256)",
+        &[Token::Address(to.into()), uint(1000)],
+        &[ParamType::Bool],
+    );
+    assert_eq!(ret, vec![Token::Bool(false)]);
+    assert_eq!(vm.balance(&vm.address), 40);
+}
+
+#[test]
+fn transient_storage() {
+    let mut vm = build_solidity(
+        r#"
+        contract guarded {
+            uint64 transient depth;
+            bool transient entered;
+            uint64 counter;
+
+            function bump(uint64 n) public returns (uint64) {
+                for (uint64 i = 0; i < n; i++) {
+                    depth += 1;
+                }
+                counter += n;
+
+                return depth;
+            }
+
+            function lock() public returns (bool) {
+                require(!entered, "reentrant");
+                entered = true;
+                bool was = entered;
+                delete entered;
+                return was && !entered;
+            }
+
+            function get() public view returns (uint64, uint64) {
+                return (depth, counter);
+            }
+        }"#,
+    );
+
+    let ret = vm.function_returns("bump(uint64)", &[uint(3)], &[ParamType::Uint(64)]);
+    assert_eq!(ret, vec![uint(3)]);
+
+    // the transient variable starts at zero in every transaction
+    let ret = vm.function_returns("bump(uint64)", &[uint(2)], &[ParamType::Uint(64)]);
+    assert_eq!(ret, vec![uint(2)]);
+
+    let ret = vm.function_returns("get()", &[], &[ParamType::Uint(64), ParamType::Uint(64)]);
+    assert_eq!(ret, vec![uint(0), uint(5)]);
+
+    let ret = vm.function_returns("lock()", &[], &[ParamType::Bool]);
+    assert_eq!(ret, vec![Token::Bool(true)]);
+}
@@ -0,0 +1,63 @@
+#![warn(clippy::pedantic)]
+
+use crate::compile_file;
+use ethabi::{ParamType, Token};
+
+#[test]
+fn storage() {
+    let mut vm = compile_file("integration/stylus/storage.sol");
+    vm.deploy_contract("C", true);
+
+    // smoelius: Push enough bytes to span more than one chunk.
+    for i in 0..40u8 {
+        vm.function("push(bytes1)", &[Token::FixedBytes(vec![i + 1])]);
+    }
+
+    let ret = vm.function_returns(
+        "get(uint32)",
+        &[Token::Uint(33.into())],
+        &[ParamType::FixedBytes(1)],
+    );
+    assert_eq!(ret, vec![Token::FixedBytes(vec![0x22])]);
+
+    vm.function(
+        "set(uint32,bytes1)",
+        &[Token::Uint(33.into()), Token::FixedBytes(vec![0xff])],
+    );
+
+    let ret = vm.function_returns(
+        "get(uint32)",
+        &[Token::Uint(33.into())],
+        &[ParamType::FixedBytes(1)],
+    );
+    assert_eq!(ret, vec![Token::FixedBytes(vec![0xff])]);
+
+    let ret = vm.function_returns("pop()", &[], &[ParamType::FixedBytes(1)]);
+    assert_eq!(ret, vec![Token::FixedBytes(vec![0x28])]);
+
+    let ret = vm.function_returns("all()", &[], &[ParamType::Bytes]);
+    let expected = (1..=39u8)
+        .map(|i| if i == 34 { 0xff } else { i })
+        .collect::<Vec<_>>();
+    assert_eq!(ret, vec![Token::Bytes(expected)]);
+
+    // smoelius: Reading past the end reverts.
+    vm.function_revert("get(uint32)", &[Token::Uint(39.into())]);
+
+    for n in [1, 2, 3] {
+        vm.function("push_number(uint64)", &[Token::Uint(n.into())]);
+    }
+    vm.function("pop_number()", &[]);
+
+    let ret = vm.function_returns("sum()", &[], &[ParamType::Uint(64)]);
+    assert_eq!(ret, vec![Token::Uint(3.into())]);
+
+    vm.function("set_callback()", &[]);
+
+    let ret = vm.function_returns(
+        "run_callback(uint64)",
+        &[Token::Uint(21.into())],
+        &[ParamType::Uint(64)],
+    );
+    assert_eq!(ret, vec![Token::Uint(42.into())]);
+}
@@ -0,0 +1,220 @@
+#![warn(clippy::pedantic)]
+
+use crate::{compile_file, keccak256, Address, MockStylus, CALLER};
+use ethabi::{ParamType, Token, Uint};
+
+// smoelius: In this test, the pool is populated with 10000 of each token. Then, `INCOMING` (an
+// unknown) number of tokens tokens are swapped-in to get 2500 tokens out. Uniswap's equations
+// require that:
+//
+//     10000 * 10^3 + INCOMING * 9997 + 7500 * 10^3 >= (10000 * 10^3)^2
+//
+// Setting `INCOMING` to 3344 makes this inequality work, but anything smaller does not.
+const INCOMING: u64 = 3344;
+const OUTGOING: u64 = 2500;
+
+#[test]
+fn uniswap() {
+    let mut vm = compile_file("integration/stylus/Uniswap/test/ERC20.sol");
+    vm.add_file("integration/stylus/Uniswap/Callee.sol");
+    vm.add_file("integration/stylus/Uniswap/UniswapV2PairBase.sol");
+    vm.add_file("integration/stylus/Uniswap/UniswapV2PairCreator.sol");
+    vm.add_file("integration/stylus/Uniswap/UniswapV2Factory.sol");
+
+    let erc20_a = deploy_erc20(&mut vm);
+    let erc20_b = deploy_erc20(&mut vm);
+
+    // smoelius: The pair's `token0` is the token with the lower address.
+    let incoming = erc20_a.min(erc20_b);
+    let outgoing = erc20_a.max(erc20_b);
+
+    let callee = deploy_callee(&mut vm, incoming);
+
+    let pair_base = deploy_pair_base(&mut vm);
+
+    let pair_creator = deploy_pair_creator(&mut vm, pair_base);
+
+    let factory = deploy_uniswap_factory(&mut vm, pair_base, pair_creator);
+
+    let pair = create_pair(&mut vm, factory, erc20_a, erc20_b);
+
+    vm.address = pair;
+    let ret = vm.function_returns("base()", &[], &[ParamType::Address]);
+    assert_eq!(ret, vec![address(pair_base)]);
+
+    let ret = vm.function_returns("token0()", &[], &[ParamType::Address]);
+    assert_eq!(ret, vec![address(incoming)]);
+
+    let ret = vm.function_returns("token1()", &[], &[ParamType::Address]);
+    assert_eq!(ret, vec![address(outgoing)]);
+
+    // smoelius: `MINIMUM_LIQUIDITY` is 1000. If the amount of each token transferred to the pair is
+    // more than this, then `MINIMUM_LIQUIDITY` will be satisfied.
+    for token in [erc20_a, erc20_b] {
+        vm.address = token;
+        vm.function(
+            "transfer(address,uint256)",
+            &[address(pair), Token::Uint(10000.into())],
+        );
+    }
+
+    vm.address = pair;
+    vm.function("mint(address)", &[address(CALLER)]);
+
+    let ret = vm.function_returns(
+        "balanceOf(address)",
+        &[address(CALLER)],
+        &[ParamType::Uint(256)],
+    );
+    assert_eq!(ret, vec![Token::Uint(9000.into())]);
+
+    // smoelius: Transfer `INCOMING` tokens to `callee` to swap them.
+    vm.address = incoming;
+    vm.function(
+        "transfer(address,uint256)",
+        &[address(callee), Token::Uint(INCOMING.into())],
+    );
+
+    assert_eq!(balance_of(&mut vm, incoming, callee), INCOMING.into());
+    assert_eq!(balance_of(&mut vm, outgoing, callee), 0.into());
+
+    // smoelius: The `0xff` is `swap`'s `data` argument. It must be non-empty for `swap` to call
+    // `callee`. However, our `callee` does not use this data.
+    vm.address = pair;
+    vm.function(
+        "swap(uint256,uint256,address,bytes)",
+        &[
+            Token::Uint(0.into()),
+            Token::Uint(OUTGOING.into()),
+            address(callee),
+            Token::Bytes(vec![0xff]),
+        ],
+    );
+
+    assert_eq!(balance_of(&mut vm, incoming, callee), 0.into());
+    assert_eq!(balance_of(&mut vm, outgoing, callee), OUTGOING.into());
+}
+
+const INITIAL_BALANCE: &str = concat!("1000000", "000000000000000000");
+
+fn deploy_erc20(vm: &mut MockStylus) -> Address {
+    let erc20 = vm.deploy_contract("ERC20", true);
+
+    let balance = Uint::from_dec_str(INITIAL_BALANCE).unwrap();
+
+    vm.function("initialize(uint256)", &[Token::Uint(balance)]);
+
+    let ret = vm.function_returns("totalSupply()", &[], &[ParamType::Uint(256)]);
+    assert_eq!(ret, vec![Token::Uint(balance)]);
+
+    erc20
+}
+
+fn deploy_callee(vm: &mut MockStylus, token: Address) -> Address {
+    let callee = vm.deploy_contract("Callee", true);
+
+    vm.function("initialize(address)", &[address(token)]);
+
+    callee
+}
+
+fn deploy_pair_base(vm: &mut MockStylus) -> Address {
+    let pair_base = vm.deploy_contract("UniswapV2PairBase", true);
+
+    let ret = vm.function_returns(
+        "balanceOf(address)",
+        &[address(CALLER)],
+        &[ParamType::Uint(256)],
+    );
+    assert_eq!(ret, vec![Token::Uint(0.into())]);
+
+    let ret = vm.function_returns("factory()", &[], &[ParamType::Address]);
+    assert_eq!(ret, vec![address([0; 20])]);
+
+    pair_base
+}
+
+fn deploy_pair_creator(vm: &mut MockStylus, pair_base: Address) -> Address {
+    let pair_creator = vm.deploy_contract("UniswapV2PairCreator", true);
+
+    // smoelius: Create a test pair with bogus token addresses. This activates the pair program,
+    // so that the pairs created later can be called.
+    let mut token_a = [0; 20];
+    token_a[19] = 1;
+    let mut token_b = [0; 20];
+    token_b[19] = 2;
+
+    vm.value = 1_000_000_000_000_000_000;
+    vm.function(
+        "createPairWithBase(address,address,address,uint256)",
+        &[
+            address(pair_base),
+            address(token_a),
+            address(token_b),
+            Token::Uint(1.into()),
+        ],
+    );
+
+    pair_creator
+}
+
+fn deploy_uniswap_factory(
+    vm: &mut MockStylus,
+    pair_base: Address,
+    pair_creator: Address,
+) -> Address {
+    let factory = vm.deploy_contract("UniswapV2Factory", true);
+
+    vm.function(
+        "initialize(address,address,address)",
+        &[address(CALLER), address(pair_base), address(pair_creator)],
+    );
+
+    factory
+}
+
+fn create_pair(
+    vm: &mut MockStylus,
+    factory: Address,
+    erc20_a: Address,
+    erc20_b: Address,
+) -> Address {
+    vm.address = factory;
+
+    let ret = vm.function_returns(
+        "createPair(address,address)",
+        &[address(erc20_a), address(erc20_b)],
+        &[ParamType::Address],
+    );
+    let [Token::Address(pair)] = ret[..] else {
+        panic!("unexpected return values: {ret:?}");
+    };
+
+    let log = vm.logs().iter().find(|log| log.address == factory).unwrap();
+    assert_eq!(
+        log.topics[0],
+        keccak256(b"PairCreated(address,address,address,uint256)")
+    );
+    assert_eq!(
+        log.data,
+        ethabi::encode(&[Token::Address(pair), Token::Uint(1.into())])
+    );
+
+    pair.0
+}
+
+fn balance_of(vm: &mut MockStylus, token: Address, owner: Address) -> Uint {
+    vm.address = token;
+
+    let ret = vm.function_returns(
+        "balanceOf(address)",
+        &[address(owner)],
+        &[ParamType::Uint(256)],
+    );
+
+    ret[0].clone().into_uint().unwrap()
+}
+
+fn address(address: Address) -> Token {
+    Token::Address(address.into())
+}
@@ -0,0 +1,18 @@
+#![warn(clippy::pedantic)]
+
+use crate::compile_file;
+use ethabi::{ParamType, Token};
+
+#[test]
+fn value() {
+    let mut vm = compile_file("integration/stylus/value.sol");
+    let address = vm.deploy_contract("C", true);
+
+    vm.value = 1_000_000_000_000_000_000;
+    let ret = vm.function_returns("test()", &[], &[ParamType::Uint(256)]);
+    println!("{}", vm.debug_buffer());
+    assert_eq!(ret, vec![Token::Uint(1_000_000_000_000_000_000u128.into())]);
+
+    // the value was sent on to the contract itself
+    assert_eq!(vm.balance(&address), 1_000_000_000_000_000_000);
+}
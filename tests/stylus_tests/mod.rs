@@ -8,5 +8,6 @@ mod erc20;
 mod milestone_1;
 mod milestone_2;
 mod milestone_3;
+mod storage;
 mod uniswap;
 mod value;
@@ -0,0 +1,62 @@
+//! This test expects you to have a devnode running:
+//! <https://docs.arbitrum.io/run-arbitrum-node/run-nitro-dev-node>
+//!
+//! It also expects `cargo-stylus` and `cast` to be installed:
+//! - <https://github.com/OffchainLabs/cargo-stylus>
+//! - <https://book.getfoundry.sh/cast/>
+#![warn(clippy::pedantic)]
+
+use crate::{call, deploy, send, MUTEX};
+use std::path::PathBuf;
+
+#[test]
+fn storage() {
+    let _lock = MUTEX.lock();
+    let (tempdir, address) = deploy(
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("integration/stylus/storage.sol"),
+        "C",
+        true,
+    )
+    .unwrap();
+    let dir = &tempdir;
+
+    // smoelius: Push enough bytes to span more than one chunk.
+    for i in 0..40u8 {
+        send(dir, &address, ["push(bytes1)", &format!("0x{:02x}", i + 1)]).unwrap();
+    }
+
+    let stdout = call(dir, &address, ["get(uint32)(bytes1)", "33"]).unwrap();
+    assert_eq!("0x22\n", stdout);
+
+    send(dir, &address, ["set(uint32,bytes1)", "33", "0xff"]).unwrap();
+
+    let stdout = call(dir, &address, ["get(uint32)(bytes1)", "33"]).unwrap();
+    assert_eq!("0xff\n", stdout);
+
+    let stdout = call(dir, &address, ["pop()(bytes1)"]).unwrap();
+    assert_eq!("0x28\n", stdout);
+
+    send(dir, &address, ["pop()"]).unwrap();
+
+    let stdout = call(dir, &address, ["all()(bytes)"]).unwrap();
+    let expected = (1..=39u8)
+        .map(|i| if i == 34 { 0xff } else { i })
+        .collect::<Vec<_>>();
+    assert_eq!(format!("0x{}\n", hex::encode(expected)), stdout);
+
+    // smoelius: Reading past the end reverts.
+    assert!(call(dir, &address, ["get(uint32)(bytes1)", "39"]).is_err());
+
+    for n in [1, 2, 3] {
+        send(dir, &address, ["push_number(uint64)", &n.to_string()]).unwrap();
+    }
+    send(dir, &address, ["pop_number()"]).unwrap();
+
+    let stdout = call(dir, &address, ["sum()(uint64)"]).unwrap();
+    assert_eq!("3\n", stdout);
+
+    send(dir, &address, ["set_callback()"]).unwrap();
+
+    let stdout = call(dir, &address, ["run_callback(uint64)(uint64)", "21"]).unwrap();
+    assert_eq!("42\n", stdout);
+}